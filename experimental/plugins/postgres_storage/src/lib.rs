@@ -60,7 +60,7 @@ pub extern fn postgresstorage_init() -> libindy::ErrorCode {
         return err;
     }
 
-    libindy::wallet::register_wallet_storage(
+    let err = libindy::wallet::register_wallet_storage(
         postgres_storage_name.as_ptr(),
         PostgresWallet::create,
         PostgresWallet::open,
@@ -86,6 +86,15 @@ pub extern fn postgresstorage_init() -> libindy::ErrorCode {
         PostgresWallet::get_search_total_count,
         PostgresWallet::fetch_search_next_record,
         PostgresWallet::free_search,
+    );
+
+    if err != ErrorCode::Success {
+        return err;
+    }
+
+    libindy::wallet::register_wallet_storage_compaction(
+        postgres_storage_name.as_ptr(),
+        PostgresWallet::compact,
     )
 }
 
@@ -886,6 +895,24 @@ impl PostgresWallet {
     }
 
 
+    pub extern fn compact(xhandle: i32, reclaimed_bytes_p: *mut u64) -> ErrorCode {
+        let handles = POSTGRES_OPEN_WALLETS.lock().unwrap();
+
+        let wallet_context = match handles.get(&xhandle) {
+            Some(wallet_context) => wallet_context,
+            None => return ErrorCode::CommonInvalidState
+        };
+
+        match wallet_context.phandle.compact() {
+            Ok(reclaimed_bytes) => {
+                unsafe { *reclaimed_bytes_p = reclaimed_bytes; }
+                ErrorCode::Success
+            }
+            Err(_err) => ErrorCode::WalletStorageError
+        }
+    }
+
+
     pub extern fn close(xhandle: i32) -> ErrorCode {
         let mut handles = POSTGRES_OPEN_WALLETS.lock().unwrap();
 
@@ -324,6 +324,15 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: IndyHandle,
 pub type WalletFreeSearch = extern fn(storage_handle: IndyHandle,
                                       search_handle: IndyHandle) -> ErrorCode;
 
+/// Reclaim space left by deleted records
+///
+/// #Params
+/// storage_handle: opened storage handle (See open handler)
+///
+/// returns: number of reclaimed bytes
+pub type WalletCompact = extern fn(storage_handle: IndyHandle,
+                                   reclaimed_bytes_p: *mut u64) -> ErrorCode;
+
 pub fn register_wallet_storage(
     wallet_storage_name: *const c_char,
     create: WalletCreate,
@@ -394,6 +403,22 @@ pub fn register_wallet_storage(
     receiver.recv().unwrap()
 }
 
+pub fn register_wallet_storage_compaction(wallet_storage_name: *const c_char, compact: WalletCompact) -> ErrorCode {
+    let (sender, receiver) = channel();
+
+    let closure: Box<dyn FnMut(ErrorCode) + Send> = Box::new(move |err| {
+        sender.send(err).unwrap();
+    });
+
+    let (cmd_handle, cb) = callbacks::closure_to_cb_ec(closure);
+
+    unsafe {
+        indy_register_wallet_storage_compaction(cmd_handle, wallet_storage_name, Some(compact), cb);
+    }
+
+    receiver.recv().unwrap()
+}
+
 extern {
     #[no_mangle]
     pub fn indy_register_wallet_storage(command_handle: IndyHandle,
@@ -424,6 +449,13 @@ extern {
                                             free_search: Option<WalletFreeSearch>,
                                             cb: Option<extern fn(command_handle_: IndyHandle,
                                                                     err: ErrorCode)>) -> ErrorCode;
+
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_compaction(command_handle: IndyHandle,
+                                                   type_: *const c_char,
+                                                   compact: Option<WalletCompact>,
+                                                   cb: Option<extern fn(command_handle_: IndyHandle,
+                                                                        err: ErrorCode)>) -> ErrorCode;
 }
 
 
@@ -211,6 +211,10 @@ const _CREATE_SCHEMA_MULTI: [&str; 14] = [
     "CREATE INDEX IF NOT EXISTS ix_tags_plaintext_wallet_id_item_id ON tags_plaintext(wallet_id, item_id)"
 ];
 const _DROP_WALLET_DATABASE: &str = "DROP DATABASE \"$1\"";
+const _CREATE_WALLET_SCHEMA: &str = "CREATE SCHEMA \"$1\"";
+const _DROP_WALLET_SCHEMA: &str = "DROP SCHEMA \"$1\" CASCADE";
+const _SET_WALLET_SEARCH_PATH: &str = "SET search_path TO \"$1\"";
+const _WALLET_SCHEMA_EXISTS: &str = "SELECT 1 FROM information_schema.schemata WHERE schema_name = $1";
 const _DROP_SCHEMA: [&str; 4] = [
     "DROP TABLE tags_plaintext",
     "DROP TABLE tags_encrypted",
@@ -984,37 +988,205 @@ impl WalletStrategy for MultiWalletSingleTableStrategy {
     }
 }
 
+// postgres silently truncates identifiers longer than this many bytes
+const _MAX_WALLET_SCHEMA_LENGTH: usize = 63;
+
+// wallet id is used as schema name as is, so wallets which ids would be truncated
+// to the same schema name are rejected instead of sharing the schema
+fn check_wallet_schema(id: &str) -> Result<(), WalletStorageError> {
+    if id.len() > _MAX_WALLET_SCHEMA_LENGTH {
+        return Err(WalletStorageError::CommonError(CommonError::InvalidStructure(
+            format!("Wallet id must not be longer than {} bytes for MultiWalletMultiTable strategy", _MAX_WALLET_SCHEMA_LENGTH))));
+    }
+    Ok(())
+}
+
+// escape wallet id to be used as quoted postgres identifier (schema name)
+fn quote_wallet_schema(sql: &str, id: &str) -> String {
+    str::replace(sql, "$1", &str::replace(id, "\"", "\"\""))
+}
+
+// points every pooled connection to the schema of a single wallet
+#[derive(Debug)]
+struct WalletSchemaCustomizer {
+    search_path_sql: String,
+}
+
+impl r2d2::CustomizeConnection<postgres::Connection, postgres::Error> for WalletSchemaCustomizer {
+    fn on_acquire(&self, conn: &mut postgres::Connection) -> Result<(), postgres::Error> {
+        conn.batch_execute(&self.search_path_sql)
+    }
+}
+
 impl WalletStrategy for MultiWalletMultiTableStrategy {
     // initialize storage based on wallet storage strategy
-    fn init_storage(&self, _config: &PostgresConfig, _credentials: &PostgresCredentials) -> Result<(), WalletStorageError> {
-        // create database for storage
-        // TODO
-        Ok(())
+    fn init_storage(&self, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<(), WalletStorageError> {
+        // create database for storage, tables are created per wallet in its own schema
+        // if admin user and password aren't provided then bail
+        debug!("Initializing storage strategy MultiWalletMultiTableStrategy.");
+        if credentials.admin_account == None || credentials.admin_password == None {
+            return Ok(());
+        }
+
+        let wallet_db_name: &str = get_multi_database_name(config);
+        debug!("wallet_db_name: {:?}", wallet_db_name);
+        let url_base = PostgresStorageType::_admin_postgres_url(&config, &credentials);
+        let conn = postgres::Connection::connect(&url_base[..], config.tls())?;
+
+        debug!("creating wallets DB");
+        let create_db_sql: String = str::replace(_CREATE_WALLET_DATABASE, "$1", wallet_db_name);
+        let ret = match conn.execute(&create_db_sql, &[]) {
+            Ok(_) => Ok(()),
+            Err(ref error) if error.code() == Some(&postgres::error::DUPLICATE_DATABASE) => {
+                debug!("database already exists");
+                Ok(())
+            }
+            Err(error) => {
+                debug!("error creating database, Error: {}", error);
+                Err(WalletStorageError::IOError(format!("Error occurred while creating the database: {}", error)))
+            }
+        };
+        conn.finish()?;
+        ret
     }
     // initialize a single wallet based on wallet storage strategy
-    fn create_wallet(&self, _id: &str, _config: &PostgresConfig, _credentials: &PostgresCredentials, _metadata: &[u8]) -> Result<(), WalletStorageError> {
-        // create tables for wallet storage
-        // TODO
-        Ok(())
+    fn create_wallet(&self, id: &str, config: &PostgresConfig, credentials: &PostgresCredentials, metadata: &[u8]) -> Result<(), WalletStorageError> {
+        check_wallet_schema(id)?;
+
+        let wallet_db_name: &str = get_multi_database_name(config);
+        debug!("wallet_db_name: {:?}", wallet_db_name);
+        let url = PostgresStorageType::_postgres_url(wallet_db_name, &config, &credentials);
+
+        let conn = match postgres::Connection::connect(&url[..], config.tls()) {
+            Ok(conn) => conn,
+            Err(error) => {
+                return Err(WalletStorageError::IOError(format!("Error occurred while connecting to wallet schema: {}", error)));
+            }
+        };
+
+        debug!("creating wallet schema");
+        let mut schema_result = match conn.execute(&quote_wallet_schema(_CREATE_WALLET_SCHEMA, id), &[]) {
+            Ok(_) => Ok(()),
+            Err(ref error) if error.code() == Some(&postgres::error::DUPLICATE_SCHEMA) => Err(WalletStorageError::AlreadyExists),
+            Err(error) => Err(WalletStorageError::IOError(format!("Error occurred while creating wallet schema: {}", error)))
+        };
+
+        if schema_result.is_ok() {
+            schema_result = conn.batch_execute(&quote_wallet_schema(_SET_WALLET_SEARCH_PATH, id))
+                .map_err(|error| WalletStorageError::IOError(format!("Error occurred while selecting wallet schema: {}", error)));
+        }
+
+        debug!("setting up wallet tables");
+        for sql in &_CREATE_SCHEMA {
+            match schema_result {
+                Ok(_) => schema_result = match conn.execute(sql, &[]) {
+                    Ok(_) => Ok(()),
+                    Err(error) => {
+                        Err(WalletStorageError::IOError(format!("Error occurred while creating wallet schema: {}", error)))
+                    }
+                },
+                _ => ()
+            }
+        };
+
+        debug!("inserting the keys");
+        let ret = match schema_result {
+            Ok(_) => {
+                match conn.execute("INSERT INTO metadata(value) VALUES($1)", &[&metadata]) {
+                    Ok(_) => Ok(()),
+                    Err(error) => {
+                        Err(WalletStorageError::IOError(format!("Error occurred while inserting the keys: {}", error)))
+                    }
+                }
+            }
+            Err(error) => Err(error)
+        };
+        conn.finish()?;
+        ret
     }
     // open a wallet based on wallet storage strategy
-    fn open_wallet(&self, _id: &str, _config: &PostgresConfig, _credentials: &PostgresCredentials) -> Result<Box<PostgresStorage>, WalletStorageError> {
-        // TODO
-        Err(WalletStorageError::NotFound)
+    fn open_wallet(&self, id: &str, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<Box<PostgresStorage>, WalletStorageError> {
+        // wallet with too long id can't be created, so its truncated schema name belongs to another wallet
+        if check_wallet_schema(id).is_err() {
+            return Err(WalletStorageError::NotFound);
+        }
+
+        let wallet_db_name: &str = get_multi_database_name(config);
+        debug!("wallet_db_name: {:?}", wallet_db_name);
+        let url = PostgresStorageType::_postgres_url(wallet_db_name, &config, &credentials);
+
+        let conn = match postgres::Connection::connect(&url[..], config.tls()) {
+            Ok(conn) => conn,
+            Err(_) => return Err(WalletStorageError::NotFound)
+        };
+
+        // make sure schema of this wallet exists
+        let exists = match conn.query(_WALLET_SCHEMA_EXISTS, &[&id]) {
+            Ok(rows) => !rows.is_empty(),
+            Err(_) => false
+        };
+        conn.finish()?;
+
+        if !exists {
+            return Err(WalletStorageError::NotFound);
+        }
+
+        let manager = match PostgresConnectionManager::new(&url[..], config.r2d2_tls()) {
+            Ok(manager) => manager,
+            Err(_) => return Err(WalletStorageError::NotFound)
+        };
+        let customizer = WalletSchemaCustomizer {
+            search_path_sql: quote_wallet_schema(_SET_WALLET_SEARCH_PATH, id)
+        };
+        let pool = match r2d2::Pool::builder()
+            .min_idle(Some(config.min_idle_count()))
+            .max_size(config.max_connections())
+            .idle_timeout(Some(Duration::new(config.connection_timeout(), 0)))
+            .connection_customizer(Box::new(customizer))
+            .build(manager) {
+            Ok(pool) => pool,
+            Err(_) => return Err(WalletStorageError::NotFound)
+        };
+
+        Ok(Box::new(PostgresStorage {
+            pool: pool,
+            wallet_id: id.to_string(),
+        }))
     }
     // delete a single wallet based on wallet storage strategy
-    fn delete_wallet(&self, _id: &str, _config: &PostgresConfig, _credentials: &PostgresCredentials) -> Result<(), WalletStorageError> {
-        // TODO
-        Ok(())
+    fn delete_wallet(&self, id: &str, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<(), WalletStorageError> {
+        // wallet with too long id can't be created, so its truncated schema name belongs to another wallet
+        if check_wallet_schema(id).is_err() {
+            return Err(WalletStorageError::NotFound);
+        }
+
+        let wallet_db_name: &str = get_multi_database_name(config);
+        debug!("wallet_db_name: {:?}", wallet_db_name);
+        let url = PostgresStorageType::_postgres_url(wallet_db_name, &config, &credentials);
+
+        let conn = match postgres::Connection::connect(&url[..], config.tls()) {
+            Ok(conn) => conn,
+            Err(error) => {
+                return Err(WalletStorageError::IOError(format!("Error occurred while connecting to wallet schema: {}", error)));
+            }
+        };
+
+        let ret = match conn.execute(&quote_wallet_schema(_DROP_WALLET_SCHEMA, id), &[]) {
+            Ok(_) => Ok(()),
+            Err(ref error) if error.code() == Some(&postgres::error::INVALID_SCHEMA_NAME) => Err(WalletStorageError::NotFound),
+            Err(error) => Err(WalletStorageError::IOError(format!("Error occurred while deleting wallet: {}", error)))
+        };
+        conn.finish()?;
+        ret
     }
     // determine phyisical table name based on wallet strategy
     fn table_name(&self, _id: &str, base_name: &str) -> String {
-        // TODO
+        // tables are resolved through the search_path of the wallet connections
         base_name.to_owned()
     }
     // determine additional query parameters based on wallet strategy
     fn query_qualifier(&self) -> Option<String> {
-        // TODO
+        // wallets are isolated by schema, so no additional qualifier is needed
         None
     }
 }
@@ -1760,6 +1932,36 @@ impl WalletStorage for PostgresStorage {
 }
 
 impl PostgresStorage {
+    ///
+    /// Vacuums and reindexes the wallet tables, returns the number of bytes the tables shrank by.
+    /// Tables are shared by all wallets of the shared table strategies, so all of them are compacted.
+    ///
+    pub fn compact(&self) -> Result<u64, WalletStorageError> {
+        let pool = self.pool.clone();
+        let conn = pool.get().unwrap();
+
+        let size_before = PostgresStorage::_tables_size(&conn)?;
+
+        for table in &["items", "tags_encrypted", "tags_plaintext"] {
+            conn.batch_execute(&format!("VACUUM ANALYZE {0}; REINDEX TABLE {0};", table))?;
+        }
+
+        let size_after = PostgresStorage::_tables_size(&conn)?;
+
+        Ok(size_before.saturating_sub(size_after))
+    }
+
+    fn _tables_size(conn: &postgres::Connection) -> Result<u64, WalletStorageError> {
+        let rows = conn.query(
+            "SELECT (pg_total_relation_size('items') + pg_total_relation_size('tags_encrypted') + pg_total_relation_size('tags_plaintext'))::BIGINT",
+            &[])?;
+        let size: i64 = match rows.iter().next() {
+            Some(row) => row.get(0),
+            None => return Err(WalletStorageError::ItemNotFound)
+        };
+        Ok(size as u64)
+    }
+
     fn _prepare_statement(&self, sql: &str) -> Result<
         OwningHandle<Rc<r2d2::PooledConnection<PostgresConnectionManager>>, Box<postgres::stmt::Statement<'static>>>,
         WalletStorageError> {
@@ -2506,6 +2708,81 @@ mod tests {
         assert_match!(Err(WalletStorageError::ItemNotFound), res);
     }
 
+    #[test]
+    fn postgres_storage_multi_table_create_open_delete_works() {
+        _cleanup_multi_table();
+
+        let storage_type = PostgresStorageType::new();
+        storage_type.create_storage(_wallet_id(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]), &_metadata()).unwrap();
+
+        let storage = storage_type.open_storage(_wallet_id(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..])).unwrap();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(storage.get_storage_metadata().unwrap(), _metadata());
+
+        storage_type.delete_storage(_wallet_id(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..])).unwrap();
+
+        let res = storage_type.open_storage(_wallet_id(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]));
+        assert_match!(Err(WalletStorageError::NotFound), res);
+    }
+
+    #[test]
+    fn postgres_storage_multi_table_works_for_separate_wallets() {
+        _cleanup_multi_table();
+
+        let storage_type = PostgresStorageType::new();
+        storage_type.create_storage(_wallet_id(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]), &_metadata()).unwrap();
+        storage_type.create_storage(_wallet_id2(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]), &_metadata()).unwrap();
+
+        let storage1 = storage_type.open_storage(_wallet_id(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..])).unwrap();
+        let storage2 = storage_type.open_storage(_wallet_id2(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..])).unwrap();
+
+        storage1.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage2.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+
+        storage_type.delete_storage(_wallet_id2(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..])).unwrap();
+    }
+
+    #[test]
+    fn postgres_storage_multi_table_works_for_too_long_wallet_id() {
+        _cleanup_multi_table();
+
+        let max_id = "w".repeat(_MAX_WALLET_SCHEMA_LENGTH);
+        let long_id = format!("{}1", max_id);
+
+        let storage_type = PostgresStorageType::new();
+        let _ = storage_type.delete_storage(&max_id, Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]));
+        storage_type.create_storage(&max_id, Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]), &_metadata()).unwrap();
+
+        let res = storage_type.create_storage(&long_id, Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]), &_metadata());
+        assert_match!(Err(WalletStorageError::CommonError(CommonError::InvalidStructure(_))), res);
+
+        let res = storage_type.open_storage(&long_id, Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]));
+        assert_match!(Err(WalletStorageError::NotFound), res);
+
+        let res = storage_type.delete_storage(&long_id, Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]));
+        assert_match!(Err(WalletStorageError::NotFound), res);
+
+        storage_type.open_storage(&max_id, Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..])).unwrap();
+        storage_type.delete_storage(&max_id, Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..])).unwrap();
+    }
+
+    fn _cleanup_multi_table() {
+        let storage_type = PostgresStorageType::new();
+        storage_type.init_storage(Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..])).unwrap();
+        let _ret = storage_type.delete_storage(_wallet_id(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]));
+        let _ret = storage_type.delete_storage(_wallet_id2(), Some(&_wallet_config_multi_table()[..]), Some(&_wallet_credentials()[..]));
+        test::cleanup_storage();
+    }
+
+    fn _wallet_id2() -> &'static str {
+        "walle2"
+    }
+
     fn _cleanup() {
         let storage_type = PostgresStorageType::new();
         let _res = storage_type.init_storage(Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..])).unwrap();
@@ -2542,6 +2819,9 @@ mod tests {
                 if scheme == "MultiWalletSingleTableSharedPool" {
                     return _wallet_config_multi_with_shared_pool();
                 }
+                if scheme == "MultiWalletMultiTable" {
+                    return _wallet_config_multi_table();
+                }
             }
             Err(_) => ()
         };
@@ -2567,6 +2847,14 @@ mod tests {
         config
     }
 
+    fn _wallet_config_multi_table() -> String {
+        let config = json!({
+            "url": "localhost:5432".to_owned(),
+            "wallet_scheme": "MultiWalletMultiTable".to_owned()
+        }).to_string();
+        config
+    }
+
     fn _wallet_config_db_pool() -> String {
         let config = json!({
             "url": "localhost:5432".to_owned(),
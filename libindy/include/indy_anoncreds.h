@@ -17,6 +17,13 @@ extern "C" {
                                                                        const char*   schema_json)
                                                   );
 
+    extern indy_error_t indy_issuer_validate_schema_attributes(indy_handle_t command_handle,
+                                                               const char *  attr_names,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err)
+                                                               );
+
     extern indy_error_t indy_issuer_create_and_store_credential_def(indy_handle_t command_handle,
                                                                     indy_handle_t wallet_handle,
                                                                     const char *  issuer_did,
@@ -73,6 +80,32 @@ extern "C" {
                                                                                  indy_error_t  err,
                                                                                  const char*   cred_offer_json)
                                                             );
+
+    extern indy_error_t indy_issuer_create_credential_offer_with_config(indy_handle_t command_handle,
+                                                                        indy_handle_t wallet_handle,
+                                                                        const char *  cred_def_id,
+                                                                        const char *  config_json,
+
+                                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                                             indy_error_t  err,
+                                                                                             const char*   cred_offer_json)
+                                                                        );
+
+    extern indy_error_t indy_issuer_list_pending_credential_offers(indy_handle_t command_handle,
+                                                                   indy_handle_t wallet_handle,
+
+                                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                                        indy_error_t  err,
+                                                                                        const char*   offers_json)
+                                                                   );
+
+    extern indy_error_t indy_issuer_revoke_credential_offer(indy_handle_t command_handle,
+                                                            indy_handle_t wallet_handle,
+                                                            const char *  nonce,
+
+                                                            void           (*cb)(indy_handle_t command_handle_,
+                                                                                 indy_error_t  err)
+                                                            );
     
     extern indy_error_t indy_issuer_create_credential(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
@@ -88,6 +121,18 @@ extern "C" {
                                                                            const char*   cred_revoc_id,
                                                                            const char*   revoc_reg_delta_json)
                                                       );
+
+    extern indy_error_t indy_issuer_create_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  credentials_json,
+                                                       const char *  rev_reg_id,
+                                                       indy_handle_t blob_storage_reader_handle,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   creds_json,
+                                                                            const char*   revoc_reg_delta_json)
+                                                       );
     
     extern indy_error_t indy_issuer_revoke_credential(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
@@ -100,6 +145,15 @@ extern "C" {
                                                                            const char*   revoc_reg_delta_json)
                                                       );
 
+    extern indy_error_t indy_issuer_get_revocation_registry_info(indy_handle_t command_handle,
+                                                                 indy_handle_t wallet_handle,
+                                                                 const char *  rev_reg_id,
+
+                                                                 void           (*cb)(indy_handle_t command_handle_,
+                                                                                      indy_error_t  err,
+                                                                                      const char*   rev_reg_info_json)
+                                                                 );
+
 /*    extern indy_error_t indy_issuer_recover_credential(indy_handle_t command_handle,
                                                        indy_handle_t wallet_handle,
                                                        indy_handle_t blob_storage_reader_handle,
@@ -121,6 +175,31 @@ extern "C" {
                                                                                           const char*   merged_rev_reg_delta)
                                                                      );
 
+    extern indy_error_t indy_issuer_enable_issuance_log(indy_handle_t command_handle,
+                                                        indy_handle_t wallet_handle,
+                                                        const char *  issuer_did,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err)
+                                                        );
+
+    extern indy_error_t indy_issuer_export_issuance_log(indy_handle_t command_handle,
+                                                        indy_handle_t wallet_handle,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   issuance_log_json)
+                                                        );
+
+    extern indy_error_t indy_issuer_verify_issuance_log(indy_handle_t command_handle,
+                                                        const char *  issuance_log_json,
+                                                        const char *  issuer_verkey,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             indy_bool_t   valid)
+                                                        );
+
     extern indy_error_t indy_prover_create_master_secret(indy_handle_t command_handle,
                                                          indy_handle_t wallet_handle,
                                                          const char *  master_secret_id,
@@ -129,6 +208,31 @@ extern "C" {
                                                                               indy_error_t  err,
                                                                               const char*   out_master_secret_id)
                                                          );
+
+    extern indy_error_t indy_prover_list_master_secrets(indy_handle_t command_handle,
+                                                        indy_handle_t wallet_handle,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   master_secrets_json)
+                                                        );
+
+    extern indy_error_t indy_prover_rename_master_secret(indy_handle_t command_handle,
+                                                         indy_handle_t wallet_handle,
+                                                         const char *  master_secret_id,
+                                                         const char *  new_master_secret_id,
+
+                                                         void           (*cb)(indy_handle_t command_handle_,
+                                                                              indy_error_t  err)
+                                                         );
+
+    extern indy_error_t indy_prover_delete_master_secret(indy_handle_t command_handle,
+                                                         indy_handle_t wallet_handle,
+                                                         const char *  master_secret_id,
+
+                                                         void           (*cb)(indy_handle_t command_handle_,
+                                                                              indy_error_t  err)
+                                                         );
     
     
     extern indy_error_t indy_prover_create_credential_req(indy_handle_t command_handle,
@@ -193,6 +297,17 @@ extern "C" {
                                                                             indy_u32_t    total_count)
                                                        );
 
+    extern indy_error_t indy_prover_search_credentials_with_projection(indy_handle_t command_handle,
+                                                                       indy_handle_t wallet_handle,
+                                                                       const char *  query_json,
+                                                                       const char *  projection_json,
+
+                                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                                            indy_error_t  err,
+                                                                                            indy_handle_t search_handle,
+                                                                                            indy_u32_t    total_count)
+                                                                       );
+
     extern indy_error_t indy_prover_fetch_credentials(indy_handle_t command_handle,
                                                       indy_handle_t search_handle,
                                                       indy_u32_t    count,
@@ -246,6 +361,29 @@ extern "C" {
                                                                                                 indy_error_t  err)
                                                                            );
 
+    /// Creates a proof according to the given proof request.
+    ///
+    /// Credentials can be taken from several opened wallets (e.g. guardian wallet and personal wallet):
+    /// "wallet_handle" of the requested attribute or predicate in requested_credentials_json selects
+    /// the wallet holding the credential. The wallet passed to this function is used if it's omitted.
+    ///
+    /// NOTE: all used credentials must be issued for the same master secret, the one stored in wallet_handle
+    /// under master_secret_name. Proof can't combine credentials issued for different master secrets, so
+    /// credentials held by the guardian on behalf of the dependent must be issued for the dependent's master secret.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet) holding the master secret.
+    /// proof_req_json: proof request json.
+    /// requested_credentials_json: either a credential or self-attested attribute for each requested attribute.
+    /// master_secret_name: the id of the master secret stored in the wallet.
+    /// schemas_json: all schemas json participating in the proof request.
+    /// credential_defs_json: all credential definitions json participating in the proof request.
+    /// rev_states_json: all revocation states json participating in the proof request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Proof json
     extern indy_error_t indy_prover_create_proof(indy_handle_t command_handle,
                                                  indy_handle_t wallet_handle,
                                                  const char *  proof_req_json,
@@ -260,6 +398,27 @@ extern "C" {
                                                                       const char*   proof_json)
                                                  );
 
+    extern indy_error_t indy_prover_preview_disclosure(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  proof_req_json,
+                                                       const char *  requested_credentials_json,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   disclosure_json)
+                                                       );
+
+    extern indy_error_t indy_prover_bind_proof(indy_handle_t command_handle,
+                                               indy_handle_t wallet_handle,
+                                               const char *  proof_req_json,
+                                               const char *  proof_json,
+                                               const char *  holder_did,
+
+                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                    indy_error_t  err,
+                                                                    const char*   proof_json)
+                                               );
+
 
     extern indy_error_t indy_verifier_verify_proof(indy_handle_t command_handle,
                                                    const char *  proof_request_json,
@@ -274,6 +433,104 @@ extern "C" {
                                                                         indy_bool_t   valid )
                                                    );
 
+    /// Creates verifier session keeping parsed ledger artifacts to verify many proofs built from them.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// schemas_json: all schemas json participating in the proofs (see `indy_verifier_verify_proof`).
+    /// credential_defs_json: all credential definitions json participating in the proofs (see `indy_verifier_verify_proof`).
+    /// rev_reg_defs_json: all revocation registry definitions json participating in the proofs (see `indy_verifier_verify_proof`).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// session_handle: handle of the session to pass to `indy_verifier_verify_proof_with_session`.
+    extern indy_error_t indy_verifier_create_session(indy_handle_t command_handle,
+                                                     const char *  schemas_json,
+                                                     const char *  credential_defs_json,
+                                                     const char *  rev_reg_defs_json,
+
+                                                     void           (*cb)(indy_handle_t command_handle_,
+                                                                          indy_error_t  err,
+                                                                          indy_handle_t session_handle)
+                                                     );
+
+    /// Verifies a proof using ledger artifacts kept by the verifier session.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// session_handle: session handle (created by `indy_verifier_create_session`).
+    /// proof_request_json: proof request json (see `indy_verifier_verify_proof`).
+    /// proof_json: created for request proof json (see `indy_verifier_verify_proof`).
+    /// rev_regs_json: all revocation registries json participating in the proof (see `indy_verifier_verify_proof`).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if signature is valid, false - otherwise
+    extern indy_error_t indy_verifier_verify_proof_with_session(indy_handle_t command_handle,
+                                                                indy_handle_t session_handle,
+                                                                const char *  proof_request_json,
+                                                                const char *  proof_json,
+                                                                const char *  rev_regs_json,
+
+                                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                                     indy_error_t  err,
+                                                                                     indy_bool_t   valid )
+                                                                );
+
+    /// Closes verifier session and frees ledger artifacts kept by it.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// session_handle: session handle (created by `indy_verifier_create_session`).
+    /// cb: Callback that takes command result as parameter.
+    extern indy_error_t indy_verifier_close_session(indy_handle_t command_handle,
+                                                    indy_handle_t session_handle,
+
+                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                         indy_error_t  err)
+                                                    );
+
+    /// Saves proof request template in the wallet to instantiate proof requests from it later.
+    /// Template with the same id is replaced.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// template_id: identifier of the template in the wallet.
+    /// template_json: proof request json without nonce. Any string value in form of "${param_name}"
+    ///     is a placeholder replaced with the parameter passed to `indy_verifier_instantiate_proof_request_template`.
+    /// cb: Callback that takes command result as parameter.
+    extern indy_error_t indy_verifier_save_proof_request_template(indy_handle_t command_handle,
+                                                                  indy_handle_t wallet_handle,
+                                                                  const char *  template_id,
+                                                                  const char *  template_json,
+
+                                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                                       indy_error_t  err)
+                                                                  );
+
+    /// Builds proof request from the template saved by `indy_verifier_save_proof_request_template`.
+    /// Placeholders of the template are replaced with the parameters and fresh nonce is generated.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// template_id: identifier of the template in the wallet.
+    /// params_json: values of the template placeholders {"<param_name>": <any json value>}
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// proof_request_json: proof request json ready to be sent to the prover.
+    extern indy_error_t indy_verifier_instantiate_proof_request_template(indy_handle_t command_handle,
+                                                                         indy_handle_t wallet_handle,
+                                                                         const char *  template_id,
+                                                                         const char *  params_json,
+
+                                                                         void           (*cb)(indy_handle_t command_handle_,
+                                                                                              indy_error_t  err,
+                                                                                              const char*   proof_request_json)
+                                                                         );
+
 
     extern indy_error_t indy_create_revocation_state(indy_handle_t command_handle,
                                                      indy_handle_t blob_storage_reader_handle,
@@ -315,6 +572,146 @@ extern "C" {
                                                                  const char*   res)
                                             );
 
+    /// Encodes json of anoncreds object (Proof, Credential and etc) into MessagePack.
+    ///
+    /// Binary form is more compact than json and can be used to transmit big objects like proofs
+    /// over constrained transports. Use indy_anoncreds_msgpack_to_json to get the original json back.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// json: json of anoncreds object to encode.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    ///   msgpack_data: a pointer to the first byte of the encoded object
+    ///   msgpack_len: the length of the encoded object in bytes
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_anoncreds_json_to_msgpack(indy_handle_t command_handle,
+                                                       const char *  json,
+                                                       void           (*cb)(indy_handle_t     command_handle_,
+                                                                            indy_error_t      err,
+                                                                            const indy_u8_t*  msgpack_data,
+                                                                            indy_u32_t        msgpack_len)
+                                                       );
+
+    /// Decodes anoncreds object encoded by indy_anoncreds_json_to_msgpack back into json.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// msgpack_data: a pointer to the first byte of the encoded object.
+    /// msgpack_len: the length of the encoded object in bytes.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    ///   json: json of the decoded object
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_anoncreds_msgpack_to_json(indy_handle_t     command_handle,
+                                                       const indy_u8_t*  msgpack_data,
+                                                       indy_u32_t        msgpack_len,
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   json)
+                                                       );
+
+    /// Encodes raw value of credential attribute into the integer used in CL signatures.
+    ///
+    /// Issuers should use this encoding for "encoded" values of credentials, so verifiers can check
+    /// that revealed raw values correspond to the signed ones:
+    ///     32-bit integers in canonical form are encoded as themselves;
+    ///     any other value is encoded as decimal representation of sha256 hash of its utf-8 bytes.
+    ///
+    /// Set "enforce_canonical_attr_encoding" of `indy_set_runtime_config` to reject credential values and proofs
+    /// which encoded values don't match this encoding.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// raw: raw value of the attribute.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    ///   encoded: encoded value of the attribute
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_encode_attribute_value(indy_handle_t command_handle,
+                                                    const char *  raw,
+                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                         indy_error_t  err,
+                                                                         const char*   encoded)
+                                                    );
+
+    /// Wraps anoncreds credential into W3C Verifiable Credential data model (Experimental)
+    ///
+    /// Allows exchanging of credentials with wallets supporting W3C Verifiable Credentials.
+    /// Raw attribute values are put into "credentialSubject", encoded values and CL signature
+    /// are put into the proof of "CLSignature2019" type, so the credential can be converted back
+    /// by `indy_from_w3c_credential`.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// cred_json: credential json returned by indy_issuer_create_credential.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// w3c_cred_json: W3C Verifiable Credential json
+    ///     {
+    ///         "@context": ["https://www.w3.org/2018/credentials/v1"],
+    ///         "type": ["VerifiableCredential", "IndyCredential"],
+    ///         "issuer": <fully qualified issuer DID>,
+    ///         "issuanceDate": Optional<date of issuance in RFC3339 format>, omitted as anoncreds credential doesn't carry it
+    ///         "credentialSchema": {
+    ///             "id": <schema id>,
+    ///             "type": "IndyCredentialSchema",
+    ///             "definition": <credential definition id>,
+    ///             "revocationRegistry": Optional<revocation registry id>,
+    ///         },
+    ///         "credentialSubject": {
+    ///             <attr_name>: <raw value>,
+    ///         },
+    ///         "proof": {
+    ///             "type": "CLSignature2019",
+    ///             "encodedValues": { <attr_name>: <encoded value> },
+    ///             "attributesMetadata": Optional<{ <attr_name>: { "mime-type": .., "encoding": .. } }>,
+    ///             "signature": <credential signature>,
+    ///             "signatureCorrectnessProof": <signature correctness proof>,
+    ///             "revReg": Optional<revocation registry accumulator>,
+    ///             "witness": Optional<witness>
+    ///         }
+    ///     }
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_to_w3c_credential(indy_handle_t command_handle,
+                                               const char *  cred_json,
+                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                    indy_error_t  err,
+                                                                    const char*   w3c_cred_json)
+                                               );
+
+    /// Converts W3C Verifiable Credential created by `indy_to_w3c_credential` back to anoncreds credential (Experimental)
+    /// Raw values of "credentialSubject" must match the encoded values of the proof.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// w3c_cred_json: W3C Verifiable Credential json (see `indy_to_w3c_credential`).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// cred_json: credential json in the format returned by indy_issuer_create_credential
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_from_w3c_credential(indy_handle_t command_handle,
+                                                 const char *  w3c_cred_json,
+                                                 void           (*cb)(indy_handle_t command_handle_,
+                                                                      indy_error_t  err,
+                                                                      const char*   cred_json)
+                                                 );
+
 #ifdef __cplusplus
 }
 #endif
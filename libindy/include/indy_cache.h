@@ -123,6 +123,102 @@ extern "C" {
                                                   void          (*cb)(indy_handle_t command_handle_,
                                                                       indy_error_t  err)
                                                  );
+
+    /// Purge NYM cache keeping verkeys resolved by indy_pack_message_for_dids.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// options_json:
+    ///  {
+    ///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+    ///  }
+    extern indy_error_t indy_purge_nym_cache(indy_handle_t command_handle,
+                                             indy_handle_t wallet_handle,
+                                             const char *  options_json,
+                                             void          (*cb)(indy_handle_t command_handle_,
+                                                                 indy_error_t  err)
+                                            );
+
+    /// Starts background prefetching of revocation states for frequently used credentials.
+    /// Revocation states are periodically updated from the ledger and stored inside of cache,
+    /// so they can be taken by indy_get_cached_revocation_state without waiting for the ledger
+    /// while building the proof. Credentials are ranked by the number of proofs created with them
+    /// by indy_prover_create_proof. Prefetching is stopped once the wallet or blob storage reader is closed.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails.
+    /// options_json:
+    ///  {
+    ///    interval: (int, optional, 300 by default) Prefetch revocation states every this many seconds.
+    ///    freshness: (int, optional, 600 by default) Refresh cached revocation state if older than this many seconds.
+    ///    maxRequests: (int, optional, 10 by default) Refresh not more than this many revocation states per round.
+    ///    maxCredentials: (int, optional, 20 by default) Keep revocation states of this many credentials
+    ///                    most frequently used to build proofs.
+    ///  }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// prefetch_handle: handle to stop prefetching with indy_stop_revocation_prefetch.
+    extern indy_error_t indy_start_revocation_prefetch(indy_handle_t command_handle,
+                                                       indy_handle_t pool_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       indy_handle_t blob_storage_reader_handle,
+                                                       const char *  options_json,
+                                                       void          (*cb)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err,
+                                                                           indy_handle_t prefetch_handle)
+                                                      );
+
+    /// Stops background prefetching of revocation states started by indy_start_revocation_prefetch.
+    /// Revocation states prefetched so far are kept in cache.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// prefetch_handle: handle returned by indy_start_revocation_prefetch.
+    /// cb: Callback that takes command result as parameter.
+    extern indy_error_t indy_stop_revocation_prefetch(indy_handle_t command_handle,
+                                                      indy_handle_t prefetch_handle,
+                                                      void          (*cb)(indy_handle_t command_handle_,
+                                                                          indy_error_t  err)
+                                                     );
+
+    /// Gets revocation state of the credential prefetched by indy_start_revocation_prefetch.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// cred_id: id of the credential stored in the wallet.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Revocation state json:
+    ///     {
+    ///         "rev_reg": <revocation registry>,
+    ///         "witness": <witness>,
+    ///         "timestamp" : integer
+    ///     }
+    ///
+    /// #Errors
+    /// WalletItemNotFound if revocation state of the credential isn't prefetched yet.
+    extern indy_error_t indy_get_cached_revocation_state(indy_handle_t command_handle,
+                                                         indy_handle_t wallet_handle,
+                                                         const char *  cred_id,
+                                                         void          (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   rev_state_json)
+                                                        );
 #ifdef __cplusplus
 }
 #endif
@@ -1,6 +1,14 @@
 #ifndef __indy__core__included__
 #define __indy__core__included__
 
+/// Callbacks of libindy functions are called on libindy dispatcher threads.
+/// Callbacks of functions called from the same application thread are called one by one in the order
+/// the commands are completed, callbacks of functions called from other threads may be called in parallel.
+///
+/// NOTE: callback must never block waiting for the result of another libindy function
+/// (e.g. call a function and wait for its callback), as that result may be delivered by the same
+/// dispatcher thread. Pass the result to the application thread (event loop, future, channel) instead.
+
 #include "indy_mod.h"
 #include "indy_types.h"
 
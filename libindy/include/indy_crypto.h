@@ -7,6 +7,72 @@ extern "C" {
 
 
 
+    /// Registers key vault (HSM, secure enclave and etc) which keeps secret keys outside of libindy.
+    ///
+    /// Keys created with "key_vault" field of indy_create_key are stored in the wallet as references only
+    /// and all signing operations (indy_crypto_sign, ledger requests signing and etc) are delegated to the vault.
+    /// Signatures returned by the vault are verified with the verkey of the key before they are used.
+    /// Vault registered before under the same name is replaced.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// key_vault: name of the key vault.
+    /// get_verkey: returns base58 verkey of the key by its reference (verkey_p must stay valid until free is called for it)
+    /// sign: signs message with the key by its reference (signature_raw_p must stay valid until free is called for it)
+    /// free: frees data returned by get_verkey and sign
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_register_key_vault(indy_handle_t     command_handle,
+                                                const char*       key_vault,
+
+                                                indy_error_t (*get_verkey)(const char*        key_ref,
+                                                                           const char**       verkey_p),
+
+                                                indy_error_t (*sign)(const char*              key_ref,
+                                                                     const indy_u8_t*         message_raw,
+                                                                     indy_u32_t               message_len,
+                                                                     const indy_u8_t**        signature_raw_p,
+                                                                     indy_u32_t*              signature_len_p),
+
+                                                indy_error_t (*free)(const void*              data),
+
+                                                void              (*cb)(indy_handle_t     command_handle_,
+                                                                        indy_error_t      err)
+                                                );
+
+    /// Registers external source of randomness (host RNG, certified DRBG and etc) used instead of the default OS one.
+    ///
+    /// The source is used for generation of keys created without seed (indy_create_key, indy_create_and_store_my_did,
+    /// indy_replace_keys_start) and generation of nonces and content encryption keys (indy_crypto_auth_crypt, indy_pack_message).
+    /// Bytes returned by the source are used as seed material of HMAC-DRBG (NIST SP 800-90A) only,
+    /// the generator is reseeded from the source every 1024 requests.
+    /// Health of the source is checked on registration and on every reseed.
+    /// Source registered before is replaced.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// fill: "fill" operation handler
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_register_entropy_source(indy_handle_t     command_handle,
+
+                                                     indy_error_t (*fill)(indy_u8_t*         buf,
+                                                                          indy_u32_t         len),
+
+                                                     void              (*cb)(indy_handle_t     command_handle_,
+                                                                             indy_error_t      err)
+                                                     );
+
     /// Creates keys pair and stores in the wallet.
     ///
     /// #Params
@@ -16,7 +82,10 @@ extern "C" {
     /// {
     ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
-    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values are 'ed25519', 'secp256k1' and 'p256'; 'secp256k1' and 'p256' keys can be used for signing only.
+    ///     "key_vault": string, (optional) Name of the key vault keeping the secret key (see indy_register_key_vault).
+    ///                          Only the key reference is stored in the wallet then and the key can be used for signing only.
+    ///     "key_ref": string, (optional) Reference to the key inside of the key vault. Required if "key_vault" is set.
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -40,6 +109,42 @@ extern "C" {
                                                                 const char *const vk)
                                        );
 
+    /// Generates BLS key pair with proof of possession of the secret key for a validator node.
+    /// Generated key isn't stored in the wallet. Public part of the key can be published with NODE transaction
+    /// (see indy_build_node_request).
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// key_json: Key information as json. Example:
+    /// {
+    ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
+    ///                                Can be UTF-8, base64 or hex string.
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - bls_key_json: Generated key as json:
+    ///   {
+    ///       "blskey": string, - BLS multi-signature key as base58-encoded string.
+    ///       "blskey_pop": string, - BLS key proof of possession as base58-encoded string.
+    ///       "signkey": string, - BLS secret key as base58-encoded string.
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_create_bls_key(indy_handle_t     command_handle,
+                                            const char *const key_json,
+
+                                            void              (*cb)(indy_handle_t     command_handle,
+                                                                    indy_error_t      err,
+                                                                    const char *const bls_key_json)
+                                           );
+
     /// Saves/replaces the meta information for the giving key in the wallet.
     ///
     /// #Params
@@ -96,6 +201,94 @@ extern "C" {
                                                                       const char *const metadata)
                                              );
 
+    /// Retrieves the path the key was derived by from the master seed (see "derivation_path" of indy_create_key).
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// verkey - The key (verkey, key id) to retrieve derivation path.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - derivation_path - The path the key was derived by.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// WalletItemNotFound if the key wasn't derived by path
+    /// Crypto*
+    extern indy_error_t indy_get_key_derivation_path(indy_handle_t     command_handle,
+                                                     indy_handle_t     wallet_handle,
+                                                     const char *const verkey,
+
+                                                     void              (*cb)(indy_handle_t     command_handle,
+                                                                             indy_error_t      err,
+                                                                             const char *const derivation_path)
+                                                    );
+
+    /// Returns X25519 key agreement key of the ed25519 key stored in the wallet.
+    ///
+    /// Key agreement key can be passed as a receiver key to indy_pack_message.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// verkey - The key (verkey, key id) stored in the wallet.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - key_agreement_key - base58 encoded X25519 public key with ":x25519" crypto type suffix.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_get_key_agreement_key(indy_handle_t     command_handle,
+                                                   indy_handle_t     wallet_handle,
+                                                   const char *const verkey,
+
+                                                   void              (*cb)(indy_handle_t     command_handle,
+                                                                           indy_error_t      err,
+                                                                           const char *const key_agreement_key)
+                                                  );
+
+    /// Converts ed25519 verkey to X25519 public key.
+    ///
+    /// Unlike indy_get_key_agreement_key the key doesn't need to be stored in the wallet,
+    /// so it can be used for keys of other parties, for instance, to implement custom envelope formats
+    /// on top of indy_crypto_box.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// verkey - ed25519 verkey to convert.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - key - base58 encoded X25519 public key with ":x25519" crypto type suffix.
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_convert_key(indy_handle_t     command_handle,
+                                         const char *const verkey,
+
+                                         void              (*cb)(indy_handle_t     command_handle,
+                                                                 indy_error_t      err,
+                                                                 const char *const key)
+                                        );
+
 
 
 
@@ -165,6 +358,41 @@ extern "C" {
                                                                 indy_bool_t   valid )
                                           );
 
+    /// Verify a signature with a verkey choosing how malformed inputs are reported.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// signer_vk: verkey of signer of the message
+    /// message_raw: a pointer to first byte of message that has been signed
+    /// message_len: a message length
+    /// signature_raw: a pointer to first byte of signature to be verified
+    /// signature_len: a signature length
+    /// strict: true - malformed verkey or signature (bad base58, wrong length, unsupported key type) is reported as an error
+    ///                with the detail, the same as indy_crypto_verify does;
+    ///         false - malformed verkey or signature is reported as invalid signature (valid = false)
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if signature is valid, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    /// CommonInvalidStructure if "strict" is set and verkey or signature is malformed
+    /// UnknownCryptoTypeError if "strict" is set and crypto type of verkey is unsupported
+    extern indy_error_t indy_crypto_verify_with_strictness(indy_handle_t      command_handle,
+                                                           const char *       signer_vk,
+                                                           const indy_u8_t *  message_raw,
+                                                           indy_u32_t         message_len,
+                                                           const indy_u8_t *  signature_raw,
+                                                           indy_u32_t         signature_len,
+                                                           indy_bool_t        strict,
+
+                                                           void           (*cb)(indy_handle_t command_handle_,
+                                                                                indy_error_t  err,
+                                                                                indy_bool_t   valid )
+                                                          );
+
     /// **** THIS FUNCTION WILL BE DEPRECATED USE indy_pack_message() INSTEAD ****
     /// Encrypt a message by authenticated-encryption scheme.
     ///
@@ -323,6 +551,79 @@ extern "C" {
                                                                       indy_u32_t        decrypted_msg_len)
                                                  );
 
+    /// Encrypts a message with libsodium crypto_box primitive using nonce provided by the caller.
+    ///
+    /// Unlike indy_crypto_auth_crypt the result isn't wrapped into any envelope and contains only the ciphertext
+    /// with authentication tag. The caller is responsible for never reusing the nonce with the same pair of keys.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// sender_vk: id (verkey) of message sender. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// recipient_vk: verkey of message recipient. X25519 keys returned by indy_convert_key can be used as well.
+    /// msg_data: a pointer to first byte of message that to be encrypted
+    /// msg_len: a message length
+    /// nonce_data: a pointer to first byte of 24 bytes nonce
+    /// nonce_len: a nonce length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// an encrypted message as a pointer to array of bytes.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_crypto_box(indy_handle_t      command_handle,
+                                        indy_handle_t      wallet_handle,
+                                        const char *       sender_vk,
+                                        const char *       recipient_vk,
+                                        const indy_u8_t*   msg_data,
+                                        indy_u32_t         msg_len,
+                                        const indy_u8_t*   nonce_data,
+                                        indy_u32_t         nonce_len,
+
+                                        void           (*cb)(indy_handle_t     command_handle_,
+                                                             indy_error_t      err,
+                                                             const indy_u8_t*  encrypted_msg,
+                                                             indy_u32_t        encrypted_len)
+                                        );
+
+    /// Decrypts a message encrypted with libsodium crypto_box primitive (see indy_crypto_box).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// recipient_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// sender_vk: verkey of message sender. X25519 keys returned by indy_convert_key can be used as well.
+    /// encrypted_msg: a pointer to first byte of message that to be decrypted
+    /// encrypted_len: a message length
+    /// nonce_data: a pointer to first byte of 24 bytes nonce the message was encrypted with
+    /// nonce_len: a nonce length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// decrypted message as a pointer to an array of bytes
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_crypto_box_open(indy_handle_t      command_handle,
+                                             indy_handle_t      wallet_handle,
+                                             const char *       recipient_vk,
+                                             const char *       sender_vk,
+                                             const indy_u8_t*   encrypted_msg,
+                                             indy_u32_t         encrypted_len,
+                                             const indy_u8_t*   nonce_data,
+                                             indy_u32_t         nonce_len,
+
+                                             void           (*cb)(indy_handle_t     command_handle_,
+                                                                  indy_error_t      err,
+                                                                  const indy_u8_t*  msg_data,
+                                                                  indy_u32_t        msg_len)
+                                             );
+
 
     /// Packs a message by encrypting the message and serializes it in a JWE-like format (Experimental)
     ///
@@ -338,6 +639,7 @@ extern "C" {
     ///                the message is being encrypted for.
     ///                Example:
     ///                "[<receiver edge_agent_1 verkey>, <receiver edge_agent_2 verkey>]"
+    ///                Key agreement keys returned by indy_get_key_agreement_key can be used as receiver's keys as well.
     /// sender: the sender's verkey as a string When null pointer is used in this parameter, anoncrypt is used
     /// cb: Callback that takes command result as parameter.
     ///
@@ -403,6 +705,102 @@ extern "C" {
                                                                indy_u32_t        jwe_msg_len)
                                           );
 
+    /// Packs a message with explicitly chosen packing mode (Experimental)
+    ///
+    /// Works the same way as indy_pack_message, but the way the sender is (or is not) disclosed
+    /// to the recipients is defined by the mode instead of presence of the sender key.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// message: a pointer to the first byte of the message to be packed
+    /// message_len: the length of the message
+    /// receiver_keys: a string in the format of a json list which will contain the list of receiver's keys
+    ///                the message is being encrypted for.
+    ///                Example:
+    ///                "[<receiver edge_agent_1 verkey>, <receiver edge_agent_2 verkey>]"
+    /// sender: the sender's verkey as a string. Must be null for anoncrypt mode and must be set for other modes.
+    /// mode: packing mode. One of:
+    ///     "authcrypt" - the sender is authenticated and its key is disclosed in the message header
+    ///                   (the same as indy_pack_message with sender),
+    ///     "anoncrypt" - the sender is anonymous (the same as indy_pack_message without sender),
+    ///     "authcrypt_with_hidden_sender" - the sender is authenticated, but the message header is indistinguishable
+    ///                   from anoncrypt one, so the sender key is visible only to the recipients.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// a JWE in the same format as indy_pack_message returns
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_pack_message_with_mode(indy_handle_t      command_handle,
+                                                    indy_handle_t      wallet_handle,
+                                                    const indy_u8_t*   message,
+                                                    indy_u32_t         message_len,
+                                                    const char *       receiver_keys,
+                                                    const char *       sender,
+                                                    const char *       mode,
+
+                                                    void           (*cb)(indy_handle_t     command_handle_,
+                                                                         indy_error_t      err,
+                                                                         const indy_u8_t*  jwe_msg_raw,
+                                                                         indy_u32_t        jwe_msg_len)
+                                                    );
+
+    /// Packs a message for receivers identified by their DIDs (Experimental)
+    ///
+    /// Receivers keys are resolved with GET_NYM requests to the ledger. Resolved keys are cached in the wallet,
+    /// so packing of the next messages doesn't require a ledger round trip.
+    /// Cached key older than minFresh is still used but refreshed in background, so rotation of receiver's key
+    /// is noticed by the next messages. Cached key older than maxStale is fetched from the ledger before packing.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// message: a pointer to the first byte of the message to be packed
+    /// message_len: the length of the message
+    /// receiver_dids: a string in the format of a json list which will contain the list of receiver's DIDs
+    ///                the message is being encrypted for.
+    ///                Example:
+    ///                "[<receiver DID>, <receiver DID>]"
+    /// sender: the sender's verkey as a string When null pointer is used in this parameter, anoncrypt is used
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional, 60 by default) Use cached key without refresh if not older than this many seconds.
+    ///              -1 means do not check age.
+    ///    maxStale: (int, optional, 3600 by default) Use cached key older than minFresh but not older than this many seconds
+    ///              and refresh it in background. -1 means do not check age.
+    ///  }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// a JWE in the same format as indy_pack_message returns
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_pack_message_for_dids(indy_handle_t      command_handle,
+                                                   indy_handle_t      pool_handle,
+                                                   indy_handle_t      wallet_handle,
+                                                   const indy_u8_t*   message,
+                                                   indy_u32_t         message_len,
+                                                   const char *       receiver_dids,
+                                                   const char *       sender,
+                                                   const char *       options_json,
+
+                                                   void           (*cb)(indy_handle_t     command_handle_,
+                                                                        indy_error_t      err,
+                                                                        const indy_u8_t*  jwe_msg_raw,
+                                                                        indy_u32_t        jwe_msg_len)
+                                                   );
+
 
     /// Unpacks a JWE-like formatted message outputted by indy_pack_message (Experimental)
     ///
@@ -418,7 +816,9 @@ extern "C" {
     /// {
     ///     message: <decrypted message>,
     ///     sender_verkey: <sender_verkey>
-    ///     recipient_verkey: <recipient_verkey>
+    ///     recipient_verkey: <recipient_verkey>,
+    ///     mode: "authcrypt" or "authcrypt_with_hidden_sender" - mode the message was packed with,
+    ///     sender_authenticated: true
     /// }
     ///
     /// OR
@@ -426,7 +826,9 @@ extern "C" {
     /// if anoncrypt was used to pack the message returns this json structure:
     /// {
     ///     message: <decrypted message>,
-    ///     recipient_verkey: <recipient_verkey>
+    ///     recipient_verkey: <recipient_verkey>,
+    ///     mode: "anoncrypt",
+    ///     sender_authenticated: false
     /// }
     ///
     ///
@@ -445,6 +847,90 @@ extern "C" {
                                                                  const indy_u8_t*  res_json_raw,
                                                                  indy_u32_t        res_json_len)
                                             );
+
+    /// Lists dead letters stored in the wallet (Experimental)
+    ///
+    /// A dead letter is stored when indy_unpack_message fails (for example message was sent to a rotated key),
+    /// so the message isn't lost and can be retried later with indy_retry_dead_letter.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// dead_letters_json: list of dead letters ordered by receiving time
+    /// [{
+    ///     "id": string, dead letter id
+    ///     "message": <JWE which failed to be unpacked>,
+    ///     "error": string, error of the last unpack attempt
+    ///     "received": int, receiving time as unix timestamp in seconds
+    ///     "attempts": int, number of unpack attempts
+    /// }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    extern indy_error_t indy_list_dead_letters(indy_handle_t      command_handle,
+                                               indy_handle_t      wallet_handle,
+
+                                               void           (*cb)(indy_handle_t     command_handle_,
+                                                                    indy_error_t      err,
+                                                                    const char*       dead_letters_json)
+                                               );
+
+    /// Retries unpacking of the message stored as dead letter (Experimental)
+    ///
+    /// Dead letter is removed from the wallet if the message is unpacked successfully,
+    /// otherwise its error and number of attempts are updated.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// dead_letter_id: id of the dead letter (see indy_list_dead_letters).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// the same json structure as indy_unpack_message returns
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_retry_dead_letter(indy_handle_t      command_handle,
+                                               indy_handle_t      wallet_handle,
+                                               const char*        dead_letter_id,
+
+                                               void           (*cb)(indy_handle_t     command_handle_,
+                                                                    indy_error_t      err,
+                                                                    const indy_u8_t*  res_json_raw,
+                                                                    indy_u32_t        res_json_len)
+                                               );
+
+    /// Removes dead letters from the wallet (Experimental)
+    ///
+    /// At most 100 dead letters are kept in the wallet, the oldest ones are dropped when a new one is stored.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// received_before: remove only dead letters received before this time (unix timestamp in seconds),
+    ///                  -1 to remove all dead letters.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    extern indy_error_t indy_purge_dead_letters(indy_handle_t      command_handle,
+                                                indy_handle_t      wallet_handle,
+                                                indy_i64_t         received_before,
+
+                                                void           (*cb)(indy_handle_t     command_handle_,
+                                                                     indy_error_t      err)
+                                                );
 #ifdef __cplusplus
 }
 #endif
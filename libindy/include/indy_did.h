@@ -23,7 +23,8 @@ extern "C" {
     ///     "seed": string, (optional) Seed that allows deterministic did creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
     ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-    ///               currently only 'ed25519' value is supported for this field)
+    ///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+    ///               'secp256k1' and 'p256' keys can be used for signing only)
     ///     "cid": bool, (optional; if not set then false is used;)
     ///     "method_name": string, method name to create fully qualified did (Example:  `did:method_name:NcYxiDXkpYi6ov5FcYDi1e`).
     /// }
@@ -52,6 +53,57 @@ extern "C" {
                                                                           const char *const   verkey)
                                                     );
 
+    /// Creates and stores in the wallet a batch of new DIDs (owned by the caller of the library)
+    /// in one command, e.g. to pre-provision pairwise DIDs for connection onboarding.
+    /// Equivalent of calling "indy_create_and_store_my_did" "count" times.
+    /// Either all DIDs are stored or none of them if the command fails.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// count: number of DIDs to create (from 1 to 1000).
+    /// options_json: batch options as json.
+    /// {
+    ///     "hd_seed": string, (optional) Master seed of 16 to 64 bytes the ed25519 key
+    ///                of every DID is derived from by SLIP-0010 path m/<index>' (see "derivation_path" of indy_create_and_store_my_did).
+    ///                Allows to recreate the same DIDs later. Random DIDs are created if not set.
+    ///     "hd_seed_encoding": string, (required if "hd_seed" is set) Encoding of "hd_seed": 'hex', 'base64' or 'utf8'.
+    ///     "start_index": int, (optional) Index of the first DID derived from "hd_seed" (0 by default).
+    ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+    ///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+    ///               'secp256k1' and 'p256' keys can be used for signing only)
+    ///     "cid": bool, (optional; if not set then false is used;)
+    ///     "method_name": string, (optional) method name to create fully qualified dids.
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   dids_json: list of created DIDs
+    /// [{
+    ///     "did": string, DID generated and stored in the wallet
+    ///     "verkey": string, The DIDs verification key
+    ///     "index": int, (only for "hd_seed") index the DID seed was derived with
+    /// }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_create_and_store_my_dids(indy_handle_t command_handle,
+                                                      indy_handle_t wallet_handle,
+                                                      indy_u32_t    count,
+                                                      const char *  options_json,
+
+                                                      void          (*cb)(indy_handle_t  command_handle_,
+                                                                           indy_error_t  err,
+                                                                           const char *const   dids_json)
+                                                     );
+
     /// Generated temporary keys (signing and encryption keys) for an existing
     /// DID (owned by the caller of the library).
     ///
@@ -63,7 +115,8 @@ extern "C" {
     ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
     ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-    ///               currently only 'ed25519' value is supported for this field)
+    ///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+    ///               'secp256k1' and 'p256' keys can be used for signing only)
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -345,6 +398,81 @@ extern "C" {
                                                                       const char *const metadata)
                                              );
 
+    /// Designates the key to be used by my DID for the particular purpose.
+    ///
+    /// By default the single DID verkey is used for everything. With designated keys the DID can
+    /// authenticate, sign assertions and receive packed messages with different keys.
+    /// Designated keys are kept in the wallet only and aren't listed in the DID Document returned by indy_resolve_did.
+    /// Issuance log heads and proof holder bindings are signed with the DID verkey anyway,
+    /// as verifiers check them against the verkey of the DID NYM.
+    /// "keyAgreement" key receives messages packed for the DID by indy_pack_message_for_dids.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did - my DID.
+    /// purpose - key purpose: "authentication" | "assertionMethod" | "keyAgreement".
+    /// verkey - the key to use:
+    ///     - for "authentication" and "assertionMethod": verkey of the key stored in the wallet (created by indy_create_key).
+    ///     - for "keyAgreement": key agreement key returned by indy_get_key_agreement_key.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: command handle to map callback to caller context.
+    /// - err: Error code.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_set_did_key(indy_handle_t     command_handle,
+                                         indy_handle_t     wallet_handle,
+                                         const char *const did,
+                                         const char *const purpose,
+                                         const char *const verkey,
+
+                                         void              (*cb)(indy_handle_t     command_handle,
+                                                                 indy_error_t      err)
+                                        );
+
+    /// Returns keys my DID uses for every purpose.
+    ///
+    /// Purposes without designated key fall back to the DID verkey,
+    /// so "assertionMethod" key should be used to sign and "keyAgreement" key to pack messages for the DID.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did - my DID.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - did_keys_json - keys of the DID:
+    ///     {
+    ///         "authentication": string, - designated key or DID verkey
+    ///         "assertionMethod": string, - designated key or DID verkey
+    ///         "keyAgreement": optional<string>, - designated key or key agreement key of DID verkey (absent for not ed25519 DID verkey)
+    ///     }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_get_did_keys(indy_handle_t     command_handle,
+                                          indy_handle_t     wallet_handle,
+                                          const char *const did,
+
+                                          void              (*cb)(indy_handle_t     command_handle,
+                                                                  indy_error_t      err,
+                                                                  const char *const did_keys_json)
+                                         );
+
     /// Retrieves the information about the giving DID in the wallet.
     ///
     /// #Params
@@ -457,6 +585,257 @@ extern "C" {
                                                                  const char *const full_qualified_did)
                                         );
 
+    /// Creates a self-contained proof of control over DID written to the ledger.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// pool_handle: Pool handle (created by open_pool_ledger).
+    /// did: DID stored in the wallet and written to the ledger.
+    /// challenge: challenge (nonce) provided by the verifier.
+    /// audience: identifier of the verifier the proof is created for (for example its DID or URL).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - proof_json: proof of DID control {"did", "verkey", "challenge", "audience", "signature", "seqNo"}
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_create_did_ownership_proof(indy_handle_t     command_handle,
+                                                        indy_handle_t     wallet_handle,
+                                                        indy_handle_t     pool_handle,
+                                                        const char *const did,
+                                                        const char *const challenge,
+                                                        const char *const audience,
+
+                                                        void              (*cb)(indy_handle_t     command_handle,
+                                                                                indy_error_t      err,
+                                                                                const char *const proof_json)
+                                                       );
+
+    /// Checks NYM of DID stored in the wallet against the ledger.
+    ///
+    /// Observed verkey and role are remembered in the wallet. If the ledger verkey differs from the wallet one
+    /// (and isn't a key rotation started by indy_replace_keys_start) or the role differs from previously observed,
+    /// "did_nym_changed" event is reported to the listener set by indy_set_event_listener.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// pool_handle: Pool handle (created by open_pool_ledger).
+    /// did: DID stored in the wallet and written to the ledger.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - nym_json: NYM state on the ledger {"verkey", "role", "changed"}
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_check_my_did_nym(indy_handle_t     command_handle,
+                                              indy_handle_t     wallet_handle,
+                                              indy_handle_t     pool_handle,
+                                              const char *const did,
+
+                                              void              (*cb)(indy_handle_t     command_handle,
+                                                                      indy_error_t      err,
+                                                                      const char *const nym_json)
+                                             );
+
+    /// Verifies a proof of DID control created by indy_create_did_ownership_proof against the ledger.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// pool_handle: Pool handle (created by open_pool_ledger).
+    /// proof_json: proof of DID control created by indy_create_did_ownership_proof.
+    /// challenge: challenge expected to be signed.
+    /// audience: identifier of the verifier expected in the proof.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - valid: true - if the proof is correct, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_verify_did_ownership_proof(indy_handle_t     command_handle,
+                                                        indy_handle_t     pool_handle,
+                                                        const char *const proof_json,
+                                                        const char *const challenge,
+                                                        const char *const audience,
+
+                                                        void              (*cb)(indy_handle_t command_handle,
+                                                                                indy_error_t  err,
+                                                                                indy_bool_t   valid)
+                                                       );
+
+    /// Publishes endpoint of the DID to the ledger as standardized endpoint ATTRIB
+    /// and updates the endpoint address stored in the wallet for this DID.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// pool_handle: Pool handle (created by open_pool_ledger).
+    /// did: The DID to publish endpoint for.
+    /// address: The DIDs endpoint address.
+    /// types_json: (Optional) json array of endpoint types.
+    /// routing_keys_json: (Optional) json array of routing keys ordered from the recipient.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - err: Error code.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_publish_endpoint_for_did(indy_handle_t     command_handle,
+                                                      indy_handle_t     wallet_handle,
+                                                      indy_handle_t     pool_handle,
+                                                      const char *const did,
+                                                      const char *const address,
+                                                      const char *const types_json,
+                                                      const char *const routing_keys_json,
+
+                                                      void              (*cb)(indy_handle_t command_handle,
+                                                                              indy_error_t  err)
+                                                     );
+
+    /// Resolves endpoint ATTRIB of the DID into DID Document service entry.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// pool_handle: Pool handle (created by open_pool_ledger).
+    /// did: The DID to resolve endpoint.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - service_json: DID Document service entry:
+    ///     {
+    ///         "id": string,
+    ///         "type": string, - the first endpoint type published ("did-communication" if types aren't published)
+    ///         "priority": 0,
+    ///         "recipientKeys": [string],
+    ///         "routingKeys": [string, ...],
+    ///         "serviceEndpoint": string
+    ///     }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_resolve_service_endpoint_for_did(indy_handle_t     command_handle,
+                                                              indy_handle_t     pool_handle,
+                                                              const char *const did,
+
+                                                              void              (*cb)(indy_handle_t     command_handle,
+                                                                                      indy_error_t      err,
+                                                                                      const char *const service_json)
+                                                             );
+
+    /// Resolves the DID into W3C DID Document assembled from its NYM and endpoint ATTRIB written to the ledger.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// pool_handle: Pool handle (created by open_pool_ledger).
+    /// did: The DID to resolve.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - did_doc_json: DID Document:
+    ///     {
+    ///         "@context": "https://www.w3.org/ns/did/v1",
+    ///         "id": string, - fully qualified DID
+    ///         "verificationMethod": [{
+    ///             "id": string, - fully qualified DID with "#key-1" fragment
+    ///             "type": "Ed25519VerificationKey2018",
+    ///             "controller": string, - fully qualified DID
+    ///             "publicKeyBase58": string - verkey of the DID
+    ///         }],
+    ///         "authentication": [string], - ids of verification methods
+    ///         "assertionMethod": [string], - ids of verification methods
+    ///         "keyAgreement": optional<[string]>, - ids of "X25519KeyAgreementKey2019" verification methods
+    ///         "service": [{ - one per published endpoint type, empty if no endpoint ATTRIB is written for the DID
+    ///             "id": string,
+    ///             "type": string, - endpoint type ("did-communication" if types aren't published)
+    ///             "priority": 0,
+    ///             "recipientKeys": [string],
+    ///             "routingKeys": [string, ...],
+    ///             "serviceEndpoint": string
+    ///         }],
+    ///         "pairwise": optional<{ - pairwise with the DID stored in the wallet (set by indy_resolve_did_with_wallet only)
+    ///             "myDid": string,
+    ///             "metadata": optional<string>
+    ///         }>,
+    ///         "provenance": { - source of each verification method and service (by id) and pairwise (by "pairwise" key):
+    ///             <id>: "ledger:NYM" | "ledger:ATTRIB" | "wallet:pairwise"
+    ///         }
+    ///     }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_resolve_did(indy_handle_t     command_handle,
+                                         indy_handle_t     pool_handle,
+                                         const char *const did,
+
+                                         void              (*cb)(indy_handle_t     command_handle,
+                                                                 indy_error_t      err,
+                                                                 const char *const did_doc_json)
+                                        );
+
+    /// Resolves the DID into W3C DID Document as indy_resolve_did does and merges pairwise
+    /// with the DID stored in the wallet into "pairwise" field of the document.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// pool_handle: Pool handle (created by open_pool_ledger).
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did: The DID to resolve.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - did_doc_json: DID Document (see `did_doc_json` of indy_resolve_did)
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_resolve_did_with_wallet(indy_handle_t     command_handle,
+                                                     indy_handle_t     pool_handle,
+                                                     indy_handle_t     wallet_handle,
+                                                     const char *const did,
+
+                                                     void              (*cb)(indy_handle_t     command_handle,
+                                                                             indy_error_t      err,
+                                                                             const char *const did_doc_json)
+                                                    );
+
 #ifdef __cplusplus
 }
 #endif
@@ -45,6 +45,11 @@ extern "C" {
     /// Publishes request message to validator pool (no signing, unlike sign_and_submit_request).
     ///
     /// The request is sent to the validator pool as is. It's assumed that it's already prepared.
+    /// Waiting for the reply can be cancelled with `indy_cancel_command`
+    /// by the handle returned by `indy_get_current_cancel_handle` right after this call.
+    /// Read requests with the same operation and protocol version as a read request already in flight
+    /// and sent to the same nodes with the same timeout (see indy_submit_action) are not sent again:
+    /// they get the reply of the request in flight with `reqId` set to the one of the sent request.
     ///
     /// #Params
     /// command_handle: command handle to map callback to caller context.
@@ -75,6 +80,8 @@ extern "C" {
     ///     GET_VALIDATOR_INFO
     ///
     /// The request is sent to the nodes as is. It's assumed that it's already prepared.
+    /// Waiting for the replies can be cancelled with `indy_cancel_command`
+    /// by the handle returned by `indy_get_current_cancel_handle` right after this call.
     ///
     /// #Params
     /// command_handle: command handle to map callback to caller context.
@@ -167,6 +174,37 @@ extern "C" {
                                                                      const char*   signed_request_json)
                                                 );
 
+    /// Multi signs request message with several DIDs at once.
+    ///
+    /// Adds signatures of all passed DIDs to `signatures` field of the request json (see indy_multi_sign_request).
+    /// It is useful for collecting of quorum of trustees signatures for the request when all DIDs are kept in one wallet.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_dids_json: Ids of Identities stored in secured Wallet as json array: [string, ...].
+    /// request_json: Request data json.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Signed request json.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+
+    extern indy_error_t indy_multi_sign_request_with_dids(indy_handle_t command_handle,
+                                                          indy_handle_t  wallet_handle,
+                                                          const char *   submitter_dids_json,
+                                                          const char *   request_json,
+
+                                                          void           (*cb)(indy_handle_t command_handle_,
+                                                                               indy_error_t  err,
+                                                                               const char*   signed_request_json)
+                                                          );
+
     /// Builds a request to get a DDO.
     ///
     /// #Params
@@ -337,6 +375,91 @@ extern "C" {
                                                                          const char*   nym_json)
                                                    );
 
+    /// Collects the history of NYM transactions written for the DID (creation, key rotations, role changes)
+    /// by walking GET_TXN requests over the domain ledger.
+    ///
+    /// Note: one GET_TXN request is sent for every transaction in the walked range, so the range can't be longer
+    /// than 1000 transactions. Narrow it with `from` and `to` options for big ledgers.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// target_did: Target DID as base58-encoded string for 16 or 32 bit DID value.
+    /// options_json: (Optional) range of domain ledger sequence numbers to walk:
+    /// {
+    ///     "from": int (optional) - first sequence number (1 by default),
+    ///     "to": int (optional) - last sequence number (sequence number of the current NYM of `target_did` by default),
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// NYM transactions of the DID in chronological order:
+    /// [{
+    ///     "seqNo": int - sequence number of the transaction,
+    ///     "txnTime": int (optional) - time when the transaction was written,
+    ///     "from": string (optional) - DID of the transaction author,
+    ///     "data": json - NYM transaction data (dest, verkey, role, alias)
+    /// }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_get_nym_history(indy_handle_t command_handle,
+                                             indy_handle_t pool_handle,
+                                             const char *  target_did,
+                                             const char *  options_json,
+
+                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                  indy_error_t  err,
+                                                                  const char*   history_json)
+                                            );
+
+    /// Fetches a batch of ledger transactions in sequence number order by sending GET_TXN requests.
+    ///
+    /// Use it to stream the ledger for audit or mirroring: pass `cursor` from the returned batch as `from`
+    /// option of the next call until `complete` is set. Fetching can be resumed later from any saved cursor.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// options_json: (Optional) batch to fetch:
+    /// {
+    ///     "ledgerType": string (optional) - type of the ledger the transactions are fetched from:
+    ///         DOMAIN - used default,
+    ///         POOL,
+    ///         CONFIG
+    ///         any number
+    ///     "from": int (optional) - first sequence number of the batch (1 by default),
+    ///     "to": int (optional) - last sequence number to fetch (the end of the ledger by default),
+    ///     "batchSize": int (optional) - max number of transactions in the batch (100 by default, 1000 at most),
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Batch of transactions:
+    /// {
+    ///     "txns": [{
+    ///         "seqNo": int - sequence number of the transaction,
+    ///         "data": json - transaction as it is returned by GET_TXN request
+    ///     }],
+    ///     "cursor": int - sequence number to pass as `from` to fetch the next batch,
+    ///     "complete": bool - true if the end of the ledger or `to` sequence number is reached
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_fetch_ledger_txns(indy_handle_t command_handle,
+                                               indy_handle_t pool_handle,
+                                               const char *  options_json,
+
+                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                    indy_error_t  err,
+                                                                    const char*   txns_batch_json)
+                                              );
+
     /// Builds a SCHEMA request. Request to add Credential's schema.
     ///
     /// #Params
@@ -523,6 +646,7 @@ extern "C" {
     ///     alias: string - Node's alias
     ///     blskey: string - (Optional) BLS multi-signature key as base58-encoded string.
     ///     blskey_pop: string - (Optional) BLS key proof of possession as base58-encoded string.
+    ///         BLS key with proof of possession can be generated by indy_create_bls_key.
     ///     client_ip: string - (Optional) Node's client listener IP address.
     ///     client_port: string - (Optional) Node's client listener port.
     ///     node_ip: string - (Optional) The IP address other Nodes use to communicate with this Node.
@@ -967,6 +1091,8 @@ extern "C" {
     ///     1) based on "seqNo" - sender knows the sequence number of transaction that he consider as a fresh enough.
     ///     2) based on "txnTime" - sender knows the timestamp that he consider as a fresh enough.
     ///
+    /// For write responses "seqNo", "txnTime" and "txnId" can be persisted as ledger anchors of the written transaction.
+    ///
     /// Note: response of GET_VALIDATOR_INFO request isn't supported
     ///
     ///
@@ -981,7 +1107,8 @@ extern "C" {
     ///     "seqNo": Option<u64> - transaction sequence number,
     ///     "txnTime": Option<u64> - transaction ordering time,
     ///     "lastSeqNo": Option<u64> - the latest transaction seqNo for particular Node,
-    ///     "lastTxnTime": Option<u64> - the latest transaction ordering time for particular Node
+    ///     "lastTxnTime": Option<u64> - the latest transaction ordering time for particular Node,
+    ///     "txnId": Option<string> - identifier of the written transaction (for example: schema or credential definition id)
     /// }
     ///
     /// #Errors
@@ -1285,6 +1412,141 @@ extern "C" {
                                                                                           const char*   request_json)
                                                                     );
 
+    /// Gets the latest Transaction Author Agreement and Acceptance Mechanisms set on the ledger
+    /// by sending GET_TXN_AUTHR_AGRMT and GET_TXN_AUTHR_AGRMT_AML requests.
+    ///
+    /// If digest of the previously accepted agreement is passed and it differs from the latest one,
+    /// the accepted agreement is checked as well. Once the accepted agreement is retired the ledger rejects
+    /// requests with its acceptance, so `LedgerTxnAuthorAgreementRetired` error is returned and the returned
+    /// active agreement must be accepted again.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// accepted_taa_digest: (Optional) digest of the accepted agreement to check for retirement.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// {
+    ///     "taa": { - the latest agreement or null if it isn't set
+    ///         "text": string,
+    ///         "version": string,
+    ///         "digest": string,
+    ///         "ratification_ts": int,
+    ///         "retirement_ts": int (optional)
+    ///     },
+    ///     "aml": { - the latest acceptance mechanisms or null if they aren't set
+    ///         "aml": {"<acceptance mechanism label>": "<acceptance mechanism description>"},
+    ///         "version": string,
+    ///         "amlContext": string (optional)
+    ///     }
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    extern indy_error_t indy_get_active_txn_author_agreement(indy_handle_t command_handle,
+                                                             indy_handle_t pool_handle,
+                                                             const char *  accepted_taa_digest,
+                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                  indy_error_t  err,
+                                                                                  const char*   active_taa_json)
+                                                             );
+
+    /// Checks whether Credential Definition published on the ledger can be used by the local anoncreds implementation.
+    /// Credential Definition is fetched by sending GET_CRED_DEF request and its signature type and public keys are verified.
+    /// It allows to find artifacts produced by incompatible or newer stacks before they break credential issuance or verification.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// cred_def_id: id of Credential Definition to check.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// {
+    ///     "cred_def_id": string,
+    ///     "compatible": bool, - whether Credential Definition can be used by the local anoncreds
+    ///     "signature_type": string, (optional)
+    ///     "primary_key_bits": int, - size of the primary public key modulus (optional)
+    ///     "revocation_supported": bool, (optional)
+    ///     "issues": [string] - descriptions of found incompatibilities
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    extern indy_error_t indy_check_cred_def_compatibility(indy_handle_t command_handle,
+                                                          indy_handle_t pool_handle,
+                                                          const char *  cred_def_id,
+                                                          void           (*cb)(indy_handle_t command_handle_,
+                                                                               indy_error_t  err,
+                                                                               const char*   report_json)
+                                                          );
+
+    /// Builds receipt of the written transaction from the reply to the write request.
+    /// Receipt can be persisted by the application and used later to prove that the transaction
+    /// has been accepted by the ledger (look at `indy_verify_write_receipt`).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// response: reply to the write request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// {
+    ///     "ledgerId": int - id of the ledger the transaction is written to (taken from the reply),
+    ///     "seqNo": int - sequence number of the transaction,
+    ///     "txnTime": int - (optional) transaction ordering time,
+    ///     "digest": string - (optional) digest of the request,
+    ///     "data": json - the transaction as stored on the ledger (`txn`, `txnMetadata`, `reqSignature`, `ver`),
+    ///     "rootHash": string - merkle tree root hash of the ledger,
+    ///     "auditPath": [string] - audit path from the transaction to the root hash,
+    ///     "ledgerSize": int - size of the ledger the root hash is calculated for,
+    ///     "multiSignature": json - BLS multi-signature of the nodes over the root hash
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    extern indy_error_t indy_get_write_receipt(indy_handle_t command_handle,
+                                               const char *  response,
+                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                    indy_error_t  err,
+                                                                    const char*   receipt_json)
+                                               );
+
+    /// Verifies receipt of the written transaction (created by `indy_get_write_receipt`).
+    ///
+    /// The receipt is verified without requests to the ledger: the audit path must lead from the transaction
+    /// to the root hash and the root hash must be signed by BLS multi-signature of enough nodes of the pool
+    /// (BLS keys of the nodes are taken from the pool ledger cached by `indy_open_pool_ledger`).
+    /// The write reply must contain the multi-signature over its root hash, otherwise `indy_get_write_receipt` fails.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// receipt_json: write receipt json.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true if the transaction of the receipt is written to the ledger
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    extern indy_error_t indy_verify_write_receipt(indy_handle_t command_handle,
+                                                  indy_handle_t pool_handle,
+                                                  const char *  receipt_json,
+                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                       indy_error_t  err,
+                                                                       indy_bool_t   valid)
+                                                  );
+
     /// Append transaction author agreement acceptance data to a request.
     /// This function should be called before signing and sending a request
     /// if there is any transaction author agreement set on the Ledger.
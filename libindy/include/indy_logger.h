@@ -15,7 +15,10 @@ extern "C" {
     /// #Params
     /// context: pointer to some logger context that will be available in logger handlers.
     /// enabled: (optional) "enabled" operation handler - calls to determines if a log record would be logged. (false positive if not specified)
-    /// log: "log" operation handler - calls to logs a record.
+    /// log: "log" operation handler - calls to logs a record. Besides the record it receives the context of libindy command
+    ///     that produced the record (handlers declared without these trailing params keep working):
+    ///     command: (optional) name of the commands group (Anoncreds, Did, Ledger, Wallet and etc) the record was produced by.
+    ///     correlation_id: identifier of the command the record was produced by (the same for all records of one command, 0 if absent).
     /// flush: (optional) "flush" operation handler - calls to flushes buffered records (in case of crash or signal).
     ///
     /// #Returns
@@ -31,7 +34,9 @@ extern "C" {
                                                       const char* message,
                                                       const char* module_path,
                                                       const char* file,
-                                                      indy_u32_t line),
+                                                      indy_u32_t line,
+                                                      const char* command,
+                                                      indy_i32_t correlation_id),
                                         void (*flushFn)(const void*  context)
                                                   );
 
@@ -57,7 +62,7 @@ extern "C" {
     /// #Params
     /// `context_p` - Reference that will contain logger context.
     /// `enabled_cb_p` - Reference that will contain pointer to enable operation handler.
-    /// `log_cb_p` - Reference that will contain pointer to log operation handler.
+    /// `log_cb_p` - Reference that will contain pointer to log operation handler (without command context params).
     /// `flush_cb_p` - Reference that will contain pointer to flush operation handler.
     ///
     /// #Returns
@@ -54,6 +54,9 @@ typedef enum
     // IO Error
     CommonIOError = 114,
 
+    // Command was cancelled by the caller or its timeout expired
+    CommonCancelled = 130,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -132,6 +135,10 @@ typedef enum
     // Item not found on ledger.
     LedgerNotFound = 309,
 
+    // Accepted Transaction Author Agreement has been retired on the ledger.
+    // The active agreement must be accepted again.
+    LedgerTxnAuthorAgreementRetired = 310,
+
     // Revocation registry is full and creation of new registry is necessary
     AnoncredsRevocationRegistryFullError = 400,
 
@@ -147,6 +154,9 @@ typedef enum
     // Attempt to create credential definition with duplicated did schema pair
     AnoncredsCredDefAlreadyExistsError = 407,
 
+    // Attempt to delete master secret referenced by stored credentials
+    AnoncredsMasterSecretInUseError = 408,
+
     // Crypto errors
     // Unknown format of DID entity keys
     UnknownCryptoTypeError = 500,
@@ -182,16 +192,91 @@ extern "C" {
     ///
     /// #Params
     /// config: {
-    ///     "crypto_thread_pool_size": Optional<int> - size of thread pool for the most expensive crypto operations. (4 by default)
+    ///     "crypto_thread_pool_size": Optional<int> - size of thread pool for the most expensive crypto operations
+    ///         like proof generation and signing. (4 by default)
     ///     "collect_backtrace": Optional<bool> - whether errors backtrace should be collected.
     ///         Capturing of backtrace can affect library performance.
     ///         NOTE: must be set before invocation of any other API functions.
+    ///     "cred_def_key_cache_size": Optional<int> - how many credential definition public keys verifier keeps built
+    ///         between proof verifications. Keys are cached by credential definition id and key data. (0 - disabled by default)
+    ///     "cred_def_key_cache_ttl": Optional<int> - time in seconds after which cached key is built again. (no expiration by default)
+    ///     "command_timeout": Optional<int> - time in milliseconds after which cancellable commands (see `indy_cancel_command`)
+    ///         are cancelled if their result isn't ready. (0 - disabled by default)
+    ///     "enforce_canonical_attr_encoding": Optional<bool> - whether encoded values of credential attributes must match
+    ///         canonical encoding of raw values (see `indy_encode_attribute_value`) on credential issuance and proof verification.
+    ///         (false by default)
+    ///     "strict_schema_attr_names": Optional<bool> - whether `indy_issuer_create_schema` rejects attribute names which are empty,
+    ///         contain characters other than letters, digits, spaces, `_`, `-` and `.` or are duplicates ignoring case and spaces
+    ///         (see `indy_issuer_validate_schema_attributes`). Set to false to create schemas violating the rules (e.g. existing ones).
+    ///         (true by default)
     /// }
     ///
     /// #Errors
     /// Common*
     extern indy_error_t indy_set_runtime_config(const char * config);
 
+    /// Set listener of libindy security events.
+    ///
+    /// Listener is called for events detected while processing of other commands, for example
+    /// when NYM of DID stored in the wallet appears changed on the ledger not through this wallet
+    /// or when wallet handle becomes invalid.
+    ///
+    /// #Params
+    /// context: pointer to some listener context that will be passed to the listener.
+    /// cb: (optional) listener. Previously set listener is removed if not specified.
+    ///     event_json: {
+    ///         "type": "did_nym_changed",
+    ///         "did": string - DID stored in the wallet,
+    ///         "wallet_verkey": string - verkey of DID stored in the wallet,
+    ///         "previous_verkey": Optional<string> - verkey observed on the ledger before,
+    ///         "previous_role": Optional<string> - role observed on the ledger before,
+    ///         "verkey": string - current verkey on the ledger,
+    ///         "role": Optional<string> - current role on the ledger
+    ///     }
+    ///     OR
+    ///     event_json: {
+    ///         "type": "wallet_invalidated",
+    ///         "wallet_handle": int - handle of the wallet that must not be used anymore,
+    ///         "reason": string - why the handle has been invalidated: "closed" - by indy_close_wallet,
+    ///                   "timeout" - wallet hasn't been used for "idle_timeout" of its config,
+    ///                   "storage_failure" - wallet storage failed
+    ///     }
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_set_event_listener(const void* context,
+                                                void (*cb)(const void* context,
+                                                           const char* event_json));
+
+    /// Cancels the command started with the given cancel handle.
+    ///
+    /// Callback of the cancelled command is called with `CommonCancelled` error. Operation which is already
+    /// in progress isn't interrupted, but its result is dropped. Cancellation is supported by
+    /// `indy_open_pool_ledger`, `indy_submit_request`, `indy_submit_action` and `indy_prover_create_proof` for now.
+    /// The commands are also cancelled automatically when "command_timeout" set by `indy_set_runtime_config` expires.
+    ///
+    /// #Params
+    /// cancel_handle: handle returned by `indy_get_current_cancel_handle` after the function starting the command.
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_cancel_command(indy_handle_t cancel_handle);
+
+    /// Get cancel handle of the last cancellable command started in the current thread.
+    ///
+    /// This function should be called right after the successful call of the function starting the command
+    /// (see `indy_cancel_command`) in the same application thread. Cancel handles are generated by libindy,
+    /// so commands started with the same command handle can be cancelled separately.
+    ///
+    /// NOTE: Handle is stored until the next cancellable command is started in the same execution thread.
+    ///
+    /// #Params
+    /// * `cancel_handle_p` - Reference that will contain cancel handle (0 if no cancellable command was started)
+    ///
+    /// #Returns
+    /// Error code
+    extern indy_error_t indy_get_current_cancel_handle(indy_handle_t* cancel_handle_p);
+
     /// Get details for last occurred error.
     ///
     /// This function should be called in two places to handle both cases of error occurrence:
@@ -98,7 +98,8 @@ extern "C" {
     ///   usage of this tag in complex search queries (comparison, predicates)
     ///   Encrypted tags can be searched only for exact matching
     ///   Note if some from provided tags already assigned to the record than
-    ///     corresponding tags values will be replaced
+    ///     corresponding tags values will be replaced.
+    ///   Tag changing from encrypted to un-encrypted (or vice versa) replaces the tag stored the other way.
 
     extern indy_error_t indy_add_wallet_record_tags(indy_handle_t  command_handle,
                                                     indy_handle_t  wallet_handle,
@@ -144,6 +145,52 @@ extern "C" {
                                                                        indy_error_t err)
                                                  );
 
+    /// Create many non-secret records of the same type in the wallet at once.
+    /// Records are added with all-or-nothing semantics: if one of them can't be added none of them are.
+    /// Records are written in a single storage transaction, so it is much faster than adding them one by one.
+    /// Plugged storages have no transactions, so records added before the failure are deleted back at best effort.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: allows to separate different record types collections
+    /// records_json: the list of records to add as json array:
+    ///   [
+    ///     {
+    ///       "id": string, the id of record
+    ///       "value": string, the value of record
+    ///       "tags": optional<object>, the record tags (see indy_add_wallet_record)
+    ///     }
+    ///   ]
+
+    extern indy_error_t indy_add_wallet_records_batch(indy_handle_t  command_handle,
+                                                      indy_handle_t  wallet_handle,
+                                                      const char*    type_,
+                                                      const char*    records_json,
+                                                      void           (*fn)(indy_handle_t command_handle_,
+                                                                           indy_error_t err)
+                                                     );
+
+    /// Delete many existing wallet records of the same type at once.
+    /// Records are deleted with all-or-nothing semantics: if one of them can't be deleted none of them are.
+    /// Records are deleted in a single storage transaction, so it is much faster than deleting them one by one.
+    /// Plugged storages have no transactions, so records deleted before the failure are added back at best effort.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: record type
+    /// ids_json: the list of ids of records to delete as json array:
+    ///   ["id1", "id2", ...]
+
+    extern indy_error_t indy_delete_wallet_records_batch(indy_handle_t  command_handle,
+                                                         indy_handle_t  wallet_handle,
+                                                         const char*    type_,
+                                                         const char*    ids_json,
+                                                         void           (*fn)(indy_handle_t command_handle_,
+                                                                              indy_error_t err)
+                                                        );
+
     /// Get an wallet record by id
     ///
     /// #Params
@@ -190,9 +237,11 @@ extern "C" {
     ///    "tagName": "tagValue",
     ///    $or: {
     ///      "tagName2": { $regex: 'pattern' },
-    ///      "tagName3": { $gte: '123' },
+    ///      "~tagName3": { $gte: '123' },
     ///    },
     ///  }
+    ///   Comparison operators ($gt, $gte, $lt, $lte) and $like are allowed only for
+    ///   un-encrypted tags (tag name starts with "~").
     /// options_json: //TODO: FIXME: Think about replacing by bitmask
     ///  {
     ///    retrieveRecords: (optional, true by default) If false only "counts" will be calculated,
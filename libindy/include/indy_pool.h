@@ -43,6 +43,28 @@ extern "C" {
                                                   indy_u64_t    protocol_version,
                                                   void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
                                                   );
+
+    extern indy_error_t indy_build_pool_genesis_txns(indy_handle_t command_handle,
+                                                     const char *  nodes_json,
+                                                     void          (*cb)(indy_handle_t command_handle_, indy_error_t err, const char *const genesis_txns)
+                                                     );
+
+    extern indy_error_t indy_set_pool_traffic_capture(indy_handle_t command_handle,
+                                                      indy_handle_t handle,
+                                                      const char *  config,
+                                                      void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
+                                                      );
+
+    extern indy_error_t indy_set_pool_for_namespace(indy_handle_t command_handle,
+                                                    const char *  namespace_,
+                                                    indy_handle_t handle,
+                                                    void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
+                                                    );
+
+    extern indy_error_t indy_remove_pool_for_namespace(indy_handle_t command_handle,
+                                                       const char *  namespace_,
+                                                       void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
+                                                       );
 #ifdef __cplusplus
 }
 #endif
@@ -144,6 +144,30 @@ extern "C" {
                                                   void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                                   );
 
+    /// Register compaction handler for custom wallet storage registered with indy_register_wallet_storage.
+    /// indy_compact_wallet and scheduled compaction call it for storages opened after the registration.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Storage type name.
+    /// compact: WalletType compact operation handler
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// WalletUnknownTypeError
+
+    extern indy_error_t indy_register_wallet_storage_compaction(indy_handle_t  command_handle,
+                                                                const char* type_,
+
+                                                                indy_error_t (*compactFn)(indy_handle_t handle,
+                                                                                          indy_u64_t* reclaimed_bytes),
+
+                                                                void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                                );
+
     /// Create a new secure wallet.
     ///
     /// #Params
@@ -162,6 +186,8 @@ extern "C" {
     ///             Defaults to $HOME/.indy_client/wallet.
     ///             Wallet will be stored in the file {path}/{id}/sqlite.db
     ///   }
+    ///   "profile": optional<string>, Id of the logically isolated wallet inside the storage.
+    ///              Allows one storage to host many wallets, each with its own keys. Can't contain NUL character.
     /// }
     /// credentials: Wallet credentials json
     /// {
@@ -175,6 +201,10 @@ extern "C" {
     ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
     ///                          RAW - raw wallet key master provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///   "key_derivation_params": optional<object> Custom limits of ARGON2I_MOD/ARGON2I_INT key derivation.
+    ///                          Can be calculated for the current hardware with indy_benchmark_wallet_key_derivation call:
+    ///                          {"ops_limit": int, "mem_limit": int}. Stored in the wallet metadata, so not needed to open the wallet.
+    ///                          Must be at least ARGON2I_MOD limits.
     /// }
     ///
     /// #Returns
@@ -210,6 +240,18 @@ extern "C" {
     ///                      Defaults to $HOME/.indy_client/wallet.
     ///                      Wallet will be stored in the file {path}/{id}/sqlite.db
     ///           }
+    ///       "profile": optional<string>, Id of the logically isolated wallet inside the storage.
+    ///                  Allows one storage to host many wallets, each with its own keys. Can't contain NUL character.
+    ///       "compression_threshold": optional<int>, Values of records longer than this number of bytes
+    ///                  are compressed (zstd) before encryption. Compression is disabled by default.
+    ///                  Ignored if libindy is built without "wallet_compression" feature.
+    ///       "slow_query_threshold": optional<int>, Storage operations taking longer than this number of milliseconds
+    ///                  are kept in the slow query log of the opened wallet (see indy_get_wallet_slow_query_log).
+    ///                  The log is disabled by default.
+    ///       "compaction_interval": optional<int>, Storage is compacted while libindy is idle if records were deleted and this number of seconds
+    ///                  has passed since the last compaction (see indy_compact_wallet). Disabled by default.
+    ///       "idle_timeout": optional<int>, Wallet is closed if it hasn't been used for this number of seconds.
+    ///                  Closing is reported by "wallet_invalidated" event (see indy_set_event_listener). Disabled by default.
     ///
     ///   }
     /// credentials: Wallet credentials json
@@ -230,6 +272,8 @@ extern "C" {
     ///                          ARGON2I_INT - derive secured wallet master rekey (less secured but faster)
     ///                          RAW - raw wallet key master provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///       "rekey_derivation_params": optional<object> Custom limits of ARGON2I_MOD/ARGON2I_INT rekey derivation:
+    ///                          {"ops_limit": int, "mem_limit": int}. Can be calculated with indy_benchmark_wallet_key_derivation call.
     ///   }
     ///
     /// #Returns
@@ -260,6 +304,8 @@ extern "C" {
     ///                              ARGON2I_INT - derive secured export key (less secured but faster)
     ///                              RAW - raw export key provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///     "key_derivation_params": optional<object> Custom limits of export key derivation (written to the export header):
+    ///                              {"ops_limit": int, "mem_limit": int}. Must be at least ARGON2I_MOD limits.
     ///   }
     ///
     /// #Returns
@@ -296,6 +342,11 @@ extern "C" {
     ///             Defaults to $HOME/.indy_client/wallet.
     ///             Wallet will be stored in the file {path}/{id}/sqlite.db
     ///   }
+    ///   "profile": optional<string>, Id of the logically isolated wallet inside the storage.
+    ///              Allows one storage to host many wallets, each with its own keys. Can't contain NUL character.
+    ///   "compression_threshold": optional<int>, Values of records longer than this number of bytes
+    ///              are compressed (zstd) before encryption. Compression is disabled by default.
+    ///              Ignored if libindy is built without "wallet_compression" feature.
     /// }
     /// credentials: Wallet credentials json
     /// {
@@ -365,6 +416,8 @@ extern "C" {
     ///             Defaults to $HOME/.indy_client/wallet.
     ///             Wallet will be stored in the file {path}/{id}/sqlite.db
     ///   }
+    ///   "profile": optional<string>, Id of the logically isolated wallet inside the storage.
+    ///              Allows one storage to host many wallets, each with its own keys. Can't contain NUL character.
     /// }
     /// credentials: Wallet credentials json
     /// {
@@ -418,6 +471,168 @@ extern "C" {
                                                                          const char *const key)
                                                 );
 
+    /// Verifies that records of opened wallet are encrypted at rest.
+    /// Reads raw records from the wallet storage and checks that ids, types, values and tags
+    /// are stored as ciphertext only: none of decrypted values may appear in the stored bytes
+    /// (plaintext tags "~" are excluded from the check by design). Values shorter than 8 bytes aren't looked for
+    /// as they can occur in ciphertext by chance.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// config: (optional) check configuration json.
+    /// {
+    ///   "sample_size": int, (optional) Maximum number of records to check (all records are checked by default).
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// report_json: report of the check
+    /// {
+    ///   "checkedRecords": int, number of checked records
+    ///   "plaintextTags": int, number of plaintext tags skipped by the check
+    ///   "encrypted": bool, true if no violation was found
+    ///   "violations": [{
+    ///       "record": int, index of the record in the checked sample
+    ///       "field": string, one of "record", "id", "type", "value", "tagName", "tagValue"
+    ///       "reason": string, description of the violation
+    ///   }]
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_verify_wallet_storage_encryption(indy_handle_t     command_handle,
+                                                              indy_handle_t     wallet_handle,
+                                                              const char *const config,
+
+                                                              void              (*cb)(indy_handle_t     command_handle,
+                                                                                      indy_error_t      err,
+                                                                                      const char *const report_json)
+                                                             );
+
+    /// Returns the latest storage operations of opened wallet that took longer than
+    /// "slow_query_threshold" set in the wallet config on opening.
+    /// Up to 100 latest operations are kept, the log is empty if the threshold wasn't set.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// slow_query_log_json: list of slow storage operations from the oldest to the latest
+    /// [{
+    ///   "operation": string, one of "add", "update", "get", "delete", "add_tags", "update_tags", "delete_tags", "search", "get_all", "compact"
+    ///   "type": optional<string>, type of the records
+    ///   "durationMs": int, duration of the operation in milliseconds
+    ///   "rows": optional<int>, number of affected or found records if known
+    ///   "timestamp": int, unix time in milliseconds when the operation was finished
+    /// }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_get_wallet_slow_query_log(indy_handle_t     command_handle,
+                                                       indy_handle_t     wallet_handle,
+
+                                                       void              (*cb)(indy_handle_t     command_handle,
+                                                                               indy_error_t      err,
+                                                                               const char *const slow_query_log_json)
+                                                      );
+
+    /// Compacts the storage of opened wallet reclaiming the space left by deleted records.
+    /// It may take a long time for big wallets, the wallet can't be used meanwhile.
+    ///
+    /// Note that the whole storage is compacted if it hosts many profiles.
+    /// Plugged storages are compacted by the handler registered with indy_register_wallet_storage_compaction,
+    /// storages without the handler report zero reclaimed bytes.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// compaction_report_json:
+    /// {
+    ///   "reclaimedBytes": int, number of bytes reclaimed by the compaction
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_compact_wallet(indy_handle_t     command_handle,
+                                            indy_handle_t     wallet_handle,
+
+                                            void              (*cb)(indy_handle_t     command_handle,
+                                                                    indy_error_t      err,
+                                                                    const char *const compaction_report_json)
+                                           );
+
+    /// Lists wallet objects which expire within the given time window.
+    /// Expiration of keys and DIDs is read from their metadata set as json with numeric "expires" field,
+    /// expiration of credentials is read from their "expires" attribute.
+    /// Already expired objects are listed as well.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// within_secs: size of the time window in seconds starting from now.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// objects_json: list of expiring objects ordered by expiration time
+    /// [{
+    ///     "type": string, one of "key", "did", "credential"
+    ///     "id": string, verkey, DID or credential referent
+    ///     "expires": int, expiration time as unix timestamp in seconds
+    /// }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_list_expiring_wallet_objects(indy_handle_t      command_handle,
+                                                          indy_handle_t      wallet_handle,
+                                                          indy_u64_t         within_secs,
+
+                                                          void               (*cb)(indy_handle_t     command_handle,
+                                                                                   indy_error_t      err,
+                                                                                   const char *const objects_json)
+                                                         );
+
+    /// Calculates limits of wallet key derivation suitable for the current hardware.
+    /// Argon2i operations limit is adjusted so that derivation of wallet master key takes about `target_ms`.
+    /// Result is never below ARGON2I_MOD limits, so slower hardware gets ARGON2I_MOD limits.
+    /// Result can be passed as "key_derivation_params" and "rekey_derivation_params" of wallet credentials.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// target_ms: desired duration of wallet key derivation in milliseconds.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// params_json: key derivation params
+    /// {
+    ///     "ops_limit": int, Argon2i operations limit
+    ///     "mem_limit": int, Argon2i memory limit in bytes
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_benchmark_wallet_key_derivation(indy_handle_t      command_handle,
+                                                             indy_u64_t         target_ms,
+
+                                                             void               (*cb)(indy_handle_t     command_handle,
+                                                                                      indy_error_t      err,
+                                                                                      const char *const params_json)
+                                                            );
+
 #ifdef __cplusplus
 }
 #endif
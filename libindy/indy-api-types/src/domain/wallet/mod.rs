@@ -8,6 +8,16 @@ pub struct Config {
     pub id: String,
     pub storage_type: Option<String>,
     pub storage_config: Option<Value>,
+    /// Id of the logically isolated wallet inside the storage, so one storage can host many wallets.
+    pub profile: Option<String>,
+    /// Values of records longer than this number of bytes are compressed before encryption.
+    pub compression_threshold: Option<usize>,
+    /// Storage operations taking longer than this number of milliseconds are kept in the slow query log.
+    pub slow_query_threshold: Option<u64>,
+    /// Storage is compacted while libindy is idle if records were deleted and this number of seconds has passed since the last compaction.
+    pub compaction_interval: Option<u64>,
+    /// Wallet is closed if it hasn't been used for this number of seconds.
+    pub idle_timeout: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -18,7 +28,10 @@ pub struct Credentials {
     #[serde(default = "default_key_derivation_method")]
     pub key_derivation_method: KeyDerivationMethod,
     #[serde(default = "default_key_derivation_method")]
-    pub rekey_derivation_method: KeyDerivationMethod
+    pub rekey_derivation_method: KeyDerivationMethod,
+    /// Custom Argon2i params used instead of the ones defined by `key_derivation_method`.
+    pub key_derivation_params: Option<KeyDerivationParams>,
+    pub rekey_derivation_params: Option<KeyDerivationParams>,
 }
 
 #[allow(non_camel_case_types)]
@@ -29,6 +42,13 @@ pub enum KeyDerivationMethod {
     ARGON2I_INT
 }
 
+/// Argon2i params, for example the ones recommended by key derivation benchmark for the device.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct KeyDerivationParams {
+    pub ops_limit: u64,
+    pub mem_limit: u64,
+}
+
 fn default_key_derivation_method() -> KeyDerivationMethod {
     KeyDerivationMethod::ARGON2I_MOD
 }
@@ -38,7 +58,9 @@ pub struct ExportConfig {
     pub key: String,
     pub path: String,
     #[serde(default = "default_key_derivation_method")]
-    pub key_derivation_method: KeyDerivationMethod
+    pub key_derivation_method: KeyDerivationMethod,
+    /// Custom Argon2i params used instead of the ones defined by `key_derivation_method`.
+    pub key_derivation_params: Option<KeyDerivationParams>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,6 +68,11 @@ pub struct KeyConfig {
     pub seed: Option<String>
 }
 
+#[derive(Debug, Deserialize)]
+pub struct StorageEncryptionCheckConfig {
+    pub sample_size: Option<usize>
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Record {
     // Wallet record type
@@ -61,11 +88,22 @@ pub struct Record {
 
 pub type Tags = HashMap<String, String>;
 
+/// Record added by `indy_add_wallet_records_batch`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchRecord {
+    pub id: String,
+    pub value: String,
+    pub tags: Option<Tags>,
+}
+
 impl Validatable for Config {
     fn validate(&self) -> Result<(), String> {
         if self.id.is_empty() {
             return Err("Wallet id is empty".to_string());
         }
+        if self.profile.as_ref().map(String::is_empty).unwrap_or(false) {
+            return Err("Wallet profile is empty".to_string());
+        }
         Ok(())
     }
 }
@@ -33,6 +33,8 @@ pub enum IndyErrorKind {
     InvalidParam(u32),
     #[fail(display = "IO error")]
     IOError,
+    #[fail(display = "Command cancelled")]
+    Cancelled,
     // Anoncreds errors
     #[fail(display = "Duplicated master secret")]
     MasterSecretDuplicateName,
@@ -46,6 +48,8 @@ pub enum IndyErrorKind {
     CredentialRevoked,
     #[fail(display = "Credential definition already exists")]
     CredDefAlreadyExists,
+    #[fail(display = "Master secret is in use")]
+    MasterSecretInUse,
     // Ledger errors
     #[fail(display = "No consensus")]
     NoConsensus,
@@ -53,6 +57,8 @@ pub enum IndyErrorKind {
     InvalidTransaction,
     #[fail(display = "Item not found on ledger")]
     LedgerItemNotFound,
+    #[fail(display = "Transaction Author Agreement retired")]
+    TxnAuthorAgreementRetired,
     // Pool errors
     #[fail(display = "Pool not created")]
     PoolNotCreated,
@@ -330,15 +336,18 @@ impl From<IndyErrorKind> for ErrorCode {
                     _ => ErrorCode::CommonInvalidState
                 },
             IndyErrorKind::IOError => ErrorCode::CommonIOError,
+            IndyErrorKind::Cancelled => ErrorCode::CommonCancelled,
             IndyErrorKind::MasterSecretDuplicateName => ErrorCode::AnoncredsMasterSecretDuplicateNameError,
             IndyErrorKind::ProofRejected => ErrorCode::AnoncredsProofRejected,
             IndyErrorKind::RevocationRegistryFull => ErrorCode::AnoncredsRevocationRegistryFullError,
             IndyErrorKind::InvalidUserRevocId => ErrorCode::AnoncredsInvalidUserRevocId,
             IndyErrorKind::CredentialRevoked => ErrorCode::AnoncredsCredentialRevoked,
             IndyErrorKind::CredDefAlreadyExists => ErrorCode::AnoncredsCredDefAlreadyExistsError,
+            IndyErrorKind::MasterSecretInUse => ErrorCode::AnoncredsMasterSecretInUseError,
             IndyErrorKind::NoConsensus => ErrorCode::LedgerNoConsensusError,
             IndyErrorKind::InvalidTransaction => ErrorCode::LedgerInvalidTransaction,
             IndyErrorKind::LedgerItemNotFound => ErrorCode::LedgerNotFound,
+            IndyErrorKind::TxnAuthorAgreementRetired => ErrorCode::LedgerTxnAuthorAgreementRetired,
             IndyErrorKind::PoolNotCreated => ErrorCode::PoolLedgerNotCreatedError,
             IndyErrorKind::InvalidPoolHandle => ErrorCode::PoolLedgerInvalidPoolHandle,
             IndyErrorKind::PoolTerminated => ErrorCode::PoolLedgerTerminated,
@@ -420,15 +429,18 @@ impl From<ErrorCode> for IndyErrorKind {
             ErrorCode::CommonInvalidParam26 => IndyErrorKind::InvalidParam(26),
             ErrorCode::CommonInvalidParam27 => IndyErrorKind::InvalidParam(27),
             ErrorCode::CommonIOError => IndyErrorKind::IOError,
+            ErrorCode::CommonCancelled => IndyErrorKind::Cancelled,
             ErrorCode::AnoncredsMasterSecretDuplicateNameError => IndyErrorKind::MasterSecretDuplicateName,
             ErrorCode::AnoncredsProofRejected => IndyErrorKind::ProofRejected,
             ErrorCode::AnoncredsRevocationRegistryFullError => IndyErrorKind::RevocationRegistryFull,
             ErrorCode::AnoncredsInvalidUserRevocId => IndyErrorKind::InvalidUserRevocId,
             ErrorCode::AnoncredsCredentialRevoked => IndyErrorKind::CredentialRevoked,
             ErrorCode::AnoncredsCredDefAlreadyExistsError => IndyErrorKind::CredDefAlreadyExists,
+            ErrorCode::AnoncredsMasterSecretInUseError => IndyErrorKind::MasterSecretInUse,
             ErrorCode::LedgerNoConsensusError => IndyErrorKind::NoConsensus,
             ErrorCode::LedgerInvalidTransaction => IndyErrorKind::InvalidTransaction,
             ErrorCode::LedgerNotFound => IndyErrorKind::LedgerItemNotFound,
+            ErrorCode::LedgerTxnAuthorAgreementRetired => IndyErrorKind::TxnAuthorAgreementRetired,
             ErrorCode::PoolLedgerNotCreatedError => IndyErrorKind::PoolNotCreated,
             ErrorCode::PoolLedgerInvalidPoolHandle => IndyErrorKind::InvalidPoolHandle,
             ErrorCode::PoolLedgerTerminated => IndyErrorKind::PoolTerminated,
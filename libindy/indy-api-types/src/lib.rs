@@ -144,6 +144,9 @@ pub enum ErrorCode
     // Caller passed invalid value as param 27 (null, invalid json and etc..)
     CommonInvalidParam27 = 129,
 
+    // Command was cancelled by the caller or its timeout expired
+    CommonCancelled = 130,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -222,6 +225,10 @@ pub enum ErrorCode
     // Item not found on ledger.
     LedgerNotFound = 309,
 
+    // Accepted Transaction Author Agreement has been retired on the ledger.
+    // The active agreement must be accepted again.
+    LedgerTxnAuthorAgreementRetired = 310,
+
     // Revocation registry is full and creation of new registry is necessary
     AnoncredsRevocationRegistryFullError = 400,
 
@@ -237,6 +244,9 @@ pub enum ErrorCode
     // Attempt to create credential definition with duplicated id
     AnoncredsCredDefAlreadyExistsError = 407,
 
+    // Attempt to delete master secret referenced by stored credentials
+    AnoncredsMasterSecretInUseError = 408,
+
     // Crypto errors
     // Unknown format of DID entity keys
     UnknownCryptoTypeError = 500,
@@ -528,7 +538,7 @@ pub mod wallet {
     ///    "tagName": "tagValue",
     ///    $or: {
     ///      "tagName2": { $regex: 'pattern' },
-    ///      "tagName3": { $gte: 123 },
+    ///      "~tagName3": { $gte: 123 },
     ///    },
     ///  }
     /// options_json: //TODO: FIXME: Think about replacing by bitmask
@@ -586,4 +596,14 @@ pub mod wallet {
     pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                           search_handle: i32) -> ErrorCode;
 
+    /// Reclaims space left by deleted records (e.g. vacuums and reindexes the storage tables)
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    ///
+    /// #Returns
+    /// reclaimed_bytes_p: number of bytes reclaimed by the compaction
+    pub type WalletCompact = extern fn(storage_handle: StorageHandle,
+                                       reclaimed_bytes_p: *mut u64) -> ErrorCode;
+
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 extern crate sodiumoxide;
 
-use indy_api_types::domain::wallet::KeyDerivationMethod;
+use indy_api_types::domain::wallet::{KeyDerivationMethod, KeyDerivationParams};
 use indy_api_types::errors::prelude::*;
 use self::sodiumoxide::crypto::aead::chacha20poly1305_ietf;
 use self::sodiumoxide::utils;
@@ -36,6 +36,15 @@ pub fn derive_key(passphrase: &str, salt: &pwhash_argon2i13::Salt, key_derivatio
     Ok(Key::new(key_bytes))
 }
 
+pub fn derive_key_with_params(passphrase: &str, salt: &pwhash_argon2i13::Salt, params: &KeyDerivationParams) -> Result<Key, IndyError> {
+    let mut key_bytes = [0u8; chacha20poly1305_ietf::KEYBYTES];
+
+    pwhash_argon2i13::pwhash_with_limits(&mut key_bytes, passphrase.as_bytes(), salt, params.ops_limit, params.mem_limit)
+        .map_err(|err| err.extend("Can't derive key"))?;
+
+    Ok(Key::new(key_bytes))
+}
+
 pub fn gen_nonce() -> Nonce {
     Nonce(chacha20poly1305_ietf::gen_nonce())
 }
@@ -56,6 +65,12 @@ pub fn gen_nonce_and_encrypt(data: &[u8], key: &Key) -> (Vec<u8>, Nonce) {
 pub fn gen_nonce_and_encrypt_detached(data: &[u8], aad: &[u8], key: &Key) -> (Vec<u8>, Nonce, Tag) {
     let nonce = gen_nonce();
 
+    let (encrypted_data, tag) = encrypt_detached(data, aad, key, &nonce);
+
+    (encrypted_data, nonce, tag)
+}
+
+pub fn encrypt_detached(data: &[u8], aad: &[u8], key: &Key, nonce: &Nonce) -> (Vec<u8>, Tag) {
     let mut plain = data.to_vec();
     let tag = chacha20poly1305_ietf::seal_detached(
         plain.as_mut_slice(),
@@ -64,7 +79,7 @@ pub fn gen_nonce_and_encrypt_detached(data: &[u8], aad: &[u8], key: &Key) -> (Ve
         &key.0
     );
 
-    (plain.to_vec(), nonce, Tag(tag))
+    (plain, Tag(tag))
 }
 
 
@@ -0,0 +1,164 @@
+extern crate sodiumoxide;
+
+use indy_api_types::errors::prelude::*;
+use crate::crypto::randombytes::randombytes;
+
+pub const X25519_PUBLICKEYBYTES: usize = 32;
+
+const RNG_SAMPLE_BYTES: usize = 32;
+
+// Curve25519 points of order 1, 2, 4 and 8 (including non-canonical encodings).
+// Mirrors the blacklist used by libsodium `crypto_scalarmult_curve25519`.
+const X25519_LOW_ORDER_POINTS: [[u8; X25519_PUBLICKEYBYTES]; 7] = [
+    // 0 (order 4)
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // 1 (order 1)
+    [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // 325606250916557431795983626356110631294008115727848805560023387167927233504 (order 8)
+    [0xe0, 0xeb, 0x7a, 0x7c, 0x3b, 0x41, 0xb8, 0xae, 0x16, 0x56, 0xe3, 0xfa, 0xf1, 0x9f, 0xc4, 0x6a,
+        0xda, 0x09, 0x8d, 0xeb, 0x9c, 0x32, 0xb1, 0xfd, 0x86, 0x62, 0x05, 0x16, 0x5f, 0x49, 0xb8, 0x00],
+    // 39382357235489614581723060781553021112529911719440698176882885853963445705823 (order 8)
+    [0x5f, 0x9c, 0x95, 0xbc, 0xa3, 0x50, 0x8c, 0x24, 0xb1, 0xd0, 0xb1, 0x55, 0x9c, 0x83, 0xef, 0x5b,
+        0x04, 0x44, 0x5c, 0xc4, 0x58, 0x1c, 0x8e, 0x86, 0xd8, 0x22, 0x4e, 0xdd, 0xd0, 0x9f, 0x11, 0x57],
+    // p-1 (order 2)
+    [0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f],
+    // p (=0, order 4)
+    [0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f],
+    // p+1 (=1, order 1)
+    [0xee, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f],
+];
+
+/// Compares two byte strings in time that depends only on their length.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    self::sodiumoxide::utils::memcmp(a, b)
+}
+
+/// Compares two strings (verkeys, MACs, challenges) in time that depends only on their length.
+pub fn ct_eq_str(a: &str, b: &str) -> bool {
+    ct_eq(a.as_bytes(), b.as_bytes())
+}
+
+/// Checks in constant time whether X25519 public key belongs to a small subgroup.
+pub fn is_low_order_point(pk: &[u8]) -> bool {
+    if pk.len() != X25519_PUBLICKEYBYTES {
+        return false;
+    }
+
+    let mut matches = 0u8;
+
+    for point in X25519_LOW_ORDER_POINTS.iter() {
+        let mut diff = 0u8;
+
+        for i in 0..X25519_PUBLICKEYBYTES - 1 {
+            diff |= pk[i] ^ point[i];
+        }
+        diff |= (pk[X25519_PUBLICKEYBYTES - 1] & 0x7f) ^ point[X25519_PUBLICKEYBYTES - 1];
+
+        // 1 if diff == 0, 0 otherwise
+        matches |= (((diff as u16).wrapping_sub(1)) >> 8) as u8 & 1;
+    }
+
+    matches != 0
+}
+
+/// Rejects low-order X25519 public keys before they are used in key agreement.
+///
+/// The check is enforced only when `hardened` feature is enabled.
+pub fn check_x25519_public_key(pk: &[u8]) -> IndyResult<()> {
+    if cfg!(feature = "hardened") && is_low_order_point(pk) {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Public key is a low order point"));
+    }
+
+    Ok(())
+}
+
+/// Performs a cheap health check of the default random number generator (see `check_rng_with`).
+///
+/// The check is enforced only when `hardened` feature is enabled.
+pub fn check_rng() -> IndyResult<()> {
+    check_rng_with(|len| Ok(randombytes(len)))
+}
+
+/// Performs a cheap health check of the random number generator taking samples of the given length
+/// from `sample`: two consecutive samples must differ and must not consist of a single repeated byte.
+///
+/// The check is enforced only when `hardened` feature is enabled.
+pub fn check_rng_with<F>(mut sample: F) -> IndyResult<()> where F: FnMut(usize) -> IndyResult<Vec<u8>> {
+    if !cfg!(feature = "hardened") {
+        return Ok(());
+    }
+
+    let first = sample(RNG_SAMPLE_BYTES)?;
+    let second = sample(RNG_SAMPLE_BYTES)?;
+
+    let is_stuck = |sample: &[u8]| sample.iter().all(|byte| *byte == sample[0]);
+
+    if first.len() != RNG_SAMPLE_BYTES || second.len() != RNG_SAMPLE_BYTES
+        || is_stuck(&first) || is_stuck(&second) || ct_eq(&first, &second) {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Random number generator health check failed"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ed25519_sign;
+
+    #[test]
+    fn ct_eq_works() {
+        assert!(ct_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+        assert!(ct_eq_str("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa"));
+        assert!(!ct_eq_str("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4vob"));
+    }
+
+    #[test]
+    fn is_low_order_point_works() {
+        for point in X25519_LOW_ORDER_POINTS.iter() {
+            assert!(is_low_order_point(point));
+        }
+
+        // high bit is ignored
+        let mut point = X25519_LOW_ORDER_POINTS[0];
+        point[X25519_PUBLICKEYBYTES - 1] |= 0x80;
+        assert!(is_low_order_point(&point));
+
+        let (vk, _) = ed25519_sign::create_key_pair_for_signature(None).unwrap();
+        let pk = ed25519_sign::vk_to_curve25519(&vk).unwrap();
+        assert!(!is_low_order_point(&pk[..]));
+    }
+
+    #[test]
+    fn check_x25519_public_key_works() {
+        let (vk, _) = ed25519_sign::create_key_pair_for_signature(None).unwrap();
+        let pk = ed25519_sign::vk_to_curve25519(&vk).unwrap();
+        check_x25519_public_key(&pk[..]).unwrap();
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn check_x25519_public_key_works_for_low_order_point() {
+        let res = check_x25519_public_key(&X25519_LOW_ORDER_POINTS[2]);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn check_rng_works() {
+        check_rng().unwrap();
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn check_rng_with_works_for_repeating_samples() {
+        let res = check_rng_with(|len| Ok((0..len).map(|i| i as u8).collect()));
+        assert_eq!(IndyErrorKind::InvalidState, res.unwrap_err().kind());
+    }
+}
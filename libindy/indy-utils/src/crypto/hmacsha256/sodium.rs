@@ -14,4 +14,30 @@ pub fn gen_key() -> Key {
 
 pub fn authenticate(data: &[u8], key: &Key) -> Tag {
     Tag(hmacsha256::authenticate(data, &key.0))
-}
\ No newline at end of file
+}
+
+/// HKDF-SHA256 (RFC 5869) with zero salt producing a single output block of `KEYBYTES` length.
+pub fn hkdf(ikm: &[u8], info: &[u8]) -> [u8; KEYBYTES] {
+    let salt = hmacsha256::Key([0u8; KEYBYTES]);
+    let prk = hmacsha256::authenticate(ikm, &salt);
+
+    let mut input = info.to_vec();
+    input.push(1);
+
+    hmacsha256::authenticate(&input, &hmacsha256::Key(prk.0)).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hkdf_works() {
+        // RFC 5869 Test Case 3 (first block of OKM)
+        let ikm = [0x0b; 22];
+        let expected_bytes = vec![141, 164, 231, 117, 165, 99, 193, 143, 113, 95, 128, 42, 6, 60, 90, 49,
+                                  184, 161, 31, 92, 94, 225, 135, 158, 195, 69, 78, 95, 60, 115, 141, 45];
+        let res = hkdf(&ikm, &[]);
+        assert_eq!(expected_bytes, res.to_vec());
+    }
+}
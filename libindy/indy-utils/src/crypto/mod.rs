@@ -9,6 +9,9 @@ pub mod base64;
 #[path = "chacha20poly1305_ietf/sodium.rs"]
 pub mod chacha20poly1305_ietf;
 
+#[cfg(all(feature = "randombytes_sodium", feature = "ed25519_sign_sodium"))]
+pub mod hardened;
+
 #[cfg(feature = "hash_openssl")]
 #[path = "hash/openssl.rs"]
 pub mod hash;
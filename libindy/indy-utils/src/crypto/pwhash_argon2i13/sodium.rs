@@ -5,6 +5,9 @@ use indy_api_types::domain::wallet::KeyDerivationMethod;
 use indy_api_types::errors::prelude::*;
 use libc::{c_int, c_ulonglong, size_t};
 use self::sodiumoxide::crypto::pwhash;
+use std::cmp;
+use std::convert::TryFrom;
+use std::time::{Duration, Instant};
 
 pub const SALTBYTES: usize = pwhash::SALTBYTES;
 
@@ -23,6 +26,42 @@ pub fn pwhash<'a>(key: &'a mut [u8], passwd: &[u8], salt: &Salt, key_derivation_
         }
     };
 
+    _pwhash(key, passwd, salt, opslimit as c_ulonglong, memlimit)
+}
+
+/// Upper bound of custom number of passes, so params read from wallet metadata can't make derivation endless
+pub const OPSLIMIT_MAX: u64 = 128;
+
+/// Upper bound of custom memory limit in bytes (4 GiB)
+pub const MEMLIMIT_MAX: u64 = 4 * 1024 * 1024 * 1024;
+
+/// Checks custom limits are not weaker than ARGON2I_MOD ones and don't exceed upper bounds.
+pub fn check_limits(opslimit: u64, memlimit: u64) -> Result<(), IndyError> {
+    let (opslimit_min, memlimit_min) = _moderate_limits();
+
+    if opslimit < opslimit_min || memlimit < memlimit_min {
+        return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure,
+                                       format!("Key derivation params are too low. Min ops limit: {}, min mem limit: {}", opslimit_min, memlimit_min)));
+    }
+
+    if opslimit > OPSLIMIT_MAX || memlimit > MEMLIMIT_MAX {
+        return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure,
+                                       format!("Key derivation params are too high. Max ops limit: {}, max mem limit: {}", OPSLIMIT_MAX, MEMLIMIT_MAX)));
+    }
+
+    Ok(())
+}
+
+pub fn pwhash_with_limits<'a>(key: &'a mut [u8], passwd: &[u8], salt: &Salt, opslimit: u64, memlimit: u64) -> Result<&'a [u8], IndyError> {
+    check_limits(opslimit, memlimit)?;
+
+    let memlimit = usize::try_from(memlimit)
+        .to_indy(IndyErrorKind::InvalidStructure, "Key derivation mem limit is too high for the platform")?;
+
+    _pwhash(key, passwd, salt, opslimit as c_ulonglong, memlimit)
+}
+
+fn _pwhash<'a>(key: &'a mut [u8], passwd: &[u8], salt: &Salt, opslimit: c_ulonglong, memlimit: size_t) -> Result<&'a [u8], IndyError> {
     let alg = unsafe { crypto_pwhash_alg_argon2i13() };
 
     let res = unsafe {
@@ -31,7 +70,7 @@ pub fn pwhash<'a>(key: &'a mut [u8], passwd: &[u8], salt: &Salt, key_derivation_
                       passwd.as_ptr(),
                       passwd.len() as c_ulonglong,
                       (salt.0).0.as_ptr(),
-                      opslimit as c_ulonglong,
+                      opslimit,
                       memlimit,
                       alg)
     };
@@ -43,6 +82,34 @@ pub fn pwhash<'a>(key: &'a mut [u8], passwd: &[u8], salt: &Salt, key_derivation_
     }
 }
 
+/// Measures key derivation on the current device and returns (opslimit, memlimit)
+/// making derivation to take about `target` time.
+///
+/// Memory limit of ARGON2I_MOD derivation is kept and number of passes is scaled.
+/// Limits are never lower than ARGON2I_MOD ones and never exceed `OPSLIMIT_MAX`.
+pub fn benchmark(target: Duration) -> Result<(u64, u64), IndyError> {
+    let (opslimit, memlimit) = _moderate_limits();
+
+    let mut key = [0u8; 32];
+    let start = Instant::now();
+    pwhash_with_limits(&mut key, b"benchmark", &gen_salt(), opslimit, memlimit)?;
+    let elapsed_ms = cmp::max(_millis(start.elapsed()), 1);
+
+    let recommended_opslimit = opslimit.checked_mul(_millis(target))
+        .map(|opslimit| opslimit / elapsed_ms)
+        .unwrap_or(OPSLIMIT_MAX);
+
+    Ok((cmp::min(cmp::max(recommended_opslimit, opslimit), OPSLIMIT_MAX), memlimit))
+}
+
+fn _moderate_limits() -> (u64, u64) {
+    unsafe { (crypto_pwhash_argon2i_opslimit_moderate() as u64, crypto_pwhash_argon2i_memlimit_moderate() as u64) }
+}
+
+fn _millis(duration: Duration) -> u64 {
+    duration.as_secs().saturating_mul(1000).saturating_add(u64::from(duration.subsec_millis()))
+}
+
 extern {
     fn crypto_pwhash_alg_argon2i13() -> c_int;
     fn crypto_pwhash_argon2i_opslimit_moderate() -> size_t;
@@ -105,4 +172,61 @@ mod tests {
 
         assert_ne!(key_moderate, key_interactive);
     }
+
+    #[test]
+    fn pwhash_with_limits_works_for_moderate_limits() {
+        let passwd = b"Correct Horse Battery Staple";
+
+        let salt = gen_salt();
+
+        let mut key = [0u8; 64];
+        let key_moderate = pwhash(&mut key, passwd, &salt, &KeyDerivationMethod::ARGON2I_MOD).unwrap().to_vec();
+
+        let (opslimit, memlimit) = _moderate_limits();
+
+        let mut key = [0u8; 64];
+        let key_with_limits = pwhash_with_limits(&mut key, passwd, &salt, opslimit, memlimit).unwrap();
+
+        assert_eq!(key_moderate, key_with_limits);
+    }
+
+    #[test]
+    fn pwhash_with_limits_works_for_too_low_limits() {
+        let mut key = [0u8; 64];
+        let err = pwhash_with_limits(&mut key, b"Correct Horse Battery Staple", &gen_salt(), 1, 1024).unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+    }
+
+    #[test]
+    fn pwhash_with_limits_works_for_interactive_limits() {
+        let (opslimit, memlimit) = unsafe { (crypto_pwhash_argon2i_opslimit_interactive() as u64, crypto_pwhash_argon2i_memlimit_interactive() as u64) };
+
+        let mut key = [0u8; 64];
+        let err = pwhash_with_limits(&mut key, b"Correct Horse Battery Staple", &gen_salt(), opslimit, memlimit).unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+    }
+
+    #[test]
+    fn check_limits_works_for_too_high_limits() {
+        let (opslimit, memlimit) = _moderate_limits();
+
+        assert_eq!(IndyErrorKind::InvalidStructure, check_limits(OPSLIMIT_MAX + 1, memlimit).unwrap_err().kind());
+        assert_eq!(IndyErrorKind::InvalidStructure, check_limits(opslimit, MEMLIMIT_MAX + 1).unwrap_err().kind());
+        assert_eq!(IndyErrorKind::InvalidStructure, check_limits(u64::max_value(), u64::max_value()).unwrap_err().kind());
+    }
+
+    #[test]
+    fn benchmark_works() {
+        let (opslimit, memlimit) = benchmark(Duration::from_millis(500)).unwrap();
+
+        check_limits(opslimit, memlimit).unwrap();
+    }
+
+    #[test]
+    fn benchmark_works_for_huge_target() {
+        let (opslimit, memlimit) = benchmark(Duration::from_secs(u64::max_value())).unwrap();
+
+        assert_eq!(OPSLIMIT_MAX, opslimit);
+        check_limits(opslimit, memlimit).unwrap();
+    }
 }
\ No newline at end of file
@@ -201,6 +201,15 @@ macro_rules! check_useful_opt_c_str {
     }
 }
 
+#[macro_export]
+macro_rules! check_useful_c_ptr {
+    ($ptr:ident, $e:expr) => {
+        if $ptr.is_null() {
+            return err_msg($e.into(), "Invalid pointer has been passed").into()
+        }
+    }
+}
+
 /// Vector helpers
 #[macro_export]
 macro_rules! check_useful_c_byte_array {
@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 use std::str;
 
-use indy_api_types::domain::wallet::KeyDerivationMethod;
+use indy_api_types::domain::wallet::{KeyDerivationMethod, KeyDerivationParams};
 use indy_api_types::errors::prelude::*;
 use indy_utils::crypto::{chacha20poly1305_ietf, hmacsha256, pwhash_argon2i13};
 
-use super::{Keys, WalletRecord, Metadata};
+use super::{Keys, WalletRecord, Metadata, MetadataArgon};
 use super::storage::{StorageRecord, Tag, TagName};
 use rust_base58::FromBase58;
 
@@ -27,18 +27,28 @@ pub enum KeyDerivationData {
     Raw(String),
     Argon2iMod(String, pwhash_argon2i13::Salt),
     Argon2iInt(String, pwhash_argon2i13::Salt),
+    Argon2iCustom(String, pwhash_argon2i13::Salt, KeyDerivationParams),
 }
 
 impl KeyDerivationData {
     pub fn from_passphrase_with_new_salt(passphrase: &str, derivation_method: &KeyDerivationMethod) -> Self {
+        KeyDerivationData::from_passphrase_with_new_salt_and_params(passphrase, derivation_method, None)
+    }
+
+    /// Custom params are applied to Argon2i derivation methods only.
+    pub fn from_passphrase_with_new_salt_and_params(passphrase: &str,
+                                                    derivation_method: &KeyDerivationMethod,
+                                                    params: Option<&KeyDerivationParams>) -> Self {
         let salt = pwhash_argon2i13::gen_salt();
         let passphrase = passphrase.to_owned();
-        match *derivation_method {
-            KeyDerivationMethod::ARGON2I_INT =>
+        match (derivation_method, params) {
+            (KeyDerivationMethod::ARGON2I_INT, Some(params)) | (KeyDerivationMethod::ARGON2I_MOD, Some(params)) =>
+                KeyDerivationData::Argon2iCustom(passphrase, salt, *params),
+            (KeyDerivationMethod::ARGON2I_INT, None) =>
                 KeyDerivationData::Argon2iInt(passphrase, salt),
-            KeyDerivationMethod::ARGON2I_MOD =>
+            (KeyDerivationMethod::ARGON2I_MOD, None) =>
                 KeyDerivationData::Argon2iMod(passphrase, salt),
-            KeyDerivationMethod::RAW =>
+            (KeyDerivationMethod::RAW, _) =>
                 KeyDerivationData::Raw(passphrase)
         }
     }
@@ -50,6 +60,13 @@ impl KeyDerivationData {
             (KeyDerivationMethod::RAW, &Metadata::MetadataRaw(_)) => {
                 KeyDerivationData::Raw(passphrase)
             }
+            (KeyDerivationMethod::ARGON2I_INT, &Metadata::MetadataArgon(MetadataArgon { key_derivation_params: Some(params), ref master_key_salt, .. })) |
+            (KeyDerivationMethod::ARGON2I_MOD, &Metadata::MetadataArgon(MetadataArgon { key_derivation_params: Some(params), ref master_key_salt, .. })) => {
+                pwhash_argon2i13::check_limits(params.ops_limit, params.mem_limit)
+                    .map_err(|err| err.extend("Invalid key derivation params in wallet metadata"))?;
+                let master_key_salt = master_key_salt_from_slice(master_key_salt)?;
+                KeyDerivationData::Argon2iCustom(passphrase, master_key_salt, params)
+            }
             (KeyDerivationMethod::ARGON2I_INT, &Metadata::MetadataArgon(ref metadata)) => {
                 let master_key_salt = master_key_salt_from_slice(&metadata.master_key_salt)?;
                 KeyDerivationData::Argon2iInt(passphrase, master_key_salt)
@@ -69,6 +86,7 @@ impl KeyDerivationData {
             KeyDerivationData::Raw(passphrase) => _raw_master_key(passphrase),
             KeyDerivationData::Argon2iInt(passphrase, salt) => _derive_master_key(passphrase, &salt, &KeyDerivationMethod::ARGON2I_INT),
             KeyDerivationData::Argon2iMod(passphrase, salt) => _derive_master_key(passphrase, &salt, &KeyDerivationMethod::ARGON2I_MOD),
+            KeyDerivationData::Argon2iCustom(passphrase, salt, params) => chacha20poly1305_ietf::derive_key_with_params(passphrase, &salt, params),
         }
     }
 }
@@ -182,17 +200,13 @@ pub(super) fn decrypt_tags(etags: &Option<Vec<Tag>>, tag_name_key: &chacha20poly
     }
 }
 
-pub(super) fn decrypt_storage_record(record: &StorageRecord, keys: &Keys) -> IndyResult<WalletRecord> {
+/// `type_` is used to get record keys if the record is fetched without type.
+pub(super) fn decrypt_storage_record(record: &StorageRecord, keys: &Keys, type_: Option<&str>) -> IndyResult<WalletRecord> {
     let decrypted_name = decrypt_merged(&record.id, &keys.name_key)?;
 
     let decrypted_name = String::from_utf8(decrypted_name)
         .to_indy(IndyErrorKind::WalletEncryptionError, "Record is invalid utf8")?;
 
-    let decrypted_value = match record.value {
-        Some(ref value) => Some(value.decrypt(&keys.value_key)?),
-        None => None
-    };
-
     let decrypted_type = match record.type_ {
         Some(ref type_) => {
             let decrypted_type = decrypt_merged(type_, &keys.type_key)?;
@@ -202,7 +216,17 @@ pub(super) fn decrypt_storage_record(record: &StorageRecord, keys: &Keys) -> Ind
         None => None,
     };
 
-    let decrypted_tags = decrypt_tags(&record.tags, &keys.tag_name_key, &keys.tag_value_key)?;
+    let type_keys = match decrypted_type.as_ref().map(String::as_str).or(type_) {
+        Some(type_) => keys.for_type(type_),
+        None => return Err(err_msg(IndyErrorKind::InvalidState, "Record type is required to decrypt record"))
+    };
+
+    let decrypted_value = match record.value {
+        Some(ref value) => Some(value.decrypt(&type_keys.value_key)?),
+        None => None
+    };
+
+    let decrypted_tags = decrypt_tags(&record.tags, &type_keys.tag_name_key, &type_keys.tag_value_key)?;
     Ok(WalletRecord::new(decrypted_name, decrypted_type, decrypted_value, decrypted_tags))
 }
 
@@ -379,14 +403,15 @@ mod tests {
         let keys = Keys::new();
         let name = "test_name";
         let value = "test_value";
-        let encrypted_value = EncryptedValue::encrypt(value, &keys.value_key);
         let type_ = "test_type";
+        let type_keys = keys.for_type(type_);
+        let encrypted_value = EncryptedValue::encrypt(value, &type_keys.value_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
         let mut tags = HashMap::new();
         tags.insert("tag_name_1".to_string(), "tag_value_1".to_string());
         tags.insert("~tag_name_2".to_string(), "tag_value_2".to_string());
-        let encrypted_tags = encrypt_tags(&tags, &keys.tag_name_key, &keys.tag_value_key, &keys.tags_hmac_key);
+        let encrypted_tags = encrypt_tags(&tags, &type_keys.tag_name_key, &type_keys.tag_value_key, &type_keys.tags_hmac_key);
 
         let storage_record = StorageRecord {
             id: encrypted_name,
@@ -394,7 +419,7 @@ mod tests {
             type_: Some(encrypted_type),
             tags: Some(encrypted_tags),
         };
-        let decrypted_wallet_record = decrypt_storage_record(&storage_record, &keys).unwrap();
+        let decrypted_wallet_record = decrypt_storage_record(&storage_record, &keys, None).unwrap();
 
         assert_eq!(&decrypted_wallet_record.id, name);
         assert_eq!(&decrypted_wallet_record.value.unwrap(), value);
@@ -402,6 +427,50 @@ mod tests {
         assert_eq!(&decrypted_wallet_record.tags.unwrap(), &tags);
     }
 
+    #[test]
+    fn test_decrypt_storage_record_works_for_search_type() {
+        let keys = Keys::new();
+        let name = "test_name";
+        let value = "test_value";
+        let type_ = "test_type";
+        let encrypted_value = EncryptedValue::encrypt(value, &keys.for_type(type_).value_key);
+        let encrypted_name = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+
+        let storage_record = StorageRecord {
+            id: encrypted_name,
+            value: Some(encrypted_value),
+            type_: None,
+            tags: None,
+        };
+
+        let res = decrypt_storage_record(&storage_record, &keys, None);
+        assert_kind!(IndyErrorKind::InvalidState, res);
+
+        let decrypted_wallet_record = decrypt_storage_record(&storage_record, &keys, Some(type_)).unwrap();
+        assert_eq!(&decrypted_wallet_record.value.unwrap(), value);
+    }
+
+    #[test]
+    fn test_decrypt_storage_record_fails_for_other_type_keys() {
+        let keys = Keys::new();
+        let name = "test_name";
+        let value = "test_value";
+        let type_ = "test_type";
+        let encrypted_value = EncryptedValue::encrypt(value, &keys.for_type("other_type").value_key);
+        let encrypted_name = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+        let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+
+        let storage_record = StorageRecord {
+            id: encrypted_name,
+            value: Some(encrypted_value),
+            type_: Some(encrypted_type),
+            tags: None,
+        };
+
+        let res = decrypt_storage_record(&storage_record, &keys, None);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
     #[test]
     fn test_decrypt_storage_record_fails_if_wrong_keys() {
         let keys = Keys::new();
@@ -423,7 +492,7 @@ mod tests {
             type_: Some(encrypted_type),
             tags: Some(encrypted_tags),
         };
-        let res = decrypt_storage_record(&storage_record, &keys2);
+        let res = decrypt_storage_record(&storage_record, &keys2, None);
 
         assert_kind!(IndyErrorKind::InvalidStructure, res);
     }
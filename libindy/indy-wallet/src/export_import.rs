@@ -6,7 +6,7 @@ use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use rmp_serde;
 
 use indy_api_types::domain::wallet::Record;
-use indy_api_types::domain::wallet::KeyDerivationMethod;
+use indy_api_types::domain::wallet::{KeyDerivationMethod, KeyDerivationParams};
 use indy_api_types::errors::prelude::*;
 use crate::encryption::KeyDerivationData;
 use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13};
@@ -36,6 +36,17 @@ pub enum EncryptionMethod {
         // size of encrypted chunk
         chunk_size: usize,
     },
+    // **ChaCha20-Poly1305-IETF custom Argon2i params key derivation** cypher in blocks per chunk_size bytes
+    ChaCha20Poly1305IETFCustom {
+        // pwhash_argon2i13::Salt as bytes. Random salt used for deriving of key from passphrase
+        salt: Vec<u8>,
+        // chacha20poly1305_ietf::Nonce as bytes. Random start nonce. We increment nonce for each chunk to be sure in export file consistency
+        nonce: Vec<u8>,
+        // size of encrypted chunk
+        chunk_size: usize,
+        // Argon2i params used for deriving of key from passphrase
+        key_derivation_params: KeyDerivationParams,
+    },
     // **ChaCha20-Poly1305-IETF raw key** cypher in blocks per chunk_size bytes
     ChaCha20Poly1305IETFRaw {
         // chacha20poly1305_ietf::Nonce as bytes. Random start nonce. We increment nonce for each chunk to be sure in export file consistency
@@ -87,7 +98,13 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
         KeyDerivationData::Raw(_) => EncryptionMethod::ChaCha20Poly1305IETFRaw {
             nonce: nonce[..].to_vec(),
             chunk_size,
-        }
+        },
+        KeyDerivationData::Argon2iCustom(_, salt, params) => EncryptionMethod::ChaCha20Poly1305IETFCustom {
+            salt: salt[..].to_vec(),
+            nonce: nonce[..].to_vec(),
+            chunk_size,
+            key_derivation_params: *params,
+        },
     };
 
     let header = Header {
@@ -164,6 +181,7 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     let key_derivation_method = match header.encryption_method {
         EncryptionMethod::ChaCha20Poly1305IETF { .. } => KeyDerivationMethod::ARGON2I_MOD,
         EncryptionMethod::ChaCha20Poly1305IETFInteractive { .. } => KeyDerivationMethod::ARGON2I_INT,
+        EncryptionMethod::ChaCha20Poly1305IETFCustom { .. } => KeyDerivationMethod::ARGON2I_MOD,
         EncryptionMethod::ChaCha20Poly1305IETFRaw { .. } => KeyDerivationMethod::RAW,
     };
 
@@ -187,6 +205,20 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
 
             (key_data, nonce, chunk_size)
         }
+        EncryptionMethod::ChaCha20Poly1305IETFCustom { salt, nonce, chunk_size, key_derivation_params } => {
+            pwhash_argon2i13::check_limits(key_derivation_params.ops_limit, key_derivation_params.mem_limit)
+                .map_err(|err| err.extend("Invalid key derivation params in header"))?;
+
+            let salt = pwhash_argon2i13::Salt::from_slice(&salt)
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid salt")?;
+
+            let nonce = chacha20poly1305_ietf::Nonce::from_slice(&nonce)
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid nonce")?;
+
+            let key_data = KeyDerivationData::Argon2iCustom(passphrase.to_owned(), salt, key_derivation_params);
+
+            (key_data, nonce, chunk_size)
+        }
         EncryptionMethod::ChaCha20Poly1305IETFRaw { nonce, chunk_size } => {
             let nonce = chacha20poly1305_ietf::Nonce::from_slice(&nonce)
                 .to_indy(IndyErrorKind::InvalidStructure, "Invalid nonce")?;
@@ -249,7 +281,7 @@ mod tests {
     use crate::encryption;
     use crate::storage::default::SQLiteStorageType;
     use crate::storage::WalletStorageType;
-    use crate::wallet::{Keys, Wallet};
+    use crate::wallet::{Keys, Wallet, WalletOptions};
     use indy_utils::crypto::pwhash_argon2i13;
     use indy_utils::test;
 
@@ -329,6 +361,30 @@ mod tests {
         _cleanup("export_import_works_for_2_items_and_interactive_method2");
     }
 
+    #[test]
+    fn export_import_works_for_2_items_and_custom_params() {
+        _cleanup("export_import_works_for_2_items_and_custom_params1");
+        _cleanup("export_import_works_for_2_items_and_custom_params2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            {
+                let params = KeyDerivationParams { ops_limit: 7, mem_limit: 128 * 1024 * 1024 };
+                let key_data = KeyDerivationData::from_passphrase_with_new_salt_and_params(_passphrase(), &KeyDerivationMethod::ARGON2I_MOD, Some(&params));
+                let key = key_data.calc_master_key().unwrap();
+
+                export_continue(&_add_2_records(_wallet("export_import_works_for_2_items_and_custom_params1")), &mut output, _version1(), key, &key_data).unwrap();
+            }
+            _cleanup("export_import_works_for_2_items_and_custom_params1");
+
+            let wallet = _wallet("export_import_works_for_2_items_and_custom_params2");
+            _assert_is_empty(&wallet);
+
+            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            _assert_has_2_records(&wallet);
+        }
+        _cleanup("export_import_works_for_2_items_and_custom_params2");
+    }
+
     #[test]
     fn export_import_works_for_multiple_items() {
         _cleanup("export_import_works_for_multiple_items1");
@@ -480,6 +536,7 @@ mod tests {
             let metadata = Metadata::MetadataArgon(MetadataArgon {
                 master_key_salt: master_key_salt[..].to_vec(),
                 keys: keys.serialize_encrypted(&master_key).unwrap(),
+                key_derivation_params: None,
             });
 
             serde_json::to_vec(&metadata)
@@ -493,7 +550,7 @@ mod tests {
 
         let storage = storage_type.open_storage(id, None, None).unwrap();
 
-        Wallet::new(id.to_string(), storage, Rc::new(keys))
+        Wallet::new(id.to_string(), storage, Rc::new(keys), WalletOptions::default())
     }
 
     fn _assert_is_empty(wallet: &Wallet) {
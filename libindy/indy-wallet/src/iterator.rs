@@ -10,21 +10,23 @@ use super::encryption::{decrypt_storage_record};
 pub(super) struct WalletIterator {
     storage_iterator: Box<dyn StorageIterator>,
     keys: Rc<Keys>,
+    type_: Option<String>,
 }
 
 
 impl WalletIterator {
-    pub fn new(storage_iter: Box<dyn StorageIterator>, keys: Rc<Keys>) -> Self {
+    pub fn new(storage_iter: Box<dyn StorageIterator>, keys: Rc<Keys>, type_: Option<String>) -> Self {
         WalletIterator {
             storage_iterator: storage_iter,
             keys,
+            type_,
         }
     }
 
     pub fn next(&mut self) -> Result<Option<WalletRecord>, IndyError> {
         let next_storage_entity = self.storage_iterator.next()?;
         if let Some(next_storage_entity) = next_storage_entity {
-            let record = decrypt_storage_record(&next_storage_entity, &self.keys)?;
+            let record = decrypt_storage_record(&next_storage_entity, &self.keys, self.type_.as_ref().map(String::as_str))?;
             Ok(Some(record))
         } else { Ok(None) }
     }
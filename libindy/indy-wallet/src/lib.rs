@@ -17,13 +17,14 @@ use std::fs;
 use std::io::BufReader;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::Instant;
 
 use named_type::NamedType;
 use serde_json::Value as SValue;
 
 use indy_api_types::wallet::*;
 
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, Tags};
+use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyDerivationParams, Tags};
 use indy_api_types::errors::prelude::*;
 pub use crate::encryption::KeyDerivationData;
 use indy_utils::crypto::chacha20poly1305_ietf;
@@ -33,7 +34,9 @@ use self::export_import::{export_continue, finish_import, preparse_file_to_impor
 use self::storage::{WalletStorage, WalletStorageType};
 use self::storage::default::SQLiteStorageType;
 use self::storage::plugged::PluggedStorageType;
-use self::wallet::{Keys, Wallet};
+use self::storage::profile::ProfileStorage;
+use self::wallet::{Keys, Wallet, WalletOptions};
+pub use self::slow_log::SlowQueryRecord;
 use indy_api_types::{WalletHandle};
 
 mod storage;
@@ -44,11 +47,12 @@ mod iterator;
 pub mod language;
 mod export_import;
 mod wallet;
+mod slow_log;
 
 pub struct WalletService {
     storage_types: RefCell<HashMap<String, Box<dyn WalletStorageType>>>,
     wallets: RefCell<HashMap<WalletHandle, Box<Wallet>>>,
-    pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>)>>,
+    pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>, WalletOptions)>>,
     pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
 }
 
@@ -118,6 +122,21 @@ impl WalletService {
         Ok(())
     }
 
+    pub fn register_wallet_storage_compaction(&self, type_: &str, compact: WalletCompact) -> IndyResult<()> {
+        trace!("register_wallet_storage_compaction >>> type_: {:?}", type_);
+
+        let storage_types = self.storage_types.borrow();
+
+        let storage_type = storage_types
+            .get(type_)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Wallet storage isn't registered for type: {}", type_)))?;
+
+        storage_type.set_compact_handler(compact)?;
+
+        trace!("register_wallet_storage_compaction <<<");
+        Ok(())
+    }
+
     pub fn create_wallet(&self,
                          config: &Config,
                          credentials: &Credentials,
@@ -138,14 +157,26 @@ impl WalletService {
         let keys = Keys::new();
         let metadata = self._prepare_metadata(master_key, key_data, &keys)?;
 
-        storage_type.create_storage(&config.id,
-                                    storage_config
-                                        .as_ref()
-                                        .map(String::as_str),
-                                    storage_credentials
-                                        .as_ref()
-                                        .map(String::as_str),
-                                    &metadata)?;
+        let storage_config = storage_config.as_ref().map(String::as_str);
+        let storage_credentials = storage_credentials.as_ref().map(String::as_str);
+
+        match config.profile {
+            Some(ref profile) => {
+                ProfileStorage::check_profile(profile)?;
+
+                // Storage is shared between profiles, so it is created only for the first one
+                match storage_type.create_storage(&config.id, storage_config, storage_credentials, &[]) {
+                    Err(ref err) if err.kind() == IndyErrorKind::WalletAlreadyExists => {}
+                    res => res?
+                }
+
+                let mut storage = ProfileStorage::new(storage_type.open_storage(&config.id, storage_config, storage_credentials)?, profile)?;
+                let res = storage.create_profile(&metadata);
+                storage.close()?;
+                res?;
+            }
+            None => storage_type.create_storage(&config.id, storage_config, storage_credentials, &metadata)?
+        }
 
         Ok(keys)
     }
@@ -176,6 +207,19 @@ impl WalletService {
 
         let (storage_type, storage_config, storage_credentials) = WalletService::_get_config_and_cred_for_storage(config, credentials, &storage_types)?;
 
+        if let Some(ref profile) = config.profile {
+            let mut storage = ProfileStorage::new(storage_type.open_storage(&config.id,
+                                                                            storage_config.as_ref().map(String::as_str),
+                                                                            storage_credentials.as_ref().map(String::as_str))?,
+                                                  profile)?;
+            let res = storage.delete_profile();
+            storage.close()?;
+            res?;
+
+            trace!("delete_wallet <<<");
+            return Ok(());
+        }
+
         storage_type.delete_storage(&config.id,
                                     storage_config
                                         .as_ref()
@@ -198,15 +242,15 @@ impl WalletService {
         let wallet_handle = indy_utils::next_wallet_handle();
 
         let rekey_data: Option<KeyDerivationData> = credentials.rekey.as_ref().map(|ref rekey|
-            KeyDerivationData::from_passphrase_with_new_salt(rekey, &credentials.rekey_derivation_method));
+            KeyDerivationData::from_passphrase_with_new_salt_and_params(rekey, &credentials.rekey_derivation_method, credentials.rekey_derivation_params.as_ref()));
 
-        self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone()));
+        self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone(), WalletOptions::from(config)));
 
         Ok((wallet_handle, key_derivation_data, rekey_data))
     }
 
     pub fn open_wallet_continue(&self, wallet_handle: WalletHandle, master_key: (&MasterKey, Option<&MasterKey>)) -> IndyResult<WalletHandle> {
-        let (id, storage, metadata, rekey_data) = self.pending_for_open.borrow_mut().remove(&wallet_handle)
+        let (id, storage, metadata, rekey_data, options) = self.pending_for_open.borrow_mut().remove(&wallet_handle)
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Open data not found"))?;
 
         let (master_key, rekey) = master_key;
@@ -218,7 +262,7 @@ impl WalletService {
             storage.set_storage_metadata(&metadata)?;
         }
 
-        let wallet = Wallet::new(id, storage, Rc::new(keys));
+        let wallet = Wallet::new(id, storage, Rc::new(keys), options);
 
         let mut wallets = self.wallets.borrow_mut();
         wallets.insert(wallet_handle, Box::new(wallet));
@@ -258,6 +302,14 @@ impl WalletService {
         }
     }
 
+    fn _map_wallet_batch_storage_error(err: IndyError, type_: &str) -> IndyError {
+        match err.kind() {
+            IndyErrorKind::WalletItemAlreadyExists => err_msg(IndyErrorKind::WalletItemAlreadyExists, format!("One of wallet items of the batch already exists with type: {}", type_)),
+            IndyErrorKind::WalletItemNotFound => err_msg(IndyErrorKind::WalletItemNotFound, format!("One of wallet items of the batch not found with type: {}", type_)),
+            _ => err
+        }
+    }
+
     pub fn add_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str, value: &str, tags: &Tags) -> IndyResult<()> {
         match self.wallets.borrow_mut().get_mut(&wallet_handle) {
             Some(wallet) => wallet.add(type_, name, value, tags)
@@ -266,6 +318,17 @@ impl WalletService {
         }
     }
 
+    /// Adds records `(name, value, tags)` of the same type with all-or-nothing semantics.
+    /// All records are written in a single storage transaction, so it is much faster than adding them one by one.
+    /// Plugged storages have no transactions, so records added before the failure are deleted back at best effort.
+    pub fn add_records_batch(&self, wallet_handle: WalletHandle, type_: &str, records: &[(&str, &str, &Tags)]) -> IndyResult<()> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.add_batch(type_, records)
+                .map_err(|err| WalletService::_map_wallet_batch_storage_error(err, type_)),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn add_indy_record<T>(&self, wallet_handle: WalletHandle, name: &str, value: &str, tags: &Tags)
                               -> IndyResult<()> where T: NamedType {
         self.add_record(wallet_handle, &self.add_prefix(T::short_type_name()), name, value,tags)
@@ -280,6 +343,22 @@ impl WalletService {
         Ok(object_json)
     }
 
+    /// Adds objects `(name, object)` of the same type without tags with all-or-nothing semantics (look at `add_records_batch`).
+    pub fn add_indy_objects_batch<T>(&self, wallet_handle: WalletHandle, objects: &[(&str, &T)])
+                                     -> IndyResult<()> where T: ::serde::Serialize + Sized + NamedType {
+        let objects_json = objects.iter()
+            .map(|&(name, object)| serde_json::to_string(object).map(|object_json| (name, object_json)))
+            .collect::<Result<Vec<_>, _>>()
+            .to_indy(IndyErrorKind::InvalidState, format!("Cannot serialize {:?}", T::short_type_name()))?;
+
+        let tags = Tags::new();
+        let records: Vec<(&str, &str, &Tags)> = objects_json.iter()
+            .map(|&(name, ref object_json)| (name, object_json.as_str(), &tags))
+            .collect();
+
+        self.add_records_batch(wallet_handle, &self.add_prefix(T::short_type_name()), &records)
+    }
+
     pub fn update_record_value(&self, wallet_handle: WalletHandle, type_: &str, name: &str, value: &str) -> IndyResult<()> {
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) =>
@@ -334,10 +413,24 @@ impl WalletService {
         }
     }
 
+    /// Deletes records of the same type with all-or-nothing semantics.
+    /// Plugged storages have no transactions, so records deleted before the failure are added back at best effort.
+    pub fn delete_records_batch(&self, wallet_handle: WalletHandle, type_: &str, names: &[&str]) -> IndyResult<()> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.delete_batch(type_, names)
+                .map_err(|err| WalletService::_map_wallet_batch_storage_error(err, type_)),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn delete_indy_record<T>(&self, wallet_handle: WalletHandle, name: &str) -> IndyResult<()> where T: NamedType {
         self.delete_record(wallet_handle, &self.add_prefix(T::short_type_name()), name)
     }
 
+    pub fn delete_indy_records_batch<T>(&self, wallet_handle: WalletHandle, names: &[&str]) -> IndyResult<()> where T: NamedType {
+        self.delete_records_batch(wallet_handle, &self.add_prefix(T::short_type_name()), names)
+    }
+
     pub fn get_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str, options_json: &str) -> IndyResult<WalletRecord> {
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) =>
@@ -423,6 +516,89 @@ impl WalletService {
         }
     }
 
+    pub fn verify_storage_encryption(&self, wallet_handle: WalletHandle, sample_size: Option<usize>) -> IndyResult<StorageEncryptionReport> {
+        trace!("verify_storage_encryption >>> wallet_handle: {:?}, sample_size: {:?}", wallet_handle, sample_size);
+
+        let res = match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.verify_storage_encryption(sample_size),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        };
+
+        trace!("verify_storage_encryption <<< res: {:?}", res);
+
+        res
+    }
+
+    pub fn compact_wallet(&self, wallet_handle: WalletHandle) -> IndyResult<WalletCompactionReport> {
+        trace!("compact_wallet >>> wallet_handle: {:?}", wallet_handle);
+
+        let res = match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.compact().map(|reclaimed_bytes| WalletCompactionReport { reclaimed_bytes }),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        };
+
+        trace!("compact_wallet <<< res: {:?}", res);
+
+        res
+    }
+
+    /// Runs compaction of opened wallets scheduled by `compaction_interval` of their configs.
+    pub fn compact_scheduled_wallets(&self) {
+        for wallet in self.wallets.borrow().values() {
+            wallet.compact_if_scheduled();
+        }
+    }
+
+    /// Time of the nearest scheduled compaction of opened wallets.
+    pub fn next_scheduled_compaction(&self) -> Option<Instant> {
+        self.wallets.borrow().values()
+            .filter_map(|wallet| wallet.next_compaction())
+            .min()
+    }
+
+    /// Closes opened wallets which haven't been used for `idle_timeout` of their configs or whose storage failed.
+    /// Returns handles of the closed wallets with reasons of closing.
+    pub fn close_invalid_wallets(&self) -> Vec<(WalletHandle, WalletCloseReason)> {
+        let mut wallets = self.wallets.borrow_mut();
+
+        let invalid: Vec<(WalletHandle, WalletCloseReason)> = wallets.iter()
+            .filter_map(|(handle, wallet)|
+                if wallet.is_storage_failed() {
+                    Some((*handle, WalletCloseReason::StorageFailure))
+                } else if wallet.is_idle() {
+                    Some((*handle, WalletCloseReason::IdleTimeout))
+                } else {
+                    None
+                })
+            .collect();
+
+        for &(handle, reason) in invalid.iter() {
+            if let Some(mut wallet) = wallets.remove(&handle) {
+                debug!("Closing wallet {} because of {:?}", wallet.get_id(), reason);
+
+                if let Err(err) = wallet.close() {
+                    warn!("Closing of wallet {} failed: {:?}", wallet.get_id(), err);
+                }
+            }
+        }
+
+        invalid
+    }
+
+    /// Time when the nearest opened wallet is closed by its idle timeout.
+    pub fn next_idle_close(&self) -> Option<Instant> {
+        self.wallets.borrow().values()
+            .filter_map(|wallet| wallet.idle_deadline())
+            .min()
+    }
+
+    pub fn get_slow_query_log(&self, wallet_handle: WalletHandle) -> IndyResult<Vec<SlowQueryRecord>> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => Ok(wallet.get_slow_query_log()),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn check(&self, handle: WalletHandle) -> IndyResult<()> {
         match self.wallets.borrow().get(&handle) {
             Some(_) => Ok(()),
@@ -477,7 +653,7 @@ impl WalletService {
                 .open(&export_config.path)?;
 
         let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
-        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt_and_params(&credentials.key, &credentials.key_derivation_method, credentials.key_derivation_params.as_ref());
 
         let wallet_handle = indy_utils::next_wallet_handle();
 
@@ -500,7 +676,7 @@ impl WalletService {
         let metadata = storage.get_storage_metadata()?;
 
         let res = {
-            let wallet = Wallet::new(WalletService::_get_wallet_id(&config), storage, Rc::new(keys));
+            let wallet = Wallet::new(WalletService::_get_wallet_id(&config), storage, Rc::new(keys), WalletOptions::from(config));
 
             finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes)
         };
@@ -546,8 +722,10 @@ impl WalletService {
 
     fn _get_wallet_id(config: &Config) -> String {
         let wallet_path = config.storage_config.as_ref().and_then(|storage_config| storage_config["path"].as_str()).unwrap_or("");
-        let wallet_id = format!("{}{}", config.id, wallet_path);
-        wallet_id
+        match config.profile {
+            Some(ref profile) => format!("{}{}#{}", config.id, wallet_path, profile),
+            None => format!("{}{}", config.id, wallet_path)
+        }
     }
 
     fn _open_storage(&self, config: &Config, credentials: &Credentials) -> IndyResult<Box<dyn WalletStorage>> {
@@ -557,7 +735,11 @@ impl WalletService {
         let storage = storage_type.open_storage(&config.id,
                                                 storage_config.as_ref().map(String::as_str),
                                                 storage_credentials.as_ref().map(String::as_str))?;
-        Ok(storage)
+
+        match config.profile {
+            Some(ref profile) => Ok(Box::new(ProfileStorage::new(storage, profile)?)),
+            None => Ok(storage)
+        }
     }
 
     fn _prepare_metadata(&self, master_key: &chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, keys: &Keys) -> IndyResult<Vec<u8>> {
@@ -573,6 +755,16 @@ impl WalletService {
                     MetadataArgon {
                         keys: encrypted_keys,
                         master_key_salt: salt[..].to_vec(),
+                        key_derivation_params: None,
+                    }
+                )
+            }
+            KeyDerivationData::Argon2iCustom(_, salt, params) => {
+                Metadata::MetadataArgon(
+                    MetadataArgon {
+                        keys: encrypted_keys,
+                        master_key_salt: salt[..].to_vec(),
+                        key_derivation_params: Some(*params),
                     }
                 )
             }
@@ -620,6 +812,9 @@ impl Metadata {
 pub struct MetadataArgon {
     pub keys: Vec<u8>,
     pub master_key_salt: Vec<u8>,
+    /// Custom Argon2i params the wallet key is derived with, so they aren't required on open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_derivation_params: Option<KeyDerivationParams>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -677,6 +872,43 @@ impl WalletRecord {
     }
 }
 
+/// Result of checking that raw storage rows don't expose plaintext of the wallet records.
+#[derive(Debug, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEncryptionReport {
+    pub checked_records: usize,
+    /// Values of plaintext (`~` prefixed) tags are kept unencrypted by design
+    pub plaintext_tags: usize,
+    pub encrypted: bool,
+    pub violations: Vec<StorageEncryptionViolation>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct StorageEncryptionViolation {
+    /// Index of the raw row in the checked sample
+    pub record: usize,
+    /// Field of the raw row: `id`, `type`, `value`, `tagName`, `tagValue` or `record` for the whole row
+    pub field: String,
+    pub reason: String,
+}
+
+/// Result of the wallet storage compaction.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletCompactionReport {
+    /// Zero for plugged storages, they aren't compacted by libindy
+    pub reclaimed_bytes: u64,
+}
+
+/// Reason of closing the wallet by libindy itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WalletCloseReason {
+    /// Wallet hasn't been used for `idle_timeout` of its config.
+    IdleTimeout,
+    /// Storage of the wallet failed.
+    StorageFailure,
+}
+
 fn default_true() -> bool { true }
 
 fn default_false() -> bool { false }
@@ -736,6 +968,22 @@ impl WalletSearch {
     pub fn fetch_next_record(&mut self) -> IndyResult<Option<WalletRecord>> {
         self.iter.next()
     }
+
+    /// Fetches up to `count` next records. Records are read from the storage cursor one by one,
+    /// so paging through a large search never holds more than `count` records in memory.
+    /// Returns empty list when the search is exhausted.
+    pub fn fetch_next(&mut self, count: usize) -> IndyResult<Vec<WalletRecord>> {
+        let mut records = Vec::with_capacity(::std::cmp::min(count, 1000));
+
+        while records.len() < count {
+            match self.iter.next()? {
+                Some(record) => records.push(record),
+                None => break
+            }
+        }
+
+        Ok(records)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -765,6 +1013,18 @@ impl SearchOptions {
 
         serde_json::to_string(&options).unwrap()
     }
+
+    pub fn id_tags() -> String {
+        let options = SearchOptions {
+            retrieve_records: true,
+            retrieve_total_count: true,
+            retrieve_type: true,
+            retrieve_value: false,
+            retrieve_tags: true,
+        };
+
+        serde_json::to_string(&options).unwrap()
+    }
 }
 
 impl Default for SearchOptions {
@@ -812,9 +1072,9 @@ mod tests {
             let wallet_handle = next_wallet_handle();
 
             let rekey_data: Option<KeyDerivationData> = credentials.rekey.as_ref().map(|ref rekey|
-                KeyDerivationData::from_passphrase_with_new_salt(rekey, &credentials.rekey_derivation_method));
+                KeyDerivationData::from_passphrase_with_new_salt_and_params(rekey, &credentials.rekey_derivation_method, credentials.rekey_derivation_params.as_ref()));
 
-            self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone()));
+            self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone(), WalletOptions::from(config)));
 
             let key = key_derivation_data.calc_master_key()?;
 
@@ -841,7 +1101,7 @@ mod tests {
                     .open(&export_config.path)?;
 
             let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
-            let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+            let key_data = KeyDerivationData::from_passphrase_with_new_salt_and_params(&credentials.key, &credentials.key_derivation_method, credentials.key_derivation_params.as_ref());
 
             let wallet_handle = next_wallet_handle();
 
@@ -1018,6 +1278,42 @@ mod tests {
         test::cleanup_wallet("wallet_service_delete_wallet_works_for_interactive_key_derivation");
     }
 
+    #[test]
+    fn wallet_service_profiles_work() {
+        test::cleanup_wallet("wallet_service_profiles_work");
+        {
+            let config_1 = _config_profile("wallet_service_profiles_work", "profile_1");
+            let config_2 = _config_profile("wallet_service_profiles_work", "profile_2");
+
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config_1, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            wallet_service.create_wallet(&config_2, &ARGON_INT_CREDENTIAL, (&INTERACTIVE_KDD, &INTERACTIVE_MASTER_KEY)).unwrap();
+
+            let res = wallet_service.create_wallet(&config_1, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY));
+            assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+
+            let wallet_handle_1 = wallet_service.open_wallet(&config_1, &RAW_CREDENTIAL).unwrap();
+            let wallet_handle_2 = wallet_service.open_wallet(&config_2, &ARGON_INT_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle_1, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let res = wallet_service.get_record(wallet_handle_2, "type", "key1", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            wallet_service.close_wallet(wallet_handle_1).unwrap();
+            wallet_service.close_wallet(wallet_handle_2).unwrap();
+
+            wallet_service.delete_wallet(&config_1, &RAW_CREDENTIAL).unwrap();
+
+            let res = wallet_service.open_wallet(&config_1, &RAW_CREDENTIAL);
+            assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+            let wallet_handle_2 = wallet_service.open_wallet(&config_2, &ARGON_INT_CREDENTIAL).unwrap();
+            wallet_service.close_wallet(wallet_handle_2).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_profiles_work");
+    }
+
     #[test]
     fn wallet_service_delete_wallet_works_for_moderate_key_derivation() {
         test::cleanup_wallet("wallet_service_delete_wallet_works_for_moderate_key_derivation");
@@ -1135,6 +1431,11 @@ mod tests {
             id: String::from("same_id"),
             storage_type: None,
             storage_config: None,
+            profile: None,
+            compression_threshold: None,
+            slow_query_threshold: None,
+            compaction_interval: None,
+            idle_timeout: None,
         };
 
         wallet_service.create_wallet(&config_1, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
@@ -1146,6 +1447,11 @@ mod tests {
             storage_config: Some(json!({
                 "path": _custom_path("wallet_service_open_wallet_works_for_two_wallets_with_same_ids_but_different_paths")
             })),
+            profile: None,
+            compression_threshold: None,
+            slow_query_threshold: None,
+            compaction_interval: None,
+            idle_timeout: None,
         };
 
         wallet_service.create_wallet(&config_2, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
@@ -1255,6 +1561,60 @@ mod tests {
         test::cleanup_wallet("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle");
     }
 
+    #[test]
+    fn wallet_service_close_invalid_wallets_works_for_idle_timeout() {
+        test::cleanup_wallet("wallet_service_close_invalid_wallets_works_for_idle_timeout");
+        {
+            let config = Config { idle_timeout: Some(0), .._config("wallet_service_close_invalid_wallets_works_for_idle_timeout") };
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+            assert!(wallet_service.next_idle_close().is_some());
+            assert_eq!(vec![(wallet_handle, WalletCloseReason::IdleTimeout)], wallet_service.close_invalid_wallets());
+
+            let res = wallet_service.check(wallet_handle);
+            assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+        }
+        test::cleanup_wallet("wallet_service_close_invalid_wallets_works_for_idle_timeout");
+    }
+
+    #[test]
+    fn wallet_service_close_invalid_wallets_works_for_used_wallet() {
+        test::cleanup_wallet("wallet_service_close_invalid_wallets_works_for_used_wallet");
+        {
+            let config = Config { idle_timeout: Some(100), .._config("wallet_service_close_invalid_wallets_works_for_used_wallet") };
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            assert!(wallet_service.close_invalid_wallets().is_empty());
+            wallet_service.check(wallet_handle).unwrap();
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_close_invalid_wallets_works_for_used_wallet");
+    }
+
+    #[test]
+    fn wallet_service_close_invalid_wallets_works_without_idle_timeout() {
+        test::cleanup_wallet("wallet_service_close_invalid_wallets_works_without_idle_timeout");
+        {
+            let config = _config("wallet_service_close_invalid_wallets_works_without_idle_timeout");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+            assert!(wallet_service.next_idle_close().is_none());
+            assert!(wallet_service.close_invalid_wallets().is_empty());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_close_invalid_wallets_works_without_idle_timeout");
+    }
+
     #[test]
     fn wallet_service_add_record_works() {
         test::cleanup_wallet("wallet_service_add_record_works");
@@ -1283,6 +1643,68 @@ mod tests {
         wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
     }
 
+    #[test]
+    fn wallet_service_add_records_batch_works() {
+        test::cleanup_wallet("wallet_service_add_records_batch_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_add_records_batch_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_add_records_batch_works"), &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("tag1".to_string(), "value1".to_string());
+            tags.insert("~tag2".to_string(), "value2".to_string());
+
+            wallet_service.add_records_batch(wallet_handle, "type", &[("key1", "value1", &tags), ("key2", "value2", &HashMap::new())]).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            assert_eq!(&tags, record.get_tags().unwrap());
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key2", &_fetch_options(false, true, false)).unwrap();
+            assert_eq!("value2", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_add_records_batch_works");
+    }
+
+    #[test]
+    fn wallet_service_add_records_batch_works_for_already_existing_record() {
+        test::cleanup_wallet("wallet_service_add_records_batch_works_for_already_existing_record");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_add_records_batch_works_for_already_existing_record"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_add_records_batch_works_for_already_existing_record"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let res = wallet_service.add_records_batch(wallet_handle, "type", &[("key1", "value1", &HashMap::new()), ("key2", "value2", &HashMap::new())]);
+            assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+            let res = wallet_service.get_record(wallet_handle, "type", "key1", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_add_records_batch_works_for_already_existing_record");
+    }
+
+    #[test]
+    fn wallet_service_add_records_batch_works_for_plugged_and_already_existing_record() {
+        _cleanup("wallet_service_add_records_batch_works_for_plugged_and_already_existing_record");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+        let res = wallet_service.add_records_batch(wallet_handle, "type", &[("key1", "value1", &HashMap::new()), ("key2", "value2", &HashMap::new())]);
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+        let res = wallet_service.get_record(wallet_handle, "type", "key1", "{}");
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
     #[test]
     fn wallet_service_get_record_works_for_id_only() {
         test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
@@ -1540,6 +1962,64 @@ mod tests {
         assert_kind!(IndyErrorKind::WalletItemNotFound, res);
     }
 
+    #[test]
+    fn wallet_service_delete_records_batch_works() {
+        test::cleanup_wallet("wallet_service_delete_records_batch_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_delete_records_batch_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_delete_records_batch_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            wallet_service.delete_records_batch(wallet_handle, "type", &["key1", "key2"]).unwrap();
+
+            let res = wallet_service.get_record(wallet_handle, "type", "key1", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = wallet_service.get_record(wallet_handle, "type", "key2", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_delete_records_batch_works");
+    }
+
+    #[test]
+    fn wallet_service_delete_records_batch_works_for_not_found_record() {
+        test::cleanup_wallet("wallet_service_delete_records_batch_works_for_not_found_record");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_delete_records_batch_works_for_not_found_record"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_delete_records_batch_works_for_not_found_record"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let res = wallet_service.delete_records_batch(wallet_handle, "type", &["key1", "key2"]);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+        }
+        test::cleanup_wallet("wallet_service_delete_records_batch_works_for_not_found_record");
+    }
+
+    #[test]
+    fn wallet_service_delete_records_batch_works_for_plugged_and_not_found_record() {
+        _cleanup("wallet_service_delete_records_batch_works_for_plugged_and_not_found_record");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+        let res = wallet_service.delete_records_batch(wallet_handle, "type", &["key1", "key2"]);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+    }
+
     /**
      * Add tags tests
      */
@@ -1737,6 +2217,28 @@ mod tests {
         test::cleanup_wallet("wallet_service_search_records_works");
     }
 
+    #[test]
+    fn wallet_service_search_fetch_next_works() {
+        test::cleanup_wallet("wallet_service_search_fetch_next_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_fetch_next_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_fetch_next_works"), &RAW_CREDENTIAL).unwrap();
+
+            for i in 0..5 {
+                wallet_service.add_record(wallet_handle, "type", &format!("key{}", i), "value", &HashMap::new()).unwrap();
+            }
+
+            let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &_fetch_options(true, true, true)).unwrap();
+
+            assert_eq!(2, search.fetch_next(2).unwrap().len());
+            assert_eq!(2, search.fetch_next(2).unwrap().len());
+            assert_eq!(1, search.fetch_next(2).unwrap().len());
+            assert!(search.fetch_next(2).unwrap().is_empty());
+        }
+        test::cleanup_wallet("wallet_service_search_fetch_next_works");
+    }
+
     #[test]
     fn wallet_service_search_records_works_for_plugged_wallet() {
         _cleanup("wallet_service_search_records_works_for_plugged_wallet");
@@ -2199,6 +2701,24 @@ mod tests {
             id: name.to_string(),
             storage_type: None,
             storage_config: None,
+            profile: None,
+            compression_threshold: None,
+            slow_query_threshold: None,
+            compaction_interval: None,
+            idle_timeout: None,
+        }
+    }
+
+    fn _config_profile(name: &str, profile: &str) -> Config {
+        Config {
+            id: name.to_string(),
+            storage_type: None,
+            storage_config: None,
+            profile: Some(profile.to_string()),
+            compression_threshold: None,
+            slow_query_threshold: None,
+            compaction_interval: None,
+            idle_timeout: None,
         }
     }
 
@@ -2207,6 +2727,11 @@ mod tests {
             id: name.to_string(),
             storage_type: Some("default".to_string()),
             storage_config: None,
+            profile: None,
+            compression_threshold: None,
+            slow_query_threshold: None,
+            compaction_interval: None,
+            idle_timeout: None,
         }
     }
 
@@ -2215,6 +2740,11 @@ mod tests {
             id: "w1".to_string(),
             storage_type: Some("inmem".to_string()),
             storage_config: None,
+            profile: None,
+            compression_threshold: None,
+            slow_query_threshold: None,
+            compaction_interval: None,
+            idle_timeout: None,
         }
     }
 
@@ -2223,6 +2753,11 @@ mod tests {
             id: name.to_string(),
             storage_type: Some("unknown".to_string()),
             storage_config: None,
+            profile: None,
+            compression_threshold: None,
+            slow_query_threshold: None,
+            compaction_interval: None,
+            idle_timeout: None,
         }
     }
 
@@ -2234,6 +2769,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
             rekey_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         };
     }
 
@@ -2245,6 +2782,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
             rekey_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         };
     }
 
@@ -2257,6 +2796,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
             rekey_derivation_method: KeyDerivationMethod::RAW,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         };
     }
 
@@ -2297,6 +2838,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
             rekey_derivation_method: KeyDerivationMethod::RAW,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         }
     }
 
@@ -2307,6 +2850,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
             rekey_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         }
     }
 
@@ -2317,6 +2862,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
             rekey_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         }
     }
 
@@ -2327,6 +2874,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
             rekey_derivation_method: KeyDerivationMethod::RAW,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         }
     }
 
@@ -2337,6 +2886,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
             rekey_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         }
     }
 
@@ -2347,6 +2898,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
             rekey_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         }
     }
 
@@ -2357,6 +2910,8 @@ mod tests {
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
             rekey_derivation_method: KeyDerivationMethod::RAW,
+            key_derivation_params: None,
+            rekey_derivation_params: None,
         }
     }
 
@@ -2371,6 +2926,7 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            key_derivation_params: None,
         }
     }
 
@@ -2389,6 +2945,7 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            key_derivation_params: None,
         }
     }
 
@@ -2401,6 +2958,7 @@ mod tests {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::RAW,
+            key_derivation_params: None,
         }
     }
 
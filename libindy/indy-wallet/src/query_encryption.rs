@@ -1,17 +1,17 @@
 use indy_api_types::errors::prelude::*;
 
-use super::wallet::Keys;
+use super::wallet::TypeKeys;
 use super::language::{Operator, TargetValue, TagName};
 use super::encryption::encrypt_as_searchable;
 use indy_utils::wql::Query;
 
 // Performs encryption of WQL query
 // WQL query is provided as top-level Operator
-pub(super) fn encrypt_query(query: Query, keys: &Keys) -> IndyResult<Operator> {
+pub(super) fn encrypt_query(query: Query, keys: &TypeKeys) -> IndyResult<Operator> {
     transform(query, keys)
 }
 
-fn transform(query: Query, keys: &Keys) -> IndyResult<Operator> {
+fn transform(query: Query, keys: &TypeKeys) -> IndyResult<Operator> {
     match query {
         Query::Eq(name, value) => {
             let (encrypted_name, encrypted_value) = encrypt_name_value(name, value, keys)?;
@@ -66,7 +66,7 @@ fn transform(query: Query, keys: &Keys) -> IndyResult<Operator> {
     }
 }
 
-fn transform_list_operators(operators: Vec<Query>, keys: &Keys) -> IndyResult<Vec<Operator>> {
+fn transform_list_operators(operators: Vec<Query>, keys: &TypeKeys) -> IndyResult<Vec<Operator>> {
     let mut transformed = Vec::with_capacity(operators.len());
 
     for operator in operators {
@@ -80,7 +80,7 @@ fn transform_list_operators(operators: Vec<Query>, keys: &Keys) -> IndyResult<Ve
 // Encrypts a single tag name, tag value pair.
 // If the tag name is EncryptedTagName enum variant, encrypts both the tag name and the tag value
 // If the tag name is PlainTagName enum variant, encrypts only the tag name
-fn encrypt_name_value(name: String, value: String, keys: &Keys) -> IndyResult<(TagName, TargetValue)> {
+fn encrypt_name_value(name: String, value: String, keys: &TypeKeys) -> IndyResult<(TagName, TargetValue)> {
     let name = TagName::from(name)?;
     let value = TargetValue::from(value);
     match (name, value) {
@@ -0,0 +1,119 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Number of the latest slow storage operations kept for the wallet.
+const SLOW_LOG_CAPACITY: usize = 100;
+
+/// Storage operation that took longer than the configured threshold.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowQueryRecord {
+    pub operation: String,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub duration_ms: u64,
+    /// Number of affected or found records if known
+    pub rows: Option<usize>,
+    /// Unix time in milliseconds when the operation was finished
+    pub timestamp: u64,
+}
+
+/// Ring buffer of the latest storage operations exceeding the latency threshold.
+pub(super) struct SlowQueryLog {
+    threshold: Duration,
+    records: RefCell<VecDeque<SlowQueryRecord>>,
+}
+
+impl SlowQueryLog {
+    pub fn new(threshold_ms: u64) -> SlowQueryLog {
+        SlowQueryLog {
+            threshold: Duration::from_millis(threshold_ms),
+            records: RefCell::new(VecDeque::with_capacity(SLOW_LOG_CAPACITY)),
+        }
+    }
+
+    /// Runs the storage operation and records it if it exceeds the threshold.
+    /// `rows` is called on the operation result only for slow operations.
+    pub fn measure<T, F, R>(&self, operation: &str, type_: Option<&str>, f: F, rows: R) -> T
+        where F: FnOnce() -> T, R: FnOnce(&T) -> Option<usize> {
+        let start = Instant::now();
+        let res = f();
+        let duration = start.elapsed();
+
+        if duration >= self.threshold {
+            self.push(operation, type_, duration, rows(&res));
+        }
+
+        res
+    }
+
+    pub fn records(&self) -> Vec<SlowQueryRecord> {
+        self.records.borrow().iter().cloned().collect()
+    }
+
+    fn push(&self, operation: &str, type_: Option<&str>, duration: Duration, rows: Option<usize>) {
+        let duration_ms = duration.as_secs() * 1000 + u64::from(duration.subsec_millis());
+
+        warn!("Slow wallet storage operation: {}, type: {:?}, duration: {} ms, rows: {:?}", operation, type_, duration_ms, rows);
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs() * 1000 + u64::from(time.subsec_millis()))
+            .unwrap_or(0);
+
+        let mut records = self.records.borrow_mut();
+
+        if records.len() == SLOW_LOG_CAPACITY {
+            records.pop_front();
+        }
+
+        records.push_back(SlowQueryRecord {
+            operation: operation.to_string(),
+            type_: type_.map(String::from),
+            duration_ms,
+            rows,
+            timestamp,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_query_log_measure_works() {
+        let log = SlowQueryLog::new(0);
+
+        let res = log.measure("get", Some("type"), || 42, |_| Some(1));
+        assert_eq!(42, res);
+
+        let records = log.records();
+        assert_eq!(1, records.len());
+        assert_eq!("get", records[0].operation);
+        assert_eq!(Some("type".to_string()), records[0].type_);
+        assert_eq!(Some(1), records[0].rows);
+    }
+
+    #[test]
+    fn slow_query_log_measure_works_for_fast_operation() {
+        let log = SlowQueryLog::new(60_000);
+
+        log.measure("get", Some("type"), || (), |_| Some(1));
+        assert!(log.records().is_empty());
+    }
+
+    #[test]
+    fn slow_query_log_keeps_latest_records() {
+        let log = SlowQueryLog::new(0);
+
+        for idx in 0..SLOW_LOG_CAPACITY + 5 {
+            log.measure("get", None, || (), |_| Some(idx));
+        }
+
+        let records = log.records();
+        assert_eq!(SLOW_LOG_CAPACITY, records.len());
+        assert_eq!(Some(5), records[0].rows);
+        assert_eq!(Some(SLOW_LOG_CAPACITY + 4), records[SLOW_LOG_CAPACITY - 1].rows);
+    }
+}
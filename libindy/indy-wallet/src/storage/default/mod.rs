@@ -245,6 +245,64 @@ impl SQLiteStorageType {
     }
 }
 
+fn _add_item(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+    let res = conn.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
+        .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
+
+    let id = match res {
+        Ok(entity) => entity,
+        Err(err) => return Err(IndyError::from(err))
+    };
+
+    if !tags.is_empty() {
+        let mut stmt_e = conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+        let mut stmt_p = conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+        for tag in tags {
+            match *tag {
+                Tag::Encrypted(ref tag_name, ref tag_data) => stmt_e.execute(rusqlite::params![&id, tag_name, tag_data])?,
+                Tag::PlainText(ref tag_name, ref tag_data) => stmt_p.execute(rusqlite::params![&id, tag_name, tag_data])?
+            };
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the smallest value greater than all values starting with the prefix, if any.
+fn _type_prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut upper = prefix.to_vec();
+
+    while let Some(last) = upper.pop() {
+        if last < u8::max_value() {
+            upper.push(last + 1);
+            return Some(upper);
+        }
+    }
+
+    None
+}
+
+fn _delete_item(conn: &rusqlite::Connection, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+    // Foreign keys aren't enforced for opened connections, so tags are removed explicitly
+    // instead of relying on ON DELETE CASCADE. Otherwise they would be attached to the next item reusing the id.
+    conn.execute("DELETE FROM tags_encrypted WHERE item_id IN (SELECT id FROM items WHERE type = ?1 AND name = ?2)",
+                 &[&type_.to_vec(), &id.to_vec()])?;
+    conn.execute("DELETE FROM tags_plaintext WHERE item_id IN (SELECT id FROM items WHERE type = ?1 AND name = ?2)",
+                 &[&type_.to_vec(), &id.to_vec()])?;
+
+    let row_count = conn.execute(
+        "DELETE FROM items where type = ?1 AND name = ?2",
+        &[&type_.to_vec(), &id.to_vec()],
+    )?;
+
+    if row_count == 1 {
+        Ok(())
+    } else {
+        Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    }
+}
+
 impl WalletStorage for SQLiteStorage {
     ///
     /// Tries to fetch values and/or tags from the storage.
@@ -349,26 +407,7 @@ impl WalletStorage for SQLiteStorage {
     ///
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
         let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-        let res = tx.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
-            .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
-
-        let id = match res {
-            Ok(entity) => entity,
-            Err(err) => return Err(IndyError::from(err))
-        };
-
-        if !tags.is_empty() {
-            let mut stmt_e = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut stmt_p = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => stmt_e.execute(rusqlite::params![&id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => stmt_p.execute(rusqlite::params![&id, tag_name, tag_data])?
-                };
-            }
-        }
-
+        _add_item(&tx, type_, id, value, tags)?;
         tx.commit()?;
         Ok(())
     }
@@ -480,16 +519,50 @@ impl WalletStorage for SQLiteStorage {
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
     fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
-        let row_count = self.conn.execute(
-            "DELETE FROM items where type = ?1 AND name = ?2",
-            &[&type_.to_vec(), &id.to_vec()],
-        )?;
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+        _delete_item(&tx, type_, id)?;
+        tx.commit()?;
+        Ok(())
+    }
 
-        if row_count == 1 {
-            Ok(())
-        } else {
-            Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    ///
+    /// adds values and tags of many items into storage in a single transaction.
+    /// Nothing is added if any of the items can't be added.
+    ///
+    /// # Errors
+    ///
+    ///  * `IndyError::ItemAlreadyExists` - One of the items is already present in database
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn add_batch(&self, type_: &[u8], records: &[(Vec<u8>, EncryptedValue, Vec<Tag>)]) -> IndyResult<()> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        for (id, value, tags) in records {
+            _add_item(&tx, type_, id, value, tags)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    ///
+    /// deletes values and tags of many items from storage in a single transaction.
+    /// Nothing is deleted if any of the items is not found.
+    ///
+    /// # Errors
+    ///
+    ///  * `IndyError::ItemNotFound` - One of the items is not found in database
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn delete_batch(&self, type_: &[u8], ids: &[Vec<u8>]) -> IndyResult<()> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        for id in ids {
+            _delete_item(&tx, type_, id)?;
         }
+
+        tx.commit()?;
+        Ok(())
     }
 
     fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
@@ -505,6 +578,22 @@ impl WalletStorage for SQLiteStorage {
         Ok(())
     }
 
+    ///
+    /// rebuilds the database file to reclaim pages left free by deleted items.
+    /// Returns the number of bytes the database file has shrunk by.
+    ///
+    /// # Errors
+    ///
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    fn compact(&self) -> IndyResult<u64> {
+        let size_before = self._db_size()?;
+        self.conn.execute_batch("VACUUM")?;
+        let size_after = self._db_size()?;
+
+        Ok(size_before.saturating_sub(size_after))
+    }
+
     fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
         let statement = self._prepare_statement("SELECT id, name, value, key, type FROM items;")?;
 
@@ -520,6 +609,53 @@ impl WalletStorage for SQLiteStorage {
         Ok(Box::new(storage_iterator))
     }
 
+    fn get_all_with_type_prefix(&self, prefix: &[u8]) -> IndyResult<Box<dyn StorageIterator>> {
+        let fetch_options = RecordOptions {
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        let tag_retriever = Some(TagRetriever::new_owned(self.conn.clone())?);
+        let lower = prefix.to_vec();
+
+        // Range over the (type, name) index instead of scanning the whole table
+        let storage_iterator = match _type_prefix_upper_bound(prefix) {
+            Some(upper) => {
+                let statement = self._prepare_statement("SELECT id, name, value, key, type FROM items WHERE type >= ?1 AND type < ?2;")?;
+                SQLiteStorageIterator::new(Some(statement), &[&lower, &upper], fetch_options, tag_retriever, None)?
+            }
+            None => {
+                let statement = self._prepare_statement("SELECT id, name, value, key, type FROM items WHERE type >= ?1;")?;
+                SQLiteStorageIterator::new(Some(statement), &[&lower], fetch_options, tag_retriever, None)?
+            }
+        };
+
+        Ok(Box::new(storage_iterator))
+    }
+
+    fn delete_all_with_type_prefix(&self, prefix: &[u8]) -> IndyResult<()> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+        let lower = prefix.to_vec();
+
+        // Tags are removed explicitly for the same reason as in `_delete_item`
+        match _type_prefix_upper_bound(prefix) {
+            Some(upper) => {
+                tx.execute("DELETE FROM tags_encrypted WHERE item_id IN (SELECT id FROM items WHERE type >= ?1 AND type < ?2)", &[&lower, &upper])?;
+                tx.execute("DELETE FROM tags_plaintext WHERE item_id IN (SELECT id FROM items WHERE type >= ?1 AND type < ?2)", &[&lower, &upper])?;
+                tx.execute("DELETE FROM items WHERE type >= ?1 AND type < ?2", &[&lower, &upper])?;
+            }
+            None => {
+                tx.execute("DELETE FROM tags_encrypted WHERE item_id IN (SELECT id FROM items WHERE type >= ?1)", &[&lower])?;
+                tx.execute("DELETE FROM tags_plaintext WHERE item_id IN (SELECT id FROM items WHERE type >= ?1)", &[&lower])?;
+                tx.execute("DELETE FROM items WHERE type >= ?1", &[&lower])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
     fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> IndyResult<Box<dyn StorageIterator>> {
         let type_ = type_.to_vec(); // FIXME
 
@@ -573,6 +709,13 @@ impl WalletStorage for SQLiteStorage {
 }
 
 impl SQLiteStorage {
+    fn _db_size(&self) -> IndyResult<u64> {
+        let page_count: i64 = self.conn.query_row("PRAGMA page_count", rusqlite::NO_PARAMS, |row| { row.get(0) })?;
+        let page_size: i64 = self.conn.query_row("PRAGMA page_size", rusqlite::NO_PARAMS, |row| { row.get(0) })?;
+
+        Ok((page_count * page_size) as u64)
+    }
+
     fn _prepare_statement(&self, sql: &str) -> IndyResult<OwningHandle<Rc<rusqlite::Connection>, Box<rusqlite::Statement<'static>>>> {
         OwningHandle::try_new(self.conn.clone(), |conn| {
             unsafe { (*conn).prepare(sql) }.map(Box::new).map_err(IndyError::from)
@@ -979,6 +1122,45 @@ mod tests {
         _cleanup("sqlite_storage_delete_works");
     }
 
+    #[test]
+    fn sqlite_storage_delete_works_for_tags() {
+        _cleanup("sqlite_storage_delete_works_for_tags");
+        {
+            let storage = _storage("sqlite_storage_delete_works_for_tags");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            storage.delete(&_type1(), &_id1()).unwrap();
+
+            // new item takes the id of the deleted one
+            storage.add(&_type1(), &_id1(), &_value1(), &[]).unwrap();
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert!(record.tags.unwrap().is_empty());
+        }
+        _cleanup("sqlite_storage_delete_works_for_tags");
+    }
+
+    #[test]
+    fn sqlite_storage_compact_works() {
+        _cleanup("sqlite_storage_compact_works");
+        {
+            let storage = _storage("sqlite_storage_compact_works");
+
+            let value = EncryptedValue { data: vec![1; 4096], key: vec![2; 32] };
+
+            for i in 0..100u8 {
+                storage.add(&_type1(), &_id(i), &value, &_tags()).unwrap();
+            }
+
+            for i in 0..100u8 {
+                storage.delete(&_type1(), &_id(i)).unwrap();
+            }
+
+            assert!(storage.compact().unwrap() > 0);
+            assert_eq!(0, storage.compact().unwrap());
+        }
+        _cleanup("sqlite_storage_compact_works");
+    }
+
     #[test]
     fn sqlite_storage_delete_works_for_non_existing() {
         _cleanup("sqlite_storage_delete_works_for_non_existing");
@@ -1031,6 +1213,55 @@ mod tests {
         _cleanup("sqlite_storage_get_all_works");
     }
 
+    #[test]
+    fn sqlite_storage_delete_all_with_type_prefix_works() {
+        _cleanup("sqlite_storage_delete_all_with_type_prefix_works");
+        {
+            let storage = _storage("sqlite_storage_delete_all_with_type_prefix_works");
+            storage.add(&[1, 255], &_id1(), &_value1(), &_tags()).unwrap();
+            storage.add(&[1, 255, 0], &_id2(), &_value2(), &_tags()).unwrap();
+            storage.add(&[2], &_id1(), &_value1(), &_tags()).unwrap();
+
+            storage.delete_all_with_type_prefix(&[1, 255]).unwrap();
+
+            let res = storage.get(&[1, 255], &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = storage.get(&[1, 255, 0], &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let record = storage.get(&[2], &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+        }
+        _cleanup("sqlite_storage_delete_all_with_type_prefix_works");
+    }
+
+    #[test]
+    fn sqlite_storage_get_all_with_type_prefix_works() {
+        _cleanup("sqlite_storage_get_all_with_type_prefix_works");
+        {
+            let storage = _storage("sqlite_storage_get_all_with_type_prefix_works");
+            storage.add(&[1, 255], &_id1(), &_value1(), &_tags()).unwrap();
+            storage.add(&[1, 255, 0], &_id2(), &_value2(), &_tags()).unwrap();
+            storage.add(&[2], &_id1(), &_value1(), &_tags()).unwrap();
+            storage.add(&[1], &_id1(), &_value1(), &_tags()).unwrap();
+
+            let mut storage_iterator = storage.get_all_with_type_prefix(&[1, 255]).unwrap();
+
+            let record = storage_iterator.next().unwrap().unwrap();
+            assert_eq!(record.type_.unwrap(), vec![1, 255]);
+            assert_eq!(record.value.unwrap(), _value1());
+
+            let record = storage_iterator.next().unwrap().unwrap();
+            assert_eq!(record.type_.unwrap(), vec![1, 255, 0]);
+            assert_eq!(record.value.unwrap(), _value2());
+
+            let record = storage_iterator.next().unwrap();
+            assert!(record.is_none());
+        }
+        _cleanup("sqlite_storage_get_all_with_type_prefix_works");
+    }
+
     #[test]
     fn sqlite_storage_get_all_works_for_empty() {
         _cleanup("sqlite_storage_get_all_works_for_empty");
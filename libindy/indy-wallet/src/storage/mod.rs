@@ -1,7 +1,9 @@
 pub mod default;
 pub mod plugged;
+pub mod profile;
 
 use indy_api_types::errors::prelude::*;
+use indy_api_types::wallet::WalletCompact;
 use crate::language;
 use crate::wallet::EncryptedValue;
 
@@ -41,6 +43,27 @@ pub trait StorageIterator {
     fn get_total_count(&self) -> Result<Option<usize>, IndyError>;
 }
 
+struct TypePrefixStorageIterator {
+    iter: Box<dyn StorageIterator>,
+    prefix: Vec<u8>,
+}
+
+impl StorageIterator for TypePrefixStorageIterator {
+    fn next(&mut self) -> Result<Option<StorageRecord>, IndyError> {
+        while let Some(record) = self.iter.next()? {
+            if record.type_.as_ref().map(|type_| type_.starts_with(&self.prefix)).unwrap_or(false) {
+                return Ok(Some(record));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn get_total_count(&self) -> Result<Option<usize>, IndyError> {
+        Ok(None)
+    }
+}
+
 pub trait WalletStorage {
     fn get(&self, type_: &[u8], id: &[u8], options: &str) -> Result<StorageRecord, IndyError>;
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> Result<(), IndyError>;
@@ -49,10 +72,44 @@ pub trait WalletStorage {
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> Result<(), IndyError>;
     fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> Result<(), IndyError>;
     fn delete(&self, type_: &[u8], id: &[u8]) -> Result<(), IndyError>;
+    /// Adds records of the same type with all-or-nothing semantics. Records are `(id, value, tags)`.
+    /// Storages without transactions revert already added records on failure
+    /// and fail with `InvalidState` error if some of them can't be reverted.
+    fn add_batch(&self, type_: &[u8], records: &[(Vec<u8>, EncryptedValue, Vec<Tag>)]) -> Result<(), IndyError>;
+    /// Deletes records of the same type with all-or-nothing semantics.
+    /// Storages without transactions restore already deleted records on failure
+    /// and fail with `InvalidState` error if some of them can't be restored.
+    fn delete_batch(&self, type_: &[u8], ids: &[Vec<u8>]) -> Result<(), IndyError>;
     fn get_storage_metadata(&self) -> Result<Vec<u8>, IndyError>;
     fn set_storage_metadata(&self, metadata: &[u8]) -> Result<(), IndyError>;
     fn get_all(&self) -> Result<Box<dyn StorageIterator>, IndyError>;
+    /// Returns records which types start with the prefix. Storages without prefix queries
+    /// scan all records and skip the others.
+    fn get_all_with_type_prefix(&self, prefix: &[u8]) -> Result<Box<dyn StorageIterator>, IndyError> {
+        let iter = self.get_all()?;
+        Ok(Box::new(TypePrefixStorageIterator { iter, prefix: prefix.to_vec() }))
+    }
+    /// Deletes records which types start with the prefix. Storages without prefix queries
+    /// scan all records and delete the matching ones one by one.
+    fn delete_all_with_type_prefix(&self, prefix: &[u8]) -> Result<(), IndyError> {
+        let mut records = Vec::new();
+        let mut iter = self.get_all_with_type_prefix(prefix)?;
+
+        while let Some(record) = iter.next()? {
+            if let Some(type_) = record.type_ {
+                records.push((type_, record.id));
+            }
+        }
+
+        for (type_, id) in records {
+            self.delete(&type_, &id)?;
+        }
+
+        Ok(())
+    }
     fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> Result<Box<dyn StorageIterator>, IndyError>;
+    /// Reclaims space left by deleted records. Returns number of reclaimed bytes.
+    fn compact(&self) -> Result<u64, IndyError>;
     fn close(&mut self) -> Result<(), IndyError>;
 }
 
@@ -60,4 +117,8 @@ pub trait WalletStorageType {
     fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), IndyError>;
     fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<dyn WalletStorage>, IndyError>;
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<(), IndyError>;
+    /// Sets the plugin handler compacting storages opened after the call. Only plugged storage types accept it.
+    fn set_compact_handler(&self, _compact: WalletCompact) -> Result<(), IndyError> {
+        Err(err_msg(IndyErrorKind::InvalidState, "Compaction handler can be set only for plugged storage types"))
+    }
 }
\ No newline at end of file
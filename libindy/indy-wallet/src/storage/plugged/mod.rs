@@ -1,4 +1,5 @@
 use std::{slice, str};
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::ptr;
@@ -235,6 +236,7 @@ struct PluggedStorage {
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
     close_handler: WalletClose,
+    compact_handler: Option<WalletCompact>,
 }
 
 impl PluggedStorage {
@@ -283,10 +285,23 @@ impl PluggedStorage {
             fetch_search_next_record_handler,
             free_search_handler,
             close_handler,
+            compact_handler: None,
         }
     }
 }
 
+fn _batch_error(err: IndyError, undo_errors: Vec<String>) -> IndyError {
+    if undo_errors.is_empty() {
+        return err;
+    }
+
+    warn!("Can't revert the failed batch: {:?}", undo_errors);
+
+    let msg = format!("Batch failed: {}. It is reverted partially, the wallet contains part of the batch: {}", err.kind(), undo_errors.join("; "));
+
+    err.map(IndyErrorKind::InvalidState, msg)
+}
+
 fn _tags_to_json(tags: &[Tag]) -> IndyResult<String> {
     let mut string_tags = HashMap::with_capacity(tags.len());
 
@@ -518,6 +533,55 @@ impl WalletStorage for PluggedStorage {
         Ok(())
     }
 
+    // Plugged storage API has no transactions, so changes made before the failure are reverted one by one.
+    // Reverting goes on after errors and all of them are reported with the batch error.
+    fn add_batch(&self, type_: &[u8], records: &[(Vec<u8>, EncryptedValue, Vec<Tag>)]) -> IndyResult<()> {
+        for (idx, (id, value, tags)) in records.iter().enumerate() {
+            if let Err(err) = self.add(type_, id, value, tags) {
+                let undo_errors = records[..idx].iter()
+                    .filter_map(|(added_id, _, _)| self.delete(type_, added_id).err())
+                    .map(|err| format!("can't delete added record: {}", err.kind()))
+                    .collect();
+
+                return Err(_batch_error(err, undo_errors));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn delete_batch(&self, type_: &[u8], ids: &[Vec<u8>]) -> IndyResult<()> {
+        let options = serde_json::to_string(&RecordOptions { retrieve_type: false, retrieve_value: true, retrieve_tags: true })
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RecordOptions")?;
+
+        // All records are read before deletion to fail early on missing ones and to be able to restore deleted ones
+        let mut records = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            records.push(self.get(type_, id, &options)?);
+        }
+
+        for (idx, id) in ids.iter().enumerate() {
+            if let Err(err) = self.delete(type_, id) {
+                let undo_errors = records[..idx].iter()
+                    .filter_map(|record| {
+                        let tags = record.tags.as_ref().map(Vec::as_slice).unwrap_or(&[]);
+
+                        match record.value {
+                            Some(ref value) => self.add(type_, &record.id, value, tags).err(),
+                            None => Some(err_msg(IndyErrorKind::InvalidState, "Value of the deleted record isn't retrieved"))
+                        }
+                    })
+                    .map(|err| format!("can't restore deleted record: {}", err.kind()))
+                    .collect();
+
+                return Err(_batch_error(err, undo_errors));
+            }
+        }
+
+        Ok(())
+    }
+
     fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
         let mut metadata_ptr: *const c_char = ptr::null_mut();
         let mut metadata_handle = -1;
@@ -606,6 +670,25 @@ impl WalletStorage for PluggedStorage {
         ))
     }
 
+    /// Calls the compaction handler registered by `indy_register_wallet_storage_compaction`.
+    /// Storages without the handler are expected to maintain themselves and report zero reclaimed bytes.
+    fn compact(&self) -> IndyResult<u64> {
+        let compact_handler = match self.compact_handler {
+            Some(compact_handler) => compact_handler,
+            None => return Ok(0)
+        };
+
+        let mut reclaimed_bytes: u64 = 0;
+
+        let err = compact_handler(self.handle, &mut reclaimed_bytes);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(reclaimed_bytes)
+    }
+
     fn close(&mut self) -> IndyResult<()> {
         let err = (self.close_handler)(self.handle);
 
@@ -655,6 +738,7 @@ pub struct PluggedStorageType {
     get_search_total_count_handler: WalletGetSearchTotalCount,
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
+    compact_handler: Cell<Option<WalletCompact>>,
 }
 
 
@@ -708,6 +792,7 @@ impl PluggedStorageType {
             get_search_total_count_handler,
             fetch_search_next_record_handler,
             free_search_handler,
+            compact_handler: Cell::new(None),
         }
     }
 }
@@ -758,7 +843,7 @@ impl WalletStorageType for PluggedStorageType {
             return Err(err.into());
         }
 
-        Ok(Box::new(
+        let mut storage =
             PluggedStorage::new(
                 handle,
                 self.add_record_handler,
@@ -781,7 +866,16 @@ impl WalletStorageType for PluggedStorageType {
                 self.get_search_total_count_handler,
                 self.fetch_search_next_record_handler,
                 self.free_search_handler,
-                self.close_handler)))
+                self.close_handler);
+
+        storage.compact_handler = self.compact_handler.get();
+
+        Ok(Box::new(storage))
+    }
+
+    fn set_compact_handler(&self, compact: WalletCompact) -> IndyResult<()> {
+        self.compact_handler.set(Some(compact));
+        Ok(())
     }
 
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> IndyResult<()> {
@@ -2185,4 +2279,18 @@ mod tests {
         assert_eq!(&expected_free_record_call, debug.get(6).unwrap());
         assert_eq!(&expected_free_search_call, debug.get(7).unwrap());
     }
+
+    #[test]
+    fn batch_error_works_for_reverted_batch() {
+        let err = _batch_error(err_msg(IndyErrorKind::WalletItemAlreadyExists, "exists"), vec![]);
+        assert_eq!(IndyErrorKind::WalletItemAlreadyExists, err.kind());
+    }
+
+    #[test]
+    fn batch_error_works_for_partially_reverted_batch() {
+        let err = _batch_error(err_msg(IndyErrorKind::WalletItemAlreadyExists, "exists"),
+                               vec!["first".to_string(), "second".to_string()]);
+        assert_eq!(IndyErrorKind::InvalidState, err.kind());
+        assert!(err.to_string().contains("first; second"));
+    }
 }
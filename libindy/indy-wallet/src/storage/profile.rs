@@ -0,0 +1,330 @@
+use indy_api_types::errors::prelude::*;
+
+use crate::language;
+use crate::wallet::EncryptedValue;
+
+use super::{StorageIterator, StorageRecord, Tag, TagName, WalletStorage};
+
+/// Type of the records keeping metadata of the profiles hosted in the storage.
+/// Starts with the separator, so it never intersects with types of the profiles records.
+const PROFILE_METADATA_TYPE: &[u8] = b"\x00profile_metadata";
+const PROFILE_SEPARATOR: u8 = 0;
+
+///
+/// Logically isolated wallet living inside the shared physical storage.
+///
+/// Types of the records are prefixed with the profile id, so records of different profiles
+/// never intersect. Profile metadata (keys encrypted with the profile master key) is kept
+/// as a separate record of the storage instead of the storage metadata.
+///
+pub struct ProfileStorage {
+    storage: Box<dyn WalletStorage>,
+    profile: Vec<u8>,
+    prefix: Vec<u8>,
+}
+
+impl ProfileStorage {
+    pub fn new(storage: Box<dyn WalletStorage>, profile: &str) -> IndyResult<ProfileStorage> {
+        ProfileStorage::check_profile(profile)?;
+
+        let profile = profile.as_bytes().to_vec();
+
+        let mut prefix = profile.clone();
+        prefix.push(PROFILE_SEPARATOR);
+
+        Ok(ProfileStorage { storage, profile, prefix })
+    }
+
+    /// Profile id can't contain the separator, otherwise types of its records could intersect with another profile ones.
+    pub fn check_profile(profile: &str) -> IndyResult<()> {
+        if profile.as_bytes().contains(&PROFILE_SEPARATOR) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Wallet profile contains NUL character"));
+        }
+
+        Ok(())
+    }
+
+    pub fn create_profile(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.storage.add(PROFILE_METADATA_TYPE, &self.profile, &EncryptedValue::new(metadata.to_vec(), Vec::new()), &[])
+            .map_err(|err| match err.kind() {
+                IndyErrorKind::WalletItemAlreadyExists => err.map(IndyErrorKind::WalletAlreadyExists, "Wallet profile already exists"),
+                _ => err
+            })
+    }
+
+    /// Removes all records of the profile and the profile itself.
+    pub fn delete_profile(&self) -> IndyResult<()> {
+        self.get_storage_metadata()?;
+
+        // Deletion is scoped with the profile prefix, so records of other profiles are never touched
+        self.storage.delete_all_with_type_prefix(&self.prefix)?;
+
+        self.storage.delete(PROFILE_METADATA_TYPE, &self.profile)
+    }
+
+    fn _type(&self, type_: &[u8]) -> Vec<u8> {
+        let mut res = self.prefix.clone();
+        res.extend_from_slice(type_);
+        res
+    }
+}
+
+impl WalletStorage for ProfileStorage {
+    fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
+        let mut record = self.storage.get(&self._type(type_), id, options)?;
+        record.type_ = record.type_.map(|_| type_.to_vec());
+        Ok(record)
+    }
+
+    fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        self.storage.add(&self._type(type_), id, value, tags)
+    }
+
+    fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        self.storage.update(&self._type(type_), id, value)
+    }
+
+    fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self.storage.add_tags(&self._type(type_), id, tags)
+    }
+
+    fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        self.storage.update_tags(&self._type(type_), id, tags)
+    }
+
+    fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        self.storage.delete_tags(&self._type(type_), id, tag_names)
+    }
+
+    fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        self.storage.delete(&self._type(type_), id)
+    }
+
+    fn add_batch(&self, type_: &[u8], records: &[(Vec<u8>, EncryptedValue, Vec<Tag>)]) -> IndyResult<()> {
+        self.storage.add_batch(&self._type(type_), records)
+    }
+
+    fn delete_batch(&self, type_: &[u8], ids: &[Vec<u8>]) -> IndyResult<()> {
+        self.storage.delete_batch(&self._type(type_), ids)
+    }
+
+    fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        self.storage.get(PROFILE_METADATA_TYPE, &self.profile, "{}")
+            .map_err(|err| match err.kind() {
+                IndyErrorKind::WalletItemNotFound => err.map(IndyErrorKind::WalletNotFound, "Wallet profile not found"),
+                _ => err
+            })?
+            .value
+            .map(|value| value.data)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Wallet profile metadata is empty"))
+    }
+
+    fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.storage.update(PROFILE_METADATA_TYPE, &self.profile, &EncryptedValue::new(metadata.to_vec(), Vec::new()))
+    }
+
+    fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
+        let iter = self.storage.get_all_with_type_prefix(&self.prefix)?;
+        Ok(Box::new(ProfileStorageIterator { iter, prefix: self.prefix.clone() }))
+    }
+
+    fn delete_all_with_type_prefix(&self, prefix: &[u8]) -> IndyResult<()> {
+        self.storage.delete_all_with_type_prefix(&self._type(prefix))
+    }
+
+    fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> IndyResult<Box<dyn StorageIterator>> {
+        let iter = self.storage.search(&self._type(type_), query, options)?;
+        Ok(Box::new(ProfileStorageIterator { iter, prefix: self.prefix.clone() }))
+    }
+
+    /// Space is reclaimed in the whole shared storage, not only in records of the profile.
+    fn compact(&self) -> IndyResult<u64> {
+        self.storage.compact()
+    }
+
+    fn close(&mut self) -> IndyResult<()> {
+        self.storage.close()
+    }
+}
+
+/// Strips the profile prefix from types of the records.
+struct ProfileStorageIterator {
+    iter: Box<dyn StorageIterator>,
+    prefix: Vec<u8>,
+}
+
+impl StorageIterator for ProfileStorageIterator {
+    fn next(&mut self) -> IndyResult<Option<StorageRecord>> {
+        let mut record = match self.iter.next()? {
+            Some(record) => record,
+            None => return Ok(None)
+        };
+
+        if let Some(ref mut type_) = record.type_ {
+            if type_.starts_with(&self.prefix) {
+                type_.drain(..self.prefix.len());
+            }
+        }
+
+        Ok(Some(record))
+    }
+
+    fn get_total_count(&self) -> IndyResult<Option<usize>> {
+        self.iter.get_total_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indy_utils::test;
+
+    use super::*;
+    use super::super::WalletStorageType;
+    use super::super::default::SQLiteStorageType;
+
+    #[test]
+    fn profile_storage_isolates_records() {
+        _cleanup("profile_storage_isolates_records");
+
+        let storage_1 = _profile("profile_storage_isolates_records", "profile_1");
+        let storage_2 = _profile("profile_storage_isolates_records", "profile_2");
+
+        storage_1.add(&_type1(), &_id1(), &_value1(), &[]).unwrap();
+
+        let record = storage_1.get(&_type1(), &_id1(), r##"{"retrieveType": true, "retrieveValue": true}"##).unwrap();
+        assert_eq!(Some(_type1()), record.type_);
+        assert_eq!(_value1(), record.value.unwrap());
+
+        let res = storage_2.get(&_type1(), &_id1(), r##"{"retrieveValue": true}"##);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        storage_2.add(&_type1(), &_id1(), &_value2(), &[]).unwrap();
+
+        let mut iter = storage_2.get_all().unwrap();
+        let record = iter.next().unwrap().unwrap();
+        assert_eq!(Some(_type1()), record.type_);
+        assert_eq!(_value2(), record.value.unwrap());
+        assert!(iter.next().unwrap().is_none());
+
+        let mut iter = storage_1.search(&_type1(), &language::Operator::And(vec![]), None).unwrap();
+        assert_eq!(_value1(), iter.next().unwrap().unwrap().value.unwrap());
+        assert!(iter.next().unwrap().is_none());
+
+        _cleanup("profile_storage_isolates_records");
+    }
+
+    #[test]
+    fn profile_storage_get_all_works_for_profile_with_same_prefix() {
+        _cleanup("profile_storage_get_all_works_for_profile_with_same_prefix");
+
+        let storage_1 = _profile("profile_storage_get_all_works_for_profile_with_same_prefix", "profile");
+        let storage_2 = _profile("profile_storage_get_all_works_for_profile_with_same_prefix", "profile_2");
+
+        storage_1.add(&_type1(), &_id1(), &_value1(), &[]).unwrap();
+        storage_2.add(&_type1(), &_id1(), &_value2(), &[]).unwrap();
+
+        let mut iter = storage_1.get_all().unwrap();
+        let record = iter.next().unwrap().unwrap();
+        assert_eq!(Some(_type1()), record.type_);
+        assert_eq!(_value1(), record.value.unwrap());
+        assert!(iter.next().unwrap().is_none());
+
+        _cleanup("profile_storage_get_all_works_for_profile_with_same_prefix");
+    }
+
+    #[test]
+    fn profile_storage_metadata_works() {
+        _cleanup("profile_storage_metadata_works");
+
+        let storage = _profile("profile_storage_metadata_works", "profile");
+        assert_eq!(_metadata(), storage.get_storage_metadata().unwrap());
+
+        storage.set_storage_metadata(&[1, 2, 3]).unwrap();
+        assert_eq!(vec![1, 2, 3], storage.get_storage_metadata().unwrap());
+
+        let res = storage.create_profile(&_metadata());
+        assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+
+        let storage = ProfileStorage::new(_storage("profile_storage_metadata_works"), "unknown").unwrap();
+        let res = storage.get_storage_metadata();
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+        _cleanup("profile_storage_metadata_works");
+    }
+
+    #[test]
+    fn profile_storage_delete_works() {
+        _cleanup("profile_storage_delete_works");
+
+        let storage_1 = _profile("profile_storage_delete_works", "profile_1");
+        let storage_2 = _profile("profile_storage_delete_works", "profile_2");
+
+        storage_1.add(&_type1(), &_id1(), &_value1(), &[]).unwrap();
+        storage_2.add(&_type1(), &_id1(), &_value2(), &[]).unwrap();
+
+        storage_1.delete_profile().unwrap();
+
+        let res = storage_1.get_storage_metadata();
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+        let res = storage_1.get(&_type1(), &_id1(), r##"{"retrieveValue": true}"##);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let record = storage_2.get(&_type1(), &_id1(), r##"{"retrieveValue": true}"##).unwrap();
+        assert_eq!(_value2(), record.value.unwrap());
+
+        _cleanup("profile_storage_delete_works");
+    }
+
+    #[test]
+    fn profile_storage_new_fails_for_nul_in_profile() {
+        _cleanup("profile_storage_new_fails_for_nul_in_profile");
+
+        let res = ProfileStorage::new(_storage("profile_storage_new_fails_for_nul_in_profile"), "profile\u{0}1");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        _cleanup("profile_storage_new_fails_for_nul_in_profile");
+    }
+
+    fn _cleanup(name: &str) {
+        test::cleanup_storage(name)
+    }
+
+    fn _storage(name: &str) -> Box<dyn WalletStorage> {
+        let storage_type = SQLiteStorageType::new();
+
+        match storage_type.create_storage(name, None, None, &[]) {
+            Ok(()) => {}
+            Err(ref err) if err.kind() == IndyErrorKind::WalletAlreadyExists => {}
+            Err(err) => panic!("Can't create storage: {:?}", err)
+        }
+
+        storage_type.open_storage(name, None, None).unwrap()
+    }
+
+    fn _profile(name: &str, profile: &str) -> ProfileStorage {
+        let storage = ProfileStorage::new(_storage(name), profile).unwrap();
+        storage.create_profile(&_metadata()).unwrap();
+        storage
+    }
+
+    fn _metadata() -> Vec<u8> {
+        vec![9; 32]
+    }
+
+    fn _type1() -> Vec<u8> {
+        vec![1, 2, 1]
+    }
+
+    fn _id1() -> Vec<u8> {
+        vec![3, 4, 1]
+    }
+
+    fn _value1() -> EncryptedValue {
+        EncryptedValue::new(vec![5, 6, 1], vec![7, 8, 1])
+    }
+
+    fn _value2() -> EncryptedValue {
+        EncryptedValue::new(vec![5, 6, 2], vec![7, 8, 2])
+    }
+}
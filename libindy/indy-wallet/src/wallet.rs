@@ -1,5 +1,7 @@
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use indy_utils::crypto::{hmacsha256, chacha20poly1305_ietf};
 use indy_utils::wql::Query;
@@ -12,7 +14,20 @@ use super::storage;
 use super::iterator::WalletIterator;
 use super::encryption::*;
 use super::query_encryption::encrypt_query;
-use super::WalletRecord;
+use super::slow_log::{SlowQueryLog, SlowQueryRecord};
+use super::{StorageEncryptionReport, StorageEncryptionViolation, WalletRecord};
+use indy_api_types::domain::wallet::Config;
+
+// Plaintext is looked for by its first bytes: 8 bytes are unlikely to occur in ciphertext by chance
+const PLAINTEXT_MARKER_LEN: usize = 8;
+
+// Magic number of zstd frame. UTF-8 string never starts with it, so compressed values are told apart from plain ones.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+#[cfg(feature = "compression")]
+const COMPRESSION_LEVEL: i32 = 3;
+// Decompressed value is bounded, so a crafted value can't exhaust memory
+#[cfg(feature = "compression")]
+const MAX_DECOMPRESSED_VALUE_LEN: usize = 16 * 1024 * 1024;
 
 #[derive(Serialize, Deserialize)]
 pub(super) struct Keys {
@@ -23,6 +38,17 @@ pub(super) struct Keys {
     pub tag_name_key: chacha20poly1305_ietf::Key,
     pub tag_value_key: chacha20poly1305_ietf::Key,
     pub tags_hmac_key: hmacsha256::Key,
+    // Wallets created before type-level key separation use the keys above for records of any type
+    #[serde(default)]
+    pub per_type_keys: bool,
+}
+
+/// Keys used to encrypt values and tags of records of a single type.
+pub(super) struct TypeKeys {
+    pub value_key: chacha20poly1305_ietf::Key,
+    pub tag_name_key: chacha20poly1305_ietf::Key,
+    pub tag_value_key: chacha20poly1305_ietf::Key,
+    pub tags_hmac_key: hmacsha256::Key,
 }
 
 impl Keys {
@@ -35,6 +61,32 @@ impl Keys {
             tag_name_key: chacha20poly1305_ietf::gen_key(),
             tag_value_key: chacha20poly1305_ietf::gen_key(),
             tags_hmac_key: hmacsha256::gen_key(),
+            per_type_keys: true,
+        }
+    }
+
+    /// Derives value and tag keys for the given record type with HKDF, so leak of keys of one type
+    /// doesn't expose records of other types. Type and name keys are shared as they are needed to locate a record.
+    pub fn for_type(&self, type_: &str) -> TypeKeys {
+        if !self.per_type_keys {
+            return TypeKeys {
+                value_key: self.value_key.clone(),
+                tag_name_key: self.tag_name_key.clone(),
+                tag_value_key: self.tag_value_key.clone(),
+                tags_hmac_key: self.tags_hmac_key.clone(),
+            };
+        }
+
+        let derive = |key: &[u8], purpose: &str| {
+            let info = format!("{}:{}", purpose, type_);
+            hmacsha256::hkdf(key, info.as_bytes())
+        };
+
+        TypeKeys {
+            value_key: chacha20poly1305_ietf::Key::new(derive(&self.value_key[..], "value_key")),
+            tag_name_key: chacha20poly1305_ietf::Key::new(derive(&self.tag_name_key[..], "tag_name_key")),
+            tag_value_key: chacha20poly1305_ietf::Key::new(derive(&self.tag_value_key[..], "tag_value_key")),
+            tags_hmac_key: hmacsha256::Key::new(derive(&self.tags_hmac_key[..], "tags_hmac_key")),
         }
     }
 
@@ -73,9 +125,36 @@ impl EncryptedValue {
     }
 
     pub fn encrypt(data: &str, key: &chacha20poly1305_ietf::Key) -> Self {
+        EncryptedValue::_encrypt(data.as_bytes(), key)
+    }
+
+    /// Compresses the value before encryption if it is longer than the threshold and compression makes it shorter.
+    #[cfg(feature = "compression")]
+    pub fn encrypt_compressed(data: &str, key: &chacha20poly1305_ietf::Key, compression_threshold: Option<usize>) -> IndyResult<Self> {
+        match compression_threshold {
+            Some(threshold) if data.len() > threshold => {
+                let compressed = zstd::block::compress(data.as_bytes(), COMPRESSION_LEVEL)
+                    .to_indy(IndyErrorKind::InvalidState, "Can't compress value")?;
+
+                if compressed.len() < data.len() {
+                    Ok(EncryptedValue::_encrypt(&compressed, key))
+                } else {
+                    Ok(EncryptedValue::encrypt(data, key))
+                }
+            }
+            _ => Ok(EncryptedValue::encrypt(data, key))
+        }
+    }
+
+    #[cfg(not(feature = "compression"))]
+    pub fn encrypt_compressed(data: &str, key: &chacha20poly1305_ietf::Key, _compression_threshold: Option<usize>) -> IndyResult<Self> {
+        Ok(EncryptedValue::encrypt(data, key))
+    }
+
+    fn _encrypt(data: &[u8], key: &chacha20poly1305_ietf::Key) -> Self {
         let value_key = chacha20poly1305_ietf::gen_key();
         EncryptedValue::new(
-            encrypt_as_not_searchable(data.as_bytes(), &value_key),
+            encrypt_as_not_searchable(data, &value_key),
             encrypt_as_not_searchable(&value_key[..], key)
         )
     }
@@ -88,12 +167,29 @@ impl EncryptedValue {
 
         value_key_bytes.zeroize();
 
-        let res = String::from_utf8(decrypt_merged(&self.data, &value_key)?)
+        let mut data = decrypt_merged(&self.data, &value_key)?;
+
+        if data.starts_with(&ZSTD_MAGIC) {
+            data = EncryptedValue::_decompress(&data)?;
+        }
+
+        let res = String::from_utf8(data)
             .to_indy(IndyErrorKind::InvalidState, "Invalid UTF8 string inside of value")?;
 
         Ok(res)
     }
 
+    #[cfg(feature = "compression")]
+    fn _decompress(data: &[u8]) -> IndyResult<Vec<u8>> {
+        zstd::block::decompress(data, MAX_DECOMPRESSED_VALUE_LEN)
+            .to_indy(IndyErrorKind::InvalidState, "Can't decompress value")
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn _decompress(_data: &[u8]) -> IndyResult<Vec<u8>> {
+        Err(err_msg(IndyErrorKind::InvalidState, "Value is compressed, but wallet is built without compression feature"))
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut result = self.key.clone();
         result.extend_from_slice(self.data.as_slice());
@@ -112,55 +208,163 @@ impl EncryptedValue {
     }
 }
 
+/// Options of the opened wallet taken from the wallet config.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(super) struct WalletOptions {
+    pub compression_threshold: Option<usize>,
+    pub slow_query_threshold: Option<u64>,
+    pub compaction_interval: Option<u64>,
+    pub idle_timeout: Option<u64>,
+}
+
+impl<'a> From<&'a Config> for WalletOptions {
+    fn from(config: &'a Config) -> Self {
+        WalletOptions {
+            compression_threshold: config.compression_threshold,
+            slow_query_threshold: config.slow_query_threshold,
+            compaction_interval: config.compaction_interval,
+            idle_timeout: config.idle_timeout,
+        }
+    }
+}
+
 pub(super) struct Wallet {
     id: String,
     storage: Box<dyn storage::WalletStorage>,
     keys: Rc<Keys>,
+    compression_threshold: Option<usize>,
+    slow_query_log: Option<SlowQueryLog>,
+    compaction_interval: Option<Duration>,
+    last_compaction: Cell<Instant>,
+    compaction_pending: Cell<bool>,
+    idle_timeout: Option<Duration>,
+    last_used: Cell<Instant>,
+    storage_failed: Cell<bool>,
 }
 
 impl Wallet {
-    pub fn new(id: String, storage: Box<dyn storage::WalletStorage>, keys: Rc<Keys>) -> Wallet {
-        Wallet { id, storage, keys }
+    pub fn new(id: String, storage: Box<dyn storage::WalletStorage>, keys: Rc<Keys>, options: WalletOptions) -> Wallet {
+        Wallet {
+            id,
+            storage,
+            keys,
+            compression_threshold: options.compression_threshold,
+            slow_query_log: options.slow_query_threshold.map(SlowQueryLog::new),
+            compaction_interval: options.compaction_interval.map(Duration::from_secs),
+            last_compaction: Cell::new(Instant::now()),
+            compaction_pending: Cell::new(false),
+            idle_timeout: options.idle_timeout.map(Duration::from_secs),
+            last_used: Cell::new(Instant::now()),
+            storage_failed: Cell::new(false),
+        }
+    }
+
+    /// Runs the storage operation recording it to the slow query log if the log is enabled.
+    /// Also tracks the last use of the wallet and failures of the storage.
+    fn _measure<T, F, R>(&self, operation: &str, type_: Option<&str>, f: F, rows: R) -> IndyResult<T>
+        where F: FnOnce() -> IndyResult<T>, R: FnOnce(&IndyResult<T>) -> Option<usize> {
+        self.last_used.set(Instant::now());
+
+        let res = match self.slow_query_log {
+            Some(ref slow_query_log) => slow_query_log.measure(operation, type_, f, rows),
+            None => f()
+        };
+
+        if let Err(ref err) = res {
+            if err.kind() == IndyErrorKind::WalletStorageError || err.kind() == IndyErrorKind::IOError {
+                warn!("Storage of wallet {} failed on {}: {:?}", self.id, operation, err);
+                self.storage_failed.set(true);
+            }
+        }
+
+        res
+    }
+
+    pub fn get_slow_query_log(&self) -> Vec<SlowQueryRecord> {
+        self.slow_query_log.as_ref()
+            .map(SlowQueryLog::records)
+            .unwrap_or_default()
     }
 
     pub fn add(&self, type_: &str, name: &str, value: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
+        _check_tag_policies(tags)?;
+
         let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let evalue = EncryptedValue::encrypt(value, &self.keys.value_key);
-        let etags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
-        self.storage.add(&etype, &ename, &evalue, &etags)?;
+        let type_keys = self.keys.for_type(type_);
+        let evalue = EncryptedValue::encrypt_compressed(value, &type_keys.value_key, self.compression_threshold)?;
+        let etags = encrypt_tags(tags, &type_keys.tag_name_key, &type_keys.tag_value_key, &type_keys.tags_hmac_key);
+        self._measure("add", Some(type_), || self.storage.add(&etype, &ename, &evalue, &etags), _single_row)?;
+        Ok(())
+    }
+
+    /// Adds records `(name, value, tags)` of the same type in a single storage transaction.
+    pub fn add_batch(&self, type_: &str, records: &[(&str, &str, &HashMap<String, String>)]) -> IndyResult<()> {
+        let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+        let type_keys = self.keys.for_type(type_);
+
+        let mut erecords = Vec::with_capacity(records.len());
+
+        for &(name, value, tags) in records {
+            _check_tag_policies(tags)?;
+
+            let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
+            let evalue = EncryptedValue::encrypt_compressed(value, &type_keys.value_key, self.compression_threshold)?;
+            let etags = encrypt_tags(tags, &type_keys.tag_name_key, &type_keys.tag_value_key, &type_keys.tags_hmac_key);
+            erecords.push((ename, evalue, etags));
+        }
+
+        let count = erecords.len();
+        self._measure("add_batch", Some(type_), || self.storage.add_batch(&etype, &erecords), |res: &IndyResult<()>| res.as_ref().ok().map(|_| count))?;
         Ok(())
     }
 
     pub fn add_tags(&self, type_: &str, name: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
+        _check_tag_policies(tags)?;
+
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_tags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
-        self.storage.add_tags(&encrypted_type, &encrypted_name, &encrypted_tags)?;
+        let type_keys = self.keys.for_type(type_);
+
+        // Tag switching between encrypted and un-encrypted replaces the tag stored the other way,
+        // so no stale copy of it is left in the storage
+        let other_tag_names = _other_policy_tag_names(tags);
+        if !other_tag_names.is_empty() {
+            let other_tag_names: Vec<&str> = other_tag_names.iter().map(String::as_str).collect();
+            let encrypted_other_tag_names = encrypt_tag_names(&other_tag_names, &type_keys.tag_name_key, &type_keys.tags_hmac_key);
+            self._measure("delete_tags", Some(type_), || self.storage.delete_tags(&encrypted_type, &encrypted_name, &encrypted_other_tag_names[..]), _single_row)?;
+        }
+
+        let encrypted_tags = encrypt_tags(tags, &type_keys.tag_name_key, &type_keys.tag_value_key, &type_keys.tags_hmac_key);
+        self._measure("add_tags", Some(type_), || self.storage.add_tags(&encrypted_type, &encrypted_name, &encrypted_tags), _single_row)?;
         Ok(())
     }
 
     pub fn update_tags(&self, type_: &str, name: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
+        _check_tag_policies(tags)?;
+
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_tags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
-        self.storage.update_tags(&encrypted_type, &encrypted_name, &encrypted_tags)?;
+        let type_keys = self.keys.for_type(type_);
+        let encrypted_tags = encrypt_tags(tags, &type_keys.tag_name_key, &type_keys.tag_value_key, &type_keys.tags_hmac_key);
+        self._measure("update_tags", Some(type_), || self.storage.update_tags(&encrypted_type, &encrypted_name, &encrypted_tags), _single_row)?;
         Ok(())
     }
 
     pub fn delete_tags(&self, type_: &str, name: &str, tag_names: &[&str]) -> IndyResult<()> {
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_tag_names = encrypt_tag_names(tag_names, &self.keys.tag_name_key, &self.keys.tags_hmac_key);
-        self.storage.delete_tags(&encrypted_type, &encrypted_name, &encrypted_tag_names[..])?;
+        let type_keys = self.keys.for_type(type_);
+        let encrypted_tag_names = encrypt_tag_names(tag_names, &type_keys.tag_name_key, &type_keys.tags_hmac_key);
+        self._measure("delete_tags", Some(type_), || self.storage.delete_tags(&encrypted_type, &encrypted_name, &encrypted_tag_names[..]), _single_row)?;
         Ok(())
     }
 
     pub fn update(&self, type_: &str, name: &str, new_value: &str) -> IndyResult<()> {
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_value = EncryptedValue::encrypt(new_value, &self.keys.value_key);
-        self.storage.update(&encrypted_type, &encrypted_name, &encrypted_value)?;
+        let encrypted_value = EncryptedValue::encrypt_compressed(new_value, &self.keys.for_type(type_).value_key, self.compression_threshold)?;
+        self._measure("update", Some(type_), || self.storage.update(&encrypted_type, &encrypted_name, &encrypted_value), _single_row)?;
         Ok(())
     }
 
@@ -168,14 +372,16 @@ impl Wallet {
         let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
 
-        let result = self.storage.get(&etype, &ename, options)?;
+        let result = self._measure("get", Some(type_), || self.storage.get(&etype, &ename, options), _single_row)?;
+
+        let type_keys = self.keys.for_type(type_);
 
         let value = match result.value {
             None => None,
-            Some(encrypted_value) => Some(encrypted_value.decrypt(&self.keys.value_key)?)
+            Some(encrypted_value) => Some(encrypted_value.decrypt(&type_keys.value_key)?)
         };
 
-        let tags = decrypt_tags(&result.tags, &self.keys.tag_name_key, &self.keys.tag_value_key)?;
+        let tags = decrypt_tags(&result.tags, &type_keys.tag_name_key, &type_keys.tag_value_key)?;
 
         Ok(WalletRecord::new(String::from(name), result.type_.map(|_| type_.to_string()), value, tags))
     }
@@ -184,18 +390,87 @@ impl Wallet {
         let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
 
-        self.storage.delete(&etype, &ename)?;
+        self._measure("delete", Some(type_), || self.storage.delete(&etype, &ename), _single_row)?;
+        self.compaction_pending.set(true);
         Ok(())
     }
 
+    /// Deletes records of the same type in a single storage transaction.
+    pub fn delete_batch(&self, type_: &str, names: &[&str]) -> IndyResult<()> {
+        let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+        let enames: Vec<Vec<u8>> = names.iter()
+            .map(|name| encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key))
+            .collect();
+
+        let count = enames.len();
+        self._measure("delete_batch", Some(type_), || self.storage.delete_batch(&etype, &enames), |res: &IndyResult<()>| res.as_ref().ok().map(|_| count))?;
+        self.compaction_pending.set(true);
+        Ok(())
+    }
+
+    /// Reclaims storage space left by deleted records. Returns number of reclaimed bytes.
+    pub fn compact(&self) -> IndyResult<u64> {
+        let reclaimed = self._measure("compact", None, || self.storage.compact(), |_| None)?;
+        self.last_compaction.set(Instant::now());
+        self.compaction_pending.set(false);
+        Ok(reclaimed)
+    }
+
+    /// Compacts the storage if records were deleted and the compaction interval set in the wallet config
+    /// has passed since the last compaction. Called by the executor while it has no commands to process.
+    pub fn compact_if_scheduled(&self) {
+        let scheduled = self.next_compaction()
+            .map(|next_compaction| next_compaction <= Instant::now())
+            .unwrap_or(false);
+
+        if scheduled {
+            // Maintenance isn't use of the wallet, so it doesn't postpone the idle timeout
+            let last_used = self.last_used.get();
+            let res = self.compact();
+            self.last_used.set(last_used);
+
+            match res {
+                Ok(reclaimed) => debug!("Scheduled compaction of wallet {} reclaimed {} bytes", self.id, reclaimed),
+                Err(err) => warn!("Scheduled compaction of wallet {} failed: {:?}", self.id, err)
+            }
+        }
+    }
+
+    /// Time of the next scheduled compaction if records were deleted since the last one.
+    pub fn next_compaction(&self) -> Option<Instant> {
+        if !self.compaction_pending.get() {
+            return None;
+        }
+
+        self.compaction_interval
+            .and_then(|interval| self.last_compaction.get().checked_add(interval))
+    }
+
+    /// Time when the wallet is closed by the idle timeout set in the wallet config.
+    pub fn idle_deadline(&self) -> Option<Instant> {
+        self.idle_timeout
+            .and_then(|timeout| self.last_used.get().checked_add(timeout))
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle_deadline()
+            .map(|idle_deadline| idle_deadline <= Instant::now())
+            .unwrap_or(false)
+    }
+
+    /// Whether the storage failed, so the wallet can't be used anymore.
+    pub fn is_storage_failed(&self) -> bool {
+        self.storage_failed.get()
+    }
+
     pub fn search<'a>(&'a self, type_: &str, query: &str, options: Option<&str>) -> IndyResult<WalletIterator> {
         let parsed_query: Query = ::serde_json::from_str(query)
             .map_err(|err| IndyError::from_msg(IndyErrorKind::WalletQueryError, err))?;
 
-        let encrypted_query = encrypt_query(parsed_query, &self.keys)?;
+        let encrypted_query = encrypt_query(parsed_query, &self.keys.for_type(type_))?;
         let encrypted_type_ = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, options)?;
-        let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(&self.keys));
+        let storage_iterator = self._measure("search", Some(type_), || self.storage.search(&encrypted_type_, &encrypted_query, options), _total_count)?;
+        let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(&self.keys), Some(type_.to_string()));
         Ok(wallet_iterator)
     }
 
@@ -205,15 +480,130 @@ impl Wallet {
     }
 
     pub fn get_all(&self) -> IndyResult<WalletIterator> {
-        let all_items = self.storage.get_all()?;
-        Ok(WalletIterator::new(all_items, Rc::clone(&self.keys)))
+        let all_items = self._measure("get_all", None, || self.storage.get_all(), _total_count)?;
+        Ok(WalletIterator::new(all_items, Rc::clone(&self.keys), None))
     }
 
     pub fn get_id<'a>(&'a self) -> &'a str {
         &self.id
     }
+
+    pub fn verify_storage_encryption(&self, sample_size: Option<usize>) -> IndyResult<StorageEncryptionReport> {
+        let mut report = StorageEncryptionReport::default();
+
+        let mut storage_iterator = self.storage.get_all()?;
+        let mut raw_records: Vec<storage::StorageRecord> = Vec::new();
+
+        while sample_size.map(|size| raw_records.len() < size).unwrap_or(true) {
+            match storage_iterator.next()? {
+                Some(record) => raw_records.push(record),
+                None => break
+            }
+        }
+
+        // Index plaintext of all sampled records by its first bytes to look for it in every raw row
+        // with a single pass over the row
+        let mut markers: HashSet<Vec<u8>> = HashSet::new();
+        let mut add_marker = |plaintext: &[u8]| {
+            if plaintext.len() >= PLAINTEXT_MARKER_LEN {
+                markers.insert(plaintext[..PLAINTEXT_MARKER_LEN].to_vec());
+            }
+        };
+
+        for (index, raw_record) in raw_records.iter().enumerate() {
+            let record = match decrypt_storage_record(raw_record, &self.keys, None) {
+                Ok(record) => record,
+                Err(err) => {
+                    report.violations.push(StorageEncryptionViolation {
+                        record: index,
+                        field: "record".to_string(),
+                        reason: format!("Row can't be decrypted with wallet keys: {}", err),
+                    });
+                    continue;
+                }
+            };
+
+            add_marker(record.id.as_bytes());
+            record.type_.iter().for_each(|type_| add_marker(type_.as_bytes()));
+            record.value.iter().for_each(|value| add_marker(value.as_bytes()));
+
+            for (tag_name, tag_value) in record.tags.iter().flat_map(|tags| tags.iter()) {
+                if tag_name.starts_with('~') {
+                    report.plaintext_tags += 1;
+                    add_marker(tag_name[1..].as_bytes());
+                } else {
+                    add_marker(tag_name.as_bytes());
+                    add_marker(tag_value.as_bytes());
+                }
+            }
+        }
+
+        for (index, raw_record) in raw_records.iter().enumerate() {
+            let mut fields: Vec<(&str, Vec<u8>)> = vec![("id", raw_record.id.clone())];
+
+            if let Some(ref type_) = raw_record.type_ {
+                fields.push(("type", type_.clone()));
+            }
+
+            if let Some(ref value) = raw_record.value {
+                fields.push(("value", value.to_bytes()));
+            }
+
+            for tag in raw_record.tags.iter().flat_map(|tags| tags.iter()) {
+                match *tag {
+                    storage::Tag::Encrypted(ref name, ref value) => {
+                        fields.push(("tagName", name.clone()));
+                        fields.push(("tagValue", value.clone()));
+                    }
+                    storage::Tag::PlainText(ref name, _) => fields.push(("tagName", name.clone()))
+                }
+            }
+
+            for (field, data) in fields {
+                if data.windows(PLAINTEXT_MARKER_LEN).any(|window| markers.contains(window)) {
+                    report.violations.push(StorageEncryptionViolation {
+                        record: index,
+                        field: field.to_string(),
+                        reason: "Raw data contains plaintext of wallet record".to_string(),
+                    });
+                }
+            }
+        }
+
+        report.checked_records = raw_records.len();
+        report.encrypted = report.violations.is_empty();
+
+        Ok(report)
+    }
+}
+
+/// Tag is stored either encrypted or un-encrypted ("~" prefix), so it can't be given both ways at once.
+fn _check_tag_policies(tags: &HashMap<String, String>) -> IndyResult<()> {
+    match tags.keys().find(|name| name.starts_with('~') && tags.contains_key(&name[1..])) {
+        Some(name) => Err(err_msg(IndyErrorKind::InvalidStructure,
+                                  format!("Tag \"{}\" is given both encrypted and un-encrypted", &name[1..]))),
+        None => Ok(())
+    }
 }
 
+/// Names of the same tags stored the other way: un-encrypted for encrypted ones and vice versa.
+fn _other_policy_tag_names(tags: &HashMap<String, String>) -> Vec<String> {
+    tags.keys()
+        .map(|name| if name.starts_with('~') { name[1..].to_string() } else { format!("~{}", name) })
+        .collect()
+}
+
+fn _single_row<T>(res: &IndyResult<T>) -> Option<usize> {
+    res.as_ref().ok().map(|_| 1)
+}
+
+fn _total_count(res: &IndyResult<Box<dyn storage::StorageIterator>>) -> Option<usize> {
+    res.as_ref().ok()
+        .and_then(|iter| iter.get_total_count().ok())
+        .and_then(|total_count| total_count)
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,6 +647,34 @@ mod tests {
         test::cleanup_wallet("wallet_get_id_works");
     }
 
+    #[test]
+    fn keys_for_type_works() {
+        let keys = Keys::new();
+
+        let type1_keys = keys.for_type(_type1());
+        let type2_keys = keys.for_type(_type2());
+
+        assert_eq!(type1_keys.value_key, keys.for_type(_type1()).value_key);
+        assert_ne!(type1_keys.value_key, keys.value_key);
+        assert_ne!(type1_keys.value_key, type2_keys.value_key);
+        assert_ne!(type1_keys.tag_name_key, type2_keys.tag_name_key);
+        assert_ne!(type1_keys.tag_value_key, type2_keys.tag_value_key);
+        assert_ne!(type1_keys.tags_hmac_key, type2_keys.tags_hmac_key);
+    }
+
+    #[test]
+    fn keys_for_type_works_for_legacy_keys() {
+        let mut keys = Keys::new();
+        keys.per_type_keys = false;
+
+        let type_keys = keys.for_type(_type1());
+
+        assert_eq!(type_keys.value_key, keys.value_key);
+        assert_eq!(type_keys.tag_name_key, keys.tag_name_key);
+        assert_eq!(type_keys.tag_value_key, keys.tag_value_key);
+        assert_eq!(type_keys.tags_hmac_key, keys.tags_hmac_key);
+    }
+
     #[test]
     fn wallet_add_get_works() {
         test::cleanup_wallet("wallet_add_get_works");
@@ -274,6 +692,120 @@ mod tests {
         test::cleanup_wallet("wallet_add_get_works");
     }
 
+    #[test]
+    fn wallet_is_storage_failed_works() {
+        test::cleanup_wallet("wallet_is_storage_failed_works");
+        {
+            let mut wallet = _wallet("wallet_is_storage_failed_works");
+
+            let res = wallet.get(_type1(), _id1(), &_fetch_options(false, true, true));
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+            assert!(!wallet.is_storage_failed());
+
+            let res: IndyResult<()> = wallet._measure("add", Some(_type1()), || Err(err_msg(IndyErrorKind::WalletStorageError, "Storage failed")), _single_row);
+            assert_kind!(IndyErrorKind::WalletStorageError, res);
+            assert!(wallet.is_storage_failed());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_is_storage_failed_works");
+    }
+
+    #[test]
+    fn wallet_is_idle_works() {
+        test::cleanup_wallet("wallet_is_idle_works");
+        {
+            let mut wallet = _wallet("wallet_is_idle_works");
+            assert!(wallet.idle_deadline().is_none());
+            assert!(!wallet.is_idle());
+
+            wallet.idle_timeout = Some(Duration::from_secs(100));
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            assert!(!wallet.is_idle());
+
+            wallet.idle_timeout = Some(Duration::from_secs(0));
+            assert!(wallet.is_idle());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_is_idle_works");
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn wallet_add_get_works_for_compressed_value() {
+        test::cleanup_wallet("wallet_add_get_works_for_compressed_value");
+        {
+            let mut wallet = _wallet("wallet_add_get_works_for_compressed_value");
+            wallet.compression_threshold = Some(100);
+
+            let value = _large_value();
+            wallet.add(_type1(), _id1(), &value, &_tags()).unwrap();
+            wallet.add(_type1(), _id2(), _value1(), &_tags()).unwrap();
+
+            let record = wallet.get(_type1(), _id1(), &_fetch_options(false, true, true)).unwrap();
+            assert_eq!(record.value.unwrap(), value);
+
+            let record = wallet.get(_type1(), _id2(), &_fetch_options(false, true, true)).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+
+            wallet.update(_type1(), _id2(), &value).unwrap();
+
+            let record = wallet.get(_type1(), _id2(), &_fetch_options(false, true, true)).unwrap();
+            assert_eq!(record.value.unwrap(), value);
+
+            wallet.close().unwrap();
+
+            let mut wallet = _exists_wallet("wallet_add_get_works_for_compressed_value");
+
+            let record = wallet.get(_type1(), _id1(), &_fetch_options(false, true, true)).unwrap();
+            assert_eq!(record.value.unwrap(), value);
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_add_get_works_for_compressed_value");
+    }
+
+    #[test]
+    fn wallet_get_slow_query_log_works() {
+        test::cleanup_wallet("wallet_get_slow_query_log_works");
+        {
+            let mut wallet = _wallet("wallet_get_slow_query_log_works");
+            assert!(wallet.get_slow_query_log().is_empty());
+
+            wallet.slow_query_log = Some(SlowQueryLog::new(0));
+
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.get(_type1(), _id1(), &_fetch_options(false, true, true)).unwrap();
+
+            let slow_query_log = wallet.get_slow_query_log();
+            assert_eq!(2, slow_query_log.len());
+            assert_eq!("add", slow_query_log[0].operation);
+            assert_eq!(Some(_type1().to_string()), slow_query_log[0].type_);
+            assert_eq!(Some(1), slow_query_log[0].rows);
+            assert_eq!("get", slow_query_log[1].operation);
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_get_slow_query_log_works");
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn encrypted_value_encrypt_compressed_works() {
+        let key = chacha20poly1305_ietf::gen_key();
+        let value = _large_value();
+
+        let plain = EncryptedValue::encrypt(&value, &key);
+        let compressed = EncryptedValue::encrypt_compressed(&value, &key, Some(100)).unwrap();
+        assert!(compressed.data.len() < plain.data.len());
+        assert_eq!(value, compressed.decrypt(&key).unwrap());
+
+        let not_compressed = EncryptedValue::encrypt_compressed(&value, &key, Some(value.len())).unwrap();
+        assert_eq!(plain.data.len(), not_compressed.data.len());
+        assert_eq!(value, not_compressed.decrypt(&key).unwrap());
+    }
+
     #[test]
     fn wallet_add_get_works_for_reopen() {
         test::cleanup_wallet("wallet_add_get_works_for_reopen");
@@ -415,6 +947,74 @@ mod tests {
         test::cleanup_wallet("wallet_add_tags_works");
     }
 
+    #[test]
+    fn wallet_add_tags_works_for_changed_policy() {
+        test::cleanup_wallet("wallet_add_tags_works_for_changed_policy");
+        {
+            let tags = jsonmap!({
+                "tag_name_1": "tag_value_1",
+                "~tag_name_2": "tag_value_2",
+            });
+
+            let mut wallet = _wallet("wallet_add_tags_works_for_changed_policy");
+            wallet.add(_type1(), _id1(), _value1(), &tags).unwrap();
+
+            let new_tags = jsonmap!({
+                "~tag_name_1": "tag_value_1",
+                "tag_name_2": "tag_value_2",
+            });
+
+            wallet.add_tags(_type1(), _id1(), &new_tags).unwrap();
+            let record = wallet.get(_type1(), _id1(), &_fetch_options(false, true, true)).unwrap();
+            assert_eq!(record.tags.unwrap(), new_tags);
+
+            let mut iterator = wallet.search(_type1(),
+                                             &jsonstr!({"~tag_name_2": "tag_value_2"}),
+                                             Some(&_search_options(true, false, false, false, false))).unwrap();
+            assert!(_fetch_all(&mut iterator).is_empty());
+
+            let mut iterator = wallet.search(_type1(),
+                                             &jsonstr!({"~tag_name_1": {"$like": "tag_%"}}),
+                                             Some(&_search_options(true, false, false, false, false))).unwrap();
+            let records = _fetch_all(&mut iterator);
+            assert_eq!(1, records.len());
+            assert_eq!(_id1(), records[0].id);
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_add_tags_works_for_changed_policy");
+    }
+
+    #[test]
+    fn wallet_add_tags_works_for_both_policies() {
+        test::cleanup_wallet("wallet_add_tags_works_for_both_policies");
+        {
+            let mut wallet = _wallet("wallet_add_tags_works_for_both_policies");
+
+            let tags = jsonmap!({
+                "tag_name_1": "tag_value_1",
+                "~tag_name_1": "tag_value_1",
+            });
+
+            let res = wallet.add(_type1(), _id1(), _value1(), &tags);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+
+            let res = wallet.add_tags(_type1(), _id1(), &tags);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            let res = wallet.update_tags(_type1(), _id1(), &tags);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            let record = wallet.get(_type1(), _id1(), &_fetch_options(false, true, true)).unwrap();
+            assert_eq!(record.tags.unwrap(), _tags());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_add_tags_works_for_both_policies");
+    }
+
     #[test]
     fn wallet_update_tags_works() {
         test::cleanup_wallet("wallet_update_tags_works");
@@ -559,7 +1159,7 @@ mod tests {
             });
 
             let query = serde_json::from_str(&query).unwrap();
-            let encrypted_query = encrypt_query(query, &Keys::new()).unwrap();
+            let encrypted_query = encrypt_query(query, &Keys::new().for_type(_type1())).unwrap();
 
             assert_match!(Operator::And(_), encrypted_query);
         }
@@ -1909,6 +2509,67 @@ mod tests {
         test::cleanup_wallet("wallet_search_works_for_nested");
     }
 
+    #[test]
+    fn wallet_verify_storage_encryption_works() {
+        test::cleanup_wallet("wallet_verify_storage_encryption_works");
+        {
+            let mut wallet = _wallet("wallet_verify_storage_encryption_works");
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.add(_type2(), _id2(), _value2(), &jsonmap!({"~plain_tag": "plain_tag_value"})).unwrap();
+
+            let report = wallet.verify_storage_encryption(None).unwrap();
+            assert_eq!(2, report.checked_records);
+            assert_eq!(1, report.plaintext_tags);
+            assert!(report.encrypted);
+            assert!(report.violations.is_empty());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_verify_storage_encryption_works");
+    }
+
+    #[test]
+    fn wallet_verify_storage_encryption_works_for_sample_size() {
+        test::cleanup_wallet("wallet_verify_storage_encryption_works_for_sample_size");
+        {
+            let mut wallet = _wallet("wallet_verify_storage_encryption_works_for_sample_size");
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.add(_type1(), _id2(), _value2(), &_tags()).unwrap();
+            wallet.add(_type1(), _id3(), _value3(), &_tags()).unwrap();
+
+            let report = wallet.verify_storage_encryption(Some(2)).unwrap();
+            assert_eq!(2, report.checked_records);
+            assert!(report.encrypted);
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_verify_storage_encryption_works_for_sample_size");
+    }
+
+    #[test]
+    fn wallet_verify_storage_encryption_works_for_plaintext_row() {
+        test::cleanup_wallet("wallet_verify_storage_encryption_works_for_plaintext_row");
+        {
+            let mut wallet = _wallet("wallet_verify_storage_encryption_works_for_plaintext_row");
+            let (id, value) = ("plaintext_id", "plaintext_value");
+            wallet.add(_type1(), id, value, &_tags()).unwrap();
+
+            // row written bypassing wallet encryption
+            let etype = encrypt_as_searchable(_type1().as_bytes(), &wallet.keys.type_key, &wallet.keys.item_hmac_key);
+            wallet.storage.add(&etype, id.as_bytes(), &EncryptedValue::new(value.as_bytes().to_vec(), vec![]), &[]).unwrap();
+
+            let report = wallet.verify_storage_encryption(None).unwrap();
+            assert_eq!(2, report.checked_records);
+            assert!(!report.encrypted);
+            assert!(report.violations.iter().any(|violation| violation.field == "record"));
+            assert!(report.violations.iter().any(|violation| violation.field == "id"));
+            assert!(report.violations.iter().any(|violation| violation.field == "value"));
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_verify_storage_encryption_works_for_plaintext_row");
+    }
+
     fn _type1() -> &'static str {
         "type1"
     }
@@ -1941,6 +2602,11 @@ mod tests {
         "value3"
     }
 
+    #[cfg(feature = "compression")]
+    fn _large_value() -> String {
+        json!({"attrs": vec!["attribute_value"; 100]}).to_string()
+    }
+
     fn _tags() -> HashMap<String, String> {
         jsonmap!({"tag1": "tag_value_1"})
     }
@@ -1961,6 +2627,7 @@ mod tests {
             let metadata = Metadata::MetadataArgon(MetadataArgon {
                 master_key_salt: master_key_salt[..].to_vec(),
                 keys: keys.serialize_encrypted(&master_key).unwrap(),
+                key_derivation_params: None,
             });
 
             serde_json::to_vec(&metadata).unwrap()
@@ -1973,7 +2640,7 @@ mod tests {
 
         let storage = storage_type.open_storage(name, None, None).unwrap();
 
-        Wallet::new(name.to_string(), storage, Rc::new(keys))
+        Wallet::new(name.to_string(), storage, Rc::new(keys), WalletOptions::default())
     }
 
     fn _exists_wallet(name: &str) -> Wallet {
@@ -1988,7 +2655,7 @@ mod tests {
         let master_key = _master_key();
         let keys = Keys::deserialize_encrypted(&metadata.keys, &master_key).unwrap();
 
-        Wallet::new(name.to_string(), storage, Rc::new(keys))
+        Wallet::new(name.to_string(), storage, Rc::new(keys), WalletOptions::default())
     }
 
     fn _master_key() -> chacha20poly1305_ietf::Key {
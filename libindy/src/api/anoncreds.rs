@@ -1,6 +1,6 @@
 use indy_api_types::{ErrorCode, IndyHandle, CommandHandle, WalletHandle, SearchHandle, INVALID_SEARCH_HANDLE};
 use indy_api_types::errors::prelude::*;
-use crate::commands::{Command, CommandExecutor};
+use crate::commands::{Command, CommandExecutor, cancellable_callback, dispatch_callback};
 use crate::commands::anoncreds::AnoncredsCommand;
 use crate::commands::anoncreds::issuer::IssuerCommand;
 use crate::commands::anoncreds::prover::ProverCommand;
@@ -8,17 +8,20 @@ use crate::commands::anoncreds::verifier::VerifierCommand;
 use crate::domain::anoncreds::schema::{Schema, AttributeNames, Schemas};
 use crate::domain::crypto::did::DidValue;
 use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionId, CredentialDefinitions};
-use crate::domain::anoncreds::credential_offer::CredentialOffer;
+use crate::domain::anoncreds::credential_offer::{CredentialOffer, CredentialOfferConfig};
 use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
-use crate::domain::anoncreds::credential::{Credential, CredentialValues};
+use crate::domain::anoncreds::credential::{Credential, CredentialIssuanceRequests, CredentialProjection, CredentialValues};
+use crate::domain::anoncreds::issuance_log::IssuanceLog;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryId, RevocationRegistryDefinitions};
 use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
 use crate::domain::anoncreds::proof::Proof;
 use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestExtraQuery};
+use crate::domain::anoncreds::proof_request_template::{ProofRequestTemplate, ProofRequestTemplateParams};
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
 use crate::domain::anoncreds::revocation_registry::RevocationRegistries;
 use crate::domain::anoncreds::revocation_state::{RevocationState, RevocationStates};
+use crate::domain::anoncreds::w3c_credential::W3CCredential;
 use indy_utils::ctypes;
 
 use libc::c_char;
@@ -52,6 +55,9 @@ https://github.com/hyperledger/indy-hipe/blob/c761c583b1e01c1e9d3ceda2b03b35336f
 /// version: a version of the schema
 /// attrs: a list of schema attributes descriptions (the number of attributes should be less or equal than 125)
 ///     `["attr1", "attr2"]`
+///     Attribute names must not be empty, must consist of letters, digits, spaces, `_`, `-` and `.` and must be unique
+///     ignoring case and spaces (see `indy_issuer_validate_schema_attributes`).
+///     The rules can be turned off by "strict_schema_attr_names" of `indy_set_runtime_config`.
 /// cb: Callback that takes command result as parameter
 ///
 /// #Returns
@@ -94,13 +100,13 @@ pub extern fn indy_issuer_create_schema(command_handle: CommandHandle,
                     name,
                     version,
                     attrs,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, id, schema_json) = prepare_result_2!(result, String::new(), String::new());
                         trace!("ursa_cl_credential_public_key_to_json: id: {:?}, schema_json: {:?}", id, schema_json);
                         let id = ctypes::string_to_cstring(id);
                         let schema_json = ctypes::string_to_cstring(schema_json);
                         cb(command_handle, err, id.as_ptr(), schema_json.as_ptr())
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -110,6 +116,54 @@ pub extern fn indy_issuer_create_schema(command_handle: CommandHandle,
     res
 }
 
+/// Validates a list of schema attributes against the rules applied by `indy_issuer_create_schema`
+/// (unless "strict_schema_attr_names" of `indy_set_runtime_config` is turned off).
+/// Allows to check attributes before building and submitting a schema.
+///
+/// The following rules are checked:
+///     - the list isn't empty and contains no more than 125 attributes
+///     - attribute names are not empty and consist of letters, digits, spaces, `_`, `-` and `.`
+///     - attribute names are unique ignoring case and spaces ("First Name" and "firstname" are duplicates)
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// attrs: a list of schema attributes descriptions
+///     `["attr1", "attr2"]`
+/// cb: Callback that takes command result as parameter
+///
+/// #Errors
+/// Common*
+/// CommonInvalidStructure - attributes violate one of the rules, the details can be retrieved with `indy_get_current_error`
+#[no_mangle]
+pub extern fn indy_issuer_validate_schema_attributes(command_handle: CommandHandle,
+                                                     attrs: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_validate_schema_attributes: >>> attrs: {:?}", attrs);
+
+    check_useful_json!(attrs, ErrorCode::CommonInvalidParam2, AttributeNames);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_issuer_validate_schema_attributes: entity >>> attrs: {:?}", attrs);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::ValidateSchemaAttributes(
+                    attrs,
+                    dispatch_callback(Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_validate_schema_attributes:");
+                        cb(command_handle, err)
+                    }))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_validate_schema_attributes: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create credential definition entity that encapsulates credentials issuer DID, credential schema, secrets used for signing credentials
 /// and secrets used for credentials revocation.
 ///
@@ -204,13 +258,13 @@ pub extern fn indy_issuer_create_and_store_credential_def(command_handle: Comman
                     tag,
                     signature_type,
                     config_json,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, cred_def_id, cred_def_json) = prepare_result_2!(result, String::new(), String::new());
                         trace!("indy_issuer_create_and_store_credential_def: cred_def_id: {:?}, cred_def_json: {:?}", cred_def_id, cred_def_json);
                         let cred_def_id = ctypes::string_to_cstring(cred_def_id);
                         let cred_def_json = ctypes::string_to_cstring(cred_def_json);
                         cb(command_handle, err, cred_def_id.as_ptr(), cred_def_json.as_ptr())
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -282,12 +336,12 @@ pub extern fn indy_issuer_rotate_credential_def_start(command_handle: CommandHan
                     wallet_handle,
                     cred_def_id,
                     config_json,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, cred_def_json) = prepare_result_1!(result, String::new());
                         trace!("indy_issuer_rotate_credential_def_start:cred_def_json: {:?}", cred_def_json);
                         let cred_def_json = ctypes::string_to_cstring(cred_def_json);
                         cb(command_handle, err, cred_def_json.as_ptr())
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -333,11 +387,11 @@ pub extern fn indy_issuer_rotate_credential_def_apply(command_handle: CommandHan
                 IssuerCommand::RotateCredentialDefinitionApply(
                     wallet_handle,
                     cred_def_id,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let err = prepare_result!(result);
                         trace!("indy_issuer_rotate_credential_def_apply:");
                         cb(command_handle, err)
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -460,7 +514,7 @@ pub extern fn indy_issuer_create_and_store_revoc_reg(command_handle: CommandHand
                     cred_def_id,
                     config_json,
                     tails_writer_handle,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, revoc_reg_id, revoc_reg_def_json, revoc_reg_json) = prepare_result_3!(result, String::new(), String::new(), String::new());
                         trace!("indy_issuer_create_and_store_credential_def: revoc_reg_id: {:?}, revoc_reg_def_json: {:?}, revoc_reg_json: {:?}",
                                revoc_reg_id, revoc_reg_def_json, revoc_reg_json);
@@ -468,7 +522,7 @@ pub extern fn indy_issuer_create_and_store_revoc_reg(command_handle: CommandHand
                         let revoc_reg_def_json = ctypes::string_to_cstring(revoc_reg_def_json);
                         let revoc_reg_json = ctypes::string_to_cstring(revoc_reg_json);
                         cb(command_handle, err, revoc_reg_id.as_ptr(), revoc_reg_def_json.as_ptr(), revoc_reg_json.as_ptr())
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -523,7 +577,8 @@ pub extern fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
                 IssuerCommand::CreateCredentialOffer(
                     wallet_handle,
                     cred_def_id,
-                    boxed_callback_string!("indy_issuer_create_credential_offer", cb, command_handle)
+                    None,
+                    dispatch_callback(boxed_callback_string!("indy_issuer_create_credential_offer", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -533,6 +588,162 @@ pub extern fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
     res
 }
 
+/// Create credential offer that will be used by Prover for
+/// credential request creation and can be used only during configured period of time.
+/// See `indy_issuer_create_credential_offer`.
+///
+/// Each created offer is stored in the wallet until credential is issued for it.
+/// Credential can't be issued for expired or revoked offer.
+/// Expired offers are removed from the wallet when the next offer is created.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// cred_def_id: id of credential definition stored in the wallet
+/// config_json: credential offer config json:
+///     {
+///         "ttl": int, (optional) - offer lifetime in seconds (one week by default)
+///         "attrs_metadata": (optional) - metadata of the offered attributes (as in Aries credential preview)
+///             carried to the credential values that don't have their own metadata:
+///             {"attr1": {"mime-type": string, (optional) "encoding": string (optional)}}
+///     }
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// credential offer json (see `indy_issuer_create_credential_offer`)
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_issuer_create_credential_offer_with_config(command_handle: CommandHandle,
+                                                              wallet_handle: WalletHandle,
+                                                              cred_def_id: *const c_char,
+                                                              config_json: *const c_char,
+                                                              cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                                   cred_offer_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credential_offer_with_config: >>> wallet_handle: {:?}, cred_def_id: {:?}, config_json: {:?}",
+           wallet_handle, cred_def_id, config_json);
+
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_validatable_json!(config_json, ErrorCode::CommonInvalidParam4, CredentialOfferConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_issuer_create_credential_offer_with_config: entities >>> wallet_handle: {:?}, cred_def_id: {:?}, config_json: {:?}",
+           wallet_handle, cred_def_id, config_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentialOffer(
+                    wallet_handle,
+                    cred_def_id,
+                    Some(config_json),
+                    dispatch_callback(boxed_callback_string!("indy_issuer_create_credential_offer_with_config", cb, command_handle))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credential_offer_with_config: <<< res: {:?}", res);
+
+    res
+}
+
+/// Lists credential offers created by the issuer for which credentials have not been issued yet.
+/// Expired and revoked offers are skipped.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// pending offers json ordered by creation time:
+///     [{
+///         "offer": <credential offer json>,
+///         "created": int, - offer creation time (seconds since Unix Epoch)
+///         "expires": Optional<int>, - offer expiration time (seconds since Unix Epoch)
+///     }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_issuer_list_pending_credential_offers(command_handle: CommandHandle,
+                                                         wallet_handle: WalletHandle,
+                                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                              offers_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_list_pending_credential_offers: >>> wallet_handle: {:?}", wallet_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_issuer_list_pending_credential_offers: entities >>> wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::ListPendingOffers(
+                    wallet_handle,
+                    dispatch_callback(boxed_callback_string!("indy_issuer_list_pending_credential_offers", cb, command_handle))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_list_pending_credential_offers: <<< res: {:?}", res);
+
+    res
+}
+
+/// Revokes credential offer created by the issuer.
+/// Credential can't be issued for revoked offer.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// nonce: nonce of the credential offer to revoke
+/// cb: Callback that takes command result as parameter
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_issuer_revoke_credential_offer(command_handle: CommandHandle,
+                                                  wallet_handle: WalletHandle,
+                                                  nonce: *const c_char,
+                                                  cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_revoke_credential_offer: >>> wallet_handle: {:?}, nonce: {:?}", wallet_handle, nonce);
+
+    check_useful_c_str!(nonce, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_revoke_credential_offer: entities >>> wallet_handle: {:?}, nonce: {:?}", wallet_handle, nonce);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::RevokeOffer(
+                    wallet_handle,
+                    nonce,
+                    dispatch_callback(Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_revoke_credential_offer: err: {:?}", err);
+                        cb(command_handle, err)
+                    }))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_revoke_credential_offer: <<< res: {:?}", res);
+
+    res
+}
+
 /// Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request.
 ///
 /// Cred Request must match Cred Offer. The credential definition and revocation registry definition
@@ -556,6 +767,9 @@ pub extern fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
 ///      "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
 ///     }
 ///   If you want to use empty value for some credential field, you should set "raw" to "" and "encoded" should not be empty
+///   Optional "mime-type" and "encoding" of the attribute (as in Aries credential preview) can be set together with values,
+///   e.g. {"photo": {"raw": "<base64 png>", "encoded": "<int>", "mime-type": "image/png", "encoding": "base64"}}.
+///   They aren't signed by the issuer and are returned by the prover in credential info as "attrs_metadata".
 /// rev_reg_id: id of revocation registry stored in the wallet
 /// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
 /// cb: Callback that takes command result as parameter.
@@ -624,7 +838,7 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
                     cred_values_json,
                     rev_reg_id,
                     blob_storage_reader_handle,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, cred_json, revoc_id, revoc_reg_delta_json) = prepare_result_3!(result, String::new(), None, None);
                         trace!("indy_issuer_create_credential: cred_json: {:?}, revoc_id: {:?}, revoc_reg_delta_json: {:?}",
                                secret!(cred_json.as_str()), secret!(&revoc_id), revoc_reg_delta_json);
@@ -634,7 +848,7 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
                         cb(command_handle, err, cred_json.as_ptr(),
                            revoc_id.as_ref().map(|id| id.as_ptr()).unwrap_or(ptr::null()),
                            revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -644,6 +858,88 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
     res
 }
 
+/// Issue Credentials for the batch of Cred Requests in one call.
+///
+/// Works as `indy_issuer_create_credential` called for every item of the batch, but credential definitions
+/// are loaded from the wallet once per batch and revocation registry deltas are merged into a single one.
+/// All credentials of the batch are issued in the same revocation registry.
+///
+/// Credentials are issued in the order they are listed. The wallet is updated once all credentials of the list are issued,
+/// so if issuance fails for some item then the error is returned and none of the items is registered in the revocation registry.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// credentials_json: a list of credentials to issue.
+///     [{
+///         "cred_offer": a cred offer created by indy_issuer_create_credential_offer,
+///         "cred_req": a credential request created by indy_prover_create_credential_req,
+///         "cred_values": a credential containing attribute values for each of requested attribute names
+///                        (see `cred_values_json` of indy_issuer_create_credential)
+///     }]
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// creds_json: issued credentials in the same order as in `credentials_json`
+///     [{
+///         "cred": <credential json> (see `cred_json` of indy_issuer_create_credential),
+///         "cred_revoc_id": Optional<string> - local id for revocation info
+///     }]
+/// revoc_reg_delta_json: Revocation registry delta json with all newly issued credentials
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             credentials_json: *const c_char,
+                                             rev_reg_id: *const c_char,
+                                             blob_storage_reader_handle: IndyHandle,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  creds_json: *const c_char,
+                                                                  revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credentials: >>> wallet_handle: {:?}, credentials_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+           wallet_handle, credentials_json, rev_reg_id, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(credentials_json, ErrorCode::CommonInvalidParam3, CredentialIssuanceRequests);
+    check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credentials: entities >>> wallet_handle: {:?}, credentials_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+           wallet_handle, secret!(&credentials_json), secret!(&rev_reg_id), blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentials(
+                    wallet_handle,
+                    credentials_json,
+                    rev_reg_id,
+                    blob_storage_reader_handle,
+                    dispatch_callback(Box::new(move |result| {
+                        let (err, creds_json, revoc_reg_delta_json) = prepare_result_2!(result, String::new(), None);
+                        trace!("indy_issuer_create_credentials: creds_json: {:?}, revoc_reg_delta_json: {:?}",
+                               secret!(creds_json.as_str()), revoc_reg_delta_json);
+                        let creds_json = ctypes::string_to_cstring(creds_json);
+                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, creds_json.as_ptr(),
+                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
+                    }))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credentials: <<< res: {:?}", res);
+
+    res
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -701,7 +997,7 @@ pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
                     blob_storage_reader_cfg_handle,
                     rev_reg_id,
                     cred_revoc_id,
-                    boxed_callback_string!("indy_issuer_revoke_credential", cb, command_handle)
+                    dispatch_callback(boxed_callback_string!("indy_issuer_revoke_credential", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -711,6 +1007,61 @@ pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
     res
 }
 
+/// Returns usage statistics of the revocation registry stored in the wallet.
+/// Allows issuer to monitor registry and create a new one before the current is exhausted.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// rev_reg_id: id of revocation registry stored in wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// rev_reg_info_json: Revocation registry statistics json
+/// {
+///     "rev_reg_id": string - id of the revocation registry,
+///     "issuance_type": string - type of issuance (ISSUANCE_BY_DEFAULT or ISSUANCE_ON_DEMAND),
+///     "max_cred_num": int - maximum number of credentials the registry can serve,
+///     "issued": int - number of credentials issued with the registry,
+///     "revoked": int - number of revoked credentials,
+///     "remaining": int - number of credentials that can still be issued,
+///     "last_delta_timestamp": Optional<int> - time the last revocation registry entry (or delta) to publish
+///                                             was created (seconds since Unix Epoch)
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_get_revocation_registry_info(command_handle: CommandHandle,
+                                                       wallet_handle: WalletHandle,
+                                                       rev_reg_id: *const c_char,
+                                                       cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                            rev_reg_info_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_revocation_registry_info: >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam3, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_get_revocation_registry_info: entities >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetRevocationRegistryInfo(
+                    wallet_handle,
+                    rev_reg_id,
+                    dispatch_callback(boxed_callback_string!("indy_issuer_get_revocation_registry_info", cb, command_handle))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_get_revocation_registry_info: <<< res: {:?}", res);
+
+    res
+}
+
 /*/// Recover a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -763,11 +1114,11 @@ pub extern fn indy_issuer_recover_credential(command_handle: CommandHandle,
                     blob_storage_reader_cfg_handle,
                     rev_reg_id,
                     cred_revoc_id,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, revoc_reg_update_json) = prepare_result_1!(result, String::new());
                         let revoc_reg_update_json = ctypes::string_to_cstring(revoc_reg_update_json);
                         cb(command_handle, err, revoc_reg_update_json.as_ptr())
-                    })
+                    }))
                 ))));
 
     prepare_result!(result)
@@ -830,7 +1181,7 @@ pub extern fn indy_issuer_merge_revocation_registry_deltas(command_handle: Comma
                 IssuerCommand::MergeRevocationRegistryDeltas(
                     rev_reg_delta_json,
                     other_rev_reg_delta_json,
-                    boxed_callback_string!("indy_issuer_merge_revocation_registry_deltas", cb, command_handle)
+                    dispatch_callback(boxed_callback_string!("indy_issuer_merge_revocation_registry_deltas", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -840,77 +1191,384 @@ pub extern fn indy_issuer_merge_revocation_registry_deltas(command_handle: Comma
     res
 }
 
-/// Creates a master secret with a given id and stores it in the wallet.
-/// The id must be unique.
+/// Enables the log of credentials issued with the wallet.
+///
+/// After the call every credential created by `indy_issuer_create_credential` or `indy_issuer_create_credentials`
+/// is recorded to the append-only issuer-side log. Entries are chained by their hashes and the head
+/// of the log is signed with the key of the issuer DID.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// master_secret_id: (optional, if not present random one will be generated) new master id
-///
-/// #Returns
-/// out_master_secret_id: Id of generated master secret
+/// issuer_did: DID of the issuer signing the log. Its key must be stored in the wallet.
+/// cb: Callback that takes command result as parameter.
 ///
 /// #Errors
-/// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub extern fn indy_prover_create_master_secret(command_handle: CommandHandle,
-                                               wallet_handle: WalletHandle,
-                                               master_secret_id: *const c_char,
-                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                    out_master_secret_id: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_create_master_secret: >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+pub extern fn indy_issuer_enable_issuance_log(command_handle: CommandHandle,
+                                              wallet_handle: WalletHandle,
+                                              issuer_did: *const c_char,
+                                              cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_enable_issuance_log: >>> wallet_handle: {:?}, issuer_did: {:?}", wallet_handle, issuer_did);
 
-    check_useful_opt_c_str!(master_secret_id, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_string!(issuer_did, ErrorCode::CommonInvalidParam3, DidValue);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
-    trace!("indy_prover_create_master_secret: entities >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+    trace!("indy_issuer_enable_issuance_log: entities >>> wallet_handle: {:?}, issuer_did: {:?}", wallet_handle, issuer_did);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
-            AnoncredsCommand::Prover(
-                ProverCommand::CreateMasterSecret(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::EnableIssuanceLog(
                     wallet_handle,
-                    master_secret_id,
-                    boxed_callback_string!("indy_prover_create_master_secret", cb, command_handle)
+                    issuer_did,
+                    dispatch_callback(Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_enable_issuance_log: ");
+                        cb(command_handle, err)
+                    }))
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_create_master_secret: <<< res: {:?}", res);
+    trace!("indy_issuer_enable_issuance_log: <<< res: {:?}", res);
 
     res
 }
 
-/// Creates a credential request for the given credential offer.
+/// Exports the log of credentials issued with the wallet.
 ///
-/// The method creates a blinded master secret for a master secret identified by a provided name.
-/// The master secret identified by the name must be already stored in the secure wallet (see prover_create_master_secret)
-/// The blinded master secret is a part of the credential request.
+/// The log must be enabled by `indy_issuer_enable_issuance_log`. Entries are chained by their hashes
+/// and the head is signed with the issuer DID verkey, so exported log can be checked for modifications by `indy_issuer_verify_issuance_log`.
 ///
 /// #Params
-/// command_handle: command handle to map callback to user context
-/// wallet_handle: wallet handle (created by open_wallet)
-/// prover_did: a DID of the prover
-/// cred_offer_json: credential offer as a json containing information about the issuer and a credential
-///     {
-///         "schema_id": string, - identifier of schema
-///         "cred_def_id": string, - identifier of credential definition
-///          ...
-///         Other fields that contains data structures internal to Ursa.
-///         These fields should not be parsed and are likely to change in future versions.
-///     }
-/// cred_def_json: credential definition json related to <cred_def_id> in <cred_offer_json>
-/// master_secret_id: the id of the master secret stored in the wallet
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// cred_req_json: Credential request json for creation of credential by Issuer
-///     {
-///      "prover_did" : string,
-///      "cred_def_id" : string,
+/// issuance_log_json: issuance log json
+/// {
+///     entries: [{
+///         seq_no: number - sequence number of the entry starting from 1,
+///         event: {
+///             cred_def_id: string - id of credential definition the credential is issued for,
+///             nonce: string - nonce of credential offer,
+///             timestamp: number - time of issuance,
+///             rev_reg_id: Optional<string> - id of revocation registry,
+///             cred_rev_id: Optional<string> - revocation index of the credential,
+///         },
+///         prev_hash: string - hash of the previous entry,
+///         hash: string - hash of the entry,
+///     }],
+///     head: {
+///         seq_no: number - sequence number of the last entry,
+///         hash: string - hash of the last entry,
+///         issuer_did: string - DID of the issuer,
+///         signature: string - base58 encoded signature of the head by the issuer DID key,
+///     }
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_export_issuance_log(command_handle: CommandHandle,
+                                              wallet_handle: WalletHandle,
+                                              cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                   issuance_log_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_export_issuance_log: >>> wallet_handle: {:?}", wallet_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_issuer_export_issuance_log: entities >>> wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::ExportIssuanceLog(
+                    wallet_handle,
+                    dispatch_callback(boxed_callback_string!("indy_issuer_export_issuance_log", cb, command_handle))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_export_issuance_log: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies integrity of the issuance log exported by `indy_issuer_export_issuance_log`.
+/// Checks that entries are numbered consecutively, chained by their hashes, the last one matches the head
+/// and the head is signed with the issuer DID verkey.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// issuance_log_json: issuance log json returned by `indy_issuer_export_issuance_log`.
+/// issuer_verkey: verkey of the issuer DID NYM resolved by the verifier from the ledger.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if the log isn't modified, false - otherwise
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_issuer_verify_issuance_log(command_handle: CommandHandle,
+                                              issuance_log_json: *const c_char,
+                                              issuer_verkey: *const c_char,
+                                              cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                   valid: bool)>) -> ErrorCode {
+    trace!("indy_issuer_verify_issuance_log: >>> issuance_log_json: {:?}, issuer_verkey: {:?}", issuance_log_json, issuer_verkey);
+
+    check_useful_json!(issuance_log_json, ErrorCode::CommonInvalidParam2, IssuanceLog);
+    check_useful_c_str!(issuer_verkey, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_verify_issuance_log: entities >>> issuance_log_json: {:?}, issuer_verkey: {:?}", issuance_log_json, issuer_verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::VerifyIssuanceLog(
+                    issuance_log_json,
+                    issuer_verkey,
+                    dispatch_callback(Box::new(move |result| {
+                        let (err, valid) = prepare_result_1!(result, false);
+                        trace!("indy_issuer_verify_issuance_log: valid: {:?}", valid);
+                        cb(command_handle, err, valid)
+                    }))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_verify_issuance_log: <<< res: {:?}", res);
+
+    res
+}
+
+/// Creates a master secret with a given id and stores it in the wallet.
+/// The id must be unique.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// master_secret_id: (optional, if not present random one will be generated) new master id
+///
+/// #Returns
+/// out_master_secret_id: Id of generated master secret
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_create_master_secret(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               master_secret_id: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                    out_master_secret_id: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_create_master_secret: >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+
+    check_useful_opt_c_str!(master_secret_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_prover_create_master_secret: entities >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::CreateMasterSecret(
+                    wallet_handle,
+                    master_secret_id,
+                    dispatch_callback(boxed_callback_string!("indy_prover_create_master_secret", cb, command_handle))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_create_master_secret: <<< res: {:?}", res);
+
+    res
+}
+
+/// Lists master secrets stored in the wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// master_secrets_json: [{
+///     "id": string, // id of master secret
+///     "credentials_count": int, // number of stored credentials bound to the master secret
+/// }]
+///
+/// Note: credentials stored by libindy versions which didn't track master secrets aren't counted.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_list_master_secrets(command_handle: CommandHandle,
+                                              wallet_handle: WalletHandle,
+                                              cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                   master_secrets_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_list_master_secrets: >>> wallet_handle: {:?}", wallet_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_prover_list_master_secrets: entities >>> wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::ListMasterSecrets(
+                    wallet_handle,
+                    dispatch_callback(boxed_callback_string!("indy_prover_list_master_secrets", cb, command_handle))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_list_master_secrets: <<< res: {:?}", res);
+
+    res
+}
+
+/// Renames master secret stored in the wallet.
+/// Stored credentials bound to the master secret are updated to refer to the new id.
+/// If renaming is interrupted (e.g. by a storage failure), calling it again with the same ids completes it.
+///
+/// Note: credential request metadata created before renaming still refers to the old id,
+/// so credentials issued for such requests can't be stored after renaming.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// master_secret_id: id of master secret to rename.
+/// new_master_secret_id: new id of master secret. Must be unique.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_rename_master_secret(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               master_secret_id: *const c_char,
+                                               new_master_secret_id: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_prover_rename_master_secret: >>> wallet_handle: {:?}, master_secret_id: {:?}, new_master_secret_id: {:?}",
+           wallet_handle, master_secret_id, new_master_secret_id);
+
+    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(new_master_secret_id, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_rename_master_secret: entities >>> wallet_handle: {:?}, master_secret_id: {:?}, new_master_secret_id: {:?}",
+           wallet_handle, master_secret_id, new_master_secret_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::RenameMasterSecret(
+                    wallet_handle,
+                    master_secret_id,
+                    new_master_secret_id,
+                    dispatch_callback(Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_prover_rename_master_secret: ");
+                        cb(command_handle, err)
+                    }))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_rename_master_secret: <<< res: {:?}", res);
+
+    res
+}
+
+/// Deletes master secret stored in the wallet.
+/// Master secret referenced by stored credentials can't be deleted, the credentials must be deleted first.
+///
+/// Note: credentials stored by libindy versions which didn't track master secrets may be bound to any
+/// master secret, so no master secret can be deleted while such credentials are stored.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// master_secret_id: id of master secret to delete.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// AnoncredsMasterSecretInUseError
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_delete_master_secret(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               master_secret_id: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_prover_delete_master_secret: >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+
+    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_prover_delete_master_secret: entities >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::DeleteMasterSecret(
+                    wallet_handle,
+                    master_secret_id,
+                    dispatch_callback(Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_prover_delete_master_secret: ");
+                        cb(command_handle, err)
+                    }))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_delete_master_secret: <<< res: {:?}", res);
+
+    res
+}
+
+/// Creates a credential request for the given credential offer.
+///
+/// The method creates a blinded master secret for a master secret identified by a provided name.
+/// The master secret identified by the name must be already stored in the secure wallet (see prover_create_master_secret)
+/// The blinded master secret is a part of the credential request.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// prover_did: a DID of the prover
+/// cred_offer_json: credential offer as a json containing information about the issuer and a credential
+///     {
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///          ...
+///         Other fields that contains data structures internal to Ursa.
+///         These fields should not be parsed and are likely to change in future versions.
+///     }
+/// cred_def_json: credential definition json related to <cred_def_id> in <cred_offer_json>
+/// master_secret_id: the id of the master secret stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_req_json: Credential request json for creation of credential by Issuer
+///     {
+///      "prover_did" : string,
+///      "cred_def_id" : string,
 ///         // Fields below can depend on Cred Def type
 ///      "blinded_ms" : <blinded_master_secret>,
 ///                     (opaque type that contains data structures internal to Ursa.
@@ -959,13 +1617,13 @@ pub extern fn indy_prover_create_credential_req(command_handle: CommandHandle,
                     cred_offer_json,
                     cred_def_json,
                     master_secret_id,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, cred_req_json, cred_req_metadata_json) = prepare_result_2!(result, String::new(), String::new());
                         trace!("indy_prover_create_credential_req: cred_req_json: {:?}, cred_req_metadata_json: {:?}", cred_req_json, cred_req_metadata_json);
                         let cred_req_json = ctypes::string_to_cstring(cred_req_json);
                         let cred_req_metadata_json = ctypes::string_to_cstring(cred_req_metadata_json);
                         cb(command_handle, err, cred_req_json.as_ptr(), cred_req_metadata_json.as_ptr())
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -1035,11 +1693,11 @@ pub extern fn indy_prover_set_credential_attr_tag_policy(command_handle: Command
                     cred_def_id,
                     tag_attrs_json,
                     retroactive,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let err = prepare_result!(result);
                         trace!("indy_prover_set_credential_attr_tag_policy: ");
                         cb(command_handle, err)
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -1087,7 +1745,7 @@ pub extern fn indy_prover_get_credential_attr_tag_policy(command_handle: Command
                 ProverCommand::GetCredentialAttrTagPolicy(
                     wallet_handle,
                     cred_def_id,
-                    boxed_callback_string!("indy_prover_get_credential_attr_tag_policy", cb, command_handle)
+                    dispatch_callback(boxed_callback_string!("indy_prover_get_credential_attr_tag_policy", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -1112,6 +1770,8 @@ pub extern fn indy_prover_get_credential_attr_tag_policy(command_handle: Command
 ///         // for every attribute in <credential values> that credential attribute tagging policy marks taggable
 ///         "attr::<attribute name>::marker": "1",
 ///         "attr::<attribute name>::value": <attribute raw value>,
+///         "attr::<attribute name>::mime_type": <attribute mime-type>, // if present in attribute metadata
+///         "attr::<attribute name>::encoding": <attribute encoding>, // if present in attribute metadata
 ///     }
 ///
 /// #Params
@@ -1177,7 +1837,7 @@ pub extern fn indy_prover_store_credential(command_handle: CommandHandle,
                     cred_json,
                     cred_def_json,
                     rev_reg_def_json,
-                    boxed_callback_string!("indy_prover_store_credential", cb, command_handle)
+                    dispatch_callback(boxed_callback_string!("indy_prover_store_credential", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -1202,7 +1862,8 @@ pub extern fn indy_prover_store_credential(command_handle: CommandHandle,
 ///         "schema_id": string, - identifier of schema
 ///         "cred_def_id": string, - identifier of credential definition
 ///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
-///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
+///         "cred_rev_id": Optional<string>, - identifier of credential in the revocation registry definition
+///         "attrs_metadata": Optional<{"key1": {"mime-type": string, "encoding": string}}> - metadata of the attributes having it
 ///     }
 ///
 /// #Errors
@@ -1229,7 +1890,7 @@ pub extern fn indy_prover_get_credential(command_handle: CommandHandle,
                 ProverCommand::GetCredential(
                     wallet_handle,
                     cred_id,
-                    boxed_callback_string!("indy_prover_get_credential", cb, command_handle)
+                    dispatch_callback(boxed_callback_string!("indy_prover_get_credential", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -1268,11 +1929,11 @@ pub extern fn indy_prover_delete_credential(command_handle: CommandHandle,
                 ProverCommand::DeleteCredential(
                     wallet_handle,
                     cred_id,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let err = prepare_result!(result);
                         trace!("indy_prover_delete_credential: ");
                         cb(command_handle, err)
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -1338,7 +1999,7 @@ pub extern fn indy_prover_get_credentials(command_handle: CommandHandle,
                 ProverCommand::GetCredentials(
                     wallet_handle,
                     filter_json,
-                    boxed_callback_string!("indy_prover_get_credentials", cb, command_handle)
+                    dispatch_callback(boxed_callback_string!("indy_prover_get_credentials", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -1390,10 +2051,10 @@ pub extern fn indy_prover_search_credentials(command_handle: CommandHandle,
                 ProverCommand::SearchCredentials(
                     wallet_handle,
                     query_json,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, handle, total_count) = prepare_result_2!(result, INVALID_SEARCH_HANDLE, 0);
                         cb(command_handle, err, handle, total_count)
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -1403,63 +2064,133 @@ pub extern fn indy_prover_search_credentials(command_handle: CommandHandle,
     res
 }
 
-/// Fetch next credentials for search.
+/// Search for credentials stored in wallet returning only the requested subset of the credentials data.
+/// Projected data is built from the credential tags, so credential values aren't read and decrypted.
+///
+/// Use indy_prover_fetch_credentials to fetch records by small batches
+/// and indy_prover_close_credentials_search to close the search.
 ///
 /// #Params
-/// search_handle: Search handle (created by indy_prover_search_credentials)
-/// count: Count of credentials to fetch
+/// wallet_handle: wallet handle (created by open_wallet).
+/// query_json: Wql query filter for credentials searching based on tags.
+///     where query: indy-sdk/docs/design/011-wallet-query-language/README.md
+/// projection_json: subset of the credentials data to return
+///     {
+///         "attrs": [string], - (Optional) names of the attributes to return raw values of
+///         "tags": [string], - (Optional) names of the credential tags to return (for example "schema_id", "cred_def_id")
+///     }
+///     NOTE: values of the attributes untaggable by credential attr tag policy aren't returned.
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// credentials_json: List of human readable credentials:
+/// search_handle: Search handle that can be used later to fetch records by small batches (with indy_prover_fetch_credentials)
+/// total_count: Total count of records
+///
+/// Fetched credentials of this search have the following format:
 ///     [{
 ///         "referent": string, - id of credential in the wallet
-///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
-///         "schema_id": string, - identifier of schema
-///         "cred_def_id": string, - identifier of credential definition
-///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
-///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
+///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - requested credential attributes
+///         "tags": {"tag1":"value1", "tag2":"value2"}, - requested credential tags
 ///     }]
-/// NOTE: The list of length less than the requested count means credentials search iterator is completed.
 ///
 /// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
 #[no_mangle]
-pub  extern fn indy_prover_fetch_credentials(command_handle: CommandHandle,
-                                             search_handle: SearchHandle,
-                                             count: usize,
-                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
-                                                                  credentials_json: *const c_char)>) -> ErrorCode {
-    trace!("indy_prover_fetch_credentials: >>> search_handle: {:?}, count: {:?}", search_handle, count);
+pub extern fn indy_prover_search_credentials_with_projection(command_handle: CommandHandle,
+                                                             wallet_handle: WalletHandle,
+                                                             query_json: *const c_char,
+                                                             projection_json: *const c_char,
+                                                             cb: Option<extern fn(
+                                                                 command_handle_: CommandHandle, err: ErrorCode,
+                                                                 search_handle: SearchHandle,
+                                                                 total_count: usize)>) -> ErrorCode {
+    trace!("indy_prover_search_credentials_with_projection: >>> wallet_handle: {:?}, query_json: {:?}, projection_json: {:?}", wallet_handle, query_json, projection_json);
 
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(query_json, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_json!(projection_json, ErrorCode::CommonInvalidParam4, CredentialProjection);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_prover_fetch_credentials: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
+    trace!("indy_prover_search_credentials_with_projection: entities >>> wallet_handle: {:?}, query_json: {:?}, projection_json: {:?}", wallet_handle, query_json, projection_json);
 
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(
             AnoncredsCommand::Prover(
-                ProverCommand::FetchCredentials(
-                    search_handle,
-                    count,
-                    boxed_callback_string!("indy_prover_fetch_credentials", cb, command_handle)
+                ProverCommand::SearchCredentialsWithProjection(
+                    wallet_handle,
+                    query_json,
+                    projection_json,
+                    dispatch_callback(Box::new(move |result| {
+                        let (err, handle, total_count) = prepare_result_2!(result, INVALID_SEARCH_HANDLE, 0);
+                        cb(command_handle, err, handle, total_count)
+                    }))
                 ))));
 
     let res = prepare_result!(result);
 
-    trace!("indy_prover_fetch_credentials: <<< res: {:?}", res);
+    trace!("indy_prover_search_credentials_with_projection: <<< res: {:?}", res);
 
     res
 }
 
-/// Close credentials search (make search handle invalid)
+/// Fetch next credentials for search.
 ///
 /// #Params
 /// search_handle: Search handle (created by indy_prover_search_credentials)
+/// count: Count of credentials to fetch
+/// cb: Callback that takes command result as parameter.
 ///
-/// #Errors
+/// #Returns
+/// credentials_json: List of human readable credentials:
+///     [{
+///         "referent": string, - id of credential in the wallet
+///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"}, - credential attributes
+///         "schema_id": string, - identifier of schema
+///         "cred_def_id": string, - identifier of credential definition
+///         "rev_reg_id": Optional<string>, - identifier of revocation registry definition
+///         "cred_rev_id": Optional<string> - identifier of credential in the revocation registry definition
+///     }]
+/// NOTE: The list of length less than the requested count means credentials search iterator is completed.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_prover_fetch_credentials(command_handle: CommandHandle,
+                                             search_handle: SearchHandle,
+                                             count: usize,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_fetch_credentials: >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_prover_fetch_credentials: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::FetchCredentials(
+                    search_handle,
+                    count,
+                    dispatch_callback(boxed_callback_string!("indy_prover_fetch_credentials", cb, command_handle))
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_fetch_credentials: <<< res: {:?}", res);
+
+    res
+}
+
+/// Close credentials search (make search handle invalid)
+///
+/// #Params
+/// search_handle: Search handle (created by indy_prover_search_credentials)
+///
+/// #Errors
 /// Anoncreds*
 /// Common*
 /// Wallet*
@@ -1478,11 +2209,11 @@ pub  extern fn indy_prover_close_credentials_search(command_handle: CommandHandl
             AnoncredsCommand::Prover(
                 ProverCommand::CloseCredentialsSearch(
                     search_handle,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let err = prepare_result!(result);
                         trace!("indy_prover_close_credentials_search:");
                         cb(command_handle, err)
-                    })
+                    }))
                 ))));
 
     let res = prepare_result!(result);
@@ -1610,7 +2341,7 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
                 ProverCommand::GetCredentialsForProofReq(
                     wallet_handle,
                     proof_request_json,
-                    boxed_callback_string!("indy_prover_get_credentials_for_proof_req", cb, command_handle)
+                    dispatch_callback(boxed_callback_string!("indy_prover_get_credentials_for_proof_req", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -1729,11 +2460,11 @@ pub extern fn indy_prover_search_credentials_for_proof_req(command_handle: Comma
                     wallet_handle,
                     proof_request_json,
                     extra_query_json,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let (err, search_handle) = prepare_result_1!(result, INVALID_SEARCH_HANDLE);
                         trace!("indy_prover_search_credentials_for_proof_req: search_handle: {:?}", search_handle);
                         cb(command_handle, err, search_handle)
-                    }),
+                    })),
                 ))));
 
     let res = prepare_result!(result);
@@ -1801,7 +2532,7 @@ pub  extern fn indy_prover_fetch_credentials_for_proof_req(command_handle: Comma
                     search_handle,
                     item_referent,
                     count,
-                    boxed_callback_string!("indy_prover_fetch_credentials_for_proof_request", cb, command_handle)
+                    dispatch_callback(boxed_callback_string!("indy_prover_fetch_credentials_for_proof_request", cb, command_handle))
                 ))));
 
     let res = prepare_result!(result);
@@ -1835,11 +2566,11 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
             AnoncredsCommand::Prover(
                 ProverCommand::CloseCredentialsSearchForProofReq(
                     search_handle,
-                    Box::new(move |result| {
+                    dispatch_callback(Box::new(move |result| {
                         let err = prepare_result!(result);
                         trace!("indy_prover_close_credentials_search:");
                         cb(command_handle, err)
-                    }),
+                    })),
                 ))));
 
     let res = prepare_result!(result);
@@ -1856,6 +2587,8 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
 /// All required schemas, public keys and revocation registries must be provided.
 /// The proof request also contains nonce.
 /// The proof contains either proof or self-attested attribute value for each requested attribute.
+/// Command waiting in the queue can be cancelled with `indy_cancel_command`
+/// by the handle returned by `indy_get_current_cancel_handle` right after this call.
 ///
 /// #Params
 /// wallet_handle: wallet handle (created by open_wallet).
@@ -1888,13 +2621,18 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
 ///             "self_attested_attribute_referent": string
 ///         },
 ///         "requested_attributes": {
-///             "requested_attribute_referent_1": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool> }},
-///             "requested_attribute_referent_2": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool> }}
+///             "requested_attribute_referent_1": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool>, "wallet_handle": Optional<number> }},
+///             "requested_attribute_referent_2": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool>, "wallet_handle": Optional<number> }}
 ///         },
 ///         "requested_predicates": {
-///             "requested_predicates_referent_1": {"cred_id": string, "timestamp": Optional<number> }},
+///             "requested_predicates_referent_1": {"cred_id": string, "timestamp": Optional<number>, "wallet_handle": Optional<number> }},
 ///         }
 ///     }
+///     "wallet_handle" allows to take the credential from another opened wallet (e.g. guardian wallet).
+///     If omitted the credential is taken from the wallet passed to this function.
+///     NOTE: all used credentials must be issued for the same master secret stored in the wallet passed to this function.
+///     Proof can't combine credentials issued for different master secrets, so credentials held by the guardian
+///     on behalf of the dependent must be issued for the dependent's master secret.
 /// master_secret_id: the id of the master secret stored in the wallet
 /// schemas_json: all schemas participating in the proof request
 ///     {
@@ -2039,7 +2777,7 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
            wallet_handle, proof_req_json, requested_credentials_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateProof(
+        .send_cancellable(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateProof(
             wallet_handle,
             proof_req_json,
             requested_credentials_json,
@@ -2047,7 +2785,7 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
             schemas_json,
             credential_defs_json,
             rev_states_json,
-            boxed_callback_string!("indy_prover_create_proof", cb, command_handle)
+            cancellable_callback(dispatch_callback(boxed_callback_string!("indy_prover_create_proof", cb, command_handle)))
         ))));
 
     let res = prepare_result!(result);
@@ -2057,12 +2795,157 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
     res
 }
 
+/// Previews what the proof created by indy_prover_create_proof for the same proof request and
+/// requested credentials would disclose, so the holder can review it before the proof is created.
+///
+/// Neither master secret nor schemas, credential definitions and revocation states are required,
+/// the proof itself isn't created.
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json (see indy_prover_create_proof)
+/// requested_credentials_json: either a credential or self-attested attribute for each requested attribute (see indy_prover_create_proof)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Disclosure preview json
+///     {
+///         "revealed_attrs": {
+///             "requested_attr1_id": {"cred_id": string, "name": string, "raw": string},
+///         },
+///         "revealed_attr_groups": {
+///             "requested_attr5_id": {"cred_id": string, "values": {"attribute_name": raw_value}},
+///         },
+///         "unrevealed_attrs": {
+///             "requested_attr3_id": {"cred_id": string, "names": [string]}, - proven to exist, value stays hidden
+///         },
+///         "self_attested_attrs": {
+///             "requested_attr2_id": self_attested_value,
+///         },
+///         "predicates": {
+///             "requested_predicate_1_referent": {"cred_id": string, "name": string, "p_type": string, "p_value": int}, - attribute value stays hidden
+///         },
+///         "credentials": {
+///             "cred_id": {
+///                 "schema_id": string, "cred_def_id": string, "rev_reg_id": Optional<string>, - disclosed as proof identifiers
+///                 "timestamps": [int], - disclosed as proof identifiers
+///                 "hidden_attrs": [string] - attributes of the credential the proof doesn't reveal
+///             }
+///         }
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_preview_disclosure(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             proof_req_json: *const c_char,
+                                             requested_credentials_json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  disclosure_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_preview_disclosure: >>> wallet_handle: {:?}, proof_req_json: {:?}, requested_credentials_json: {:?}",
+           wallet_handle, proof_req_json, requested_credentials_json);
+
+    check_useful_validatable_json!(proof_req_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_validatable_json!(requested_credentials_json, ErrorCode::CommonInvalidParam4, RequestedCredentials);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_preview_disclosure: entities >>> wallet_handle: {:?}, proof_req_json: {:?}, requested_credentials_json: {:?}",
+           wallet_handle, proof_req_json, requested_credentials_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::PreviewDisclosure(
+            wallet_handle,
+            proof_req_json,
+            requested_credentials_json,
+            dispatch_callback(boxed_callback_string!("indy_prover_preview_disclosure", cb, command_handle))
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_preview_disclosure: <<< res: {:?}", res);
+
+    res
+}
+
+/// Binds a proof to the holder DID by signing it with the DID verkey stored in the wallet.
+///
+/// The signature covers the proof and the nonce of the proof request it was created for,
+/// so it can't be moved to another proof or replayed for another proof request.
+/// Verifier checks the binding as part of `indy_verifier_verify_proof` if the proof request requires it
+/// with `holder_binding` field, in this case `holder_did` must be the required one.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_req_json: proof request json the proof was created for (see `indy_prover_create_proof`).
+/// proof_json: proof json created by `indy_prover_create_proof`.
+/// holder_did: DID of the holder stored in the wallet (created by `indy_create_and_store_my_did`).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Proof json with appended holder binding:
+///     {
+///         ... - fields of the proof (see `indy_prover_create_proof`)
+///         "holder_binding": {
+///             "did": string, - holder DID
+///             "signature": string - base58 encoded signature over the proof request nonce and the proof
+///         }
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_prover_bind_proof(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     proof_req_json: *const c_char,
+                                     proof_json: *const c_char,
+                                     holder_did: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                          proof_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_bind_proof: >>> wallet_handle: {:?}, proof_req_json: {:?}, proof_json: {:?}, holder_did: {:?}",
+           wallet_handle, proof_req_json, proof_json, holder_did);
+
+    check_useful_validatable_json!(proof_req_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam4, Proof);
+    check_useful_validatable_string!(holder_did, ErrorCode::CommonInvalidParam5, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_prover_bind_proof: entities >>> wallet_handle: {:?}, proof_req_json: {:?}, proof_json: {:?}, holder_did: {:?}",
+           wallet_handle, proof_req_json, proof_json, holder_did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::BindProof(
+            wallet_handle,
+            proof_req_json,
+            proof_json,
+            holder_did,
+            dispatch_callback(boxed_callback_string!("indy_prover_bind_proof", cb, command_handle))
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_bind_proof: <<< res: {:?}", res);
+
+    res
+}
+
 /// Verifies a proof (of multiple credential).
 /// All required schemas, public keys and revocation registries must be provided.
 ///
 /// IMPORTANT: You must use *_id's (`schema_id`, `cred_def_id`, `rev_reg_id`) listed in `proof[identifiers]`
 /// as the keys for corresponding `schemas_json`, `credential_defs_json`, `rev_reg_defs_json`, `rev_regs_json` objects.
 ///
+/// If the proof request requires holder binding (`proof_request[holder_binding]`) the proof must be bound
+/// to the requested DID (see `indy_prover_bind_proof`) and the binding signature must match the requested verkey.
+/// The verifier should resolve the current verkey of the DID NYM from the ledger before building the proof request.
+///
 /// #Params
 /// wallet_handle: wallet handle (created by open_wallet).
 /// command_handle: command handle to map callback to user context.
@@ -2086,6 +2969,10 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
 ///         "ver": Optional<str>  - proof request version:
 ///             - omit or "1.0" to use unqualified identifiers for restrictions
 ///             - "2.0" to use fully qualified identifiers for restrictions
+///         "holder_binding": Optional<{ - proof must be bound to the holder DID
+///             "did": string, - holder DID
+///             "verkey": string - verkey of holder DID the binding is checked against
+///         }>
 ///     }
 /// proof_json: created for request proof json
 ///     {
@@ -2235,12 +3122,12 @@ pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
             credential_defs_json,
             rev_reg_defs_json,
             rev_regs_json,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, valid) = prepare_result_1!(result, false);
                 trace!("indy_verifier_verify_proof: valid: {:?}", valid);
 
                 cb(command_handle, err, valid)
-            })
+            }))
         ))));
 
     let res = prepare_result!(result);
@@ -2250,6 +3137,270 @@ pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
     res
 }
 
+/// Creates verifier session keeping parsed ledger artifacts to verify many proofs built from them.
+///
+/// High-throughput verifiers can avoid parsing of the same schemas, credential definitions
+/// and revocation registry definitions for every proof with `indy_verifier_verify_proof_with_session`.
+/// Session must be closed with `indy_verifier_close_session` when it isn't needed anymore.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// schemas_json: all schemas json participating in the proofs (see `indy_verifier_verify_proof`).
+/// credential_defs_json: all credential definitions json participating in the proofs (see `indy_verifier_verify_proof`).
+/// rev_reg_defs_json: all revocation registry definitions json participating in the proofs (see `indy_verifier_verify_proof`).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// session_handle: handle of the session to pass to `indy_verifier_verify_proof_with_session`.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_verifier_create_session(command_handle: CommandHandle,
+                                           schemas_json: *const c_char,
+                                           credential_defs_json: *const c_char,
+                                           rev_reg_defs_json: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                session_handle: IndyHandle)>) -> ErrorCode {
+    trace!("indy_verifier_create_session: >>> schemas_json: {:?}, credential_defs_json: {:?}, rev_reg_defs_json: {:?}",
+           schemas_json, credential_defs_json, rev_reg_defs_json);
+
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam2, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam3, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam4, RevocationRegistryDefinitions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_verifier_create_session: entities >>> schemas_json: {:?}, credential_defs_json: {:?}, rev_reg_defs_json: {:?}",
+           schemas_json, credential_defs_json, rev_reg_defs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::CreateSession(
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            dispatch_callback(Box::new(move |result| {
+                let (err, session_handle) = prepare_result_1!(result, 0);
+                trace!("indy_verifier_create_session: session_handle: {:?}", session_handle);
+
+                cb(command_handle, err, session_handle)
+            }))
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_create_session: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies a proof using ledger artifacts kept by the verifier session.
+///
+/// Works the same way as `indy_verifier_verify_proof` does.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// session_handle: session handle (created by `indy_verifier_create_session`).
+/// proof_request_json: proof request json (see `indy_verifier_verify_proof`).
+/// proof_json: created for request proof json (see `indy_verifier_verify_proof`).
+/// rev_regs_json: all revocation registries json participating in the proof (see `indy_verifier_verify_proof`).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if signature is valid, false - otherwise
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_with_session(command_handle: CommandHandle,
+                                                      session_handle: IndyHandle,
+                                                      proof_request_json: *const c_char,
+                                                      proof_json: *const c_char,
+                                                      rev_regs_json: *const c_char,
+                                                      cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                           valid: bool)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_with_session: >>> session_handle: {:?}, proof_request_json: {:?}, proof_json: {:?}, rev_regs_json: {:?}",
+           session_handle, proof_request_json, proof_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam4, Proof);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam5, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_verifier_verify_proof_with_session: entities >>> session_handle: {:?}, proof_request_json: {:?}, proof_json: {:?}, rev_regs_json: {:?}",
+           session_handle, proof_request_json, proof_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithSession(
+            session_handle,
+            proof_request_json,
+            proof_json,
+            rev_regs_json,
+            dispatch_callback(Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_verifier_verify_proof_with_session: valid: {:?}", valid);
+
+                cb(command_handle, err, valid)
+            }))
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_with_session: <<< res: {:?}", res);
+
+    res
+}
+
+/// Closes verifier session and frees ledger artifacts kept by it.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// session_handle: session handle (created by `indy_verifier_create_session`).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_verifier_close_session(command_handle: CommandHandle,
+                                          session_handle: IndyHandle,
+                                          cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_verifier_close_session: >>> session_handle: {:?}", session_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_verifier_close_session: entities >>> session_handle: {:?}", session_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::CloseSession(
+            session_handle,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_verifier_close_session:");
+                cb(command_handle, err)
+            }))
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_close_session: <<< res: {:?}", res);
+
+    res
+}
+
+/// Saves proof request template in the wallet to instantiate proof requests from it later.
+/// Template with the same id is replaced.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// template_id: identifier of the template in the wallet.
+/// template_json: proof request json (see `indy_prover_get_credentials_for_proof_req`) without nonce.
+///     Any string value in form of "${param_name}" is a placeholder replaced with the parameter
+///     passed to `indy_verifier_instantiate_proof_request_template`, for example:
+///     {
+///         "name": "proof_req",
+///         "version": "0.1",
+///         "requested_attributes": {
+///             "attr1_referent": {"name": "name", "restrictions": {"cred_def_id": "${cred_def_id}"}}
+///         },
+///         "non_revoked": {"from": "${from}", "to": "${to}"}
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verifier_save_proof_request_template(command_handle: CommandHandle,
+                                                        wallet_handle: WalletHandle,
+                                                        template_id: *const c_char,
+                                                        template_json: *const c_char,
+                                                        cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_verifier_save_proof_request_template: >>> wallet_handle: {:?}, template_id: {:?}, template_json: {:?}",
+           wallet_handle, template_id, template_json);
+
+    check_useful_c_str!(template_id, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_json!(template_json, ErrorCode::CommonInvalidParam4, ProofRequestTemplate);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_verifier_save_proof_request_template: entities >>> wallet_handle: {:?}, template_id: {:?}, template_json: {:?}",
+           wallet_handle, template_id, template_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::SaveProofRequestTemplate(
+            wallet_handle,
+            template_id,
+            template_json,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_verifier_save_proof_request_template:");
+                cb(command_handle, err)
+            }))
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_save_proof_request_template: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds proof request from the template saved by `indy_verifier_save_proof_request_template`.
+/// Placeholders of the template are replaced with the parameters and fresh nonce is generated.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// template_id: identifier of the template in the wallet.
+/// params_json: values of the template placeholders
+///     {
+///         "<param_name>": <any json value>,
+///         ...
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// proof_request_json: proof request json ready to be sent to the prover.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verifier_instantiate_proof_request_template(command_handle: CommandHandle,
+                                                               wallet_handle: WalletHandle,
+                                                               template_id: *const c_char,
+                                                               params_json: *const c_char,
+                                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                                    proof_request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_instantiate_proof_request_template: >>> wallet_handle: {:?}, template_id: {:?}, params_json: {:?}",
+           wallet_handle, template_id, params_json);
+
+    check_useful_c_str!(template_id, ErrorCode::CommonInvalidParam3);
+    check_useful_json!(params_json, ErrorCode::CommonInvalidParam4, ProofRequestTemplateParams);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_verifier_instantiate_proof_request_template: entities >>> wallet_handle: {:?}, template_id: {:?}, params_json: {:?}",
+           wallet_handle, template_id, params_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::InstantiateProofRequestTemplate(
+            wallet_handle,
+            template_id,
+            params_json,
+            dispatch_callback(boxed_callback_string!("indy_verifier_instantiate_proof_request_template", cb, command_handle))
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_instantiate_proof_request_template: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
 /// Note that revocation delta must cover the whole registry existence time.
@@ -2309,7 +3460,7 @@ pub extern fn indy_create_revocation_state(command_handle: CommandHandle,
             rev_reg_delta_json,
             timestamp,
             cred_rev_id,
-            boxed_callback_string!("indy_create_revocation_state", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_create_revocation_state", cb, command_handle))
         ))));
 
     let res = prepare_result!(result);
@@ -2381,7 +3532,7 @@ pub extern fn indy_update_revocation_state(command_handle: CommandHandle,
             rev_reg_delta_json,
             timestamp,
             cred_rev_id,
-            boxed_callback_string!("indy_update_revocation_state", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_update_revocation_state", cb, command_handle))
         ))));
 
     let res = prepare_result!(result);
@@ -2413,7 +3564,7 @@ pub extern fn indy_generate_nonce(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(AnoncredsCommand::Verifier(
             VerifierCommand::GenerateNonce(
-                boxed_callback_string!("indy_generate_nonce", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_generate_nonce", cb, command_handle))
             ))));
 
     let res = prepare_result!(result);
@@ -2462,12 +3613,12 @@ pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Anoncreds(AnoncredsCommand::ToUnqualified(
             entity,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, res) = prepare_result_1!(result, String::new());
                 trace!("indy_to_unqualified: did: {:?}", res);
                 let res = ctypes::string_to_cstring(res);
                 cb(command_handle, err, res.as_ptr())
-            }),
+            })),
         )));
 
     let res = prepare_result!(result);
@@ -2477,3 +3628,242 @@ pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
     res
 }
 
+/// Encodes json of anoncreds object (Proof, Credential and etc) into MessagePack.
+///
+/// Binary form is more compact than json and can be used to transmit big objects like proofs
+/// over constrained transports. Use indy_anoncreds_msgpack_to_json to get the original json back.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// json: json of anoncreds object to encode.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+///   msgpack_data: a pointer to the first byte of the encoded object
+///   msgpack_len: the length of the encoded object in bytes
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_anoncreds_json_to_msgpack(command_handle: CommandHandle,
+                                             json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode,
+                                                                  msgpack_data: *const u8,
+                                                                  msgpack_len: u32)>) -> ErrorCode {
+    trace!("indy_anoncreds_json_to_msgpack: >>> json: {:?}", json);
+
+    check_useful_c_str!(json, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_anoncreds_json_to_msgpack: entities >>> json: {:?}", json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::ToMsgpack(
+            json,
+            dispatch_callback(Box::new(move |result| {
+                let (err, msgpack) = prepare_result_1!(result, Vec::new());
+                trace!("indy_anoncreds_json_to_msgpack: msgpack: {:?}", msgpack);
+                let (msgpack_data, msgpack_len) = ctypes::vec_to_pointer(&msgpack);
+                cb(command_handle, err, msgpack_data, msgpack_len)
+            })),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_anoncreds_json_to_msgpack: <<< res: {:?}", res);
+
+    res
+}
+
+/// Decodes anoncreds object encoded by indy_anoncreds_json_to_msgpack back into json.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// msgpack_data: a pointer to the first byte of the encoded object.
+/// msgpack_len: the length of the encoded object in bytes.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+///   json: json of the decoded object
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_anoncreds_msgpack_to_json(command_handle: CommandHandle,
+                                             msgpack_data: *const u8,
+                                             msgpack_len: u32,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode,
+                                                                  json: *const c_char)>) -> ErrorCode {
+    trace!("indy_anoncreds_msgpack_to_json: >>> msgpack_data: {:?}, msgpack_len: {:?}", msgpack_data, msgpack_len);
+
+    check_useful_c_byte_array!(msgpack_data, msgpack_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_anoncreds_msgpack_to_json: entities >>> msgpack_data: {:?}", msgpack_data);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::FromMsgpack(
+            msgpack_data,
+            dispatch_callback(boxed_callback_string!("indy_anoncreds_msgpack_to_json", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_anoncreds_msgpack_to_json: <<< res: {:?}", res);
+
+    res
+}
+
+/// Encodes raw value of credential attribute into the integer used in CL signatures.
+///
+/// Issuers should use this encoding for "encoded" values of credentials, so verifiers can check
+/// that revealed raw values correspond to the signed ones:
+///     32-bit integers in canonical form are encoded as themselves;
+///     any other value is encoded as decimal representation of sha256 hash of its utf-8 bytes.
+///
+/// Set "enforce_canonical_attr_encoding" of `indy_set_runtime_config` to reject credential values and proofs
+/// which encoded values don't match this encoding.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// raw: raw value of the attribute.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+///   encoded: encoded value of the attribute
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_encode_attribute_value(command_handle: CommandHandle,
+                                          raw: *const c_char,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode,
+                                                               encoded: *const c_char)>) -> ErrorCode {
+    trace!("indy_encode_attribute_value: >>> raw: {:?}", raw);
+
+    check_useful_c_str!(raw, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_encode_attribute_value: entities >>> raw: {:?}", secret!(&raw));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::EncodeAttributeValue(
+            raw,
+            dispatch_callback(boxed_callback_string!("indy_encode_attribute_value", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_encode_attribute_value: <<< res: {:?}", res);
+
+    res
+}
+
+/// Wraps anoncreds credential into W3C Verifiable Credential data model (Experimental)
+///
+/// Allows exchanging of credentials with wallets supporting W3C Verifiable Credentials.
+/// Raw attribute values are put into "credentialSubject", encoded values and CL signature
+/// are put into the proof of "CLSignature2019" type, so the credential can be converted back
+/// by `indy_from_w3c_credential`.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// cred_json: credential json returned by indy_issuer_create_credential.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// w3c_cred_json: W3C Verifiable Credential json
+///     {
+///         "@context": ["https://www.w3.org/2018/credentials/v1"],
+///         "type": ["VerifiableCredential", "IndyCredential"],
+///         "issuer": <fully qualified issuer DID>,
+///         "issuanceDate": Optional<date of issuance in RFC3339 format>, omitted as anoncreds credential doesn't carry it
+///         "credentialSchema": {
+///             "id": <schema id>,
+///             "type": "IndyCredentialSchema",
+///             "definition": <credential definition id>,
+///             "revocationRegistry": Optional<revocation registry id>,
+///         },
+///         "credentialSubject": {
+///             <attr_name>: <raw value>,
+///         },
+///         "proof": {
+///             "type": "CLSignature2019",
+///             "encodedValues": { <attr_name>: <encoded value> },
+///             "attributesMetadata": Optional<{ <attr_name>: { "mime-type": .., "encoding": .. } }>,
+///             "signature": <credential signature>,
+///             "signatureCorrectnessProof": <signature correctness proof>,
+///             "revReg": Optional<revocation registry accumulator>,
+///             "witness": Optional<witness>
+///         }
+///     }
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_to_w3c_credential(command_handle: CommandHandle,
+                                     cred_json: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          w3c_cred_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_to_w3c_credential: >>> cred_json: {:?}", cred_json);
+
+    check_useful_validatable_json!(cred_json, ErrorCode::CommonInvalidParam2, Credential);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_to_w3c_credential: entities >>> cred_json: {:?}", secret!(&cred_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::ToW3cCredential(
+            cred_json,
+            dispatch_callback(boxed_callback_string!("indy_to_w3c_credential", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_to_w3c_credential: <<< res: {:?}", res);
+
+    res
+}
+
+/// Converts W3C Verifiable Credential created by `indy_to_w3c_credential` back to anoncreds credential (Experimental)
+/// Raw values of "credentialSubject" must match the encoded values of the proof.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// w3c_cred_json: W3C Verifiable Credential json (see `indy_to_w3c_credential`).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_json: credential json in the format returned by indy_issuer_create_credential
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_from_w3c_credential(command_handle: CommandHandle,
+                                       w3c_cred_json: *const c_char,
+                                       cb: Option<extern fn(command_handle_: CommandHandle,
+                                                            err: ErrorCode,
+                                                            cred_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_from_w3c_credential: >>> w3c_cred_json: {:?}", w3c_cred_json);
+
+    check_useful_validatable_json!(w3c_cred_json, ErrorCode::CommonInvalidParam2, W3CCredential);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_from_w3c_credential: entities >>> w3c_cred_json: {:?}", secret!(&w3c_cred_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::FromW3cCredential(
+            w3c_cred_json,
+            dispatch_callback(boxed_callback_string!("indy_from_w3c_credential", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_from_w3c_credential: <<< res: {:?}", res);
+
+    res
+}
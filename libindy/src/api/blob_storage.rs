@@ -1,6 +1,6 @@
 
 use indy_api_types::{ErrorCode, IndyHandle, CommandHandle};
-use crate::commands::{Command, CommandExecutor};
+use crate::commands::{Command, CommandExecutor, dispatch_callback};
 use crate::commands::blob_storage::BlobStorageCommand;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -26,11 +26,11 @@ pub extern fn indy_open_blob_storage_reader(command_handle: CommandHandle,
         .send(Command::BlobStorage(BlobStorageCommand::OpenReader(
             type_,
             config_json,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, handle) = prepare_result_1!(result, 0);
                 trace!("indy_open_blob_storage_reader: handle: {:?}", handle);
                 cb(command_handle, err, handle)
-            }),
+            })),
         )));
 
     let res = prepare_result!(result);
@@ -59,11 +59,11 @@ pub extern fn indy_open_blob_storage_writer(command_handle: CommandHandle,
         .send(Command::BlobStorage(BlobStorageCommand::OpenWriter(
             type_,
             config_json,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, handle) = prepare_result_1!(result, 0);
                 trace!("indy_open_blob_storage_writer: handle: {:?}", handle);
                 cb(command_handle, err, handle)
-            }),
+            })),
         )));
 
     let res = prepare_result!(result);
@@ -1,9 +1,9 @@
-use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, PoolHandle};
-use crate::commands::{Command, CommandExecutor};
+use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, PoolHandle, IndyHandle};
+use crate::commands::{Command, CommandExecutor, dispatch_callback};
 use crate::commands::cache::CacheCommand;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
-use crate::domain::cache::{GetCacheOptions, PurgeOptions};
+use crate::domain::cache::{GetCacheOptions, PurgeOptions, RevocationPrefetchOptions};
 use crate::domain::anoncreds::schema::SchemaId;
 use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
 use crate::domain::crypto::did::DidValue;
@@ -56,7 +56,7 @@ pub extern fn indy_get_cred_def(command_handle: CommandHandle,
             submitter_did,
             id,
             options_json,
-            boxed_callback_string!("indy_get_cred_def", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_get_cred_def", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -114,7 +114,7 @@ pub extern fn indy_get_schema(command_handle: CommandHandle,
             submitter_did,
             id,
             options_json,
-            boxed_callback_string!("indy_get_schema", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_get_schema", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -155,11 +155,11 @@ pub extern fn indy_purge_cred_def_cache(command_handle: CommandHandle,
         .send(Command::Cache(CacheCommand::PurgeCredDefCache(
             wallet_handle,
             options_json,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_purge_cred_def_cache:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -200,11 +200,11 @@ pub extern fn indy_purge_schema_cache(command_handle: CommandHandle,
         .send(Command::Cache(CacheCommand::PurgeSchemaCache(
             wallet_handle,
             options_json,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_purge_schema_cache:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -213,3 +213,196 @@ pub extern fn indy_purge_schema_cache(command_handle: CommandHandle,
 
     res
 }
+
+/// Purge NYM cache keeping verkeys resolved by `indy_pack_message_for_dids`.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// options_json:
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_purge_nym_cache(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   options_json: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_purge_nym_cache: >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam3, PurgeOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_purge_nym_cache: entities >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::PurgeNymCache(
+            wallet_handle,
+            options_json,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_purge_nym_cache:");
+                cb(command_handle, err)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_nym_cache: <<< res: {:?}", res);
+
+    res
+}
+
+/// Starts background prefetching of revocation states for frequently used credentials.
+/// Revocation states are periodically updated from the ledger and stored inside of cache,
+/// so they can be taken by `indy_get_cached_revocation_state` without waiting for the ledger
+/// while building the proof. Credentials are ranked by the number of proofs created with them
+/// by `indy_prover_create_proof`. Prefetching is stopped once the wallet or blob storage reader is closed.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails.
+/// options_json:
+///  {
+///    interval: (int, optional, 300 by default) Prefetch revocation states every this many seconds.
+///    freshness: (int, optional, 600 by default) Refresh cached revocation state if older than this many seconds.
+///    maxRequests: (int, optional, 10 by default) Refresh not more than this many revocation states per round.
+///    maxCredentials: (int, optional, 20 by default) Keep revocation states of this many credentials
+///                    most frequently used to build proofs.
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// prefetch_handle: handle to stop prefetching with `indy_stop_revocation_prefetch`.
+#[no_mangle]
+pub extern fn indy_start_revocation_prefetch(command_handle: CommandHandle,
+                                             pool_handle: PoolHandle,
+                                             wallet_handle: WalletHandle,
+                                             blob_storage_reader_handle: IndyHandle,
+                                             options_json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode,
+                                                                  prefetch_handle: IndyHandle)>) -> ErrorCode {
+    trace!("indy_start_revocation_prefetch: >>> pool_handle: {:?}, wallet_handle: {:?}, blob_storage_reader_handle: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, blob_storage_reader_handle, options_json);
+
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam5, RevocationPrefetchOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_start_revocation_prefetch: entities >>> pool_handle: {:?}, wallet_handle: {:?}, blob_storage_reader_handle: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, blob_storage_reader_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::StartRevocationPrefetch(
+            pool_handle,
+            wallet_handle,
+            blob_storage_reader_handle,
+            options_json,
+            dispatch_callback(Box::new(move |result| {
+                let (err, prefetch_handle) = prepare_result_1!(result, 0);
+                trace!("indy_start_revocation_prefetch: prefetch_handle: {:?}", prefetch_handle);
+                cb(command_handle, err, prefetch_handle)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_start_revocation_prefetch: <<< res: {:?}", res);
+
+    res
+}
+
+/// Stops background prefetching of revocation states started by `indy_start_revocation_prefetch`.
+/// Revocation states prefetched so far are kept in cache.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// prefetch_handle: handle returned by `indy_start_revocation_prefetch`.
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_stop_revocation_prefetch(command_handle: CommandHandle,
+                                            prefetch_handle: IndyHandle,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_stop_revocation_prefetch: >>> prefetch_handle: {:?}", prefetch_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_stop_revocation_prefetch: entities >>> prefetch_handle: {:?}", prefetch_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::StopRevocationPrefetch(
+            prefetch_handle,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_stop_revocation_prefetch:");
+                cb(command_handle, err)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_stop_revocation_prefetch: <<< res: {:?}", res);
+
+    res
+}
+
+/// Gets revocation state of the credential prefetched by `indy_start_revocation_prefetch`.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_id: id of the credential stored in the wallet.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Revocation state json:
+///     {
+///         "rev_reg": <revocation registry>,
+///         "witness": <witness>,
+///         "timestamp" : integer
+///     }
+///
+/// #Errors
+/// WalletItemNotFound if revocation state of the credential isn't prefetched yet.
+#[no_mangle]
+pub extern fn indy_get_cached_revocation_state(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               cred_id: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode,
+                                                                    rev_state_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_cached_revocation_state: >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
+
+    check_useful_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_cached_revocation_state: entities >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::GetCachedRevocationState(
+            wallet_handle,
+            cred_id,
+            dispatch_callback(boxed_callback_string!("indy_get_cached_revocation_state", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_cached_revocation_state: <<< res: {:?}", res);
+
+    res
+}
@@ -1,15 +1,150 @@
 
-use indy_api_types::{ErrorCode, CommandHandle, WalletHandle};
-use crate::commands::{Command, CommandExecutor};
+use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, PoolHandle};
+use crate::commands::{Command, CommandExecutor, dispatch_callback};
 use crate::commands::crypto::CryptoCommand;
-use crate::domain::crypto::pack::JWE;
-use crate::domain::crypto::key::KeyInfo;
+use crate::domain::crypto::pack::{JWE, PackMode};
+use crate::domain::crypto::key::{BlsKeyInfo, KeyInfo};
+use crate::domain::crypto::did::DidValue;
+use crate::domain::cache::NymCacheOptions;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 
 use serde_json;
-use libc::c_char;
+use libc::{c_char, c_void};
+use crate::services::crypto::entropy::PluggedEntropySource;
+use crate::services::crypto::key_vault::PluggedKeyVault;
 
+/// Returns verkey of the key kept in the key vault.
+///
+/// #Params
+/// key_ref: reference to the key inside of the key vault.
+/// verkey_p: pointer to store base58 encoded verkey. The string must stay valid until free callback is called for it.
+pub type KeyVaultGetVerkeyCB = extern fn(key_ref: *const c_char,
+                                         verkey_p: *mut *const c_char) -> ErrorCode;
+
+/// Signs a message with the key kept in the key vault.
+///
+/// #Params
+/// key_ref: reference to the key inside of the key vault.
+/// message_raw: a pointer to first byte of message to be signed.
+/// message_len: a message length.
+/// signature_raw_p: pointer to store signature. The data must stay valid until free callback is called for it.
+/// signature_len_p: pointer to store signature length.
+pub type KeyVaultSignCB = extern fn(key_ref: *const c_char,
+                                    message_raw: *const u8,
+                                    message_len: u32,
+                                    signature_raw_p: *mut *const u8,
+                                    signature_len_p: *mut u32) -> ErrorCode;
+
+/// Frees data returned by key vault callbacks.
+pub type KeyVaultFreeCB = extern fn(data: *const c_void) -> ErrorCode;
+
+
+/// Registers key vault (HSM, secure enclave and etc) which keeps secret keys outside of libindy.
+///
+/// Keys created with "key_vault" field of indy_create_key are stored in the wallet as references only
+/// and all signing operations (indy_crypto_sign, ledger requests signing and etc) are delegated to the vault.
+/// Signatures returned by the vault are verified with the verkey of the key before they are used.
+/// Vault registered before under the same name is replaced.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// key_vault: name of the key vault.
+/// get_verkey: "get_verkey" operation handler
+/// sign: "sign" operation handler
+/// free: "free" operation handler
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_register_key_vault(command_handle: CommandHandle,
+                                      key_vault: *const c_char,
+                                      get_verkey: Option<KeyVaultGetVerkeyCB>,
+                                      sign: Option<KeyVaultSignCB>,
+                                      free: Option<KeyVaultFreeCB>,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_key_vault: >>> key_vault: {:?}", key_vault);
+
+    check_useful_c_str!(key_vault, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(get_verkey, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(sign, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(free, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_register_key_vault: entities >>> key_vault: {:?}", key_vault);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::RegisterKeyVault(
+            key_vault,
+            PluggedKeyVault::new(get_verkey, sign, free),
+            dispatch_callback(Box::new(move |result| {
+                cb(command_handle, result.into());
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_register_key_vault: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fills buffer with random bytes.
+///
+/// #Params
+/// buf: buffer to fill.
+/// len: length of the buffer.
+pub type EntropySourceFillCB = extern fn(buf: *mut u8,
+                                         len: u32) -> ErrorCode;
+
+/// Registers external source of randomness (host RNG, certified DRBG and etc) used instead of the default OS one.
+///
+/// The source is used for generation of keys created without seed (indy_create_key, indy_create_and_store_my_did,
+/// indy_replace_keys_start) and generation of nonces and content encryption keys (indy_crypto_auth_crypt, indy_pack_message).
+/// Bytes returned by the source are used as seed material of HMAC-DRBG (NIST SP 800-90A) only,
+/// the generator is reseeded from the source every 1024 requests.
+/// Health of the source is checked on registration and on every reseed.
+/// Source registered before is replaced.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// fill: "fill" operation handler
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_register_entropy_source(command_handle: CommandHandle,
+                                           fill: Option<EntropySourceFillCB>,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_entropy_source: >>>");
+
+    check_useful_c_callback!(fill, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::RegisterEntropySource(
+            PluggedEntropySource::new(fill),
+            dispatch_callback(Box::new(move |result| {
+                cb(command_handle, result.into());
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_register_entropy_source: <<< res: {:?}", res);
+
+    res
+}
 
 /// Creates keys pair and stores in the wallet.
 ///
@@ -20,7 +155,15 @@ use libc::c_char;
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values are 'ed25519', 'secp256k1' and 'p256'; 'secp256k1' and 'p256' keys can be used for signing only.
+///     "key_vault": string, (optional) Name of the key vault keeping the secret key (see indy_register_key_vault).
+///                          Only the key reference is stored in the wallet then and the key can be used for signing only.
+///     "key_ref": string, (optional) Reference to the key inside of the key vault. Required if "key_vault" is set.
+///     "derivation_path": string, (optional) SLIP-0010 path like "m/44'/0'/0'" to derive the ed25519 key by from "seed"
+///                                used as a master seed of 16 to 64 bytes.
+///                                Only hardened indexes are supported. Mnemonic phrases must be converted to the seed by the caller.
+///                                The path is stored with the key (see indy_get_key_derivation_path).
+///     "seed_encoding": string, (required if "derivation_path" is set) Encoding of the master seed: 'hex', 'base64' or 'utf8'.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -53,7 +196,7 @@ pub extern fn indy_create_key(command_handle: CommandHandle,
         .send(Command::Crypto(CryptoCommand::CreateKey(
             wallet_handle,
             key_json,
-            boxed_callback_string!("indy_create_key", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_create_key", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -63,6 +206,60 @@ pub extern fn indy_create_key(command_handle: CommandHandle,
     res
 }
 
+/// Generates BLS key pair with proof of possession of the secret key for a validator node.
+/// Generated key isn't stored in the wallet. Public part of the key can be published with NODE transaction
+/// (see indy_build_node_request).
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// key_json: Key information as json. Example:
+/// {
+///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
+///                                Can be UTF-8, base64 or hex string.
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code.
+/// - bls_key_json: Generated key as json:
+///   {
+///       "blskey": string, - BLS multi-signature key as base58-encoded string.
+///       "blskey_pop": string, - BLS key proof of possession as base58-encoded string.
+///       "signkey": string, - BLS secret key as base58-encoded string.
+///   }
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_create_bls_key(command_handle: CommandHandle,
+                                  key_json: *const c_char,
+                                  cb: Option<extern fn(command_handle_: CommandHandle,
+                                                       err: ErrorCode,
+                                                       bls_key_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_create_bls_key: >>> key_json: {:?}", key_json);
+
+    check_useful_json!(key_json, ErrorCode::CommonInvalidParam2, BlsKeyInfo);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_create_bls_key: entities >>> key_json: {:?}", secret!(&key_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CreateBlsKey(
+            key_json,
+            dispatch_callback(boxed_callback_string!("indy_create_bls_key", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_create_bls_key: <<< res: {:?}", res);
+
+    res
+}
+
 /// Saves/replaces the meta information for the giving key in the wallet.
 ///
 /// #Params
@@ -102,11 +299,11 @@ pub  extern fn indy_set_key_metadata(command_handle: CommandHandle,
             wallet_handle,
             verkey,
             metadata,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_set_key_metadata: ");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -153,7 +350,7 @@ pub  extern fn indy_get_key_metadata(command_handle: CommandHandle,
         .send(Command::Crypto(CryptoCommand::GetKeyMetadata(
             wallet_handle,
             verkey,
-            boxed_callback_string!("indy_get_key_metadata", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_get_key_metadata", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -163,6 +360,152 @@ pub  extern fn indy_get_key_metadata(command_handle: CommandHandle,
     res
 }
 
+/// Retrieves the path the key was derived by from the master seed (see "derivation_path" of indy_create_key).
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// verkey - The key (verkey, key id) to retrieve derivation path.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - derivation_path - The path the key was derived by.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// WalletItemNotFound if the key wasn't derived by path
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_get_key_derivation_path(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            verkey: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 derivation_path: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_key_derivation_path: >>> wallet_handle: {:?}, verkey: {:?}", wallet_handle, verkey);
+
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_key_derivation_path: entities >>> wallet_handle: {:?}, verkey: {:?}", wallet_handle, verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::GetKeyDerivationPath(
+            wallet_handle,
+            verkey,
+            dispatch_callback(boxed_callback_string!("indy_get_key_derivation_path", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_key_derivation_path: <<< res: {:?}", res);
+
+    res
+}
+
+/// Returns X25519 key agreement key of the ed25519 key stored in the wallet.
+///
+/// Key agreement key can be listed in DID Document separately from the verification key
+/// and can be passed as a receiver key to indy_pack_message.
+/// Messages packed for the key agreement key are unpacked by indy_unpack_message with the key it has been derived from.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// verkey - The key (verkey, key id) stored in the wallet.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - key_agreement_key - base58 encoded X25519 public key with ":x25519" crypto type suffix.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_get_key_agreement_key(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         verkey: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode,
+                                                              key_agreement_key: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_key_agreement_key: >>> wallet_handle: {:?}, verkey: {:?}", wallet_handle, verkey);
+
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_key_agreement_key: entities >>> wallet_handle: {:?}, verkey: {:?}", wallet_handle, verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::GetKeyAgreementKey(
+            wallet_handle,
+            verkey,
+            dispatch_callback(boxed_callback_string!("indy_get_key_agreement_key", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_key_agreement_key: <<< res: {:?}", res);
+
+    res
+}
+
+/// Converts ed25519 verkey to X25519 public key.
+///
+/// Unlike indy_get_key_agreement_key the key doesn't need to be stored in the wallet,
+/// so it can be used for keys of other parties, for instance, to implement custom envelope formats
+/// on top of indy_crypto_box.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// verkey - ed25519 verkey to convert.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - key - base58 encoded X25519 public key with ":x25519" crypto type suffix.
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_convert_key(command_handle: CommandHandle,
+                               verkey: *const c_char,
+                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                    err: ErrorCode,
+                                                    key: *const c_char)>) -> ErrorCode {
+    trace!("indy_convert_key: >>> verkey: {:?}", verkey);
+
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_convert_key: entities >>> verkey: {:?}", verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::ConvertKey(
+            verkey,
+            dispatch_callback(boxed_callback_string!("indy_convert_key", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_convert_key: <<< res: {:?}", res);
+
+    res
+}
+
 /// Signs a message with a key.
 ///
 /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
@@ -208,12 +551,12 @@ pub  extern fn indy_crypto_sign(command_handle: CommandHandle,
             wallet_handle,
             signer_vk,
             message_raw,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, signature) = prepare_result_1!(result, Vec::new());
                 trace!("indy_crypto_sign: signature: {:?}", signature);
                 let (signature_raw, signature_len) = ctypes::vec_to_pointer(&signature);
                 cb(command_handle, err, signature_raw, signature_len)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -245,6 +588,7 @@ pub  extern fn indy_crypto_sign(command_handle: CommandHandle,
 /// Wallet*
 /// Ledger*
 /// Crypto*
+/// CommonInvalidStructure if verkey or signature is malformed (see indy_crypto_verify_with_strictness)
 #[no_mangle]
 pub  extern fn indy_crypto_verify(command_handle: CommandHandle,
                                   signer_vk: *const c_char,
@@ -271,11 +615,12 @@ pub  extern fn indy_crypto_verify(command_handle: CommandHandle,
             signer_vk,
             message_raw,
             signature_raw,
-            Box::new(move |result| {
+            true,
+            dispatch_callback(Box::new(move |result| {
                 let (err, valid) = prepare_result_1!(result, false);
                 trace!("indy_crypto_verify: valid: {:?}", valid);
                 cb(command_handle, err, valid)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -285,6 +630,70 @@ pub  extern fn indy_crypto_verify(command_handle: CommandHandle,
     res
 }
 
+/// Verify a signature with a verkey choosing how malformed inputs are reported.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// signer_vk: verkey of the message signer
+/// message_raw: a pointer to first byte of message that has been signed
+/// message_len: a message length
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+/// strict: true - malformed verkey or signature (bad base58, wrong length, unsupported key type) is reported as an error
+///                with the detail, the same as indy_crypto_verify does;
+///         false - malformed verkey or signature is reported as invalid signature (valid = false)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if signature is valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Crypto*
+/// CommonInvalidStructure if "strict" is set and verkey or signature is malformed
+/// UnknownCryptoTypeError if "strict" is set and crypto type of verkey is unsupported
+#[no_mangle]
+pub  extern fn indy_crypto_verify_with_strictness(command_handle: CommandHandle,
+                                                  signer_vk: *const c_char,
+                                                  message_raw: *const u8,
+                                                  message_len: u32,
+                                                  signature_raw: *const u8,
+                                                  signature_len: u32,
+                                                  strict: bool,
+                                                  cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                       err: ErrorCode,
+                                                                       valid: bool)>) -> ErrorCode {
+    trace!("indy_crypto_verify_with_strictness: >>> signer_vk: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}, strict: {:?}",
+           signer_vk, message_raw, message_len, signature_raw, signature_len, strict);
+
+    check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam2);
+    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_crypto_verify_with_strictness: entities >>> signer_vk: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}, strict: {:?}",
+           signer_vk, message_raw, message_len, signature_raw, signature_len, strict);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoVerify(
+            signer_vk,
+            message_raw,
+            signature_raw,
+            strict,
+            dispatch_callback(Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_crypto_verify_with_strictness: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_verify_with_strictness: <<< res: {:?}", res);
+
+    res
+}
+
 /// **** THIS FUNCTION WILL BE DEPRECATED USE indy_pack_message() INSTEAD ****
 /// Encrypt a message by authenticated-encryption scheme.
 ///
@@ -342,12 +751,12 @@ pub  extern fn indy_crypto_auth_crypt(command_handle: CommandHandle,
             sender_vk,
             recipient_vk,
             msg_data,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, encrypted_msg) = prepare_result_1!(result, Vec::new());
                 trace!("indy_crypto_auth_crypt: encrypted_msg: {:?}", encrypted_msg);
                 let (encrypted_msg_raw, encrypted_msg_len) = ctypes::vec_to_pointer(&encrypted_msg);
                 cb(command_handle, err, encrypted_msg_raw, encrypted_msg_len)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -410,13 +819,13 @@ pub  extern fn indy_crypto_auth_decrypt(command_handle: CommandHandle,
             wallet_handle,
             recipient_vk,
             encrypted_msg,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, sender_vk, msg) = prepare_result_2!(result, String::new(), Vec::new());
                 trace!("indy_crypto_auth_decrypt: sender_vk: {:?}, msg: {:?}", sender_vk, msg);
                 let (msg_data, msg_len) = ctypes::vec_to_pointer(&msg);
                 let sender_vk = ctypes::string_to_cstring(sender_vk);
                 cb(command_handle, err, sender_vk.as_ptr(), msg_data, msg_len)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -473,12 +882,12 @@ pub  extern fn indy_crypto_anon_crypt(command_handle: CommandHandle,
         .send(Command::Crypto(CryptoCommand::AnonymousEncrypt(
             recipient_vk,
             msg_data,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, encrypted_msg) = prepare_result_1!(result, Vec::new());
                 trace!("indy_crypto_anon_crypt: encrypted_msg: {:?}", encrypted_msg);
                 let (encrypted_msg_raw, encrypted_msg_len) = ctypes::vec_to_pointer(&encrypted_msg);
                 cb(command_handle, err, encrypted_msg_raw, encrypted_msg_len)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -539,12 +948,12 @@ pub  extern fn indy_crypto_anon_decrypt(command_handle: CommandHandle,
             wallet_handle,
             recipient_vk,
             encrypted_msg,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, msg) = prepare_result_1!(result, Vec::new());
                 trace!("indy_crypto_anon_decrypt: msg: {:?}", msg);
                 let (msg_data, msg_len) = ctypes::vec_to_pointer(&msg);
                 cb(command_handle, err, msg_data, msg_len)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -554,44 +963,182 @@ pub  extern fn indy_crypto_anon_decrypt(command_handle: CommandHandle,
     res
 }
 
-/// Packs a message by encrypting the message and serializes it in a JWE-like format (Experimental)
+/// Encrypts a message with libsodium crypto_box primitive using nonce provided by the caller.
 ///
-/// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
-/// for specific DID.
+/// Unlike indy_crypto_auth_crypt the result isn't wrapped into any envelope and contains only the ciphertext
+/// with authentication tag. The caller is responsible for never reusing the nonce with the same pair of keys.
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// wallet_handle: wallet handle (created by open_wallet).
-/// message: a pointer to the first byte of the message to be packed
-/// message_len: the length of the message
-/// receivers: a string in the format of a json list which will contain the list of receiver's keys
-///                the message is being encrypted for.
-///                Example:
-///                "[<receiver edge_agent_1 verkey>, <receiver edge_agent_2 verkey>]"
-/// sender: the sender's verkey as a string When null pointer is used in this parameter, anoncrypt is used
+/// sender_vk: id (verkey) of message sender. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// recipient_vk: verkey of message recipient. X25519 keys returned by indy_convert_key can be used as well.
+/// msg_data: a pointer to first byte of message that to be encrypted
+/// msg_len: a message length
+/// nonce_data: a pointer to first byte of 24 bytes nonce
+/// nonce_len: a nonce length
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// a JWE using authcrypt alg is defined below:
-/// {
-///     "protected": "b64URLencoded({
-///        "enc": "xsalsa20poly1305",
-///        "typ": "JWM/1.0",
-///        "alg": "Authcrypt",
-///        "recipients": [
-///            {
-///                "encrypted_key": base64URLencode(libsodium.crypto_box(my_key, their_vk, cek, cek_iv))
-///                "header": {
-///                     "kid": "base58encode(recipient_verkey)",
-///                     "sender" : base64URLencode(libsodium.crypto_box_seal(their_vk, base58encode(sender_vk)),
-///                     "iv" : base64URLencode(cek_iv)
-///                }
-///            },
-///        ],
-///     })",
-///     "iv": <b64URLencode(iv)>,
-///     "ciphertext": b64URLencode(encrypt_detached({'@type'...}, protected_value_encoded, iv, cek),
-///     "tag": <b64URLencode(tag)>
+/// an encrypted message as a pointer to array of bytes.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_box(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              sender_vk: *const c_char,
+                              recipient_vk: *const c_char,
+                              msg_data: *const u8,
+                              msg_len: u32,
+                              nonce_data: *const u8,
+                              nonce_len: u32,
+                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                   err: ErrorCode,
+                                                   encrypted_msg: *const u8,
+                                                   encrypted_len: u32)>) -> ErrorCode {
+    trace!("indy_crypto_box: >>> wallet_handle: {:?}, sender_vk: {:?}, recipient_vk: {:?}, msg_data: {:?}, msg_len: {:?}, nonce_data: {:?}, nonce_len: {:?}",
+           wallet_handle, sender_vk, recipient_vk, msg_data, msg_len, nonce_data, nonce_len);
+
+    check_useful_c_str!(sender_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(recipient_vk, ErrorCode::CommonInvalidParam4);
+    check_useful_c_byte_array!(msg_data, msg_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
+    check_useful_c_byte_array!(nonce_data, nonce_len, ErrorCode::CommonInvalidParam7, ErrorCode::CommonInvalidParam8);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_crypto_box: entities >>> wallet_handle: {:?}, sender_vk: {:?}, recipient_vk: {:?}, msg_data: {:?}, msg_len: {:?}, nonce_data: {:?}, nonce_len: {:?}",
+           wallet_handle, sender_vk, recipient_vk, msg_data, msg_len, nonce_data, nonce_len);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoBox(
+            wallet_handle,
+            sender_vk,
+            recipient_vk,
+            msg_data,
+            nonce_data,
+            dispatch_callback(Box::new(move |result| {
+                let (err, encrypted_msg) = prepare_result_1!(result, Vec::new());
+                trace!("indy_crypto_box: encrypted_msg: {:?}", encrypted_msg);
+                let (encrypted_msg_raw, encrypted_msg_len) = ctypes::vec_to_pointer(&encrypted_msg);
+                cb(command_handle, err, encrypted_msg_raw, encrypted_msg_len)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_box: <<< res: {:?}", res);
+
+    res
+}
+
+/// Decrypts a message encrypted with libsodium crypto_box primitive (see indy_crypto_box).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// recipient_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// sender_vk: verkey of message sender. X25519 keys returned by indy_convert_key can be used as well.
+/// encrypted_msg: a pointer to first byte of message that to be decrypted
+/// encrypted_len: a message length
+/// nonce_data: a pointer to first byte of 24 bytes nonce the message was encrypted with
+/// nonce_len: a nonce length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// decrypted message as a pointer to an array of bytes
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_box_open(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   recipient_vk: *const c_char,
+                                   sender_vk: *const c_char,
+                                   encrypted_msg: *const u8,
+                                   encrypted_len: u32,
+                                   nonce_data: *const u8,
+                                   nonce_len: u32,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        msg_data: *const u8,
+                                                        msg_len: u32)>) -> ErrorCode {
+    trace!("indy_crypto_box_open: >>> wallet_handle: {:?}, recipient_vk: {:?}, sender_vk: {:?}, encrypted_msg: {:?}, encrypted_len: {:?}, nonce_data: {:?}, nonce_len: {:?}",
+           wallet_handle, recipient_vk, sender_vk, encrypted_msg, encrypted_len, nonce_data, nonce_len);
+
+    check_useful_c_str!(recipient_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(sender_vk, ErrorCode::CommonInvalidParam4);
+    check_useful_c_byte_array!(encrypted_msg, encrypted_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
+    check_useful_c_byte_array!(nonce_data, nonce_len, ErrorCode::CommonInvalidParam7, ErrorCode::CommonInvalidParam8);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_crypto_box_open: entities >>> wallet_handle: {:?}, recipient_vk: {:?}, sender_vk: {:?}, encrypted_msg: {:?}, encrypted_len: {:?}, nonce_data: {:?}, nonce_len: {:?}",
+           wallet_handle, recipient_vk, sender_vk, encrypted_msg, encrypted_len, nonce_data, nonce_len);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoBoxOpen(
+            wallet_handle,
+            recipient_vk,
+            sender_vk,
+            encrypted_msg,
+            nonce_data,
+            dispatch_callback(Box::new(move |result| {
+                let (err, msg) = prepare_result_1!(result, Vec::new());
+                trace!("indy_crypto_box_open: msg: {:?}", msg);
+                let (msg_data, msg_len) = ctypes::vec_to_pointer(&msg);
+                cb(command_handle, err, msg_data, msg_len)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_box_open: <<< res: {:?}", res);
+
+    res
+}
+
+/// Packs a message by encrypting the message and serializes it in a JWE-like format (Experimental)
+///
+/// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
+/// for specific DID.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// message: a pointer to the first byte of the message to be packed
+/// message_len: the length of the message
+/// receivers: a string in the format of a json list which will contain the list of receiver's keys
+///                the message is being encrypted for.
+///                Example:
+///                "[<receiver edge_agent_1 verkey>, <receiver edge_agent_2 verkey>]"
+///                Key agreement keys returned by indy_get_key_agreement_key can be used as receiver's keys as well.
+/// sender: the sender's verkey as a string When null pointer is used in this parameter, anoncrypt is used
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// a JWE using authcrypt alg is defined below:
+/// {
+///     "protected": "b64URLencoded({
+///        "enc": "xsalsa20poly1305",
+///        "typ": "JWM/1.0",
+///        "alg": "Authcrypt",
+///        "recipients": [
+///            {
+///                "encrypted_key": base64URLencode(libsodium.crypto_box(my_key, their_vk, cek, cek_iv))
+///                "header": {
+///                     "kid": "base58encode(recipient_verkey)",
+///                     "sender" : base64URLencode(libsodium.crypto_box_seal(their_vk, base58encode(sender_vk)),
+///                     "iv" : base64URLencode(cek_iv)
+///                }
+///            },
+///        ],
+///     })",
+///     "iv": <b64URLencode(iv)>,
+///     "ciphertext": b64URLencode(encrypt_detached({'@type'...}, protected_value_encoded, iv, cek),
+///     "tag": <b64URLencode(tag)>
 /// }
 ///
 /// Alternative example in using anoncrypt alg is defined below:
@@ -654,17 +1201,20 @@ pub extern fn indy_pack_message(
         return IndyError::from_msg(IndyErrorKind::InvalidParam(4), "Empty RecipientKeys has been passed").into();
     }
 
+    let mode = PackMode::for_sender(sender.as_ref());
+
     let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::PackMessage(
         message,
         receiver_list,
         sender,
+        mode,
         wallet_handle,
-        Box::new(move |result| {
+        dispatch_callback(Box::new(move |result| {
             let (err, jwe) = prepare_result_1!(result, Vec::new());
             trace!("indy_auth_pack_message: jwe: {:?}", jwe);
             let (jwe_data, jwe_len) = ctypes::vec_to_pointer(&jwe);
             cb(command_handle, err, jwe_data, jwe_len)
-        }),
+        })),
     )));
 
     let res = prepare_result!(result);
@@ -674,6 +1224,185 @@ pub extern fn indy_pack_message(
     res
 }
 
+/// Packs a message with explicitly chosen packing mode (Experimental)
+///
+/// Works the same way as indy_pack_message, but the way the sender is (or is not) disclosed
+/// to the recipients is defined by the mode instead of presence of the sender key.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// message: a pointer to the first byte of the message to be packed
+/// message_len: the length of the message
+/// receiver_keys: a string in the format of a json list which will contain the list of receiver's keys
+///                the message is being encrypted for.
+///                Example:
+///                "[<receiver edge_agent_1 verkey>, <receiver edge_agent_2 verkey>]"
+/// sender: the sender's verkey as a string. Must be null for anoncrypt mode and must be set for other modes.
+/// mode: packing mode. One of:
+///     "authcrypt" - the sender is authenticated and its key is disclosed in the message header
+///                   (the same as indy_pack_message with sender),
+///     "anoncrypt" - the sender is anonymous (the same as indy_pack_message without sender),
+///     "authcrypt_with_hidden_sender" - the sender is authenticated, but the message header is indistinguishable
+///                   from anoncrypt one, so the sender key is visible only to the recipients.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// a JWE in the same format as indy_pack_message returns
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_pack_message_with_mode(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    message: *const u8,
+    message_len: u32,
+    receiver_keys: *const c_char,
+    sender: *const c_char,
+    mode: *const c_char,
+    cb: Option<extern fn(xcommand_handle: CommandHandle, err: ErrorCode, jwe_data: *const u8, jwe_len: u32)>,
+) -> ErrorCode {
+    trace!("indy_pack_message_with_mode: >>> wallet_handle: {:?}, message: {:?}, message_len {:?},\
+            receiver_keys: {:?}, sender: {:?}, mode: {:?}", wallet_handle, message, message_len, receiver_keys, sender, mode);
+
+    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(receiver_keys, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(sender, ErrorCode::CommonInvalidParam5);
+    check_useful_c_str!(mode, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_pack_message_with_mode: entities >>> wallet_handle: {:?}, message: {:?}, message_len {:?},\
+            receiver_keys: {:?}, sender: {:?}, mode: {:?}", wallet_handle, message, message_len, receiver_keys, sender, mode);
+
+    let receiver_list = match serde_json::from_str::<Vec<String>>(&receiver_keys) {
+        Ok(x) => x,
+        Err(_) => {
+            return IndyError::from_msg(IndyErrorKind::InvalidParam(4), "Invalid RecipientKeys has been passed").into();
+        },
+    };
+
+    if receiver_list.is_empty() {
+        return IndyError::from_msg(IndyErrorKind::InvalidParam(4), "Empty RecipientKeys has been passed").into();
+    }
+
+    let mode = match serde_json::from_value::<PackMode>(serde_json::Value::String(mode)) {
+        Ok(mode) => mode,
+        Err(_) => {
+            return IndyError::from_msg(IndyErrorKind::InvalidParam(6), "Invalid pack mode has been passed").into();
+        },
+    };
+
+    let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::PackMessage(
+        message,
+        receiver_list,
+        sender,
+        mode,
+        wallet_handle,
+        dispatch_callback(Box::new(move |result| {
+            let (err, jwe) = prepare_result_1!(result, Vec::new());
+            trace!("indy_pack_message_with_mode: jwe: {:?}", jwe);
+            let (jwe_data, jwe_len) = ctypes::vec_to_pointer(&jwe);
+            cb(command_handle, err, jwe_data, jwe_len)
+        })),
+    )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_pack_message_with_mode: <<< res: {:?}", res);
+
+    res
+}
+
+
+/// Packs a message for receivers identified by their DIDs (Experimental)
+///
+/// Receivers keys are resolved with GET_NYM requests to the ledger. Resolved keys are cached in the wallet,
+/// so packing of the next messages doesn't require a ledger round trip.
+/// Cached key older than minFresh is still used but refreshed in background, so rotation of receiver's key
+/// is noticed by the next messages. Cached key older than maxStale is fetched from the ledger before packing.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// message: a pointer to the first byte of the message to be packed
+/// message_len: the length of the message
+/// receiver_dids: a string in the format of a json list which will contain the list of receiver's DIDs
+///                the message is being encrypted for.
+///                Example:
+///                "[<receiver DID>, <receiver DID>]"
+/// sender: the sender's verkey as a string When null pointer is used in this parameter, anoncrypt is used
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, 60 by default) Use cached key without refresh if not older than this many seconds.
+///              -1 means do not check age.
+///    maxStale: (int, optional, 3600 by default) Use cached key older than minFresh but not older than this many seconds
+///              and refresh it in background. -1 means do not check age.
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// a JWE in the same format as indy_pack_message returns
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_pack_message_for_dids(
+    command_handle: CommandHandle,
+    pool_handle: PoolHandle,
+    wallet_handle: WalletHandle,
+    message: *const u8,
+    message_len: u32,
+    receiver_dids: *const c_char,
+    sender: *const c_char,
+    options_json: *const c_char,
+    cb: Option<extern fn(xcommand_handle: CommandHandle, err: ErrorCode, jwe_data: *const u8, jwe_len: u32)>,
+) -> ErrorCode {
+    trace!("indy_pack_message_for_dids: >>> pool_handle: {:?}, wallet_handle: {:?}, message: {:?}, message_len {:?}, \
+            receiver_dids: {:?}, sender: {:?}, options_json: {:?}", pool_handle, wallet_handle, message, message_len, receiver_dids, sender, options_json);
+
+    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_json!(receiver_dids, ErrorCode::CommonInvalidParam6, Vec<DidValue>);
+    check_useful_opt_c_str!(sender, ErrorCode::CommonInvalidParam7);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam8, NymCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_pack_message_for_dids: entities >>> pool_handle: {:?}, wallet_handle: {:?}, message: {:?}, message_len {:?}, \
+            receiver_dids: {:?}, sender: {:?}, options_json: {:?}", pool_handle, wallet_handle, message, message_len, receiver_dids, sender, options_json);
+
+    if receiver_dids.is_empty() {
+        return IndyError::from_msg(IndyErrorKind::InvalidParam(6), "Empty receiver DIDs has been passed").into();
+    }
+
+    let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::PackMessageForDids(
+        message,
+        receiver_dids,
+        sender,
+        pool_handle,
+        wallet_handle,
+        options_json,
+        dispatch_callback(Box::new(move |result| {
+            let (err, jwe) = prepare_result_1!(result, Vec::new());
+            trace!("indy_pack_message_for_dids: jwe: {:?}", jwe);
+            let (jwe_data, jwe_len) = ctypes::vec_to_pointer(&jwe);
+            cb(command_handle, err, jwe_data, jwe_len)
+        })),
+    )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_pack_message_for_dids: <<< res: {:?}", res);
+
+    res
+}
 
 /// Unpacks a JWE-like formatted message outputted by indy_pack_message (Experimental)
 ///
@@ -689,7 +1418,9 @@ pub extern fn indy_pack_message(
 /// {
 ///     message: <decrypted message>,
 ///     sender_verkey: <sender_verkey>,
-///     recipient_verkey: <recipient_verkey>
+///     recipient_verkey: <recipient_verkey>,
+///     mode: "authcrypt" or "authcrypt_with_hidden_sender" - mode the message was packed with,
+///     sender_authenticated: true
 /// }
 ///
 /// OR
@@ -697,7 +1428,9 @@ pub extern fn indy_pack_message(
 /// if anoncrypt was used to pack the message returns this json structure:
 /// {
 ///     message: <decrypted message>,
-///     recipient_verkey: <recipient_verkey>
+///     recipient_verkey: <recipient_verkey>,
+///     mode: "anoncrypt",
+///     sender_authenticated: false
 /// }
 ///
 ///
@@ -747,14 +1480,14 @@ pub extern fn indy_unpack_message(
     let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::UnpackMessage(
         jwe_struct,
         wallet_handle,
-        Box::new(move |result| {
+        dispatch_callback(Box::new(move |result| {
             let (err, res_json) = prepare_result_1!(result, Vec::new());
             trace!("indy_unpack_message: cb command_handle: {:?}, err: {:?}, res_json: {:?}",
                 command_handle, err, res_json
             );
             let (res_json_data, res_json_len) = ctypes::vec_to_pointer(&res_json);
             cb(command_handle, err, res_json_data, res_json_len)
-        }),
+        })),
     )));
 
     let res = prepare_result!(result);
@@ -763,3 +1496,159 @@ pub extern fn indy_unpack_message(
 
     res
 }
+
+/// Lists dead letters stored in the wallet (Experimental)
+///
+/// A dead letter is stored when indy_unpack_message fails (for example message was sent to a rotated key),
+/// so the message isn't lost and can be retried later with indy_retry_dead_letter.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// dead_letters_json: list of dead letters ordered by receiving time
+/// [{
+///     "id": string, dead letter id
+///     "message": <JWE which failed to be unpacked>,
+///     "error": string, error of the last unpack attempt
+///     "received": int, receiving time as unix timestamp in seconds
+///     "attempts": int, number of unpack attempts
+/// }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_list_dead_letters(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          dead_letters_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_list_dead_letters: >>> wallet_handle: {:?}", wallet_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    trace!("indy_list_dead_letters: entities >>> wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::ListDeadLetters(
+            wallet_handle,
+            dispatch_callback(boxed_callback_string!("indy_list_dead_letters", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_list_dead_letters: <<< res: {:?}", res);
+
+    res
+}
+
+/// Retries unpacking of the message stored as dead letter (Experimental)
+///
+/// Dead letter is removed from the wallet if the message is unpacked successfully,
+/// otherwise its error and number of attempts are updated.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// dead_letter_id: id of the dead letter (see indy_list_dead_letters).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// the same json structure as indy_unpack_message returns
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_retry_dead_letter(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    dead_letter_id: *const c_char,
+    cb: Option<
+        extern fn(
+            xcommand_handle: CommandHandle,
+            err: ErrorCode,
+            res_json_data : *const u8,
+            res_json_len : u32
+        ),
+    >,
+) -> ErrorCode {
+    trace!("indy_retry_dead_letter: >>> wallet_handle: {:?}, dead_letter_id: {:?}", wallet_handle, dead_letter_id);
+
+    check_useful_c_str!(dead_letter_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_retry_dead_letter: entities >>> wallet_handle: {:?}, dead_letter_id: {:?}", wallet_handle, dead_letter_id);
+
+    let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::RetryDeadLetter(
+        wallet_handle,
+        dead_letter_id,
+        dispatch_callback(Box::new(move |result| {
+            let (err, res_json) = prepare_result_1!(result, Vec::new());
+            trace!("indy_retry_dead_letter: cb command_handle: {:?}, err: {:?}, res_json: {:?}",
+                command_handle, err, res_json
+            );
+            let (res_json_data, res_json_len) = ctypes::vec_to_pointer(&res_json);
+            cb(command_handle, err, res_json_data, res_json_len)
+        })),
+    )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_retry_dead_letter: <<< res: {:?}", res);
+
+    res
+}
+
+/// Removes dead letters from the wallet (Experimental)
+///
+/// At most 100 dead letters are kept in the wallet, the oldest ones are dropped when a new one is stored.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// received_before: remove only dead letters received before this time (unix timestamp in seconds),
+///                  -1 to remove all dead letters.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_purge_dead_letters(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      received_before: i64,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_purge_dead_letters: >>> wallet_handle: {:?}, received_before: {:?}", wallet_handle, received_before);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let received_before: Option<u64> = if received_before == -1 { None } else { Some(received_before as u64) };
+
+    trace!("indy_purge_dead_letters: entities >>> wallet_handle: {:?}, received_before: {:?}", wallet_handle, received_before);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::PurgeDeadLetters(
+            wallet_handle,
+            received_before,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_purge_dead_letters: ");
+                cb(command_handle, err)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_dead_letters: <<< res: {:?}", res);
+
+    res
+}
@@ -1,7 +1,7 @@
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, PoolHandle};
-use crate::commands::{Command, CommandExecutor};
+use crate::commands::{Command, CommandExecutor, dispatch_callback};
 use crate::commands::did::DidCommand;
-use crate::domain::crypto::did::{TheirDidInfo, DidValue, MyDidInfo, DidMethod};
+use crate::domain::crypto::did::{TheirDidInfo, DidValue, MyDidInfo, MyDidsInfo, DidMethod, DidOwnershipProof};
 use crate::domain::crypto::key::KeyInfo;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -11,7 +11,7 @@ use serde_json;
 use libc::c_char;
 
 use std::ptr;
-use crate::domain::ledger::attrib::Endpoint;
+use crate::domain::ledger::attrib::{Endpoint, ServiceEndpoint};
 
 
 /// Creates keys (signing and encryption keys) for a new
@@ -33,9 +33,14 @@ use crate::domain::ledger::attrib::Endpoint;
 ///     "seed": string, (optional) Seed that allows deterministic did creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+///               'secp256k1' and 'p256' keys can be used for signing only)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///     "derivation_path": string, (optional) SLIP-0010 path like "m/44'/0'/0'" to derive the ed25519 key by from "seed"
+///                                used as a master seed of 16 to 64 bytes.
+///                                Only hardened indexes are supported. The path is stored with the key (see indy_get_key_derivation_path).
+///     "seed_encoding": string, (required if "derivation_path" is set) Encoding of the master seed: 'hex', 'base64' or 'utf8'.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -70,13 +75,13 @@ pub  extern fn indy_create_and_store_my_did(command_handle: CommandHandle,
         .send(Command::Did(DidCommand::CreateAndStoreMyDid(
             wallet_handle,
             did_info,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, did, verkey) = prepare_result_2!(result, String::new(), String::new());
                 trace!("indy_create_and_store_my_did: did: {:?}, verkey: {:?}", did, verkey);
                 let did = ctypes::string_to_cstring(did);
                 let verkey = ctypes::string_to_cstring(verkey);
                 cb(command_handle, err, did.as_ptr(), verkey.as_ptr())
-            }),
+            })),
         )));
 
     let res = prepare_result!(result);
@@ -86,6 +91,76 @@ pub  extern fn indy_create_and_store_my_did(command_handle: CommandHandle,
     res
 }
 
+/// Creates and stores in the wallet a batch of new DIDs (owned by the caller of the library)
+/// in one command, e.g. to pre-provision pairwise DIDs for connection onboarding.
+/// Equivalent of calling "indy_create_and_store_my_did" "count" times.
+/// Either all DIDs are stored or none of them if the command fails.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// count: number of DIDs to create (from 1 to 1000).
+/// options_json: batch options as json.
+/// {
+///     "hd_seed": string, (optional) Master seed of 16 to 64 bytes the ed25519 key
+///                of every DID is derived from by SLIP-0010 path m/<index>' (see "derivation_path" of indy_create_and_store_my_did).
+///                Allows to recreate the same DIDs later. Random DIDs are created if not set.
+///     "hd_seed_encoding": string, (required if "hd_seed" is set) Encoding of "hd_seed": 'hex', 'base64' or 'utf8'.
+///     "start_index": int, (optional) Index of the first DID derived from "hd_seed" (0 by default).
+///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+///               'secp256k1' and 'p256' keys can be used for signing only)
+///     "cid": bool, (optional; if not set then false is used;)
+///     "method_name": string, (optional) method name to create fully qualified dids.
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   dids_json: list of created DIDs
+/// [{
+///     "did": string, DID generated and stored in the wallet
+///     "verkey": string, The DIDs verification key
+///     "index": int, (only for "hd_seed") index the DID seed was derived with
+/// }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_create_and_store_my_dids(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            count: u32,
+                                            options_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 dids_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_create_and_store_my_dids: >>> wallet_handle: {:?}, count: {:?}, options_json: {:?}", wallet_handle, count, options_json);
+
+    check_useful_validatable_json!(options_json, ErrorCode::CommonInvalidParam4, MyDidsInfo);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_create_and_store_my_dids: entities >>> wallet_handle: {:?}, count: {:?}, options_json: {:?}", wallet_handle, count, secret!(&options_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::CreateAndStoreMyDids(
+            wallet_handle,
+            count,
+            options_json,
+            dispatch_callback(boxed_callback_string!("indy_create_and_store_my_dids", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_create_and_store_my_dids: <<< res: {:?}", res);
+
+    res
+}
+
 /// Generated temporary keys (signing and encryption keys) for an existing
 /// DID (owned by the caller of the library).
 ///
@@ -98,7 +173,8 @@ pub  extern fn indy_create_and_store_my_did(command_handle: CommandHandle,
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+///               'secp256k1' and 'p256' keys can be used for signing only)
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -135,7 +211,7 @@ pub  extern fn indy_replace_keys_start(command_handle: CommandHandle,
             wallet_handle,
             key_info,
             did,
-            boxed_callback_string!("indy_replace_keys_start", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_replace_keys_start", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -180,11 +256,11 @@ pub  extern fn indy_replace_keys_apply(command_handle: CommandHandle,
         .send(Command::Did(DidCommand::ReplaceKeysApply(
             wallet_handle,
             did,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_replace_keys_apply:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -237,11 +313,11 @@ pub  extern fn indy_store_their_did(command_handle: CommandHandle,
         .send(Command::Did(DidCommand::StoreTheirDid(
             wallet_handle,
             identity_json,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_store_their_did:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -302,7 +378,7 @@ pub extern fn indy_key_for_did(command_handle: CommandHandle,
             pool_handle,
             wallet_handle,
             did,
-            boxed_callback_string!("indy_key_for_did", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_key_for_did", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -358,7 +434,7 @@ pub extern fn indy_key_for_local_did(command_handle: CommandHandle,
         .send(Command::Did(DidCommand::KeyForLocalDid(
             wallet_handle,
             did,
-            boxed_callback_string!("indy_key_for_local_did", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_key_for_local_did", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -413,11 +489,11 @@ pub extern fn indy_set_endpoint_for_did(command_handle: CommandHandle,
             wallet_handle,
             did,
             endpoint,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_set_endpoint_for_did:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -468,14 +544,14 @@ pub extern fn indy_get_endpoint_for_did(command_handle: CommandHandle,
             wallet_handle,
             pool_handle,
             did,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, address, transport_vk) = prepare_result_2!(result, String::new(), None);
                 trace!("indy_get_endpoint_for_did: address: {:?}, transport_vk: {:?}", address, transport_vk);
                 let address = ctypes::string_to_cstring(address);
                 let transport_vk = transport_vk.map(ctypes::string_to_cstring);
                 cb(command_handle, err, address.as_ptr(),
                    transport_vk.as_ref().map(|vk| vk.as_ptr()).unwrap_or(ptr::null()));
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -524,11 +600,11 @@ pub extern fn indy_set_did_metadata(command_handle: CommandHandle,
             wallet_handle,
             did,
             metadata,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_set_did_metadata:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -575,7 +651,7 @@ pub extern fn indy_get_did_metadata(command_handle: CommandHandle,
         .send(Command::Did(DidCommand::GetDidMetadata(
             wallet_handle,
             did,
-            boxed_callback_string!("indy_get_did_metadata", cb, command_handle))));
+            dispatch_callback(boxed_callback_string!("indy_get_did_metadata", cb, command_handle)))));
 
     let res = prepare_result!(result);
 
@@ -584,6 +660,126 @@ pub extern fn indy_get_did_metadata(command_handle: CommandHandle,
     res
 }
 
+/// Designates the key to be used by my DID for the particular purpose.
+///
+/// By default the single DID verkey is used for everything. With designated keys the DID can
+/// authenticate, sign assertions and receive packed messages with different keys.
+/// Designated keys are kept in the wallet only and aren't listed in the DID Document returned by indy_resolve_did.
+/// Issuance log heads and proof holder bindings are signed with the DID verkey anyway,
+/// as verifiers check them against the verkey of the DID NYM.
+/// "keyAgreement" key receives messages packed for the DID by indy_pack_message_for_dids.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did - my DID.
+/// purpose - key purpose: "authentication" | "assertionMethod" | "keyAgreement".
+/// verkey - the key to use:
+///     - for "authentication" and "assertionMethod": verkey of the key stored in the wallet (created by indy_create_key).
+///     - for "keyAgreement": key agreement key returned by indy_get_key_agreement_key.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_set_did_key(command_handle: CommandHandle,
+                               wallet_handle: WalletHandle,
+                               did: *const c_char,
+                               purpose: *const c_char,
+                               verkey: *const c_char,
+                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_set_did_key: >>> wallet_handle: {:?}, did: {:?}, purpose: {:?}, verkey: {:?}", wallet_handle, did, purpose, verkey);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_str!(purpose, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_set_did_key: entities >>> wallet_handle: {:?}, did: {:?}, purpose: {:?}, verkey: {:?}", wallet_handle, did, purpose, verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::SetDidKey(
+            wallet_handle,
+            did,
+            purpose,
+            verkey,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_set_did_key:");
+                cb(command_handle, err)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_set_did_key: <<< res: {:?}", res);
+
+    res
+}
+
+/// Returns keys my DID uses for every purpose.
+///
+/// Purposes without designated key fall back to the DID verkey,
+/// so "assertionMethod" key should be used to sign and "keyAgreement" key to pack messages for the DID.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did - my DID.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - did_keys_json - keys of the DID:
+///     {
+///         "authentication": string, - designated key or DID verkey
+///         "assertionMethod": string, - designated key or DID verkey
+///         "keyAgreement": optional<string>, - designated key or key agreement key of DID verkey (absent for not ed25519 DID verkey)
+///     }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_get_did_keys(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                did: *const c_char,
+                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                     err: ErrorCode,
+                                                     did_keys_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_did_keys: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_did_keys: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::GetDidKeys(
+            wallet_handle,
+            did,
+            dispatch_callback(boxed_callback_string!("indy_get_did_keys", cb, command_handle)))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_did_keys: <<< res: {:?}", res);
+
+    res
+}
+
 /// Retrieves the information about the giving DID in the wallet.
 ///
 /// #Params
@@ -627,7 +823,7 @@ pub extern fn indy_get_my_did_with_meta(command_handle: CommandHandle,
         .send(Command::Did(DidCommand::GetMyDidWithMeta(
             wallet_handle,
             my_did,
-            boxed_callback_string!("indy_get_my_did_with_meta", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_get_my_did_with_meta", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -674,7 +870,7 @@ pub extern fn indy_list_my_dids_with_meta(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Did(DidCommand::ListMyDidsWithMeta(
             wallet_handle,
-            boxed_callback_string!("indy_list_my_dids_with_meta", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_list_my_dids_with_meta", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -721,7 +917,7 @@ pub  extern fn indy_abbreviate_verkey(command_handle: CommandHandle,
         .send(Command::Did(DidCommand::AbbreviateVerkey(
             did,
             full_verkey,
-            boxed_callback_string!("indy_abbreviate_verkey", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_abbreviate_verkey", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -774,7 +970,7 @@ pub extern fn indy_qualify_did(command_handle: CommandHandle,
             wallet_handle,
             did,
             method,
-            boxed_callback_string!("indy_qualify_did", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_qualify_did", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -782,4 +978,464 @@ pub extern fn indy_qualify_did(command_handle: CommandHandle,
     trace!("indy_qualify_did: <<< res: {:?}", res);
 
     res
-}
\ No newline at end of file
+}
+/// Creates a self-contained proof of control over DID written to the ledger.
+///
+/// The DID's key stored in the wallet signs the challenge bound to the DID and the audience (verifier)
+/// the proof is created for, so neither the challenge alone nor the proof for another verifier can be replayed.
+/// The proof references the ledger NYM transaction (by sequence number) so it can be checked by any party
+/// with access to the ledger.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// did: DID stored in the wallet and written to the ledger.
+/// challenge: challenge (nonce) provided by the verifier.
+/// audience: identifier of the verifier the proof is created for (for example its DID or URL).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - proof_json: proof of DID control:
+///     {
+///         "did": string,
+///         "verkey": string,
+///         "challenge": string,
+///         "audience": string,
+///         "signature": string, - base58 encoded signature of "indy-did-ownership-proof/v1" prefix followed by
+///                                did, challenge and audience, each prefixed with its length as 4-byte big-endian integer
+///         "seqNo": Optional<int> - sequence number of the DID's NYM transaction on the ledger
+///     }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_create_did_ownership_proof(command_handle: CommandHandle,
+                                              wallet_handle: WalletHandle,
+                                              pool_handle: PoolHandle,
+                                              did: *const c_char,
+                                              challenge: *const c_char,
+                                              audience: *const c_char,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode,
+                                                                   proof_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_create_did_ownership_proof: >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, challenge: {:?}, audience: {:?}", wallet_handle, pool_handle, did, challenge, audience);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_c_str!(challenge, ErrorCode::CommonInvalidParam5);
+    check_useful_c_str!(audience, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_create_did_ownership_proof: entities >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, challenge: {:?}, audience: {:?}", wallet_handle, pool_handle, did, challenge, audience);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::CreateDidOwnershipProof(
+            wallet_handle,
+            pool_handle,
+            did,
+            challenge,
+            audience,
+            dispatch_callback(boxed_callback_string!("indy_create_did_ownership_proof", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_create_did_ownership_proof: <<< res: {:?}", res);
+
+    res
+}
+
+/// Checks NYM of DID stored in the wallet against the ledger.
+///
+/// Observed verkey and role are remembered in the wallet. If the ledger verkey differs from the wallet one
+/// (and isn't a key rotation started by indy_replace_keys_start) or the role differs from previously observed,
+/// "did_nym_changed" event is reported to the listener set by indy_set_event_listener.
+/// NYM of the DID is also checked this way while creating of DID ownership proof.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// did: DID stored in the wallet and written to the ledger.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - nym_json: NYM state on the ledger:
+///     {
+///         "verkey": string,
+///         "role": Optional<string>,
+///         "changed": bool - whether NYM has been changed not through this wallet
+///     }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_check_my_did_nym(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    pool_handle: PoolHandle,
+                                    did: *const c_char,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         nym_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_check_my_did_nym: >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}", wallet_handle, pool_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_check_my_did_nym: entities >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}", wallet_handle, pool_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::CheckMyDidNym(
+            wallet_handle,
+            pool_handle,
+            did,
+            dispatch_callback(boxed_callback_string!("indy_check_my_did_nym", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_check_my_did_nym: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies a proof of DID control created by indy_create_did_ownership_proof.
+///
+/// The proof is valid if it answers the given challenge, is created for the given audience, the signature
+/// matches the verkey and the verkey (and NYM transaction sequence number if present) matches the current ledger state.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// proof_json: proof of DID control created by indy_create_did_ownership_proof.
+/// challenge: challenge expected to be signed.
+/// audience: identifier of the verifier expected in the proof.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - valid: true - if the proof is correct, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_verify_did_ownership_proof(command_handle: CommandHandle,
+                                              pool_handle: PoolHandle,
+                                              proof_json: *const c_char,
+                                              challenge: *const c_char,
+                                              audience: *const c_char,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode,
+                                                                   valid: bool)>) -> ErrorCode {
+    trace!("indy_verify_did_ownership_proof: >>> pool_handle: {:?}, proof_json: {:?}, challenge: {:?}, audience: {:?}", pool_handle, proof_json, challenge, audience);
+
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, DidOwnershipProof);
+    check_useful_c_str!(challenge, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(audience, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_verify_did_ownership_proof: entities >>> pool_handle: {:?}, proof_json: {:?}, challenge: {:?}, audience: {:?}", pool_handle, proof_json, challenge, audience);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::VerifyDidOwnershipProof(
+            pool_handle,
+            proof_json,
+            challenge,
+            audience,
+            dispatch_callback(Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_verify_did_ownership_proof: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verify_did_ownership_proof: <<< res: {:?}", res);
+
+    res
+}
+
+/// Publishes endpoint of the DID to the ledger as standardized endpoint ATTRIB:
+///     {"endpoint": {"endpoint": string, "types": [string, ...], "routingKeys": [string, ...]}}
+/// and updates the endpoint address stored in the wallet for this DID (see indy_get_endpoint_for_did).
+///
+/// ATTRIB transaction is signed by the DID itself, so the DID must be stored in the wallet
+/// and written to the ledger.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// did: The DID to publish endpoint for.
+/// address: The DIDs endpoint address (for example: "https://agent.example.com:8080/messages").
+/// types_json: (Optional) json array of endpoint types (for example: ["did-communication"]).
+/// routing_keys_json: (Optional) json array of routing keys (verkeys of mediators) ordered from the recipient.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_publish_endpoint_for_did(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            pool_handle: PoolHandle,
+                                            did: *const c_char,
+                                            address: *const c_char,
+                                            types_json: *const c_char,
+                                            routing_keys_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_publish_endpoint_for_did: >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, address: {:?}, types_json: {:?}, routing_keys_json: {:?}",
+           wallet_handle, pool_handle, did, address, types_json, routing_keys_json);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_c_str!(address, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_json!(types_json, ErrorCode::CommonInvalidParam6, Vec<String>);
+    check_useful_opt_json!(routing_keys_json, ErrorCode::CommonInvalidParam7, Vec<String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_publish_endpoint_for_did: entities >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, address: {:?}, types_json: {:?}, routing_keys_json: {:?}",
+           wallet_handle, pool_handle, did, address, types_json, routing_keys_json);
+
+    let endpoint = ServiceEndpoint {
+        endpoint: address,
+        types: types_json.unwrap_or_default(),
+        routing_keys: routing_keys_json.unwrap_or_default(),
+        verkey: None,
+    };
+
+    if let Err(err) = endpoint.validate() {
+        return err_msg(IndyErrorKind::InvalidStructure, err).into();
+    }
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::PublishEndpointForDid(
+            wallet_handle,
+            pool_handle,
+            did,
+            endpoint,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_publish_endpoint_for_did:");
+                cb(command_handle, err)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_publish_endpoint_for_did: <<< res: {:?}", res);
+
+    res
+}
+
+/// Resolves endpoint of the DID published by indy_publish_endpoint_for_did (or written as
+/// endpoint ATTRIB by other means) into DID Document service entry.
+///
+/// Recipient key of the service is the current verkey of the DID fetched from the ledger.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// did: The DID to resolve endpoint.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - service_json: DID Document service entry:
+///     {
+///         "id": string, - fully qualified DID with the service type fragment
+///         "type": string, - the first endpoint type published ("did-communication" if types aren't published)
+///         "priority": 0,
+///         "recipientKeys": [string], - verkey of the DID
+///         "routingKeys": [string, ...],
+///         "serviceEndpoint": string
+///     }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_resolve_service_endpoint_for_did(command_handle: CommandHandle,
+                                                    pool_handle: PoolHandle,
+                                                    did: *const c_char,
+                                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                         err: ErrorCode,
+                                                                         service_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_resolve_service_endpoint_for_did: >>> pool_handle: {:?}, did: {:?}", pool_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_resolve_service_endpoint_for_did: entities >>> pool_handle: {:?}, did: {:?}", pool_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ResolveServiceEndpointForDid(
+            pool_handle,
+            did,
+            dispatch_callback(boxed_callback_string!("indy_resolve_service_endpoint_for_did", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_resolve_service_endpoint_for_did: <<< res: {:?}", res);
+
+    res
+}
+
+/// Resolves the DID into W3C DID Document assembled from its NYM and endpoint ATTRIB written to the ledger.
+///
+/// Verification method of the document is the current verkey of the DID. Service is built
+/// from endpoint ATTRIB as indy_resolve_service_endpoint_for_did does. Transport key published
+/// with the endpoint is added as one more verification method if it differs from the NYM verkey.
+/// X25519 key agreement key derived from ed25519 NYM verkey is added as "keyAgreement" method.
+/// Keys designated by indy_set_did_key aren't published, so they aren't listed.
+/// Source of every entry is reported in "provenance" field.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// did: The DID to resolve.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - did_doc_json: DID Document:
+///     {
+///         "@context": "https://www.w3.org/ns/did/v1",
+///         "id": string, - fully qualified DID
+///         "verificationMethod": [{
+///             "id": string, - fully qualified DID with "#key-1" fragment
+///             "type": "Ed25519VerificationKey2018" | "EcdsaSecp256k1VerificationKey2019" | "EcdsaSecp256r1VerificationKey2019"
+///                     | "X25519KeyAgreementKey2019",
+///             "controller": string, - fully qualified DID
+///             "publicKeyBase58": string - verkey of the DID
+///         }],
+///         "authentication": [string], - ids of verification methods
+///         "assertionMethod": [string], - ids of verification methods
+///         "keyAgreement": optional<[string]>, - ids of "X25519KeyAgreementKey2019" verification methods
+///         "service": [{ - one per published endpoint type, empty if no endpoint ATTRIB is written for the DID
+///             "id": string,
+///             "type": string, - endpoint type ("did-communication" if types aren't published)
+///             "priority": 0,
+///             "recipientKeys": [string],
+///             "routingKeys": [string, ...],
+///             "serviceEndpoint": string
+///         }],
+///         "pairwise": optional<{ - pairwise with the DID stored in the wallet (set by indy_resolve_did_with_wallet only)
+///             "myDid": string,
+///             "metadata": optional<string>
+///         }>,
+///         "provenance": { - source of each verification method and service (by id) and pairwise (by "pairwise" key):
+///             <id>: "ledger:NYM" | "ledger:ATTRIB" | "wallet:pairwise"
+///         }
+///     }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_resolve_did(command_handle: CommandHandle,
+                               pool_handle: PoolHandle,
+                               did: *const c_char,
+                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                    err: ErrorCode,
+                                                    did_doc_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_resolve_did: >>> pool_handle: {:?}, did: {:?}", pool_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_resolve_did: entities >>> pool_handle: {:?}, did: {:?}", pool_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ResolveDid(
+            pool_handle,
+            None,
+            did,
+            dispatch_callback(boxed_callback_string!("indy_resolve_did", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_resolve_did: <<< res: {:?}", res);
+
+    res
+}
+
+/// Resolves the DID into W3C DID Document as indy_resolve_did does and merges pairwise
+/// with the DID stored in the wallet into "pairwise" field of the document.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did: The DID to resolve.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - did_doc_json: DID Document (see `did_doc_json` of indy_resolve_did)
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_resolve_did_with_wallet(command_handle: CommandHandle,
+                                           pool_handle: PoolHandle,
+                                           wallet_handle: WalletHandle,
+                                           did: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                did_doc_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_resolve_did_with_wallet: >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}", pool_handle, wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_resolve_did_with_wallet: entities >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}", pool_handle, wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ResolveDid(
+            pool_handle,
+            Some(wallet_handle),
+            did,
+            dispatch_callback(boxed_callback_string!("indy_resolve_did_with_wallet", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_resolve_did_with_wallet: <<< res: {:?}", res);
+
+    res
+}
@@ -5,7 +5,7 @@ use indy_utils::ctypes;
 use libc::c_char;
 use serde_json;
 
-use crate::commands::{Command, CommandExecutor};
+use crate::commands::{Command, CommandExecutor, cancellable_callback, dispatch_callback};
 use crate::commands::ledger::LedgerCommand;
 use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionId};
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryId};
@@ -15,7 +15,10 @@ use crate::domain::crypto::did::DidValue;
 use crate::domain::ledger::auth_rule::{AuthRules, Constraint};
 use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, GetTxnAuthorAgreementData};
 use crate::domain::ledger::node::NodeOperationData;
+use crate::domain::ledger::nym::NymHistoryOptions;
 use crate::domain::ledger::pool::Schedule;
+use crate::domain::ledger::response::WriteReceipt;
+use crate::domain::ledger::txn::FetchTxnsOptions;
 
 /// Signs and submits request message to validator pool.
 ///
@@ -64,7 +67,7 @@ pub extern fn indy_sign_and_submit_request(command_handle: CommandHandle,
             wallet_handle,
             submitter_did,
             request_json,
-            boxed_callback_string!("indy_sign_and_submit_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_sign_and_submit_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -77,6 +80,11 @@ pub extern fn indy_sign_and_submit_request(command_handle: CommandHandle,
 /// Publishes request message to validator pool (no signing, unlike sign_and_submit_request).
 ///
 /// The request is sent to the validator pool as is. It's assumed that it's already prepared.
+/// Waiting for the reply can be cancelled with `indy_cancel_command`
+/// by the handle returned by `indy_get_current_cancel_handle` right after this call.
+/// Read requests with the same operation and protocol version as a read request already in flight
+/// and sent to the same nodes with the same timeout (see `indy_submit_action`) are not sent again:
+/// they get the reply of the request in flight with `reqId` set to the one of the sent request.
 ///
 /// #Params
 /// command_handle: command handle to map callback to caller context.
@@ -105,10 +113,10 @@ pub extern fn indy_submit_request(command_handle: CommandHandle,
     trace!("indy_submit_request: entities >>> pool_handle: {:?}, request_json: {:?}", pool_handle, request_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::SubmitRequest(
+        .send_cancellable(Command::Ledger(LedgerCommand::SubmitRequest(
             pool_handle,
             request_json,
-            boxed_callback_string!("indy_submit_request", cb, command_handle)
+            cancellable_callback(dispatch_callback(boxed_callback_string!("indy_submit_request", cb, command_handle)))
         )));
 
     let res = prepare_result!(result);
@@ -125,6 +133,8 @@ pub extern fn indy_submit_request(command_handle: CommandHandle,
 ///     GET_VALIDATOR_INFO
 ///
 /// The request is sent to the nodes as is. It's assumed that it's already prepared.
+/// Waiting for the replies can be cancelled with `indy_cancel_command`
+/// by the handle returned by `indy_get_current_cancel_handle` right after this call.
 ///
 /// #Params
 /// command_handle: command handle to map callback to caller context.
@@ -162,13 +172,13 @@ pub extern fn indy_submit_action(command_handle: CommandHandle,
     trace!("indy_submit_action: entities >>> pool_handle: {:?}, request_json: {:?}, nodes: {:?}, timeout: {:?}", pool_handle, request_json, nodes, timeout);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .send_cancellable(Command::Ledger(
             LedgerCommand::SubmitAction(
                 pool_handle,
                 request_json,
                 nodes,
                 timeout,
-                boxed_callback_string!("indy_submit_action", cb, command_handle)
+                cancellable_callback(dispatch_callback(boxed_callback_string!("indy_submit_action", cb, command_handle)))
             )));
 
     let res = prepare_result!(result);
@@ -219,7 +229,7 @@ pub extern fn indy_sign_request(command_handle: CommandHandle,
             wallet_handle,
             submitter_did,
             request_json,
-            boxed_callback_string!("indy_sign_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_sign_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -269,7 +279,7 @@ pub extern fn indy_multi_sign_request(command_handle: CommandHandle,
             wallet_handle,
             submitter_did,
             request_json,
-            boxed_callback_string!("indy_multi_sign_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_multi_sign_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -279,6 +289,56 @@ pub extern fn indy_multi_sign_request(command_handle: CommandHandle,
     res
 }
 
+/// Multi signs request message with several DIDs at once.
+///
+/// Adds signatures of all passed DIDs to `signatures` field of the request json (see indy_multi_sign_request).
+/// It is useful for collecting of quorum of trustees signatures for the request when all DIDs are kept in one wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_dids_json: Ids of Identities stored in secured Wallet as json array: [string, ...].
+/// request_json: Request data json.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Signed request json.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_multi_sign_request_with_dids(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                submitter_dids_json: *const c_char,
+                                                request_json: *const c_char,
+                                                cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                     signed_request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_multi_sign_request_with_dids: >>> wallet_handle: {:?}, submitter_dids_json: {:?}, request_json: {:?}", wallet_handle, submitter_dids_json, request_json);
+
+    check_useful_json!(submitter_dids_json, ErrorCode::CommonInvalidParam3, Vec<DidValue>);
+    check_useful_c_str!(request_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_multi_sign_request_with_dids: entities >>> wallet_handle: {:?}, submitter_dids_json: {:?}, request_json: {:?}", wallet_handle, submitter_dids_json, request_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::MultiSignRequestWithDids(
+            wallet_handle,
+            submitter_dids_json,
+            request_json,
+            dispatch_callback(boxed_callback_string!("indy_multi_sign_request_with_dids", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_multi_sign_request_with_dids: <<< res: {:?}", res);
+
+    res
+}
+
 
 /// Builds a request to get a DDO.
 ///
@@ -312,7 +372,7 @@ pub extern fn indy_build_get_ddo_request(command_handle: CommandHandle,
         .send(Command::Ledger(LedgerCommand::BuildGetDdoRequest(
             submitter_did,
             target_did,
-            boxed_callback_string!("indy_build_get_ddo_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_ddo_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -377,7 +437,7 @@ pub extern fn indy_build_nym_request(command_handle: CommandHandle,
             verkey,
             alias,
             role,
-            boxed_callback_string!("indy_build_nym_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_nym_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -419,7 +479,7 @@ pub extern fn indy_build_get_nym_request(command_handle: CommandHandle,
         .send(Command::Ledger(LedgerCommand::BuildGetNymRequest(
             submitter_did,
             target_did,
-            boxed_callback_string!("indy_build_get_nym_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_nym_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -469,7 +529,7 @@ pub extern fn indy_parse_get_nym_response(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Ledger(LedgerCommand::ParseGetNymResponse(
             get_nym_response,
-            boxed_callback_string!("indy_parse_get_nym_response", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_parse_get_nym_response", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -479,6 +539,129 @@ pub extern fn indy_parse_get_nym_response(command_handle: CommandHandle,
     res
 }
 
+/// Collects the history of NYM transactions written for the DID (creation, key rotations, role changes)
+/// by walking GET_TXN requests over the domain ledger.
+///
+/// Note: one GET_TXN request is sent for every transaction in the walked range, so the range can't be longer
+/// than 1000 transactions. Narrow it with `from` and `to` options for big ledgers.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// target_did: Target DID as base58-encoded string for 16 or 32 bit DID value.
+/// options_json: (Optional) range of domain ledger sequence numbers to walk:
+/// {
+///     "from": int (optional) - first sequence number (1 by default),
+///     "to": int (optional) - last sequence number (sequence number of the current NYM of `target_did` by default),
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// NYM transactions of the DID in chronological order:
+/// [{
+///     "seqNo": int - sequence number of the transaction,
+///     "txnTime": int (optional) - time when the transaction was written,
+///     "from": string (optional) - DID of the transaction author,
+///     "data": json - NYM transaction data (dest, verkey, role, alias)
+/// }]
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_get_nym_history(command_handle: CommandHandle,
+                                   pool_handle: PoolHandle,
+                                   target_did: *const c_char,
+                                   options_json: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        history_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_nym_history: >>> pool_handle: {:?}, target_did: {:?}, options_json: {:?}", pool_handle, target_did, options_json);
+
+    check_useful_validatable_string!(target_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_opt_validatable_json!(options_json, ErrorCode::CommonInvalidParam4, NymHistoryOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_get_nym_history: entities >>> pool_handle: {:?}, target_did: {:?}, options_json: {:?}", pool_handle, target_did, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::GetNymHistory(
+            pool_handle,
+            target_did,
+            options_json,
+            dispatch_callback(boxed_callback_string!("indy_get_nym_history", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_nym_history: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fetches a batch of ledger transactions in sequence number order by sending GET_TXN requests.
+///
+/// Use it to stream the ledger for audit or mirroring: pass `cursor` from the returned batch as `from`
+/// option of the next call until `complete` is set. Fetching can be resumed later from any saved cursor.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// options_json: (Optional) batch to fetch:
+/// {
+///     "ledgerType": string (optional) - type of the ledger the transactions are fetched from:
+///         DOMAIN - used default,
+///         POOL,
+///         CONFIG
+///         any number
+///     "from": int (optional) - first sequence number of the batch (1 by default),
+///     "to": int (optional) - last sequence number to fetch (the end of the ledger by default),
+///     "batchSize": int (optional) - max number of transactions in the batch (100 by default, 1000 at most),
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Batch of transactions:
+/// {
+///     "txns": [{
+///         "seqNo": int - sequence number of the transaction,
+///         "data": json - transaction as it is returned by GET_TXN request
+///     }],
+///     "cursor": int - sequence number to pass as `from` to fetch the next batch,
+///     "complete": bool - true if the end of the ledger or `to` sequence number is reached
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_fetch_ledger_txns(command_handle: CommandHandle,
+                                     pool_handle: PoolHandle,
+                                     options_json: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          txns_batch_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_fetch_ledger_txns: >>> pool_handle: {:?}, options_json: {:?}", pool_handle, options_json);
+
+    check_useful_opt_validatable_json!(options_json, ErrorCode::CommonInvalidParam3, FetchTxnsOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_fetch_ledger_txns: entities >>> pool_handle: {:?}, options_json: {:?}", pool_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::FetchTxns(
+            pool_handle,
+            options_json,
+            dispatch_callback(boxed_callback_string!("indy_fetch_ledger_txns", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_fetch_ledger_txns: <<< res: {:?}", res);
+
+    res
+}
+
 /// Builds an ATTRIB request. Request to add attribute to a NYM record.
 ///
 /// Note: one of the fields `hash`, `raw`, `enc` must be specified.
@@ -532,7 +715,7 @@ pub extern fn indy_build_attrib_request(command_handle: CommandHandle,
             hash,
             raw,
             enc,
-            boxed_callback_string!("indy_build_attrib_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_attrib_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -594,7 +777,7 @@ pub extern fn indy_build_get_attrib_request(command_handle: CommandHandle,
             raw,
             hash,
             enc,
-            boxed_callback_string!("indy_build_get_attrib_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_attrib_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -644,7 +827,7 @@ pub extern fn indy_build_schema_request(command_handle: CommandHandle,
         .send(Command::Ledger(LedgerCommand::BuildSchemaRequest(
             submitter_did,
             data,
-            boxed_callback_string!("indy_build_schema_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_schema_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -686,7 +869,7 @@ pub extern fn indy_build_get_schema_request(command_handle: CommandHandle,
         .send(Command::Ledger(LedgerCommand::BuildGetSchemaRequest(
             submitter_did,
             id,
-            boxed_callback_string!("indy_build_get_schema_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_schema_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -732,13 +915,13 @@ pub extern fn indy_parse_get_schema_response(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Ledger(LedgerCommand::ParseGetSchemaResponse(
             get_schema_response,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, schema_id, schema_json) = prepare_result_2!(result, String::new(), String::new());
                 trace!("indy_parse_get_schema_response: schema_id: {:?}, schema_json: {:?}", schema_id, schema_json);
                 let schema_id = ctypes::string_to_cstring(schema_id);
                 let schema_json = ctypes::string_to_cstring(schema_json);
                 cb(command_handle, err, schema_id.as_ptr(), schema_json.as_ptr())
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -793,7 +976,7 @@ pub extern fn indy_build_cred_def_request(command_handle: CommandHandle,
         .send(Command::Ledger(LedgerCommand::BuildCredDefRequest(
             submitter_did,
             data,
-            boxed_callback_string!("indy_build_cred_def_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_cred_def_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -836,7 +1019,7 @@ pub extern fn indy_build_get_cred_def_request(command_handle: CommandHandle,
         .send(Command::Ledger(LedgerCommand::BuildGetCredDefRequest(
             submitter_did,
             id,
-            boxed_callback_string!("indy_build_get_cred_def_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_cred_def_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -886,13 +1069,13 @@ pub extern fn indy_parse_get_cred_def_response(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Ledger(LedgerCommand::ParseGetCredDefResponse(
             get_cred_def_response,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, cred_def_id, cred_def_json) = prepare_result_2!(result, String::new(), String::new());
                 trace!("indy_parse_get_cred_def_response: cred_def_id: {:?}, cred_def_json: {:?}", cred_def_id, cred_def_json);
                 let cred_def_id = ctypes::string_to_cstring(cred_def_id);
                 let cred_def_json = ctypes::string_to_cstring(cred_def_json);
                 cb(command_handle, err, cred_def_id.as_ptr(), cred_def_json.as_ptr())
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -913,6 +1096,7 @@ pub extern fn indy_parse_get_cred_def_response(command_handle: CommandHandle,
 ///     alias: string - Node's alias
 ///     blskey: string - (Optional) BLS multi-signature key as base58-encoded string.
 ///     blskey_pop: string - (Optional) BLS key proof of possession as base58-encoded string.
+///         BLS key with proof of possession can be generated by indy_create_bls_key.
 ///     client_ip: string - (Optional) Node's client listener IP address.
 ///     client_port: string - (Optional) Node's client listener port.
 ///     node_ip: string - (Optional) The IP address other Nodes use to communicate with this Node.
@@ -948,7 +1132,7 @@ pub extern fn indy_build_node_request(command_handle: CommandHandle,
             submitter_did,
             target_did,
             data,
-            boxed_callback_string!("indy_build_node_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_node_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -981,7 +1165,7 @@ pub extern fn indy_build_get_validator_info_request(command_handle: CommandHandl
     let result = CommandExecutor::instance()
         .send(Command::Ledger(LedgerCommand::BuildGetValidatorInfoRequest(
             submitter_did,
-            boxed_callback_string!("indy_build_get_validator_info_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_validator_info_request", cb, command_handle))
         )));
 
     prepare_result!(result)
@@ -1026,7 +1210,7 @@ pub extern fn indy_build_get_txn_request(command_handle: CommandHandle,
             submitter_did,
             ledger_type,
             seq_no,
-            boxed_callback_string!("indy_build_get_txn_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_txn_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1073,7 +1257,7 @@ pub extern fn indy_build_pool_config_request(command_handle: CommandHandle,
             submitter_did,
             writes,
             force,
-            boxed_callback_string!("indy_build_pool_config_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_pool_config_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1124,7 +1308,7 @@ pub extern fn indy_build_pool_restart_request(command_handle: CommandHandle,
                 submitter_did,
                 action,
                 datetime,
-                boxed_callback_string!("indy_build_pool_restart_request", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_build_pool_restart_request", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -1219,7 +1403,7 @@ pub extern fn indy_build_pool_upgrade_request(command_handle: CommandHandle,
                 reinstall,
                 force,
                 package,
-                boxed_callback_string!("indy_build_pool_upgrade_request", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_build_pool_upgrade_request", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -1277,7 +1461,7 @@ pub extern fn indy_build_revoc_reg_def_request(command_handle: CommandHandle,
         .send(Command::Ledger(LedgerCommand::BuildRevocRegDefRequest(
             submitter_did,
             data,
-            boxed_callback_string!("indy_build_revoc_reg_def_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_revoc_reg_def_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1320,7 +1504,7 @@ pub extern fn indy_build_get_revoc_reg_def_request(command_handle: CommandHandle
         .send(Command::Ledger(LedgerCommand::BuildGetRevocRegDefRequest(
             submitter_did,
             id,
-            boxed_callback_string!("indy_build_get_revoc_reg_def_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_revoc_reg_def_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1374,14 +1558,14 @@ pub extern fn indy_parse_get_revoc_reg_def_response(command_handle: CommandHandl
     let result = CommandExecutor::instance()
         .send(Command::Ledger(LedgerCommand::ParseGetRevocRegDefResponse(
             get_revoc_reg_def_response,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, revoc_reg_def_id, revoc_reg_def_json) = prepare_result_2!(result, String::new(), String::new());
                 trace!("indy_parse_get_revoc_reg_def_response: revoc_reg_def_id: {:?}, revoc_reg_def_json: {:?}", revoc_reg_def_id, revoc_reg_def_json);
 
                 let revoc_reg_def_id = ctypes::string_to_cstring(revoc_reg_def_id);
                 let revoc_reg_def_json = ctypes::string_to_cstring(revoc_reg_def_json);
                 cb(command_handle, err, revoc_reg_def_id.as_ptr(), revoc_reg_def_json.as_ptr())
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -1445,7 +1629,7 @@ pub extern fn indy_build_revoc_reg_entry_request(command_handle: CommandHandle,
             revoc_reg_def_id,
             rev_def_type,
             value,
-            boxed_callback_string!("indy_build_revoc_reg_entry_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_revoc_reg_entry_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1491,7 +1675,7 @@ pub extern fn indy_build_get_revoc_reg_request(command_handle: CommandHandle,
             submitter_did,
             revoc_reg_def_id,
             timestamp,
-            boxed_callback_string!("indy_build_get_revoc_reg_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_revoc_reg_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1537,7 +1721,7 @@ pub extern fn indy_parse_get_revoc_reg_response(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Ledger(LedgerCommand::ParseGetRevocRegResponse(
             get_revoc_reg_response,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, revoc_reg_def_id, revoc_reg_json, timestamp) = prepare_result_3!(result, String::new(), String::new(), 0);
                 trace!("indy_parse_get_revoc_reg_response: revoc_reg_def_id: {:?}, revoc_reg_json: {:?}, timestamp: {:?}",
                        revoc_reg_def_id, revoc_reg_json, timestamp);
@@ -1545,7 +1729,7 @@ pub extern fn indy_parse_get_revoc_reg_response(command_handle: CommandHandle,
                 let revoc_reg_def_id = ctypes::string_to_cstring(revoc_reg_def_id);
                 let revoc_reg_json = ctypes::string_to_cstring(revoc_reg_json);
                 cb(command_handle, err, revoc_reg_def_id.as_ptr(), revoc_reg_json.as_ptr(), timestamp)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -1599,7 +1783,7 @@ pub extern fn indy_build_get_revoc_reg_delta_request(command_handle: CommandHand
             revoc_reg_def_id,
             from,
             to,
-            boxed_callback_string!("indy_build_get_revoc_reg_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_revoc_reg_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1648,7 +1832,7 @@ pub extern fn indy_parse_get_revoc_reg_delta_response(command_handle: CommandHan
     let result = CommandExecutor::instance()
         .send(Command::Ledger(LedgerCommand::ParseGetRevocRegDeltaResponse(
             get_revoc_reg_delta_response,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, revoc_reg_def_id, revoc_reg_delta_json, timestamp) = prepare_result_3!(result, String::new(), String::new(), 0);
                 trace!("indy_parse_get_revoc_reg_delta_response: revoc_reg_def_id: {:?}, revoc_reg_delta_json: {:?}, timestamp: {:?}",
                        revoc_reg_def_id, revoc_reg_delta_json, timestamp);
@@ -1656,7 +1840,7 @@ pub extern fn indy_parse_get_revoc_reg_delta_response(command_handle: CommandHan
                 let revoc_reg_def_id = ctypes::string_to_cstring(revoc_reg_def_id);
                 let revoc_reg_delta_json = ctypes::string_to_cstring(revoc_reg_delta_json);
                 cb(command_handle, err, revoc_reg_def_id.as_ptr(), revoc_reg_delta_json.as_ptr(), timestamp)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -1718,11 +1902,11 @@ pub extern fn indy_register_transaction_parser_for_sp(command_handle: CommandHan
             txn_type,
             parser,
             free,
-            Box::new(move |res| {
+            dispatch_callback(Box::new(move |res| {
                 let res = prepare_result!(res);
                 trace!("indy_register_transaction_parser_for_sp: res: {:?}", res);
                 cb(command_handle, res)
-            }),
+            })),
         )));
 
     let res = prepare_result!(res);
@@ -1747,6 +1931,8 @@ pub extern fn indy_register_transaction_parser_for_sp(command_handle: CommandHan
 ///     1) based on "seqNo" - sender knows the sequence number of transaction that he consider as a fresh enough.
 ///     2) based on "txnTime" - sender knows the timestamp that he consider as a fresh enough.
 ///
+/// For write responses "seqNo", "txnTime" and "txnId" can be persisted as ledger anchors of the written transaction.
+///
 /// Note: response of GET_VALIDATOR_INFO request isn't supported
 ///
 /// #Params
@@ -1760,7 +1946,8 @@ pub extern fn indy_register_transaction_parser_for_sp(command_handle: CommandHan
 ///     "seqNo": Option<u64> - transaction sequence number,
 ///     "txnTime": Option<u64> - transaction ordering time,
 ///     "lastSeqNo": Option<u64> - the latest transaction seqNo for particular Node,
-///     "lastTxnTime": Option<u64> - the latest transaction ordering time for particular Node
+///     "lastTxnTime": Option<u64> - the latest transaction ordering time for particular Node,
+///     "txnId": Option<string> - identifier of the written transaction (for example: schema or credential definition id)
 /// }
 ///
 /// #Errors
@@ -1782,7 +1969,7 @@ pub extern fn indy_get_response_metadata(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Ledger(LedgerCommand::GetResponseMetadata(
             response,
-            boxed_callback_string!("indy_get_response_metadata", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_get_response_metadata", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1869,7 +2056,7 @@ pub extern fn indy_build_auth_rule_request(command_handle: CommandHandle,
             old_value,
             new_value,
             constraint,
-            boxed_callback_string!("indy_build_auth_rule_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_auth_rule_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1931,7 +2118,7 @@ pub extern fn indy_build_auth_rules_request(command_handle: CommandHandle,
         .send(Command::Ledger(LedgerCommand::BuildAuthRulesRequest(
             submitter_did,
             rules,
-            boxed_callback_string!("indy_build_auth_rules_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_auth_rules_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -1998,7 +2185,7 @@ pub extern fn indy_build_get_auth_rule_request(command_handle: CommandHandle,
             field,
             old_value,
             new_value,
-            boxed_callback_string!("indy_build_get_auth_rule_request", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_build_get_auth_rule_request", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -2076,7 +2263,7 @@ pub extern fn indy_build_txn_author_agreement_request(command_handle: CommandHan
                 version,
                 ratification_ts,
                 retirement_ts,
-                boxed_callback_string!("indy_build_txn_author_agreement_request", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_build_txn_author_agreement_request", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -2119,7 +2306,7 @@ pub extern fn indy_build_disable_all_txn_author_agreements_request(command_handl
         .send(Command::Ledger(
             LedgerCommand::BuildDisableAllTxnAuthorAgreementsRequest(
                 submitter_did,
-                boxed_callback_string!("indy_build_disable_all_txn_author_agreements_request", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_build_disable_all_txn_author_agreements_request", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -2172,7 +2359,7 @@ pub extern fn indy_build_get_txn_author_agreement_request(command_handle: Comman
             LedgerCommand::BuildGetTxnAuthorAgreementRequest(
                 submitter_did,
                 data,
-                boxed_callback_string!("indy_build_get_txn_author_agreement_request", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_build_get_txn_author_agreement_request", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -2234,7 +2421,7 @@ pub extern fn indy_build_acceptance_mechanisms_request(command_handle: CommandHa
                 aml,
                 version,
                 aml_context,
-                boxed_callback_string!("indy_build_acceptance_mechanisms_request", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_build_acceptance_mechanisms_request", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -2287,7 +2474,7 @@ pub extern fn indy_build_get_acceptance_mechanisms_request(command_handle: Comma
                 submitter_did,
                 timestamp,
                 version,
-                boxed_callback_string!("indy_build_get_acceptance_mechanisms_request", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_build_get_acceptance_mechanisms_request", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -2297,6 +2484,224 @@ pub extern fn indy_build_get_acceptance_mechanisms_request(command_handle: Comma
     res
 }
 
+/// Gets the latest Transaction Author Agreement and Acceptance Mechanisms set on the ledger
+/// by sending GET_TXN_AUTHR_AGRMT and GET_TXN_AUTHR_AGRMT_AML requests.
+///
+/// If digest of the previously accepted agreement is passed and it differs from the latest one,
+/// the accepted agreement is checked as well. Once the accepted agreement is retired the ledger rejects
+/// requests with its acceptance, so `LedgerTxnAuthorAgreementRetired` error is returned and the returned
+/// active agreement must be accepted again.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// accepted_taa_digest: (Optional) digest of the accepted agreement to check for retirement.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// {
+///     "taa": { - the latest agreement or null if it isn't set
+///         "text": string,
+///         "version": string,
+///         "digest": string,
+///         "ratification_ts": int,
+///         "retirement_ts": int (optional)
+///     },
+///     "aml": { - the latest acceptance mechanisms or null if they aren't set
+///         "aml": {"<acceptance mechanism label>": "<acceptance mechanism description>"},
+///         "version": string,
+///         "amlContext": string (optional)
+///     }
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_get_active_txn_author_agreement(command_handle: CommandHandle,
+                                                   pool_handle: PoolHandle,
+                                                   accepted_taa_digest: *const c_char,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                        err: ErrorCode,
+                                                                        active_taa_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_active_txn_author_agreement: >>> pool_handle: {:?}, accepted_taa_digest: {:?}", pool_handle, accepted_taa_digest);
+
+    check_useful_opt_c_str!(accepted_taa_digest, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_active_txn_author_agreement: entities >>> pool_handle: {:?}, accepted_taa_digest: {:?}", pool_handle, accepted_taa_digest);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::GetActiveTxnAuthorAgreement(
+            pool_handle,
+            accepted_taa_digest,
+            dispatch_callback(boxed_callback_string!("indy_get_active_txn_author_agreement", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_active_txn_author_agreement: <<< res: {:?}", res);
+
+    res
+}
+
+/// Checks whether Credential Definition published on the ledger can be used by the local anoncreds implementation.
+/// Credential Definition is fetched by sending GET_CRED_DEF request and its signature type and public keys are verified.
+/// It allows to find artifacts produced by incompatible or newer stacks before they break credential issuance or verification.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// cred_def_id: id of Credential Definition to check.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// {
+///     "cred_def_id": string,
+///     "compatible": bool, - whether Credential Definition can be used by the local anoncreds
+///     "signature_type": string, (optional)
+///     "primary_key_bits": int, - size of the primary public key modulus (optional)
+///     "revocation_supported": bool, (optional)
+///     "issues": [string] - descriptions of found incompatibilities
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_check_cred_def_compatibility(command_handle: CommandHandle,
+                                                pool_handle: PoolHandle,
+                                                cred_def_id: *const c_char,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode,
+                                                                     report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_check_cred_def_compatibility: >>> pool_handle: {:?}, cred_def_id: {:?}", pool_handle, cred_def_id);
+
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_check_cred_def_compatibility: entities >>> pool_handle: {:?}, cred_def_id: {:?}", pool_handle, cred_def_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::CheckCredDefCompatibility(
+            pool_handle,
+            cred_def_id,
+            dispatch_callback(boxed_callback_string!("indy_check_cred_def_compatibility", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_check_cred_def_compatibility: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds receipt of the written transaction from the reply to the write request.
+/// Receipt can be persisted by the application and used later to prove that the transaction
+/// has been accepted by the ledger (look at `indy_verify_write_receipt`).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// response: reply to the write request.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// {
+///     "ledgerId": int - id of the ledger the transaction is written to (taken from the reply),
+///     "seqNo": int - sequence number of the transaction,
+///     "txnTime": int - (optional) transaction ordering time,
+///     "digest": string - (optional) digest of the request,
+///     "data": json - the transaction as stored on the ledger (`txn`, `txnMetadata`, `reqSignature`, `ver`),
+///     "rootHash": string - merkle tree root hash of the ledger,
+///     "auditPath": [string] - audit path from the transaction to the root hash,
+///     "ledgerSize": int - size of the ledger the root hash is calculated for,
+///     "multiSignature": json - BLS multi-signature of the nodes over the root hash
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_get_write_receipt(command_handle: CommandHandle,
+                                     response: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          receipt_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_write_receipt: >>> response: {:?}", response);
+
+    check_useful_c_str!(response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_get_write_receipt: entities >>> response: {:?}", response);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::GetWriteReceipt(
+            response,
+            dispatch_callback(boxed_callback_string!("indy_get_write_receipt", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_write_receipt: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies receipt of the written transaction (created by `indy_get_write_receipt`).
+///
+/// The receipt is verified without requests to the ledger: the audit path must lead from the transaction
+/// to the root hash and the root hash must be signed by BLS multi-signature of enough nodes of the pool
+/// (BLS keys of the nodes are taken from the pool ledger cached by `indy_open_pool_ledger`).
+/// The write reply must contain the multi-signature over its root hash, otherwise `indy_get_write_receipt` fails.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// receipt_json: write receipt json.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true if the transaction of the receipt is written to the ledger
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_verify_write_receipt(command_handle: CommandHandle,
+                                        pool_handle: PoolHandle,
+                                        receipt_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             valid: bool)>) -> ErrorCode {
+    trace!("indy_verify_write_receipt: >>> pool_handle: {:?}, receipt_json: {:?}", pool_handle, receipt_json);
+
+    check_useful_json!(receipt_json, ErrorCode::CommonInvalidParam3, WriteReceipt);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_verify_write_receipt: entities >>> pool_handle: {:?}, receipt_json: {:?}", pool_handle, receipt_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::VerifyWriteReceipt(
+            pool_handle,
+            receipt_json,
+            dispatch_callback(Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_verify_write_receipt: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verify_write_receipt: <<< res: {:?}", res);
+
+    res
+}
+
 /// Append transaction author agreement acceptance data to a request.
 /// This function should be called before signing and sending a request
 /// if there is any transaction author agreement set on the Ledger.
@@ -2359,7 +2764,7 @@ pub extern fn indy_append_txn_author_agreement_acceptance_to_request(command_han
                 taa_digest,
                 mechanism,
                 time,
-                boxed_callback_string!("indy_append_txn_author_agreement_acceptance_to_request", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_append_txn_author_agreement_acceptance_to_request", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -2409,7 +2814,7 @@ pub extern fn indy_append_request_endorser(command_handle: CommandHandle,
             LedgerCommand::AppendRequestEndorser(
                 request_json,
                 endorser_did,
-                boxed_callback_string!("indy_append_request_endorser", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_append_request_endorser", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -3,7 +3,7 @@ use libc::{c_void, c_char};
 use indy_api_types::ErrorCode;
 use indy_api_types::errors::prelude::*;
 
-use crate::utils::logger::{EnabledCB, LogCB, FlushCB, LibindyLogger, LibindyDefaultLogger, LOGGER_STATE};
+use crate::utils::logger::{EnabledCB, LogCB, CommandLogCB, FlushCB, LibindyLogger, LibindyDefaultLogger, LOGGER_STATE};
 use indy_utils::ctypes;
 
 /// Set custom logger implementation.
@@ -13,7 +13,10 @@ use indy_utils::ctypes;
 /// #Params
 /// context: pointer to some logger context that will be available in logger handlers.
 /// enabled: (optional) "enabled" operation handler - calls to determines if a log record would be logged. (false positive if not specified)
-/// log: "log" operation handler - calls to logs a record.
+/// log: "log" operation handler - calls to logs a record. Besides the record it receives the context of libindy command
+///     that produced the record (handlers declared without these trailing params keep working):
+///     command: (optional) name of the commands group (Anoncreds, Did, Ledger, Wallet and etc) the record was produced by.
+///     correlation_id: identifier of the command the record was produced by (the same for all records of one command, 0 if absent).
 /// flush: (optional) "flush" operation handler - calls to flushes buffered records (in case of crash or signal).
 ///
 /// #Returns
@@ -21,7 +24,7 @@ use indy_utils::ctypes;
 #[no_mangle]
 pub extern fn indy_set_logger(context: *const c_void,
                               enabled: Option<EnabledCB>,
-                              log: Option<LogCB>,
+                              log: Option<CommandLogCB>,
                               flush: Option<FlushCB>) -> ErrorCode {
     trace!("indy_set_logger >>> context: {:?}, enabled: {:?}, log: {:?}, flush: {:?}", context, enabled, log, flush);
 
@@ -72,7 +75,7 @@ pub extern fn indy_set_default_logger(pattern: *const c_char) -> ErrorCode {
 /// #Params
 /// `context_p` - Reference that will contain logger context.
 /// `enabled_cb_p` - Reference that will contain pointer to enable operation handler.
-/// `log_cb_p` - Reference that will contain pointer to log operation handler.
+/// `log_cb_p` - Reference that will contain pointer to log operation handler (without command context params).
 /// `flush_cb_p` - Reference that will contain pointer to flush operation handler.
 ///
 /// #Returns
@@ -1,8 +1,8 @@
 
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, SearchHandle, INVALID_SEARCH_HANDLE};
-use crate::commands::{Command, CommandExecutor};
+use crate::commands::{Command, CommandExecutor, dispatch_callback};
 use crate::commands::non_secrets::NonSecretsCommand;
-use indy_api_types::domain::wallet::Tags;
+use indy_api_types::domain::wallet::{BatchRecord, Tags};
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 
@@ -54,11 +54,11 @@ pub extern fn indy_add_wallet_record(command_handle: CommandHandle,
                 id,
                 value,
                 tags_json,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let err = prepare_result!(result);
                     trace!("indy_add_wallet_record:");
                     cb(command_handle, err)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -99,11 +99,11 @@ pub extern fn indy_update_wallet_record_value(command_handle: CommandHandle,
                 type_,
                 id,
                 value,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let err = prepare_result!(result);
                     trace!("indy_update_wallet_record_value:");
                     cb(command_handle, err)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -153,11 +153,11 @@ pub extern fn indy_update_wallet_record_tags(command_handle: CommandHandle,
                 type_,
                 id,
                 tags_json,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let err = prepare_result!(result);
                     trace!("indy_update_wallet_record_tags:");
                     cb(command_handle, err)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -185,7 +185,8 @@ pub extern fn indy_update_wallet_record_tags(command_handle: CommandHandle,
 ///   usage of this tag in complex search queries (comparison, predicates)
 ///   Encrypted tags can be searched only for exact matching
 ///   Note if some from provided tags already assigned to the record than
-///     corresponding tags values will be replaced
+///     corresponding tags values will be replaced.
+///   Tag changing from encrypted to un-encrypted (or vice versa) replaces the tag stored the other way.
 #[no_mangle]
 pub extern fn indy_add_wallet_record_tags(command_handle: CommandHandle,
                                           wallet_handle: WalletHandle,
@@ -209,11 +210,11 @@ pub extern fn indy_add_wallet_record_tags(command_handle: CommandHandle,
                 type_,
                 id,
                 tags_json,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let err = prepare_result!(result);
                     trace!("indy_add_wallet_record_tags:");
                     cb(command_handle, err)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -255,11 +256,11 @@ pub extern fn indy_delete_wallet_record_tags(command_handle: CommandHandle,
                 type_,
                 id,
                 tag_names_json,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let err = prepare_result!(result);
                     trace!("indy_delete_wallet_record_tags:");
                     cb(command_handle, err)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -296,11 +297,11 @@ pub extern fn indy_delete_wallet_record(command_handle: CommandHandle,
                 wallet_handle,
                 type_,
                 id,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let err = prepare_result!(result);
                     trace!("indy_delete_wallet_record:");
                     cb(command_handle, err)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -310,6 +311,102 @@ pub extern fn indy_delete_wallet_record(command_handle: CommandHandle,
     res
 }
 
+/// Create many non-secret records of the same type in the wallet at once.
+/// Records are added with all-or-nothing semantics: if one of them can't be added none of them are.
+/// Records are written in a single storage transaction, so it is much faster than adding them one by one.
+/// Plugged storages have no transactions, so records added before the failure are deleted back at best effort.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: allows to separate different record types collections
+/// records_json: the list of records to add as json array:
+///   [
+///     {
+///       "id": string, the id of record
+///       "value": string, the value of record
+///       "tags": optional<object>, the record tags (see indy_add_wallet_record)
+///     }
+///   ]
+#[no_mangle]
+pub extern fn indy_add_wallet_records_batch(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            type_: *const c_char,
+                                            records_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_add_wallet_records_batch: >>> wallet_handle: {:?}, type_: {:?}, records_json: {:?}", wallet_handle, type_, records_json);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_json!(records_json, ErrorCode::CommonInvalidParam4, Vec<BatchRecord>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_add_wallet_records_batch: entities >>> wallet_handle: {:?}, type_: {:?}, records_json: {:?}", wallet_handle, type_, records_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::AddRecordsBatch(
+                wallet_handle,
+                type_,
+                records_json,
+                dispatch_callback(Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_add_wallet_records_batch:");
+                    cb(command_handle, err)
+                }))
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_add_wallet_records_batch: <<< res: {:?}", res);
+
+    res
+}
+
+/// Delete many existing wallet records of the same type at once.
+/// Records are deleted with all-or-nothing semantics: if one of them can't be deleted none of them are.
+/// Records are deleted in a single storage transaction, so it is much faster than deleting them one by one.
+/// Plugged storages have no transactions, so records deleted before the failure are added back at best effort.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: record type
+/// ids_json: the list of ids of records to delete as json array:
+///   ["id1", "id2", ...]
+#[no_mangle]
+pub extern fn indy_delete_wallet_records_batch(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               type_: *const c_char,
+                                               ids_json: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_delete_wallet_records_batch: >>> wallet_handle: {:?}, type_: {:?}, ids_json: {:?}", wallet_handle, type_, ids_json);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_json!(ids_json, ErrorCode::CommonInvalidParam4, Vec<String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_delete_wallet_records_batch: entities >>> wallet_handle: {:?}, type_: {:?}, ids_json: {:?}", wallet_handle, type_, ids_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::DeleteRecordsBatch(
+                wallet_handle,
+                type_,
+                ids_json,
+                dispatch_callback(Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_delete_wallet_records_batch:");
+                    cb(command_handle, err)
+                }))
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_delete_wallet_records_batch: <<< res: {:?}", res);
+
+    res
+}
+
 /// Get an wallet record by id
 ///
 /// #Params
@@ -355,7 +452,7 @@ pub  extern fn indy_get_wallet_record(command_handle: CommandHandle,
                 type_,
                 id,
                 options_json,
-                boxed_callback_string!("indy_get_wallet_record", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_get_wallet_record", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -379,9 +476,11 @@ pub  extern fn indy_get_wallet_record(command_handle: CommandHandle,
 ///    "tagName": "tagValue",
 ///    $or: {
 ///      "tagName2": { $regex: 'pattern' },
-///      "tagName3": { $gte: '123' },
+///      "~tagName3": { $gte: '123' },
 ///    },
 ///  }
+///   Comparison operators ($gt, $gte, $lt, $lte) and $like are allowed only for
+///   un-encrypted tags (tag name starts with "~").
 /// options_json: //TODO: FIXME: Think about replacing by bitmask
 ///  {
 ///    retrieveRecords: (optional, true by default) If false only "counts" will be calculated,
@@ -417,11 +516,11 @@ pub  extern fn indy_open_wallet_search(command_handle: CommandHandle,
                 type_,
                 query_json,
                 options_json,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let (err, handle) = prepare_result_1!(result, INVALID_SEARCH_HANDLE);
                     trace!("indy_open_wallet_search: handle: {:?}", handle);
                     cb(command_handle, err, handle)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -470,7 +569,7 @@ pub  extern fn indy_fetch_wallet_search_next_records(command_handle: CommandHand
                 wallet_handle,
                 wallet_search_handle,
                 count,
-                boxed_callback_string!("indy_fetch_wallet_search_next_records", cb, command_handle)
+                dispatch_callback(boxed_callback_string!("indy_fetch_wallet_search_next_records", cb, command_handle))
             )));
 
     let res = prepare_result!(result);
@@ -498,11 +597,11 @@ pub  extern fn indy_close_wallet_search(command_handle: CommandHandle,
         .send(Command::NonSecrets(
             NonSecretsCommand::CloseSearch(
                 wallet_search_handle,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let err = prepare_result!(result);
                     trace!("indy_close_wallet_search:");
                     cb(command_handle, err)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -1,5 +1,5 @@
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle};
-use crate::commands::{Command, CommandExecutor};
+use crate::commands::{Command, CommandExecutor, dispatch_callback};
 use crate::commands::pairwise::PairwiseCommand;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -40,11 +40,11 @@ pub  extern fn indy_is_pairwise_exists(command_handle: CommandHandle,
         .send(Command::Pairwise(PairwiseCommand::PairwiseExists(
             wallet_handle,
             their_did,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, exists) = prepare_result_1!(result, false);
                 trace!("indy_is_pairwise_exists: exists: {:?}", exists);
                 cb(command_handle, err, exists)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -94,11 +94,11 @@ pub  extern fn indy_create_pairwise(command_handle: CommandHandle,
             their_did,
             my_did,
             metadata,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_create_pairwise:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -136,7 +136,7 @@ pub  extern fn indy_list_pairwise(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Pairwise(PairwiseCommand::ListPairwise(
             wallet_handle,
-            boxed_callback_string!("indy_list_pairwise", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_list_pairwise", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -178,7 +178,7 @@ pub  extern fn indy_get_pairwise(command_handle: CommandHandle,
         .send(Command::Pairwise(PairwiseCommand::GetPairwise(
             wallet_handle,
             their_did,
-            boxed_callback_string!("indy_get_pairwise", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_get_pairwise", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
@@ -223,11 +223,11 @@ pub  extern fn indy_set_pairwise_metadata(command_handle: CommandHandle,
             wallet_handle,
             their_did,
             metadata,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_set_pairwise_metadata:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -1,8 +1,8 @@
 
 use indy_api_types::{ErrorCode, CommandHandle, PoolHandle, INVALID_POOL_HANDLE};
-use crate::commands::{Command, CommandExecutor};
+use crate::commands::{Command, CommandExecutor, cancellable_callback_with_cleanup, dispatch_callback};
 use crate::commands::pool::PoolCommand;
-use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+use crate::domain::pool::{GenesisNodes, PoolConfig, PoolOpenConfig, TrafficCaptureConfig};
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 use indy_api_types::validation::Validatable;
@@ -44,11 +44,11 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
         .send(Command::Pool(PoolCommand::Create(
             config_name,
             config,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_create_pool_ledger_config:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -63,6 +63,8 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
 /// Pool ledger configuration with corresponded name must be previously created
 /// with indy_create_pool_ledger_config method.
 /// It is impossible to open pool with the same name more than once.
+/// Waiting for the connection can be cancelled with `indy_cancel_command`
+/// by the handle returned by `indy_get_current_cancel_handle` right after this call.
 ///
 /// config_name: Name of the pool ledger configuration.
 /// config (optional): Runtime pool configuration json.
@@ -79,6 +81,17 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
 ///     "number_read_nodes": int (optional) - the number of nodes to send read requests (2 by default)
 ///         By default Libindy sends a read requests to 2 nodes in the pool.
 ///         If response isn't received or `state proof` is invalid Libindy sends the request again but to 2 (`number_read_nodes`) * 2 = 4 nodes and so far until completion.
+///     "retry_backoff_factor": float (optional) - multiplier applied to `timeout` each time a read request is resent to the next node (1.0 by default).
+///     "max_retry_timeout": int (optional) - upper bound of the timeout (in sec) for resent read requests. Must not be less than `timeout`.
+///     "client_key_pair": (optional) - CURVE key pair of the client used for connections to the nodes:
+///         {"public_key": string, "secret_key": string} z85 encoded (as generated by `zmq_curve_keypair`).
+///         By default an ephemeral key pair is generated for every connection.
+///     "persist_client_key_pair": bool (optional) - generate CURVE key pair of the client on the first opening
+///         and keep it in the pool config directory, so it can be allow-listed by the nodes (false by default).
+///         Public key of the stored key pair is returned by `indy_list_pools`. Can't be used with `client_key_pair`.
+///     "pin_genesis_node_keys": bool (optional) - reject nodes whose transport keys differ from the keys
+///         in the genesis transactions, e.g. after key rotation on the ledger (false by default).
+///         Nodes added to the pool after the genesis are accepted.
 /// }
 ///
 /// #Returns
@@ -103,14 +116,20 @@ pub extern fn indy_open_pool_ledger(command_handle: CommandHandle,
     trace!("indy_open_pool_ledger: entities >>> config_name: {:?}, config: {:?}", config_name, config);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(PoolCommand::Open(
+        .send_cancellable(Command::Pool(PoolCommand::Open(
             config_name,
             config,
-            Box::new(move |result| {
-                let (err, pool_handle) = prepare_result_1!(result, INVALID_POOL_HANDLE);
-                trace!("indy_open_pool_ledger: pool_handle: {:?}", pool_handle);
-                cb(command_handle, err, pool_handle)
-            })
+            cancellable_callback_with_cleanup(
+                dispatch_callback(Box::new(move |result| {
+                    let (err, pool_handle) = prepare_result_1!(result, INVALID_POOL_HANDLE);
+                    trace!("indy_open_pool_ledger: pool_handle: {:?}", pool_handle);
+                    cb(command_handle, err, pool_handle)
+                })),
+                Box::new(|pool_handle| {
+                    // Pool opened after the command was cancelled isn't known to the caller
+                    let _ = CommandExecutor::instance()
+                        .send(Command::Pool(PoolCommand::Close(pool_handle, Box::new(|_| {}))));
+                }))
         )));
 
     let res = prepare_result!(result);
@@ -145,11 +164,11 @@ pub extern fn indy_refresh_pool_ledger(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Pool(PoolCommand::Refresh(
             handle,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_refresh_pool_ledger:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -165,6 +184,10 @@ pub extern fn indy_refresh_pool_ledger(command_handle: CommandHandle,
 ///
 /// #Returns
 /// Error code
+/// pools: [{
+///     "pool": string, - name of the pool ledger
+///     "client_public_key": string, - (optional) z85 encoded public key of the persisted client key pair (see `persist_client_key_pair`)
+/// }]
 ///
 /// #Errors
 #[no_mangle]
@@ -179,7 +202,7 @@ pub extern fn indy_list_pools(command_handle: CommandHandle,
     trace!("indy_list_pools: entities >>>");
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(PoolCommand::List(boxed_callback_string!("indy_list_pools", cb, command_handle))));
+        .send(Command::Pool(PoolCommand::List(dispatch_callback(boxed_callback_string!("indy_list_pools", cb, command_handle)))));
 
     let res = prepare_result!(result);
 
@@ -213,11 +236,11 @@ pub extern fn indy_close_pool_ledger(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Pool(PoolCommand::Close(
             handle,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_close_pool_ledger:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -253,11 +276,11 @@ pub extern fn indy_delete_pool_ledger_config(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Pool(PoolCommand::Delete(
             config_name,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_delete_pool_ledger_config:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -299,11 +322,11 @@ pub extern fn indy_set_protocol_version(command_handle: CommandHandle,
         .send(Command::Pool(
             PoolCommand::SetProtocolVersion(
             protocol_version,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_set_protocol_version:");
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -312,3 +335,205 @@ pub extern fn indy_set_protocol_version(command_handle: CommandHandle,
 
     res
 }
+
+/// Builds genesis transactions of the pool ledger from the list of nodes.
+/// The result can be written to the file and used as `genesis_txn` of `indy_create_pool_ledger_config`.
+///
+/// Transactions are built in the format of Indy Node 1.4 and greater (PROTOCOL_VERSION=2)
+/// and numbered in the order of the nodes passed.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// nodes_json: List of the genesis nodes:
+/// [{
+///     "alias": string, Node's alias
+///     "dest": string, Target Node's verkey as base58
+///     "steward": string, DID of the Steward owning the node
+///     "verkey": string, (optional) Node's verkey
+///     "node_ip": string, Node's ip address
+///     "node_port": int, Node's port
+///     "client_ip": string, Node's client listener ip address
+///     "client_port": int, Node's client listener port
+///     "services": array<string>, (optional) The service of the Node. VALIDATOR or OBSERVER (["VALIDATOR"] by default)
+///     "blskey": string, (optional) BLS multi-signature key as base58
+///     "blskey_pop": string, (optional) BLS key proof of possession as base58
+/// }]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Genesis transactions separated by new line.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_pool_genesis_txns(command_handle: CommandHandle,
+                                           nodes_json: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                genesis_txns: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_pool_genesis_txns: >>> nodes_json: {:?}", nodes_json);
+
+    check_useful_validatable_json!(nodes_json, ErrorCode::CommonInvalidParam2, GenesisNodes);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_build_pool_genesis_txns: entities >>> nodes_json: {:?}", nodes_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::BuildGenesisTxns(
+            nodes_json,
+            dispatch_callback(boxed_callback_string!("indy_build_pool_genesis_txns", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_build_pool_genesis_txns: <<< res: {:?}", res);
+
+    res
+}
+
+/// Enables or disables capturing of raw traffic between the pool and its nodes.
+///
+/// Every request sent to a node and every reply received from a node is written to the capture file
+/// as a json line: {"timestamp": <ms since epoch>, "direction": "request"|"reply", "node": <alias>, "message": <msg>}.
+/// Signatures and private attribute values (`raw`, `enc`) are redacted. When the file exceeds
+/// `max_file_size` it is rotated to `<path>.1`, `<path>.2` and so on, keeping at most `max_files` files.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// handle: pool handle returned by indy_open_pool_ledger
+/// config (optional): capture config json. If NULL, capturing is stopped.
+/// {
+///     "path": string, path to the capture file.
+///     "max_file_size": int (optional), max size of the capture file in bytes before rotation. Defaults to 10485760.
+///     "max_files": int (optional), max number of capture files including rotated ones. Defaults to 5.
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_set_pool_traffic_capture(command_handle: CommandHandle,
+                                            handle: PoolHandle,
+                                            config: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_set_pool_traffic_capture: >>> handle: {:?}, config: {:?}", handle, config);
+
+    check_useful_opt_validatable_json!(config, ErrorCode::CommonInvalidParam3, TrafficCaptureConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_set_pool_traffic_capture: entities >>> handle: {:?}, config: {:?}", handle, config);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::SetTrafficCapture(
+            handle,
+            config,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_set_pool_traffic_capture:");
+                cb(command_handle, err)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_set_pool_traffic_capture: <<< res: {:?}", res);
+
+    res
+}
+
+/// Routes ledger operations on fully-qualified identifiers of the DID namespace to the pool.
+///
+/// Ledger-backed operations taking pool handle (DID and verkey resolution, schema and credential definition fetching
+/// and etc) called with INVALID_POOL_HANDLE (0) send requests for DIDs of the namespace (and schemas and credential
+/// definitions issued by them) to the registered pool. Explicitly passed pool handle always takes precedence.
+/// Registration is removed when the pool is closed.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// namespace: DID namespace: part of the fully-qualified DID between `did:` prefix and the method-specific id
+///     (`sov` for `did:sov:...` or `indicio:testnet` for `did:indicio:testnet:...`).
+/// handle: pool handle returned by indy_open_pool_ledger
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_set_pool_for_namespace(command_handle: CommandHandle,
+                                          namespace: *const c_char,
+                                          handle: PoolHandle,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_set_pool_for_namespace: >>> namespace: {:?}, handle: {:?}", namespace, handle);
+
+    check_useful_c_str!(namespace, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_set_pool_for_namespace: entities >>> namespace: {:?}, handle: {:?}", namespace, handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::SetNamespacePool(
+            namespace,
+            handle,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_set_pool_for_namespace:");
+                cb(command_handle, err)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_set_pool_for_namespace: <<< res: {:?}", res);
+
+    res
+}
+
+/// Removes routing of the DID namespace set by indy_set_pool_for_namespace.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// namespace: DID namespace.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_remove_pool_for_namespace(command_handle: CommandHandle,
+                                             namespace: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_remove_pool_for_namespace: >>> namespace: {:?}", namespace);
+
+    check_useful_c_str!(namespace, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_remove_pool_for_namespace: entities >>> namespace: {:?}", namespace);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::RemoveNamespacePool(
+            namespace,
+            dispatch_callback(Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_remove_pool_for_namespace:");
+                cb(command_handle, err)
+            }))
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_remove_pool_for_namespace: <<< res: {:?}", res);
+
+    res
+}
@@ -1,8 +1,8 @@
 
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, INVALID_WALLET_HANDLE};
-use crate::commands::{Command, CommandExecutor};
+use crate::commands::{Command, CommandExecutor, dispatch_callback};
 use crate::commands::wallet::WalletCommand;
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig, StorageEncryptionCheckConfig};
 use indy_api_types::wallet::*;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -131,11 +131,11 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
                 get_search_total_count,
                 fetch_search_next_record,
                 free_search,
-                Box::new(move |result| {
+                dispatch_callback(Box::new(move |result| {
                     let err = prepare_result!(result);
                     trace!("indy_register_wallet_type: cb command_handle: {:?}, err: {:?}", command_handle, err);
                     cb(command_handle, err)
-                })
+                }))
             )));
 
     let res = prepare_result!(result);
@@ -143,6 +143,52 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
     res
 }
 
+/// Register compaction handler for custom wallet storage registered with indy_register_wallet_storage.
+/// indy_compact_wallet and scheduled compaction call it for storages opened after the registration.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Storage type name.
+/// compact: WalletType compact operation handler
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// WalletUnknownTypeError
+#[no_mangle]
+pub extern fn indy_register_wallet_storage_compaction(command_handle: CommandHandle,
+                                                      type_: *const c_char,
+                                                      compact: Option<WalletCompact>,
+                                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                           err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_storage_compaction: >>> command_handle: {:?}, type_: {:?}, compact: {:?}, cb: {:?}",
+           command_handle, type_, compact, cb);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(compact, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_register_wallet_storage_compaction: entities >>> type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(
+            WalletCommand::RegisterWalletStorageCompaction(
+                type_,
+                compact,
+                dispatch_callback(Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_wallet_storage_compaction: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                }))
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_wallet_storage_compaction: <<< res: {:?}", res);
+    res
+}
+
 /// Create a new secure wallet.
 ///
 /// #Params
@@ -161,6 +207,8 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
 ///             Defaults to $HOME/.indy_client/wallet.
 ///             Wallet will be stored in the file {path}/{id}/sqlite.db
 ///   }
+///   "profile": optional<string>, Id of the logically isolated wallet inside the storage.
+///              Allows one storage to host many wallets, each with its own keys. Can't contain NUL character.
 /// }
 /// credentials: Wallet credentials json
 /// {
@@ -174,6 +222,10 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
 ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
 ///                          RAW - raw wallet key master provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///   "key_derivation_params": optional<object> Custom limits of ARGON2I_MOD/ARGON2I_INT key derivation.
+///                          Can be calculated for the current hardware with indy_benchmark_wallet_key_derivation call:
+///                          {"ops_limit": int, "mem_limit": int}. Stored in the wallet metadata, so not needed to open the wallet.
+///                          Must be at least ARGON2I_MOD limits.
 /// }
 ///
 /// #Returns
@@ -202,11 +254,11 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
         .send(Command::Wallet(WalletCommand::Create(
             config,
             credentials,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_create_wallet: cb command_handle: {:?}, err: {:?}", command_handle, err);
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -234,6 +286,18 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///                      Defaults to $HOME/.indy_client/wallet.
 ///                      Wallet will be stored in the file {path}/{id}/sqlite.db
 ///           }
+///       "profile": optional<string>, Id of the logically isolated wallet inside the storage.
+///                  Allows one storage to host many wallets, each with its own keys. Can't contain NUL character.
+///       "compression_threshold": optional<int>, Values of records longer than this number of bytes
+///                  are compressed (zstd) before encryption. Compression is disabled by default.
+///                  Ignored if libindy is built without "wallet_compression" feature.
+///       "slow_query_threshold": optional<int>, Storage operations taking longer than this number of milliseconds
+///                  are kept in the slow query log of the opened wallet (see indy_get_wallet_slow_query_log).
+///                  The log is disabled by default.
+///       "compaction_interval": optional<int>, Storage is compacted while libindy is idle if records were deleted and this number of seconds
+///                  has passed since the last compaction (see indy_compact_wallet). Disabled by default.
+///       "idle_timeout": optional<int>, Wallet is closed if it hasn't been used for this number of seconds.
+///                  Closing is reported by "wallet_invalidated" event (see indy_set_event_listener). Disabled by default.
 ///
 ///   }
 /// credentials: Wallet credentials json
@@ -254,6 +318,8 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///                          ARGON2I_INT - derive secured wallet master rekey (less secured but faster)
 ///                          RAW - raw wallet rekey master provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///       "rekey_derivation_params": optional<object> Custom limits of ARGON2I_MOD/ARGON2I_INT rekey derivation:
+///                          {"ops_limit": int, "mem_limit": int}. Can be calculated with indy_benchmark_wallet_key_derivation call.
 ///   }
 ///
 /// #Returns
@@ -284,12 +350,12 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
         .send(Command::Wallet(WalletCommand::Open(
             config,
             credentials,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let (err, handle) = prepare_result_1!(result, INVALID_WALLET_HANDLE);
                 trace!("indy_open_wallet: cb command_handle: {:?} err: {:?}, handle: {:?}",
                        command_handle, err, handle);
                 cb(command_handle, err, handle)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -311,6 +377,8 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
 ///                              ARGON2I_INT - derive secured export key (less secured but faster)
 ///                              RAW - raw export key provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///     "key_derivation_params": optional<object> Custom limits of export key derivation (written to the export header):
+///                              {"ops_limit": int, "mem_limit": int}. Must be at least ARGON2I_MOD limits.
 ///   }
 ///
 /// #Returns
@@ -336,11 +404,11 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
         .send(Command::Wallet(WalletCommand::Export(
             wallet_handle,
             export_config,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_export_wallet: cb command_handle: {:?} err: {:?}", command_handle, err);
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -369,6 +437,11 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 ///             Defaults to $HOME/.indy_client/wallet.
 ///             Wallet will be stored in the file {path}/{id}/sqlite.db
 ///   }
+///   "profile": optional<string>, Id of the logically isolated wallet inside the storage.
+///              Allows one storage to host many wallets, each with its own keys. Can't contain NUL character.
+///   "compression_threshold": optional<int>, Values of records longer than this number of bytes
+///              are compressed (zstd) before encryption. Compression is disabled by default.
+///              Ignored if libindy is built without "wallet_compression" feature.
 /// }
 /// credentials: Wallet credentials json
 /// {
@@ -418,11 +491,11 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
             config,
             credentials,
             import_config,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_import_wallet: cb command_handle: {:?}, err: {:?}", command_handle, err);
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -457,11 +530,11 @@ pub extern fn indy_close_wallet(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Wallet(WalletCommand::Close(
             wallet_handle,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_close_wallet: cb command_handle: {:?}, err: {:?}", command_handle, err);
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -487,6 +560,8 @@ pub extern fn indy_close_wallet(command_handle: CommandHandle,
 ///             Defaults to $HOME/.indy_client/wallet.
 ///             Wallet will be stored in the file {path}/{id}/sqlite.db
 ///   }
+///   "profile": optional<string>, Id of the logically isolated wallet inside the storage.
+///              Allows one storage to host many wallets, each with its own keys. Can't contain NUL character.
 /// }
 /// credentials: Wallet credentials json
 /// {
@@ -527,11 +602,11 @@ pub extern fn indy_delete_wallet(command_handle: CommandHandle,
         .send(Command::Wallet(WalletCommand::Delete(
             config,
             credentials,
-            Box::new(move |result| {
+            dispatch_callback(Box::new(move |result| {
                 let err = prepare_result!(result);
                 trace!("indy_delete_wallet: cb command_handle: {:?}, err: {:?}", command_handle, err);
                 cb(command_handle, err)
-            })
+            }))
         )));
 
     let res = prepare_result!(result);
@@ -573,10 +648,250 @@ pub extern fn indy_generate_wallet_key(command_handle: CommandHandle,
     let result = CommandExecutor::instance()
         .send(Command::Wallet(WalletCommand::GenerateKey(
             config,
-            boxed_callback_string!("indy_generate_wallet_key", cb, command_handle)
+            dispatch_callback(boxed_callback_string!("indy_generate_wallet_key", cb, command_handle))
         )));
 
     let res = prepare_result!(result);
     trace!("indy_generate_wallet_key: <<< res: {:?}", res);
     res
 }
+
+/// Verifies that records of opened wallet are encrypted at rest.
+/// Reads raw records from the wallet storage and checks that ids, types, values and tags
+/// are stored as ciphertext only: none of decrypted values may appear in the stored bytes
+/// (plaintext tags "~" are excluded from the check by design). Values shorter than 8 bytes aren't looked for
+/// as they can occur in ciphertext by chance.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// config: (optional) check configuration json.
+/// {
+///   "sample_size": int, (optional) Maximum number of records to check (all records are checked by default).
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: report of the check
+/// {
+///   "checkedRecords": int, number of checked records
+///   "plaintextTags": int, number of plaintext tags skipped by the check
+///   "encrypted": bool, true if no violation was found
+///   "violations": [{
+///       "record": int, index of the record in the checked sample
+///       "field": string, one of "record", "id", "type", "value", "tagName", "tagValue"
+///       "reason": string, description of the violation
+///   }]
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verify_wallet_storage_encryption(command_handle: CommandHandle,
+                                                    wallet_handle: WalletHandle,
+                                                    config: *const c_char,
+                                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                         err: ErrorCode,
+                                                                         report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verify_wallet_storage_encryption: >>> command_handle: {:?}, wallet_handle: {:?}, config: {:?}, cb: {:?}",
+           command_handle, wallet_handle, config, cb);
+
+    check_useful_opt_json!(config, ErrorCode::CommonInvalidParam3, StorageEncryptionCheckConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_verify_wallet_storage_encryption: params wallet_handle: {:?}, config: {:?}", wallet_handle, config);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::VerifyStorageEncryption(
+            wallet_handle,
+            config,
+            dispatch_callback(boxed_callback_string!("indy_verify_wallet_storage_encryption", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_verify_wallet_storage_encryption: <<< res: {:?}", res);
+    res
+}
+
+/// Returns the latest storage operations of opened wallet that took longer than
+/// "slow_query_threshold" set in the wallet config on opening.
+/// Up to 100 latest operations are kept, the log is empty if the threshold wasn't set.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// slow_query_log_json: list of slow storage operations from the oldest to the latest
+/// [{
+///   "operation": string, one of "add", "update", "get", "delete", "add_tags", "update_tags", "delete_tags", "search", "get_all", "compact"
+///   "type": optional<string>, type of the records
+///   "durationMs": int, duration of the operation in milliseconds
+///   "rows": optional<int>, number of affected or found records if known
+///   "timestamp": int, unix time in milliseconds when the operation was finished
+/// }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_get_wallet_slow_query_log(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode,
+                                                                  slow_query_log_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_wallet_slow_query_log: >>> command_handle: {:?}, wallet_handle: {:?}, cb: {:?}",
+           command_handle, wallet_handle, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_get_wallet_slow_query_log: params wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::GetSlowQueryLog(
+            wallet_handle,
+            dispatch_callback(boxed_callback_string!("indy_get_wallet_slow_query_log", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_get_wallet_slow_query_log: <<< res: {:?}", res);
+    res
+}
+
+/// Compacts the storage of opened wallet reclaiming the space left by deleted records.
+/// It may take a long time for big wallets, the wallet can't be used meanwhile.
+///
+/// Note that the whole storage is compacted if it hosts many profiles.
+/// Plugged storages are compacted by the handler registered with indy_register_wallet_storage_compaction,
+/// storages without the handler report zero reclaimed bytes.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// compaction_report_json:
+/// {
+///   "reclaimedBytes": int, number of bytes reclaimed by the compaction
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_compact_wallet(command_handle: CommandHandle,
+                                  wallet_handle: WalletHandle,
+                                  cb: Option<extern fn(command_handle_: CommandHandle,
+                                                       err: ErrorCode,
+                                                       compaction_report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_compact_wallet: >>> command_handle: {:?}, wallet_handle: {:?}, cb: {:?}",
+           command_handle, wallet_handle, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_compact_wallet: params wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Compact(
+            wallet_handle,
+            dispatch_callback(boxed_callback_string!("indy_compact_wallet", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_compact_wallet: <<< res: {:?}", res);
+    res
+}
+
+/// Lists wallet objects which expire within the given time window.
+/// Expiration of keys and DIDs is read from their metadata set as json with numeric "expires" field,
+/// expiration of credentials is read from their "expires" attribute.
+/// Already expired objects are listed as well.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// within_secs: size of the time window in seconds starting from now.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// objects_json: list of expiring objects ordered by expiration time
+/// [{
+///     "type": string, one of "key", "did", "credential"
+///     "id": string, verkey, DID or credential referent
+///     "expires": int, expiration time as unix timestamp in seconds
+/// }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_list_expiring_wallet_objects(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                within_secs: u64,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode,
+                                                                     objects_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_list_expiring_wallet_objects: >>> command_handle: {:?}, wallet_handle: {:?}, within_secs: {:?}, cb: {:?}",
+           command_handle, wallet_handle, within_secs, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_list_expiring_wallet_objects: params wallet_handle: {:?}, within_secs: {:?}", wallet_handle, within_secs);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ListExpiringObjects(
+            wallet_handle,
+            within_secs,
+            dispatch_callback(boxed_callback_string!("indy_list_expiring_wallet_objects", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_list_expiring_wallet_objects: <<< res: {:?}", res);
+    res
+}
+
+/// Calculates limits of wallet key derivation suitable for the current hardware.
+/// Argon2i operations limit is adjusted so that derivation of wallet master key takes about `target_ms`.
+/// Result is never below ARGON2I_MOD limits, so slower hardware gets ARGON2I_MOD limits.
+/// Result can be passed as "key_derivation_params" and "rekey_derivation_params" of wallet credentials.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// target_ms: desired duration of wallet key derivation in milliseconds.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// params_json: key derivation params
+/// {
+///     "ops_limit": int, Argon2i operations limit
+///     "mem_limit": int, Argon2i memory limit in bytes
+/// }
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_benchmark_wallet_key_derivation(command_handle: CommandHandle,
+                                                   target_ms: u64,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                        err: ErrorCode,
+                                                                        params_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_benchmark_wallet_key_derivation: >>> command_handle: {:?}, target_ms: {:?}, cb: {:?}",
+           command_handle, target_ms, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_benchmark_wallet_key_derivation: params target_ms: {:?}", target_ms);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::BenchmarkKeyDerivation(
+            target_ms,
+            dispatch_callback(boxed_callback_string!("indy_benchmark_wallet_key_derivation", cb, command_handle))
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_benchmark_wallet_key_derivation: <<< res: {:?}", res);
+    res
+}
@@ -0,0 +1,532 @@
+use std::future::Future;
+
+use indy_api_types::{IndyHandle, SearchHandle, WalletHandle};
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::{parse_json, parse_opt_json, parse_opt_validatable_json, parse_validatable_json, send, validate};
+use crate::commands::Command;
+use crate::commands::anoncreds::AnoncredsCommand;
+use crate::commands::anoncreds::issuer::IssuerCommand;
+use crate::commands::anoncreds::prover::ProverCommand;
+use crate::commands::anoncreds::verifier::VerifierCommand;
+use crate::domain::anoncreds::credential::{Credential, CredentialIssuanceRequests, CredentialProjection, CredentialValues};
+use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
+use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionId, CredentialDefinitions};
+use crate::domain::anoncreds::credential_offer::{CredentialOffer, CredentialOfferConfig};
+use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
+use crate::domain::anoncreds::issuance_log::IssuanceLog;
+use crate::domain::anoncreds::proof::Proof;
+use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestExtraQuery};
+use crate::domain::anoncreds::proof_request_template::{ProofRequestTemplate, ProofRequestTemplateParams};
+use crate::domain::anoncreds::requested_credential::RequestedCredentials;
+use crate::domain::anoncreds::revocation_registry::RevocationRegistries;
+use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryDefinitions, RevocationRegistryId};
+use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::anoncreds::revocation_state::{RevocationState, RevocationStates};
+use crate::domain::anoncreds::schema::{AttributeNames, Schema, Schemas};
+use crate::domain::anoncreds::w3c_credential::W3CCredential;
+use crate::domain::crypto::did::DidValue;
+
+fn issuer(cmd: IssuerCommand) -> Command {
+    Command::Anoncreds(AnoncredsCommand::Issuer(cmd))
+}
+
+fn prover(cmd: ProverCommand) -> Command {
+    Command::Anoncreds(AnoncredsCommand::Prover(cmd))
+}
+
+fn verifier(cmd: VerifierCommand) -> Command {
+    Command::Anoncreds(AnoncredsCommand::Verifier(cmd))
+}
+
+/// Create credential schema entity. See `indy_issuer_create_schema`.
+///
+/// Resolves to the schema id and the schema json.
+pub fn issuer_create_schema(issuer_did: &str, name: &str, version: &str, attrs: &str) -> impl Future<Output=IndyResult<(String, String)>> {
+    let issuer_did = try_future!(validate(DidValue(issuer_did.to_string())));
+    let attrs: AttributeNames = try_future!(parse_validatable_json(attrs, "AttributeNames"));
+    let (name, version) = (name.to_string(), version.to_string());
+
+    send(|cb| issuer(IssuerCommand::CreateSchema(issuer_did, name, version, attrs, cb)))
+}
+
+/// Validates a list of schema attributes. See `indy_issuer_validate_schema_attributes`.
+pub fn issuer_validate_schema_attributes(attrs: &str) -> impl Future<Output=IndyResult<()>> {
+    let attrs: AttributeNames = try_future!(parse_json(attrs, "AttributeNames"));
+
+    send(|cb| issuer(IssuerCommand::ValidateSchemaAttributes(attrs, cb)))
+}
+
+/// Create credential definition entity and store its private part in the wallet. See `indy_issuer_create_and_store_credential_def`.
+///
+/// Resolves to the credential definition id and the credential definition json.
+pub fn issuer_create_and_store_credential_def(wallet_handle: WalletHandle,
+                                              issuer_did: &str,
+                                              schema_json: &str,
+                                              tag: &str,
+                                              signature_type: Option<&str>,
+                                              config_json: Option<&str>) -> impl Future<Output=IndyResult<(String, String)>> {
+    let issuer_did = try_future!(validate(DidValue(issuer_did.to_string())));
+    let schema: Schema = try_future!(parse_validatable_json(schema_json, "Schema"));
+    let config: Option<CredentialDefinitionConfig> = try_future!(parse_opt_validatable_json(config_json, "CredentialDefinitionConfig"));
+    let tag = tag.to_string();
+    let signature_type = signature_type.map(String::from);
+
+    send(|cb| issuer(IssuerCommand::CreateAndStoreCredentialDefinition(wallet_handle, issuer_did, schema, tag, signature_type, config, cb)))
+}
+
+/// Generate temporary credential definitional keys for an existing one. See `indy_issuer_rotate_credential_def_start`.
+pub fn issuer_rotate_credential_def_start(wallet_handle: WalletHandle, cred_def_id: &str, config_json: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let cred_def_id = try_future!(validate(CredentialDefinitionId(cred_def_id.to_string())));
+    let config: Option<CredentialDefinitionConfig> = try_future!(parse_opt_validatable_json(config_json, "CredentialDefinitionConfig"));
+
+    send(|cb| issuer(IssuerCommand::RotateCredentialDefinitionStart(wallet_handle, cred_def_id, config, cb)))
+}
+
+/// Apply temporary keys as main for an existing Credential Definition in the wallet. See `indy_issuer_rotate_credential_def_apply`.
+pub fn issuer_rotate_credential_def_apply(wallet_handle: WalletHandle, cred_def_id: &str) -> impl Future<Output=IndyResult<()>> {
+    let cred_def_id = try_future!(validate(CredentialDefinitionId(cred_def_id.to_string())));
+
+    send(|cb| issuer(IssuerCommand::RotateCredentialDefinitionApply(wallet_handle, cred_def_id, cb)))
+}
+
+/// Create a new revocation registry for the given credential definition. See `indy_issuer_create_and_store_revoc_reg`.
+///
+/// Resolves to the revocation registry id, the definition json and the initial entry json.
+pub fn issuer_create_and_store_revoc_reg(wallet_handle: WalletHandle,
+                                         issuer_did: &str,
+                                         revoc_def_type: Option<&str>,
+                                         tag: &str,
+                                         cred_def_id: &str,
+                                         config_json: &str,
+                                         tails_writer_handle: IndyHandle) -> impl Future<Output=IndyResult<(String, String, String)>> {
+    let issuer_did = try_future!(validate(DidValue(issuer_did.to_string())));
+    let cred_def_id = try_future!(validate(CredentialDefinitionId(cred_def_id.to_string())));
+    let config: RevocationRegistryConfig = try_future!(parse_validatable_json(config_json, "RevocationRegistryConfig"));
+    let revoc_def_type = revoc_def_type.map(String::from);
+    let tag = tag.to_string();
+
+    send(|cb| issuer(IssuerCommand::CreateAndStoreRevocationRegistry(wallet_handle, issuer_did, revoc_def_type, tag, cred_def_id, config, tails_writer_handle, cb)))
+}
+
+/// Create credential offer that will be used by Prover for credential request creation. See `indy_issuer_create_credential_offer`.
+pub fn issuer_create_credential_offer(wallet_handle: WalletHandle, cred_def_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let cred_def_id = try_future!(validate(CredentialDefinitionId(cred_def_id.to_string())));
+
+    send(|cb| issuer(IssuerCommand::CreateCredentialOffer(wallet_handle, cred_def_id, None, cb)))
+}
+
+/// Create credential offer which expires after configured period of time. See `indy_issuer_create_credential_offer_with_config`.
+pub fn issuer_create_credential_offer_with_config(wallet_handle: WalletHandle, cred_def_id: &str, config_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let cred_def_id = try_future!(validate(CredentialDefinitionId(cred_def_id.to_string())));
+    let config: CredentialOfferConfig = try_future!(parse_validatable_json(config_json, "CredentialOfferConfig"));
+
+    send(|cb| issuer(IssuerCommand::CreateCredentialOffer(wallet_handle, cred_def_id, Some(config), cb)))
+}
+
+/// Lists credential offers for which credentials have not been issued yet. See `indy_issuer_list_pending_credential_offers`.
+pub fn issuer_list_pending_credential_offers(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| issuer(IssuerCommand::ListPendingOffers(wallet_handle, cb)))
+}
+
+/// Revokes credential offer created by the issuer. See `indy_issuer_revoke_credential_offer`.
+pub fn issuer_revoke_credential_offer(wallet_handle: WalletHandle, nonce: &str) -> impl Future<Output=IndyResult<()>> {
+    let nonce = nonce.to_string();
+
+    send(|cb| issuer(IssuerCommand::RevokeOffer(wallet_handle, nonce, cb)))
+}
+
+/// Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request. See `indy_issuer_create_credential`.
+///
+/// Resolves to the credential json, the optional credential revocation id and the optional revocation registry delta json.
+pub fn issuer_create_credential(wallet_handle: WalletHandle,
+                                cred_offer_json: &str,
+                                cred_req_json: &str,
+                                cred_values_json: &str,
+                                rev_reg_id: Option<&str>,
+                                blob_storage_reader_handle: Option<IndyHandle>) -> impl Future<Output=IndyResult<(String, Option<String>, Option<String>)>> {
+    let cred_offer: CredentialOffer = try_future!(parse_validatable_json(cred_offer_json, "CredentialOffer"));
+    let cred_req: CredentialRequest = try_future!(parse_validatable_json(cred_req_json, "CredentialRequest"));
+    let cred_values: CredentialValues = try_future!(parse_validatable_json(cred_values_json, "CredentialValues"));
+    let rev_reg_id = try_future!(rev_reg_id.map(|id| validate(RevocationRegistryId(id.to_string()))).transpose());
+
+    send(|cb| issuer(IssuerCommand::CreateCredential(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle, cb)))
+}
+
+/// Issue Credentials for the batch of Cred Requests. See `indy_issuer_create_credentials`.
+pub fn issuer_create_credentials(wallet_handle: WalletHandle,
+                                 credentials_json: &str,
+                                 rev_reg_id: Option<&str>,
+                                 blob_storage_reader_handle: Option<IndyHandle>) -> impl Future<Output=IndyResult<(String, Option<String>)>> {
+    let requests: CredentialIssuanceRequests = try_future!(parse_validatable_json(credentials_json, "CredentialIssuanceRequests"));
+    let rev_reg_id = try_future!(rev_reg_id.map(|id| validate(RevocationRegistryId(id.to_string()))).transpose());
+
+    send(|cb| issuer(IssuerCommand::CreateCredentials(wallet_handle, requests, rev_reg_id, blob_storage_reader_handle, cb)))
+}
+
+/// Revoke a credential identified by a cred_revoc_id. See `indy_issuer_revoke_credential`.
+pub fn issuer_revoke_credential(wallet_handle: WalletHandle, blob_storage_reader_handle: IndyHandle, rev_reg_id: &str, cred_revoc_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let rev_reg_id = try_future!(validate(RevocationRegistryId(rev_reg_id.to_string())));
+    let cred_revoc_id = cred_revoc_id.to_string();
+
+    send(|cb| issuer(IssuerCommand::RevokeCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb)))
+}
+
+/// Returns usage statistics of the revocation registry. See `indy_issuer_get_revocation_registry_info`.
+pub fn issuer_get_revocation_registry_info(wallet_handle: WalletHandle, rev_reg_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let rev_reg_id = try_future!(validate(RevocationRegistryId(rev_reg_id.to_string())));
+
+    send(|cb| issuer(IssuerCommand::GetRevocationRegistryInfo(wallet_handle, rev_reg_id, cb)))
+}
+
+/// Merge two revocation registry deltas (returned by `issuer_create_credential` or `issuer_revoke_credential`) to accumulate common delta.
+/// See `indy_issuer_merge_revocation_registry_deltas`.
+pub fn issuer_merge_revocation_registry_deltas(rev_reg_delta_json: &str, other_rev_reg_delta_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let rev_reg_delta: RevocationRegistryDelta = try_future!(parse_validatable_json(rev_reg_delta_json, "RevocationRegistryDelta"));
+    let other_rev_reg_delta: RevocationRegistryDelta = try_future!(parse_validatable_json(other_rev_reg_delta_json, "RevocationRegistryDelta"));
+
+    send(|cb| issuer(IssuerCommand::MergeRevocationRegistryDeltas(rev_reg_delta, other_rev_reg_delta, cb)))
+}
+
+/// Enables the log of issued credentials. See `indy_issuer_enable_issuance_log`.
+pub fn issuer_enable_issuance_log(wallet_handle: WalletHandle, issuer_did: &str) -> impl Future<Output=IndyResult<()>> {
+    let issuer_did = try_future!(validate(DidValue(issuer_did.to_string())));
+
+    send(|cb| issuer(IssuerCommand::EnableIssuanceLog(wallet_handle, issuer_did, cb)))
+}
+
+/// Exports the log of issued credentials. See `indy_issuer_export_issuance_log`.
+pub fn issuer_export_issuance_log(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| issuer(IssuerCommand::ExportIssuanceLog(wallet_handle, cb)))
+}
+
+/// Verifies integrity of the exported issuance log. See `indy_issuer_verify_issuance_log`.
+pub fn issuer_verify_issuance_log(issuance_log_json: &str, issuer_verkey: &str) -> impl Future<Output=IndyResult<bool>> {
+    let issuance_log: IssuanceLog = try_future!(parse_json(issuance_log_json, "IssuanceLog"));
+    let issuer_verkey = issuer_verkey.to_string();
+
+    send(|cb| issuer(IssuerCommand::VerifyIssuanceLog(issuance_log, issuer_verkey, cb)))
+}
+
+/// Creates a master secret with a given id and stores it in the wallet. See `indy_prover_create_master_secret`.
+pub fn prover_create_master_secret(wallet_handle: WalletHandle, master_secret_id: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let master_secret_id = master_secret_id.map(String::from);
+
+    send(|cb| prover(ProverCommand::CreateMasterSecret(wallet_handle, master_secret_id, cb)))
+}
+
+/// Lists master secrets stored in the wallet. See `indy_prover_list_master_secrets`.
+pub fn prover_list_master_secrets(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| prover(ProverCommand::ListMasterSecrets(wallet_handle, cb)))
+}
+
+/// Renames master secret stored in the wallet. See `indy_prover_rename_master_secret`.
+pub fn prover_rename_master_secret(wallet_handle: WalletHandle, master_secret_id: &str, new_master_secret_id: &str) -> impl Future<Output=IndyResult<()>> {
+    let master_secret_id = master_secret_id.to_string();
+    let new_master_secret_id = new_master_secret_id.to_string();
+
+    send(|cb| prover(ProverCommand::RenameMasterSecret(wallet_handle, master_secret_id, new_master_secret_id, cb)))
+}
+
+/// Deletes master secret not referenced by stored credentials. See `indy_prover_delete_master_secret`.
+pub fn prover_delete_master_secret(wallet_handle: WalletHandle, master_secret_id: &str) -> impl Future<Output=IndyResult<()>> {
+    let master_secret_id = master_secret_id.to_string();
+
+    send(|cb| prover(ProverCommand::DeleteMasterSecret(wallet_handle, master_secret_id, cb)))
+}
+
+/// Creates a credential request for the given credential offer. See `indy_prover_create_credential_req`.
+///
+/// Resolves to the credential request json and the credential request metadata json.
+pub fn prover_create_credential_req(wallet_handle: WalletHandle,
+                                    prover_did: &str,
+                                    cred_offer_json: &str,
+                                    cred_def_json: &str,
+                                    master_secret_id: &str) -> impl Future<Output=IndyResult<(String, String)>> {
+    let prover_did = try_future!(validate(DidValue(prover_did.to_string())));
+    let cred_offer: CredentialOffer = try_future!(parse_validatable_json(cred_offer_json, "CredentialOffer"));
+    let cred_def: CredentialDefinition = try_future!(parse_validatable_json(cred_def_json, "CredentialDefinition"));
+    let master_secret_id = master_secret_id.to_string();
+
+    send(|cb| prover(ProverCommand::CreateCredentialRequest(wallet_handle, prover_did, cred_offer, cred_def, master_secret_id, cb)))
+}
+
+/// Set credential attribute tagging policy. See `indy_prover_set_credential_attr_tag_policy`.
+pub fn prover_set_credential_attr_tag_policy(wallet_handle: WalletHandle, cred_def_id: &str, tag_attrs_json: Option<&str>, retroactive: bool) -> impl Future<Output=IndyResult<()>> {
+    let cred_def_id = try_future!(validate(CredentialDefinitionId(cred_def_id.to_string())));
+    let tag_attrs: Option<CredentialAttrTagPolicy> = try_future!(parse_opt_json(tag_attrs_json, "CredentialAttrTagPolicy"));
+
+    send(|cb| prover(ProverCommand::SetCredentialAttrTagPolicy(wallet_handle, cred_def_id, tag_attrs, retroactive, cb)))
+}
+
+/// Get credential attribute tagging policy by credential definition id. See `indy_prover_get_credential_attr_tag_policy`.
+pub fn prover_get_credential_attr_tag_policy(wallet_handle: WalletHandle, cred_def_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let cred_def_id = try_future!(validate(CredentialDefinitionId(cred_def_id.to_string())));
+
+    send(|cb| prover(ProverCommand::GetCredentialAttrTagPolicy(wallet_handle, cred_def_id, cb)))
+}
+
+/// Check credential provided by Issuer for the given credential request, updates the credential and stores it in the wallet.
+/// See `indy_prover_store_credential`.
+pub fn prover_store_credential(wallet_handle: WalletHandle,
+                               cred_id: Option<&str>,
+                               cred_req_metadata_json: &str,
+                               cred_json: &str,
+                               cred_def_json: &str,
+                               rev_reg_def_json: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let cred_req_metadata: CredentialRequestMetadata = try_future!(parse_validatable_json(cred_req_metadata_json, "CredentialRequestMetadata"));
+    let credential: Credential = try_future!(parse_validatable_json(cred_json, "Credential"));
+    let cred_def: CredentialDefinition = try_future!(parse_validatable_json(cred_def_json, "CredentialDefinition"));
+    let rev_reg_def: Option<RevocationRegistryDefinition> = try_future!(parse_opt_validatable_json(rev_reg_def_json, "RevocationRegistryDefinition"));
+    let cred_id = cred_id.map(String::from);
+
+    send(|cb| prover(ProverCommand::StoreCredential(wallet_handle, cred_id, cred_req_metadata, credential, cred_def, rev_reg_def, cb)))
+}
+
+/// Gets human readable credential by the given id. See `indy_prover_get_credential`.
+pub fn prover_get_credential(wallet_handle: WalletHandle, cred_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let cred_id = cred_id.to_string();
+
+    send(|cb| prover(ProverCommand::GetCredential(wallet_handle, cred_id, cb)))
+}
+
+/// Delete identified credential from wallet. See `indy_prover_delete_credential`.
+pub fn prover_delete_credential(wallet_handle: WalletHandle, cred_id: &str) -> impl Future<Output=IndyResult<()>> {
+    let cred_id = cred_id.to_string();
+
+    send(|cb| prover(ProverCommand::DeleteCredential(wallet_handle, cred_id, cb)))
+}
+
+/// Gets human readable credentials according to the filter. See `indy_prover_get_credentials`.
+pub fn prover_get_credentials(wallet_handle: WalletHandle, filter_json: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let filter_json = filter_json.map(String::from);
+
+    send(|cb| prover(ProverCommand::GetCredentials(wallet_handle, filter_json, cb)))
+}
+
+/// Search for credentials stored in wallet. See `indy_prover_search_credentials`.
+///
+/// Resolves to the search handle and the total count of found credentials.
+pub fn prover_search_credentials(wallet_handle: WalletHandle, query_json: Option<&str>) -> impl Future<Output=IndyResult<(SearchHandle, usize)>> {
+    let query_json = query_json.map(String::from);
+
+    send(|cb| prover(ProverCommand::SearchCredentials(wallet_handle, query_json, cb)))
+}
+
+/// Search for credentials stored in wallet returning only the projected data. See `indy_prover_search_credentials_with_projection`.
+///
+/// Resolves to the search handle and the total count of found credentials.
+pub fn prover_search_credentials_with_projection(wallet_handle: WalletHandle, query_json: Option<&str>, projection_json: &str) -> impl Future<Output=IndyResult<(SearchHandle, usize)>> {
+    let query_json = query_json.map(String::from);
+    let projection: CredentialProjection = try_future!(parse_validatable_json(projection_json, "CredentialProjection"));
+
+    send(|cb| prover(ProverCommand::SearchCredentialsWithProjection(wallet_handle, query_json, projection, cb)))
+}
+
+/// Fetch next credentials for search. See `indy_prover_fetch_credentials`.
+pub fn prover_fetch_credentials(search_handle: SearchHandle, count: usize) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| prover(ProverCommand::FetchCredentials(search_handle, count, cb)))
+}
+
+/// Close credentials search. See `indy_prover_close_credentials_search`.
+pub fn prover_close_credentials_search(search_handle: SearchHandle) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| prover(ProverCommand::CloseCredentialsSearch(search_handle, cb)))
+}
+
+/// Gets human readable credentials matching the given proof request. See `indy_prover_get_credentials_for_proof_req`.
+pub fn prover_get_credentials_for_proof_req(wallet_handle: WalletHandle, proof_request_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let proof_request: ProofRequest = try_future!(parse_validatable_json(proof_request_json, "ProofRequest"));
+
+    send(|cb| prover(ProverCommand::GetCredentialsForProofReq(wallet_handle, proof_request, cb)))
+}
+
+/// Search for credentials matching the given proof request. See `indy_prover_search_credentials_for_proof_req`.
+pub fn prover_search_credentials_for_proof_req(wallet_handle: WalletHandle, proof_request_json: &str, extra_query_json: Option<&str>) -> impl Future<Output=IndyResult<SearchHandle>> {
+    let proof_request: ProofRequest = try_future!(parse_validatable_json(proof_request_json, "ProofRequest"));
+    let extra_query: Option<ProofRequestExtraQuery> = try_future!(parse_opt_json(extra_query_json, "ProofRequestExtraQuery"));
+
+    send(|cb| prover(ProverCommand::SearchCredentialsForProofReq(wallet_handle, proof_request, extra_query, cb)))
+}
+
+/// Fetch next credentials for the requested item using proof request search handle. See `indy_prover_fetch_credentials_for_proof_req`.
+pub fn prover_fetch_credentials_for_proof_req(search_handle: SearchHandle, item_referent: &str, count: usize) -> impl Future<Output=IndyResult<String>> {
+    let item_referent = item_referent.to_string();
+
+    send(|cb| prover(ProverCommand::FetchCredentialForProofReq(search_handle, item_referent, count, cb)))
+}
+
+/// Close credentials search for proof request. See `indy_prover_close_credentials_search_for_proof_req`.
+pub fn prover_close_credentials_search_for_proof_req(search_handle: SearchHandle) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| prover(ProverCommand::CloseCredentialsSearchForProofReq(search_handle, cb)))
+}
+
+/// Creates a proof according to the given proof request. See `indy_prover_create_proof`.
+pub fn prover_create_proof(wallet_handle: WalletHandle,
+                           proof_req_json: &str,
+                           requested_credentials_json: &str,
+                           master_secret_id: &str,
+                           schemas_json: &str,
+                           credential_defs_json: &str,
+                           rev_states_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let proof_req: ProofRequest = try_future!(parse_validatable_json(proof_req_json, "ProofRequest"));
+    let requested_credentials: RequestedCredentials = try_future!(parse_validatable_json(requested_credentials_json, "RequestedCredentials"));
+    let schemas: Schemas = try_future!(parse_json(schemas_json, "Schemas"));
+    let credential_defs: CredentialDefinitions = try_future!(parse_json(credential_defs_json, "CredentialDefinitions"));
+    let rev_states: RevocationStates = try_future!(parse_json(rev_states_json, "RevocationStates"));
+    let master_secret_id = master_secret_id.to_string();
+
+    send(|cb| prover(ProverCommand::CreateProof(wallet_handle, proof_req, requested_credentials, master_secret_id, schemas, credential_defs, rev_states, cb)))
+}
+
+/// Previews what the proof would disclose. See `indy_prover_preview_disclosure`.
+pub fn prover_preview_disclosure(wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let proof_req: ProofRequest = try_future!(parse_validatable_json(proof_req_json, "ProofRequest"));
+    let requested_credentials: RequestedCredentials = try_future!(parse_validatable_json(requested_credentials_json, "RequestedCredentials"));
+
+    send(|cb| prover(ProverCommand::PreviewDisclosure(wallet_handle, proof_req, requested_credentials, cb)))
+}
+
+/// Binds a proof to the holder DID. See `indy_prover_bind_proof`.
+pub fn prover_bind_proof(wallet_handle: WalletHandle, proof_req_json: &str, proof_json: &str, holder_did: &str) -> impl Future<Output=IndyResult<String>> {
+    let proof_req: ProofRequest = try_future!(parse_validatable_json(proof_req_json, "ProofRequest"));
+    let proof: Proof = try_future!(parse_validatable_json(proof_json, "Proof"));
+    let holder_did = try_future!(validate(DidValue(holder_did.to_string())));
+
+    send(|cb| prover(ProverCommand::BindProof(wallet_handle, proof_req, proof, holder_did, cb)))
+}
+
+/// Create revocation state for a credential that corresponds to a particular time. See `indy_create_revocation_state`.
+pub fn create_revocation_state(blob_storage_reader_handle: IndyHandle,
+                               rev_reg_def_json: &str,
+                               rev_reg_delta_json: &str,
+                               timestamp: u64,
+                               cred_rev_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let rev_reg_def: RevocationRegistryDefinition = try_future!(parse_validatable_json(rev_reg_def_json, "RevocationRegistryDefinition"));
+    let rev_reg_delta: RevocationRegistryDelta = try_future!(parse_validatable_json(rev_reg_delta_json, "RevocationRegistryDelta"));
+    let cred_rev_id = cred_rev_id.to_string();
+
+    send(|cb| prover(ProverCommand::CreateRevocationState(blob_storage_reader_handle, rev_reg_def, rev_reg_delta, timestamp, cred_rev_id, cb)))
+}
+
+/// Create new revocation state for a credential based on existed state. See `indy_update_revocation_state`.
+pub fn update_revocation_state(blob_storage_reader_handle: IndyHandle,
+                               rev_state_json: &str,
+                               rev_reg_def_json: &str,
+                               rev_reg_delta_json: &str,
+                               timestamp: u64,
+                               cred_rev_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let rev_state: RevocationState = try_future!(parse_validatable_json(rev_state_json, "RevocationState"));
+    let rev_reg_def: RevocationRegistryDefinition = try_future!(parse_validatable_json(rev_reg_def_json, "RevocationRegistryDefinition"));
+    let rev_reg_delta: RevocationRegistryDelta = try_future!(parse_validatable_json(rev_reg_delta_json, "RevocationRegistryDelta"));
+    let cred_rev_id = cred_rev_id.to_string();
+
+    send(|cb| prover(ProverCommand::UpdateRevocationState(blob_storage_reader_handle, rev_state, rev_reg_def, rev_reg_delta, timestamp, cred_rev_id, cb)))
+}
+
+/// Verifies a proof (of multiple credential). See `indy_verifier_verify_proof`.
+pub fn verifier_verify_proof(proof_request_json: &str,
+                             proof_json: &str,
+                             schemas_json: &str,
+                             credential_defs_json: &str,
+                             rev_reg_defs_json: &str,
+                             rev_regs_json: &str) -> impl Future<Output=IndyResult<bool>> {
+    let proof_request: ProofRequest = try_future!(parse_validatable_json(proof_request_json, "ProofRequest"));
+    let proof: Proof = try_future!(parse_validatable_json(proof_json, "Proof"));
+    let schemas: Schemas = try_future!(parse_json(schemas_json, "Schemas"));
+    let credential_defs: CredentialDefinitions = try_future!(parse_json(credential_defs_json, "CredentialDefinitions"));
+    let rev_reg_defs: RevocationRegistryDefinitions = try_future!(parse_json(rev_reg_defs_json, "RevocationRegistryDefinitions"));
+    let rev_regs: RevocationRegistries = try_future!(parse_json(rev_regs_json, "RevocationRegistries"));
+
+    send(|cb| verifier(VerifierCommand::VerifyProof(proof_request, proof, schemas, credential_defs, rev_reg_defs, rev_regs, cb)))
+}
+
+/// Creates verifier session keeping parsed ledger artifacts. See `indy_verifier_create_session`.
+pub fn verifier_create_session(schemas_json: &str,
+                               credential_defs_json: &str,
+                               rev_reg_defs_json: &str) -> impl Future<Output=IndyResult<IndyHandle>> {
+    let schemas: Schemas = try_future!(parse_json(schemas_json, "Schemas"));
+    let credential_defs: CredentialDefinitions = try_future!(parse_json(credential_defs_json, "CredentialDefinitions"));
+    let rev_reg_defs: RevocationRegistryDefinitions = try_future!(parse_json(rev_reg_defs_json, "RevocationRegistryDefinitions"));
+
+    send(|cb| verifier(VerifierCommand::CreateSession(schemas, credential_defs, rev_reg_defs, cb)))
+}
+
+/// Verifies a proof using verifier session. See `indy_verifier_verify_proof_with_session`.
+pub fn verifier_verify_proof_with_session(session_handle: IndyHandle,
+                                          proof_request_json: &str,
+                                          proof_json: &str,
+                                          rev_regs_json: &str) -> impl Future<Output=IndyResult<bool>> {
+    let proof_request: ProofRequest = try_future!(parse_validatable_json(proof_request_json, "ProofRequest"));
+    let proof: Proof = try_future!(parse_validatable_json(proof_json, "Proof"));
+    let rev_regs: RevocationRegistries = try_future!(parse_json(rev_regs_json, "RevocationRegistries"));
+
+    send(|cb| verifier(VerifierCommand::VerifyProofWithSession(session_handle, proof_request, proof, rev_regs, cb)))
+}
+
+/// Closes verifier session. See `indy_verifier_close_session`.
+pub fn verifier_close_session(session_handle: IndyHandle) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| verifier(VerifierCommand::CloseSession(session_handle, cb)))
+}
+
+/// Saves proof request template in the wallet. See `indy_verifier_save_proof_request_template`.
+pub fn verifier_save_proof_request_template(wallet_handle: WalletHandle, template_id: &str, template_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let template_id = template_id.to_string();
+    let template: ProofRequestTemplate = try_future!(parse_validatable_json(template_json, "ProofRequestTemplate"));
+
+    send(|cb| verifier(VerifierCommand::SaveProofRequestTemplate(wallet_handle, template_id, template, cb)))
+}
+
+/// Builds proof request from the saved template. See `indy_verifier_instantiate_proof_request_template`.
+pub fn verifier_instantiate_proof_request_template(wallet_handle: WalletHandle, template_id: &str, params_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let template_id = template_id.to_string();
+    let params: ProofRequestTemplateParams = try_future!(parse_json(params_json, "ProofRequestTemplateParams"));
+
+    send(|cb| verifier(VerifierCommand::InstantiateProofRequestTemplate(wallet_handle, template_id, params, cb)))
+}
+
+/// Generates 80-bit numbers that can be used as a nonce for proof request. See `indy_generate_nonce`.
+pub fn generate_nonce() -> impl Future<Output=IndyResult<String>> {
+    send(|cb| verifier(VerifierCommand::GenerateNonce(cb)))
+}
+
+/// Get unqualified form (short form without method) of a fully qualified entity. See `indy_to_unqualified`.
+pub fn to_unqualified(entity: &str) -> impl Future<Output=IndyResult<String>> {
+    let entity = entity.to_string();
+
+    send(|cb| Command::Anoncreds(AnoncredsCommand::ToUnqualified(entity, cb)))
+}
+
+/// Encodes json of anoncreds object into MessagePack. See `indy_anoncreds_json_to_msgpack`.
+pub fn json_to_msgpack(json: &str) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let json = json.to_string();
+
+    send(|cb| Command::Anoncreds(AnoncredsCommand::ToMsgpack(json, cb)))
+}
+
+/// Decodes MessagePack encoded anoncreds object into json. See `indy_anoncreds_msgpack_to_json`.
+pub fn msgpack_to_json(msgpack: &[u8]) -> impl Future<Output=IndyResult<String>> {
+    let msgpack = msgpack.to_vec();
+
+    send(|cb| Command::Anoncreds(AnoncredsCommand::FromMsgpack(msgpack, cb)))
+}
+
+/// Encodes raw value of credential attribute into the integer used in CL signatures. See `indy_encode_attribute_value`.
+pub fn encode_attribute_value(raw: &str) -> impl Future<Output=IndyResult<String>> {
+    let raw = raw.to_string();
+
+    send(|cb| Command::Anoncreds(AnoncredsCommand::EncodeAttributeValue(raw, cb)))
+}
+
+/// Wraps anoncreds credential into W3C Verifiable Credential data model. See `indy_to_w3c_credential`.
+pub fn to_w3c_credential(cred_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let credential: Credential = try_future!(parse_validatable_json(cred_json, "Credential"));
+
+    send(|cb| Command::Anoncreds(AnoncredsCommand::ToW3cCredential(credential, cb)))
+}
+
+/// Converts W3C Verifiable Credential back to anoncreds credential. See `indy_from_w3c_credential`.
+pub fn from_w3c_credential(w3c_cred_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let w3c_credential: W3CCredential = try_future!(parse_validatable_json(w3c_cred_json, "W3CCredential"));
+
+    send(|cb| Command::Anoncreds(AnoncredsCommand::FromW3cCredential(w3c_credential, cb)))
+}
@@ -0,0 +1,22 @@
+use std::future::Future;
+
+use indy_api_types::IndyHandle;
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::send;
+use crate::commands::Command;
+use crate::commands::blob_storage::BlobStorageCommand;
+
+/// Opens BLOB storage reader. See `indy_open_blob_storage_reader`.
+pub fn open_blob_storage_reader(type_: &str, config_json: &str) -> impl Future<Output=IndyResult<IndyHandle>> {
+    let (type_, config_json) = (type_.to_string(), config_json.to_string());
+
+    send(|cb| Command::BlobStorage(BlobStorageCommand::OpenReader(type_, config_json, cb)))
+}
+
+/// Opens BLOB storage writer. See `indy_open_blob_storage_writer`.
+pub fn open_blob_storage_writer(type_: &str, config_json: &str) -> impl Future<Output=IndyResult<IndyHandle>> {
+    let (type_, config_json) = (type_.to_string(), config_json.to_string());
+
+    send(|cb| Command::BlobStorage(BlobStorageCommand::OpenWriter(type_, config_json, cb)))
+}
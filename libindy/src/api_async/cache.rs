@@ -0,0 +1,70 @@
+use std::future::Future;
+
+use indy_api_types::{IndyHandle, PoolHandle, WalletHandle};
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::{parse_json, send, validate};
+use crate::commands::Command;
+use crate::commands::cache::CacheCommand;
+use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
+use crate::domain::anoncreds::schema::SchemaId;
+use crate::domain::cache::{GetCacheOptions, PurgeOptions, RevocationPrefetchOptions};
+use crate::domain::crypto::did::DidValue;
+
+/// Gets credential definition json data for specified credential definition id using cache. See `indy_get_cred_def`.
+pub fn get_cred_def(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, id: &str, options_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(validate(DidValue(submitter_did.to_string())));
+    let id = try_future!(validate(CredentialDefinitionId(id.to_string())));
+    let options: GetCacheOptions = try_future!(parse_json(options_json, "GetCacheOptions"));
+
+    send(|cb| Command::Cache(CacheCommand::GetCredDef(pool_handle, wallet_handle, submitter_did, id, options, cb)))
+}
+
+/// Gets schema json data for specified schema id using cache. See `indy_get_schema`.
+pub fn get_schema(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, id: &str, options_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(validate(DidValue(submitter_did.to_string())));
+    let id = try_future!(validate(SchemaId(id.to_string())));
+    let options: GetCacheOptions = try_future!(parse_json(options_json, "GetCacheOptions"));
+
+    send(|cb| Command::Cache(CacheCommand::GetSchema(pool_handle, wallet_handle, submitter_did, id, options, cb)))
+}
+
+/// Purges credential definition cache. See `indy_purge_cred_def_cache`.
+pub fn purge_cred_def_cache(wallet_handle: WalletHandle, options_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let options: PurgeOptions = try_future!(parse_json(options_json, "PurgeOptions"));
+
+    send(|cb| Command::Cache(CacheCommand::PurgeCredDefCache(wallet_handle, options, cb)))
+}
+
+/// Purges schema cache. See `indy_purge_schema_cache`.
+pub fn purge_schema_cache(wallet_handle: WalletHandle, options_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let options: PurgeOptions = try_future!(parse_json(options_json, "PurgeOptions"));
+
+    send(|cb| Command::Cache(CacheCommand::PurgeSchemaCache(wallet_handle, options, cb)))
+}
+
+/// Purges NYM cache. See `indy_purge_nym_cache`.
+pub fn purge_nym_cache(wallet_handle: WalletHandle, options_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let options: PurgeOptions = try_future!(parse_json(options_json, "PurgeOptions"));
+
+    send(|cb| Command::Cache(CacheCommand::PurgeNymCache(wallet_handle, options, cb)))
+}
+
+/// Starts background prefetching of revocation states. See `indy_start_revocation_prefetch`.
+pub fn start_revocation_prefetch(pool_handle: PoolHandle, wallet_handle: WalletHandle, blob_storage_reader_handle: IndyHandle, options_json: &str) -> impl Future<Output=IndyResult<IndyHandle>> {
+    let options: RevocationPrefetchOptions = try_future!(parse_json(options_json, "RevocationPrefetchOptions"));
+
+    send(|cb| Command::Cache(CacheCommand::StartRevocationPrefetch(pool_handle, wallet_handle, blob_storage_reader_handle, options, cb)))
+}
+
+/// Stops background prefetching of revocation states. See `indy_stop_revocation_prefetch`.
+pub fn stop_revocation_prefetch(prefetch_handle: IndyHandle) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| Command::Cache(CacheCommand::StopRevocationPrefetch(prefetch_handle, cb)))
+}
+
+/// Gets prefetched revocation state of the credential. See `indy_get_cached_revocation_state`.
+pub fn get_cached_revocation_state(wallet_handle: WalletHandle, cred_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let cred_id = cred_id.to_string();
+
+    send(|cb| Command::Cache(CacheCommand::GetCachedRevocationState(wallet_handle, cred_id, cb)))
+}
@@ -0,0 +1,220 @@
+use std::future::Future;
+
+use indy_api_types::{WalletHandle, PoolHandle};
+use indy_api_types::errors::prelude::*;
+use serde_json;
+
+use crate::api_async::{CommandFuture, parse_json, send};
+use crate::commands::Command;
+use crate::commands::crypto::CryptoCommand;
+use crate::domain::crypto::key::{BlsKeyInfo, KeyInfo};
+use crate::domain::crypto::pack::{JWE, PackMode};
+use crate::domain::crypto::did::DidValue;
+use crate::domain::cache::NymCacheOptions;
+
+/// Creates key pair in wallet. See `indy_create_key`.
+pub fn create_key(wallet_handle: WalletHandle, key_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let key_info: KeyInfo = try_future!(parse_json(key_json, "KeyInfo"));
+
+    send(|cb| Command::Crypto(CryptoCommand::CreateKey(wallet_handle, key_info, cb)))
+}
+
+/// Generates BLS key pair of a validator node. See `indy_create_bls_key`.
+pub fn create_bls_key(key_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let key_info: BlsKeyInfo = try_future!(parse_json(key_json, "BlsKeyInfo"));
+
+    send(|cb| Command::Crypto(CryptoCommand::CreateBlsKey(key_info, cb)))
+}
+
+/// Saves/replaces the metadata for the given verkey. See `indy_set_key_metadata`.
+pub fn set_key_metadata(wallet_handle: WalletHandle, verkey: &str, metadata: &str) -> impl Future<Output=IndyResult<()>> {
+    let verkey = verkey.to_string();
+    let metadata = metadata.to_string();
+
+    send(|cb| Command::Crypto(CryptoCommand::SetKeyMetadata(wallet_handle, verkey, metadata, cb)))
+}
+
+/// Retrieves the metadata for the given verkey. See `indy_get_key_metadata`.
+pub fn get_key_metadata(wallet_handle: WalletHandle, verkey: &str) -> impl Future<Output=IndyResult<String>> {
+    let verkey = verkey.to_string();
+
+    send(|cb| Command::Crypto(CryptoCommand::GetKeyMetadata(wallet_handle, verkey, cb)))
+}
+
+/// Retrieves the path the given verkey was derived by. See `indy_get_key_derivation_path`.
+pub fn get_key_derivation_path(wallet_handle: WalletHandle, verkey: &str) -> impl Future<Output=IndyResult<String>> {
+    let verkey = verkey.to_string();
+
+    send(|cb| Command::Crypto(CryptoCommand::GetKeyDerivationPath(wallet_handle, verkey, cb)))
+}
+
+/// Retrieves X25519 key agreement key for the given verkey. See `indy_get_key_agreement_key`.
+pub fn get_key_agreement_key(wallet_handle: WalletHandle, verkey: &str) -> impl Future<Output=IndyResult<String>> {
+    let verkey = verkey.to_string();
+
+    send(|cb| Command::Crypto(CryptoCommand::GetKeyAgreementKey(wallet_handle, verkey, cb)))
+}
+
+/// Converts ed25519 verkey to X25519 public key. See `indy_convert_key`.
+pub fn convert_key(verkey: &str) -> impl Future<Output=IndyResult<String>> {
+    let verkey = verkey.to_string();
+
+    send(|cb| Command::Crypto(CryptoCommand::ConvertKey(verkey, cb)))
+}
+
+/// Signs a message with a key. See `indy_crypto_sign`.
+pub fn sign(wallet_handle: WalletHandle, signer_vk: &str, message: &[u8]) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let signer_vk = signer_vk.to_string();
+    let message = message.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::CryptoSign(wallet_handle, signer_vk, message, cb)))
+}
+
+/// Verifies a message signature with a key. See `indy_crypto_verify`.
+pub fn verify(signer_vk: &str, message: &[u8], signature: &[u8]) -> impl Future<Output=IndyResult<bool>> {
+    let signer_vk = signer_vk.to_string();
+    let message = message.to_vec();
+    let signature = signature.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::CryptoVerify(signer_vk, message, signature, true, cb)))
+}
+
+/// Verifies a message signature with a key reporting malformed inputs as errors or as invalid signature.
+/// See `indy_crypto_verify_with_strictness`.
+pub fn verify_with_strictness(signer_vk: &str, message: &[u8], signature: &[u8], strict: bool) -> impl Future<Output=IndyResult<bool>> {
+    let signer_vk = signer_vk.to_string();
+    let message = message.to_vec();
+    let signature = signature.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::CryptoVerify(signer_vk, message, signature, strict, cb)))
+}
+
+/// Encrypts a message by authenticated-encryption scheme. See `indy_crypto_auth_crypt`.
+pub fn auth_crypt(wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &str, message: &[u8]) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let sender_vk = sender_vk.to_string();
+    let recipient_vk = recipient_vk.to_string();
+    let message = message.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::AuthenticatedEncrypt(wallet_handle, sender_vk, recipient_vk, message, cb)))
+}
+
+/// Decrypts a message by authenticated-encryption scheme. See `indy_crypto_auth_decrypt`.
+///
+/// Resolves to the sender verkey and the decrypted message.
+pub fn auth_decrypt(wallet_handle: WalletHandle, recipient_vk: &str, encrypted_message: &[u8]) -> impl Future<Output=IndyResult<(String, Vec<u8>)>> {
+    let recipient_vk = recipient_vk.to_string();
+    let encrypted_message = encrypted_message.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::AuthenticatedDecrypt(wallet_handle, recipient_vk, encrypted_message, cb)))
+}
+
+/// Encrypts a message by anonymous-encryption scheme. See `indy_crypto_anon_crypt`.
+pub fn anon_crypt(recipient_vk: &str, message: &[u8]) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let recipient_vk = recipient_vk.to_string();
+    let message = message.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::AnonymousEncrypt(recipient_vk, message, cb)))
+}
+
+/// Decrypts a message by anonymous-encryption scheme. See `indy_crypto_anon_decrypt`.
+pub fn anon_decrypt(wallet_handle: WalletHandle, recipient_vk: &str, encrypted_message: &[u8]) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let recipient_vk = recipient_vk.to_string();
+    let encrypted_message = encrypted_message.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::AnonymousDecrypt(wallet_handle, recipient_vk, encrypted_message, cb)))
+}
+
+/// Encrypts a message with crypto_box primitive using the given nonce. See `indy_crypto_box`.
+pub fn crypto_box(wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &str, message: &[u8], nonce: &[u8]) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let sender_vk = sender_vk.to_string();
+    let recipient_vk = recipient_vk.to_string();
+    let message = message.to_vec();
+    let nonce = nonce.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::CryptoBox(wallet_handle, sender_vk, recipient_vk, message, nonce, cb)))
+}
+
+/// Decrypts a message encrypted with crypto_box primitive. See `indy_crypto_box_open`.
+pub fn crypto_box_open(wallet_handle: WalletHandle, recipient_vk: &str, sender_vk: &str, encrypted_message: &[u8], nonce: &[u8]) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let recipient_vk = recipient_vk.to_string();
+    let sender_vk = sender_vk.to_string();
+    let encrypted_message = encrypted_message.to_vec();
+    let nonce = nonce.to_vec();
+
+    send(|cb| Command::Crypto(CryptoCommand::CryptoBoxOpen(wallet_handle, recipient_vk, sender_vk, encrypted_message, nonce, cb)))
+}
+
+/// Packs a message by encrypting the message and serializes it in a JWE-like format. See `indy_pack_message`.
+pub fn pack_message(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let receiver_list: Vec<String> = try_future!(serde_json::from_str(receiver_keys)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid RecipientKeys has been passed"));
+
+    if receiver_list.is_empty() {
+        return CommandFuture::ready(Err(err_msg(IndyErrorKind::InvalidStructure, "Empty RecipientKeys has been passed")));
+    }
+
+    let message = message.to_vec();
+    let sender = sender.map(String::from);
+
+    let mode = PackMode::for_sender(sender.as_ref());
+
+    send(|cb| Command::Crypto(CryptoCommand::PackMessage(message, receiver_list, sender, mode, wallet_handle, cb)))
+}
+
+/// Packs a message with explicitly chosen packing mode. See `indy_pack_message_with_mode`.
+pub fn pack_message_with_mode(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, mode: &str) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let receiver_list: Vec<String> = try_future!(serde_json::from_str(receiver_keys)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid RecipientKeys has been passed"));
+
+    if receiver_list.is_empty() {
+        return CommandFuture::ready(Err(err_msg(IndyErrorKind::InvalidStructure, "Empty RecipientKeys has been passed")));
+    }
+
+    let mode: PackMode = try_future!(serde_json::from_value(serde_json::Value::String(mode.to_string()))
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid pack mode has been passed"));
+
+    let message = message.to_vec();
+    let sender = sender.map(String::from);
+
+    send(|cb| Command::Crypto(CryptoCommand::PackMessage(message, receiver_list, sender, mode, wallet_handle, cb)))
+}
+
+/// Packs a message for receivers identified by their DIDs. See `indy_pack_message_for_dids`.
+pub fn pack_message_for_dids(pool_handle: PoolHandle, wallet_handle: WalletHandle, message: &[u8], receiver_dids: &str, sender: Option<&str>, options_json: &str) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let receiver_dids: Vec<DidValue> = try_future!(parse_json(receiver_dids, "Vec<DidValue>"));
+    let options: NymCacheOptions = try_future!(parse_json(options_json, "NymCacheOptions"));
+
+    if receiver_dids.is_empty() {
+        return CommandFuture::ready(Err(err_msg(IndyErrorKind::InvalidStructure, "Empty receiver DIDs has been passed")));
+    }
+
+    let message = message.to_vec();
+    let sender = sender.map(String::from);
+
+    send(|cb| Command::Crypto(CryptoCommand::PackMessageForDids(message, receiver_dids, sender, pool_handle, wallet_handle, options, cb)))
+}
+
+/// Unpacks a JWE-like formatted message outputted by `pack_message`. See `indy_unpack_message`.
+pub fn unpack_message(wallet_handle: WalletHandle, jwe: &[u8]) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let jwe: JWE = try_future!(serde_json::from_slice(jwe)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid JWE json has been passed"));
+
+    send(|cb| Command::Crypto(CryptoCommand::UnpackMessage(jwe, wallet_handle, cb)))
+}
+
+/// Lists messages failed to be unpacked. See `indy_list_dead_letters`.
+pub fn list_dead_letters(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Crypto(CryptoCommand::ListDeadLetters(wallet_handle, cb)))
+}
+
+/// Retries unpacking of the message failed to be unpacked. See `indy_retry_dead_letter`.
+pub fn retry_dead_letter(wallet_handle: WalletHandle, dead_letter_id: &str) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let dead_letter_id = dead_letter_id.to_string();
+
+    send(|cb| Command::Crypto(CryptoCommand::RetryDeadLetter(wallet_handle, dead_letter_id, cb)))
+}
+
+/// Removes dead letters received before the given time, all of them if the time isn't set. See `indy_purge_dead_letters`.
+pub fn purge_dead_letters(wallet_handle: WalletHandle, received_before: Option<u64>) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| Command::Crypto(CryptoCommand::PurgeDeadLetters(wallet_handle, received_before, cb)))
+}
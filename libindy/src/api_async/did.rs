@@ -0,0 +1,203 @@
+use std::future::Future;
+
+use indy_api_types::{PoolHandle, WalletHandle};
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::{parse_json, parse_opt_json, parse_validatable_json, send, validate};
+use crate::commands::Command;
+use crate::commands::did::DidCommand;
+use crate::domain::crypto::did::{DidMethod, DidOwnershipProof, DidValue, MyDidInfo, MyDidsInfo, TheirDidInfo};
+use crate::domain::crypto::key::KeyInfo;
+use crate::domain::ledger::attrib::{Endpoint, ServiceEndpoint};
+
+/// Creates keys (signing and encryption keys) for a new DID. See `indy_create_and_store_my_did`.
+///
+/// Resolves to the DID and its verkey.
+pub fn create_and_store_my_did(wallet_handle: WalletHandle, did_info: &str) -> impl Future<Output=IndyResult<(String, String)>> {
+    let did_info: MyDidInfo = try_future!(parse_validatable_json(did_info, "MyDidInfo"));
+
+    send(|cb| Command::Did(DidCommand::CreateAndStoreMyDid(wallet_handle, did_info, cb)))
+}
+
+/// Creates and stores a batch of new DIDs. See `indy_create_and_store_my_dids`.
+///
+/// Resolves to the json list of created DIDs and their verkeys.
+pub fn create_and_store_my_dids(wallet_handle: WalletHandle, count: u32, options: &str) -> impl Future<Output=IndyResult<String>> {
+    let options: MyDidsInfo = try_future!(parse_validatable_json(options, "MyDidsInfo"));
+
+    send(|cb| Command::Did(DidCommand::CreateAndStoreMyDids(wallet_handle, count, options, cb)))
+}
+
+/// Generates temporary keys for an existing DID. See `indy_replace_keys_start`.
+pub fn replace_keys_start(wallet_handle: WalletHandle, did: &str, key_info: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+    let key_info: KeyInfo = try_future!(parse_json(key_info, "KeyInfo"));
+
+    send(|cb| Command::Did(DidCommand::ReplaceKeysStart(wallet_handle, key_info, did, cb)))
+}
+
+/// Applies temporary keys as main for an existing DID. See `indy_replace_keys_apply`.
+pub fn replace_keys_apply(wallet_handle: WalletHandle, did: &str) -> impl Future<Output=IndyResult<()>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::ReplaceKeysApply(wallet_handle, did, cb)))
+}
+
+/// Saves their DID for a pairwise connection in a secured wallet. See `indy_store_their_did`.
+pub fn store_their_did(wallet_handle: WalletHandle, identity_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let their_did_info: TheirDidInfo = try_future!(parse_validatable_json(identity_json, "TheirDidInfo"));
+
+    send(|cb| Command::Did(DidCommand::StoreTheirDid(wallet_handle, their_did_info, cb)))
+}
+
+/// Returns ver key (key id) for the given DID, resolving it on the ledger if needed. See `indy_key_for_did`.
+pub fn key_for_did(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::KeyForDid(pool_handle, wallet_handle, did, cb)))
+}
+
+/// Returns ver key (key id) for the given DID from the wallet only. See `indy_key_for_local_did`.
+pub fn key_for_local_did(wallet_handle: WalletHandle, did: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::KeyForLocalDid(wallet_handle, did, cb)))
+}
+
+/// Sets/replaces endpoint information for the given DID. See `indy_set_endpoint_for_did`.
+pub fn set_endpoint_for_did(wallet_handle: WalletHandle, did: &str, address: &str, transport_key: &str) -> impl Future<Output=IndyResult<()>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+    let endpoint = Endpoint::new(address.to_string(), Some(transport_key.to_string()));
+
+    send(|cb| Command::Did(DidCommand::SetEndpointForDid(wallet_handle, did, endpoint, cb)))
+}
+
+/// Returns endpoint information for the given DID. See `indy_get_endpoint_for_did`.
+///
+/// Resolves to the endpoint address and the optional transport key.
+pub fn get_endpoint_for_did(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str) -> impl Future<Output=IndyResult<(String, Option<String>)>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::GetEndpointForDid(wallet_handle, pool_handle, did, cb)))
+}
+
+/// Saves/replaces the metadata for the given DID. See `indy_set_did_metadata`.
+pub fn set_did_metadata(wallet_handle: WalletHandle, did: &str, metadata: &str) -> impl Future<Output=IndyResult<()>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+    let metadata = metadata.to_string();
+
+    send(|cb| Command::Did(DidCommand::SetDidMetadata(wallet_handle, did, metadata, cb)))
+}
+
+/// Retrieves the metadata for the given DID. See `indy_get_did_metadata`.
+pub fn get_did_metadata(wallet_handle: WalletHandle, did: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::GetDidMetadata(wallet_handle, did, cb)))
+}
+
+/// Designates the key to be used by my DID for the purpose. See `indy_set_did_key`.
+pub fn set_did_key(wallet_handle: WalletHandle, did: &str, purpose: &str, verkey: &str) -> impl Future<Output=IndyResult<()>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+    let purpose = purpose.to_string();
+    let verkey = verkey.to_string();
+
+    send(|cb| Command::Did(DidCommand::SetDidKey(wallet_handle, did, purpose, verkey, cb)))
+}
+
+/// Returns keys my DID uses for every purpose. See `indy_get_did_keys`.
+pub fn get_did_keys(wallet_handle: WalletHandle, did: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::GetDidKeys(wallet_handle, did, cb)))
+}
+
+/// Retrieves the information about the given DID with metadata. See `indy_get_my_did_with_meta`.
+pub fn get_my_did_with_meta(wallet_handle: WalletHandle, my_did: &str) -> impl Future<Output=IndyResult<String>> {
+    let my_did = try_future!(validate(DidValue(my_did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::GetMyDidWithMeta(wallet_handle, my_did, cb)))
+}
+
+/// Retrieves the information about all DIDs stored in the wallet. See `indy_list_my_dids_with_meta`.
+pub fn list_my_dids_with_meta(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Did(DidCommand::ListMyDidsWithMeta(wallet_handle, cb)))
+}
+
+/// Retrieves abbreviated verkey if it is possible otherwise return full verkey. See `indy_abbreviate_verkey`.
+pub fn abbreviate_verkey(did: &str, full_verkey: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+    let full_verkey = full_verkey.to_string();
+
+    send(|cb| Command::Did(DidCommand::AbbreviateVerkey(did, full_verkey, cb)))
+}
+
+/// Updates the DID related entities stored in the wallet to the fully qualified form. See `indy_qualify_did`.
+pub fn qualify_did(wallet_handle: WalletHandle, did: &str, method: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+    let method = try_future!(validate(DidMethod(method.to_string())));
+
+    send(|cb| Command::Did(DidCommand::QualifyDid(wallet_handle, did, method, cb)))
+}
+
+/// Creates a self-contained proof of control over DID written to the ledger. See `indy_create_did_ownership_proof`.
+pub fn create_did_ownership_proof(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, challenge: &str, audience: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+    let challenge = challenge.to_string();
+    let audience = audience.to_string();
+
+    send(|cb| Command::Did(DidCommand::CreateDidOwnershipProof(wallet_handle, pool_handle, did, challenge, audience, cb)))
+}
+
+/// Checks NYM of DID stored in the wallet against the ledger. See `indy_check_my_did_nym`.
+pub fn check_my_did_nym(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::CheckMyDidNym(wallet_handle, pool_handle, did, cb)))
+}
+
+/// Verifies a proof of DID control against the ledger. See `indy_verify_did_ownership_proof`.
+pub fn verify_did_ownership_proof(pool_handle: PoolHandle, proof_json: &str, challenge: &str, audience: &str) -> impl Future<Output=IndyResult<bool>> {
+    let proof: DidOwnershipProof = try_future!(parse_validatable_json(proof_json, "DidOwnershipProof"));
+    let challenge = challenge.to_string();
+    let audience = audience.to_string();
+
+    send(|cb| Command::Did(DidCommand::VerifyDidOwnershipProof(pool_handle, proof, challenge, audience, cb)))
+}
+
+/// Publishes endpoint of the DID to the ledger as standardized endpoint ATTRIB. See `indy_publish_endpoint_for_did`.
+pub fn publish_endpoint_for_did(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, address: &str,
+                                types_json: Option<&str>, routing_keys_json: Option<&str>) -> impl Future<Output=IndyResult<()>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+    let types: Option<Vec<String>> = try_future!(parse_opt_json(types_json, "types"));
+    let routing_keys: Option<Vec<String>> = try_future!(parse_opt_json(routing_keys_json, "routing keys"));
+    let endpoint = try_future!(validate(ServiceEndpoint {
+        endpoint: address.to_string(),
+        types: types.unwrap_or_default(),
+        routing_keys: routing_keys.unwrap_or_default(),
+        verkey: None,
+    }));
+
+    send(|cb| Command::Did(DidCommand::PublishEndpointForDid(wallet_handle, pool_handle, did, endpoint, cb)))
+}
+
+/// Resolves endpoint ATTRIB of the DID into DID Document service entry. See `indy_resolve_service_endpoint_for_did`.
+pub fn resolve_service_endpoint_for_did(pool_handle: PoolHandle, did: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::ResolveServiceEndpointForDid(pool_handle, did, cb)))
+}
+
+/// Resolves the DID into W3C DID Document. See `indy_resolve_did`.
+pub fn resolve_did(pool_handle: PoolHandle, did: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::ResolveDid(pool_handle, None, did, cb)))
+}
+
+/// Resolves the DID into W3C DID Document with pairwise stored in the wallet. See `indy_resolve_did_with_wallet`.
+pub fn resolve_did_with_wallet(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> impl Future<Output=IndyResult<String>> {
+    let did = try_future!(validate(DidValue(did.to_string())));
+
+    send(|cb| Command::Did(DidCommand::ResolveDid(pool_handle, Some(wallet_handle), did, cb)))
+}
@@ -0,0 +1,463 @@
+use std::future::Future;
+
+use indy_api_types::{PoolHandle, WalletHandle};
+use indy_api_types::errors::prelude::*;
+use serde_json;
+
+use crate::api_async::{parse_json, parse_opt_json, parse_opt_validatable_json, parse_validatable_json, send, validate};
+use crate::commands::Command;
+use crate::commands::ledger::LedgerCommand;
+use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionId};
+use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryId};
+use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::anoncreds::schema::{Schema, SchemaId};
+use crate::domain::crypto::did::DidValue;
+use crate::domain::ledger::auth_rule::{AuthRules, Constraint};
+use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, GetTxnAuthorAgreementData};
+use crate::domain::ledger::node::NodeOperationData;
+use crate::domain::ledger::nym::NymHistoryOptions;
+use crate::domain::ledger::pool::Schedule;
+use crate::domain::ledger::response::WriteReceipt;
+use crate::domain::ledger::txn::FetchTxnsOptions;
+
+fn did(did: &str) -> IndyResult<DidValue> {
+    validate(DidValue(did.to_string()))
+}
+
+fn opt_did(did: Option<&str>) -> IndyResult<Option<DidValue>> {
+    did.map(self::did).transpose()
+}
+
+/// Signs and submits request message to validator pool. See `indy_sign_and_submit_request`.
+pub fn sign_and_submit_request(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, request_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let request_json = request_json.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::SignAndSubmitRequest(pool_handle, wallet_handle, submitter_did, request_json, cb)))
+}
+
+/// Publishes request message to validator pool (no signing, unlike `sign_and_submit_request`). See `indy_submit_request`.
+pub fn submit_request(pool_handle: PoolHandle, request_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let request_json = request_json.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::SubmitRequest(pool_handle, request_json, cb)))
+}
+
+/// Send action to particular nodes of validator pool. See `indy_submit_action`.
+pub fn submit_action(pool_handle: PoolHandle, request_json: &str, nodes: Option<&str>, timeout: Option<i32>) -> impl Future<Output=IndyResult<String>> {
+    let request_json = request_json.to_string();
+    let nodes = nodes.map(String::from);
+
+    send(|cb| Command::Ledger(LedgerCommand::SubmitAction(pool_handle, request_json, nodes, timeout, cb)))
+}
+
+/// Signs request message. See `indy_sign_request`.
+pub fn sign_request(wallet_handle: WalletHandle, submitter_did: &str, request_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let request_json = request_json.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::SignRequest(wallet_handle, submitter_did, request_json, cb)))
+}
+
+/// Multi signs request message. See `indy_multi_sign_request`.
+pub fn multi_sign_request(wallet_handle: WalletHandle, submitter_did: &str, request_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let request_json = request_json.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::MultiSignRequest(wallet_handle, submitter_did, request_json, cb)))
+}
+
+/// Multi signs request message with several DIDs at once. See `indy_multi_sign_request_with_dids`.
+pub fn multi_sign_request_with_dids(wallet_handle: WalletHandle, submitter_dids_json: &str, request_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_dids: Vec<DidValue> = try_future!(parse_json(submitter_dids_json, "Vec<DidValue>"));
+    let request_json = request_json.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::MultiSignRequestWithDids(wallet_handle, submitter_dids, request_json, cb)))
+}
+
+/// Builds a request to get a DDO. See `indy_build_get_ddo_request`.
+pub fn build_get_ddo_request(submitter_did: Option<&str>, target_did: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let target_did = try_future!(did(target_did));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetDdoRequest(submitter_did, target_did, cb)))
+}
+
+/// Builds a NYM request. See `indy_build_nym_request`.
+pub fn build_nym_request(submitter_did: &str, target_did: &str, verkey: Option<&str>, alias: Option<&str>, role: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let target_did = try_future!(did(target_did));
+    let (verkey, alias, role) = (verkey.map(String::from), alias.map(String::from), role.map(String::from));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildNymRequest(submitter_did, target_did, verkey, alias, role, cb)))
+}
+
+/// Builds a GET_NYM request. See `indy_build_get_nym_request`.
+pub fn build_get_nym_request(submitter_did: Option<&str>, target_did: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let target_did = try_future!(did(target_did));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetNymRequest(submitter_did, target_did, cb)))
+}
+
+/// Parses a GET_NYM response to get NYM data. See `indy_parse_get_nym_response`.
+pub fn parse_get_nym_response(get_nym_response: &str) -> impl Future<Output=IndyResult<String>> {
+    let get_nym_response = get_nym_response.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::ParseGetNymResponse(get_nym_response, cb)))
+}
+
+/// Collects NYM transactions of the DID from the ledger. See `indy_get_nym_history`.
+pub fn get_nym_history(pool_handle: PoolHandle, target_did: &str, options_json: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let target_did = try_future!(did(target_did));
+    let options: Option<NymHistoryOptions> = try_future!(parse_opt_validatable_json(options_json, "NymHistoryOptions"));
+
+    send(|cb| Command::Ledger(LedgerCommand::GetNymHistory(pool_handle, target_did, options, cb)))
+}
+
+/// Fetches a batch of ledger transactions. See `indy_fetch_ledger_txns`.
+pub fn fetch_ledger_txns(pool_handle: PoolHandle, options_json: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let options: Option<FetchTxnsOptions> = try_future!(parse_opt_validatable_json(options_json, "FetchTxnsOptions"));
+
+    send(|cb| Command::Ledger(LedgerCommand::FetchTxns(pool_handle, options, cb)))
+}
+
+/// Builds an ATTRIB request. See `indy_build_attrib_request`.
+pub fn build_attrib_request(submitter_did: &str, target_did: &str, hash: Option<&str>, raw: Option<&str>, enc: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let target_did = try_future!(did(target_did));
+    let raw: Option<serde_json::Value> = try_future!(parse_opt_json(raw, "Value"));
+    let (hash, enc) = (hash.map(String::from), enc.map(String::from));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildAttribRequest(submitter_did, target_did, hash, raw, enc, cb)))
+}
+
+/// Builds a GET_ATTRIB request. See `indy_build_get_attrib_request`.
+pub fn build_get_attrib_request(submitter_did: Option<&str>, target_did: &str, raw: Option<&str>, hash: Option<&str>, enc: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let target_did = try_future!(did(target_did));
+    let (raw, hash, enc) = (raw.map(String::from), hash.map(String::from), enc.map(String::from));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetAttribRequest(submitter_did, target_did, raw, hash, enc, cb)))
+}
+
+/// Builds a SCHEMA request. See `indy_build_schema_request`.
+pub fn build_schema_request(submitter_did: &str, data: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let data: Schema = try_future!(parse_validatable_json(data, "Schema"));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildSchemaRequest(submitter_did, data, cb)))
+}
+
+/// Builds a GET_SCHEMA request. See `indy_build_get_schema_request`.
+pub fn build_get_schema_request(submitter_did: Option<&str>, id: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let id = try_future!(validate(SchemaId(id.to_string())));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetSchemaRequest(submitter_did, id, cb)))
+}
+
+/// Parses a GET_SCHEMA response to get Schema in the format compatible with Anoncreds API. See `indy_parse_get_schema_response`.
+///
+/// Resolves to the schema id and the schema json.
+pub fn parse_get_schema_response(get_schema_response: &str) -> impl Future<Output=IndyResult<(String, String)>> {
+    let get_schema_response = get_schema_response.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::ParseGetSchemaResponse(get_schema_response, cb)))
+}
+
+/// Builds an CRED_DEF request. See `indy_build_cred_def_request`.
+pub fn build_cred_def_request(submitter_did: &str, data: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let data: CredentialDefinition = try_future!(parse_validatable_json(data, "CredentialDefinition"));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildCredDefRequest(submitter_did, data, cb)))
+}
+
+/// Builds a GET_CRED_DEF request. See `indy_build_get_cred_def_request`.
+pub fn build_get_cred_def_request(submitter_did: Option<&str>, id: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let id = try_future!(validate(CredentialDefinitionId(id.to_string())));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetCredDefRequest(submitter_did, id, cb)))
+}
+
+/// Parses a GET_CRED_DEF response to get Credential Definition in the format compatible with Anoncreds API. See `indy_parse_get_cred_def_response`.
+///
+/// Resolves to the credential definition id and the credential definition json.
+pub fn parse_get_cred_def_response(get_cred_def_response: &str) -> impl Future<Output=IndyResult<(String, String)>> {
+    let get_cred_def_response = get_cred_def_response.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::ParseGetCredDefResponse(get_cred_def_response, cb)))
+}
+
+/// Builds a NODE request. See `indy_build_node_request`.
+pub fn build_node_request(submitter_did: &str, target_did: &str, data: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let target_did = try_future!(did(target_did));
+    let data: NodeOperationData = try_future!(parse_json(data, "NodeOperationData"));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildNodeRequest(submitter_did, target_did, data, cb)))
+}
+
+/// Builds a GET_VALIDATOR_INFO request. See `indy_build_get_validator_info_request`.
+pub fn build_get_validator_info_request(submitter_did: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetValidatorInfoRequest(submitter_did, cb)))
+}
+
+/// Builds a GET_TXN request. See `indy_build_get_txn_request`.
+pub fn build_get_txn_request(submitter_did: Option<&str>, ledger_type: Option<&str>, seq_no: i32) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let ledger_type = ledger_type.map(String::from);
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetTxnRequest(submitter_did, ledger_type, seq_no, cb)))
+}
+
+/// Builds a POOL_CONFIG request. See `indy_build_pool_config_request`.
+pub fn build_pool_config_request(submitter_did: &str, writes: bool, force: bool) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildPoolConfigRequest(submitter_did, writes, force, cb)))
+}
+
+/// Builds a POOL_RESTART request. See `indy_build_pool_restart_request`.
+pub fn build_pool_restart_request(submitter_did: &str, action: &str, datetime: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let action = action.to_string();
+    let datetime = datetime.map(String::from);
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildPoolRestartRequest(submitter_did, action, datetime, cb)))
+}
+
+/// Builds a POOL_UPGRADE request. See `indy_build_pool_upgrade_request`.
+pub fn build_pool_upgrade_request(submitter_did: &str,
+                                  name: &str,
+                                  version: &str,
+                                  action: &str,
+                                  sha256: &str,
+                                  timeout: Option<u32>,
+                                  schedule: Option<&str>,
+                                  justification: Option<&str>,
+                                  reinstall: bool,
+                                  force: bool,
+                                  package: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let schedule: Option<Schedule> = try_future!(parse_opt_json(schedule, "Schedule"));
+    let (name, version, action, sha256) = (name.to_string(), version.to_string(), action.to_string(), sha256.to_string());
+    let (justification, package) = (justification.map(String::from), package.map(String::from));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildPoolUpgradeRequest(
+        submitter_did, name, version, action, sha256, timeout, schedule, justification, reinstall, force, package, cb)))
+}
+
+/// Builds a REVOC_REG_DEF request. See `indy_build_revoc_reg_def_request`.
+pub fn build_revoc_reg_def_request(submitter_did: &str, data: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let data: RevocationRegistryDefinition = try_future!(parse_validatable_json(data, "RevocationRegistryDefinition"));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildRevocRegDefRequest(submitter_did, data, cb)))
+}
+
+/// Builds a GET_REVOC_REG_DEF request. See `indy_build_get_revoc_reg_def_request`.
+pub fn build_get_revoc_reg_def_request(submitter_did: Option<&str>, id: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let id = try_future!(validate(RevocationRegistryId(id.to_string())));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetRevocRegDefRequest(submitter_did, id, cb)))
+}
+
+/// Parses a GET_REVOC_REG_DEF response. See `indy_parse_get_revoc_reg_def_response`.
+///
+/// Resolves to the revocation registry definition id and json.
+pub fn parse_get_revoc_reg_def_response(get_revoc_reg_def_response: &str) -> impl Future<Output=IndyResult<(String, String)>> {
+    let get_revoc_reg_def_response = get_revoc_reg_def_response.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::ParseGetRevocRegDefResponse(get_revoc_reg_def_response, cb)))
+}
+
+/// Builds a REVOC_REG_ENTRY request. See `indy_build_revoc_reg_entry_request`.
+pub fn build_revoc_reg_entry_request(submitter_did: &str, revoc_reg_def_id: &str, rev_def_type: &str, value: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let revoc_reg_def_id = try_future!(validate(RevocationRegistryId(revoc_reg_def_id.to_string())));
+    let rev_def_type = rev_def_type.to_string();
+    let value: RevocationRegistryDelta = try_future!(parse_json(value, "RevocationRegistryDelta"));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildRevocRegEntryRequest(submitter_did, revoc_reg_def_id, rev_def_type, value, cb)))
+}
+
+/// Builds a GET_REVOC_REG request. See `indy_build_get_revoc_reg_request`.
+pub fn build_get_revoc_reg_request(submitter_did: Option<&str>, revoc_reg_def_id: &str, timestamp: i64) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let revoc_reg_def_id = try_future!(validate(RevocationRegistryId(revoc_reg_def_id.to_string())));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetRevocRegRequest(submitter_did, revoc_reg_def_id, timestamp, cb)))
+}
+
+/// Parses a GET_REVOC_REG response. See `indy_parse_get_revoc_reg_response`.
+///
+/// Resolves to the revocation registry definition id, the revocation registry json and its timestamp.
+pub fn parse_get_revoc_reg_response(get_revoc_reg_response: &str) -> impl Future<Output=IndyResult<(String, String, u64)>> {
+    let get_revoc_reg_response = get_revoc_reg_response.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::ParseGetRevocRegResponse(get_revoc_reg_response, cb)))
+}
+
+/// Builds a GET_REVOC_REG_DELTA request. See `indy_build_get_revoc_reg_delta_request`.
+pub fn build_get_revoc_reg_delta_request(submitter_did: Option<&str>, revoc_reg_def_id: &str, from: Option<i64>, to: i64) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let revoc_reg_def_id = try_future!(validate(RevocationRegistryId(revoc_reg_def_id.to_string())));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetRevocRegDeltaRequest(submitter_did, revoc_reg_def_id, from, to, cb)))
+}
+
+/// Parses a GET_REVOC_REG_DELTA response. See `indy_parse_get_revoc_reg_delta_response`.
+///
+/// Resolves to the revocation registry definition id, the revocation registry delta json and its timestamp.
+pub fn parse_get_revoc_reg_delta_response(get_revoc_reg_delta_response: &str) -> impl Future<Output=IndyResult<(String, String, u64)>> {
+    let get_revoc_reg_delta_response = get_revoc_reg_delta_response.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::ParseGetRevocRegDeltaResponse(get_revoc_reg_delta_response, cb)))
+}
+
+/// Parse transaction response to fetch metadata. See `indy_get_response_metadata`.
+pub fn get_response_metadata(response: &str) -> impl Future<Output=IndyResult<String>> {
+    let response = response.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::GetResponseMetadata(response, cb)))
+}
+
+/// Builds an AUTH_RULE request. See `indy_build_auth_rule_request`.
+pub fn build_auth_rule_request(submitter_did: &str,
+                               txn_type: &str,
+                               action: &str,
+                               field: &str,
+                               old_value: Option<&str>,
+                               new_value: Option<&str>,
+                               constraint: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let constraint: Constraint = try_future!(parse_json(constraint, "Constraint"));
+    let (txn_type, action, field) = (txn_type.to_string(), action.to_string(), field.to_string());
+    let (old_value, new_value) = (old_value.map(String::from), new_value.map(String::from));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildAuthRuleRequest(submitter_did, txn_type, action, field, old_value, new_value, constraint, cb)))
+}
+
+/// Builds an AUTH_RULES request. See `indy_build_auth_rules_request`.
+pub fn build_auth_rules_request(submitter_did: &str, rules: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let rules: AuthRules = try_future!(parse_json(rules, "AuthRules"));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildAuthRulesRequest(submitter_did, rules, cb)))
+}
+
+/// Builds a GET_AUTH_RULE request. See `indy_build_get_auth_rule_request`.
+pub fn build_get_auth_rule_request(submitter_did: Option<&str>,
+                                   txn_type: Option<&str>,
+                                   action: Option<&str>,
+                                   field: Option<&str>,
+                                   old_value: Option<&str>,
+                                   new_value: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let (txn_type, action, field) = (txn_type.map(String::from), action.map(String::from), field.map(String::from));
+    let (old_value, new_value) = (old_value.map(String::from), new_value.map(String::from));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetAuthRuleRequest(submitter_did, txn_type, action, field, old_value, new_value, cb)))
+}
+
+/// Builds a TXN_AUTHR_AGRMT request. See `indy_build_txn_author_agreement_request`.
+pub fn build_txn_author_agreement_request(submitter_did: &str,
+                                          text: Option<&str>,
+                                          version: &str,
+                                          ratification_ts: Option<u64>,
+                                          retirement_ts: Option<u64>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let text = text.map(String::from);
+    let version = version.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildTxnAuthorAgreementRequest(submitter_did, text, version, ratification_ts, retirement_ts, cb)))
+}
+
+/// Builds a DISABLE_ALL_TXN_AUTHR_AGRMTS request. See `indy_build_disable_all_txn_author_agreements_request`.
+pub fn build_disable_all_txn_author_agreements_request(submitter_did: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildDisableAllTxnAuthorAgreementsRequest(submitter_did, cb)))
+}
+
+/// Builds a GET_TXN_AUTHR_AGRMT request. See `indy_build_get_txn_author_agreement_request`.
+pub fn build_get_txn_author_agreement_request(submitter_did: Option<&str>, data: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let data: Option<GetTxnAuthorAgreementData> = try_future!(parse_opt_validatable_json(data, "GetTxnAuthorAgreementData"));
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetTxnAuthorAgreementRequest(submitter_did, data, cb)))
+}
+
+/// Builds a SET_TXN_AUTHR_AGRMT_AML request. See `indy_build_acceptance_mechanisms_request`.
+pub fn build_acceptance_mechanisms_request(submitter_did: &str, aml: &str, version: &str, aml_context: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(did(submitter_did));
+    let aml: AcceptanceMechanisms = try_future!(parse_validatable_json(aml, "AcceptanceMechanisms"));
+    let version = version.to_string();
+    let aml_context = aml_context.map(String::from);
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildAcceptanceMechanismRequests(submitter_did, aml, version, aml_context, cb)))
+}
+
+/// Builds a GET_TXN_AUTHR_AGRMT_AML request. See `indy_build_get_acceptance_mechanisms_request`.
+pub fn build_get_acceptance_mechanisms_request(submitter_did: Option<&str>, timestamp: Option<u64>, version: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let version = version.map(String::from);
+
+    send(|cb| Command::Ledger(LedgerCommand::BuildGetAcceptanceMechanismsRequest(submitter_did, timestamp, version, cb)))
+}
+
+/// Gets the latest Transaction Author Agreement and Acceptance Mechanisms. See `indy_get_active_txn_author_agreement`.
+pub fn get_active_txn_author_agreement(pool_handle: PoolHandle, accepted_taa_digest: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let accepted_taa_digest = accepted_taa_digest.map(String::from);
+
+    send(|cb| Command::Ledger(LedgerCommand::GetActiveTxnAuthorAgreement(pool_handle, accepted_taa_digest, cb)))
+}
+
+/// Checks whether Credential Definition on the ledger can be used by the local anoncreds. See `indy_check_cred_def_compatibility`.
+pub fn check_cred_def_compatibility(pool_handle: PoolHandle, cred_def_id: &str) -> impl Future<Output=IndyResult<String>> {
+    let cred_def_id = try_future!(validate(CredentialDefinitionId(cred_def_id.to_string())));
+
+    send(|cb| Command::Ledger(LedgerCommand::CheckCredDefCompatibility(pool_handle, cred_def_id, cb)))
+}
+
+/// Builds receipt of the written transaction from the write reply. See `indy_get_write_receipt`.
+pub fn get_write_receipt(response: &str) -> impl Future<Output=IndyResult<String>> {
+    let response = response.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::GetWriteReceipt(response, cb)))
+}
+
+/// Verifies receipt of the written transaction against the nodes multi-signature. See `indy_verify_write_receipt`.
+pub fn verify_write_receipt(pool_handle: PoolHandle, receipt_json: &str) -> impl Future<Output=IndyResult<bool>> {
+    let receipt: WriteReceipt = try_future!(parse_json(receipt_json, "WriteReceipt"));
+
+    send(|cb| Command::Ledger(LedgerCommand::VerifyWriteReceipt(pool_handle, receipt, cb)))
+}
+
+/// Append transaction author agreement acceptance data to a request. See `indy_append_txn_author_agreement_acceptance_to_request`.
+pub fn append_txn_author_agreement_acceptance_to_request(request_json: &str,
+                                                         text: Option<&str>,
+                                                         version: Option<&str>,
+                                                         taa_digest: Option<&str>,
+                                                         mechanism: &str,
+                                                         time: u64) -> impl Future<Output=IndyResult<String>> {
+    let request_json = request_json.to_string();
+    let (text, version, taa_digest) = (text.map(String::from), version.map(String::from), taa_digest.map(String::from));
+    let mechanism = mechanism.to_string();
+
+    send(|cb| Command::Ledger(LedgerCommand::AppendTxnAuthorAgreementAcceptanceToRequest(request_json, text, version, taa_digest, mechanism, time, cb)))
+}
+
+/// Append Endorser to an existing request. See `indy_append_request_endorser`.
+pub fn append_request_endorser(request_json: &str, endorser_did: &str) -> impl Future<Output=IndyResult<String>> {
+    let request_json = request_json.to_string();
+    let endorser_did = try_future!(did(endorser_did));
+
+    send(|cb| Command::Ledger(LedgerCommand::AppendRequestEndorser(request_json, endorser_did, cb)))
+}
@@ -0,0 +1,182 @@
+//! Futures based counterpart of the C-callable `api` module.
+//!
+//! Every function here accepts the same JSON and string parameters as the matching
+//! `indy_*` call, sends the very same command to the `CommandExecutor` and returns
+//! a future that resolves once the executor invokes the command callback. This allows
+//! native Rust consumers to use `async`/`await` instead of hand-rolled callbacks.
+//!
+//! Calls that require C function pointers (custom wallet storages, transaction parsers
+//! and `register_payment_method`) are available through the `api` module only.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use indy_api_types::errors::prelude::*;
+use indy_api_types::validation::Validatable;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+use crate::commands::{Command, CommandExecutor};
+
+macro_rules! try_future {
+    ($expr:expr) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return $crate::api_async::CommandFuture::ready(Err(err))
+        }
+    }
+}
+
+pub mod anoncreds;
+pub mod blob_storage;
+pub mod cache;
+pub mod crypto;
+pub mod did;
+pub mod ledger;
+pub mod non_secrets;
+pub mod pairwise;
+pub mod payments;
+pub mod pool;
+pub mod wallet;
+
+struct CommandState<T> {
+    result: Option<IndyResult<T>>,
+    waker: Option<Waker>,
+}
+
+/// Future resolved by the callback of a command sent to the `CommandExecutor`.
+pub struct CommandFuture<T> {
+    state: Arc<Mutex<CommandState<T>>>,
+}
+
+impl<T> CommandFuture<T> {
+    fn ready(result: IndyResult<T>) -> CommandFuture<T> {
+        CommandFuture {
+            state: Arc::new(Mutex::new(CommandState { result: Some(result), waker: None }))
+        }
+    }
+}
+
+impl<T> Future for CommandFuture<T> {
+    type Output = IndyResult<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<IndyResult<T>> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn send<T, F>(build: F) -> CommandFuture<T>
+    where T: Send + 'static,
+          F: FnOnce(Box<dyn Fn(IndyResult<T>) + Send>) -> Command {
+    let state = Arc::new(Mutex::new(CommandState { result: None, waker: None }));
+
+    let cb_state = state.clone();
+    let cmd = build(Box::new(move |result| {
+        let mut state = cb_state.lock().unwrap();
+        state.result = Some(result);
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }));
+
+    if let Err(err) = CommandExecutor::instance().send(cmd) {
+        return CommandFuture::ready(Err(err));
+    }
+
+    CommandFuture { state }
+}
+
+fn parse_json<T: DeserializeOwned>(json: &str, name: &str) -> IndyResult<T> {
+    serde_json::from_str::<T>(json)
+        .to_indy(IndyErrorKind::InvalidStructure, format!("Invalid {} json has been passed", name))
+}
+
+fn parse_opt_json<T: DeserializeOwned>(json: Option<&str>, name: &str) -> IndyResult<Option<T>> {
+    json.map(|json| parse_json(json, name)).transpose()
+}
+
+fn validate<T: Validatable>(value: T) -> IndyResult<T> {
+    value.validate()
+        .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+    Ok(value)
+}
+
+fn parse_validatable_json<T: DeserializeOwned + Validatable>(json: &str, name: &str) -> IndyResult<T> {
+    validate(parse_json(json, name)?)
+}
+
+fn parse_opt_validatable_json<T: DeserializeOwned + Validatable>(json: Option<&str>, name: &str) -> IndyResult<Option<T>> {
+    json.map(|json| parse_validatable_json(json, name)).transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::task::{RawWaker, RawWakerVTable};
+    use std::thread;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        fn clone(data: *const ()) -> RawWaker {
+            let thread = unsafe { &*(data as *const thread::Thread) };
+            RawWaker::new(Box::into_raw(Box::new(thread.clone())) as *const (), &VTABLE)
+        }
+
+        fn wake(data: *const ()) {
+            let thread = unsafe { Box::from_raw(data as *mut thread::Thread) };
+            thread.unpark();
+        }
+
+        fn wake_by_ref(data: *const ()) {
+            unsafe { &*(data as *const thread::Thread) }.unpark();
+        }
+
+        fn drop(data: *const ()) {
+            unsafe { std::mem::drop(Box::from_raw(data as *mut thread::Thread)) };
+        }
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+        let waker = unsafe {
+            Waker::from_raw(RawWaker::new(Box::into_raw(Box::new(thread::current())) as *const (), &VTABLE))
+        };
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(result) => return result,
+                Poll::Pending => thread::park()
+            }
+        }
+    }
+
+    #[test]
+    fn command_future_resolves_from_command_callback() {
+        let nonce = block_on(anoncreds::generate_nonce()).unwrap();
+        assert!(!nonce.is_empty());
+    }
+
+    #[test]
+    fn command_future_resolves_parsing_error_immediately() {
+        let err = block_on(wallet::create_wallet("not a json", r#"{"key":"key"}"#)).unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+    }
+
+    #[test]
+    fn payments_future_resolves_parsing_error_immediately() {
+        let err = block_on(payments::get_request_info("{}", "not a json", "{}")).unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+    }
+}
@@ -0,0 +1,94 @@
+use std::future::Future;
+
+use indy_api_types::{SearchHandle, WalletHandle};
+use indy_api_types::domain::wallet::{BatchRecord, Tags};
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::{parse_json, parse_opt_json, send};
+use crate::commands::Command;
+use crate::commands::non_secrets::NonSecretsCommand;
+
+/// Creates a new secure wallet record. See `indy_add_wallet_record`.
+pub fn add_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str, value: &str, tags_json: Option<&str>) -> impl Future<Output=IndyResult<()>> {
+    let tags: Option<Tags> = try_future!(parse_opt_json(tags_json, "Tags"));
+    let (type_, id, value) = (type_.to_string(), id.to_string(), value.to_string());
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::AddRecord(wallet_handle, type_, id, value, tags, cb)))
+}
+
+/// Updates a non-secret wallet record value. See `indy_update_wallet_record_value`.
+pub fn update_wallet_record_value(wallet_handle: WalletHandle, type_: &str, id: &str, value: &str) -> impl Future<Output=IndyResult<()>> {
+    let (type_, id, value) = (type_.to_string(), id.to_string(), value.to_string());
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::UpdateRecordValue(wallet_handle, type_, id, value, cb)))
+}
+
+/// Updates a non-secret wallet record tags. See `indy_update_wallet_record_tags`.
+pub fn update_wallet_record_tags(wallet_handle: WalletHandle, type_: &str, id: &str, tags_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let tags: Tags = try_future!(parse_json(tags_json, "Tags"));
+    let (type_, id) = (type_.to_string(), id.to_string());
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::UpdateRecordTags(wallet_handle, type_, id, tags, cb)))
+}
+
+/// Adds new tags to the wallet record. See `indy_add_wallet_record_tags`.
+pub fn add_wallet_record_tags(wallet_handle: WalletHandle, type_: &str, id: &str, tags_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let tags: Tags = try_future!(parse_json(tags_json, "Tags"));
+    let (type_, id) = (type_.to_string(), id.to_string());
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::AddRecordTags(wallet_handle, type_, id, tags, cb)))
+}
+
+/// Deletes tags from the wallet record. See `indy_delete_wallet_record_tags`.
+pub fn delete_wallet_record_tags(wallet_handle: WalletHandle, type_: &str, id: &str, tag_names_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let (type_, id, tag_names_json) = (type_.to_string(), id.to_string(), tag_names_json.to_string());
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::DeleteRecordTags(wallet_handle, type_, id, tag_names_json, cb)))
+}
+
+/// Deletes an existing wallet record in the wallet. See `indy_delete_wallet_record`.
+pub fn delete_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str) -> impl Future<Output=IndyResult<()>> {
+    let (type_, id) = (type_.to_string(), id.to_string());
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::DeleteRecord(wallet_handle, type_, id, cb)))
+}
+
+/// Creates many wallet records of the same type at once. See `indy_add_wallet_records_batch`.
+pub fn add_wallet_records_batch(wallet_handle: WalletHandle, type_: &str, records_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let records: Vec<BatchRecord> = try_future!(parse_json(records_json, "Batch records"));
+    let type_ = type_.to_string();
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::AddRecordsBatch(wallet_handle, type_, records, cb)))
+}
+
+/// Deletes many wallet records of the same type at once. See `indy_delete_wallet_records_batch`.
+pub fn delete_wallet_records_batch(wallet_handle: WalletHandle, type_: &str, ids_json: &str) -> impl Future<Output=IndyResult<()>> {
+    let ids: Vec<String> = try_future!(parse_json(ids_json, "Record ids"));
+    let type_ = type_.to_string();
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::DeleteRecordsBatch(wallet_handle, type_, ids, cb)))
+}
+
+/// Gets a wallet record by id. See `indy_get_wallet_record`.
+pub fn get_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str, options_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let (type_, id, options_json) = (type_.to_string(), id.to_string(), options_json.to_string());
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::GetRecord(wallet_handle, type_, id, options_json, cb)))
+}
+
+/// Searches for wallet records. See `indy_open_wallet_search`.
+pub fn open_wallet_search(wallet_handle: WalletHandle, type_: &str, query_json: &str, options_json: &str) -> impl Future<Output=IndyResult<SearchHandle>> {
+    let (type_, query_json, options_json) = (type_.to_string(), query_json.to_string(), options_json.to_string());
+
+    send(|cb| Command::NonSecrets(NonSecretsCommand::OpenSearch(wallet_handle, type_, query_json, options_json, cb)))
+}
+
+/// Fetches next records for the wallet search. See `indy_fetch_wallet_search_next_records`.
+pub fn fetch_wallet_search_next_records(wallet_handle: WalletHandle, wallet_search_handle: SearchHandle, count: usize) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::NonSecrets(NonSecretsCommand::FetchSearchNextRecords(wallet_handle, wallet_search_handle, count, cb)))
+}
+
+/// Closes the wallet search. See `indy_close_wallet_search`.
+pub fn close_wallet_search(wallet_search_handle: SearchHandle) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| Command::NonSecrets(NonSecretsCommand::CloseSearch(wallet_search_handle, cb)))
+}
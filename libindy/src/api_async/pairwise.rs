@@ -0,0 +1,45 @@
+use std::future::Future;
+
+use indy_api_types::WalletHandle;
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::{send, validate};
+use crate::commands::Command;
+use crate::commands::pairwise::PairwiseCommand;
+use crate::domain::crypto::did::DidValue;
+
+/// Checks if pairwise is exists. See `indy_is_pairwise_exists`.
+pub fn is_pairwise_exists(wallet_handle: WalletHandle, their_did: &str) -> impl Future<Output=IndyResult<bool>> {
+    let their_did = try_future!(validate(DidValue(their_did.to_string())));
+
+    send(|cb| Command::Pairwise(PairwiseCommand::PairwiseExists(wallet_handle, their_did, cb)))
+}
+
+/// Creates pairwise. See `indy_create_pairwise`.
+pub fn create_pairwise(wallet_handle: WalletHandle, their_did: &str, my_did: &str, metadata: Option<&str>) -> impl Future<Output=IndyResult<()>> {
+    let their_did = try_future!(validate(DidValue(their_did.to_string())));
+    let my_did = try_future!(validate(DidValue(my_did.to_string())));
+    let metadata = metadata.map(String::from);
+
+    send(|cb| Command::Pairwise(PairwiseCommand::CreatePairwise(wallet_handle, their_did, my_did, metadata, cb)))
+}
+
+/// Gets list of saved pairwise. See `indy_list_pairwise`.
+pub fn list_pairwise(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Pairwise(PairwiseCommand::ListPairwise(wallet_handle, cb)))
+}
+
+/// Gets pairwise information for specific their_did. See `indy_get_pairwise`.
+pub fn get_pairwise(wallet_handle: WalletHandle, their_did: &str) -> impl Future<Output=IndyResult<String>> {
+    let their_did = try_future!(validate(DidValue(their_did.to_string())));
+
+    send(|cb| Command::Pairwise(PairwiseCommand::GetPairwise(wallet_handle, their_did, cb)))
+}
+
+/// Save some data in the Wallet for pairwise associated with Did. See `indy_set_pairwise_metadata`.
+pub fn set_pairwise_metadata(wallet_handle: WalletHandle, their_did: &str, metadata: Option<&str>) -> impl Future<Output=IndyResult<()>> {
+    let their_did = try_future!(validate(DidValue(their_did.to_string())));
+    let metadata = metadata.map(String::from);
+
+    send(|cb| Command::Pairwise(PairwiseCommand::SetPairwiseMetadata(wallet_handle, their_did, metadata, cb)))
+}
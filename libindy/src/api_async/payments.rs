@@ -0,0 +1,163 @@
+use std::future::Future;
+
+use indy_api_types::WalletHandle;
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::{parse_json, send, validate};
+use crate::commands::Command;
+use crate::commands::payments::PaymentsCommand;
+use crate::domain::crypto::did::DidValue;
+use crate::services::payments::{Fees, RequesterInfo};
+
+fn opt_did(did: Option<&str>) -> IndyResult<Option<DidValue>> {
+    did.map(|did| validate(DidValue(did.to_string()))).transpose()
+}
+
+/// Create the payment address for specified payment method. See `indy_create_payment_address`.
+pub fn create_payment_address(wallet_handle: WalletHandle, payment_method: &str, config: &str) -> impl Future<Output=IndyResult<String>> {
+    let (payment_method, config) = (payment_method.to_string(), config.to_string());
+
+    send(|cb| Command::Payments(PaymentsCommand::CreateAddress(wallet_handle, payment_method, config, cb)))
+}
+
+/// Lists all payment addresses that are stored in the wallet. See `indy_list_payment_addresses`.
+pub fn list_payment_addresses(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Payments(PaymentsCommand::ListAddresses(wallet_handle, cb)))
+}
+
+/// Modifies Indy request by adding information how to pay fees for this transaction. See `indy_add_request_fees`.
+///
+/// Resolves to the tuple of request with fees and used payment method.
+pub fn add_request_fees(wallet_handle: WalletHandle, submitter_did: Option<&str>, req_json: &str, inputs_json: &str, outputs_json: &str, extra: Option<&str>) -> impl Future<Output=IndyResult<(String, String)>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let (req_json, inputs_json, outputs_json) = (req_json.to_string(), inputs_json.to_string(), outputs_json.to_string());
+    let extra = extra.map(String::from);
+
+    send(|cb| Command::Payments(PaymentsCommand::AddRequestFees(wallet_handle, submitter_did, req_json, inputs_json, outputs_json, extra, cb)))
+}
+
+/// Parses response for Indy request with fees. See `indy_parse_response_with_fees`.
+pub fn parse_response_with_fees(payment_method: &str, resp_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let (payment_method, resp_json) = (payment_method.to_string(), resp_json.to_string());
+
+    send(|cb| Command::Payments(PaymentsCommand::ParseResponseWithFees(payment_method, resp_json, cb)))
+}
+
+/// Builds Indy request for getting sources list for payment address. See `indy_build_get_payment_sources_with_from_request`.
+///
+/// Resolves to the tuple of request and used payment method.
+pub fn build_get_payment_sources_request(wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_address: &str, from: Option<i64>) -> impl Future<Output=IndyResult<(String, String)>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let payment_address = payment_address.to_string();
+
+    send(|cb| Command::Payments(PaymentsCommand::BuildGetPaymentSourcesRequest(wallet_handle, submitter_did, payment_address, from, cb)))
+}
+
+/// Parses response for Indy request for getting sources list. See `indy_parse_get_payment_sources_with_from_response`.
+///
+/// Resolves to the tuple of sources and pointer to the next slice of sources (-1 if there is none).
+pub fn parse_get_payment_sources_response(payment_method: &str, resp_json: &str) -> impl Future<Output=IndyResult<(String, i64)>> {
+    let (payment_method, resp_json) = (payment_method.to_string(), resp_json.to_string());
+
+    send(|cb| Command::Payments(PaymentsCommand::ParseGetPaymentSourcesResponse(payment_method, resp_json, cb)))
+}
+
+/// Builds Indy request for doing payment according to this payment method. See `indy_build_payment_req`.
+///
+/// Resolves to the tuple of request and used payment method.
+pub fn build_payment_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, inputs_json: &str, outputs_json: &str, extra: Option<&str>) -> impl Future<Output=IndyResult<(String, String)>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let (inputs_json, outputs_json) = (inputs_json.to_string(), outputs_json.to_string());
+    let extra = extra.map(String::from);
+
+    send(|cb| Command::Payments(PaymentsCommand::BuildPaymentReq(wallet_handle, submitter_did, inputs_json, outputs_json, extra, cb)))
+}
+
+/// Parses response for Indy request for payment txn. See `indy_parse_payment_response`.
+pub fn parse_payment_response(payment_method: &str, resp_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let (payment_method, resp_json) = (payment_method.to_string(), resp_json.to_string());
+
+    send(|cb| Command::Payments(PaymentsCommand::ParsePaymentResponse(payment_method, resp_json, cb)))
+}
+
+/// Append payment extra JSON with TAA acceptance data. See `indy_prepare_payment_extra_with_acceptance_data`.
+pub fn prepare_payment_extra_with_acceptance_data(extra_json: Option<&str>, text: Option<&str>, version: Option<&str>, taa_digest: Option<&str>, mechanism: &str, time: u64) -> impl Future<Output=IndyResult<String>> {
+    let (extra_json, text, version, taa_digest) = (extra_json.map(String::from), text.map(String::from), version.map(String::from), taa_digest.map(String::from));
+    let mechanism = mechanism.to_string();
+
+    send(|cb| Command::Payments(PaymentsCommand::AppendTxnAuthorAgreementAcceptanceToExtra(extra_json, text, version, taa_digest, mechanism, time, cb)))
+}
+
+/// Builds Indy request for doing minting according to this payment method. See `indy_build_mint_req`.
+///
+/// Resolves to the tuple of request and used payment method.
+pub fn build_mint_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, outputs_json: &str, extra: Option<&str>) -> impl Future<Output=IndyResult<(String, String)>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let outputs_json = outputs_json.to_string();
+    let extra = extra.map(String::from);
+
+    send(|cb| Command::Payments(PaymentsCommand::BuildMintReq(wallet_handle, submitter_did, outputs_json, extra, cb)))
+}
+
+/// Builds Indy request for setting fees for transactions in the ledger. See `indy_build_set_txn_fees_req`.
+pub fn build_set_txn_fees_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_method: &str, fees_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let (payment_method, fees_json) = (payment_method.to_string(), fees_json.to_string());
+
+    send(|cb| Command::Payments(PaymentsCommand::BuildSetTxnFeesReq(wallet_handle, submitter_did, payment_method, fees_json, cb)))
+}
+
+/// Builds Indy get request for getting fees for transactions in the ledger. See `indy_build_get_txn_fees_req`.
+pub fn build_get_txn_fees_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_method: &str) -> impl Future<Output=IndyResult<String>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let payment_method = payment_method.to_string();
+
+    send(|cb| Command::Payments(PaymentsCommand::BuildGetTxnFeesReq(wallet_handle, submitter_did, payment_method, cb)))
+}
+
+/// Parses response for Indy request for getting fees. See `indy_parse_get_txn_fees_response`.
+pub fn parse_get_txn_fees_response(payment_method: &str, resp_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let (payment_method, resp_json) = (payment_method.to_string(), resp_json.to_string());
+
+    send(|cb| Command::Payments(PaymentsCommand::ParseGetTxnFeesResponse(payment_method, resp_json, cb)))
+}
+
+/// Builds Indy request for information to verify the payment receipt. See `indy_build_verify_payment_req`.
+///
+/// Resolves to the tuple of request and used payment method.
+pub fn build_verify_payment_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, receipt: &str) -> impl Future<Output=IndyResult<(String, String)>> {
+    let submitter_did = try_future!(opt_did(submitter_did));
+    let receipt = receipt.to_string();
+
+    send(|cb| Command::Payments(PaymentsCommand::BuildVerifyPaymentReq(wallet_handle, submitter_did, receipt, cb)))
+}
+
+/// Parses Indy response with information to verify receipt. See `indy_parse_verify_payment_response`.
+pub fn parse_verify_payment_response(payment_method: &str, resp_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let (payment_method, resp_json) = (payment_method.to_string(), resp_json.to_string());
+
+    send(|cb| Command::Payments(PaymentsCommand::ParseVerifyPaymentResponse(payment_method, resp_json, cb)))
+}
+
+/// Gets request requirements (with minimal price) correspondent to specific auth rule. See `indy_get_request_info`.
+pub fn get_request_info(get_auth_rule_response_json: &str, requester_info_json: &str, fees_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let requester_info: RequesterInfo = try_future!(parse_json(requester_info_json, "RequesterInfo"));
+    let fees: Fees = try_future!(parse_json(fees_json, "Fees"));
+    let get_auth_rule_response_json = get_auth_rule_response_json.to_string();
+
+    send(|cb| Command::Payments(PaymentsCommand::GetRequestInfo(get_auth_rule_response_json, requester_info, fees, cb)))
+}
+
+/// Signs a message with a payment address. See `indy_sign_with_address`.
+pub fn sign_with_address(wallet_handle: WalletHandle, address: &str, message: &[u8]) -> impl Future<Output=IndyResult<Vec<u8>>> {
+    let (address, message) = (address.to_string(), message.to_vec());
+
+    send(|cb| Command::Payments(PaymentsCommand::SignWithAddressReq(wallet_handle, address, message, cb)))
+}
+
+/// Verify a signature with a payment address. See `indy_verify_with_address`.
+pub fn verify_with_address(address: &str, message: &[u8], signature: &[u8]) -> impl Future<Output=IndyResult<bool>> {
+    let (address, message, signature) = (address.to_string(), message.to_vec(), signature.to_vec());
+
+    send(|cb| Command::Payments(PaymentsCommand::VerifyWithAddressReq(address, message, signature, cb)))
+}
@@ -0,0 +1,80 @@
+use std::future::Future;
+
+use indy_api_types::PoolHandle;
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::{parse_opt_json, parse_opt_validatable_json, parse_validatable_json, send};
+use crate::commands::Command;
+use crate::commands::pool::PoolCommand;
+use crate::domain::pool::{GenesisNodes, PoolConfig, PoolOpenConfig, TrafficCaptureConfig};
+
+/// Creates a new local pool ledger configuration. See `indy_create_pool_ledger_config`.
+pub fn create_pool_ledger_config(config_name: &str, config: Option<&str>) -> impl Future<Output=IndyResult<()>> {
+    let config: Option<PoolConfig> = try_future!(parse_opt_json(config, "PoolConfig"));
+    let config_name = config_name.to_string();
+
+    send(|cb| Command::Pool(PoolCommand::Create(config_name, config, cb)))
+}
+
+/// Opens pool ledger and performs connecting to pool nodes. See `indy_open_pool_ledger`.
+pub fn open_pool_ledger(config_name: &str, config: Option<&str>) -> impl Future<Output=IndyResult<PoolHandle>> {
+    let config: Option<PoolOpenConfig> = try_future!(parse_opt_validatable_json(config, "PoolOpenConfig"));
+    let config_name = config_name.to_string();
+
+    send(|cb| Command::Pool(PoolCommand::Open(config_name, config, cb)))
+}
+
+/// Refreshes a local copy of a pool ledger and updates pool nodes connections. See `indy_refresh_pool_ledger`.
+pub fn refresh_pool_ledger(pool_handle: PoolHandle) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| Command::Pool(PoolCommand::Refresh(pool_handle, cb)))
+}
+
+/// Lists names of created pool ledgers. See `indy_list_pools`.
+pub fn list_pools() -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Pool(PoolCommand::List(cb)))
+}
+
+/// Closes opened pool ledger, opened nodes connections and frees allocated resources. See `indy_close_pool_ledger`.
+pub fn close_pool_ledger(pool_handle: PoolHandle) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| Command::Pool(PoolCommand::Close(pool_handle, cb)))
+}
+
+/// Deletes created pool ledger configuration. See `indy_delete_pool_ledger_config`.
+pub fn delete_pool_ledger_config(config_name: &str) -> impl Future<Output=IndyResult<()>> {
+    let config_name = config_name.to_string();
+
+    send(|cb| Command::Pool(PoolCommand::Delete(config_name, cb)))
+}
+
+/// Sets PROTOCOL_VERSION to specific value. See `indy_set_protocol_version`.
+pub fn set_protocol_version(protocol_version: usize) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| Command::Pool(PoolCommand::SetProtocolVersion(protocol_version, cb)))
+}
+
+/// Builds genesis transactions of the pool ledger from the list of nodes. See `indy_build_pool_genesis_txns`.
+pub fn build_pool_genesis_txns(nodes_json: &str) -> impl Future<Output=IndyResult<String>> {
+    let nodes: GenesisNodes = try_future!(parse_validatable_json(nodes_json, "GenesisNodes"));
+
+    send(|cb| Command::Pool(PoolCommand::BuildGenesisTxns(nodes, cb)))
+}
+
+/// Enables or disables capturing of raw pool traffic. See `indy_set_pool_traffic_capture`.
+pub fn set_pool_traffic_capture(pool_handle: PoolHandle, config: Option<&str>) -> impl Future<Output=IndyResult<()>> {
+    let config: Option<TrafficCaptureConfig> = try_future!(parse_opt_validatable_json(config, "TrafficCaptureConfig"));
+
+    send(|cb| Command::Pool(PoolCommand::SetTrafficCapture(pool_handle, config, cb)))
+}
+
+/// Routes ledger operations on identifiers of the DID namespace to the pool. See `indy_set_pool_for_namespace`.
+pub fn set_pool_for_namespace(namespace: &str, pool_handle: PoolHandle) -> impl Future<Output=IndyResult<()>> {
+    let namespace = namespace.to_string();
+
+    send(|cb| Command::Pool(PoolCommand::SetNamespacePool(namespace, pool_handle, cb)))
+}
+
+/// Removes routing of the DID namespace. See `indy_remove_pool_for_namespace`.
+pub fn remove_pool_for_namespace(namespace: &str) -> impl Future<Output=IndyResult<()>> {
+    let namespace = namespace.to_string();
+
+    send(|cb| Command::Pool(PoolCommand::RemoveNamespacePool(namespace, cb)))
+}
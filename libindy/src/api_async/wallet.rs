@@ -0,0 +1,88 @@
+use std::future::Future;
+
+use indy_api_types::WalletHandle;
+use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig, StorageEncryptionCheckConfig};
+use indy_api_types::errors::prelude::*;
+
+use crate::api_async::{parse_json, parse_opt_json, parse_validatable_json, send};
+use crate::commands::Command;
+use crate::commands::wallet::WalletCommand;
+
+/// Creates a new secure wallet with the given unique name. See `indy_create_wallet`.
+pub fn create_wallet(config: &str, credentials: &str) -> impl Future<Output=IndyResult<()>> {
+    let config: Config = try_future!(parse_validatable_json(config, "Config"));
+    let credentials: Credentials = try_future!(parse_json(credentials, "Credentials"));
+
+    send(|cb| Command::Wallet(WalletCommand::Create(config, credentials, cb)))
+}
+
+/// Opens the wallet with specific name. See `indy_open_wallet`.
+pub fn open_wallet(config: &str, credentials: &str) -> impl Future<Output=IndyResult<WalletHandle>> {
+    let config: Config = try_future!(parse_validatable_json(config, "Config"));
+    let credentials: Credentials = try_future!(parse_json(credentials, "Credentials"));
+
+    send(|cb| Command::Wallet(WalletCommand::Open(config, credentials, cb)))
+}
+
+/// Exports opened wallet. See `indy_export_wallet`.
+pub fn export_wallet(wallet_handle: WalletHandle, export_config: &str) -> impl Future<Output=IndyResult<()>> {
+    let export_config: ExportConfig = try_future!(parse_json(export_config, "ExportConfig"));
+
+    send(|cb| Command::Wallet(WalletCommand::Export(wallet_handle, export_config, cb)))
+}
+
+/// Creates a new secure wallet and then imports its content from the file created by `export_wallet`. See `indy_import_wallet`.
+pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> impl Future<Output=IndyResult<()>> {
+    let config: Config = try_future!(parse_validatable_json(config, "Config"));
+    let credentials: Credentials = try_future!(parse_json(credentials, "Credentials"));
+    let import_config: ExportConfig = try_future!(parse_json(import_config, "ExportConfig"));
+
+    send(|cb| Command::Wallet(WalletCommand::Import(config, credentials, import_config, cb)))
+}
+
+/// Closes opened wallet and frees allocated resources. See `indy_close_wallet`.
+pub fn close_wallet(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<()>> {
+    send(|cb| Command::Wallet(WalletCommand::Close(wallet_handle, cb)))
+}
+
+/// Deletes created wallet. See `indy_delete_wallet`.
+pub fn delete_wallet(config: &str, credentials: &str) -> impl Future<Output=IndyResult<()>> {
+    let config: Config = try_future!(parse_validatable_json(config, "Config"));
+    let credentials: Credentials = try_future!(parse_json(credentials, "Credentials"));
+
+    send(|cb| Command::Wallet(WalletCommand::Delete(config, credentials, cb)))
+}
+
+/// Generates wallet master key. See `indy_generate_wallet_key`.
+pub fn generate_wallet_key(config: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let config: Option<KeyConfig> = try_future!(parse_opt_json(config, "KeyConfig"));
+
+    send(|cb| Command::Wallet(WalletCommand::GenerateKey(config, cb)))
+}
+
+/// Verifies that records of opened wallet are encrypted at rest. See `indy_verify_wallet_storage_encryption`.
+pub fn verify_wallet_storage_encryption(wallet_handle: WalletHandle, config: Option<&str>) -> impl Future<Output=IndyResult<String>> {
+    let config: Option<StorageEncryptionCheckConfig> = try_future!(parse_opt_json(config, "StorageEncryptionCheckConfig"));
+
+    send(|cb| Command::Wallet(WalletCommand::VerifyStorageEncryption(wallet_handle, config, cb)))
+}
+
+/// Returns the latest slow storage operations of opened wallet. See `indy_get_wallet_slow_query_log`.
+pub fn get_wallet_slow_query_log(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Wallet(WalletCommand::GetSlowQueryLog(wallet_handle, cb)))
+}
+
+/// Compacts the storage of opened wallet. See `indy_compact_wallet`.
+pub fn compact_wallet(wallet_handle: WalletHandle) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Wallet(WalletCommand::Compact(wallet_handle, cb)))
+}
+
+/// Lists wallet objects which expire within the given time window. See `indy_list_expiring_wallet_objects`.
+pub fn list_expiring_wallet_objects(wallet_handle: WalletHandle, within_secs: u64) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Wallet(WalletCommand::ListExpiringObjects(wallet_handle, within_secs, cb)))
+}
+
+/// Calculates limits of wallet key derivation suitable for the current hardware. See `indy_benchmark_wallet_key_derivation`.
+pub fn benchmark_wallet_key_derivation(target_ms: u64) -> impl Future<Output=IndyResult<String>> {
+    send(|cb| Command::Wallet(WalletCommand::BenchmarkKeyDerivation(target_ms, cb)))
+}
@@ -1,9 +1,13 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use rust_base58::{FromBase58, ToBase58};
 use ursa::cl::{
     new_nonce,
+    RevocationKeyPrivate,
+    RevocationRegistry as CryptoRevocationRegistry,
     RevocationRegistryDelta as CryptoRevocationRegistryDelta,
     Witness,
 };
@@ -11,7 +15,8 @@ use ursa::cl::{CredentialKeyCorrectnessProof, CredentialPrivateKey};
 
 use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
 use crate::commands::anoncreds::AnoncredsCommand;
-use crate::domain::anoncreds::credential::{CredentialValues, Credential};
+use crate::commands::did::get_my_did_signing_key;
+use crate::domain::anoncreds::credential::{CredentialValues, Credential, CredentialIssuanceRequests, IssuedCredential};
 use crate::domain::anoncreds::credential_definition::{
     CredentialDefinition,
     CredentialDefinitionConfig,
@@ -23,8 +28,16 @@ use crate::domain::anoncreds::credential_definition::{
     TemporaryCredentialDefinition,
     CredentialDefinitionId
 };
-use crate::domain::anoncreds::credential_offer::CredentialOffer;
+use crate::domain::anoncreds::credential_offer::{CredentialOffer, CredentialOfferConfig, CredentialOfferInfo, DEFAULT_CREDENTIAL_OFFER_TTL};
 use crate::domain::anoncreds::credential_request::CredentialRequest;
+use crate::domain::anoncreds::issuance_log::{
+    IssuanceEvent,
+    IssuanceLog,
+    IssuanceLogEntry,
+    IssuanceLogHead,
+    ISSUANCE_LOG_GENESIS_HASH,
+    ISSUANCE_LOG_HEAD_ID,
+};
 use crate::domain::anoncreds::revocation_registry::{
     RevocationRegistry,
     RevocationRegistryV1,
@@ -37,8 +50,10 @@ use crate::domain::anoncreds::revocation_registry_definition::{
     RevocationRegistryDefinitionPrivate,
     RevocationRegistryDefinitionV1,
     RevocationRegistryDefinitionValue,
+    RevocationRegistryDefinitionValuePublicKeys,
     RevocationRegistryInfo,
-    RevocationRegistryId
+    RevocationRegistryId,
+    RevocationRegistryStatistics
 };
 use crate::domain::anoncreds::revocation_registry_delta::{
     RevocationRegistryDelta,
@@ -48,14 +63,15 @@ use crate::domain::anoncreds::schema::{AttributeNames, Schema, SchemaV1, SchemaI
 use crate::domain::crypto::did::DidValue;
 use indy_api_types::domain::wallet::Tags;
 use indy_api_types::errors::prelude::*;
+use indy_api_types::validation::Validatable;
 use crate::services::anoncreds::AnoncredsService;
-use crate::services::anoncreds::helpers::parse_cred_rev_id;
+use crate::services::anoncreds::helpers::{parse_cred_rev_id, strict_schema_attr_names};
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::pool::PoolService;
-use indy_wallet::{RecordOptions, WalletService};
+use indy_wallet::{RecordOptions, SearchOptions, WalletService};
 
-use super::tails::{SDKTailsAccessor, store_tails_from_generator};
+use super::tails::{SDKTailsAccessor, generate_tails, store_tails};
 use indy_api_types::{WalletHandle, CommandHandle};
 use indy_utils::next_command_handle;
 
@@ -66,6 +82,9 @@ pub enum IssuerCommand {
         String, // version
         AttributeNames, // attribute names
         BoxedCallbackStringStringSend),
+    ValidateSchemaAttributes(
+        AttributeNames, // attribute names
+        Box<dyn Fn(IndyResult<()>) + Send>),
     CreateAndStoreCredentialDefinition(
         WalletHandle,
         DidValue, // issuer did
@@ -113,10 +132,32 @@ pub enum IssuerCommand {
         RevocationRegistryConfig, // config
         i32, // tails writer handle
         Box<dyn Fn(IndyResult<(String, String, String)>) + Send>),
+    CreateAndStoreRevocationRegistryContinue(
+        WalletHandle,
+        RevocationRegistryId, // revocation registry id
+        RegistryType, // type
+        String, // tag
+        CredentialDefinitionId, // credential definition id
+        u32, // max credential num
+        IssuanceType, // issuance type
+        i32, // tails writer handle
+        IndyResult<(RevocationRegistryDefinitionValuePublicKeys,
+                    RevocationKeyPrivate,
+                    CryptoRevocationRegistry,
+                    Vec<u8>)>, // keys, registry and tails
+        CommandHandle),
     CreateCredentialOffer(
         WalletHandle,
         CredentialDefinitionId, // credential definition id
+        Option<CredentialOfferConfig>, // credential offer config
         Box<dyn Fn(IndyResult<String>) + Send>),
+    ListPendingOffers(
+        WalletHandle,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    RevokeOffer(
+        WalletHandle,
+        String, // credential offer nonce
+        Box<dyn Fn(IndyResult<()>) + Send>),
     CreateCredential(
         WalletHandle,
         CredentialOffer, // credential offer
@@ -125,12 +166,22 @@ pub enum IssuerCommand {
         Option<RevocationRegistryId>, // revocation registry id
         Option<i32>, // blob storage reader config handle
         Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>)>) + Send>),
+    CreateCredentials(
+        WalletHandle,
+        CredentialIssuanceRequests, // credential offers, requests and values
+        Option<RevocationRegistryId>, // revocation registry id
+        Option<i32>, // blob storage reader config handle
+        Box<dyn Fn(IndyResult<(String, Option<String>)>) + Send>),
     RevokeCredential(
         WalletHandle,
         i32, // blob storage reader config handle
         RevocationRegistryId, //revocation registry id
         String, //credential revoc id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRevocationRegistryInfo(
+        WalletHandle,
+        RevocationRegistryId, //revocation registry id
+        Box<dyn Fn(IndyResult<String>) + Send>),
     /*    RecoverCredential(
             WalletHandle,
             i32, // blob storage reader config handle
@@ -141,6 +192,33 @@ pub enum IssuerCommand {
         RevocationRegistryDelta, //revocation registry delta
         RevocationRegistryDelta, //other revocation registry delta
         Box<dyn Fn(IndyResult<String>) + Send>),
+    EnableIssuanceLog(
+        WalletHandle,
+        DidValue, // issuer did
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    ExportIssuanceLog(
+        WalletHandle,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerifyIssuanceLog(
+        IssuanceLog, // exported issuance log
+        String, // issuer verkey
+        Box<dyn Fn(IndyResult<bool>) + Send>),
+}
+
+// Revocation registry state advanced in memory while credentials are issued
+struct RevRegIssuanceState {
+    rev_reg_def: RevocationRegistryDefinitionV1,
+    rev_reg: RevocationRegistryV1,
+    rev_key_priv: RevocationRegistryDefinitionPrivate,
+    tails_accessor: SDKTailsAccessor,
+    rev_reg_info: RevocationRegistryInfo,
+}
+
+// Wallet changes of issued credentials that are stored once issuance succeeds
+#[derive(Default)]
+struct PendingIssuance {
+    used_offers: HashSet<String>,
+    events: Vec<IssuanceEvent>,
 }
 
 pub struct IssuerCommandExecutor {
@@ -151,6 +229,7 @@ pub struct IssuerCommandExecutor {
     pub crypto_service: Rc<CryptoService>,
     pending_str_str_callbacks: RefCell<HashMap<CommandHandle, BoxedCallbackStringStringSend>>,
     pending_str_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
+    pending_str_str_str_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String, String)>) + Send>>>,
 }
 
 impl IssuerCommandExecutor {
@@ -167,6 +246,7 @@ impl IssuerCommandExecutor {
             crypto_service,
             pending_str_str_callbacks: RefCell::new(HashMap::new()),
             pending_str_callbacks: RefCell::new(HashMap::new()),
+            pending_str_str_str_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -176,6 +256,10 @@ impl IssuerCommandExecutor {
                 debug!(target: "issuer_command_executor", "CreateSchema command received");
                 cb(self.create_schema(&issuer_did, &name, &version, attrs));
             }
+            IssuerCommand::ValidateSchemaAttributes(attrs, cb) => {
+                debug!(target: "issuer_command_executor", "ValidateSchemaAttributes command received");
+                cb(self.validate_schema_attributes(&attrs));
+            }
             IssuerCommand::CreateAndStoreCredentialDefinition(wallet_handle, issuer_did, schema, tag, type_, config, cb) => {
                 debug!(target: "issuer_command_executor", "CreateAndStoreCredentialDefinition command received");
                 self.create_and_store_credential_definition(wallet_handle, &issuer_did, &SchemaV1::from(schema), &tag,
@@ -200,26 +284,49 @@ impl IssuerCommandExecutor {
             IssuerCommand::CreateAndStoreRevocationRegistry(wallet_handle, issuer_did, type_, tag, cred_def_id, config,
                                                             tails_writer_handle, cb) => {
                 debug!(target: "issuer_command_executor", "CreateAndStoreRevocationRegistryRegistry command received");
-                cb(self.create_and_store_revocation_registry(wallet_handle,
-                                                             &issuer_did,
-                                                             type_.as_ref().map(String::as_str),
-                                                             &tag,
-                                                             &cred_def_id,
-                                                             &config,
-                                                             tails_writer_handle));
-            }
-            IssuerCommand::CreateCredentialOffer(wallet_handle, cred_def_id, cb) => {
+                self.create_and_store_revocation_registry(wallet_handle,
+                                                          &issuer_did,
+                                                          type_.as_ref().map(String::as_str),
+                                                          &tag,
+                                                          &cred_def_id,
+                                                          &config,
+                                                          tails_writer_handle,
+                                                          cb);
+            }
+            IssuerCommand::CreateAndStoreRevocationRegistryContinue(wallet_handle, rev_reg_id, rev_reg_type, tag, cred_def_id, max_cred_num,
+                                                                    issuance_type, tails_writer_handle, result, cb_id) => {
+                debug!(target: "issuer_command_executor", "CreateAndStoreRevocationRegistryContinue command received");
+                self._create_and_store_revocation_registry_continue(cb_id, wallet_handle, rev_reg_id, rev_reg_type, &tag, &cred_def_id,
+                                                                    max_cred_num, issuance_type, tails_writer_handle, result);
+            }
+            IssuerCommand::CreateCredentialOffer(wallet_handle, cred_def_id, config, cb) => {
                 debug!(target: "issuer_command_executor", "CreateCredentialOffer command received");
-                cb(self.create_credential_offer(wallet_handle, &cred_def_id));
+                cb(self.create_credential_offer(wallet_handle, &cred_def_id, config.as_ref()));
+            }
+            IssuerCommand::ListPendingOffers(wallet_handle, cb) => {
+                debug!(target: "issuer_command_executor", "ListPendingOffers command received");
+                cb(self.list_pending_offers(wallet_handle));
+            }
+            IssuerCommand::RevokeOffer(wallet_handle, nonce, cb) => {
+                debug!(target: "issuer_command_executor", "RevokeOffer command received");
+                cb(self.revoke_offer(wallet_handle, &nonce));
             }
             IssuerCommand::CreateCredential(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle, cb) => {
                 debug!(target: "issuer_command_executor", "CreateCredential command received");
                 cb(self.new_credential(wallet_handle, &cred_offer, &cred_req, &cred_values, rev_reg_id.as_ref(), blob_storage_reader_handle));
             }
+            IssuerCommand::CreateCredentials(wallet_handle, requests, rev_reg_id, blob_storage_reader_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentials command received");
+                cb(self.new_credentials(wallet_handle, &requests, rev_reg_id.as_ref(), blob_storage_reader_handle));
+            }
             IssuerCommand::RevokeCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                 debug!(target: "issuer_command_executor", "RevokeCredential command received");
                 cb(self.revoke_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
             }
+            IssuerCommand::GetRevocationRegistryInfo(wallet_handle, rev_reg_id, cb) => {
+                debug!(target: "issuer_command_executor", "GetRevocationRegistryInfo command received");
+                cb(self.get_revocation_registry_info(wallet_handle, &rev_reg_id));
+            }
             /*            IssuerCommand::RecoverCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                             debug!(target: "issuer_command_executor", "RecoverCredential command received");
                             cb(self.recovery_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
@@ -229,6 +336,18 @@ impl IssuerCommandExecutor {
                 cb(self.merge_revocation_registry_deltas(&mut RevocationRegistryDeltaV1::from(rev_reg_delta),
                                                          &RevocationRegistryDeltaV1::from(other_rev_reg_delta)));
             }
+            IssuerCommand::EnableIssuanceLog(wallet_handle, issuer_did, cb) => {
+                debug!(target: "issuer_command_executor", "EnableIssuanceLog command received");
+                cb(self.enable_issuance_log(wallet_handle, &issuer_did));
+            }
+            IssuerCommand::ExportIssuanceLog(wallet_handle, cb) => {
+                debug!(target: "issuer_command_executor", "ExportIssuanceLog command received");
+                cb(self.export_issuance_log(wallet_handle));
+            }
+            IssuerCommand::VerifyIssuanceLog(issuance_log, issuer_verkey, cb) => {
+                debug!(target: "issuer_command_executor", "VerifyIssuanceLog command received");
+                cb(self.verify_issuance_log(&issuance_log, &issuer_verkey));
+            }
         };
     }
 
@@ -241,6 +360,11 @@ impl IssuerCommandExecutor {
 
         self.crypto_service.validate_did(issuer_did)?;
 
+        if strict_schema_attr_names() {
+            attrs.validate_names()
+                .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+        }
+
         let schema_id = SchemaId::new(&issuer_did, name, version);
 
         let schema = Schema::SchemaV1(SchemaV1 {
@@ -259,6 +383,18 @@ impl IssuerCommandExecutor {
         Ok((schema_id.0, schema_json))
     }
 
+    fn validate_schema_attributes(&self, attrs: &AttributeNames) -> IndyResult<()> {
+        debug!("validate_schema_attributes >>> attrs: {:?}", attrs);
+
+        attrs.validate()
+            .and_then(|_| attrs.validate_names())
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        debug!("validate_schema_attributes <<<");
+
+        Ok(())
+    }
+
     fn create_and_store_credential_definition(&self,
                                               wallet_handle: WalletHandle,
                                               issuer_did: &DidValue,
@@ -548,23 +684,24 @@ impl IssuerCommandExecutor {
                                             tag: &str,
                                             cred_def_id: &CredentialDefinitionId,
                                             config: &RevocationRegistryConfig,
-                                            tails_writer_handle: i32) -> IndyResult<(String, String, String)> {
+                                            tails_writer_handle: i32,
+                                            cb: Box<dyn Fn(IndyResult<(String, String, String)>) + Send>) {
         debug!("create_and_store_revocation_registry >>> wallet_handle: {:?}, issuer_did: {:?}, type_: {:?}, tag: {:?}, cred_def_id: {:?}, config: {:?}, \
                tails_handle: {:?}", wallet_handle, issuer_did, type_, tag, cred_def_id, config, tails_writer_handle);
 
         match (issuer_did.get_method(), cred_def_id.get_method()) {
             (None, Some(_)) => {
-                return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "You can't use unqualified Did with fully qualified Credential Definition"));
+                return cb(Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "You can't use unqualified Did with fully qualified Credential Definition")));
             }
             (Some(_), None) => {
-                return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "You can't use fully qualified Did with unqualified Credential Definition"));
+                return cb(Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "You can't use fully qualified Did with unqualified Credential Definition")));
             }
             _ => {}
         };
 
         let rev_reg_type = if let Some(type_) = type_ {
-            serde_json::from_str::<RegistryType>(&format!("\"{}\"", type_))
-                .to_indy(IndyErrorKind::InvalidStructure, "Invalid Registry Type format")?
+            try_cb!(serde_json::from_str::<RegistryType>(&format!("\"{}\"", type_))
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid Registry Type format"), cb)
         } else {
             RegistryType::CL_ACCUM
         };
@@ -576,19 +713,88 @@ impl IssuerCommandExecutor {
 
         if let (Ok(rev_reg_def), Ok(rev_reg)) = (self.wallet_service.get_indy_record_value::<RevocationRegistryDefinition>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value()),
                                                  self.wallet_service.get_indy_record_value::<RevocationRegistry>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())) {
-            return Ok((cred_def_id.0.to_string(), rev_reg_def, rev_reg));
+            return cb(Ok((cred_def_id.0.to_string(), rev_reg_def, rev_reg)));
         }
 
-        let cred_def: CredentialDefinition = self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+        let cred_def: CredentialDefinition = try_cb!(self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value()), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_str_str_str_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let issuer_did = issuer_did.clone();
+        let tag = tag.to_string();
+        let cred_def_id = cred_def_id.clone();
+
+        // Keys and tails are generated on the thread pool, but tails are written by the commands thread
+        // as blob storage isn't thread safe.
+        crate::commands::THREADPOOL.lock().unwrap().execute(move || {
+            let res = crate::services::anoncreds::issuer::Issuer::new()
+                .new_revocation_registry(&CredentialDefinitionV1::from(cred_def), max_cred_num, issuance_type.to_bool(), &issuer_did)
+                .and_then(|(revoc_public_keys, revoc_key_private, revoc_registry, mut revoc_tails_generator)| {
+                    let tails = generate_tails(&mut revoc_tails_generator)?;
+                    Ok((revoc_public_keys, revoc_key_private, revoc_registry, tails))
+                });
+
+            CommandExecutor::instance().send(
+                Command::Anoncreds(
+                    AnoncredsCommand::Issuer(
+                        IssuerCommand::CreateAndStoreRevocationRegistryContinue(
+                            wallet_handle,
+                            rev_reg_id,
+                            rev_reg_type,
+                            tag,
+                            cred_def_id,
+                            max_cred_num,
+                            issuance_type,
+                            tails_writer_handle,
+                            res,
+                            cb_id,
+                        ))
+                )).unwrap();
+        });
+    }
+
+    fn _create_and_store_revocation_registry_continue(&self,
+                                                      cb_id: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      rev_reg_id: RevocationRegistryId,
+                                                      rev_reg_type: RegistryType,
+                                                      tag: &str,
+                                                      cred_def_id: &CredentialDefinitionId,
+                                                      max_cred_num: u32,
+                                                      issuance_type: IssuanceType,
+                                                      tails_writer_handle: i32,
+                                                      result: IndyResult<(RevocationRegistryDefinitionValuePublicKeys,
+                                                                          RevocationKeyPrivate,
+                                                                          CryptoRevocationRegistry,
+                                                                          Vec<u8>)>) {
+        let cb = self.pending_str_str_str_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let res = result.and_then(|result| {
+            self._complete_create_and_store_revocation_registry(wallet_handle, rev_reg_id, rev_reg_type, tag, cred_def_id,
+                                                                max_cred_num, issuance_type, tails_writer_handle, result)
+        });
 
-        let (revoc_public_keys, revoc_key_private, revoc_registry, mut revoc_tails_generator) =
-            self.anoncreds_service.issuer.new_revocation_registry(&CredentialDefinitionV1::from(cred_def),
-                                                                  max_cred_num,
-                                                                  issuance_type.to_bool(),
-                                                                  &issuer_did)?;
+        cb(res)
+    }
+
+    fn _complete_create_and_store_revocation_registry(&self,
+                                                      wallet_handle: WalletHandle,
+                                                      rev_reg_id: RevocationRegistryId,
+                                                      rev_reg_type: RegistryType,
+                                                      tag: &str,
+                                                      cred_def_id: &CredentialDefinitionId,
+                                                      max_cred_num: u32,
+                                                      issuance_type: IssuanceType,
+                                                      tails_writer_handle: i32,
+                                                      res: (RevocationRegistryDefinitionValuePublicKeys,
+                                                            RevocationKeyPrivate,
+                                                            CryptoRevocationRegistry,
+                                                            Vec<u8>)) -> IndyResult<(String, String, String)> {
+        let (revoc_public_keys, revoc_key_private, revoc_registry, tails) = res;
 
         let (tails_location, tails_hash) =
-            store_tails_from_generator(self.blob_storage_service.clone(), tails_writer_handle, &mut revoc_tails_generator)?;
+            store_tails(self.blob_storage_service.clone(), tails_writer_handle, &tails)?;
 
         let revoc_reg_def_value = RevocationRegistryDefinitionValue {
             max_cred_num,
@@ -629,6 +835,7 @@ impl IssuerCommandExecutor {
             id: rev_reg_id.clone(),
             curr_id: 0,
             used_ids: HashSet::new(),
+            last_delta_timestamp: Some(IssuerCommandExecutor::_now()?),
         };
 
         self.wallet_service.add_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info, &HashMap::new())?;
@@ -641,8 +848,9 @@ impl IssuerCommandExecutor {
 
     fn create_credential_offer(&self,
                                wallet_handle: WalletHandle,
-                               cred_def_id: &CredentialDefinitionId) -> IndyResult<String> {
-        debug!("create_credential_offer >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+                               cred_def_id: &CredentialDefinitionId,
+                               config: Option<&CredentialOfferConfig>) -> IndyResult<String> {
+        debug!("create_credential_offer >>> wallet_handle: {:?}, cred_def_id: {:?}, config: {:?}", wallet_handle, cred_def_id, config);
 
         let cred_def_correctness_proof: CredentialDefinitionCorrectnessProof =
             self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
@@ -657,16 +865,106 @@ impl IssuerCommandExecutor {
             key_correctness_proof: cred_def_correctness_proof.value,
             nonce,
             method_name: None,
+            attrs_metadata: config.map(|config| config.attrs_metadata.clone()).unwrap_or_default(),
         };
 
         let credential_offer_json = serde_json::to_string(&credential_offer)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialOffer")?;
 
+        let offer_id = credential_offer.nonce.to_dec()
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Nonce")?;
+
+        let created = IssuerCommandExecutor::_now()?;
+
+        self._purge_expired_offers(wallet_handle, created)?;
+
+        let ttl = config.and_then(|config| config.ttl).unwrap_or(DEFAULT_CREDENTIAL_OFFER_TTL);
+        let expires = created.checked_add(ttl)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Credential offer `ttl` is too big"))?;
+
+        let mut tags = Tags::new();
+        tags.insert("schema_id".to_string(), credential_offer.schema_id.0.clone());
+        tags.insert("cred_def_id".to_string(), credential_offer.cred_def_id.0.clone());
+        tags.insert("~expires".to_string(), CredentialOfferInfo::expires_tag(expires));
+
+        let offer_info = CredentialOfferInfo { offer: credential_offer, created, expires: Some(expires), revoked: false };
+
+        self.wallet_service.add_indy_object(wallet_handle, &offer_id, &offer_info, &tags)?;
+
         debug!("create_credential_offer <<< credential_offer_json: {:?}", credential_offer_json);
 
         Ok(credential_offer_json)
     }
 
+    fn list_pending_offers(&self,
+                           wallet_handle: WalletHandle) -> IndyResult<String> {
+        debug!("list_pending_offers >>> wallet_handle: {:?}", wallet_handle);
+
+        let now = IssuerCommandExecutor::_now()?;
+
+        let mut offers_search =
+            self.wallet_service.search_indy_records::<CredentialOfferInfo>(wallet_handle, "{}", &SearchOptions::id_value())?;
+
+        let mut offers: Vec<CredentialOfferInfo> = Vec::new();
+
+        while let Some(offer_record) = offers_search.fetch_next_record()? {
+            let offer_info: CredentialOfferInfo = offer_record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, format!("CredentialOfferInfo not found for id: {}", offer_record.get_id())))
+                .and_then(|value| serde_json::from_str(value)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize CredentialOfferInfo"))?;
+
+            if offer_info.is_pending(now) {
+                offers.push(offer_info);
+            }
+        }
+
+        offers.sort_by_key(|offer_info| offer_info.created);
+
+        let res = serde_json::to_string(&offers)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of CredentialOfferInfo")?;
+
+        debug!("list_pending_offers <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn revoke_offer(&self,
+                    wallet_handle: WalletHandle,
+                    nonce: &str) -> IndyResult<()> {
+        debug!("revoke_offer >>> wallet_handle: {:?}, nonce: {:?}", wallet_handle, nonce);
+
+        let mut offer_info: CredentialOfferInfo =
+            self.wallet_service.get_indy_object(wallet_handle, nonce, &RecordOptions::id_value())?;
+
+        // Revoked offer is kept in the wallet until it expires to reject credential requests built for it
+        offer_info.revoked = true;
+
+        self.wallet_service.update_indy_object(wallet_handle, nonce, &offer_info)?;
+
+        debug!("revoke_offer <<<");
+
+        Ok(())
+    }
+
+    fn _purge_expired_offers(&self, wallet_handle: WalletHandle, now: u64) -> IndyResult<()> {
+        let query_json = json!({"~expires": {"$lte": CredentialOfferInfo::expires_tag(now)}}).to_string();
+
+        let mut offers_search =
+            self.wallet_service.search_indy_records::<CredentialOfferInfo>(wallet_handle, &query_json, &SearchOptions::id_value())?;
+
+        let mut expired_ids: Vec<String> = Vec::new();
+
+        while let Some(offer_record) = offers_search.fetch_next_record()? {
+            expired_ids.push(offer_record.get_id().to_string());
+        }
+
+        for offer_id in expired_ids {
+            self.wallet_service.delete_indy_record::<CredentialOfferInfo>(wallet_handle, &offer_id)?;
+        }
+
+        Ok(())
+    }
+
     fn new_credential(&self,
                       wallet_handle: WalletHandle,
                       cred_offer: &CredentialOffer,
@@ -677,84 +975,189 @@ impl IssuerCommandExecutor {
         debug!("new_credential >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
                wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), rev_reg_id, blob_storage_reader_handle);
 
-        let cred_def_id = match cred_offer.method_name {
-            Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
-            None => cred_offer.cred_def_id.clone()
+        let mut rev_reg_state = self._load_rev_reg_issuance_state(wallet_handle, rev_reg_id, blob_storage_reader_handle)?;
+        let mut issuance = PendingIssuance::default();
+
+        let (credential, cred_rev_id, rev_reg_delta) =
+            self._new_credential(wallet_handle, cred_offer, cred_request, cred_values, rev_reg_id,
+                                 rev_reg_state.as_mut(), &mut HashMap::new(), &mut issuance)?;
+
+        self._store_issuance(wallet_handle, rev_reg_id, rev_reg_state, rev_reg_delta.is_some(), issuance)?;
+
+        let cred_json = serde_json::to_string(&credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+
+        let rev_reg_delta_json = IssuerCommandExecutor::_rev_reg_delta_to_json(rev_reg_delta)?;
+
+        debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
+
+        Ok((cred_json, cred_rev_id, rev_reg_delta_json))
+    }
+
+    fn new_credentials(&self,
+                       wallet_handle: WalletHandle,
+                       requests: &CredentialIssuanceRequests,
+                       rev_reg_id: Option<&RevocationRegistryId>,
+                       blob_storage_reader_handle: Option<i32>) -> IndyResult<(String, Option<String>)> {
+        debug!("new_credentials >>> wallet_handle: {:?}, requests: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+               wallet_handle, secret!(&requests), rev_reg_id, blob_storage_reader_handle);
+
+        // Credential definitions, their private keys and the revocation registry are loaded from the wallet once per batch.
+        // Changes of the batch are kept in memory and stored only after every credential is issued.
+        let mut cred_defs = HashMap::new();
+        let mut rev_reg_state = self._load_rev_reg_issuance_state(wallet_handle, rev_reg_id, blob_storage_reader_handle)?;
+        let mut issuance = PendingIssuance::default();
+
+        let mut credentials: Vec<IssuedCredential> = Vec::with_capacity(requests.0.len());
+        let mut rev_reg_delta: Option<CryptoRevocationRegistryDelta> = None;
+
+        for request in requests.0.iter() {
+            let (cred, cred_revoc_id, cred_rev_reg_delta) =
+                self._new_credential(wallet_handle, &request.cred_offer, &request.cred_req, &request.cred_values,
+                                     rev_reg_id, rev_reg_state.as_mut(), &mut cred_defs, &mut issuance)?;
+
+            rev_reg_delta = match (rev_reg_delta, cred_rev_reg_delta) {
+                (Some(mut delta), Some(other_delta)) => {
+                    delta.merge(&other_delta)?;
+                    Some(delta)
+                }
+                (delta, other_delta) => delta.or(other_delta)
+            };
+
+            credentials.push(IssuedCredential { cred, cred_revoc_id });
+        }
+
+        self._store_issuance(wallet_handle, rev_reg_id, rev_reg_state, rev_reg_delta.is_some(), issuance)?;
+
+        let credentials_json = serde_json::to_string(&credentials)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of Credential")?;
+
+        let rev_reg_delta_json = IssuerCommandExecutor::_rev_reg_delta_to_json(rev_reg_delta)?;
+
+        debug!("new_credentials <<< credentials_json: {:?}, rev_reg_delta_json: {:?}", secret!(&credentials_json), rev_reg_delta_json);
+
+        Ok((credentials_json, rev_reg_delta_json))
+    }
+
+    fn _load_rev_reg_issuance_state(&self,
+                                    wallet_handle: WalletHandle,
+                                    rev_reg_id: Option<&RevocationRegistryId>,
+                                    blob_storage_reader_handle: Option<i32>) -> IndyResult<Option<RevRegIssuanceState>> {
+        let rev_reg_id = match rev_reg_id {
+            Some(rev_reg_id) => rev_reg_id,
+            None => return Ok(None)
         };
 
-        let cred_def: CredentialDefinitionV1 =
-            CredentialDefinitionV1::from(
-                self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?);
+        let rev_reg_def: RevocationRegistryDefinitionV1 =
+            RevocationRegistryDefinitionV1::from(
+                self._wallet_get_rev_reg_def(wallet_handle, &rev_reg_id)?);
 
-        let cred_def_priv_key: CredentialDefinitionPrivateKey =
-            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+        let rev_reg: RevocationRegistryV1 =
+            RevocationRegistryV1::from(
+                self._wallet_get_rev_reg(wallet_handle, &rev_reg_id)?);
 
-        let (rev_reg_def, mut rev_reg,
-            rev_reg_def_priv, sdk_tails_accessor, rev_reg_info) = match rev_reg_id {
-            Some(ref r_reg_id) => {
-                let rev_reg_def: RevocationRegistryDefinitionV1 =
-                    RevocationRegistryDefinitionV1::from(
-                        self._wallet_get_rev_reg_def(wallet_handle, &r_reg_id)?);
+        let rev_key_priv: RevocationRegistryDefinitionPrivate =
+            self.wallet_service.get_indy_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?;
 
-                let rev_reg: RevocationRegistryV1 =
-                    RevocationRegistryV1::from(
-                        self._wallet_get_rev_reg(wallet_handle, &r_reg_id)?);
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
 
-                let rev_key_priv: RevocationRegistryDefinitionPrivate =
-                    self.wallet_service.get_indy_object(wallet_handle, &r_reg_id.0, &RecordOptions::id_value())?;
+        // TODO: FIXME: Review error kind!
+        let blob_storage_reader_handle = blob_storage_reader_handle
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "TailsReaderHandle not found"))?;
 
-                let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &r_reg_id)?;
+        let tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                   blob_storage_reader_handle,
+                                                   &rev_reg_def)?;
 
-                rev_reg_info.curr_id += 1;
+        Ok(Some(RevRegIssuanceState { rev_reg_def, rev_reg, rev_key_priv, tails_accessor, rev_reg_info }))
+    }
 
-                if rev_reg_info.curr_id > rev_reg_def.value.max_cred_num {
-                    return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
-                }
+    fn _new_credential(&self,
+                       wallet_handle: WalletHandle,
+                       cred_offer: &CredentialOffer,
+                       cred_request: &CredentialRequest,
+                       cred_values: &CredentialValues,
+                       rev_reg_id: Option<&RevocationRegistryId>,
+                       mut rev_reg_state: Option<&mut RevRegIssuanceState>,
+                       cred_defs: &mut HashMap<CredentialDefinitionId, (CredentialDefinitionV1, CredentialDefinitionPrivateKey)>,
+                       issuance: &mut PendingIssuance) -> IndyResult<(Credential, Option<String>, Option<CryptoRevocationRegistryDelta>)> {
+        let offer_id = cred_offer.nonce.to_dec()
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Nonce")?;
+
+        let offer_info: Option<CredentialOfferInfo> =
+            self.wallet_service.get_indy_opt_object(wallet_handle, &offer_id, &RecordOptions::id_value())?;
+
+        if let Some(ref offer_info) = offer_info {
+            if !offer_info.is_pending(IssuerCommandExecutor::_now()?) || issuance.used_offers.contains(&offer_id) {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "Credential offer is expired or revoked"));
+            }
+        }
 
-                if rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
-                    rev_reg_info.used_ids.insert(rev_reg_info.curr_id);
-                }
+        let cred_def_id = match cred_offer.method_name {
+            Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
+            None => cred_offer.cred_def_id.clone()
+        };
 
-                // TODO: FIXME: Review error kind!
-                let blob_storage_reader_handle = blob_storage_reader_handle
-                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "TailsReaderHandle not found"))?;
+        if !cred_defs.contains_key(&cred_def_id) {
+            let cred_def: CredentialDefinitionV1 =
+                CredentialDefinitionV1::from(
+                    self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?);
 
-                let sdk_tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
-                                                               blob_storage_reader_handle,
-                                                               &rev_reg_def)?;
+            let cred_def_priv_key: CredentialDefinitionPrivateKey =
+                self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
 
-                (Some(rev_reg_def), Some(rev_reg), Some(rev_key_priv), Some(sdk_tails_accessor), Some(rev_reg_info))
+            cred_defs.insert(cred_def_id.clone(), (cred_def, cred_def_priv_key));
+        }
+
+        let (cred_def, cred_def_priv_key) = &cred_defs[&cred_def_id];
+
+        if let Some(ref mut state) = rev_reg_state {
+            if state.rev_reg_info.curr_id >= state.rev_reg_def.value.max_cred_num {
+                return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
+            }
+
+            state.rev_reg_info.curr_id += 1;
+
+            if state.rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
+                state.rev_reg_info.used_ids.insert(state.rev_reg_info.curr_id);
             }
+        }
+
+        let (rev_idx, rev_reg_def, rev_reg, rev_key_priv, rev_tails_accessor) = match rev_reg_state {
+            Some(ref mut state) =>
+                (Some(state.rev_reg_info.curr_id), Some(&state.rev_reg_def), Some(&mut state.rev_reg.value),
+                 Some(&state.rev_key_priv.value), Some(&state.tails_accessor)),
             None => (None, None, None, None, None)
         };
 
         let (credential_signature, signature_correctness_proof, rev_reg_delta) =
-            self.anoncreds_service.issuer.new_credential(&cred_def,
+            self.anoncreds_service.issuer.new_credential(cred_def,
                                                          &cred_def_priv_key.value,
                                                          &cred_offer.nonce,
                                                          &cred_request,
                                                          &cred_values,
-                                                         rev_reg_info.as_ref().map(|r_reg_info| r_reg_info.curr_id),
-                                                         rev_reg_def.as_ref(),
-                                                         rev_reg.as_mut().map(|r_reg| &mut r_reg.value),
-                                                         rev_reg_def_priv.as_ref().map(|r_reg_def_priv| &r_reg_def_priv.value),
-                                                         sdk_tails_accessor.as_ref())?;
-
-        let witness =
-            if let (&Some(ref r_reg_def), &Some(ref r_reg), &Some(ref rev_tails_accessor), &Some(ref rev_reg_info)) =
-            (&rev_reg_def, &rev_reg, &sdk_tails_accessor, &rev_reg_info) {
-                let (issued, revoked) = match r_reg_def.value.issuance_type {
-                    IssuanceType::ISSUANCE_ON_DEMAND => (rev_reg_info.used_ids.clone(), HashSet::new()),
-                    IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), rev_reg_info.used_ids.clone())
+                                                         rev_idx,
+                                                         rev_reg_def,
+                                                         rev_reg,
+                                                         rev_key_priv,
+                                                         rev_tails_accessor)?;
+
+        let (rev_reg, witness, cred_rev_id) = match rev_reg_state {
+            Some(state) => {
+                let (issued, revoked) = match state.rev_reg_def.value.issuance_type {
+                    IssuanceType::ISSUANCE_ON_DEMAND => (state.rev_reg_info.used_ids.clone(), HashSet::new()),
+                    IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), state.rev_reg_info.used_ids.clone())
                 };
 
-                let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(None, &r_reg.value, &issued, &revoked);
+                let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(None, &state.rev_reg.value, &issued, &revoked);
 
-                Some(Witness::new(rev_reg_info.curr_id, r_reg_def.value.max_cred_num,
-                                  r_reg_def.value.issuance_type.to_bool(), &rev_reg_delta, rev_tails_accessor)?)
-            } else {
-                None
-            };
+                let witness = Witness::new(state.rev_reg_info.curr_id, state.rev_reg_def.value.max_cred_num,
+                                           state.rev_reg_def.value.issuance_type.to_bool(), &rev_reg_delta, &state.tails_accessor)?;
+
+                (Some(state.rev_reg.value.clone()), Some(witness), Some(state.rev_reg_info.curr_id.to_string()))
+            }
+            None => (None, None, None)
+        };
 
         let cred_rev_reg_id = match (rev_reg_id, cred_offer.method_name.as_ref()) {
             (Some(rev_reg_id), Some(ref _method_name)) => Some(rev_reg_id.to_unqualified()),
@@ -765,35 +1168,95 @@ impl IssuerCommandExecutor {
             schema_id: cred_offer.schema_id.clone(),
             cred_def_id: cred_offer.cred_def_id.clone(),
             rev_reg_id: cred_rev_reg_id,
-            values: cred_values.clone(),
+            values: cred_values.with_metadata(&cred_offer.attrs_metadata),
             signature: credential_signature,
             signature_correctness_proof,
-            rev_reg: rev_reg.map(|r_reg| r_reg.value),
+            rev_reg,
             witness,
         };
 
-        let cred_json = serde_json::to_string(&credential)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+        if offer_info.is_some() {
+            issuance.used_offers.insert(offer_id.clone());
+        }
 
-        let rev_reg_delta_json = rev_reg_delta
-            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
-            .as_ref()
-            .map(serde_json::to_string)
-            .map_or(Ok(None), |v| v.map(Some))
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+        issuance.events.push(IssuanceEvent {
+            cred_def_id,
+            nonce: offer_id,
+            timestamp: IssuerCommandExecutor::_now()?,
+            rev_reg_id: rev_reg_id.cloned(),
+            cred_rev_id: cred_rev_id.clone(),
+        });
+
+        Ok((credential, cred_rev_id, rev_reg_delta))
+    }
 
-        if let (Some(r_reg), Some(r_reg_id), Some(r_reg_info)) = (credential.rev_reg, rev_reg_id, rev_reg_info.clone()) {
-            let revoc_reg = RevocationRegistry::RevocationRegistryV1(RevocationRegistryV1 { value: r_reg });
+    fn _store_issuance(&self,
+                       wallet_handle: WalletHandle,
+                       rev_reg_id: Option<&RevocationRegistryId>,
+                       rev_reg_state: Option<RevRegIssuanceState>,
+                       has_rev_reg_delta: bool,
+                       issuance: PendingIssuance) -> IndyResult<()> {
+        // Everything that can fail apart from the wallet writes (including signing of the issuance log head)
+        // is done before the first write.
+        let rev_reg = match (rev_reg_id, rev_reg_state) {
+            (Some(rev_reg_id), Some(state)) => {
+                let RevRegIssuanceState { rev_reg, mut rev_reg_info, .. } = state;
+
+                if has_rev_reg_delta {
+                    rev_reg_info.last_delta_timestamp = Some(IssuerCommandExecutor::_now()?);
+                }
 
-            self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &revoc_reg)?;
-            self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &r_reg_info)?;
+                Some((rev_reg_id, rev_reg, rev_reg_info))
+            }
+            _ => None
         };
 
-        let cred_rev_id = rev_reg_info.map(|r_reg_info| r_reg_info.curr_id.to_string());
+        let log = self._prepare_issuance_log(wallet_handle, issuance.events)?;
 
-        debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
+        // Nothing is stored until the first write succeeds, so its failure is returned.
+        // Credentials are issued once anything is stored, so failures of the later writes are only logged.
+        let mut stored = false;
+        let mut check = |res: IndyResult<()>, item: &str| -> IndyResult<()> {
+            match res {
+                Ok(()) => {
+                    stored = true;
+                    Ok(())
+                }
+                Err(err) if !stored => Err(err),
+                Err(err) => {
+                    warn!("Credentials are issued, but {} isn't stored: {:?}", item, err);
+                    Ok(())
+                }
+            }
+        };
 
-        Ok((cred_json, cred_rev_id, rev_reg_delta_json))
+        if let Some((rev_reg_id, rev_reg, rev_reg_info)) = rev_reg {
+            check(self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &RevocationRegistry::RevocationRegistryV1(rev_reg)), "revocation registry")?;
+            check(self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info), "revocation registry info")?;
+        }
+
+        if let Some((entries, head)) = log {
+            for entry in entries {
+                check(self.wallet_service.upsert_indy_object(wallet_handle, &entry.seq_no.to_string(), &entry), "issuance log entry")?;
+            }
+
+            check(self.wallet_service.update_indy_object(wallet_handle, ISSUANCE_LOG_HEAD_ID, &head), "issuance log head")?;
+        }
+
+        for offer_id in issuance.used_offers {
+            check(self.wallet_service.delete_indy_record::<CredentialOfferInfo>(wallet_handle, &offer_id), "used credential offer deletion")?;
+        }
+
+        Ok(())
+    }
+
+    fn _rev_reg_delta_to_json(rev_reg_delta: Option<CryptoRevocationRegistryDelta>) -> IndyResult<Option<String>> {
+        rev_reg_delta
+            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
+            .as_ref()
+            .map(serde_json::to_string)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")
     }
 
     fn revoke_credential(&self,
@@ -845,6 +1308,8 @@ impl IssuerCommandExecutor {
         let rev_reg_delta_json = serde_json::to_string(&rev_reg_delta)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
 
+        rev_reg_info.last_delta_timestamp = Some(IssuerCommandExecutor::_now()?);
+
         let rev_reg = RevocationRegistry::RevocationRegistryV1(rev_reg);
 
         self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
@@ -855,6 +1320,27 @@ impl IssuerCommandExecutor {
         Ok(rev_reg_delta_json)
     }
 
+    fn get_revocation_registry_info(&self,
+                                    wallet_handle: WalletHandle,
+                                    rev_reg_id: &RevocationRegistryId) -> IndyResult<String> {
+        debug!("get_revocation_registry_info >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+        let rev_reg_def: RevocationRegistryDefinitionV1 =
+            RevocationRegistryDefinitionV1::from(
+                self._wallet_get_rev_reg_def(wallet_handle, &rev_reg_id)?);
+
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
+
+        let statistics = RevocationRegistryStatistics::new(&rev_reg_def, &rev_reg_info);
+
+        let res = serde_json::to_string(&statistics)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryStatistics")?;
+
+        debug!("get_revocation_registry_info <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn _recovery_credential(&self,
                             wallet_handle: WalletHandle,
                             blob_storage_reader_handle: i32,
@@ -904,6 +1390,8 @@ impl IssuerCommandExecutor {
         let rev_reg_delta_json = serde_json::to_string(&rev_reg_delta)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta: {:?}")?;
 
+        rev_reg_info.last_delta_timestamp = Some(IssuerCommandExecutor::_now()?);
+
         let rev_reg = RevocationRegistry::RevocationRegistryV1(rev_reg);
 
         self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
@@ -931,6 +1419,128 @@ impl IssuerCommandExecutor {
         Ok(merged_rev_reg_delta_json)
     }
 
+    fn enable_issuance_log(&self,
+                           wallet_handle: WalletHandle,
+                           issuer_did: &DidValue) -> IndyResult<()> {
+        debug!("enable_issuance_log >>> wallet_handle: {:?}, issuer_did: {:?}", wallet_handle, issuer_did);
+
+        self.crypto_service.validate_did(issuer_did)?;
+
+        let head = self._sign_issuance_log_head(wallet_handle, issuer_did, 0, ISSUANCE_LOG_GENESIS_HASH)?;
+
+        self.wallet_service.add_indy_object(wallet_handle, ISSUANCE_LOG_HEAD_ID, &head, &Tags::new())?;
+
+        debug!("enable_issuance_log <<<");
+
+        Ok(())
+    }
+
+    fn export_issuance_log(&self,
+                           wallet_handle: WalletHandle) -> IndyResult<String> {
+        debug!("export_issuance_log >>> wallet_handle: {:?}", wallet_handle);
+
+        let head: IssuanceLogHead =
+            self.wallet_service.get_indy_object(wallet_handle, ISSUANCE_LOG_HEAD_ID, &RecordOptions::id_value())?;
+
+        let mut entries: Vec<IssuanceLogEntry> = Vec::with_capacity(head.seq_no as usize);
+
+        for seq_no in 1..=head.seq_no {
+            entries.push(self.wallet_service.get_indy_object(wallet_handle, &seq_no.to_string(), &RecordOptions::id_value())?);
+        }
+
+        let res = serde_json::to_string(&IssuanceLog { entries, head })
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize IssuanceLog")?;
+
+        debug!("export_issuance_log <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn verify_issuance_log(&self,
+                           issuance_log: &IssuanceLog,
+                           issuer_verkey: &str) -> IndyResult<bool> {
+        debug!("verify_issuance_log >>> issuance_log: {:?}, issuer_verkey: {:?}", issuance_log, issuer_verkey);
+
+        self.crypto_service.validate_key(issuer_verkey)?;
+
+        let valid = match issuance_log.verify()? {
+            Some(violation) => {
+                warn!("Issuance log verification failed: {}", violation);
+                false
+            }
+            None => {
+                let head = &issuance_log.head;
+
+                let signature = head.signature.from_base58()
+                    .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidStructure, format!("Invalid signature of the issuance log head: {:?}", err)))?;
+
+                let payload = IssuanceLogHead::signature_payload(&head.issuer_did, head.seq_no, &head.hash);
+
+                let valid = self.crypto_service.verify(issuer_verkey, &payload, &signature)?;
+
+                if !valid {
+                    warn!("Issuance log verification failed: head isn't signed by the issuer");
+                }
+
+                valid
+            }
+        };
+
+        debug!("verify_issuance_log <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    // Builds entries of the events and signs the new head without storing them
+    fn _prepare_issuance_log(&self, wallet_handle: WalletHandle, events: Vec<IssuanceEvent>) -> IndyResult<Option<(Vec<IssuanceLogEntry>, IssuanceLogHead)>> {
+        let head: Option<IssuanceLogHead> =
+            self.wallet_service.get_indy_opt_object(wallet_handle, ISSUANCE_LOG_HEAD_ID, &RecordOptions::id_value())?;
+
+        // The log is opt-in and is kept only after `enable_issuance_log`
+        let head = match head {
+            Some(head) => head,
+            None => return Ok(None)
+        };
+
+        if events.is_empty() {
+            return Ok(None);
+        }
+
+        let mut seq_no = head.seq_no;
+        let mut hash = head.hash.clone();
+        let mut entries = Vec::with_capacity(events.len());
+
+        // Entries are written before the head, so entries left by a failed head update
+        // are beyond the head and are overwritten by the next append.
+        for event in events {
+            seq_no += 1;
+
+            let entry = IssuanceLogEntry::new(seq_no, &hash, event)?;
+
+            hash = entry.hash.clone();
+            entries.push(entry);
+        }
+
+        let new_head = self._sign_issuance_log_head(wallet_handle, &head.issuer_did, seq_no, &hash)?;
+
+        Ok(Some((entries, new_head)))
+    }
+
+    fn _sign_issuance_log_head(&self, wallet_handle: WalletHandle, issuer_did: &DidValue, seq_no: u64, hash: &str) -> IndyResult<IssuanceLogHead> {
+        let my_key = get_my_did_signing_key(&self.wallet_service, wallet_handle, issuer_did)?;
+
+        let payload = IssuanceLogHead::signature_payload(issuer_did, seq_no, hash);
+
+        let signature = self.crypto_service.sign(&my_key, &payload)?;
+
+        Ok(IssuanceLogHead {
+            seq_no,
+            hash: hash.to_string(),
+            issuer_did: issuer_did.clone(),
+            signature: signature.to_base58(),
+        })
+    }
+
     // TODO: DELETE IT
     fn _wallet_set_schema_id(&self, wallet_handle: WalletHandle, id: &str, schema_id: &SchemaId) -> IndyResult<()> {
         self.wallet_service.add_record(wallet_handle, &self.wallet_service.add_prefix("SchemaId"), id, &schema_id.0, &Tags::new())
@@ -956,4 +1566,10 @@ impl IssuerCommandExecutor {
     fn _wallet_get_rev_reg_info(&self, wallet_handle: WalletHandle, key: &RevocationRegistryId) -> IndyResult<RevocationRegistryInfo> {
         self.wallet_service.get_indy_object(wallet_handle, &key.0, &RecordOptions::id_value())
     }
+
+    fn _now() -> IndyResult<u64> {
+        SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|ts| ts.as_secs())
+            .to_indy(IndyErrorKind::InvalidState, "Cannot get time")
+    }
 }
@@ -12,7 +12,9 @@ use crate::services::blob_storage::BlobStorageService;
 use crate::services::pool::PoolService;
 use indy_wallet::WalletService;
 use crate::services::crypto::CryptoService;
-use crate::services::anoncreds::helpers::to_unqualified;
+use crate::services::anoncreds::helpers::{json_to_msgpack, msgpack_to_json, to_unqualified, encode_attribute_value, verify_attribute_encoding};
+use crate::domain::anoncreds::credential::Credential;
+use crate::domain::anoncreds::w3c_credential::W3CCredential;
 
 use indy_api_types::errors::prelude::*;
 
@@ -24,7 +26,22 @@ pub enum AnoncredsCommand {
     Verifier(VerifierCommand),
     ToUnqualified(
         String, // entity
-        Box<dyn Fn(IndyResult<String>) + Send>)
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ToMsgpack(
+        String, // json
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>),
+    FromMsgpack(
+        Vec<u8>, // msgpack data
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EncodeAttributeValue(
+        String, // raw
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ToW3cCredential(
+        Credential,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    FromW3cCredential(
+        W3CCredential,
+        Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
 pub struct AnoncredsCommandExecutor {
@@ -46,7 +63,7 @@ impl AnoncredsCommandExecutor {
             prover_command_cxecutor: ProverCommandExecutor::new(
                 anoncreds_service.clone(), wallet_service.clone(), crypto_service.clone(), blob_storage_service.clone()),
             verifier_command_cxecutor: VerifierCommandExecutor::new(
-                anoncreds_service.clone()),
+                anoncreds_service.clone(), wallet_service.clone(), crypto_service.clone()),
         }
     }
 
@@ -68,6 +85,57 @@ impl AnoncredsCommandExecutor {
                 debug!("ToUnqualified command received");
                 cb(to_unqualified(&entity));
             }
+            AnoncredsCommand::ToMsgpack(json, cb) => {
+                debug!("ToMsgpack command received");
+                cb(json_to_msgpack(&json));
+            }
+            AnoncredsCommand::FromMsgpack(data, cb) => {
+                debug!("FromMsgpack command received");
+                cb(msgpack_to_json(&data));
+            }
+            AnoncredsCommand::EncodeAttributeValue(raw, cb) => {
+                debug!("EncodeAttributeValue command received");
+                cb(encode_attribute_value(&raw));
+            }
+            AnoncredsCommand::ToW3cCredential(credential, cb) => {
+                debug!("ToW3cCredential command received");
+                cb(self.to_w3c_credential(credential));
+            }
+            AnoncredsCommand::FromW3cCredential(w3c_credential, cb) => {
+                debug!("FromW3cCredential command received");
+                cb(self.from_w3c_credential(w3c_credential));
+            }
         };
     }
+
+    fn to_w3c_credential(&self, credential: Credential) -> IndyResult<String> {
+        trace!("to_w3c_credential >>> credential: {:?}", credential);
+
+        let w3c_credential = W3CCredential::from_credential(credential, None)?;
+
+        let res = serde_json::to_string(&w3c_credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize W3C Credential")?;
+
+        trace!("to_w3c_credential <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn from_w3c_credential(&self, w3c_credential: W3CCredential) -> IndyResult<String> {
+        trace!("from_w3c_credential >>> w3c_credential: {:?}", w3c_credential);
+
+        let credential = w3c_credential.into_credential()?;
+
+        // Credential subject isn't covered by CL signature, so raw values must match the signed encoded ones
+        for (attr, values) in credential.values.0.iter() {
+            verify_attribute_encoding(attr, &values.raw, &values.encoded)?;
+        }
+
+        let res = serde_json::to_string(&credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+
+        trace!("from_w3c_credential <<< res: {:?}", res);
+
+        Ok(res)
+    }
 }
@@ -1,19 +1,22 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::rc::Rc;
 
+use rust_base58::ToBase58;
 use ursa::cl::{new_nonce, RevocationRegistry, Witness};
 
 use serde_json::Value;
 
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
-use crate::domain::anoncreds::credential::{Credential, CredentialInfo};
+use crate::domain::anoncreds::credential::{AttributeMetadata, Credential, CredentialInfo, CredentialProjection, ProjectedCredentialInfo};
 use crate::domain::anoncreds::credential_definition::{cred_defs_map_to_cred_defs_v1_map, CredentialDefinition, CredentialDefinitionV1, CredentialDefinitionId, CredentialDefinitions};
 use crate::domain::anoncreds::credential_for_proof_request::{CredentialsForProofRequest, RequestedCredential};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
-use crate::domain::anoncreds::master_secret::MasterSecret;
-use crate::domain::anoncreds::proof_request::{NonRevocedInterval, PredicateInfo, ProofRequest, ProofRequestExtraQuery};
+use crate::domain::anoncreds::master_secret::{MasterSecret, MasterSecretInfo, MASTER_SECRET_ID_TAG};
+use crate::domain::anoncreds::proof::{HolderBinding, Proof};
+use crate::domain::anoncreds::proof_request::{NonRevocedInterval, PredicateInfo, ProofRequest, ProofRequestExtraQuery, ProofRequestPayload};
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1};
 use crate::domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
@@ -22,22 +25,38 @@ use crate::domain::anoncreds::schema::{schemas_map_to_schemas_v1_map, SchemaV1,
 use crate::domain::crypto::did::DidValue;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
-use crate::services::anoncreds::helpers::{parse_cred_rev_id, get_non_revoc_interval};
+use crate::services::anoncreds::prover::Prover;
+use crate::services::anoncreds::helpers::{attr_common_view, parse_cred_rev_id, get_non_revoc_interval};
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
-use indy_utils::{next_search_handle};
+use indy_utils::{next_command_handle, next_search_handle};
 use crate::utils::wql::Query;
 
 use super::tails::SDKTailsAccessor;
-use indy_api_types::{WalletHandle, SearchHandle};
-use crate::commands::BoxedCallbackStringStringSend;
+use indy_api_types::{WalletHandle, SearchHandle, CommandHandle};
+use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
+use crate::commands::anoncreds::AnoncredsCommand;
+use crate::commands::cache::CacheCommand;
+use crate::commands::did::get_my_did_signing_key;
 
 pub enum ProverCommand {
     CreateMasterSecret(
         WalletHandle,
         Option<String>, // master secret id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    ListMasterSecrets(
+        WalletHandle,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    RenameMasterSecret(
+        WalletHandle,
+        String, // master secret id
+        String, // new master secret id
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    DeleteMasterSecret(
+        WalletHandle,
+        String, // master secret id
+        Box<dyn Fn(IndyResult<()>) + Send>),
     CreateCredentialRequest(
         WalletHandle,
         DidValue, // prover did
@@ -63,6 +82,13 @@ pub enum ProverCommand {
         CredentialDefinition, // credential definition
         Option<RevocationRegistryDefinition>, // revocation registry definition
         Box<dyn Fn(IndyResult<String>) + Send>),
+    StoreCredentialContinue(
+        WalletHandle,
+        Option<String>, // credential id
+        String, // master secret name
+        IndyResult<Credential>, // processed credential
+        CommandHandle, // cb_id
+    ),
     GetCredentials(
         WalletHandle,
         Option<String>, // filter json
@@ -79,6 +105,11 @@ pub enum ProverCommand {
         WalletHandle,
         Option<String>, // query json
         Box<dyn Fn(IndyResult<(SearchHandle, usize)>) + Send>),
+    SearchCredentialsWithProjection(
+        WalletHandle,
+        Option<String>, // query json
+        CredentialProjection, // projection
+        Box<dyn Fn(IndyResult<(SearchHandle, usize)>) + Send>),
     FetchCredentials(
         SearchHandle,
         usize, // count
@@ -112,6 +143,17 @@ pub enum ProverCommand {
         CredentialDefinitions, // credential defs
         RevocationStates, // revocation states
         Box<dyn Fn(IndyResult<String>) + Send>),
+    PreviewDisclosure(
+        WalletHandle,
+        ProofRequest, // proof request
+        RequestedCredentials, // requested credentials
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    BindProof(
+        WalletHandle,
+        ProofRequest, // proof request
+        Proof, // proof
+        DidValue, // holder did
+        Box<dyn Fn(IndyResult<String>) + Send>),
     CreateRevocationState(
         i32, // blob storage reader handle
         RevocationRegistryDefinition, // revocation registry definition
@@ -153,7 +195,9 @@ pub struct ProverCommandExecutor {
     crypto_service: Rc<CryptoService>,
     blob_storage_service: Rc<BlobStorageService>,
     searches: RefCell<HashMap<SearchHandle, Box<WalletSearch>>>,
+    search_projections: RefCell<HashMap<SearchHandle, CredentialProjection>>,
     searches_for_proof_requests: RefCell<HashMap<SearchHandle, Box<HashMap<String, SearchForProofRequest>>>>,
+    pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
 }
 
 impl ProverCommandExecutor {
@@ -167,7 +211,9 @@ impl ProverCommandExecutor {
             crypto_service,
             blob_storage_service,
             searches: RefCell::new(HashMap::new()),
+            search_projections: RefCell::new(HashMap::new()),
             searches_for_proof_requests: RefCell::new(HashMap::new()),
+            pending_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -177,11 +223,23 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "CreateMasterSecret command received");
                 cb(self.create_master_secret(wallet_handle, master_secret_id.as_ref().map(String::as_str)));
             }
+            ProverCommand::ListMasterSecrets(wallet_handle, cb) => {
+                debug!(target: "prover_command_executor", "ListMasterSecrets command received");
+                cb(self.list_master_secrets(wallet_handle));
+            }
+            ProverCommand::RenameMasterSecret(wallet_handle, master_secret_id, new_master_secret_id, cb) => {
+                debug!(target: "prover_command_executor", "RenameMasterSecret command received");
+                cb(self.rename_master_secret(wallet_handle, &master_secret_id, &new_master_secret_id));
+            }
+            ProverCommand::DeleteMasterSecret(wallet_handle, master_secret_id, cb) => {
+                debug!(target: "prover_command_executor", "DeleteMasterSecret command received");
+                cb(self.delete_master_secret(wallet_handle, &master_secret_id));
+            }
             ProverCommand::CreateCredentialRequest(wallet_handle, prover_did, credential_offer,
                                                    credential_def, master_secret_name, cb) => {
                 debug!(target: "prover_command_executor", "CreateCredentialRequest command received");
-                cb(self.create_credential_request(wallet_handle, &prover_did, &credential_offer,
-                                                  &CredentialDefinitionV1::from(credential_def), &master_secret_name));
+                self.create_credential_request(wallet_handle, &prover_did, credential_offer,
+                                               CredentialDefinitionV1::from(credential_def), &master_secret_name, cb);
             }
             ProverCommand::SetCredentialAttrTagPolicy(wallet_handle, cred_def_id, catpol, retroactive, cb) => {
                 debug!(target: "prover_command_executor", "SetCredentialAttrTagPolicy command received");
@@ -191,12 +249,15 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "GetCredentialAttrTagPolicy command received");
                 cb(self.get_credential_attr_tag_policy(wallet_handle, &cred_def_id));
             }
-            ProverCommand::StoreCredential(wallet_handle, cred_id, cred_req_metadata, mut cred, cred_def, rev_reg_def, cb) => {
+            ProverCommand::StoreCredential(wallet_handle, cred_id, cred_req_metadata, cred, cred_def, rev_reg_def, cb) => {
                 debug!(target: "prover_command_executor", "StoreCredential command received");
-                cb(self.store_credential(wallet_handle, cred_id.as_ref().map(String::as_str),
-                                         &cred_req_metadata, &mut cred,
-                                         &CredentialDefinitionV1::from(cred_def),
-                                         rev_reg_def.map(RevocationRegistryDefinitionV1::from).as_ref()));
+                self.store_credential(wallet_handle, cred_id, cred_req_metadata, cred,
+                                      CredentialDefinitionV1::from(cred_def),
+                                      rev_reg_def.map(RevocationRegistryDefinitionV1::from), cb);
+            }
+            ProverCommand::StoreCredentialContinue(wallet_handle, cred_id, master_secret_name, result, cb_id) => {
+                debug!(target: "prover_command_executor", "StoreCredentialContinue command received");
+                self._store_credential_continue(wallet_handle, cred_id.as_ref().map(String::as_str), &master_secret_name, result, cb_id);
             }
             ProverCommand::GetCredentials(wallet_handle, filter_json, cb) => {
                 debug!(target: "prover_command_executor", "GetCredentials command received");
@@ -214,6 +275,10 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "SearchCredentials command received");
                 cb(self.search_credentials(wallet_handle, query_json.as_ref().map(String::as_str)));
             }
+            ProverCommand::SearchCredentialsWithProjection(wallet_handle, query_json, projection, cb) => {
+                debug!(target: "prover_command_executor", "SearchCredentialsWithProjection command received");
+                cb(self.search_credentials_with_projection(wallet_handle, query_json.as_ref().map(String::as_str), projection));
+            }
             ProverCommand::FetchCredentials(search_handle, count, cb) => {
                 debug!(target: "prover_command_executor", "FetchCredentials command received");
                 cb(self.fetch_credentials(search_handle, count));
@@ -241,10 +306,18 @@ impl ProverCommandExecutor {
             ProverCommand::CreateProof(wallet_handle, proof_req, requested_credentials, master_secret_name,
                                        schemas, cred_defs, rev_states, cb) => {
                 debug!(target: "prover_command_executor", "CreateProof command received");
-                cb(self.create_proof(wallet_handle, &proof_req, &requested_credentials, &master_secret_name,
-                                     &schemas_map_to_schemas_v1_map(schemas),
-                                     &cred_defs_map_to_cred_defs_v1_map(cred_defs),
-                                     &rev_states));
+                self.create_proof(wallet_handle, proof_req, requested_credentials, &master_secret_name,
+                                  schemas_map_to_schemas_v1_map(schemas),
+                                  cred_defs_map_to_cred_defs_v1_map(cred_defs),
+                                  rev_states, cb);
+            }
+            ProverCommand::PreviewDisclosure(wallet_handle, proof_req, requested_credentials, cb) => {
+                debug!(target: "prover_command_executor", "PreviewDisclosure command received");
+                cb(self.preview_disclosure(wallet_handle, &proof_req, &requested_credentials));
+            }
+            ProverCommand::BindProof(wallet_handle, proof_req, proof, holder_did, cb) => {
+                debug!(target: "prover_command_executor", "BindProof command received");
+                cb(self.bind_proof(wallet_handle, proof_req.value(), proof, &holder_did));
             }
             ProverCommand::CreateRevocationState(blob_storage_reader_handle, rev_reg_def, rev_reg_delta, timestamp, cred_rev_id, cb) => {
                 debug!(target: "prover_command_executor", "CreateRevocationState command received");
@@ -281,23 +354,157 @@ impl ProverCommandExecutor {
         Ok(master_secret_id)
     }
 
+    fn list_master_secrets(&self,
+                           wallet_handle: WalletHandle) -> IndyResult<String> {
+        debug!("list_master_secrets >>> wallet_handle: {:?}", wallet_handle);
+
+        let options_json = json!({
+            "retrieveType": false,
+            "retrieveValue": false,
+            "retrieveTags": false,
+        }).to_string();
+
+        let mut master_secrets_search =
+            self.wallet_service.search_indy_records::<MasterSecret>(wallet_handle, "{}", &options_json)?;
+
+        let mut master_secrets: Vec<MasterSecretInfo> = Vec::new();
+
+        while let Some(master_secret_record) = master_secrets_search.fetch_next_record()? {
+            let id = master_secret_record.get_id().to_string();
+            let credentials_count = self._count_master_secret_credentials(wallet_handle, &id)?;
+
+            master_secrets.push(MasterSecretInfo { id, credentials_count });
+        }
+
+        let res = serde_json::to_string(&master_secrets)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of MasterSecretInfo")?;
+
+        debug!("list_master_secrets <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn rename_master_secret(&self,
+                            wallet_handle: WalletHandle,
+                            master_secret_id: &str,
+                            new_master_secret_id: &str) -> IndyResult<()> {
+        debug!("rename_master_secret >>> wallet_handle: {:?}, master_secret_id: {:?}, new_master_secret_id: {:?}",
+               wallet_handle, master_secret_id, new_master_secret_id);
+
+        let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, master_secret_id)?;
+
+        // Renaming takes several wallet writes. The master secret under the new id is stored first and
+        // the old one is deleted last, so the rename interrupted in between is completed by calling it again.
+        let existing_master_secret: Option<MasterSecret> =
+            self.wallet_service.get_indy_opt_object(wallet_handle, new_master_secret_id, &RecordOptions::id_value())?;
+
+        match existing_master_secret {
+            Some(ref existing_master_secret) if !self._is_same_master_secret(existing_master_secret, &master_secret)? =>
+                return Err(err_msg(IndyErrorKind::MasterSecretDuplicateName, format!("MasterSecret already exists {}", new_master_secret_id))),
+            Some(_) => {}
+            None => {
+                self.wallet_service.add_indy_object(wallet_handle, new_master_secret_id, &master_secret, &HashMap::new())?;
+            }
+        }
+
+        let query_json = json!({ MASTER_SECRET_ID_TAG: master_secret_id }).to_string();
+        let options_json = json!({
+            "retrieveType": false,
+            "retrieveValue": false,
+            "retrieveTags": false,
+        }).to_string();
+
+        let mut credentials_search =
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query_json, &options_json)?;
+
+        let mut cred_ids: Vec<String> = Vec::new();
+
+        while let Some(credential_record) = credentials_search.fetch_next_record()? {
+            cred_ids.push(credential_record.get_id().to_string());
+        }
+
+        let mut cred_tags = HashMap::new();
+        cred_tags.insert(MASTER_SECRET_ID_TAG.to_string(), new_master_secret_id.to_string());
+
+        for cred_id in cred_ids {
+            self.wallet_service.add_record_tags(wallet_handle, self.wallet_service.add_prefix("Credential").as_str(), &cred_id, &cred_tags)?;
+        }
+
+        self.wallet_service.delete_indy_record::<MasterSecret>(wallet_handle, master_secret_id)?;
+
+        debug!("rename_master_secret <<< res: ()");
+
+        Ok(())
+    }
+
+    fn delete_master_secret(&self,
+                            wallet_handle: WalletHandle,
+                            master_secret_id: &str) -> IndyResult<()> {
+        debug!("delete_master_secret >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
+
+        if !self.wallet_service.record_exists::<MasterSecret>(wallet_handle, master_secret_id)? {
+            return Err(err_msg(IndyErrorKind::WalletItemNotFound, format!("MasterSecret {} not found", master_secret_id)));
+        }
+
+        let credentials_count = self._count_master_secret_credentials(wallet_handle, master_secret_id)?;
+
+        if credentials_count > 0 {
+            return Err(err_msg(IndyErrorKind::MasterSecretInUse,
+                               format!("MasterSecret {} is referenced by {} stored credentials", master_secret_id, credentials_count)));
+        }
+
+        // Credentials stored before master secret references were tagged may be bound to any master secret
+        let untagged_credentials_count = self._count_untagged_credentials(wallet_handle)?;
+
+        if untagged_credentials_count > 0 {
+            return Err(err_msg(IndyErrorKind::MasterSecretInUse,
+                               format!("MasterSecret {} may be referenced by {} stored credentials without master secret reference",
+                                       master_secret_id, untagged_credentials_count)));
+        }
+
+        self.wallet_service.delete_indy_record::<MasterSecret>(wallet_handle, master_secret_id)?;
+
+        debug!("delete_master_secret <<< res: ()");
+
+        Ok(())
+    }
+
+    // Master secret is read on the commands thread, but the request itself is built on the thread pool.
     fn create_credential_request(&self,
                                  wallet_handle: WalletHandle,
                                  prover_did: &DidValue,
-                                 cred_offer: &CredentialOffer,
-                                 cred_def: &CredentialDefinitionV1,
-                                 master_secret_id: &str) -> IndyResult<(String, String)> {
+                                 cred_offer: CredentialOffer,
+                                 cred_def: CredentialDefinitionV1,
+                                 master_secret_id: &str,
+                                 cb: BoxedCallbackStringStringSend) {
         debug!("create_credential_request >>> wallet_handle: {:?}, prover_did: {:?}, cred_offer: {:?}, cred_def: {:?}, master_secret_id: {:?}",
                wallet_handle, prover_did, cred_offer, cred_def, master_secret_id);
 
-        self.crypto_service.validate_did(&prover_did)?;
+        try_cb!(self.crypto_service.validate_did(&prover_did), cb);
 
-        let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &master_secret_id)?;
+        let master_secret: MasterSecret = try_cb!(self._wallet_get_master_secret(wallet_handle, &master_secret_id), cb);
+
+        let prover_did = prover_did.clone();
+        let master_secret_id = master_secret_id.to_string();
+
+        crate::commands::THREADPOOL.lock().unwrap().execute(move || {
+            let res = ProverCommandExecutor::_new_credential_request(&prover_did, &cred_offer, &cred_def, &master_secret, &master_secret_id);
 
+            debug!("create_credential_request <<< res: {:?}", res);
+
+            cb(res)
+        });
+    }
+
+    fn _new_credential_request(prover_did: &DidValue,
+                               cred_offer: &CredentialOffer,
+                               cred_def: &CredentialDefinitionV1,
+                               master_secret: &MasterSecret,
+                               master_secret_id: &str) -> IndyResult<(String, String)> {
         let (blinded_ms, ms_blinding_data, blinded_ms_correctness_proof) =
-            self.anoncreds_service.prover.new_credential_request(cred_def,
-                                                                 &master_secret.value,
-                                                                 &cred_offer)?;
+            Prover::new().new_credential_request(cred_def,
+                                                 &master_secret.value,
+                                                 &cred_offer)?;
 
         let nonce = new_nonce()?;
 
@@ -321,8 +528,6 @@ impl ProverCommandExecutor {
         let cred_req_metadata_json = serde_json::to_string(&credential_request_metadata)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialRequestMetadata")?;
 
-        debug!("create_credential_request <<< cred_req_json: {:?}, cred_req_metadata_json: {:?}", cred_req_json, cred_req_metadata_json);
-
         Ok((cred_req_json, cred_req_metadata_json))
     }
 
@@ -347,11 +552,23 @@ impl ProverCommandExecutor {
         // Cascade whether we updated policy or not: could be a retroactive cred attr tags reset to existing policy
         if retroactive {
             let query_json = format!(r#"{{"cred_def_id": "{}"}}"#, cred_def_id.0);
-            let mut credentials_search = self.wallet_service.search_indy_records::<Credential>(wallet_handle, query_json.as_str(), &SearchOptions::id_value())?;
+            let options_json = json!({
+                "retrieveType": false,
+                "retrieveValue": true,
+                "retrieveTags": true,
+            }).to_string();
+
+            let mut credentials_search = self.wallet_service.search_indy_records::<Credential>(wallet_handle, query_json.as_str(), &options_json)?;
 
             while let Some(credential_record) = credentials_search.fetch_next_record()? {
                 let (_, credential) = self._get_credential(&credential_record)?;
-                let cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol)?;
+                let mut cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol)?;
+
+                // Reference to master secret isn't derived from the credential, so it's kept as is
+                if let Some(master_secret_id) = credential_record.get_tags().and_then(|tags| tags.get(MASTER_SECRET_ID_TAG)) {
+                    cred_tags.insert(MASTER_SECRET_ID_TAG.to_string(), master_secret_id.clone());
+                }
+
                 self.wallet_service.update_record_tags(wallet_handle, self.wallet_service.add_prefix("Credential").as_str(), credential_record.get_id(), &cred_tags)?;
             }
         }
@@ -379,24 +596,61 @@ impl ProverCommandExecutor {
         Ok(catpol_json)
     }
 
+    // Credential is processed on the thread pool and stored on the commands thread.
     fn store_credential(&self,
                         wallet_handle: WalletHandle,
-                        cred_id: Option<&str>,
-                        cred_req_metadata: &CredentialRequestMetadata,
-                        credential: &mut Credential,
-                        cred_def: &CredentialDefinitionV1,
-                        rev_reg_def: Option<&RevocationRegistryDefinitionV1>) -> IndyResult<String> {
+                        cred_id: Option<String>,
+                        cred_req_metadata: CredentialRequestMetadata,
+                        mut credential: Credential,
+                        cred_def: CredentialDefinitionV1,
+                        rev_reg_def: Option<RevocationRegistryDefinitionV1>,
+                        cb: Box<dyn Fn(IndyResult<String>) + Send>) {
         debug!("store_credential >>> wallet_handle: {:?}, cred_id: {:?}, cred_req_metadata: {:?}, credential: {:?}, cred_def: {:?}, \
         rev_reg_def: {:?}", wallet_handle, cred_id, cred_req_metadata, credential, cred_def, rev_reg_def);
 
-        let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &cred_req_metadata.master_secret_name)?;
+        let master_secret: MasterSecret = try_cb!(self._wallet_get_master_secret(wallet_handle, &cred_req_metadata.master_secret_name), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        crate::commands::THREADPOOL.lock().unwrap().execute(move || {
+            let res = Prover::new().process_credential(&mut credential,
+                                                       &cred_req_metadata,
+                                                       &master_secret.value,
+                                                       &cred_def,
+                                                       rev_reg_def.as_ref())
+                .map(|_| credential);
+
+            CommandExecutor::instance().send(
+                Command::Anoncreds(
+                    AnoncredsCommand::Prover(
+                        ProverCommand::StoreCredentialContinue(wallet_handle, cred_id, cred_req_metadata.master_secret_name, res, cb_id)
+                    )
+                )
+            ).unwrap();
+        });
+    }
+
+    fn _store_credential_continue(&self,
+                                  wallet_handle: WalletHandle,
+                                  cred_id: Option<&str>,
+                                  master_secret_name: &str,
+                                  result: IndyResult<Credential>,
+                                  cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let res = result.and_then(|mut credential| self._store_processed_credential(wallet_handle, cred_id, master_secret_name, &mut credential));
 
-        self.anoncreds_service.prover.process_credential(credential,
-                                                         &cred_req_metadata,
-                                                         &master_secret.value,
-                                                         cred_def,
-                                                         rev_reg_def)?;
+        debug!("store_credential <<< res: {:?}", res);
 
+        cb(res)
+    }
+
+    fn _store_processed_credential(&self,
+                                   wallet_handle: WalletHandle,
+                                   cred_id: Option<&str>,
+                                   master_secret_name: &str,
+                                   credential: &mut Credential) -> IndyResult<String> {
         credential.rev_reg = None;
         credential.witness = None;
 
@@ -409,10 +663,10 @@ impl ProverCommandExecutor {
             None
         };
 
-        let cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol.as_ref())?;
-        self.wallet_service.add_indy_object(wallet_handle, &out_cred_id, credential, &cred_tags)?;
+        let mut cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol.as_ref())?;
+        cred_tags.insert(MASTER_SECRET_ID_TAG.to_string(), master_secret_name.to_string());
 
-        debug!("store_credential <<< out_cred_id: {:?}", out_cred_id);
+        self.wallet_service.add_indy_object(wallet_handle, &out_cred_id, credential, &cred_tags)?;
 
         Ok(out_cred_id)
     }
@@ -479,6 +733,29 @@ impl ProverCommandExecutor {
         Ok(res)
     }
 
+    fn search_credentials_with_projection(&self,
+                                          wallet_handle: WalletHandle,
+                                          query_json: Option<&str>,
+                                          projection: CredentialProjection) -> IndyResult<(SearchHandle, usize)> {
+        debug!("search_credentials_with_projection >>> wallet_handle: {:?}, query_json: {:?}, projection: {:?}", wallet_handle, query_json, projection);
+
+        let credentials_search =
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, query_json.unwrap_or("{}"), &SearchOptions::id_tags())?;
+
+        let total_count = credentials_search.get_total_count()?.unwrap_or(0);
+
+        let handle : SearchHandle = next_search_handle();
+
+        self.searches.borrow_mut().insert(handle, Box::new(credentials_search));
+        self.search_projections.borrow_mut().insert(handle, projection);
+
+        let res = (handle, total_count);
+
+        trace!("search_credentials_with_projection <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn fetch_credentials(&self,
                          search_handle: SearchHandle,
                          count: usize, ) -> IndyResult<String> {
@@ -488,20 +765,31 @@ impl ProverCommandExecutor {
         let search = searches.get_mut(&search_handle)
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown CredentialsSearch handle: {:?}", search_handle)))?;
 
-        let mut credentials_info: Vec<CredentialInfo> = Vec::new();
+        let credential_records = search.fetch_next(count)?;
 
-        for _ in 0..count {
-            match search.fetch_next_record()? {
-                Some(credential_record) => {
-                    let (referent, credential) = self._get_credential(&credential_record)?;
-                    credentials_info.push(self._get_credential_info(&referent, credential))
-                }
-                None => break
-            }
-        }
+        let credentials_info_json = match self.search_projections.borrow().get(&search_handle) {
+            Some(projection) => {
+                let credentials_info = credential_records
+                    .iter()
+                    .map(|credential_record| self._get_projected_credential_info(credential_record, projection))
+                    .collect::<IndyResult<Vec<ProjectedCredentialInfo>>>()?;
 
-        let credentials_info_json = serde_json::to_string(&credentials_info)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of CredentialInfo")?;
+                serde_json::to_string(&credentials_info)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of ProjectedCredentialInfo")?
+            }
+            None => {
+                let credentials_info = credential_records
+                    .iter()
+                    .map(|credential_record| {
+                        let (referent, credential) = self._get_credential(credential_record)?;
+                        Ok(self._get_credential_info(&referent, credential))
+                    })
+                    .collect::<IndyResult<Vec<CredentialInfo>>>()?;
+
+                serde_json::to_string(&credentials_info)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of CredentialInfo")?
+            }
+        };
 
         trace!("fetch_credentials <<< credentials_info_json: {:?}", credentials_info_json);
 
@@ -516,6 +804,8 @@ impl ProverCommandExecutor {
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown CredentialsSearch handle: {:?}", search_handle)))
         }?;
 
+        self.search_projections.borrow_mut().remove(&search_handle);
+
         trace!("close_credentials_search <<< res: ()");
 
         Ok(())
@@ -669,53 +959,136 @@ impl ProverCommandExecutor {
         self.wallet_service.delete_indy_record::<Credential>(wallet_handle, cred_id)
     }
 
+    // Wallet records are read on the commands thread, but proof itself is generated on the thread pool
+    // so a long proof generation doesn't block other commands.
     fn create_proof(&self,
                     wallet_handle: WalletHandle,
-                    proof_req: &ProofRequest,
-                    requested_credentials: &RequestedCredentials,
+                    proof_req: ProofRequest,
+                    requested_credentials: RequestedCredentials,
                     master_secret_id: &str,
-                    schemas: &HashMap<SchemaId, SchemaV1>,
-                    cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
-                    rev_states: &RevocationStates) -> IndyResult<String> {
+                    schemas: HashMap<SchemaId, SchemaV1>,
+                    cred_defs: HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                    rev_states: RevocationStates,
+                    cb: Box<dyn Fn(IndyResult<String>) + Send>) {
         debug!("create_proof >>> wallet_handle: {:?}, proof_req: {:?}, requested_credentials: {:?}, master_secret_id: {:?}, schemas: {:?}, \
         cred_defs: {:?}, rev_states: {:?}",
                wallet_handle, proof_req, requested_credentials, master_secret_id, schemas, cred_defs, rev_states);
 
+        let (master_secret, credentials) = try_cb!(self._get_proof_secrets(wallet_handle, &requested_credentials, master_secret_id), cb);
+
+        // Usage is tracked to prefetch revocation states of frequently used credentials
+        if let Ok(cred_wallets) = self._get_credential_wallets(wallet_handle, &requested_credentials) {
+            CommandExecutor::instance().send(Command::Cache(CacheCommand::CredentialsUsed(cred_wallets))).unwrap();
+        }
+
+        crate::commands::THREADPOOL.lock().unwrap().execute(move || {
+            let res = ProverCommandExecutor::_create_proof(&credentials, &proof_req, &requested_credentials, &master_secret,
+                                                           &schemas, &cred_defs, &rev_states);
+
+            debug!("create_proof <<< res: {:?}", res);
+
+            cb(res)
+        });
+    }
+
+    fn _get_proof_secrets(&self,
+                          wallet_handle: WalletHandle,
+                          requested_credentials: &RequestedCredentials,
+                          master_secret_id: &str) -> IndyResult<(MasterSecret, HashMap<String, Credential>)> {
         let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &master_secret_id)?;
 
-        let cred_refs_for_attrs =
-            requested_credentials.requested_attributes
-                .values()
-                .map(|requested_attr| requested_attr.cred_id.clone())
-                .collect::<HashSet<String>>();
+        let credentials = self._load_requested_credentials(wallet_handle, requested_credentials)?;
 
-        let cred_refs_for_predicates =
-            requested_credentials.requested_predicates
-                .values()
-                .map(|requested_predicate| requested_predicate.cred_id.clone())
-                .collect::<HashSet<String>>();
+        Ok((master_secret, credentials))
+    }
 
-        let cred_referents = cred_refs_for_attrs.union(&cred_refs_for_predicates).cloned().collect::<Vec<String>>();
+    fn _load_requested_credentials(&self,
+                                   wallet_handle: WalletHandle,
+                                   requested_credentials: &RequestedCredentials) -> IndyResult<HashMap<String, Credential>> {
+        let cred_wallets = self._get_credential_wallets(wallet_handle, requested_credentials)?;
 
-        let mut credentials: HashMap<String, Credential> = HashMap::with_capacity(cred_referents.len());
+        let mut credentials: HashMap<String, Credential> = HashMap::with_capacity(cred_wallets.len());
 
-        for cred_referent in cred_referents.into_iter() {
-            let credential: Credential = self.wallet_service.get_indy_object(wallet_handle, &cred_referent, &RecordOptions::id_value())?;
+        for (cred_referent, cred_wallet_handle) in cred_wallets.into_iter() {
+            let credential: Credential = self.wallet_service.get_indy_object(cred_wallet_handle, &cred_referent, &RecordOptions::id_value())?;
             credentials.insert(cred_referent, credential);
         }
 
-        let proof = self.anoncreds_service.prover.create_proof(&credentials,
-                                                               &proof_req,
-                                                               &requested_credentials,
-                                                               &master_secret.value,
-                                                               schemas,
-                                                               cred_defs,
-                                                               rev_states)?;
+        Ok(credentials)
+    }
+
+    fn _create_proof(credentials: &HashMap<String, Credential>,
+                     proof_req: &ProofRequest,
+                     requested_credentials: &RequestedCredentials,
+                     master_secret: &MasterSecret,
+                     schemas: &HashMap<SchemaId, SchemaV1>,
+                     cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                     rev_states: &RevocationStates) -> IndyResult<String> {
+        let proof = Prover::new().create_proof(credentials,
+                                               proof_req,
+                                               requested_credentials,
+                                               &master_secret.value,
+                                               schemas,
+                                               cred_defs,
+                                               rev_states)?;
+
+        serde_json::to_string(&proof)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize FullProof")
+    }
+
+    fn preview_disclosure(&self,
+                          wallet_handle: WalletHandle,
+                          proof_req: &ProofRequest,
+                          requested_credentials: &RequestedCredentials) -> IndyResult<String> {
+        debug!("preview_disclosure >>> wallet_handle: {:?}, proof_req: {:?}, requested_credentials: {:?}",
+               wallet_handle, proof_req, requested_credentials);
+
+        let credentials = self._load_requested_credentials(wallet_handle, requested_credentials)?;
+
+        let preview = self.anoncreds_service.prover.preview_disclosure(&credentials, proof_req, requested_credentials)?;
+
+        let res = serde_json::to_string(&preview)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize DisclosurePreview")?;
+
+        debug!("preview_disclosure <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn bind_proof(&self,
+                  wallet_handle: WalletHandle,
+                  proof_req: &ProofRequestPayload,
+                  mut proof: Proof,
+                  holder_did: &DidValue) -> IndyResult<String> {
+        debug!("bind_proof >>> wallet_handle: {:?}, proof_req: {:?}, proof: {:?}, holder_did: {:?}", wallet_handle, proof_req, proof, holder_did);
+
+        self.crypto_service.validate_did(holder_did)?;
+
+        if let Some(ref requested_binding) = proof_req.holder_binding {
+            if requested_binding.did != *holder_did {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "Proof Request requires binding to another DID"));
+            }
+        }
+
+        let my_key = get_my_did_signing_key(&self.wallet_service, wallet_handle, holder_did)?;
+
+        let nonce = proof_req.nonce.to_dec()
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Nonce")?;
+
+        let data = proof.holder_binding_data(&nonce)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Proof")?;
+
+        let signature = self.crypto_service.sign(&my_key, &data)?;
+
+        proof.holder_binding = Some(HolderBinding {
+            did: holder_did.clone(),
+            signature: signature.to_base58(),
+        });
 
         let proof_json = serde_json::to_string(&proof)
             .to_indy(IndyErrorKind::InvalidState, "Cannot serialize FullProof")?;
 
-        debug!("create_proof <<< proof_json: {:?}", proof_json);
+        debug!("bind_proof <<< proof_json: {:?}", proof_json);
 
         Ok(proof_json)
     }
@@ -791,6 +1164,13 @@ impl ProverCommandExecutor {
     fn _get_credential_info(&self,
                             referent: &str,
                             credential: Credential) -> CredentialInfo {
+        let attrs_metadata: HashMap<String, AttributeMetadata> =
+            credential.values.0
+                .iter()
+                .filter(|(_, values)| !values.metadata.is_empty())
+                .map(|(attr, values)| (attr.clone(), values.metadata.clone()))
+                .collect();
+
         let credential_values: HashMap<String, String> =
             credential.values.0
                 .into_iter()
@@ -803,10 +1183,39 @@ impl ProverCommandExecutor {
             schema_id: credential.schema_id,
             cred_def_id: credential.cred_def_id,
             rev_reg_id: credential.rev_reg_id,
-            cred_rev_id: credential.signature.extract_index().map(|idx| idx.to_string())
+            cred_rev_id: credential.signature.extract_index().map(|idx| idx.to_string()),
+            attrs_metadata,
         }
     }
 
+    /// Attributes which values aren't tagged (see credential attr tag policy) are omitted from the result.
+    fn _get_projected_credential_info(&self,
+                                      record: &WalletRecord,
+                                      projection: &CredentialProjection) -> IndyResult<ProjectedCredentialInfo> {
+        let referent = record.get_id();
+
+        let tags = record.get_tags()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, format!("Credential tags not found for id: {}", referent)))?;
+
+        let attrs = projection.attrs
+            .iter()
+            .filter_map(|attr|
+                tags.get(&format!("attr::{}::value", attr_common_view(attr)))
+                    .map(|value| (attr.clone(), value.clone())))
+            .collect();
+
+        let tags = projection.tags
+            .iter()
+            .filter_map(|tag| tags.get(tag).map(|value| (tag.clone(), value.clone())))
+            .collect();
+
+        Ok(ProjectedCredentialInfo {
+            referent: referent.to_string(),
+            attrs,
+            tags,
+        })
+    }
+
     fn _get_credential(&self,
                        record: &WalletRecord) -> IndyResult<(String, Credential)> {
         let referent = record.get_id();
@@ -866,9 +1275,8 @@ impl ProverCommandExecutor {
                     interval: interval.clone()
                 });
 
-            if let Some(mut count) = max_count {
-                count -= 1;
-                if count == 0 {
+            if let Some(count) = max_count {
+                if credentials.len() >= count {
                     break;
                 }
             }
@@ -877,9 +1285,86 @@ impl ProverCommandExecutor {
         Ok(credentials)
     }
 
+    // Resolves the wallet holding each requested credential.
+    // Referents without explicit `wallet_handle` are taken from the wallet used for the proof creation.
+    fn _get_credential_wallets(&self,
+                               wallet_handle: WalletHandle,
+                               requested_credentials: &RequestedCredentials) -> IndyResult<HashMap<String, WalletHandle>> {
+        let requested_attrs = requested_credentials.requested_attributes
+            .values()
+            .map(|requested_attr| (&requested_attr.cred_id, requested_attr.wallet_handle));
+
+        let requested_predicates = requested_credentials.requested_predicates
+            .values()
+            .map(|requested_predicate| (&requested_predicate.cred_id, requested_predicate.wallet_handle));
+
+        let mut cred_wallets: HashMap<String, WalletHandle> = HashMap::new();
+
+        for (cred_id, cred_wallet_handle) in requested_attrs.chain(requested_predicates) {
+            let cred_wallet_handle = cred_wallet_handle.unwrap_or(wallet_handle);
+
+            match cred_wallets.entry(cred_id.to_string()) {
+                Entry::Occupied(entry) => {
+                    if *entry.get() != cred_wallet_handle {
+                        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                           format!("Credential {} is requested from different wallets: {:?} and {:?}", cred_id, entry.get(), cred_wallet_handle)));
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(cred_wallet_handle);
+                }
+            }
+        }
+
+        Ok(cred_wallets)
+    }
 
     fn _wallet_get_master_secret(&self, wallet_handle: WalletHandle, key: &str) -> IndyResult<MasterSecret> {
         self.wallet_service.get_indy_object(wallet_handle, &key, &RecordOptions::id_value())
     }
+
+    fn _count_master_secret_credentials(&self, wallet_handle: WalletHandle, master_secret_id: &str) -> IndyResult<usize> {
+        let query_json = json!({ MASTER_SECRET_ID_TAG: master_secret_id }).to_string();
+        let options_json = json!({
+            "retrieveRecords": false,
+            "retrieveTotalCount": true,
+        }).to_string();
+
+        let credentials_search =
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query_json, &options_json)?;
+
+        Ok(credentials_search.get_total_count()?.unwrap_or(0))
+    }
+
+    fn _count_untagged_credentials(&self, wallet_handle: WalletHandle) -> IndyResult<usize> {
+        let options_json = json!({
+            "retrieveType": false,
+            "retrieveValue": false,
+            "retrieveTags": true,
+        }).to_string();
+
+        let mut credentials_search =
+            self.wallet_service.search_indy_records::<Credential>(wallet_handle, "{}", &options_json)?;
+
+        let mut count = 0;
+
+        while let Some(credential_record) = credentials_search.fetch_next_record()? {
+            if credential_record.get_tags().map_or(true, |tags| !tags.contains_key(MASTER_SECRET_ID_TAG)) {
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    fn _is_same_master_secret(&self, master_secret: &MasterSecret, other_master_secret: &MasterSecret) -> IndyResult<bool> {
+        let master_secret = serde_json::to_string(master_secret)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize MasterSecret")?;
+
+        let other_master_secret = serde_json::to_string(other_master_secret)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize MasterSecret")?;
+
+        Ok(master_secret == other_master_secret)
+    }
 }
 
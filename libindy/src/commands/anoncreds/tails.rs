@@ -64,23 +64,33 @@ impl RevocationTailsAccessor for SDKTailsAccessor {
     }
 }
 
-pub fn store_tails_from_generator(service: Rc<BlobStorageService>,
-                                  writer_handle: i32,
-                                  rtg: &mut RevocationTailsGenerator) -> IndyResult<(String, String)> {
-    debug!("store_tails_from_generator >>> writer_handle: {:?}", writer_handle);
+/// Builds content of the tails blob. Blob storage isn't used, so tails can be generated outside of the commands thread.
+pub fn generate_tails(rtg: &mut RevocationTailsGenerator) -> IndyResult<Vec<u8>> {
+    debug!("generate_tails >>> count: {:?}", rtg.count());
 
-    let blob_handle = service.create_blob(writer_handle)?;
+    let mut tails = Vec::with_capacity(TAILS_BLOB_TAG_SZ as usize + TAIL_SIZE * rtg.count() as usize);
 
-    let version = vec![0u8, TAILS_BLOB_TAG_SZ];
-    service.append(blob_handle, version.as_slice())?;
+    tails.extend_from_slice(&[0u8, TAILS_BLOB_TAG_SZ]);
 
     while let Some(tail) = rtg.try_next()? {
-        let tail_bytes = tail.to_bytes()?;
-        service.append(blob_handle, tail_bytes.as_slice())?;
+        tails.extend_from_slice(tail.to_bytes()?.as_slice());
     }
 
+    debug!("generate_tails <<< len: {:?}", tails.len());
+    Ok(tails)
+}
+
+pub fn store_tails(service: Rc<BlobStorageService>,
+                   writer_handle: i32,
+                   tails: &[u8]) -> IndyResult<(String, String)> {
+    debug!("store_tails >>> writer_handle: {:?}", writer_handle);
+
+    let blob_handle = service.create_blob(writer_handle)?;
+
+    service.append(blob_handle, tails)?;
+
     let res = service.finalize(blob_handle).map(|(location, hash)| (location, hash.to_base58()))?;
 
-    debug!("store_tails_from_generator <<< res: {:?}", res);
+    debug!("store_tails <<< res: {:?}", res);
     Ok(res)
 }
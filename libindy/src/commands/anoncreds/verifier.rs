@@ -1,14 +1,25 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
+
+use rust_base58::FromBase58;
 
 use crate::domain::anoncreds::credential_definition::{cred_defs_map_to_cred_defs_v1_map, CredentialDefinitionV1, CredentialDefinitionId, CredentialDefinitions};
 use crate::domain::anoncreds::proof::Proof;
 use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestPayload};
+use crate::domain::anoncreds::proof_request_template::{ProofRequestTemplate, ProofRequestTemplateParams};
 use crate::domain::anoncreds::revocation_registry::{rev_regs_map_to_rev_regs_local_map, RevocationRegistryV1, RevocationRegistries};
 use crate::domain::anoncreds::revocation_registry_definition::{rev_reg_defs_map_to_rev_reg_defs_v1_map, RevocationRegistryDefinitionV1, RevocationRegistryId, RevocationRegistryDefinitions};
 use crate::domain::anoncreds::schema::{schemas_map_to_schemas_v1_map, SchemaV1, SchemaId, Schemas};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
+use crate::services::anoncreds::verifier::Verifier;
+use crate::services::crypto::CryptoService;
+use indy_api_types::{IndyHandle, WalletHandle};
+use indy_api_types::validation::Validatable;
+use indy_utils::sequence;
+use indy_wallet::{RecordOptions, WalletService};
 
 pub enum VerifierCommand {
     VerifyProof(
@@ -20,17 +31,57 @@ pub enum VerifierCommand {
         RevocationRegistries, // rev reg entries
         Box<dyn Fn(IndyResult<bool>) + Send>),
     GenerateNonce(
-        Box<dyn Fn(IndyResult<String>) + Send>)
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CreateSession(
+        Schemas, // credential schemas
+        CredentialDefinitions, // credential defs
+        RevocationRegistryDefinitions, // rev reg defs
+        Box<dyn Fn(IndyResult<IndyHandle>) + Send>),
+    VerifyProofWithSession(
+        IndyHandle, // session handle
+        ProofRequest, // proof request
+        Proof, // proof
+        RevocationRegistries, // rev reg entries
+        Box<dyn Fn(IndyResult<bool>) + Send>),
+    CloseSession(
+        IndyHandle, // session handle
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    SaveProofRequestTemplate(
+        WalletHandle,
+        String, // template id
+        ProofRequestTemplate, // proof request template
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    InstantiateProofRequestTemplate(
+        WalletHandle,
+        String, // template id
+        ProofRequestTemplateParams, // template params
+        Box<dyn Fn(IndyResult<String>) + Send>),
+}
+
+/// Ledger artifacts parsed once and reused for verification of many proofs.
+/// Shared with the thread pool verifying proofs.
+struct VerifierSession {
+    schemas: HashMap<SchemaId, SchemaV1>,
+    cred_defs: HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+    rev_reg_defs: HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
 }
 
 pub struct VerifierCommandExecutor {
     anoncreds_service: Rc<AnoncredsService>,
+    wallet_service: Rc<WalletService>,
+    crypto_service: Rc<CryptoService>,
+    sessions: RefCell<HashMap<IndyHandle, Arc<VerifierSession>>>,
 }
 
 impl VerifierCommandExecutor {
-    pub fn new(anoncreds_service: Rc<AnoncredsService>) -> VerifierCommandExecutor {
+    pub fn new(anoncreds_service: Rc<AnoncredsService>,
+               wallet_service: Rc<WalletService>,
+               crypto_service: Rc<CryptoService>) -> VerifierCommandExecutor {
         VerifierCommandExecutor {
             anoncreds_service,
+            wallet_service,
+            crypto_service,
+            sessions: RefCell::new(HashMap::new()),
         }
     }
 
@@ -38,40 +89,204 @@ impl VerifierCommandExecutor {
         match command {
             VerifierCommand::VerifyProof(proof_request, proof, schemas, credential_defs, rev_reg_defs, rev_regs, cb) => {
                 debug!(target: "verifier_command_executor", "VerifyProof command received");
-                cb(self.verify_proof(&proof_request.value(), proof,
-                                     &schemas_map_to_schemas_v1_map(schemas),
-                                     &cred_defs_map_to_cred_defs_v1_map(credential_defs),
-                                     &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
-                                     &rev_regs_map_to_rev_regs_local_map(rev_regs)));
+                self.verify_proof(proof_request, proof,
+                                  schemas_map_to_schemas_v1_map(schemas),
+                                  cred_defs_map_to_cred_defs_v1_map(credential_defs),
+                                  rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
+                                  rev_regs_map_to_rev_regs_local_map(rev_regs), cb);
             }
             VerifierCommand::GenerateNonce(cb) => {
                 debug!(target: "verifier_command_executor", "GenerateNonce command received");
                 cb(self.generate_nonce());
             }
+            VerifierCommand::CreateSession(schemas, credential_defs, rev_reg_defs, cb) => {
+                debug!(target: "verifier_command_executor", "CreateSession command received");
+                cb(self.create_session(schemas, credential_defs, rev_reg_defs));
+            }
+            VerifierCommand::VerifyProofWithSession(session_handle, proof_request, proof, rev_regs, cb) => {
+                debug!(target: "verifier_command_executor", "VerifyProofWithSession command received");
+                self.verify_proof_with_session(session_handle, proof_request, proof,
+                                               rev_regs_map_to_rev_regs_local_map(rev_regs), cb);
+            }
+            VerifierCommand::CloseSession(session_handle, cb) => {
+                debug!(target: "verifier_command_executor", "CloseSession command received");
+                cb(self.close_session(session_handle));
+            }
+            VerifierCommand::SaveProofRequestTemplate(wallet_handle, template_id, template, cb) => {
+                debug!(target: "verifier_command_executor", "SaveProofRequestTemplate command received");
+                cb(self.save_proof_request_template(wallet_handle, &template_id, &template));
+            }
+            VerifierCommand::InstantiateProofRequestTemplate(wallet_handle, template_id, params, cb) => {
+                debug!(target: "verifier_command_executor", "InstantiateProofRequestTemplate command received");
+                cb(self.instantiate_proof_request_template(wallet_handle, &template_id, &params));
+            }
         };
     }
 
     fn verify_proof(&self,
-                    proof_req: &ProofRequestPayload,
+                    proof_req: ProofRequest,
                     proof: Proof,
-                    schemas: &HashMap<SchemaId, SchemaV1>,
-                    cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
-                    rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
-                    rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<bool> {
+                    schemas: HashMap<SchemaId, SchemaV1>,
+                    cred_defs: HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                    rev_reg_defs: HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                    rev_regs: HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                    cb: Box<dyn Fn(IndyResult<bool>) + Send>) {
         debug!("verify_proof >>> proof_req: {:?}, proof: {:?}, schemas: {:?}, cred_defs: {:?},  \
                rev_reg_defs: {:?}, rev_regs: {:?}",
                proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs);
 
-        let result = self.anoncreds_service.verifier.verify(&proof,
-                                                            &proof_req,
-                                                            schemas,
-                                                            cred_defs,
-                                                            rev_reg_defs,
-                                                            rev_regs)?;
+        let session = Arc::new(VerifierSession { schemas, cred_defs, rev_reg_defs });
+
+        self._verify_proof(session, proof_req, proof, rev_regs, Box::new(move |result| {
+            debug!("verify_proof <<< result: {:?}", result);
+            cb(result)
+        }));
+    }
 
-        debug!("verify_proof <<< result: {:?}", result);
+    // Holder binding is checked on the commands thread, but the proof itself is verified on the thread pool
+    // so a long verification doesn't block other commands.
+    fn _verify_proof(&self,
+                     session: Arc<VerifierSession>,
+                     proof_req: ProofRequest,
+                     proof: Proof,
+                     rev_regs: HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                     cb: Box<dyn Fn(IndyResult<bool>) + Send>) {
+        let holder_binding = self._verify_holder_binding(&proof, proof_req.value());
+        let verifier: Verifier = self.anoncreds_service.verifier.clone();
 
-        Ok(result)
+        crate::commands::THREADPOOL.lock().unwrap().execute(move || {
+            let result = verifier.verify(&proof,
+                                         proof_req.value(),
+                                         &session.schemas,
+                                         &session.cred_defs,
+                                         &session.rev_reg_defs,
+                                         &rev_regs)
+                .and_then(|valid| if valid { holder_binding } else { Ok(false) });
+
+            cb(result)
+        });
+    }
+
+    fn create_session(&self,
+                      schemas: Schemas,
+                      cred_defs: CredentialDefinitions,
+                      rev_reg_defs: RevocationRegistryDefinitions) -> IndyResult<IndyHandle> {
+        debug!("create_session >>> schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?}", schemas, cred_defs, rev_reg_defs);
+
+        let session = Arc::new(VerifierSession {
+            schemas: schemas_map_to_schemas_v1_map(schemas),
+            cred_defs: cred_defs_map_to_cred_defs_v1_map(cred_defs),
+            rev_reg_defs: rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
+        });
+
+        let session_handle = sequence::get_next_id();
+
+        self.sessions.borrow_mut().insert(session_handle, session);
+
+        debug!("create_session <<< session_handle: {:?}", session_handle);
+
+        Ok(session_handle)
+    }
+
+    fn verify_proof_with_session(&self,
+                                 session_handle: IndyHandle,
+                                 proof_req: ProofRequest,
+                                 proof: Proof,
+                                 rev_regs: HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                                 cb: Box<dyn Fn(IndyResult<bool>) + Send>) {
+        debug!("verify_proof_with_session >>> session_handle: {:?}, proof_req: {:?}, proof: {:?}, rev_regs: {:?}",
+               session_handle, proof_req, proof, rev_regs);
+
+        let session = try_cb!(self.sessions.borrow().get(&session_handle).cloned()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid VerifierSession handle: {:?}", session_handle))), cb);
+
+        self._verify_proof(session, proof_req, proof, rev_regs, Box::new(move |result| {
+            debug!("verify_proof_with_session <<< result: {:?}", result);
+            cb(result)
+        }));
+    }
+
+    fn close_session(&self, session_handle: IndyHandle) -> IndyResult<()> {
+        debug!("close_session >>> session_handle: {:?}", session_handle);
+
+        self.sessions.borrow_mut().remove(&session_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid VerifierSession handle: {:?}", session_handle)))?;
+
+        debug!("close_session <<<");
+
+        Ok(())
+    }
+
+    fn save_proof_request_template(&self,
+                                   wallet_handle: WalletHandle,
+                                   template_id: &str,
+                                   template: &ProofRequestTemplate) -> IndyResult<()> {
+        debug!("save_proof_request_template >>> wallet_handle: {:?}, template_id: {:?}, template: {:?}", wallet_handle, template_id, template);
+
+        self.wallet_service.upsert_indy_object(wallet_handle, template_id, template)?;
+
+        debug!("save_proof_request_template <<<");
+
+        Ok(())
+    }
+
+    fn instantiate_proof_request_template(&self,
+                                          wallet_handle: WalletHandle,
+                                          template_id: &str,
+                                          params: &ProofRequestTemplateParams) -> IndyResult<String> {
+        debug!("instantiate_proof_request_template >>> wallet_handle: {:?}, template_id: {:?}, params: {:?}", wallet_handle, template_id, params);
+
+        let template: ProofRequestTemplate =
+            self.wallet_service.get_indy_object(wallet_handle, template_id, &RecordOptions::id_value())?;
+
+        let mut proof_req = template.instantiate(params)?;
+
+        // Every instantiated proof request gets a fresh nonce to prevent replay of proofs built for another request
+        proof_req["nonce"] = serde_json::Value::String(self.generate_nonce()?);
+
+        let proof_req: ProofRequest = serde_json::from_value(proof_req)
+            .to_indy(IndyErrorKind::InvalidStructure, "Proof Request Template doesn't produce valid Proof Request")?;
+
+        proof_req.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        let res = serde_json::to_string(&proof_req)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize ProofRequest")?;
+
+        debug!("instantiate_proof_request_template <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _verify_holder_binding(&self,
+                              proof: &Proof,
+                              proof_req: &ProofRequestPayload) -> IndyResult<bool> {
+        // binding is checked only if required by proof request as only the verifier knows the key expected for the DID
+        let requested_binding = match proof_req.holder_binding {
+            Some(ref requested_binding) => requested_binding,
+            None => return Ok(true)
+        };
+
+        let holder_binding = match proof.holder_binding {
+            Some(ref holder_binding) => holder_binding,
+            None => return Ok(false)
+        };
+
+        self.crypto_service.validate_key(&requested_binding.verkey)?;
+
+        if holder_binding.did != requested_binding.did {
+            return Ok(false);
+        }
+
+        let nonce = proof_req.nonce.to_dec()
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Nonce")?;
+
+        let data = proof.holder_binding_data(&nonce)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Proof")?;
+
+        let signature = holder_binding.signature.from_base58()?;
+
+        self.crypto_service.verify(&requested_binding.verkey, &data, &signature)
     }
 
     fn generate_nonce(&self) -> IndyResult<String> {
@@ -1,23 +1,48 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use indy_api_types::domain::wallet::Tags;
 use crate::domain::anoncreds::schema::SchemaId;
+use crate::domain::anoncreds::credential::Credential;
 use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
+use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryId};
+use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::anoncreds::revocation_state::RevocationState;
 use indy_api_types::errors::prelude::*;
-use indy_wallet::{WalletService, WalletRecord};
-use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
+use indy_wallet::{RecordOptions, WalletService, WalletRecord};
+use indy_api_types::{WalletHandle, PoolHandle, CommandHandle, IndyHandle};
 use crate::commands::{Command, CommandExecutor};
+use crate::commands::anoncreds::AnoncredsCommand;
+use crate::commands::anoncreds::prover::ProverCommand;
 use crate::commands::ledger::LedgerCommand;
-use crate::domain::cache::{GetCacheOptions, PurgeOptions};
+use crate::domain::cache::{GetCacheOptions, NymCacheOptions, PurgeOptions, RevocationPrefetchOptions};
 use crate::domain::crypto::did::DidValue;
+use crate::domain::ledger::nym::NymData;
+use crate::services::ledger::LedgerService;
+use crate::services::pool::PoolService;
+use crate::services::blob_storage::BlobStorageService;
+use crate::utils::crypto::verkey_builder::build_full_verkey;
 
 use indy_utils::next_command_handle;
 
 const CRED_DEF_CACHE: &str = "cred_def_cache";
 const SCHEMA_CACHE: &str = "schema_cache";
+const NYM_CACHE: &str = "nym_cache";
+const REV_STATE_CACHE: &str = "rev_state_cache";
+const CRED_USAGE: &str = "cred_usage";
+
+const DEFAULT_NYM_MIN_FRESH: i32 = 60;
+const DEFAULT_NYM_MAX_STALE: i32 = 3600;
+
+const DEFAULT_PREFETCH_INTERVAL: u64 = 300;
+const DEFAULT_PREFETCH_FRESHNESS: u64 = 600;
+const DEFAULT_PREFETCH_MAX_REQUESTS: usize = 10;
+const DEFAULT_PREFETCH_MAX_CREDENTIALS: usize = 20;
 
 pub enum CacheCommand {
     GetSchema(PoolHandle,
@@ -50,12 +75,105 @@ pub enum CacheCommand {
     PurgeCredDefCache(WalletHandle,
                       PurgeOptions, // options
                       Box<dyn Fn(IndyResult<()>) + Send>),
+    PurgeNymCache(WalletHandle,
+                  PurgeOptions, // options
+                  Box<dyn Fn(IndyResult<()>) + Send>),
+    ResolveNymKeys(PoolHandle,
+                   WalletHandle,
+                   Vec<DidValue>, // dids
+                   NymCacheOptions, // options
+                   Box<dyn Fn(IndyResult<Vec<String>>) + Send>),
+    ResolveNymKeysContinue(
+        PoolHandle,
+        WalletHandle,
+        String, // cache record id
+        DidValue, // did
+        IndyResult<String>, // get_nym_response
+        CommandHandle, // cb_id
+    ),
+    RefreshNymContinue(
+        WalletHandle,
+        String, // cache record id
+        DidValue, // did
+        IndyResult<String>, // get_nym_response
+    ),
+    StartRevocationPrefetch(
+        PoolHandle,
+        WalletHandle,
+        IndyHandle, // blob storage reader handle
+        RevocationPrefetchOptions, // options
+        Box<dyn Fn(IndyResult<IndyHandle>) + Send>),
+    StopRevocationPrefetch(
+        IndyHandle, // prefetch handle
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    GetCachedRevocationState(
+        WalletHandle,
+        String, // credential id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    PrefetchRevocationStates(
+        IndyHandle, // prefetch handle
+    ),
+    PrefetchRevocationStateContinue(
+        RevocationStateFetch,
+        IndyResult<String>, // GET_REVOC_REG_DEF or GET_REVOC_REG_DELTA response or revocation state
+    ),
+    CredentialsUsed(
+        HashMap<String, WalletHandle>, // ids of credentials used to build the proof and their wallets
+    ),
+}
+
+/// Verkeys of DIDs resolved so far for the pending `ResolveNymKeys` command.
+struct PendingNymKeys {
+    dids: Vec<DidValue>,
+    keys: Vec<String>,
+    options: NymCacheOptions,
+    cb: Box<dyn Fn(IndyResult<Vec<String>>) + Send>,
+}
+
+/// Revocation states of frequently used credentials kept fresh in background by `StartRevocationPrefetch` command.
+struct RevocationPrefetch {
+    pool_handle: PoolHandle,
+    wallet_handle: WalletHandle,
+    blob_storage_reader_handle: IndyHandle,
+    max_credentials: usize,
+    freshness: u64,
+    max_requests: usize,
+    pending: usize, // number of revocation states being fetched in the current round
+    stopped: Arc<AtomicBool>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum RevocationStateFetchStep {
+    Definition,
+    Delta,
+    State,
+    Done,
+}
+
+/// State of the fetch of revocation state of the single credential.
+#[derive(Clone, Debug)]
+pub struct RevocationStateFetch {
+    prefetch_handle: IndyHandle,
+    cred_id: String,
+    rev_reg_id: RevocationRegistryId,
+    cred_rev_id: String,
+    cached_rev_state: Option<String>,
+    rev_reg_def: Option<String>,
+    rev_reg_delta: Option<String>,
+    timestamp: u64,
+    step: RevocationStateFetchStep,
 }
 
 pub struct CacheCommandExecutor {
     wallet_service: Rc<WalletService>,
+    ledger_service: Rc<LedgerService>,
+    pool_service: Rc<PoolService>,
+    blob_storage_service: Rc<BlobStorageService>,
 
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    pending_nym_keys: RefCell<HashMap<CommandHandle, PendingNymKeys>>,
+    refreshing_nyms: RefCell<HashSet<(WalletHandle, String)>>,
+    revocation_prefetches: RefCell<HashMap<IndyHandle, RevocationPrefetch>>,
 }
 
 macro_rules! check_cache {
@@ -80,10 +198,19 @@ macro_rules! check_cache {
 }
 
 impl CacheCommandExecutor {
-    pub fn new(wallet_service: Rc<WalletService>) -> CacheCommandExecutor {
+    pub fn new(wallet_service: Rc<WalletService>,
+               ledger_service: Rc<LedgerService>,
+               pool_service: Rc<PoolService>,
+               blob_storage_service: Rc<BlobStorageService>) -> CacheCommandExecutor {
         CacheCommandExecutor {
             wallet_service,
+            ledger_service,
+            pool_service,
+            blob_storage_service,
             pending_callbacks: RefCell::new(HashMap::new()),
+            pending_nym_keys: RefCell::new(HashMap::new()),
+            refreshing_nyms: RefCell::new(HashSet::new()),
+            revocation_prefetches: RefCell::new(HashMap::new()),
         }
     }
 
@@ -113,6 +240,46 @@ impl CacheCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "PurgeCredDefCache command received");
                 cb(self.purge_cred_def_cache(wallet_handle, options));
             }
+            CacheCommand::PurgeNymCache(wallet_handle, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "PurgeNymCache command received");
+                cb(self.purge_nym_cache(wallet_handle, options));
+            }
+            CacheCommand::ResolveNymKeys(pool_handle, wallet_handle, dids, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "ResolveNymKeys command received");
+                self.resolve_nym_keys(pool_handle, wallet_handle, dids, options, cb);
+            }
+            CacheCommand::ResolveNymKeysContinue(pool_handle, wallet_handle, cache_id, did, get_nym_response, cb_id) => {
+                debug!(target: "non_secrets_command_executor", "ResolveNymKeysContinue command received");
+                self._resolve_nym_keys_continue(pool_handle, wallet_handle, cache_id, did, get_nym_response, cb_id);
+            }
+            CacheCommand::RefreshNymContinue(wallet_handle, cache_id, did, get_nym_response) => {
+                debug!(target: "non_secrets_command_executor", "RefreshNymContinue command received");
+                self._refresh_nym_continue(wallet_handle, cache_id, did, get_nym_response);
+            }
+            CacheCommand::StartRevocationPrefetch(pool_handle, wallet_handle, blob_storage_reader_handle, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "StartRevocationPrefetch command received");
+                cb(self.start_revocation_prefetch(pool_handle, wallet_handle, blob_storage_reader_handle, options));
+            }
+            CacheCommand::StopRevocationPrefetch(prefetch_handle, cb) => {
+                debug!(target: "non_secrets_command_executor", "StopRevocationPrefetch command received");
+                cb(self.stop_revocation_prefetch(prefetch_handle));
+            }
+            CacheCommand::GetCachedRevocationState(wallet_handle, cred_id, cb) => {
+                debug!(target: "non_secrets_command_executor", "GetCachedRevocationState command received");
+                cb(self.get_cached_revocation_state(wallet_handle, &cred_id));
+            }
+            CacheCommand::PrefetchRevocationStates(prefetch_handle) => {
+                debug!(target: "non_secrets_command_executor", "PrefetchRevocationStates command received");
+                self._prefetch_revocation_states(prefetch_handle);
+            }
+            CacheCommand::PrefetchRevocationStateContinue(fetch, response) => {
+                debug!(target: "non_secrets_command_executor", "PrefetchRevocationStateContinue command received");
+                self._prefetch_revocation_state_continue(fetch, response);
+            }
+            CacheCommand::CredentialsUsed(cred_wallets) => {
+                debug!(target: "non_secrets_command_executor", "CredentialsUsed command received");
+                self._credentials_used(cred_wallets);
+            }
         }
     }
 
@@ -169,21 +336,29 @@ impl CacheCommandExecutor {
                               which_cache: &str) -> IndyResult<()>
     {
         if !options.no_store.unwrap_or(false) {
-            let mut tags = Tags::new();
-            let ts = match SystemTime::now().duration_since(UNIX_EPOCH) {
-                Ok(ts) => ts.as_secs() as i32,
-                Err(err) => {
-                    warn!("Cannot get time: {:?}", err);
-                    0
-                }
-            };
-            tags.insert("timestamp".to_string(), ts.to_string());
-            let _ignore = self.wallet_service.delete_record(wallet_handle, which_cache, &schema_id);
-            self.wallet_service.add_record(wallet_handle, which_cache, &schema_id, &schema_json, &tags)?
+            self._store_record(wallet_handle, schema_id, schema_json, which_cache)?
         }
         Ok(())
     }
 
+    fn _store_record(&self,
+                     wallet_handle: WalletHandle,
+                     id: &str,
+                     value: &str,
+                     which_cache: &str) -> IndyResult<()> {
+        let mut tags = Tags::new();
+        let ts = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(ts) => ts.as_secs() as i32,
+            Err(err) => {
+                warn!("Cannot get time: {:?}", err);
+                0
+            }
+        };
+        tags.insert("timestamp".to_string(), ts.to_string());
+        let _ignore = self.wallet_service.delete_record(wallet_handle, which_cache, id);
+        self.wallet_service.add_record(wallet_handle, which_cache, id, value, &tags)
+    }
+
     fn _get_schema_continue(&self,
                             wallet_handle: WalletHandle,
                             ledger_response: IndyResult<(String, String)>,
@@ -245,18 +420,22 @@ impl CacheCommandExecutor {
 
     fn get_record_from_cache(&self, wallet_handle: WalletHandle, id: &str, options: &GetCacheOptions, which_cache: &str) -> Result<Option<WalletRecord>, IndyError> {
         if !options.no_cache.unwrap_or(false) {
-            let options_json = json!({
-                "retrieveType": false,
-                "retrieveValue": true,
-                "retrieveTags": true,
-            }).to_string();
-            match self.wallet_service.get_record(wallet_handle, which_cache, &id, &options_json) {
-                Ok(record) => Ok(Some(record)),
-                Err(err) => if err.kind() == IndyErrorKind::WalletItemNotFound { Ok(None) } else { Err(err) }
-            }
+            self._get_record(wallet_handle, id, which_cache)
         } else { Ok(None) }
     }
 
+    fn _get_record(&self, wallet_handle: WalletHandle, id: &str, which_cache: &str) -> Result<Option<WalletRecord>, IndyError> {
+        let options_json = json!({
+            "retrieveType": false,
+            "retrieveValue": true,
+            "retrieveTags": true,
+        }).to_string();
+        match self.wallet_service.get_record(wallet_handle, which_cache, &id, &options_json) {
+            Ok(record) => Ok(Some(record)),
+            Err(err) => if err.kind() == IndyErrorKind::WalletItemNotFound { Ok(None) } else { Err(err) }
+        }
+    }
+
     fn _get_cred_def_continue(&self, wallet_handle: WalletHandle, ledger_response: IndyResult<(String, String)>, options: GetCacheOptions, cb_id: CommandHandle) {
         let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
 
@@ -346,4 +525,548 @@ impl CacheCommandExecutor {
 
         Ok(())
     }
+
+    fn purge_nym_cache(&self,
+                       wallet_handle: WalletHandle,
+                       options: PurgeOptions) -> IndyResult<()> {
+        trace!("purge_nym_cache >>> wallet_handle: {:?}, options: {:?}", wallet_handle, options);
+
+        let max_age = options.max_age.unwrap_or(-1);
+        let query_json = CacheCommandExecutor::build_query_json(max_age)?;
+
+        let options_json = json!({
+            "retrieveType": false,
+            "retrieveValue": false,
+            "retrieveTags": false,
+        }).to_string();
+
+        let mut search = self.wallet_service.search_records(
+            wallet_handle,
+            NYM_CACHE,
+            &query_json,
+            &options_json,
+        )?;
+
+        while let Some(record) = search.fetch_next_record()? {
+            self.wallet_service.delete_record(wallet_handle, NYM_CACHE, record.get_id())?;
+        }
+
+        trace!("purge_nym_cache <<< res: ()");
+
+        Ok(())
+    }
+
+    fn resolve_nym_keys(&self,
+                        pool_handle: PoolHandle,
+                        wallet_handle: WalletHandle,
+                        dids: Vec<DidValue>,
+                        options: NymCacheOptions,
+                        cb: Box<dyn Fn(IndyResult<Vec<String>>) + Send>) {
+        trace!("resolve_nym_keys >>> pool_handle: {:?}, wallet_handle: {:?}, dids: {:?}, options: {:?}",
+               pool_handle, wallet_handle, dids, options);
+
+        self._resolve_nym_keys(pool_handle, wallet_handle, PendingNymKeys { dids, keys: Vec::new(), options, cb });
+    }
+
+    // Resolves keys from the cache in order and stops at the first DID requiring the ledger round trip.
+    fn _resolve_nym_keys(&self, pool_handle: PoolHandle, wallet_handle: WalletHandle, mut pending: PendingNymKeys) {
+        while let Some(did) = pending.dids.get(pending.keys.len()).cloned() {
+            let did_pool_handle = self.pool_service.pool_for_did(pool_handle, &did);
+
+            let cache_id = match self._nym_cache_id(did_pool_handle, &did) {
+                Ok(cache_id) => cache_id,
+                Err(err) => return (pending.cb)(Err(err))
+            };
+
+            match self._get_cached_nym_verkey(did_pool_handle, wallet_handle, &cache_id, &did, &pending.options) {
+                Ok(Some(verkey)) => pending.keys.push(verkey),
+                Ok(None) => {
+                    let cb_id = next_command_handle();
+                    self.pending_nym_keys.borrow_mut().insert(cb_id, pending);
+
+                    return self._submit_get_nym(did_pool_handle, did, move |did, get_nym_response|
+                        CacheCommand::ResolveNymKeysContinue(pool_handle, wallet_handle, cache_id.clone(), did, get_nym_response, cb_id));
+                }
+                Err(err) => return (pending.cb)(Err(err))
+            }
+        }
+
+        trace!("resolve_nym_keys <<< res: {:?}", pending.keys);
+
+        (pending.cb)(Ok(pending.keys))
+    }
+
+    // The same DID may be written to different ledgers, so cached verkeys are scoped by the pool name.
+    fn _nym_cache_id(&self, pool_handle: PoolHandle, did: &DidValue) -> IndyResult<String> {
+        let pool_name = self.pool_service.get_pool_name(pool_handle)?;
+        Ok(format!("{}:{}", pool_name, did.0))
+    }
+
+    // Returns cached verkey if it isn't older than the staleness budget.
+    // Verkey older than `min_fresh` is refreshed in background to notice keys rotation.
+    fn _get_cached_nym_verkey(&self,
+                              pool_handle: PoolHandle,
+                              wallet_handle: WalletHandle,
+                              cache_id: &str,
+                              did: &DidValue,
+                              options: &NymCacheOptions) -> IndyResult<Option<String>> {
+        if options.no_cache.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let record = match self._get_record(wallet_handle, cache_id, NYM_CACHE)? {
+            Some(record) => record,
+            None => return Ok(None)
+        };
+
+        let verkey = match record.get_value() {
+            Some(verkey) => verkey.to_string(),
+            None => return Ok(None)
+        };
+
+        let timestamp: i32 = record.get_tags()
+            .and_then(|tags| tags.get("timestamp"))
+            .and_then(|timestamp| timestamp.parse().ok())
+            .unwrap_or(0);
+
+        let age = CacheCommandExecutor::get_seconds_since_epoch()? - timestamp;
+        let min_fresh = options.min_fresh.unwrap_or(DEFAULT_NYM_MIN_FRESH);
+        let max_stale = options.max_stale.unwrap_or(DEFAULT_NYM_MAX_STALE);
+
+        if min_fresh < 0 || age <= min_fresh {
+            return Ok(Some(verkey));
+        }
+
+        if max_stale < 0 || age <= max_stale {
+            self._refresh_nym(pool_handle, wallet_handle, cache_id, did, options);
+            return Ok(Some(verkey));
+        }
+
+        Ok(None)
+    }
+
+    fn _refresh_nym(&self, pool_handle: PoolHandle, wallet_handle: WalletHandle, cache_id: &str, did: &DidValue, options: &NymCacheOptions) {
+        if options.no_store.unwrap_or(false) {
+            return;
+        }
+
+        // Refresh of the same DID is already in progress
+        if !self.refreshing_nyms.borrow_mut().insert((wallet_handle, cache_id.to_string())) {
+            return;
+        }
+
+        let cache_id = cache_id.to_string();
+
+        self._submit_get_nym(pool_handle, did.clone(), move |did, get_nym_response|
+            CacheCommand::RefreshNymContinue(wallet_handle, cache_id.clone(), did, get_nym_response));
+    }
+
+    fn _refresh_nym_continue(&self, wallet_handle: WalletHandle, cache_id: String, did: DidValue, get_nym_response: IndyResult<String>) {
+        self.refreshing_nyms.borrow_mut().remove(&(wallet_handle, cache_id.clone()));
+
+        let res = self._parse_nym_verkey(&did, get_nym_response)
+            .and_then(|verkey| self._store_record(wallet_handle, &cache_id, &verkey, NYM_CACHE));
+
+        if let Err(err) = res {
+            warn!("Cannot refresh cached verkey of DID {:?}: {:?}", did, err);
+        }
+    }
+
+    fn _resolve_nym_keys_continue(&self,
+                                  pool_handle: PoolHandle,
+                                  wallet_handle: WalletHandle,
+                                  cache_id: String,
+                                  did: DidValue,
+                                  get_nym_response: IndyResult<String>,
+                                  cb_id: CommandHandle) {
+        let mut pending = match self.pending_nym_keys.borrow_mut().remove(&cb_id) {
+            Some(pending) => pending,
+            None => return error!("No pending command for id: {}", cb_id)
+        };
+
+        let verkey = match self._parse_nym_verkey(&did, get_nym_response) {
+            Ok(verkey) => verkey,
+            Err(err) => return (pending.cb)(Err(err))
+        };
+
+        if !pending.options.no_store.unwrap_or(false) {
+            if let Err(err) = self._store_record(wallet_handle, &cache_id, &verkey, NYM_CACHE) {
+                return (pending.cb)(Err(err));
+            }
+        }
+
+        pending.keys.push(verkey);
+
+        self._resolve_nym_keys(pool_handle, wallet_handle, pending);
+    }
+
+    fn _submit_get_nym<F>(&self, pool_handle: PoolHandle, did: DidValue, continuation: F)
+        where F: Fn(DidValue, IndyResult<String>) -> CacheCommand + Send + 'static {
+        let get_nym_request = match self.ledger_service.build_get_nym_request(None, &did) {
+            Ok(get_nym_request) => get_nym_request,
+            Err(err) => return CommandExecutor::instance().send(Command::Cache(continuation(did, Err(err)))).unwrap()
+        };
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::SubmitRequest(
+                    pool_handle,
+                    get_nym_request,
+                    Box::new(move |get_nym_response| {
+                        CommandExecutor::instance().send(Command::Cache(continuation(did.clone(), get_nym_response))).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _parse_nym_verkey(&self, did: &DidValue, get_nym_response: IndyResult<String>) -> IndyResult<String> {
+        let nym_data = self.ledger_service.parse_get_nym_response(&get_nym_response?)?;
+
+        let nym_data: NymData = serde_json::from_str(&nym_data)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize NymData")?;
+
+        build_full_verkey(&did.to_unqualified().0, nym_data.verkey.as_ref().map(String::as_str))
+    }
+
+    fn start_revocation_prefetch(&self,
+                                 pool_handle: PoolHandle,
+                                 wallet_handle: WalletHandle,
+                                 blob_storage_reader_handle: IndyHandle,
+                                 options: RevocationPrefetchOptions) -> IndyResult<IndyHandle> {
+        trace!("start_revocation_prefetch >>> pool_handle: {:?}, wallet_handle: {:?}, blob_storage_reader_handle: {:?}, options: {:?}",
+               pool_handle, wallet_handle, blob_storage_reader_handle, options);
+
+        let interval = options.interval.unwrap_or(DEFAULT_PREFETCH_INTERVAL);
+        let max_requests = options.max_requests.unwrap_or(DEFAULT_PREFETCH_MAX_REQUESTS);
+        let max_credentials = options.max_credentials.unwrap_or(DEFAULT_PREFETCH_MAX_CREDENTIALS);
+
+        if interval == 0 || max_requests == 0 || max_credentials == 0 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Revocation prefetch interval, max requests and max credentials must be positive"));
+        }
+
+        self.wallet_service.check(wallet_handle)?;
+        self.blob_storage_service.check_reader(blob_storage_reader_handle)?;
+
+        let prefetch_handle = next_command_handle();
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        self.revocation_prefetches.borrow_mut().insert(prefetch_handle, RevocationPrefetch {
+            pool_handle,
+            wallet_handle,
+            blob_storage_reader_handle,
+            max_credentials,
+            freshness: options.freshness.unwrap_or(DEFAULT_PREFETCH_FRESHNESS),
+            max_requests,
+            pending: 0,
+            stopped: stopped.clone(),
+        });
+
+        // Ticker thread only schedules prefetch rounds, all the work is done by the commands thread
+        thread::spawn(move || {
+            loop {
+                if CommandExecutor::instance().send(Command::Cache(CacheCommand::PrefetchRevocationStates(prefetch_handle))).is_err() {
+                    return;
+                }
+
+                for _ in 0..interval {
+                    if stopped.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    thread::sleep(Duration::from_secs(1));
+                }
+            }
+        });
+
+        trace!("start_revocation_prefetch <<< prefetch_handle: {:?}", prefetch_handle);
+
+        Ok(prefetch_handle)
+    }
+
+    fn stop_revocation_prefetch(&self, prefetch_handle: IndyHandle) -> IndyResult<()> {
+        trace!("stop_revocation_prefetch >>> prefetch_handle: {:?}", prefetch_handle);
+
+        let prefetch = self.revocation_prefetches.borrow_mut().remove(&prefetch_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid revocation prefetch handle"))?;
+
+        prefetch.stopped.store(true, Ordering::SeqCst);
+
+        trace!("stop_revocation_prefetch <<< res: ()");
+
+        Ok(())
+    }
+
+    fn get_cached_revocation_state(&self, wallet_handle: WalletHandle, cred_id: &str) -> IndyResult<String> {
+        trace!("get_cached_revocation_state >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
+
+        let rev_state = self._get_record(wallet_handle, cred_id, REV_STATE_CACHE)?
+            .and_then(|record| record.get_value().map(String::from))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, format!("Revocation state of credential {:?} isn't prefetched", cred_id)))?;
+
+        trace!("get_cached_revocation_state <<< rev_state: {:?}", rev_state);
+
+        Ok(rev_state)
+    }
+
+    fn _prefetch_revocation_states(&self, prefetch_handle: IndyHandle) {
+        let mut prefetches = self.revocation_prefetches.borrow_mut();
+
+        let prefetch = match prefetches.get_mut(&prefetch_handle) {
+            Some(prefetch) => prefetch,
+            None => return // stopped
+        };
+
+        // Prefetching can't be continued once the wallet or blob storage reader is closed
+        let handles = self.wallet_service.check(prefetch.wallet_handle)
+            .and_then(|_| self.blob_storage_service.check_reader(prefetch.blob_storage_reader_handle));
+
+        if let Err(err) = handles {
+            warn!("Revocation prefetch is stopped, prefetch_handle: {:?}, err: {:?}", prefetch_handle, err);
+            prefetch.stopped.store(true, Ordering::SeqCst);
+            prefetches.remove(&prefetch_handle);
+            return;
+        }
+
+        // The next round isn't started until all revocation states of the previous one are fetched
+        if prefetch.pending > 0 {
+            debug!("Previous revocation prefetch round isn't finished yet, prefetch_handle: {:?}", prefetch_handle);
+            return;
+        }
+
+        let now = match CacheCommandExecutor::get_seconds_since_epoch() {
+            Ok(now) => now as u64,
+            Err(_) => return
+        };
+
+        let cred_ids = match self._frequently_used_credentials(prefetch.wallet_handle, prefetch.max_credentials) {
+            Ok(cred_ids) => cred_ids,
+            Err(err) => return warn!("Frequently used credentials can't be found, prefetch_handle: {:?}, err: {:?}", prefetch_handle, err)
+        };
+
+        let mut fetches = Vec::new();
+
+        for cred_id in cred_ids.iter() {
+            if fetches.len() >= prefetch.max_requests {
+                break;
+            }
+
+            match self._stale_revocation_state_fetch(prefetch, prefetch_handle, cred_id, now) {
+                Ok(Some(fetch)) => fetches.push(fetch),
+                Ok(None) => {}
+                Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {
+                    // Credential is deleted from the wallet
+                    let _ignore = self.wallet_service.delete_record(prefetch.wallet_handle, CRED_USAGE, cred_id);
+                }
+                Err(err) => warn!("Revocation state of credential {:?} can't be prefetched: {:?}", cred_id, err)
+            }
+        }
+
+        prefetch.pending = fetches.len();
+        let pool_handle = prefetch.pool_handle;
+        drop(prefetches);
+
+        for fetch in fetches {
+            self._prefetch_revocation_state_next(pool_handle, fetch);
+        }
+    }
+
+    // Returns ids of credentials most often used to build proofs
+    fn _frequently_used_credentials(&self, wallet_handle: WalletHandle, max_credentials: usize) -> IndyResult<Vec<String>> {
+        let options_json = json!({
+            "retrieveType": false,
+            "retrieveValue": true,
+            "retrieveTags": false,
+        }).to_string();
+
+        let mut search = self.wallet_service.search_records(wallet_handle, CRED_USAGE, "{}", &options_json)?;
+
+        let mut usages: Vec<(String, u64)> = Vec::new();
+
+        while let Some(record) = search.fetch_next_record()? {
+            let count = record.get_value().and_then(|count| count.parse().ok()).unwrap_or(0);
+            usages.push((record.get_id().to_string(), count));
+        }
+
+        usages.sort_by(|(_, count_a), (_, count_b)| count_b.cmp(count_a));
+
+        Ok(usages.into_iter().take(max_credentials).map(|(cred_id, _)| cred_id).collect())
+    }
+
+    fn _credentials_used(&self, cred_wallets: HashMap<String, WalletHandle>) {
+        for (cred_id, wallet_handle) in cred_wallets {
+            let res = self._get_record(wallet_handle, &cred_id, CRED_USAGE)
+                .and_then(|record| {
+                    let count: u64 = record.as_ref()
+                        .and_then(|record| record.get_value())
+                        .and_then(|count| count.parse().ok())
+                        .unwrap_or(0);
+
+                    self._store_record(wallet_handle, &cred_id, &(count + 1).to_string(), CRED_USAGE)
+                });
+
+            if let Err(err) = res {
+                warn!("Usage of credential {:?} can't be recorded: {:?}", cred_id, err);
+            }
+        }
+    }
+
+    // Returns None if the credential isn't revocable or its cached revocation state is fresh enough
+    fn _stale_revocation_state_fetch(&self,
+                                     prefetch: &RevocationPrefetch,
+                                     prefetch_handle: IndyHandle,
+                                     cred_id: &str,
+                                     now: u64) -> IndyResult<Option<RevocationStateFetch>> {
+        let credential: Credential = self.wallet_service.get_indy_object(prefetch.wallet_handle, cred_id, &RecordOptions::id_value())?;
+
+        let (rev_reg_id, cred_rev_id) = match (credential.rev_reg_id, credential.signature.extract_index()) {
+            (Some(rev_reg_id), Some(cred_rev_id)) => (rev_reg_id, cred_rev_id.to_string()),
+            _ => return Ok(None)
+        };
+
+        let cached = self._get_record(prefetch.wallet_handle, cred_id, REV_STATE_CACHE)?;
+
+        let cached_at = cached.as_ref()
+            .and_then(|record| record.get_tags())
+            .and_then(|tags| tags.get("timestamp"))
+            .and_then(|timestamp| timestamp.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        if cached_at + prefetch.freshness > now {
+            return Ok(None);
+        }
+
+        Ok(Some(RevocationStateFetch {
+            prefetch_handle,
+            cred_id: cred_id.to_string(),
+            rev_reg_id,
+            cred_rev_id,
+            cached_rev_state: cached.and_then(|record| record.get_value().map(String::from)),
+            rev_reg_def: None,
+            rev_reg_delta: None,
+            timestamp: now,
+            step: RevocationStateFetchStep::Definition,
+        }))
+    }
+
+    fn _prefetch_revocation_state_continue(&self, mut fetch: RevocationStateFetch, response: IndyResult<String>) {
+        let (pool_handle, wallet_handle) = match self.revocation_prefetches.borrow().get(&fetch.prefetch_handle) {
+            Some(prefetch) => (prefetch.pool_handle, prefetch.wallet_handle),
+            None => return // stopped
+        };
+
+        let res = match fetch.step {
+            RevocationStateFetchStep::Definition => {
+                response
+                    .and_then(|response| self.ledger_service.parse_get_revoc_reg_def_response(&response))
+                    .map(|(_, rev_reg_def)| {
+                        fetch.rev_reg_def = Some(rev_reg_def);
+                        fetch.step = RevocationStateFetchStep::Delta;
+                    })
+            }
+            RevocationStateFetchStep::Delta => {
+                response
+                    .and_then(|response| self.ledger_service.parse_get_revoc_reg_delta_response(&response))
+                    .map(|(_, rev_reg_delta, timestamp)| {
+                        fetch.rev_reg_delta = Some(rev_reg_delta);
+                        fetch.timestamp = timestamp;
+                        fetch.step = RevocationStateFetchStep::State;
+                    })
+            }
+            RevocationStateFetchStep::State => {
+                response
+                    .and_then(|rev_state| self._store_record(wallet_handle, &fetch.cred_id, &rev_state, REV_STATE_CACHE))
+                    .map(|_| fetch.step = RevocationStateFetchStep::Done)
+            }
+            RevocationStateFetchStep::Done => Ok(())
+        };
+
+        match res {
+            Ok(()) => self._prefetch_revocation_state_next(pool_handle, fetch),
+            Err(err) => {
+                warn!("Revocation state of credential {:?} can't be prefetched: {:?}", fetch.cred_id, err);
+                self._finish_revocation_state_fetch(&fetch);
+            }
+        }
+    }
+
+    fn _prefetch_revocation_state_next(&self, pool_handle: PoolHandle, fetch: RevocationStateFetch) {
+        match fetch.step {
+            RevocationStateFetchStep::Definition => {
+                let request = self.ledger_service.build_get_revoc_reg_def_request(None, &fetch.rev_reg_id);
+                self._submit_revocation_state_fetch(pool_handle, fetch, request);
+            }
+            RevocationStateFetchStep::Delta => {
+                // Cached revocation state is updated with changes made since it was fetched
+                let from = fetch.cached_rev_state.as_ref()
+                    .and_then(|rev_state| serde_json::from_str::<RevocationState>(rev_state).ok())
+                    .map(|rev_state| rev_state.timestamp as i64);
+
+                let request = self.ledger_service.build_get_revoc_reg_delta_request(None, &fetch.rev_reg_id, from, fetch.timestamp as i64);
+                self._submit_revocation_state_fetch(pool_handle, fetch, request);
+            }
+            RevocationStateFetchStep::State => {
+                let blob_storage_reader_handle = match self.revocation_prefetches.borrow().get(&fetch.prefetch_handle) {
+                    Some(prefetch) => prefetch.blob_storage_reader_handle,
+                    None => return
+                };
+
+                match self._build_revocation_state_command(blob_storage_reader_handle, &fetch) {
+                    Ok(command) => CommandExecutor::instance().send(Command::Anoncreds(AnoncredsCommand::Prover(command))).unwrap(),
+                    Err(err) => CommandExecutor::instance().send(Command::Cache(CacheCommand::PrefetchRevocationStateContinue(fetch, Err(err)))).unwrap()
+                }
+            }
+            RevocationStateFetchStep::Done => self._finish_revocation_state_fetch(&fetch)
+        }
+    }
+
+    fn _build_revocation_state_command(&self, blob_storage_reader_handle: IndyHandle, fetch: &RevocationStateFetch) -> IndyResult<ProverCommand> {
+        let rev_reg_def: RevocationRegistryDefinition = serde_json::from_str(fetch.rev_reg_def.as_ref().map(String::as_str).unwrap_or_default())
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize RevocationRegistryDefinition")?;
+
+        let rev_reg_delta: RevocationRegistryDelta = serde_json::from_str(fetch.rev_reg_delta.as_ref().map(String::as_str).unwrap_or_default())
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize RevocationRegistryDelta")?;
+
+        let cached_rev_state = fetch.cached_rev_state.as_ref()
+            .and_then(|rev_state| serde_json::from_str::<RevocationState>(rev_state).ok());
+
+        let continuation = fetch.clone();
+        let cb = Box::new(move |rev_state| {
+            CommandExecutor::instance().send(
+                Command::Cache(CacheCommand::PrefetchRevocationStateContinue(continuation.clone(), rev_state))).unwrap();
+        });
+
+        let command = match cached_rev_state {
+            Some(rev_state) => ProverCommand::UpdateRevocationState(blob_storage_reader_handle, rev_state, rev_reg_def, rev_reg_delta,
+                                                                    fetch.timestamp, fetch.cred_rev_id.clone(), cb),
+            None => ProverCommand::CreateRevocationState(blob_storage_reader_handle, rev_reg_def, rev_reg_delta,
+                                                         fetch.timestamp, fetch.cred_rev_id.clone(), cb)
+        };
+
+        Ok(command)
+    }
+
+    fn _submit_revocation_state_fetch(&self, pool_handle: PoolHandle, fetch: RevocationStateFetch, request: IndyResult<String>) {
+        let request = match request {
+            Ok(request) => request,
+            Err(err) => return CommandExecutor::instance().send(Command::Cache(CacheCommand::PrefetchRevocationStateContinue(fetch, Err(err)))).unwrap()
+        };
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::SubmitRequest(
+                    pool_handle,
+                    request,
+                    Box::new(move |response| {
+                        CommandExecutor::instance().send(Command::Cache(CacheCommand::PrefetchRevocationStateContinue(fetch.clone(), response))).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _finish_revocation_state_fetch(&self, fetch: &RevocationStateFetch) {
+        if let Some(prefetch) = self.revocation_prefetches.borrow_mut().get_mut(&fetch.prefetch_handle) {
+            prefetch.pending = prefetch.pending.saturating_sub(1);
+        }
+    }
 }
@@ -1,29 +1,56 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 
-use crate::domain::crypto::key::{Key, KeyInfo, KeyMetadata};
+use crate::domain::crypto::key::{BlsKeyInfo, Key, KeyAgreementKey, KeyAgreementKeysMigration, KeyInfo, KeyMetadata};
 use crate::domain::crypto::pack::*;
 use indy_api_types::errors::prelude::*;
-use crate::services::crypto::CryptoService;
-use indy_wallet::{RecordOptions, WalletService};
+use crate::services::crypto::{CryptoService, X25519_CRYPTO_TYPE};
+use crate::services::crypto::entropy::PluggedEntropySource;
+use crate::services::crypto::key_vault::PluggedKeyVault;
+use crate::utils::crypto::verkey_builder::split_verkey;
+use indy_wallet::{RecordOptions, SearchOptions, WalletService};
 
 use std::rc::Rc;
 use std::str;
+use std::time::{SystemTime, UNIX_EPOCH};
 use indy_utils::crypto::base64;
 use indy_utils::crypto::chacha20poly1305_ietf;
 use crate::domain::crypto::combo_box::ComboBox;
-use indy_api_types::WalletHandle;
+use crate::domain::crypto::did::{DidKeys, DidValue};
+use crate::domain::cache::NymCacheOptions;
+use crate::commands::{Command, CommandExecutor};
+use crate::commands::cache::CacheCommand;
+use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
+use indy_utils::next_command_handle;
 
 pub const PROTECTED_HEADER_ENC: &str = "xchacha20poly1305_ietf";
 pub const PROTECTED_HEADER_TYP: &str = "JWM/1.0";
 pub const PROTECTED_HEADER_ALG_AUTH: &str = "Authcrypt";
 pub const PROTECTED_HEADER_ALG_ANON: &str = "Anoncrypt";
 
+const MAX_DEAD_LETTERS: usize = 100;
+
+const KEY_AGREEMENT_KEYS_MIGRATION_ID: &str = "key_agreement_keys";
+
 pub enum CryptoCommand {
+    RegisterKeyVault(
+        String, // key vault name
+        PluggedKeyVault,
+        Box<dyn Fn(IndyResult<()>) + Send>,
+    ),
+    RegisterEntropySource(
+        PluggedEntropySource,
+        Box<dyn Fn(IndyResult<()>) + Send>,
+    ),
     CreateKey(
         WalletHandle,
         KeyInfo, // key info
         Box<dyn Fn(IndyResult<String /*verkey*/>) + Send>,
     ),
+    CreateBlsKey(
+        BlsKeyInfo, // key info
+        Box<dyn Fn(IndyResult<String /*bls key json*/>) + Send>,
+    ),
     SetKeyMetadata(
         WalletHandle,
         String, // verkey
@@ -35,6 +62,20 @@ pub enum CryptoCommand {
         String, // verkey
         Box<dyn Fn(IndyResult<String>) + Send>,
     ),
+    GetKeyDerivationPath(
+        WalletHandle,
+        String, // verkey
+        Box<dyn Fn(IndyResult<String /*derivation path*/>) + Send>,
+    ),
+    GetKeyAgreementKey(
+        WalletHandle,
+        String, // verkey
+        Box<dyn Fn(IndyResult<String /*key agreement key*/>) + Send>,
+    ),
+    ConvertKey(
+        String, // ed25519 verkey
+        Box<dyn Fn(IndyResult<String /*x25519 key*/>) + Send>,
+    ),
     CryptoSign(
         WalletHandle,
         String,  // my vk
@@ -45,6 +86,7 @@ pub enum CryptoCommand {
         String,  // their vk
         Vec<u8>, // msg
         Vec<u8>, // signature
+        bool,    // strict
         Box<dyn Fn(IndyResult<bool>) + Send>,
     ),
     AuthenticatedEncrypt(
@@ -60,6 +102,22 @@ pub enum CryptoCommand {
         Vec<u8>, // encrypted msg
         Box<dyn Fn(IndyResult<(String, Vec<u8>)>) + Send>,
     ),
+    CryptoBox(
+        WalletHandle,
+        String,  // my vk
+        String,  // their vk
+        Vec<u8>, // msg
+        Vec<u8>, // nonce
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    CryptoBoxOpen(
+        WalletHandle,
+        String,  // my vk
+        String,  // their vk
+        Vec<u8>, // encrypted msg
+        Vec<u8>, // nonce
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
     AnonymousEncrypt(
         String,  // their vk
         Vec<u8>, // msg
@@ -75,19 +133,52 @@ pub enum CryptoCommand {
         Vec<u8>, // plaintext message
         Vec<String>,  // list of receiver's keys
         Option<String>,  // senders verkey
+        PackMode,
         WalletHandle,
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
+    PackMessageForDids(
+        Vec<u8>, // plaintext message
+        Vec<DidValue>, // list of receiver's DIDs
+        Option<String>, // senders verkey
+        PoolHandle,
+        WalletHandle,
+        NymCacheOptions, // options
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    PackMessageForDidsContinue(
+        Vec<u8>, // plaintext message
+        Vec<DidValue>, // list of receiver's DIDs
+        IndyResult<Vec<String>>, // receiver's keys
+        Option<String>, // senders verkey
+        WalletHandle,
+        CommandHandle, // cb_id
+    ),
     UnpackMessage(
         JWE,
         WalletHandle,
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
+    ListDeadLetters(
+        WalletHandle,
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    RetryDeadLetter(
+        WalletHandle,
+        String, // dead letter id
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    PurgeDeadLetters(
+        WalletHandle,
+        Option<u64>, // received before
+        Box<dyn Fn(IndyResult<()>) + Send>,
+    ),
 }
 
 pub struct CryptoCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
+    pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<Vec<u8>>) + Send>>>,
 }
 
 impl CryptoCommandExecutor {
@@ -98,15 +189,28 @@ impl CryptoCommandExecutor {
         CryptoCommandExecutor {
             wallet_service,
             crypto_service,
+            pending_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
     pub fn execute(&self, command: CryptoCommand) {
         match command {
+            CryptoCommand::RegisterKeyVault(name, key_vault, cb) => {
+                debug!("RegisterKeyVault command received");
+                cb(self.register_key_vault(&name, key_vault));
+            }
+            CryptoCommand::RegisterEntropySource(entropy_source, cb) => {
+                debug!("RegisterEntropySource command received");
+                cb(self.register_entropy_source(entropy_source));
+            }
             CryptoCommand::CreateKey(wallet_handle, key_info, cb) => {
                 debug!("CreateKey command received");
                 cb(self.create_key(wallet_handle, &key_info));
             }
+            CryptoCommand::CreateBlsKey(key_info, cb) => {
+                debug!("CreateBlsKey command received");
+                cb(self.create_bls_key(&key_info));
+            }
             CryptoCommand::SetKeyMetadata(wallet_handle, verkey, metadata, cb) => {
                 debug!("SetKeyMetadata command received");
                 cb(self.set_key_metadata(wallet_handle, &verkey, &metadata));
@@ -115,18 +219,38 @@ impl CryptoCommandExecutor {
                 debug!("GetKeyMetadata command received");
                 cb(self.get_key_metadata(wallet_handle, &verkey));
             }
+            CryptoCommand::GetKeyDerivationPath(wallet_handle, verkey, cb) => {
+                debug!("GetKeyDerivationPath command received");
+                cb(self.get_key_derivation_path(wallet_handle, &verkey));
+            }
+            CryptoCommand::GetKeyAgreementKey(wallet_handle, verkey, cb) => {
+                debug!("GetKeyAgreementKey command received");
+                cb(self.get_key_agreement_key(wallet_handle, &verkey));
+            }
+            CryptoCommand::ConvertKey(verkey, cb) => {
+                debug!("ConvertKey command received");
+                cb(self.convert_key(&verkey));
+            }
             CryptoCommand::CryptoSign(wallet_handle, my_vk, msg, cb) => {
                 debug!("CryptoSign command received");
-                cb(self.crypto_sign(wallet_handle, &my_vk, &msg));
+                self.crypto_sign(wallet_handle, &my_vk, msg, cb);
             }
-            CryptoCommand::CryptoVerify(their_vk, msg, signature, cb) => {
+            CryptoCommand::CryptoVerify(their_vk, msg, signature, strict, cb) => {
                 debug!("CryptoVerify command received");
-                cb(self.crypto_verify(&their_vk, &msg, &signature));
+                cb(self.crypto_verify(&their_vk, &msg, &signature, strict));
             }
             CryptoCommand::AuthenticatedEncrypt(wallet_handle, my_vk, their_vk, msg, cb) => {
                 debug!("AuthenticatedEncrypt command received");
                 cb(self.authenticated_encrypt(wallet_handle, &my_vk, &their_vk, &msg));
             }
+            CryptoCommand::CryptoBox(wallet_handle, my_vk, their_vk, msg, nonce, cb) => {
+                debug!("CryptoBox command received");
+                cb(self.crypto_box(wallet_handle, &my_vk, &their_vk, &msg, &nonce));
+            }
+            CryptoCommand::CryptoBoxOpen(wallet_handle, my_vk, their_vk, encrypted_msg, nonce, cb) => {
+                debug!("CryptoBoxOpen command received");
+                cb(self.crypto_box_open(wallet_handle, &my_vk, &their_vk, &encrypted_msg, &nonce));
+            }
             CryptoCommand::AuthenticatedDecrypt(wallet_handle, my_vk, encrypted_msg, cb) => {
                 debug!("AuthenticatedDecrypt command received");
                 cb(self.authenticated_decrypt(wallet_handle, &my_vk, &encrypted_msg));
@@ -139,17 +263,57 @@ impl CryptoCommandExecutor {
                 debug!("AnonymousDecrypt command received");
                 cb(self.anonymous_decrypt(wallet_handle, &my_vk, &encrypted_msg));
             }
-            CryptoCommand::PackMessage(message, receivers, sender_vk, wallet_handle, cb) => {
+            CryptoCommand::PackMessage(message, receivers, sender_vk, mode, wallet_handle, cb) => {
                 debug!("PackMessage command received");
-                cb(self.pack_msg(message, receivers, sender_vk, wallet_handle));
+                cb(self.pack_msg(message, receivers, sender_vk, mode, wallet_handle));
+            }
+            CryptoCommand::PackMessageForDids(message, receiver_dids, sender_vk, pool_handle, wallet_handle, options, cb) => {
+                debug!("PackMessageForDids command received");
+                self.pack_msg_for_dids(message, receiver_dids, sender_vk, pool_handle, wallet_handle, options, cb);
+            }
+            CryptoCommand::PackMessageForDidsContinue(message, receiver_dids, receiver_keys, sender_vk, wallet_handle, cb_id) => {
+                debug!("PackMessageForDidsContinue command received");
+                self._pack_msg_for_dids_continue(message, receiver_dids, receiver_keys, sender_vk, wallet_handle, cb_id);
             }
             CryptoCommand::UnpackMessage(jwe_json, wallet_handle, cb) => {
                 debug!("UnpackMessage command received");
-                cb(self.unpack_msg(jwe_json, wallet_handle));
+                cb(self.unpack_msg_or_store(jwe_json, wallet_handle));
+            }
+            CryptoCommand::ListDeadLetters(wallet_handle, cb) => {
+                debug!("ListDeadLetters command received");
+                cb(self.list_dead_letters(wallet_handle));
+            }
+            CryptoCommand::RetryDeadLetter(wallet_handle, dead_letter_id, cb) => {
+                debug!("RetryDeadLetter command received");
+                cb(self.retry_dead_letter(wallet_handle, &dead_letter_id));
+            }
+            CryptoCommand::PurgeDeadLetters(wallet_handle, received_before, cb) => {
+                debug!("PurgeDeadLetters command received");
+                cb(self.purge_dead_letters(wallet_handle, received_before));
             }
         };
     }
 
+    fn register_key_vault(&self, name: &str, key_vault: PluggedKeyVault) -> IndyResult<()> {
+        debug!("register_key_vault >>> name: {:?}", name);
+
+        self.crypto_service.register_key_vault(name, Box::new(key_vault));
+
+        debug!("register_key_vault <<<");
+
+        Ok(())
+    }
+
+    fn register_entropy_source(&self, entropy_source: PluggedEntropySource) -> IndyResult<()> {
+        debug!("register_entropy_source >>>");
+
+        self.crypto_service.register_entropy_source(Box::new(entropy_source))?;
+
+        debug!("register_entropy_source <<<");
+
+        Ok(())
+    }
+
     fn create_key(&self, wallet_handle: WalletHandle, key_info: &KeyInfo) -> IndyResult<String> {
         debug!(
             "create_key >>> wallet_handle: {:?}, key_info: {:?}",
@@ -160,51 +324,103 @@ impl CryptoCommandExecutor {
         let key = self.crypto_service.create_key(key_info)?;
         self.wallet_service
             .add_indy_object(wallet_handle, &key.verkey, &key, &HashMap::new())?;
+        store_key_agreement_key(&self.wallet_service, &self.crypto_service, wallet_handle, &key.verkey)?;
+
+        if let Some(ref derivation_path) = key_info.derivation_path {
+            let metadata = self.wallet_service.get_indy_opt_object::<KeyMetadata>(
+                wallet_handle,
+                &key.verkey,
+                &RecordOptions::id_value(),
+            )?;
+
+            let metadata = KeyMetadata {
+                value: metadata.map(|metadata| metadata.value).unwrap_or_default(),
+                derivation_path: Some(derivation_path.to_string()),
+            };
+
+            self.wallet_service
+                .upsert_indy_object(wallet_handle, &key.verkey, &metadata)?;
+        }
 
         let res = key.verkey.to_string();
         debug!("create_key <<< res: {:?}", res);
         Ok(res)
     }
 
-    fn crypto_sign(&self, wallet_handle: WalletHandle, my_vk: &str, msg: &[u8]) -> IndyResult<Vec<u8>> {
+    fn create_bls_key(&self, key_info: &BlsKeyInfo) -> IndyResult<String> {
+        debug!("create_bls_key >>> key_info: {:?}", secret!(key_info));
+
+        let key = self.crypto_service.create_bls_key(key_info)?;
+
+        let res = serde_json::to_string(&key)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize BLS key")?;
+
+        debug!("create_bls_key <<< blskey: {:?}", key.blskey);
+
+        Ok(res)
+    }
+
+    // Signature is produced on the thread pool, so signing doesn't block other commands.
+    fn crypto_sign(&self,
+                   wallet_handle: WalletHandle,
+                   my_vk: &str,
+                   msg: Vec<u8>,
+                   cb: Box<dyn Fn(IndyResult<Vec<u8>>) + Send>) {
         trace!(
             "crypto_sign >>> wallet_handle: {:?}, sender_vk: {:?}, msg: {:?}",
             wallet_handle, my_vk, msg
         );
 
-        self.crypto_service.validate_key(my_vk)?;
+        try_cb!(self.crypto_service.validate_key(my_vk), cb);
 
-        let key: Key = self.wallet_service.get_indy_object(
+        let key: Key = try_cb!(self.wallet_service.get_indy_object(
             wallet_handle,
             &my_vk,
             &RecordOptions::id_value(),
-        )?;
+        ), cb);
 
-        let res = self.crypto_service.sign(&key, msg)?;
+        let signer = try_cb!(self.crypto_service.signer(&key, msg), cb);
 
-        trace!("crypto_sign <<< res: {:?}", res);
+        crate::commands::THREADPOOL.lock().unwrap().execute(move || {
+            let res = signer();
 
-        Ok(res)
+            trace!("crypto_sign <<< res: {:?}", res);
+
+            cb(res)
+        });
     }
 
     fn crypto_verify(&self,
                      their_vk: &str,
                      msg: &[u8],
-                     signature: &[u8]) -> IndyResult<bool> {
+                     signature: &[u8],
+                     strict: bool) -> IndyResult<bool> {
         trace!(
-            "crypto_verify >>> their_vk: {:?}, msg: {:?}, signature: {:?}",
-            their_vk, msg, signature
+            "crypto_verify >>> their_vk: {:?}, msg: {:?}, signature: {:?}, strict: {:?}",
+            their_vk, msg, signature, strict
         );
 
-        self.crypto_service.validate_key(their_vk)?;
+        let res = self.crypto_service.validate_key(their_vk)
+            .and_then(|_| self.crypto_service.verify(their_vk, msg, signature));
 
-        let res = self.crypto_service.verify(their_vk, msg, signature)?;
+        // Not strict verification treats malformed key or signature as just invalid signature
+        let res = match res {
+            Err(ref err) if !strict && CryptoCommandExecutor::_is_malformed_input(err) => false,
+            res => res?
+        };
 
         trace!("crypto_verify <<< res: {:?}", res);
 
         Ok(res)
     }
 
+    fn _is_malformed_input(err: &IndyError) -> bool {
+        match err.kind() {
+            IndyErrorKind::InvalidStructure | IndyErrorKind::UnknownCrypto => true,
+            _ => false
+        }
+    }
+
     //TODO begin deprecation process this function. It will be replaced by pack
     fn authenticated_encrypt(
         &self,
@@ -273,6 +489,53 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn crypto_box(&self,
+                  wallet_handle: WalletHandle,
+                  my_vk: &str,
+                  their_vk: &str,
+                  msg: &[u8],
+                  nonce: &[u8]) -> IndyResult<Vec<u8>> {
+        trace!("crypto_box >>> wallet_handle: {:?}, my_vk: {:?}, their_vk: {:?}, msg: {:?}, nonce: {:?}", wallet_handle, my_vk, their_vk, msg, nonce);
+
+        self.crypto_service.validate_key(my_vk)?;
+
+        let my_key: Key = self.wallet_service.get_indy_object(
+            wallet_handle,
+            my_vk,
+            &RecordOptions::id_value(),
+        )?;
+
+        let res = self.crypto_service.crypto_box_with_nonce(&my_key, their_vk, msg, nonce)?;
+
+        trace!("crypto_box <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn crypto_box_open(&self,
+                       wallet_handle: WalletHandle,
+                       my_vk: &str,
+                       their_vk: &str,
+                       encrypted_msg: &[u8],
+                       nonce: &[u8]) -> IndyResult<Vec<u8>> {
+        trace!("crypto_box_open >>> wallet_handle: {:?}, my_vk: {:?}, their_vk: {:?}, encrypted_msg: {:?}, nonce: {:?}", wallet_handle, my_vk, their_vk, encrypted_msg, nonce);
+
+        self.crypto_service.validate_key(my_vk)?;
+        self.crypto_service.validate_key(their_vk)?;
+
+        let my_key: Key = self.wallet_service.get_indy_object(
+            wallet_handle,
+            my_vk,
+            &RecordOptions::id_value(),
+        )?;
+
+        let res = self.crypto_service.crypto_box_open(&my_key, their_vk, encrypted_msg, nonce)?;
+
+        trace!("crypto_box_open <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn anonymous_encrypt(&self,
                          their_vk: &str,
                          msg: &[u8]) -> IndyResult<Vec<u8>> {
@@ -324,8 +587,15 @@ impl CryptoCommandExecutor {
 
         self.crypto_service.validate_key(verkey)?;
 
+        let derivation_path = self.wallet_service.get_indy_opt_object::<KeyMetadata>(
+            wallet_handle,
+            &verkey,
+            &RecordOptions::id_value(),
+        )?.and_then(|metadata| metadata.derivation_path);
+
         let metadata = KeyMetadata {
             value: metadata.to_string(),
+            derivation_path,
         };
 
         self.wallet_service
@@ -357,6 +627,57 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn get_key_derivation_path(&self, wallet_handle: WalletHandle, verkey: &str) -> IndyResult<String> {
+        debug!(
+            "get_key_derivation_path >>> wallet_handle: {:?}, verkey: {:?}",
+            wallet_handle, verkey
+        );
+
+        self.crypto_service.validate_key(verkey)?;
+
+        let metadata = self.wallet_service.get_indy_object::<KeyMetadata>(
+            wallet_handle,
+            &verkey,
+            &RecordOptions::id_value(),
+        )?;
+
+        let res = metadata.derivation_path
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, format!("Key {} wasn't derived by path", verkey)))?;
+
+        debug!("get_key_derivation_path <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn get_key_agreement_key(&self, wallet_handle: WalletHandle, verkey: &str) -> IndyResult<String> {
+        debug!(
+            "get_key_agreement_key >>> wallet_handle: {:?}, verkey: {:?}",
+            wallet_handle, verkey
+        );
+
+        self.crypto_service.validate_key(verkey)?;
+
+        let key: Key = self.wallet_service.get_indy_object(wallet_handle, verkey, &RecordOptions::id_value())?;
+
+        let res = self.crypto_service.convert_to_key_agreement_key(&key)?;
+
+        debug!("get_key_agreement_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn convert_key(&self, verkey: &str) -> IndyResult<String> {
+        debug!("convert_key >>> verkey: {:?}", verkey);
+
+        self.crypto_service.validate_key(verkey)?;
+
+        let res = self.crypto_service.convert_key(verkey)?;
+
+        debug!("convert_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     //TODO: Refactor pack to be more modular to version changes or crypto_scheme changes
     //this match statement is super messy, but the easiest way to comply with current architecture
     pub fn pack_msg(
@@ -364,6 +685,7 @@ impl CryptoCommandExecutor {
         message: Vec<u8>,
         receiver_list: Vec<String>,
         sender_vk: Option<String>,
+        mode: PackMode,
         wallet_handle: WalletHandle,
     ) -> IndyResult<Vec<u8>> {
 
@@ -373,26 +695,124 @@ impl CryptoCommandExecutor {
         }
 
         //generate content encryption key that will encrypt `message`
-        let cek = chacha20poly1305_ietf::gen_key();
+        let cek = self.crypto_service.gen_cek()?;
 
-        let base64_protected = if let Some(sender_vk) = sender_vk {
-            self.crypto_service.validate_key(&sender_vk)?;
+        let base64_protected = match (mode, sender_vk) {
+            (PackMode::Anoncrypt, None) => {
+                //returns anoncrypted pack_message format. See Wire message format HIPE for details
+                self._prepare_protected_anoncrypt(&cek, receiver_list)?
+            }
+            (PackMode::Authcrypt, Some(sender_vk)) => {
+                self.crypto_service.validate_key(&sender_vk)?;
 
-            //returns authcrypted pack_message format. See Wire message format HIPE for details
-            self._prepare_protected_authcrypt(&cek, receiver_list, &sender_vk, wallet_handle)?
-        } else {
-            //returns anoncrypted pack_message format. See Wire message format HIPE for details
-            self._prepare_protected_anoncrypt(&cek, receiver_list)?
+                //returns authcrypted pack_message format. See Wire message format HIPE for details
+                self._prepare_protected_authcrypt(&cek, receiver_list, &sender_vk, wallet_handle)?
+            }
+            (PackMode::AuthcryptWithHiddenSender, Some(sender_vk)) => {
+                self.crypto_service.validate_key(&sender_vk)?;
+
+                //returns anoncrypted pack_message format with authcrypted cek inside of encrypted key
+                self._prepare_protected_authcrypt_hidden_sender(&cek, receiver_list, &sender_vk, wallet_handle)?
+            }
+            (PackMode::Anoncrypt, Some(_)) =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "Sender verkey can't be used for anoncrypt pack mode")),
+            (_, None) =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Sender verkey is required for {:?} pack mode", mode))),
         };
 
         // Use AEAD to encrypt `message` with "protected" data as "associated data"
         let (ciphertext, iv, tag) =
             self.crypto_service
-                .encrypt_plaintext(message, &base64_protected, &cek);
+                .encrypt_plaintext(message, &base64_protected, &cek)?;
 
         self._format_pack_message(&base64_protected, &ciphertext, &iv, &tag)
     }
 
+    // Receivers keys are resolved through the NYM cache, so only stale or unknown DIDs require the ledger round trip.
+    fn pack_msg_for_dids(
+        &self,
+        message: Vec<u8>,
+        receiver_dids: Vec<DidValue>,
+        sender_vk: Option<String>,
+        pool_handle: PoolHandle,
+        wallet_handle: WalletHandle,
+        options: NymCacheOptions,
+        cb: Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ) {
+        debug!("pack_msg_for_dids >>> receiver_dids: {:?}, sender_vk: {:?}, pool_handle: {:?}, wallet_handle: {:?}, options: {:?}",
+               receiver_dids, sender_vk, pool_handle, wallet_handle, options);
+
+        if receiver_dids.is_empty() {
+            return cb(Err(err_msg(IndyErrorKind::InvalidStructure, "No receiver DIDs found")));
+        }
+
+        for did in receiver_dids.iter() {
+            try_cb!(self.crypto_service.validate_did(did), cb);
+        }
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance()
+            .send(Command::Cache(CacheCommand::ResolveNymKeys(
+                pool_handle,
+                wallet_handle,
+                receiver_dids.clone(),
+                options,
+                Box::new(move |receiver_keys| {
+                    CommandExecutor::instance()
+                        .send(Command::Crypto(CryptoCommand::PackMessageForDidsContinue(
+                            message.clone(),
+                            receiver_dids.clone(),
+                            receiver_keys,
+                            sender_vk.clone(),
+                            wallet_handle,
+                            cb_id,
+                        ))).unwrap();
+                }),
+            ))).unwrap();
+    }
+
+    fn _pack_msg_for_dids_continue(&self,
+                                   message: Vec<u8>,
+                                   receiver_dids: Vec<DidValue>,
+                                   receiver_keys: IndyResult<Vec<String>>,
+                                   sender_vk: Option<String>,
+                                   wallet_handle: WalletHandle,
+                                   cb_id: CommandHandle) {
+        let cb = match self.pending_callbacks.borrow_mut().remove(&cb_id) {
+            Some(cb) => cb,
+            None => return error!("No pending command for id: {}", cb_id)
+        };
+
+        let res = receiver_keys
+            .and_then(|receiver_keys| self._designated_key_agreement_keys(wallet_handle, &receiver_dids, receiver_keys))
+            .and_then(|receiver_keys| {
+                let mode = PackMode::for_sender(sender_vk.as_ref());
+                self.pack_msg(message, receiver_keys, sender_vk, mode, wallet_handle)
+            });
+
+        debug!("pack_msg_for_dids <<< res: {:?}", res);
+
+        cb(res)
+    }
+
+    /// My DIDs with designated key agreement key receive messages packed to that key instead of the NYM verkey.
+    fn _designated_key_agreement_keys(&self,
+                                      wallet_handle: WalletHandle,
+                                      receiver_dids: &[DidValue],
+                                      receiver_keys: Vec<String>) -> IndyResult<Vec<String>> {
+        receiver_dids.iter()
+            .zip(receiver_keys.into_iter())
+            .map(|(did, key)| {
+                let key_agreement = self.wallet_service.get_indy_opt_object::<DidKeys>(wallet_handle, &did.0, &RecordOptions::id_value())?
+                    .and_then(|did_keys| did_keys.key_agreement);
+
+                Ok(key_agreement.unwrap_or(key))
+            })
+            .collect()
+    }
+
     fn _prepare_protected_anoncrypt(&self,
                                     cek: &chacha20poly1305_ietf::Key,
                                     receiver_list: Vec<String>,
@@ -450,6 +870,48 @@ impl CryptoCommandExecutor {
         Ok(self._base64_encode_protected(encrypted_recipients_struct, true)?)
     }
 
+    fn _prepare_protected_authcrypt_hidden_sender(&self,
+                                                  cek: &chacha20poly1305_ietf::Key,
+                                                  receiver_list: Vec<String>, sender_vk: &str,
+                                                  wallet_handle: WalletHandle,
+    ) -> IndyResult<String> {
+        let mut encrypted_recipients_struct : Vec<Recipient> = Vec::with_capacity(receiver_list.len());
+
+        //get my_key from my wallet
+        let my_key = self.wallet_service.get_indy_object(
+            wallet_handle,
+            sender_vk,
+            &RecordOptions::id_value()
+        )?;
+
+        for their_vk in receiver_list {
+            let (enc_cek, iv) = self.crypto_service.crypto_box(&my_key, &their_vk, &cek[..])?;
+
+            //seal sender verkey together with authcrypted cek, so headers look like anoncrypted ones
+            let hidden_sender_key = HiddenSenderKey {
+                sender: sender_vk.to_string(),
+                iv: base64::encode_urlsafe(iv.as_slice()),
+                encrypted_key: base64::encode_urlsafe(enc_cek.as_slice()),
+            };
+
+            let hidden_sender_key = serde_json::to_vec(&hidden_sender_key)
+                .to_indy(IndyErrorKind::InvalidState, "Failed to serialize hidden sender key")?;
+
+            let enc_key = self.crypto_service.crypto_box_seal(&their_vk, &hidden_sender_key)?;
+
+            encrypted_recipients_struct.push(Recipient {
+                encrypted_key: base64::encode_urlsafe(enc_key.as_slice()),
+                header: Header {
+                    kid: their_vk,
+                    sender: None,
+                    iv: None
+                },
+            });
+        }
+
+        Ok(self._base64_encode_protected(encrypted_recipients_struct, false)?)
+    }
+
     fn _base64_encode_protected(&self, encrypted_recipients_struct: Vec<Recipient>, alg_is_authcrypt: bool) -> IndyResult<String> {
         let alg_val = if alg_is_authcrypt { String::from(PROTECTED_HEADER_ALG_AUTH) } else { String::from(PROTECTED_HEADER_ALG_ANON) };
 
@@ -515,7 +977,7 @@ impl CryptoCommandExecutor {
         let (recipient, is_auth_recipient) = self._find_correct_recipient(protected_struct, wallet_handle)?;
 
         //get cek and sender data
-        let (sender_verkey_option, cek) = if is_auth_recipient {
+        let (sender_verkey_option, cek, mode) = if is_auth_recipient {
             self._unpack_cek_authcrypt(recipient.clone(), wallet_handle)
         } else {
             self._unpack_cek_anoncrypt(recipient.clone(), wallet_handle)
@@ -534,7 +996,9 @@ impl CryptoCommandExecutor {
         let res = UnpackMessage {
             message,
             sender_verkey: sender_verkey_option,
-            recipient_verkey: recipient.header.kid
+            recipient_verkey: recipient.header.kid,
+            mode,
+            sender_authenticated: mode.is_authenticated(),
         };
 
         serde_json::to_vec(&res).map_err(|err| {
@@ -545,13 +1009,160 @@ impl CryptoCommandExecutor {
         })
     }
 
+    fn unpack_msg_or_store(&self, jwe_struct: JWE, wallet_handle: WalletHandle) -> IndyResult<Vec<u8>> {
+        self.unpack_msg(jwe_struct.clone(), wallet_handle)
+            .map_err(|err| {
+                self._store_dead_letter(wallet_handle, jwe_struct, &err);
+                err
+            })
+    }
+
+    fn list_dead_letters(&self, wallet_handle: WalletHandle) -> IndyResult<String> {
+        debug!("list_dead_letters >>> wallet_handle: {:?}", wallet_handle);
+
+        let dead_letters = self._get_dead_letters(wallet_handle)?;
+
+        let res = serde_json::to_string(&dead_letters)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of dead letters")?;
+
+        debug!("list_dead_letters <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn retry_dead_letter(&self, wallet_handle: WalletHandle, dead_letter_id: &str) -> IndyResult<Vec<u8>> {
+        debug!("retry_dead_letter >>> wallet_handle: {:?}, dead_letter_id: {:?}", wallet_handle, dead_letter_id);
+
+        let mut dead_letter: DeadLetter =
+            self.wallet_service.get_indy_object(wallet_handle, dead_letter_id, &RecordOptions::id_value())?;
+
+        let res = match self.unpack_msg(dead_letter.message.clone(), wallet_handle) {
+            Ok(res) => {
+                self.wallet_service.delete_indy_record::<DeadLetter>(wallet_handle, dead_letter_id)?;
+                res
+            }
+            Err(err) => {
+                dead_letter.error = err.to_string();
+                dead_letter.attempts += 1;
+                self.wallet_service.update_indy_object(wallet_handle, dead_letter_id, &dead_letter)?;
+                return Err(err);
+            }
+        };
+
+        debug!("retry_dead_letter <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn purge_dead_letters(&self, wallet_handle: WalletHandle, received_before: Option<u64>) -> IndyResult<()> {
+        debug!("purge_dead_letters >>> wallet_handle: {:?}, received_before: {:?}", wallet_handle, received_before);
+
+        let query_json = match received_before {
+            Some(time) => json!({"~received": {"$lt": DeadLetter::received_tag(time)}}).to_string(),
+            None => "{}".to_string(),
+        };
+        let options_json = json!({"retrieveValue": false}).to_string();
+
+        let mut search = self.wallet_service.search_indy_records::<DeadLetter>(wallet_handle, &query_json, &options_json)?;
+
+        let mut dead_letter_ids: Vec<String> = Vec::new();
+
+        while let Some(record) = search.fetch_next_record()? {
+            dead_letter_ids.push(record.get_id().to_string());
+        }
+
+        for dead_letter_id in dead_letter_ids {
+            self.wallet_service.delete_indy_record::<DeadLetter>(wallet_handle, &dead_letter_id)?;
+        }
+
+        debug!("purge_dead_letters <<<");
+
+        Ok(())
+    }
+
+    fn _get_dead_letters(&self, wallet_handle: WalletHandle) -> IndyResult<Vec<DeadLetterInfo>> {
+        let mut search = self.wallet_service.search_indy_records::<DeadLetter>(wallet_handle, "{}", &SearchOptions::id_value())?;
+
+        let mut dead_letters: Vec<DeadLetterInfo> = Vec::new();
+
+        while let Some(record) = search.fetch_next_record()? {
+            let dead_letter: DeadLetter = record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value for DeadLetter record"))
+                .and_then(|value| serde_json::from_str(value)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize DeadLetter"))?;
+
+            dead_letters.push(DeadLetterInfo { id: record.get_id().to_string(), dead_letter });
+        }
+
+        dead_letters.sort_by_key(|info| info.dead_letter.received);
+
+        Ok(dead_letters)
+    }
+
+    fn _store_dead_letter(&self, wallet_handle: WalletHandle, message: JWE, err: &IndyError) {
+        if err.kind() == IndyErrorKind::InvalidWalletHandle {
+            return;
+        }
+
+        // the oldest dead letters are dropped to keep the store bounded
+        if let Err(err) = self._evict_dead_letters(wallet_handle, MAX_DEAD_LETTERS - 1) {
+            warn!("Cannot evict old dead letters: {:?}", err);
+        }
+
+        let received = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        let dead_letter = DeadLetter {
+            message,
+            error: err.to_string(),
+            received,
+            attempts: 1,
+        };
+
+        let dead_letter_id = uuid::Uuid::new_v4().to_string();
+
+        let mut tags = HashMap::new();
+        tags.insert("~received".to_string(), DeadLetter::received_tag(received));
+
+        if let Err(err) = self.wallet_service.add_indy_object(wallet_handle, &dead_letter_id, &dead_letter, &tags) {
+            warn!("Cannot store dead letter for message failed to unpack: {:?}", err);
+        }
+    }
+
+    fn _evict_dead_letters(&self, wallet_handle: WalletHandle, keep: usize) -> IndyResult<()> {
+        // only ids and receiving time tags are fetched, so the stored messages aren't decrypted
+        let mut search = self.wallet_service.search_indy_records::<DeadLetter>(wallet_handle, "{}", &SearchOptions::id_tags())?;
+
+        let evict = search.get_total_count()?.unwrap_or(0).saturating_sub(keep);
+
+        if evict == 0 {
+            return Ok(());
+        }
+
+        let mut dead_letters: Vec<(String, String)> = Vec::new();
+
+        while let Some(record) = search.fetch_next_record()? {
+            let received = record.get_tags()
+                .and_then(|tags| tags.get("~received"))
+                .cloned()
+                .unwrap_or_default();
+
+            dead_letters.push((received, record.get_id().to_string()));
+        }
+
+        dead_letters.sort();
+
+        for (_, dead_letter_id) in dead_letters.into_iter().take(evict) {
+            self.wallet_service.delete_indy_record::<DeadLetter>(wallet_handle, &dead_letter_id)?;
+        }
+
+        Ok(())
+    }
+
     fn _find_correct_recipient(&self, protected_struct: Protected, wallet_handle: WalletHandle) -> IndyResult<(Recipient, bool)>{
         for recipient in protected_struct.recipients {
-            let my_key_res = self.wallet_service.get_indy_object::<Key>(
-                wallet_handle,
-                &recipient.header.kid,
-                &RecordOptions::id_value()
-            );
+            let my_key_res = self._get_recipient_key(&recipient.header.kid, wallet_handle);
 
 
             if my_key_res.is_ok() {
@@ -561,17 +1172,59 @@ impl CryptoCommandExecutor {
         Err(IndyError::from(IndyErrorKind::WalletItemNotFound))
     }
 
-    fn _unpack_cek_authcrypt(&self, recipient: Recipient, wallet_handle: WalletHandle) -> IndyResult<(Option<String>, chacha20poly1305_ietf::Key)> {
+    fn _get_recipient_key(&self, kid: &str, wallet_handle: WalletHandle) -> IndyResult<Key> {
+        //resolve key agreement key to the key it has been derived from
+        let verkey = if split_verkey(kid).1 == X25519_CRYPTO_TYPE {
+            let key_agreement_key = match self.wallet_service.get_indy_opt_object::<KeyAgreementKey>(wallet_handle, kid, &RecordOptions::id_value())? {
+                None if self._migrate_key_agreement_keys(wallet_handle)? =>
+                    self.wallet_service.get_indy_opt_object::<KeyAgreementKey>(wallet_handle, kid, &RecordOptions::id_value())?,
+                key_agreement_key => key_agreement_key
+            };
+
+            key_agreement_key
+                .map(|key_agreement_key| key_agreement_key.verkey)
+                .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, format!("Key the key agreement key {} has been derived from not found", kid)))?
+        } else {
+            kid.to_string()
+        };
+
+        self.wallet_service.get_indy_object(
+            wallet_handle,
+            &verkey,
+            &RecordOptions::id_value()
+        )
+    }
+
+    /// Keys created before key agreement keys were stored with them get ones by one-time migration of the wallet.
+    /// Returns false if the wallet has been migrated already.
+    fn _migrate_key_agreement_keys(&self, wallet_handle: WalletHandle) -> IndyResult<bool> {
+        if self.wallet_service.record_exists::<KeyAgreementKeysMigration>(wallet_handle, KEY_AGREEMENT_KEYS_MIGRATION_ID)? {
+            return Ok(false);
+        }
+
+        let mut search = self.wallet_service.search_indy_records::<Key>(wallet_handle, "{}", &SearchOptions::id_tags())?;
+        let mut verkeys = Vec::new();
+
+        while let Some(record) = search.fetch_next_record()? {
+            verkeys.push(record.get_id().to_string());
+        }
+
+        for verkey in verkeys.iter() {
+            store_key_agreement_key(&self.wallet_service, &self.crypto_service, wallet_handle, verkey)?;
+        }
+
+        self.wallet_service.upsert_indy_object(wallet_handle, KEY_AGREEMENT_KEYS_MIGRATION_ID, &KeyAgreementKeysMigration { keys: verkeys.len() as u64 })?;
+
+        Ok(true)
+    }
+
+    fn _unpack_cek_authcrypt(&self, recipient: Recipient, wallet_handle: WalletHandle) -> IndyResult<(Option<String>, chacha20poly1305_ietf::Key, PackMode)> {
         let encrypted_key_vec = base64::decode_urlsafe(&recipient.encrypted_key)?;
         let iv = base64::decode_urlsafe(&recipient.header.iv.unwrap())?;
         let enc_sender_vk = base64::decode_urlsafe(&recipient.header.sender.unwrap())?;
 
         //get my private key
-        let my_key = self.wallet_service.get_indy_object(
-            wallet_handle,
-            &recipient.header.kid,
-            &RecordOptions::id_value(),
-        )?;
+        let my_key = self._get_recipient_key(&recipient.header.kid, wallet_handle)?;
 
         //decrypt sender_vk
         let sender_vk_vec = self.crypto_service.crypto_box_seal_open(&my_key, enc_sender_vk.as_slice())?;
@@ -593,23 +1246,24 @@ impl CryptoCommandExecutor {
                 },
             )?;
 
-        Ok((Some(sender_vk), cek))
+        Ok((Some(sender_vk), cek, PackMode::Authcrypt))
     }
 
-    fn _unpack_cek_anoncrypt(&self, recipient: Recipient, wallet_handle: WalletHandle) -> IndyResult<(Option<String>, chacha20poly1305_ietf::Key)> {
+    fn _unpack_cek_anoncrypt(&self, recipient: Recipient, wallet_handle: WalletHandle) -> IndyResult<(Option<String>, chacha20poly1305_ietf::Key, PackMode)> {
         let encrypted_key_vec = base64::decode_urlsafe(&recipient.encrypted_key)?;
 
         //get my private key
-        let my_key : Key = self.wallet_service.get_indy_object(
-            wallet_handle,
-            &recipient.header.kid,
-            &RecordOptions::id_value(),
-        )?;
+        let my_key = self._get_recipient_key(&recipient.header.kid, wallet_handle)?;
 
         //decrypt cek
         let cek_as_vec = self.crypto_service
             .crypto_box_seal_open(&my_key, encrypted_key_vec.as_slice())?;
 
+        //sealed content longer than the key means that the sender is hidden
+        if cek_as_vec.len() != chacha20poly1305_ietf::KEYBYTES {
+            return self._unpack_cek_hidden_sender(&my_key, &cek_as_vec);
+        }
+
         //convert cek to chacha Key struct
         let cek: chacha20poly1305_ietf::Key =
             chacha20poly1305_ietf::Key::from_slice(&cek_as_vec[..]).map_err(
@@ -618,7 +1272,43 @@ impl CryptoCommandExecutor {
                 },
             )?;
 
-        Ok((None, cek))
+        Ok((None, cek, PackMode::Anoncrypt))
+    }
+
+    fn _unpack_cek_hidden_sender(&self, my_key: &Key, hidden_sender_key: &[u8]) -> IndyResult<(Option<String>, chacha20poly1305_ietf::Key, PackMode)> {
+        let hidden_sender_key: HiddenSenderKey = serde_json::from_slice(hidden_sender_key)
+            .to_indy(IndyErrorKind::InvalidStructure, "Failed to deserialize encrypted key")?;
+
+        self.crypto_service.validate_key(&hidden_sender_key.sender)?;
+
+        let encrypted_key_vec = base64::decode_urlsafe(&hidden_sender_key.encrypted_key)?;
+        let iv = base64::decode_urlsafe(&hidden_sender_key.iv)?;
+
+        //decrypt cek
+        let cek_as_vec = self.crypto_service.crypto_box_open(
+            my_key,
+            &hidden_sender_key.sender,
+            encrypted_key_vec.as_slice(),
+            iv.as_slice())?;
+
+        //convert cek to chacha Key struct
+        let cek: chacha20poly1305_ietf::Key =
+            chacha20poly1305_ietf::Key::from_slice(&cek_as_vec[..]).map_err(
+                |err| {
+                    err_msg(IndyErrorKind::InvalidStructure, format!("Failed to decrypt cek {}", err))
+                },
+            )?;
+
+        Ok((Some(hidden_sender_key.sender), cek, PackMode::AuthcryptWithHiddenSender))
+    }
+
+}
+
+/// Stores key agreement key of ed25519 key, so messages packed for it can be unpacked.
+pub fn store_key_agreement_key(wallet_service: &WalletService, crypto_service: &CryptoService, wallet_handle: WalletHandle, verkey: &str) -> IndyResult<()> {
+    if let Ok(key_agreement_key) = crypto_service.convert_key(verkey) {
+        wallet_service.upsert_indy_object(wallet_handle, &key_agreement_key, &KeyAgreementKey { verkey: verkey.to_string() })?;
     }
 
+    Ok(())
 }
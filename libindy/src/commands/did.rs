@@ -5,27 +5,43 @@ use std::rc::Rc;
 use serde_json;
 
 use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
+use crate::commands::crypto::store_key_agreement_key;
 use crate::commands::ledger::LedgerCommand;
-use crate::domain::crypto::did::{Did, DidValue, DidMetadata, DidWithMeta, MyDidInfo, TemporaryDid, TheirDid, TheirDidInfo, DidMethod};
-use crate::domain::crypto::key::KeyInfo;
-use crate::domain::ledger::attrib::{AttribData, Endpoint, GetAttrReplyResult};
+use crate::domain::crypto::did::{Did, DidValue, DidKeys, DidMetadata, DidNymState, DidWithMeta, MyDidInfo, MyDidsInfo, CreatedDid, TemporaryDid, TheirDid, TheirDidInfo, DidMethod, DidOwnershipProof, ASSERTION_METHOD_KEY_PURPOSE, AUTHENTICATION_KEY_PURPOSE, KEY_AGREEMENT_KEY_PURPOSE};
+use crate::domain::event::Event;
+use crate::domain::crypto::key::{Key, KeyAgreementKey, KeyInfo, KeyMetadata};
+use crate::domain::ledger::attrib::{AttribData, DidDocPairwise, DidDocService, DidDocument, Endpoint, GetAttrReplyResult, ServiceEndpoint, ServiceEndpointAttribData, VerificationMethod, DEFAULT_SERVICE_TYPE, DID_DOC_CONTEXT, PAIRWISE_PROVENANCE_KEY, PROVENANCE_LEDGER_ATTRIB, PROVENANCE_LEDGER_NYM, PROVENANCE_WALLET_PAIRWISE, verification_method_type};
 use crate::domain::ledger::nym::{GetNymReplyResult, GetNymResultDataV0};
 use crate::domain::ledger::response::Reply;
 use crate::domain::pairwise::Pairwise;
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
+use crate::utils::crypto::verkey_builder::split_verkey;
 use crate::services::ledger::LedgerService;
+use crate::services::pool::{parse_response_metadata, PoolService};
+use crate::utils::event::emit_event;
 use indy_wallet::{RecordOptions, SearchOptions, WalletService};
 use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
 use indy_utils::next_command_handle;
+use indy_utils::crypto::hardened;
 use rust_base58::{FromBase58, ToBase58};
 use named_type::NamedType;
 
+/// Maximum number of DIDs created by one batch command.
+const MAX_DIDS_BATCH_SIZE: u32 = 1000;
+/// Index of DID derived from HD seed must fit into hardened SLIP-0010 index.
+const MAX_HD_DID_INDEX: u32 = 0x8000_0000;
+
 pub enum DidCommand {
     CreateAndStoreMyDid(
         WalletHandle,
         MyDidInfo, // my did info
         BoxedCallbackStringStringSend),
+    CreateAndStoreMyDids(
+        WalletHandle,
+        u32, // count
+        MyDidsInfo, // my dids info
+        Box<dyn Fn(IndyResult<String>) + Send>),
     ReplaceKeysStart(
         WalletHandle,
         KeyInfo, // key info
@@ -74,6 +90,16 @@ pub enum DidCommand {
         WalletHandle,
         DidValue, // did
         Box<dyn Fn(IndyResult<String>) + Send>),
+    SetDidKey(
+        WalletHandle,
+        DidValue, // my did
+        String, // key purpose
+        String, // verkey
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    GetDidKeys(
+        WalletHandle,
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<String /*did keys json*/>) + Send>),
     AbbreviateVerkey(
         DidValue, // did
         String, // verkey
@@ -97,6 +123,66 @@ pub enum DidCommand {
         DidMethod, // method
         Box<dyn Fn(IndyResult<String /*full qualified did*/>) + Send>,
     ),
+    CreateDidOwnershipProof(
+        WalletHandle,
+        PoolHandle, // pool handle
+        DidValue, // my did
+        String, // challenge
+        String, // audience
+        Box<dyn Fn(IndyResult<String /*proof json*/>) + Send>,
+    ),
+    VerifyDidOwnershipProof(
+        PoolHandle, // pool handle
+        DidOwnershipProof, // proof
+        String, // challenge
+        String, // audience
+        Box<dyn Fn(IndyResult<bool>) + Send>,
+    ),
+    CheckMyDidNym(
+        WalletHandle,
+        PoolHandle, // pool handle
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<String /*nym state json*/>) + Send>,
+    ),
+    // Internal commands
+    NymVerkeyAck(
+        IndyResult<String>, // GetNym Result
+        CommandHandle, // deferred cmd id
+    ),
+    PublishEndpointForDid(
+        WalletHandle,
+        PoolHandle, // pool handle
+        DidValue, // my did
+        ServiceEndpoint, // endpoint address, types and routing keys
+        Box<dyn Fn(IndyResult<()>) + Send>,
+    ),
+    ResolveServiceEndpointForDid(
+        PoolHandle, // pool handle
+        DidValue, // did
+        Box<dyn Fn(IndyResult<String /*service json*/>) + Send>,
+    ),
+    // Internal commands
+    PublishEndpointAck(
+        IndyResult<String>, // Attrib Result
+        CommandHandle, // deferred cmd id
+    ),
+    ServiceEndpointAttribAck(
+        IndyResult<String>, // GetAttrib Result
+        CommandHandle, // deferred cmd id
+    ),
+    ResolveDid(
+        PoolHandle, // pool handle
+        Option<WalletHandle>, // wallet handle to merge pairwise from
+        DidValue, // did
+        Box<dyn Fn(IndyResult<String /*did doc json*/>) + Send>,
+    ),
+    // Internal commands
+    ResolveDidContinue(
+        Option<WalletHandle>, // wallet handle to merge pairwise from
+        DidValue, // did
+        String, // verkey fetched from ledger
+        Box<dyn Fn(IndyResult<String /*did doc json*/>) + Send>,
+    ),
 }
 
 macro_rules! ensure_their_did {
@@ -114,18 +200,24 @@ pub struct DidCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     ledger_service: Rc<LedgerService>,
+    pool_service: Rc<PoolService>,
     deferred_commands: RefCell<HashMap<CommandHandle, DidCommand>>,
+    // Endpoints fetched for deferred `ResolveServiceEndpointForDid` commands waiting for the DID verkey
+    fetched_service_endpoints: RefCell<HashMap<CommandHandle, ServiceEndpoint>>,
 }
 
 impl DidCommandExecutor {
     pub fn new(wallet_service: Rc<WalletService>,
                crypto_service: Rc<CryptoService>,
-               ledger_service: Rc<LedgerService>) -> DidCommandExecutor {
+               ledger_service: Rc<LedgerService>,
+               pool_service: Rc<PoolService>) -> DidCommandExecutor {
         DidCommandExecutor {
             wallet_service,
             crypto_service,
             ledger_service,
+            pool_service,
             deferred_commands: RefCell::new(HashMap::new()),
+            fetched_service_endpoints: RefCell::new(HashMap::new()),
         }
     }
 
@@ -135,6 +227,10 @@ impl DidCommandExecutor {
                 debug!("CreateAndStoreMyDid command received");
                 cb(self.create_and_store_my_did(wallet_handle, &my_did_info));
             }
+            DidCommand::CreateAndStoreMyDids(wallet_handle, count, my_dids_info, cb) => {
+                debug!("CreateAndStoreMyDids command received");
+                cb(self.create_and_store_my_dids(wallet_handle, count, &my_dids_info));
+            }
             DidCommand::ReplaceKeysStart(wallet_handle, key_info, did, cb) => {
                 debug!("ReplaceKeysStart command received");
                 cb(self.replace_keys_start(wallet_handle, &key_info, &did));
@@ -157,6 +253,7 @@ impl DidCommandExecutor {
             }
             DidCommand::KeyForDid(pool_handle, wallet_handle, did, cb) => {
                 debug!("KeyForDid command received");
+                let pool_handle = self.pool_service.pool_for_did(pool_handle, &did);
                 self.key_for_did(pool_handle, wallet_handle, did, cb);
             }
             DidCommand::KeyForLocalDid(wallet_handle, did, cb) => {
@@ -169,6 +266,7 @@ impl DidCommandExecutor {
             }
             DidCommand::GetEndpointForDid(wallet_handle, pool_handle, did, cb) => {
                 debug!("GetEndpointForDid command received");
+                let pool_handle = self.pool_service.pool_for_did(pool_handle, &did);
                 self.get_endpoint_for_did(wallet_handle, pool_handle, did, cb);
             }
             DidCommand::SetDidMetadata(wallet_handle, did, metadata, cb) => {
@@ -179,6 +277,14 @@ impl DidCommandExecutor {
                 debug!("GetDidMetadata command received");
                 cb(self.get_did_metadata(wallet_handle, &did));
             }
+            DidCommand::SetDidKey(wallet_handle, did, purpose, verkey, cb) => {
+                debug!("SetDidKey command received");
+                cb(self.set_did_key(wallet_handle, &did, &purpose, verkey));
+            }
+            DidCommand::GetDidKeys(wallet_handle, did, cb) => {
+                debug!("GetDidKeys command received");
+                cb(self.get_did_keys(wallet_handle, &did));
+            }
             DidCommand::AbbreviateVerkey(did, verkey, cb) => {
                 debug!("AbbreviateVerkey command received");
                 cb(self.abbreviate_verkey(&did, verkey));
@@ -195,9 +301,404 @@ impl DidCommandExecutor {
                 info!("QualifyDid command received");
                 cb(self.qualify_did(wallet_handle, &did, &method));
             }
+            DidCommand::CreateDidOwnershipProof(wallet_handle, pool_handle, did, challenge, audience, cb) => {
+                debug!("CreateDidOwnershipProof command received");
+                let pool_handle = self.pool_service.pool_for_did(pool_handle, &did);
+                self.create_did_ownership_proof(wallet_handle, pool_handle, did, challenge, audience, cb);
+            }
+            DidCommand::VerifyDidOwnershipProof(pool_handle, proof, challenge, audience, cb) => {
+                debug!("VerifyDidOwnershipProof command received");
+                let pool_handle = self.pool_service.pool_for_did(pool_handle, &proof.did);
+                self.verify_did_ownership_proof(pool_handle, proof, challenge, audience, cb);
+            }
+            DidCommand::CheckMyDidNym(wallet_handle, pool_handle, did, cb) => {
+                debug!("CheckMyDidNym command received");
+                let pool_handle = self.pool_service.pool_for_did(pool_handle, &did);
+                self.check_my_did_nym(wallet_handle, pool_handle, did, cb);
+            }
+            DidCommand::NymVerkeyAck(result, deferred_cmd_id) => {
+                debug!("NymVerkeyAck command received");
+                self.nym_verkey_ack(result, deferred_cmd_id);
+            }
+            DidCommand::PublishEndpointForDid(wallet_handle, pool_handle, did, endpoint, cb) => {
+                debug!("PublishEndpointForDid command received");
+                let pool_handle = self.pool_service.pool_for_did(pool_handle, &did);
+                self.publish_endpoint_for_did(wallet_handle, pool_handle, did, endpoint, cb);
+            }
+            DidCommand::ResolveServiceEndpointForDid(pool_handle, did, cb) => {
+                debug!("ResolveServiceEndpointForDid command received");
+                let pool_handle = self.pool_service.pool_for_did(pool_handle, &did);
+                self.resolve_service_endpoint_for_did(pool_handle, did, cb);
+            }
+            DidCommand::PublishEndpointAck(result, deferred_cmd_id) => {
+                debug!("PublishEndpointAck command received");
+                self.publish_endpoint_ack(result, deferred_cmd_id);
+            }
+            DidCommand::ServiceEndpointAttribAck(result, deferred_cmd_id) => {
+                debug!("ServiceEndpointAttribAck command received");
+                self.service_endpoint_attrib_ack(result, deferred_cmd_id);
+            }
+            DidCommand::ResolveDid(pool_handle, wallet_handle, did, cb) => {
+                debug!("ResolveDid command received");
+                let pool_handle = self.pool_service.pool_for_did(pool_handle, &did);
+                self.resolve_did(pool_handle, wallet_handle, did, cb);
+            }
+            DidCommand::ResolveDidContinue(_, did, _, cb) => {
+                // Is sent only as deferred command and completed by ServiceEndpointAttribAck
+                cb(Err(err_msg(IndyErrorKind::InvalidState, format!("Unexpected DID resolving continuation for DID {}", did.0))));
+            }
         };
     }
 
+    fn publish_endpoint_for_did(&self,
+                                wallet_handle: WalletHandle,
+                                pool_handle: PoolHandle,
+                                did: DidValue,
+                                endpoint: ServiceEndpoint,
+                                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        debug!("publish_endpoint_for_did >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, endpoint: {:?}", wallet_handle, pool_handle, did, endpoint);
+
+        try_cb!(self.crypto_service.validate_did(&did), cb);
+        try_cb!(self._wallet_get_my_did(wallet_handle, &did), cb);
+
+        let attrib_data = ServiceEndpointAttribData { endpoint: endpoint.clone() };
+        let raw = try_cb!(serde_json::to_value(&attrib_data)
+                              .to_indy(IndyErrorKind::InvalidState, "Cannot serialize endpoint ATTRIB data"), cb);
+        let attrib_request = try_cb!(self.ledger_service.build_attrib_request(&did, &did, None, Some(&raw), None), cb);
+
+        // The local endpoint record is updated once ATTRIB transaction is written to the ledger.
+        let deferred_cmd_id = self._defer_command(DidCommand::PublishEndpointForDid(wallet_handle, pool_handle, did.clone(), endpoint, cb));
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SignAndSubmitRequest(
+                pool_handle,
+                wallet_handle,
+                did,
+                attrib_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::PublishEndpointAck(
+                            result,
+                            deferred_cmd_id,
+                        ))).unwrap();
+                }),
+            ))).unwrap();
+    }
+
+    fn publish_endpoint_ack(&self,
+                            attrib_reply_result: IndyResult<String>,
+                            deferred_cmd_id: CommandHandle) {
+        let deferred_cmd = self.deferred_commands.borrow_mut().remove(&deferred_cmd_id);
+
+        match deferred_cmd {
+            Some(DidCommand::PublishEndpointForDid(wallet_handle, _, did, endpoint, cb)) =>
+                cb(self._publish_endpoint_for_did(wallet_handle, &did, &endpoint, attrib_reply_result)),
+            Some(cmd) => self._call_error_cb(cmd, err_msg(IndyErrorKind::InvalidState, "Unexpected deferred command")),
+            None => error!("No deferred command for id: {:?}", deferred_cmd_id)
+        }
+    }
+
+    fn _publish_endpoint_for_did(&self,
+                                 wallet_handle: WalletHandle,
+                                 did: &DidValue,
+                                 endpoint: &ServiceEndpoint,
+                                 attrib_reply_result: IndyResult<String>) -> IndyResult<()> {
+        let attrib_reply = attrib_reply_result?;
+
+        let attrib_reply: serde_json::Value = serde_json::from_str(&attrib_reply)
+            .to_indy(IndyErrorKind::InvalidTransaction, "Response is invalid json")?;
+
+        if attrib_reply["op"] != json!("REPLY") {
+            return Err(err_msg(IndyErrorKind::InvalidTransaction,
+                               format!("Transaction has been failed: {:?}", attrib_reply["reason"].as_str())));
+        }
+
+        // Keep transport key that may be already set for the DID
+        let verkey = match self.wallet_service.get_indy_object::<Endpoint>(wallet_handle, &did.0, &RecordOptions::id_value()) {
+            Ok(current_endpoint) => current_endpoint.verkey,
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => None,
+            Err(err) => return Err(err)
+        };
+
+        self.wallet_service.upsert_indy_object(wallet_handle, &did.0, &Endpoint::new(endpoint.endpoint.clone(), verkey))?;
+
+        debug!("publish_endpoint_for_did <<<");
+
+        Ok(())
+    }
+
+    fn resolve_service_endpoint_for_did(&self,
+                                        pool_handle: PoolHandle,
+                                        did: DidValue,
+                                        cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("resolve_service_endpoint_for_did >>> pool_handle: {:?}, did: {:?}", pool_handle, did);
+
+        try_cb!(self.crypto_service.validate_did(&did), cb);
+
+        let get_attrib_request = try_cb!(self.ledger_service.build_get_attrib_request(None, &did, Some("endpoint"), None, None), cb);
+
+        // Endpoint is fetched first, then DID verkey is fetched to fill service recipient keys.
+        let deferred_cmd_id = self._defer_command(DidCommand::ResolveServiceEndpointForDid(pool_handle, did, cb));
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                pool_handle,
+                get_attrib_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::ServiceEndpointAttribAck(
+                            result,
+                            deferred_cmd_id,
+                        ))).unwrap();
+                }),
+            ))).unwrap();
+    }
+
+    fn service_endpoint_attrib_ack(&self,
+                                   get_attrib_reply_result: IndyResult<String>,
+                                   deferred_cmd_id: CommandHandle) {
+        let deferred_cmd = self.deferred_commands.borrow_mut().remove(&deferred_cmd_id);
+
+        match deferred_cmd {
+            Some(DidCommand::ResolveServiceEndpointForDid(pool_handle, did, cb)) => {
+                let endpoint = try_cb!(self._parse_service_endpoint_reply(get_attrib_reply_result), cb);
+                let target_did = did.clone();
+
+                let deferred_cmd_id = self._defer_command(DidCommand::ResolveServiceEndpointForDid(pool_handle, did, cb));
+                self.fetched_service_endpoints.borrow_mut().insert(deferred_cmd_id, endpoint);
+
+                self._submit_get_nym_for_deferred_command(pool_handle, &target_did, deferred_cmd_id);
+            }
+            Some(DidCommand::ResolveDidContinue(wallet_handle, did, verkey, cb)) =>
+                cb(self._build_did_doc(wallet_handle, &did, verkey, get_attrib_reply_result)),
+            Some(cmd) => self._call_error_cb(cmd, err_msg(IndyErrorKind::InvalidState, "Unexpected deferred command")),
+            None => error!("No deferred command for id: {:?}", deferred_cmd_id)
+        }
+    }
+
+    fn _parse_service_endpoint_reply(&self, get_attrib_reply_result: IndyResult<String>) -> IndyResult<ServiceEndpoint> {
+        let get_attrib_reply = get_attrib_reply_result?;
+
+        let get_attrib_reply_json: serde_json::Value = serde_json::from_str(&get_attrib_reply)
+            .to_indy(IndyErrorKind::InvalidState, "Invalid GetAttrReplyResult json")?;
+
+        if get_attrib_reply_json["result"]["data"].is_null() {
+            return Err(err_msg(IndyErrorKind::LedgerItemNotFound, "Endpoint ATTRIB isn't found on the ledger"));
+        }
+
+        let get_attrib_reply: Reply<GetAttrReplyResult> = serde_json::from_value(get_attrib_reply_json)
+            .to_indy(IndyErrorKind::InvalidState, "Invalid GetAttrReplyResult json")?;
+
+        let raw = match get_attrib_reply.result() {
+            GetAttrReplyResult::GetAttrReplyResultV0(res) => res.data,
+            GetAttrReplyResult::GetAttrReplyResultV1(res) => res.txn.data.raw
+        };
+
+        let attrib_data: ServiceEndpointAttribData = serde_json::from_str(&raw)
+            .to_indy(IndyErrorKind::InvalidState, "Invalid endpoint ATTRIB json")?;
+
+        Ok(attrib_data.endpoint)
+    }
+
+    fn _build_did_doc_service(&self,
+                              did: &DidValue,
+                              endpoint: ServiceEndpoint,
+                              get_nym_reply_result: IndyResult<String>) -> IndyResult<String> {
+        let (verkey, _) = self._parse_nym_verkey_reply(did, get_nym_reply_result)?
+            .ok_or_else(|| err_msg(IndyErrorKind::LedgerItemNotFound, "DID isn't found on the ledger"))?;
+
+        // Service of the first published type is returned
+        let service = self._did_doc_services(&self._did_url(did), verkey, endpoint).remove(0);
+
+        let res = serde_json::to_string(&service)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize DidDocService")?;
+
+        debug!("resolve_service_endpoint_for_did <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _did_url(&self, did: &DidValue) -> String {
+        match did.get_method() {
+            Some(_) => did.0.clone(),
+            None => did.qualify("sov").0
+        }
+    }
+
+    fn _did_doc_verification_method(&self, did_url: &str, key_id: &str, verkey: &str) -> IndyResult<VerificationMethod> {
+        // Crypto type suffix of the verkey defines the type of the method
+        let (public_key, crypto_type_name) = split_verkey(verkey);
+
+        let type_ = verification_method_type(crypto_type_name)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Unknown crypto of DID key: {}", crypto_type_name)))?;
+
+        Ok(VerificationMethod {
+            id: key_id.to_string(),
+            type_: type_.to_string(),
+            controller: did_url.to_string(),
+            public_key_base58: public_key.to_string(),
+        })
+    }
+
+    /// Builds one service per type published with the endpoint ("did-communication" if types aren't published).
+    fn _did_doc_services(&self, did_url: &str, verkey: String, endpoint: ServiceEndpoint) -> Vec<DidDocService> {
+        let mut types: Vec<String> = Vec::new();
+
+        for type_ in endpoint.types {
+            if !types.contains(&type_) {
+                types.push(type_);
+            }
+        }
+
+        if types.is_empty() {
+            types.push(DEFAULT_SERVICE_TYPE.to_string());
+        }
+
+        // Transport key published with the endpoint takes precedence over the NYM one
+        let recipient_key = endpoint.verkey.unwrap_or(verkey);
+
+        types.into_iter()
+            .map(|type_| DidDocService {
+                id: format!("{}#{}", did_url, type_),
+                type_,
+                priority: 0,
+                recipient_keys: vec![recipient_key.clone()],
+                routing_keys: endpoint.routing_keys.clone(),
+                service_endpoint: endpoint.endpoint.clone(),
+            })
+            .collect()
+    }
+
+    fn resolve_did(&self,
+                   pool_handle: PoolHandle,
+                   wallet_handle: Option<WalletHandle>,
+                   did: DidValue,
+                   cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("resolve_did >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}", pool_handle, wallet_handle, did);
+
+        try_cb!(self.crypto_service.validate_did(&did), cb);
+        if let Some(wallet_handle) = wallet_handle {
+            try_cb!(self.wallet_service.check(wallet_handle), cb);
+        }
+
+        // NYM is fetched first, then endpoint ATTRIB is fetched to fill DID Document services.
+        // Local pairwise is merged at the end if the wallet is given.
+        self._fetch_nym_verkey_from_ledger(pool_handle,
+                                           &did.clone(),
+                                           DidCommand::ResolveDid(pool_handle, wallet_handle, did, cb));
+    }
+
+    fn _resolve_did_nym(&self,
+                        pool_handle: PoolHandle,
+                        wallet_handle: Option<WalletHandle>,
+                        did: DidValue,
+                        get_nym_reply_result: IndyResult<String>,
+                        cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        let (verkey, _) = match self._parse_nym_verkey_reply(&did, get_nym_reply_result) {
+            Ok(Some(nym)) => nym,
+            Ok(None) => return cb(Err(err_msg(IndyErrorKind::LedgerItemNotFound, "DID isn't found on the ledger"))),
+            Err(err) => return cb(Err(err))
+        };
+
+        let get_attrib_request = try_cb!(self.ledger_service.build_get_attrib_request(None, &did, Some("endpoint"), None, None), cb);
+
+        let deferred_cmd_id = self._defer_command(DidCommand::ResolveDidContinue(wallet_handle, did, verkey, cb));
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                pool_handle,
+                get_attrib_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::ServiceEndpointAttribAck(
+                            result,
+                            deferred_cmd_id,
+                        ))).unwrap();
+                }),
+            ))).unwrap();
+    }
+
+    fn _build_did_doc(&self,
+                      wallet_handle: Option<WalletHandle>,
+                      did: &DidValue,
+                      verkey: String,
+                      get_attrib_reply_result: IndyResult<String>) -> IndyResult<String> {
+        let get_attrib_reply = get_attrib_reply_result?;
+
+        // DID may have no endpoint ATTRIB written
+        let get_attrib_reply_json: serde_json::Value = serde_json::from_str(&get_attrib_reply)
+            .to_indy(IndyErrorKind::InvalidState, "Invalid GetAttrReplyResult json")?;
+
+        let endpoint = if get_attrib_reply_json["result"]["data"].is_null() {
+            None
+        } else {
+            Some(self._parse_service_endpoint_reply(Ok(get_attrib_reply))?)
+        };
+
+        let pairwise = match wallet_handle {
+            Some(wallet_handle) => self.wallet_service.get_indy_opt_object::<Pairwise>(wallet_handle, &did.0, &RecordOptions::id_value())?,
+            None => None
+        };
+
+        let did_url = self._did_url(did);
+        let key_id = format!("{}#key-1", did_url);
+
+        let mut provenance = HashMap::new();
+        provenance.insert(key_id.clone(), PROVENANCE_LEDGER_NYM.to_string());
+
+        let mut verification_method = vec![self._did_doc_verification_method(&did_url, &key_id, &verkey)?];
+        let mut service = Vec::new();
+
+        if let Some(endpoint) = endpoint {
+            if let Some(endpoint_verkey) = endpoint.verkey.as_ref().filter(|endpoint_verkey| **endpoint_verkey != verkey) {
+                let endpoint_key_id = format!("{}#key-2", did_url);
+                provenance.insert(endpoint_key_id.clone(), PROVENANCE_LEDGER_ATTRIB.to_string());
+                verification_method.push(self._did_doc_verification_method(&did_url, &endpoint_key_id, endpoint_verkey)?);
+            }
+
+            for endpoint_service in self._did_doc_services(&did_url, verkey.clone(), endpoint) {
+                provenance.insert(endpoint_service.id.clone(), PROVENANCE_LEDGER_ATTRIB.to_string());
+                service.push(endpoint_service);
+            }
+        }
+
+        let pairwise = pairwise.map(|pairwise| {
+            provenance.insert(PAIRWISE_PROVENANCE_KEY.to_string(), PROVENANCE_WALLET_PAIRWISE.to_string());
+            DidDocPairwise { my_did: pairwise.my_did.0, metadata: pairwise.metadata }
+        });
+
+        // Only published keys are listed: keys designated in the wallet are unknown to other parties.
+        // Key agreement key is derived from ed25519 NYM verkey, so it is published as well.
+        let mut key_agreement = Vec::new();
+
+        if let Ok(key_agreement_key) = self.crypto_service.convert_key(&verkey) {
+            let key_agreement_id = format!("{}#key-{}", did_url, verification_method.len() + 1);
+            provenance.insert(key_agreement_id.clone(), PROVENANCE_LEDGER_NYM.to_string());
+            verification_method.push(self._did_doc_verification_method(&did_url, &key_agreement_id, &key_agreement_key)?);
+            key_agreement.push(key_agreement_id);
+        }
+
+        let did_doc = DidDocument {
+            context: DID_DOC_CONTEXT.to_string(),
+            id: did_url.clone(),
+            verification_method,
+            // Only NYM verkey controls the DID
+            authentication: vec![key_id.clone()],
+            assertion_method: vec![key_id],
+            key_agreement,
+            service,
+            pairwise,
+            provenance,
+        };
+
+        let res = serde_json::to_string(&did_doc)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize DidDocument")?;
+
+        debug!("resolve_did <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn create_and_store_my_did(&self,
                                wallet_handle: WalletHandle,
                                my_did_info: &MyDidInfo) -> IndyResult<(String, String)> {
@@ -205,17 +706,18 @@ impl DidCommandExecutor {
 
         let (did, key) = self.crypto_service.create_my_did(&my_did_info)?;
 
-        if let Ok(current_did) = self._wallet_get_my_did(wallet_handle, &did.did) {
-            if did.verkey == current_did.verkey {
-                return Ok((did.did.0, did.verkey));
-            } else {
-                return Err(err_msg(IndyErrorKind::DIDAlreadyExists,
-                                   format!("DID \"{}\" already exists but with different Verkey. You should specify Seed used for initial generation", did.did.0)));
-            }
+        if self._my_did_stored(wallet_handle, &did)? {
+            return Ok((did.did.0, did.verkey));
         }
 
         self.wallet_service.add_indy_object(wallet_handle, &did.did.0, &did, &HashMap::new())?;
         let _ = self.wallet_service.add_indy_object(wallet_handle, &key.verkey, &key, &HashMap::new()).ok();
+        store_key_agreement_key(&self.wallet_service, &self.crypto_service, wallet_handle, &key.verkey)?;
+
+        if let Some(ref derivation_path) = my_did_info.derivation_path {
+            let metadata = KeyMetadata { value: String::new(), derivation_path: Some(derivation_path.to_string()) };
+            let _ = self.wallet_service.add_indy_object(wallet_handle, &key.verkey, &metadata, &HashMap::new()).ok();
+        }
 
         let res = (did.did.0, did.verkey);
 
@@ -224,6 +726,127 @@ impl DidCommandExecutor {
         Ok(res)
     }
 
+    fn create_and_store_my_dids(&self,
+                                wallet_handle: WalletHandle,
+                                count: u32,
+                                my_dids_info: &MyDidsInfo) -> IndyResult<String> {
+        debug!("create_and_store_my_dids >>> wallet_handle: {:?}, count: {:?}, my_dids_info: {:?}", wallet_handle, count, secret!(my_dids_info));
+
+        if count == 0 || count > MAX_DIDS_BATCH_SIZE {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Number of DIDs to create must be from 1 to {}", MAX_DIDS_BATCH_SIZE)));
+        }
+
+        let start_index = my_dids_info.start_index.unwrap_or(0);
+
+        let end_index = start_index.checked_add(count)
+            .filter(|end_index| my_dids_info.hd_seed.is_none() || *end_index <= MAX_HD_DID_INDEX)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "DID index overflow"))?;
+
+        if let Some(ref hd_seed) = my_dids_info.hd_seed {
+            self.crypto_service.check_master_seed(hd_seed, my_dids_info.hd_seed_encoding)?;
+        }
+
+        // All DIDs are created before anything is written, so nothing is stored if any of them fails
+        let mut dids = Vec::with_capacity(count as usize);
+        let mut new_dids: Vec<Did> = Vec::new();
+        let mut new_keys: Vec<Key> = Vec::new();
+        let mut new_key_agreement_keys: Vec<(String, KeyAgreementKey)> = Vec::new();
+        let mut new_metadata: Vec<(String, KeyMetadata)> = Vec::new();
+
+        for index in start_index..end_index {
+            let my_did_info = my_dids_info.did_info(index);
+            let (did, key) = self.crypto_service.create_my_did(&my_did_info)?;
+
+            dids.push(CreatedDid { did: did.did.0.clone(), verkey: did.verkey.clone(), index: my_dids_info.hd_seed.as_ref().map(|_| index) });
+
+            if self._my_did_stored(wallet_handle, &did)? {
+                continue;
+            }
+
+            if let Ok(key_agreement_key) = self.crypto_service.convert_key(&key.verkey) {
+                if !self.wallet_service.record_exists::<KeyAgreementKey>(wallet_handle, &key_agreement_key)? {
+                    new_key_agreement_keys.push((key_agreement_key, KeyAgreementKey { verkey: key.verkey.clone() }));
+                }
+            }
+
+            if let Some(derivation_path) = my_did_info.derivation_path {
+                if !self.wallet_service.record_exists::<KeyMetadata>(wallet_handle, &key.verkey)? {
+                    new_metadata.push((key.verkey.clone(), KeyMetadata { value: String::new(), derivation_path: Some(derivation_path) }));
+                }
+            }
+
+            if !self.wallet_service.record_exists::<Key>(wallet_handle, &key.verkey)? {
+                new_keys.push(key);
+            }
+
+            new_dids.push(did);
+        }
+
+        let keys: Vec<(&str, &Key)> = new_keys.iter().map(|key| (key.verkey.as_str(), key)).collect();
+        let key_agreement_keys: Vec<(&str, &KeyAgreementKey)> = new_key_agreement_keys.iter().map(|(name, key)| (name.as_str(), key)).collect();
+        let metadata: Vec<(&str, &KeyMetadata)> = new_metadata.iter().map(|(name, metadata)| (name.as_str(), metadata)).collect();
+        let my_dids: Vec<(&str, &Did)> = new_dids.iter().map(|did| (did.did.0.as_str(), did)).collect();
+
+        // DIDs are written last, so they are never stored without their keys
+        self._add_batch(wallet_handle, &keys)?;
+
+        if let Err(err) = self._add_batch(wallet_handle, &key_agreement_keys) {
+            self._undo_batch::<Key, _>(wallet_handle, &keys);
+            return Err(err);
+        }
+
+        if let Err(err) = self._add_batch(wallet_handle, &metadata) {
+            self._undo_batch::<KeyAgreementKey, _>(wallet_handle, &key_agreement_keys);
+            self._undo_batch::<Key, _>(wallet_handle, &keys);
+            return Err(err);
+        }
+
+        if let Err(err) = self._add_batch(wallet_handle, &my_dids) {
+            self._undo_batch::<KeyMetadata, _>(wallet_handle, &metadata);
+            self._undo_batch::<KeyAgreementKey, _>(wallet_handle, &key_agreement_keys);
+            self._undo_batch::<Key, _>(wallet_handle, &keys);
+            return Err(err);
+        }
+
+        let res = serde_json::to_string(&dids)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize created DIDs")?;
+
+        debug!("create_and_store_my_dids <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Returns whether the DID is already stored in the wallet, fails if it is stored with another verkey.
+    fn _my_did_stored(&self, wallet_handle: WalletHandle, did: &Did) -> IndyResult<bool> {
+        match self._wallet_get_my_did(wallet_handle, &did.did) {
+            Ok(current_did) if did.verkey == current_did.verkey => Ok(true),
+            Ok(_) => Err(err_msg(IndyErrorKind::DIDAlreadyExists,
+                                 format!("DID \"{}\" already exists but with different Verkey. You should specify Seed used for initial generation", did.did.0))),
+            Err(_) => Ok(false),
+        }
+    }
+
+    fn _add_batch<T>(&self, wallet_handle: WalletHandle, objects: &[(&str, &T)]) -> IndyResult<()> where T: ::serde::Serialize + NamedType {
+        if objects.is_empty() {
+            return Ok(());
+        }
+
+        self.wallet_service.add_indy_objects_batch(wallet_handle, objects)
+    }
+
+    fn _undo_batch<T, V>(&self, wallet_handle: WalletHandle, objects: &[(&str, V)]) where T: NamedType {
+        if objects.is_empty() {
+            return;
+        }
+
+        let names: Vec<&str> = objects.iter().map(|&(name, _)| name).collect();
+
+        if let Err(err) = self.wallet_service.delete_indy_records_batch::<T>(wallet_handle, &names) {
+            warn!("Cannot delete {} records stored by the failed batch: {:?}", T::short_type_name(), err);
+        }
+    }
+
     fn replace_keys_start(&self,
                           wallet_handle: WalletHandle,
                           key_info: &KeyInfo,
@@ -238,6 +861,7 @@ impl DidCommandExecutor {
         let my_temporary_did = TemporaryDid { did: my_did.did, verkey: temporary_key.verkey.clone() };
 
         self.wallet_service.add_indy_object(wallet_handle, &temporary_key.verkey, &temporary_key, &HashMap::new())?;
+        store_key_agreement_key(&self.wallet_service, &self.crypto_service, wallet_handle, &temporary_key.verkey)?;
         self.wallet_service.add_indy_object(wallet_handle, &my_temporary_did.did.0, &my_temporary_did, &HashMap::new())?;
 
         let res = my_temporary_did.verkey;
@@ -480,6 +1104,78 @@ impl DidCommandExecutor {
         Ok(res)
     }
 
+    fn set_did_key(&self,
+                   wallet_handle: WalletHandle,
+                   did: &DidValue,
+                   purpose: &str,
+                   verkey: String) -> IndyResult<()> {
+        debug!("set_did_key >>> wallet_handle: {:?}, did: {:?}, purpose: {:?}, verkey: {:?}", wallet_handle, did, purpose, verkey);
+
+        self.crypto_service.validate_did(did)?;
+
+        self._wallet_get_my_did(wallet_handle, did)?;
+
+        let mut did_keys = self.wallet_service.get_indy_opt_object::<DidKeys>(wallet_handle, &did.0, &RecordOptions::id_value())?
+            .unwrap_or_default();
+
+        match purpose {
+            AUTHENTICATION_KEY_PURPOSE | ASSERTION_METHOD_KEY_PURPOSE => {
+                // Signing key has to be stored in the wallet
+                self.crypto_service.validate_key(&verkey)?;
+                self.wallet_service.get_indy_object::<Key>(wallet_handle, &verkey, &RecordOptions::id_value())?;
+
+                if purpose == AUTHENTICATION_KEY_PURPOSE {
+                    did_keys.authentication = Some(verkey);
+                } else {
+                    did_keys.assertion_method = Some(verkey);
+                }
+            }
+            KEY_AGREEMENT_KEY_PURPOSE => {
+                // Key agreement key has to be derived from a key of the wallet to make packed messages unpackable
+                self.wallet_service.get_indy_object::<KeyAgreementKey>(wallet_handle, &verkey, &RecordOptions::id_value())?;
+
+                did_keys.key_agreement = Some(verkey);
+            }
+            _ => return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unknown key purpose: {}", purpose)))
+        }
+
+        self.wallet_service.upsert_indy_object(wallet_handle, &did.0, &did_keys)?;
+
+        debug!("set_did_key <<<");
+
+        Ok(())
+    }
+
+    fn get_did_keys(&self,
+                    wallet_handle: WalletHandle,
+                    did: &DidValue) -> IndyResult<String> {
+        debug!("get_did_keys >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+        self.crypto_service.validate_did(did)?;
+
+        let my_did = self._wallet_get_my_did(wallet_handle, did)?;
+
+        let did_keys = self.wallet_service.get_indy_opt_object::<DidKeys>(wallet_handle, &did.0, &RecordOptions::id_value())?
+            .unwrap_or_default();
+
+        // Only ed25519 keys can be converted to key agreement keys
+        let key_agreement = did_keys.key_agreement
+            .or_else(|| self.crypto_service.convert_key(&my_did.verkey).ok());
+
+        let did_keys = DidKeys {
+            authentication: Some(did_keys.authentication.unwrap_or_else(|| my_did.verkey.clone())),
+            assertion_method: Some(did_keys.assertion_method.unwrap_or_else(|| my_did.verkey.clone())),
+            key_agreement,
+        };
+
+        let res = serde_json::to_string(&did_keys)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize DidKeys")?;
+
+        debug!("get_did_keys <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn abbreviate_verkey(&self,
                          did: &DidValue,
                          verkey: String) -> IndyResult<String> {
@@ -493,12 +1189,17 @@ impl DidCommandExecutor {
         }
 
         let did = &did.to_unqualified().0.from_base58()?;
-        let dverkey = &verkey.from_base58()?;
+
+        // Crypto type suffix of not ed25519 keys is kept in abbreviated form as well
+        let (dverkey, crypto_type) = match verkey.find(':') {
+            Some(pos) => (verkey[..pos].from_base58()?, Some(&verkey[pos..])),
+            None => (verkey.from_base58()?, None)
+        };
 
         let (first_part, second_part) = dverkey.split_at(16);
 
         let res = if first_part.eq(did.as_slice()) {
-            format!("~{}", second_part.to_base58())
+            format!("~{}{}", second_part.to_base58(), crypto_type.unwrap_or(""))
         } else {
             verkey
         };
@@ -536,6 +1237,9 @@ impl DidCommandExecutor {
         // move endpoint
         self.update_dependent_entity_reference::<Endpoint>(wallet_handle, &did.0, &curr_did.did.0)?;
 
+        // move observed ledger state
+        self.update_dependent_entity_reference::<DidNymState>(wallet_handle, &did.0, &curr_did.did.0)?;
+
         // move all pairwise
         let mut pairwise_search =
             self.wallet_service.search_indy_records::<Pairwise>(wallet_handle, "{}", &RecordOptions::id_value())?;
@@ -557,6 +1261,256 @@ impl DidCommandExecutor {
         Ok(curr_did.did.0)
     }
 
+    fn create_did_ownership_proof(&self,
+                                  wallet_handle: WalletHandle,
+                                  pool_handle: PoolHandle,
+                                  did: DidValue,
+                                  challenge: String,
+                                  audience: String,
+                                  cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("create_did_ownership_proof >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, challenge: {:?}, audience: {:?}", wallet_handle, pool_handle, did, challenge, audience);
+
+        try_cb!(self.crypto_service.validate_did(&did), cb);
+        try_cb!(self._wallet_get_my_did(wallet_handle, &did), cb);
+
+        // The proof references the NYM transaction, so it is completed once the DID is fetched from ledger.
+        self._fetch_nym_verkey_from_ledger(pool_handle,
+                                           &did.clone(),
+                                           DidCommand::CreateDidOwnershipProof(wallet_handle, pool_handle, did, challenge, audience, cb));
+    }
+
+    fn _create_did_ownership_proof(&self,
+                                   wallet_handle: WalletHandle,
+                                   did: &DidValue,
+                                   challenge: &str,
+                                   audience: &str,
+                                   get_nym_reply: IndyResult<String>) -> IndyResult<String> {
+        let my_did = self._wallet_get_my_did(wallet_handle, did)?;
+
+        let (ledger_verkey, seq_no) = self._parse_nym_verkey_reply(did, get_nym_reply)?
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "DID isn't found on the ledger"))?;
+
+        if !hardened::ct_eq_str(&ledger_verkey, &my_did.verkey) {
+            return Err(err_msg(IndyErrorKind::InvalidState, "DID verkey stored in the wallet differs from the ledger one"));
+        }
+
+        let my_key: Key = self.wallet_service.get_indy_object(wallet_handle, &my_did.verkey, &RecordOptions::id_value())?;
+        let payload = DidOwnershipProof::signature_payload(&my_did.did, challenge, audience);
+        let signature = self.crypto_service.sign(&my_key, &payload)?;
+
+        let proof = DidOwnershipProof {
+            did: my_did.did,
+            verkey: my_did.verkey,
+            challenge: challenge.to_string(),
+            audience: audience.to_string(),
+            signature: signature.to_base58(),
+            seq_no,
+        };
+
+        let res = serde_json::to_string(&proof)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize DidOwnershipProof")?;
+
+        debug!("create_did_ownership_proof <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn check_my_did_nym(&self,
+                        wallet_handle: WalletHandle,
+                        pool_handle: PoolHandle,
+                        did: DidValue,
+                        cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("check_my_did_nym >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}", wallet_handle, pool_handle, did);
+
+        try_cb!(self.crypto_service.validate_did(&did), cb);
+        try_cb!(self._wallet_get_my_did(wallet_handle, &did), cb);
+
+        self._fetch_nym_verkey_from_ledger(pool_handle,
+                                           &did.clone(),
+                                           DidCommand::CheckMyDidNym(wallet_handle, pool_handle, did, cb));
+    }
+
+    fn _check_my_did_nym(&self,
+                         wallet_handle: WalletHandle,
+                         did: &DidValue,
+                         get_nym_reply: IndyResult<String>) -> IndyResult<String> {
+        let my_did = self._wallet_get_my_did(wallet_handle, did)?;
+
+        let (nym_state, changed) = self._watch_my_did_nym(wallet_handle, &my_did, &get_nym_reply?)?
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "DID isn't found on the ledger"))?;
+
+        let res = json!({
+            "verkey": nym_state.verkey,
+            "role": nym_state.role,
+            "changed": changed,
+        }).to_string();
+
+        debug!("check_my_did_nym <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Compares NYM of my DID read from the ledger with the wallet and the previously observed one
+    // and emits `DidNymChanged` event if verkey or role have been changed not through this wallet.
+    fn _watch_my_did_nym(&self,
+                         wallet_handle: WalletHandle,
+                         my_did: &Did,
+                         get_nym_reply: &str) -> IndyResult<Option<(DidNymState, bool)>> {
+        trace!("_watch_my_did_nym >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+
+        let their_did_info = match self._parse_get_nym_reply(&my_did.did, get_nym_reply) {
+            Ok(their_did_info) => their_did_info,
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => return Ok(None),
+            Err(err) => return Err(err)
+        };
+
+        let nym_state = DidNymState {
+            verkey: self.crypto_service.create_their_did(&their_did_info)?.verkey,
+            role: self._parse_get_nym_role(get_nym_reply)?,
+        };
+
+        let previous_state: Option<DidNymState> = match self.wallet_service.get_indy_object(wallet_handle, &my_did.did.0, &RecordOptions::id_value()) {
+            Ok(previous_state) => Some(previous_state),
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => None,
+            Err(err) => return Err(err)
+        };
+
+        // Key rotation started by replace_keys_start is written to the ledger before it is applied to the wallet.
+        let temp_verkey = match self.wallet_service.get_indy_object::<TemporaryDid>(wallet_handle, &my_did.did.0, &RecordOptions::id_value()) {
+            Ok(temp_did) => Some(temp_did.verkey),
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => None,
+            Err(err) => return Err(err)
+        };
+
+        let verkey_changed = nym_state.verkey != my_did.verkey && Some(&nym_state.verkey) != temp_verkey.as_ref();
+        let role_changed = previous_state.as_ref().map(|state| state.role != nym_state.role).unwrap_or(false);
+        let changed = verkey_changed || role_changed;
+
+        if changed {
+            emit_event(&Event::DidNymChanged {
+                did: my_did.did.clone(),
+                wallet_verkey: my_did.verkey.clone(),
+                previous_verkey: previous_state.as_ref().map(|state| state.verkey.clone()),
+                previous_role: previous_state.as_ref().and_then(|state| state.role.clone()),
+                verkey: nym_state.verkey.clone(),
+                role: nym_state.role.clone(),
+            });
+        }
+
+        if previous_state.is_some() {
+            self.wallet_service.update_indy_object(wallet_handle, &my_did.did.0, &nym_state)?;
+        } else {
+            self.wallet_service.add_indy_object(wallet_handle, &my_did.did.0, &nym_state, &HashMap::new())?;
+        }
+
+        trace!("_watch_my_did_nym <<< nym_state: {:?}, changed: {:?}", nym_state, changed);
+
+        Ok(Some((nym_state, changed)))
+    }
+
+    fn _parse_get_nym_role(&self, get_nym_reply: &str) -> IndyResult<Option<String>> {
+        let get_nym_response: Reply<GetNymReplyResult> = serde_json::from_str(get_nym_reply)
+            .to_indy(IndyErrorKind::InvalidState, "Invalid GetNymReplyResult json")?;
+
+        let role = match get_nym_response.result() {
+            GetNymReplyResult::GetNymReplyResultV0(res) => match res.data {
+                Some(ref data) => serde_json::from_str::<GetNymResultDataV0>(data)
+                    .to_indy(IndyErrorKind::InvalidState, "Invalid GetNymResultData json")?
+                    .role,
+                None => None
+            },
+            GetNymReplyResult::GetNymReplyResultV1(res) => res.txn.data.role
+        };
+
+        Ok(role)
+    }
+
+    fn verify_did_ownership_proof(&self,
+                                  pool_handle: PoolHandle,
+                                  proof: DidOwnershipProof,
+                                  challenge: String,
+                                  audience: String,
+                                  cb: Box<dyn Fn(IndyResult<bool>) + Send>) {
+        debug!("verify_did_ownership_proof >>> pool_handle: {:?}, proof: {:?}, challenge: {:?}, audience: {:?}", pool_handle, proof, challenge, audience);
+
+        try_cb!(self.crypto_service.validate_did(&proof.did), cb);
+        try_cb!(self.crypto_service.validate_key(&proof.verkey), cb);
+
+        if !hardened::ct_eq_str(&proof.challenge, &challenge) || proof.audience != audience {
+            debug!("verify_did_ownership_proof <<< res: false (challenge or audience mismatch)");
+            return cb(Ok(false));
+        }
+
+        self._fetch_nym_verkey_from_ledger(pool_handle,
+                                           &proof.did.clone(),
+                                           DidCommand::VerifyDidOwnershipProof(pool_handle, proof, challenge, audience, cb));
+    }
+
+    fn _verify_did_ownership_proof(&self,
+                                   proof: &DidOwnershipProof,
+                                   get_nym_reply: IndyResult<String>) -> IndyResult<bool> {
+        let (ledger_verkey, seq_no) = match self._parse_nym_verkey_reply(&proof.did, get_nym_reply)? {
+            Some(nym) => nym,
+            None => return Ok(false)
+        };
+
+        if !hardened::ct_eq_str(&ledger_verkey, &proof.verkey) || (proof.seq_no.is_some() && seq_no != proof.seq_no) {
+            return Ok(false);
+        }
+
+        let signature = proof.signature.from_base58()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid signature in DidOwnershipProof: {:?}", err)))?;
+
+        let payload = DidOwnershipProof::signature_payload(&proof.did, &proof.challenge, &proof.audience);
+        let res = self.crypto_service.verify(&proof.verkey, &payload, &signature)?;
+
+        debug!("verify_did_ownership_proof <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn nym_verkey_ack(&self,
+                      get_nym_reply_result: IndyResult<String>,
+                      deferred_cmd_id: CommandHandle) {
+        let deferred_cmd = self.deferred_commands.borrow_mut().remove(&deferred_cmd_id);
+
+        match deferred_cmd {
+            Some(DidCommand::CreateDidOwnershipProof(wallet_handle, _, did, challenge, audience, cb)) =>
+                cb(self._create_did_ownership_proof(wallet_handle, &did, &challenge, &audience, get_nym_reply_result)),
+            Some(DidCommand::VerifyDidOwnershipProof(_, proof, _, _, cb)) =>
+                cb(self._verify_did_ownership_proof(&proof, get_nym_reply_result)),
+            Some(DidCommand::CheckMyDidNym(wallet_handle, _, did, cb)) =>
+                cb(self._check_my_did_nym(wallet_handle, &did, get_nym_reply_result)),
+            Some(DidCommand::ResolveServiceEndpointForDid(_, did, cb)) => {
+                let endpoint = self.fetched_service_endpoints.borrow_mut().remove(&deferred_cmd_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No endpoint fetched for deferred command"));
+                let endpoint = try_cb!(endpoint, cb);
+
+                cb(self._build_did_doc_service(&did, endpoint, get_nym_reply_result))
+            }
+            Some(DidCommand::ResolveDid(pool_handle, wallet_handle, did, cb)) =>
+                self._resolve_did_nym(pool_handle, wallet_handle, did, get_nym_reply_result, cb),
+            Some(cmd) => self._call_error_cb(cmd, err_msg(IndyErrorKind::InvalidState, "Unexpected deferred command")),
+            None => error!("No deferred command for id: {:?}", deferred_cmd_id)
+        }
+    }
+
+    // Returns the full verkey and seqNo of NYM transaction or None if the DID isn't written to the ledger.
+    fn _parse_nym_verkey_reply(&self, did: &DidValue, get_nym_reply_result: IndyResult<String>) -> IndyResult<Option<(String, Option<u64>)>> {
+        let get_nym_reply = get_nym_reply_result?;
+
+        let their_did_info = match self._parse_get_nym_reply(did, &get_nym_reply) {
+            Ok(their_did_info) => their_did_info,
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => return Ok(None),
+            Err(err) => return Err(err)
+        };
+
+        let their_did = self.crypto_service.create_their_did(&their_did_info)?;
+        let seq_no = parse_response_metadata(&get_nym_reply)?.seq_no;
+
+        Ok(Some((their_did.verkey, seq_no)))
+    }
+
     fn update_dependent_entity_reference<T>(&self, wallet_handle: WalletHandle, id: &str, new_id: &str) -> IndyResult<()>
         where T: ::serde::Serialize + ::serde::de::DeserializeOwned + NamedType {
         if let Ok(record) = self.wallet_service.get_indy_record_value::<T>(wallet_handle, id, "{}") {
@@ -580,7 +1534,19 @@ impl DidCommandExecutor {
 
         let get_nym_reply = get_nym_reply_result?;
 
-        let get_nym_response: Reply<GetNymReplyResult> = serde_json::from_str(&get_nym_reply)
+        let their_did_info = self._parse_get_nym_reply(&did, &get_nym_reply)?;
+
+        let their_did = self.crypto_service.create_their_did(&their_did_info)?;
+
+        self.wallet_service.add_indy_object(wallet_handle, &their_did.did.0, &their_did, &HashMap::new())?;
+
+        trace!("_get_nym_ack <<<");
+
+        Ok(())
+    }
+
+    fn _parse_get_nym_reply(&self, did: &DidValue, get_nym_reply: &str) -> IndyResult<TheirDidInfo> {
+        let get_nym_response: Reply<GetNymReplyResult> = serde_json::from_str(get_nym_reply)
             .to_indy(IndyErrorKind::InvalidState, "Invalid GetNymReplyResult json")?;
 
         let their_did_info = match get_nym_response.result() {
@@ -597,13 +1563,7 @@ impl DidCommandExecutor {
             GetNymReplyResult::GetNymReplyResultV1(res) => TheirDidInfo::new(res.txn.data.did.qualify(did.get_method()), res.txn.data.verkey)
         };
 
-        let their_did = self.crypto_service.create_their_did(&their_did_info)?;
-
-        self.wallet_service.add_indy_object(wallet_handle, &their_did.did.0, &their_did, &HashMap::new())?;
-
-        trace!("_get_nym_ack <<<");
-
-        Ok(())
+        Ok(their_did_info)
     }
 
     fn get_attrib_ack(&self,
@@ -677,6 +1637,27 @@ impl DidCommandExecutor {
             DidCommand::GetEndpointForDid(_, _, _, cb) => {
                 cb(Err(err));
             }
+            DidCommand::CreateDidOwnershipProof(_, _, _, _, _, cb) => {
+                cb(Err(err));
+            }
+            DidCommand::VerifyDidOwnershipProof(_, _, _, _, cb) => {
+                cb(Err(err));
+            }
+            DidCommand::CheckMyDidNym(_, _, _, cb) => {
+                cb(Err(err));
+            }
+            DidCommand::PublishEndpointForDid(_, _, _, _, cb) => {
+                cb(Err(err));
+            }
+            DidCommand::ResolveServiceEndpointForDid(_, _, cb) => {
+                cb(Err(err));
+            }
+            DidCommand::ResolveDid(_, _, _, cb) => {
+                cb(Err(err));
+            }
+            DidCommand::ResolveDidContinue(_, _, _, cb) => {
+                cb(Err(err));
+            }
             _ => {}
         }
     }
@@ -733,6 +1714,31 @@ impl DidCommandExecutor {
             ))).unwrap();
     }
 
+    fn _fetch_nym_verkey_from_ledger(&self, pool_handle: PoolHandle, did: &DidValue, deferred_cmd: DidCommand) {
+        let deferred_cmd_id = self._defer_command(deferred_cmd);
+        self._submit_get_nym_for_deferred_command(pool_handle, did, deferred_cmd_id);
+    }
+
+    fn _submit_get_nym_for_deferred_command(&self, pool_handle: PoolHandle, did: &DidValue, deferred_cmd_id: CommandHandle) {
+        let get_nym_request = match self.ledger_service.build_get_nym_request(None, did) {
+            Ok(get_nym_request) => get_nym_request,
+            Err(err) => return self.nym_verkey_ack(Err(err), deferred_cmd_id)
+        };
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                pool_handle,
+                get_nym_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::NymVerkeyAck(
+                            result,
+                            deferred_cmd_id,
+                        ))).unwrap();
+                }),
+            ))).unwrap();
+    }
+
     fn _wallet_get_my_did(&self, wallet_handle: WalletHandle, my_did: &DidValue) -> IndyResult<Did> {
         self.wallet_service.get_indy_object(wallet_handle, &my_did.0, &RecordOptions::id_value())
     }
@@ -741,3 +1747,11 @@ impl DidCommandExecutor {
         self.wallet_service.get_indy_object(wallet_handle, &their_did.0, &RecordOptions::id_value())
     }
 }
+
+/// Key my DID signs issuance log heads and proof holder bindings with.
+/// It is the DID verkey: designated keys aren't published, so verifiers check these signatures against the ledger NYM verkey.
+pub fn get_my_did_signing_key(wallet_service: &WalletService, wallet_handle: WalletHandle, did: &DidValue) -> IndyResult<Key> {
+    let my_did: Did = wallet_service.get_indy_object(wallet_handle, &did.0, &RecordOptions::id_value())?;
+
+    wallet_service.get_indy_object(wallet_handle, &my_did.verkey, &RecordOptions::id_value())
+}
@@ -1,7 +1,9 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use std::rc::Rc;
 use std::string::ToString;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use indy_api_types::{CommandHandle, PoolHandle, WalletHandle};
 use indy_api_types::errors::prelude::*;
@@ -18,17 +20,21 @@ use crate::domain::anoncreds::credential_definition::{CredentialDefinition, Cred
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1, RevocationRegistryId};
 use crate::domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
 use crate::domain::anoncreds::schema::{Schema, SchemaId, SchemaV1};
-use crate::domain::crypto::did::{Did, DidValue};
+use crate::domain::crypto::did::{Did, DidValue, ShortDidValue};
 use crate::domain::crypto::key::Key;
 use crate::domain::ledger::auth_rule::{AuthRules, Constraint};
-use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, GetTxnAuthorAgreementData};
+use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, AcceptanceMechanismsData, ActiveTxnAuthorAgreement, GetTxnAuthorAgreementData, TxnAuthorAgreementData};
 use crate::domain::ledger::node::NodeOperationData;
+use crate::domain::ledger::nym::{NymHistoryEntry, NymHistoryOptions};
 use crate::domain::ledger::pool::Schedule;
 use crate::domain::ledger::request::Request;
+use crate::domain::ledger::response::WriteReceipt;
+use crate::domain::ledger::txn::{DEFAULT_FETCH_TXNS_BATCH_SIZE, MAX_TXN_WALK_LENGTH, FetchTxnsOptions, LedgerTxn, TxnsBatch};
 use crate::services::crypto::CryptoService;
 use crate::services::ledger::LedgerService;
 use crate::services::pool::{
     parse_response_metadata,
+    parse_write_receipt,
     PoolService
 };
 use crate::utils::crypto::signature_serializer::serialize_signature;
@@ -64,6 +70,11 @@ pub enum LedgerCommand {
         DidValue, // submitter did
         String, // request json
         Box<dyn Fn(IndyResult<String>) + Send>),
+    MultiSignRequestWithDids(
+        WalletHandle,
+        Vec<DidValue>, // submitter dids
+        String, // request json
+        Box<dyn Fn(IndyResult<String>) + Send>),
     BuildGetDdoRequest(
         Option<DidValue>, // submitter did
         DidValue, // target did
@@ -239,6 +250,27 @@ pub enum LedgerCommand {
         IndyResult<String>,
         CommandHandle,
     ),
+    GetNymHistory(
+        PoolHandle,
+        DidValue, // target did
+        Option<NymHistoryOptions>,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetNymHistoryContinue(
+        PoolHandle,
+        ShortDidValue, // target did
+        u64, // first sequence number
+        IndyResult<String>, // GET_NYM response
+        CommandHandle,
+    ),
+    FetchTxns(
+        PoolHandle,
+        Option<FetchTxnsOptions>,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    WalkTxnsContinue(
+        TxnWalk,
+        IndyResult<String>, // GET_TXN response
+        CommandHandle,
+    ),
     BuildTxnAuthorAgreementRequest(
         DidValue, // submitter did
         Option<String>, // text
@@ -264,6 +296,15 @@ pub enum LedgerCommand {
         Option<u64>, // timestamp
         Option<String>, // version
         Box<dyn Fn(IndyResult<String>) + Send>),
+    GetActiveTxnAuthorAgreement(
+        PoolHandle,
+        Option<String>, // accepted taa digest
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetActiveTxnAuthorAgreementContinue(
+        TxnAuthorAgreementFetch,
+        IndyResult<String>, // GET_TXN_AUTHR_AGRMT or GET_TXN_AUTHR_AGRMT_AML response
+        CommandHandle,
+    ),
     AppendTxnAuthorAgreementAcceptanceToRequest(
         String, // request json
         Option<String>, // text
@@ -276,6 +317,22 @@ pub enum LedgerCommand {
         String, // request json
         DidValue, // endorser did
         Box<dyn Fn(IndyResult<String>) + Send>),
+    CheckCredDefCompatibility(
+        PoolHandle,
+        CredentialDefinitionId,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CheckCredDefCompatibilityContinue(
+        CredentialDefinitionId,
+        IndyResult<String>, // GET_CRED_DEF response
+        CommandHandle,
+    ),
+    GetWriteReceipt(
+        String, // response
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerifyWriteReceipt(
+        PoolHandle,
+        WriteReceipt,
+        Box<dyn Fn(IndyResult<bool>) + Send>),
 }
 
 pub struct LedgerCommandExecutor {
@@ -286,6 +343,10 @@ pub struct LedgerCommandExecutor {
 
     send_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String)>)>>>,
+    nym_history_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    txn_walk_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<TxnWalk>)>>>,
+    txn_author_agreement_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    cred_def_compatibility_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
 }
 
 impl LedgerCommandExecutor {
@@ -300,6 +361,10 @@ impl LedgerCommandExecutor {
             ledger_service,
             send_callbacks: RefCell::new(HashMap::new()),
             pending_callbacks: RefCell::new(HashMap::new()),
+            nym_history_callbacks: RefCell::new(HashMap::new()),
+            txn_walk_callbacks: RefCell::new(HashMap::new()),
+            txn_author_agreement_callbacks: RefCell::new(HashMap::new()),
+            cred_def_compatibility_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -339,6 +404,10 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "MultiSignRequest command received");
                 cb(self.multi_sign_request(wallet_handle, &submitter_did, &request_json));
             }
+            LedgerCommand::MultiSignRequestWithDids(wallet_handle, submitter_dids, request_json, cb) => {
+                debug!(target: "ledger_command_executor", "MultiSignRequestWithDids command received");
+                cb(self.multi_sign_request_with_dids(wallet_handle, &submitter_dids, &request_json));
+            }
             LedgerCommand::BuildGetDdoRequest(submitter_did, target_did, cb) => {
                 debug!(target: "ledger_command_executor", "BuildGetDdoRequest command received");
                 cb(self.build_get_ddo_request(submitter_did.as_ref(), &target_did));
@@ -492,6 +561,22 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "GetCredDefContinue command received");
                 self._get_cred_def_continue(id, pool_response, cb_id);
             }
+            LedgerCommand::GetNymHistory(pool_handle, did, options, cb) => {
+                debug!(target: "ledger_command_executor", "GetNymHistory command received");
+                self.get_nym_history(pool_handle, &did, options.unwrap_or_default(), cb);
+            }
+            LedgerCommand::GetNymHistoryContinue(pool_handle, did, from, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetNymHistoryContinue command received");
+                self._get_nym_history_continue(pool_handle, did, from, pool_response, cb_id);
+            }
+            LedgerCommand::FetchTxns(pool_handle, options, cb) => {
+                debug!(target: "ledger_command_executor", "FetchTxns command received");
+                self.fetch_txns(pool_handle, options.unwrap_or_default(), cb);
+            }
+            LedgerCommand::WalkTxnsContinue(walk, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "WalkTxnsContinue command received");
+                self._walk_txns_continue(walk, pool_response, cb_id);
+            }
             LedgerCommand::BuildTxnAuthorAgreementRequest(submitter_did, text, version, ratification_ts, retirement_ts, cb) => {
                 debug!(target: "ledger_command_executor", "BuildTxnAuthorAgreementRequest command received");
                 cb(self.build_txn_author_agreement_request(&submitter_did, text.as_ref().map(String::as_str), &version, ratification_ts, retirement_ts));
@@ -514,6 +599,14 @@ impl LedgerCommandExecutor {
                                                                 timestamp,
                                                                 version.as_ref().map(String::as_str)));
             }
+            LedgerCommand::GetActiveTxnAuthorAgreement(pool_handle, accepted_digest, cb) => {
+                debug!(target: "ledger_command_executor", "GetActiveTxnAuthorAgreement command received");
+                self.get_active_txn_author_agreement(pool_handle, accepted_digest, cb);
+            }
+            LedgerCommand::GetActiveTxnAuthorAgreementContinue(fetch, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetActiveTxnAuthorAgreementContinue command received");
+                self._get_active_txn_author_agreement_continue(fetch, pool_response, cb_id);
+            }
             LedgerCommand::AppendTxnAuthorAgreementAcceptanceToRequest(request_json, text, version, hash, acc_mech_type, time_of_acceptance, cb) => {
                 debug!(target: "ledger_command_executor", "AppendTxnAuthorAgreementAcceptanceToRequest command received");
                 cb(self.append_txn_author_agreement_acceptance_to_request(&request_json,
@@ -528,6 +621,22 @@ impl LedgerCommandExecutor {
                 cb(self.append_request_endorser(&request_json,
                                                 &endorser_did));
             }
+            LedgerCommand::CheckCredDefCompatibility(pool_handle, id, cb) => {
+                debug!(target: "ledger_command_executor", "CheckCredDefCompatibility command received");
+                self.check_cred_def_compatibility(pool_handle, &id, cb);
+            }
+            LedgerCommand::CheckCredDefCompatibilityContinue(id, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "CheckCredDefCompatibilityContinue command received");
+                self._check_cred_def_compatibility_continue(id, pool_response, cb_id);
+            }
+            LedgerCommand::GetWriteReceipt(response, cb) => {
+                debug!(target: "ledger_command_executor", "GetWriteReceipt command received");
+                cb(self.get_write_receipt(&response));
+            }
+            LedgerCommand::VerifyWriteReceipt(pool_handle, receipt, cb) => {
+                debug!(target: "ledger_command_executor", "VerifyWriteReceipt command received");
+                cb(self.verify_write_receipt(pool_handle, receipt));
+            }
         };
     }
 
@@ -664,6 +773,39 @@ impl LedgerCommandExecutor {
 
         Ok(res)
     }
+
+    fn multi_sign_request_with_dids(&self,
+                                    wallet_handle: WalletHandle,
+                                    submitter_dids: &[DidValue],
+                                    request_json: &str) -> IndyResult<String> {
+        debug!("multi_sign_request_with_dids >>> wallet_handle: {:?}, submitter_dids: {:?}, request_json: {:?}", wallet_handle, submitter_dids, request_json);
+
+        if submitter_dids.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Empty list of DIDs has been passed"));
+        }
+
+        let mut unique_dids = HashSet::new();
+
+        for did in submitter_dids {
+            self.crypto_service.validate_did(did)?;
+
+            if !unique_dids.insert(did.to_short()) {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Duplicated DID has been passed: {}", did.0)));
+            }
+        }
+
+        // Signatures are calculated over the request without `signatures` field, so each DID signs the same data
+        let mut res = request_json.to_string();
+
+        for did in submitter_dids {
+            res = self._sign_request(wallet_handle, did, &res, SignatureType::Multi)?;
+        }
+
+        debug!("multi_sign_request_with_dids <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn build_get_ddo_request(&self,
                              submitter_did: Option<&DidValue>,
                              target_did: &DidValue) -> IndyResult<String> {
@@ -1260,6 +1402,11 @@ impl LedgerCommandExecutor {
     fn get_schema(&self, pool_handle: i32, submitter_did: Option<&DidValue>, id: &SchemaId, cb: BoxedCallbackStringStringSend) {
         let request_json = try_cb!(self.build_get_schema_request(submitter_did, id), cb);
 
+        let pool_handle = match id.parts() {
+            Some((issuer_did, _, _)) => self.pool_service.pool_for_did(pool_handle, &issuer_did),
+            None => pool_handle
+        };
+
         let cb_id = next_command_handle();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
         let id = id.clone();
@@ -1286,6 +1433,11 @@ impl LedgerCommandExecutor {
     fn get_cred_def(&self, pool_handle: i32, submitter_did: Option<&DidValue>, id: &CredentialDefinitionId, cb: BoxedCallbackStringStringSend) {
         let request_json = try_cb!(self.build_get_cred_def_request(submitter_did, id), cb);
 
+        let pool_handle = match id.issuer_did() {
+            Some(issuer_did) => self.pool_service.pool_for_did(pool_handle, &issuer_did),
+            None => pool_handle
+        };
+
         let cb_id = next_command_handle();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
         let id = id.clone();
@@ -1308,6 +1460,416 @@ impl LedgerCommandExecutor {
         let pool_response = try_cb!(pool_response, cb);
         cb(self.ledger_service.parse_get_cred_def_response(&pool_response, id.get_method().as_ref().map(String::as_str)))
     }
+
+    fn get_nym_history(&self, pool_handle: PoolHandle, did: &DidValue, options: NymHistoryOptions, cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        try_cb!(self.crypto_service.validate_did(did), cb);
+
+        let pool_handle = self.pool_service.pool_for_did(pool_handle, did);
+        let from = options.from.unwrap_or(1);
+
+        match options.to {
+            Some(to) => self._get_nym_history_walk(pool_handle, did.to_short(), from, to, cb),
+            // The last NYM transaction for DID is taken from the ledger
+            None => {
+                let request_json = try_cb!(self.build_get_nym_request(None, did), cb);
+
+                let cb_id = next_command_handle();
+                self.nym_history_callbacks.borrow_mut().insert(cb_id, cb);
+
+                let did = did.to_short();
+
+                self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+                    CommandExecutor::instance().send(
+                        Command::Ledger(
+                            LedgerCommand::GetNymHistoryContinue(
+                                pool_handle,
+                                did.clone(),
+                                from,
+                                response,
+                                cb_id
+                            )
+                        )
+                    ).unwrap();
+                }));
+            }
+        }
+    }
+
+    fn _get_nym_history_continue(&self, pool_handle: PoolHandle, did: ShortDidValue, from: u64, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.nym_history_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let to = try_cb!(pool_response.and_then(|response| self.ledger_service.parse_get_nym_seq_no(&response)), cb);
+
+        self._get_nym_history_walk(pool_handle, did, from, to, cb)
+    }
+
+    fn _get_nym_history_walk(&self, pool_handle: PoolHandle, did: ShortDidValue, from: u64, to: u64, cb: Box<dyn Fn(IndyResult<String>)>) {
+        let walk = try_cb!(TxnWalk::new(pool_handle, None, from, to), cb);
+
+        let ledger_service = self.ledger_service.clone();
+
+        self._walk_txns(walk, Box::new(move |res| {
+            cb(res.and_then(|walk| {
+                let mut entries: Vec<NymHistoryEntry> = Vec::new();
+
+                for txn in walk.txns.iter() {
+                    if let Some(entry) = ledger_service.parse_nym_history_entry(&txn.data, &did)? {
+                        entries.push(entry);
+                    }
+                }
+
+                serde_json::to_string(&entries)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize NYM history")
+            }))
+        }))
+    }
+
+    fn fetch_txns(&self, pool_handle: PoolHandle, options: FetchTxnsOptions, cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("fetch_txns >>> pool_handle: {:?}, options: {:?}", pool_handle, options);
+
+        let from = options.from.unwrap_or(1);
+        let batch_size = options.batch_size.unwrap_or(DEFAULT_FETCH_TXNS_BATCH_SIZE);
+
+        let batch_last_seq_no = try_cb!(from.checked_add(batch_size - 1)
+                                            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Sequence number is out of range")), cb);
+
+        let last_seq_no = options.to.map(|to| ::std::cmp::min(to, batch_last_seq_no)).unwrap_or(batch_last_seq_no);
+
+        let walk = try_cb!(TxnWalk::new(pool_handle, options.ledger_type, from, last_seq_no), cb);
+
+        let to = options.to;
+
+        self._walk_txns(walk, Box::new(move |res| {
+            let res = res.and_then(|walk| {
+                let complete = walk.end_of_ledger || to.map(|to| walk.next_seq_no > to).unwrap_or(false);
+
+                let batch = TxnsBatch {
+                    txns: walk.txns,
+                    cursor: walk.next_seq_no,
+                    complete,
+                };
+
+                serde_json::to_string(&batch)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize transactions batch")
+            });
+
+            debug!("fetch_txns <<< res: {:?}", res);
+
+            cb(res)
+        }))
+    }
+
+    // Sends GET_TXN requests one by one for the range of the walk, stopping at the end of the ledger.
+    fn _walk_txns(&self, walk: TxnWalk, cb: Box<dyn Fn(IndyResult<TxnWalk>)>) {
+        let cb_id = next_command_handle();
+        self.txn_walk_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self._walk_txns_next(walk, cb_id)
+    }
+
+    fn _walk_txns_continue(&self, mut walk: TxnWalk, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let data = match pool_response.and_then(|response| self.ledger_service.parse_get_txn_data(&response)) {
+            Ok(data) => data,
+            Err(err) => return self._finish_walk_txns(Err(err), cb_id)
+        };
+
+        match data {
+            Some(data) => {
+                walk.txns.push(LedgerTxn { seq_no: walk.next_seq_no, data });
+                walk.next_seq_no += 1;
+                self._walk_txns_next(walk, cb_id)
+            }
+            None => {
+                walk.end_of_ledger = true;
+                self._finish_walk_txns(Ok(walk), cb_id)
+            }
+        }
+    }
+
+    fn _walk_txns_next(&self, walk: TxnWalk, cb_id: CommandHandle) {
+        if walk.next_seq_no > walk.last_seq_no {
+            return self._finish_walk_txns(Ok(walk), cb_id);
+        }
+
+        let request_json = i32::try_from(walk.next_seq_no)
+            .to_indy(IndyErrorKind::InvalidStructure, "Sequence number is out of range")
+            .and_then(|seq_no| self.build_get_txn_request(None, walk.ledger_type.as_ref().map(String::as_str), seq_no));
+
+        let request_json = match request_json {
+            Ok(request_json) => request_json,
+            Err(err) => return self._finish_walk_txns(Err(err), cb_id)
+        };
+
+        self.submit_request(walk.pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::WalkTxnsContinue(
+                        walk.clone(),
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _finish_walk_txns(&self, res: IndyResult<TxnWalk>, cb_id: CommandHandle) {
+        let cb = self.txn_walk_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        cb(res)
+    }
+
+    fn get_active_txn_author_agreement(&self, pool_handle: PoolHandle, accepted_digest: Option<String>, cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("get_active_txn_author_agreement >>> pool_handle: {:?}, accepted_digest: {:?}", pool_handle, accepted_digest);
+
+        let cb_id = next_command_handle();
+        self.txn_author_agreement_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let fetch = TxnAuthorAgreementFetch {
+            pool_handle,
+            accepted_digest,
+            step: TxnAuthorAgreementFetchStep::Agreement,
+            taa: None,
+            aml: None,
+        };
+
+        self._get_active_txn_author_agreement_next(fetch, cb_id)
+    }
+
+    fn _get_active_txn_author_agreement_continue(&self, mut fetch: TxnAuthorAgreementFetch, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let pool_response = match pool_response {
+            Ok(pool_response) => pool_response,
+            Err(err) => return self._finish_get_active_txn_author_agreement(Err(err), cb_id)
+        };
+
+        let res = match fetch.step {
+            TxnAuthorAgreementFetchStep::Agreement => {
+                self.ledger_service.parse_get_txn_author_agreement_response(&pool_response)
+                    .map(|taa| {
+                        fetch.taa = taa;
+                        fetch.step = TxnAuthorAgreementFetchStep::Mechanisms;
+                    })
+            }
+            TxnAuthorAgreementFetchStep::Mechanisms => {
+                self.ledger_service.parse_get_acceptance_mechanisms_response(&pool_response)
+                    .map(|aml| {
+                        fetch.aml = aml;
+                        fetch.step = TxnAuthorAgreementFetchStep::Accepted;
+                    })
+            }
+            TxnAuthorAgreementFetchStep::Accepted => {
+                self.ledger_service.parse_get_txn_author_agreement_response(&pool_response)
+                    .and_then(|accepted| _check_accepted_txn_author_agreement(accepted, fetch.taa.as_ref()))
+                    .map(|_| fetch.step = TxnAuthorAgreementFetchStep::Done)
+            }
+            TxnAuthorAgreementFetchStep::Done => Ok(())
+        };
+
+        match res {
+            Ok(()) => self._get_active_txn_author_agreement_next(fetch, cb_id),
+            Err(err) => self._finish_get_active_txn_author_agreement(Err(err), cb_id)
+        }
+    }
+
+    fn _get_active_txn_author_agreement_next(&self, mut fetch: TxnAuthorAgreementFetch, cb_id: CommandHandle) {
+        if let TxnAuthorAgreementFetchStep::Accepted = fetch.step {
+            match (fetch.accepted_digest.as_ref(), fetch.taa.as_ref()) {
+                // Nothing is accepted, so there is nothing to check
+                (None, _) => fetch.step = TxnAuthorAgreementFetchStep::Done,
+                // The latest agreement is accepted, it can be checked without fetching it again
+                (Some(accepted_digest), Some(taa)) if taa.digest.as_ref().map(|digest| digest.eq_ignore_ascii_case(accepted_digest)).unwrap_or(false) => {
+                    if let Err(err) = _check_accepted_txn_author_agreement(Some(taa.clone()), Some(taa)) {
+                        return self._finish_get_active_txn_author_agreement(Err(err), cb_id);
+                    }
+
+                    fetch.step = TxnAuthorAgreementFetchStep::Done;
+                }
+                _ => {}
+            }
+        }
+
+        let request_json = match fetch.step {
+            TxnAuthorAgreementFetchStep::Agreement =>
+                self.build_get_txn_author_agreement_request(None, None),
+            TxnAuthorAgreementFetchStep::Mechanisms =>
+                self.build_get_acceptance_mechanisms_request(None, None, None),
+            TxnAuthorAgreementFetchStep::Accepted => {
+                let data = GetTxnAuthorAgreementData { digest: fetch.accepted_digest.clone(), version: None, timestamp: None };
+                self.build_get_txn_author_agreement_request(None, Some(&data))
+            }
+            TxnAuthorAgreementFetchStep::Done => {
+                let active = ActiveTxnAuthorAgreement { taa: fetch.taa, aml: fetch.aml };
+
+                let res = serde_json::to_string(&active)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize active Transaction Author Agreement");
+
+                debug!("get_active_txn_author_agreement <<< res: {:?}", res);
+
+                return self._finish_get_active_txn_author_agreement(res, cb_id);
+            }
+        };
+
+        let request_json = match request_json {
+            Ok(request_json) => request_json,
+            Err(err) => return self._finish_get_active_txn_author_agreement(Err(err), cb_id)
+        };
+
+        self.submit_request(fetch.pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetActiveTxnAuthorAgreementContinue(
+                        fetch.clone(),
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _finish_get_active_txn_author_agreement(&self, res: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.txn_author_agreement_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        cb(res)
+    }
+
+    fn check_cred_def_compatibility(&self, pool_handle: PoolHandle, id: &CredentialDefinitionId, cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("check_cred_def_compatibility >>> pool_handle: {:?}, id: {:?}", pool_handle, id);
+
+        let request_json = try_cb!(self.build_get_cred_def_request(None, id), cb);
+
+        let pool_handle = match id.issuer_did() {
+            Some(issuer_did) => self.pool_service.pool_for_did(pool_handle, &issuer_did),
+            None => pool_handle
+        };
+
+        let cb_id = next_command_handle();
+        self.cred_def_compatibility_callbacks.borrow_mut().insert(cb_id, cb);
+        let id = id.clone();
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::CheckCredDefCompatibilityContinue(
+                        id.clone(),
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _check_cred_def_compatibility_continue(&self, id: CredentialDefinitionId, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.cred_def_compatibility_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+
+        let res = self.ledger_service.check_cred_def_compatibility(&id, &pool_response)
+            .and_then(|report| {
+                if !report.compatible {
+                    warn!("Credential Definition {:?} is incompatible with local anoncreds: {:?}", id, report.issues);
+                }
+
+                serde_json::to_string(&report)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential Definition compatibility report")
+            });
+
+        debug!("check_cred_def_compatibility <<< res: {:?}", res);
+
+        cb(res)
+    }
+
+    fn get_write_receipt(&self,
+                         response: &str) -> IndyResult<String> {
+        debug!("get_write_receipt >>> response: {:?}", response);
+
+        let receipt = parse_write_receipt(response)?;
+
+        let res = serde_json::to_string(&receipt)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize WriteReceipt")?;
+
+        debug!("get_write_receipt <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn verify_write_receipt(&self, pool_handle: PoolHandle, receipt: WriteReceipt) -> IndyResult<bool> {
+        debug!("verify_write_receipt >>> pool_handle: {:?}, receipt: {:?}", pool_handle, receipt);
+
+        // digest is taken from the transaction data which is covered by the audit path
+        if receipt.digest.as_ref().map_or(false, |digest| receipt.data["txn"]["metadata"]["digest"].as_str() != Some(digest.as_str())) {
+            warn!("Write receipt of transaction {} is invalid: digest differs from the transaction", receipt.seq_no);
+            return Ok(false);
+        }
+
+        let res = self.pool_service.verify_write_receipt(pool_handle, &receipt)?;
+
+        debug!("verify_write_receipt <<< res: {:?}", res);
+
+        Ok(res)
+    }
+}
+
+fn _check_accepted_txn_author_agreement(accepted: Option<TxnAuthorAgreementData>, active: Option<&TxnAuthorAgreementData>) -> IndyResult<()> {
+    let accepted = accepted
+        .ok_or_else(|| err_msg(IndyErrorKind::LedgerItemNotFound, "Accepted Transaction Author Agreement not found on the ledger"))?;
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    if accepted.is_retired(now) {
+        return Err(err_msg(IndyErrorKind::TxnAuthorAgreementRetired,
+                           format!("Accepted Transaction Author Agreement version \"{}\" is retired, the active version {:?} must be accepted",
+                                   accepted.version, active.map(|taa| taa.version.as_str()))));
+    }
+
+    Ok(())
+}
+
+/// State of the walk through a range of ledger transactions sending GET_TXN request for each of them.
+#[derive(Clone)]
+pub struct TxnWalk {
+    pool_handle: PoolHandle,
+    ledger_type: Option<String>,
+    next_seq_no: u64,
+    last_seq_no: u64,
+    end_of_ledger: bool,
+    txns: Vec<LedgerTxn>,
+}
+
+impl TxnWalk {
+    fn new(pool_handle: PoolHandle, ledger_type: Option<String>, from: u64, to: u64) -> IndyResult<TxnWalk> {
+        if to >= from && to - from >= MAX_TXN_WALK_LENGTH {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Too many transactions are requested: {} at most can be walked at once", MAX_TXN_WALK_LENGTH)));
+        }
+
+        Ok(TxnWalk {
+            pool_handle,
+            ledger_type,
+            next_seq_no: from,
+            last_seq_no: to,
+            end_of_ledger: false,
+            txns: Vec::new(),
+        })
+    }
+}
+
+/// State of fetching of the active Transaction Author Agreement.
+#[derive(Clone)]
+pub struct TxnAuthorAgreementFetch {
+    pool_handle: PoolHandle,
+    accepted_digest: Option<String>,
+    step: TxnAuthorAgreementFetchStep,
+    taa: Option<TxnAuthorAgreementData>,
+    aml: Option<AcceptanceMechanismsData>,
+}
+
+#[derive(Clone)]
+enum TxnAuthorAgreementFetchStep {
+    Agreement,
+    Mechanisms,
+    Accepted,
+    Done,
 }
 
 enum SignatureType {
@@ -1,11 +1,16 @@
 extern crate ursa;
 extern crate threadpool;
 
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
 use std::env;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
-use std::sync::{Mutex, MutexGuard};
-use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::commands::anoncreds::{AnoncredsCommand, AnoncredsCommandExecutor};
 use crate::commands::blob_storage::{BlobStorageCommand, BlobStorageCommandExecutor};
@@ -19,13 +24,19 @@ use crate::commands::pool::{PoolCommand, PoolCommandExecutor};
 use crate::commands::wallet::{WalletCommand, WalletCommandExecutor};
 use crate::commands::cache::{CacheCommand, CacheCommandExecutor};
 use crate::domain::IndyConfig;
+use crate::domain::event::{Event, WalletInvalidationReason};
+use crate::utils::event::emit_event;
+use indy_api_types::{IndyHandle, INVALID_COMMAND_HANDLE};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
+use crate::services::anoncreds::helpers::{set_canonical_encoding_enforced, set_strict_schema_attr_names};
+use crate::services::anoncreds::key_cache::set_cred_def_key_cache_config;
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::ledger::LedgerService;
 use crate::services::payments::PaymentsService;
 use crate::services::pool::{PoolService, set_freshness_threshold};
+use crate::utils::logger::with_log_context;
 use indy_wallet::WalletService;
 
 use self::threadpool::ThreadPool;
@@ -44,8 +55,11 @@ pub mod cache;
 
 type BoxedCallbackStringStringSend = Box<dyn Fn(IndyResult<(String, String)>) + Send>;
 
+type DispatchedCallback = Box<dyn FnOnce() + Send>;
+
 pub enum Command {
     Exit,
+    Cancel(IndyHandle),
     Anoncreds(AnoncredsCommand),
     BlobStorage(BlobStorageCommand),
     Crypto(CryptoCommand),
@@ -59,8 +73,171 @@ pub enum Command {
     Cache(CacheCommand),
 }
 
+/// Command waiting for its result which can be cancelled by the caller or when its deadline expires.
+struct PendingCommand {
+    deadline: Option<Instant>,
+    cancel: Box<dyn FnOnce() + Send>,
+}
+
+const CALLBACK_DISPATCHER_THREADS: usize = 4;
+
 lazy_static! {
     static ref THREADPOOL: Mutex<ThreadPool> = Mutex::new(ThreadPool::new(4));
+    static ref CALLBACK_DISPATCHERS: Vec<Mutex<Sender<DispatchedCallback>>> = (0..CALLBACK_DISPATCHER_THREADS)
+        .map(|_| Mutex::new(start_callback_dispatcher()))
+        .collect();
+    static ref PENDING_COMMANDS: Mutex<HashMap<IndyHandle, PendingCommand>> = Mutex::new(HashMap::new());
+    static ref COMMAND_TIMEOUT: Mutex<Option<Duration>> = Mutex::new(None);
+}
+
+thread_local! {
+    static CURRENT_CANCEL_HANDLE: Cell<IndyHandle> = Cell::new(INVALID_COMMAND_HANDLE);
+}
+
+fn start_callback_dispatcher() -> Sender<DispatchedCallback> {
+    let (sender, receiver) = channel::<DispatchedCallback>();
+
+    thread::spawn(move || {
+        info!(target: "callback_dispatcher", "Dispatcher thread started");
+
+        for callback in receiver {
+            callback();
+        }
+
+        info!(target: "callback_dispatcher", "Dispatcher thread finished");
+    });
+
+    sender
+}
+
+/// Returns index of the dispatcher thread calling callbacks of commands started in the current thread.
+fn current_callback_dispatcher() -> usize {
+    let mut hasher = DefaultHasher::new();
+    thread::current().id().hash(&mut hasher);
+    (hasher.finish() % CALLBACK_DISPATCHER_THREADS as u64) as usize
+}
+
+/// Wraps command callback so it is called on one of the dispatcher threads instead of the executor one.
+///
+/// Slow or re-entrant callbacks don't stall processing of other commands this way.
+/// Callbacks of commands started in the same application thread are called one by one on the same
+/// dispatcher thread in the order the executor completes commands, so results of commands sent from
+/// the same source are delivered in the order they were produced. Callbacks of commands started in
+/// other threads may be called in parallel.
+///
+/// Callback must not block waiting for the result of another command: it may be delivered
+/// by the same dispatcher thread.
+pub fn dispatch_callback<A: Send + 'static>(cb: Box<dyn Fn(A) + Send>) -> Box<dyn Fn(A) + Send> {
+    let cb = Arc::new(Mutex::new(cb));
+    let dispatcher = current_callback_dispatcher();
+
+    Box::new(move |arg| {
+        let cb = cb.clone();
+        let callback: DispatchedCallback = Box::new(move || (*cb.lock().unwrap())(arg));
+
+        if let Err(err) = CALLBACK_DISPATCHERS[dispatcher].lock().unwrap().send(callback) {
+            error!("Can't send callback to dispatcher: {}", err);
+        }
+    })
+}
+
+/// Wraps command callback so the command can be cancelled with `Command::Cancel`.
+///
+/// The command gets a new cancel handle which is returned to the caller by `get_current_cancel_handle`
+/// in the thread that started the command. Cancelled command gets `Cancelled` error as the result.
+/// The operation itself isn't interrupted, but its result is dropped. Commands are also cancelled
+/// when the configured command timeout expires.
+pub fn cancellable_callback<T: Send + 'static>(cb: Box<dyn Fn(IndyResult<T>) + Send>) -> Box<dyn Fn(IndyResult<T>) + Send> {
+    cancellable_callback_with_cleanup(cb, Box::new(|_| {}))
+}
+
+/// Same as `cancellable_callback`, but successful result produced after cancellation is passed to `cleanup`
+/// so resources it holds (e.g. opened pool) aren't leaked.
+pub fn cancellable_callback_with_cleanup<T: Send + 'static>(cb: Box<dyn Fn(IndyResult<T>) + Send>,
+                                                           cleanup: Box<dyn Fn(T) + Send>) -> Box<dyn Fn(IndyResult<T>) + Send> {
+    let cancel_handle = indy_utils::sequence::get_next_id();
+    let cb = Arc::new(Mutex::new(Some(cb)));
+
+    let cancel_cb = cb.clone();
+    let pending_command = PendingCommand {
+        deadline: COMMAND_TIMEOUT.lock().unwrap().map(|timeout| Instant::now() + timeout),
+        cancel: Box::new(move || {
+            let cb = cancel_cb.lock().unwrap().take();
+
+            if let Some(cb) = cb {
+                cb(Err(err_msg(IndyErrorKind::Cancelled, "Command has been cancelled")))
+            }
+        }),
+    };
+
+    PENDING_COMMANDS.lock().unwrap().insert(cancel_handle, pending_command);
+    CURRENT_CANCEL_HANDLE.with(|handle| handle.set(cancel_handle));
+
+    Box::new(move |result| {
+        let cb = cb.lock().unwrap().take();
+
+        match (cb, result) {
+            (Some(cb), result) => {
+                PENDING_COMMANDS.lock().unwrap().remove(&cancel_handle);
+                cb(result)
+            }
+            (None, Ok(res)) => {
+                debug!("Result of cancelled command {} is dropped", cancel_handle);
+                cleanup(res)
+            }
+            (None, Err(err)) => debug!("Error of cancelled command {} is dropped: {:?}", cancel_handle, err)
+        }
+    })
+}
+
+/// Returns cancel handle of the last cancellable command started in the current thread.
+pub fn get_current_cancel_handle() -> IndyHandle {
+    CURRENT_CANCEL_HANDLE.with(|handle| handle.get())
+}
+
+/// Forgets the last cancellable command started in the current thread without calling its callback.
+///
+/// Used when the command can't be sent to the executor, so its callback is never called with `Cancelled`
+/// error afterwards, neither by `Command::Cancel` nor by timeout.
+fn forget_current_cancellable_command() {
+    let cancel_handle = CURRENT_CANCEL_HANDLE.with(|handle| handle.replace(INVALID_COMMAND_HANDLE));
+    PENDING_COMMANDS.lock().unwrap().remove(&cancel_handle);
+}
+
+fn cancel_command(cancel_handle: IndyHandle) {
+    let pending_command = PENDING_COMMANDS.lock().unwrap().remove(&cancel_handle);
+
+    match pending_command {
+        Some(pending_command) => (pending_command.cancel)(),
+        None => debug!("Command {} is not pending, nothing to cancel", cancel_handle),
+    }
+}
+
+fn cancel_expired_commands() {
+    let now = Instant::now();
+
+    let expired: Vec<PendingCommand> = {
+        let mut pending_commands = PENDING_COMMANDS.lock().unwrap();
+
+        let expired_handles: Vec<IndyHandle> = pending_commands.iter()
+            .filter(|(_, pending_command)| pending_command.deadline.map(|deadline| deadline <= now).unwrap_or(false))
+            .map(|(cancel_handle, _)| *cancel_handle)
+            .collect();
+
+        expired_handles.iter()
+            .filter_map(|cancel_handle| pending_commands.remove(cancel_handle))
+            .collect()
+    };
+
+    for pending_command in expired {
+        (pending_command.cancel)();
+    }
+}
+
+fn next_command_deadline() -> Option<Instant> {
+    PENDING_COMMANDS.lock().unwrap().values()
+        .filter_map(|pending_command| pending_command.deadline)
+        .min()
 }
 
 pub fn indy_set_runtime_config(config: IndyConfig) {
@@ -75,6 +252,16 @@ pub fn indy_set_runtime_config(config: IndyConfig) {
     if let Some(threshold) = config.freshness_threshold {
         set_freshness_threshold(threshold);
     }
+    set_cred_def_key_cache_config(config.cred_def_key_cache_size, config.cred_def_key_cache_ttl);
+    if let Some(command_timeout) = config.command_timeout {
+        *COMMAND_TIMEOUT.lock().unwrap() = if command_timeout > 0 { Some(Duration::from_millis(command_timeout)) } else { None };
+    }
+    if let Some(enforced) = config.enforce_canonical_attr_encoding {
+        set_canonical_encoding_enforced(enforced);
+    }
+    if let Some(strict) = config.strict_schema_attr_names {
+        set_strict_schema_attr_names(strict);
+    }
 }
 
 pub struct CommandExecutor {
@@ -112,64 +299,89 @@ impl CommandExecutor {
                 let crypto_command_executor = CryptoCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
                 let ledger_command_executor = LedgerCommandExecutor::new(pool_service.clone(), crypto_service.clone(), wallet_service.clone(), ledger_service.clone());
                 let pool_command_executor = PoolCommandExecutor::new(pool_service.clone());
-                let did_command_executor = DidCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), ledger_service.clone());
+                let did_command_executor = DidCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), ledger_service.clone(), pool_service.clone());
                 let wallet_command_executor = WalletCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
                 let pairwise_command_executor = PairwiseCommandExecutor::new(wallet_service.clone());
                 let blob_storage_command_executor = BlobStorageCommandExecutor::new(blob_storage_service.clone());
                 let non_secret_command_executor = NonSecretsCommandExecutor::new(wallet_service.clone());
                 let payments_command_executor = PaymentsCommandExecutor::new(payments_service.clone(), wallet_service.clone(), crypto_service.clone(), ledger_service.clone());
-                let cache_command_executor = CacheCommandExecutor::new(wallet_service.clone());
+                let cache_command_executor = CacheCommandExecutor::new(wallet_service.clone(), ledger_service.clone(), pool_service.clone(), blob_storage_service.clone());
 
                 loop {
-                    match receiver.recv() {
+                    cancel_expired_commands();
+
+                    for (wallet_handle, reason) in wallet_service.close_invalid_wallets() {
+                        emit_event(&Event::WalletInvalidated { wallet_handle, reason: WalletInvalidationReason::from(reason) });
+                    }
+
+                    // Sleep until the next command or the nearest deadline of pending commands, scheduled compactions and idle wallets
+                    let deadline = next_command_deadline().into_iter()
+                        .chain(wallet_service.next_scheduled_compaction())
+                        .chain(wallet_service.next_idle_close())
+                        .min();
+
+                    let command = match deadline {
+                        Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                    };
+
+                    match command {
                         Ok(Command::Anoncreds(cmd)) => {
                             debug!("AnoncredsCommand command received");
-                            anoncreds_command_executor.execute(cmd);
+                            with_log_context("Anoncreds", || anoncreds_command_executor.execute(cmd));
                         }
                         Ok(Command::BlobStorage(cmd)) => {
                             debug!("BlobStorageCommand command received");
-                            blob_storage_command_executor.execute(cmd);
+                            with_log_context("BlobStorage", || blob_storage_command_executor.execute(cmd));
                         }
                         Ok(Command::Crypto(cmd)) => {
                             debug!("CryptoCommand command received");
-                            crypto_command_executor.execute(cmd);
+                            with_log_context("Crypto", || crypto_command_executor.execute(cmd));
                         }
                         Ok(Command::Ledger(cmd)) => {
                             debug!("LedgerCommand command received");
-                            ledger_command_executor.execute(cmd);
+                            with_log_context("Ledger", || ledger_command_executor.execute(cmd));
                         }
                         Ok(Command::Pool(cmd)) => {
                             debug!("PoolCommand command received");
-                            pool_command_executor.execute(cmd);
+                            with_log_context("Pool", || pool_command_executor.execute(cmd));
                         }
                         Ok(Command::Did(cmd)) => {
                             debug!("DidCommand command received");
-                            did_command_executor.execute(cmd);
+                            with_log_context("Did", || did_command_executor.execute(cmd));
                         }
                         Ok(Command::Wallet(cmd)) => {
                             debug!("WalletCommand command received");
-                            wallet_command_executor.execute(cmd);
+                            with_log_context("Wallet", || wallet_command_executor.execute(cmd));
                         }
                         Ok(Command::Pairwise(cmd)) => {
                             debug!("PairwiseCommand command received");
-                            pairwise_command_executor.execute(cmd);
+                            with_log_context("Pairwise", || pairwise_command_executor.execute(cmd));
                         }
                         Ok(Command::NonSecrets(cmd)) => {
                             debug!("NonSecretCommand command received");
-                            non_secret_command_executor.execute(cmd);
+                            with_log_context("NonSecrets", || non_secret_command_executor.execute(cmd));
                         }
                         Ok(Command::Payments(cmd)) => {
                             debug!("PaymentsCommand command received");
-                            payments_command_executor.execute(cmd);
+                            with_log_context("Payments", || payments_command_executor.execute(cmd));
                         }
                         Ok(Command::Cache(cmd)) => {
                             debug!("CacheCommand command received");
-                            cache_command_executor.execute(cmd);
+                            with_log_context("Cache", || cache_command_executor.execute(cmd));
+                        }
+                        Ok(Command::Cancel(cancel_handle)) => {
+                            debug!("Cancel command received");
+                            cancel_command(cancel_handle);
                         }
                         Ok(Command::Exit) => {
                             debug!("Exit command received");
                             break
                         }
+                        Err(RecvTimeoutError::Timeout) => {
+                            // Scheduled maintenance runs only while there are no commands to process
+                            wallet_service.compact_scheduled_wallets();
+                        }
                         Err(err) => {
                             error!("Failed to get command!");
                             panic!("Failed to get command! {:?}", err)
@@ -185,6 +397,17 @@ impl CommandExecutor {
             .send(cmd)
             .map_err(|err| err_msg(IndyErrorKind::InvalidState, format!("Can't send msg to CommandExecutor: {}", err)))
     }
+
+    /// Sends the command with callback wrapped by `cancellable_callback` in the current thread.
+    ///
+    /// The command is forgotten if it can't be sent, so the caller gets the error only once.
+    pub fn send_cancellable(&self, cmd: Command) -> IndyResult<()> {
+        self.send(cmd)
+            .map_err(|err| {
+                forget_current_cancellable_command();
+                err
+            })
+    }
 }
 
 impl Drop for CommandExecutor {
@@ -223,4 +446,150 @@ mod tests {
         // Deadlock if another one instance will be requested (try to uncomment the next line)
         // let ref other_ce: CommandExecutor = *CommandExecutor::instance();
     }
+
+    #[test]
+    fn dispatch_callback_calls_callbacks_in_order_on_dispatcher_thread() {
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+
+        let cb = dispatch_callback(Box::new(move |i: u32| {
+            sender.lock().unwrap().send((i, thread::current().id())).unwrap();
+        }));
+
+        for i in 0..10 {
+            cb(i);
+        }
+
+        for i in 0..10 {
+            let (received, thread_id) = receiver.recv().unwrap();
+            assert_eq!(i, received);
+            assert_ne!(thread::current().id(), thread_id);
+        }
+    }
+
+    #[test]
+    fn dispatch_callback_calls_callbacks_of_same_source_in_order_on_same_thread() {
+        let (sender, receiver) = channel();
+        let first_sender = Mutex::new(sender.clone());
+        let second_sender = Mutex::new(sender);
+
+        let first_cb = dispatch_callback(Box::new(move |i: u32| {
+            first_sender.lock().unwrap().send((i, thread::current().id())).unwrap();
+        }));
+        let second_cb = dispatch_callback(Box::new(move |i: u32| {
+            second_sender.lock().unwrap().send((i, thread::current().id())).unwrap();
+        }));
+
+        for i in 0..10 {
+            if i % 2 == 0 { first_cb(i) } else { second_cb(i) }
+        }
+
+        let (_, dispatcher_thread_id) = receiver.recv().unwrap();
+
+        for i in 1..10 {
+            let (received, thread_id) = receiver.recv().unwrap();
+            assert_eq!(i, received);
+            assert_eq!(dispatcher_thread_id, thread_id);
+        }
+    }
+
+    #[test]
+    fn cancellable_callback_works_for_cancelled_command() {
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+
+        let cb = cancellable_callback(Box::new(move |result: IndyResult<u32>| {
+            sender.lock().unwrap().send(result.map_err(|err| err.kind())).unwrap();
+        }));
+        let cancel_handle = get_current_cancel_handle();
+
+        cancel_command(cancel_handle);
+        cb(Ok(1));
+
+        assert_eq!(Err(IndyErrorKind::Cancelled), receiver.recv().unwrap());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn cancellable_callback_works_for_completed_command() {
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+
+        let cb = cancellable_callback(Box::new(move |result: IndyResult<u32>| {
+            sender.lock().unwrap().send(result.map_err(|err| err.kind())).unwrap();
+        }));
+        let cancel_handle = get_current_cancel_handle();
+
+        cb(Ok(1));
+        cancel_command(cancel_handle);
+
+        assert_eq!(Ok(1), receiver.recv().unwrap());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn cancellable_callback_works_for_same_command_handle() {
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+
+        let first_cb = cancellable_callback(Box::new(move |result: IndyResult<u32>| {
+            sender.lock().unwrap().send(result.map_err(|err| err.kind())).unwrap();
+        }));
+        let first_cancel_handle = get_current_cancel_handle();
+
+        let _second_cb = cancellable_callback(Box::new(|_: IndyResult<u32>| {}));
+        let second_cancel_handle = get_current_cancel_handle();
+
+        assert_ne!(first_cancel_handle, second_cancel_handle);
+
+        cancel_command(second_cancel_handle);
+        first_cb(Ok(1));
+
+        assert_eq!(Ok(1), receiver.recv().unwrap());
+    }
+
+    #[test]
+    fn cancellable_callback_with_cleanup_works_for_result_after_cancel() {
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+
+        let cb = cancellable_callback_with_cleanup(Box::new(|_: IndyResult<u32>| {}), Box::new(move |res: u32| {
+            sender.lock().unwrap().send(res).unwrap();
+        }));
+
+        cancel_command(get_current_cancel_handle());
+        cb(Ok(1));
+
+        assert_eq!(1, receiver.recv().unwrap());
+    }
+
+    #[test]
+    fn forget_current_cancellable_command_works() {
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+
+        let _cb = cancellable_callback(Box::new(move |result: IndyResult<u32>| {
+            sender.lock().unwrap().send(result.map_err(|err| err.kind())).unwrap();
+        }));
+        let cancel_handle = get_current_cancel_handle();
+
+        forget_current_cancellable_command();
+        cancel_command(cancel_handle);
+
+        assert_eq!(INVALID_COMMAND_HANDLE, get_current_cancel_handle());
+        assert!(!PENDING_COMMANDS.lock().unwrap().contains_key(&cancel_handle));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn next_command_deadline_works() {
+        *COMMAND_TIMEOUT.lock().unwrap() = Some(Duration::from_secs(60));
+        let _cb = cancellable_callback(Box::new(|_: IndyResult<u32>| {}));
+        let cancel_handle = get_current_cancel_handle();
+        *COMMAND_TIMEOUT.lock().unwrap() = None;
+
+        assert!(next_command_deadline().unwrap() > Instant::now());
+
+        cancel_command(cancel_handle);
+    }
 }
@@ -2,7 +2,7 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use indy_api_types::domain::wallet::Tags;
+use indy_api_types::domain::wallet::{BatchRecord, Tags};
 use indy_api_types::errors::prelude::*;
 use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
 use indy_utils::next_search_handle;
@@ -40,6 +40,14 @@ pub enum NonSecretsCommand {
                  String, // type
                  String, // id
                  Box<dyn Fn(IndyResult<()>) + Send>),
+    AddRecordsBatch(WalletHandle,
+                    String, // type
+                    Vec<BatchRecord>, // records
+                    Box<dyn Fn(IndyResult<()>) + Send>),
+    DeleteRecordsBatch(WalletHandle,
+                       String, // type
+                       Vec<String>, // ids
+                       Box<dyn Fn(IndyResult<()>) + Send>),
     GetRecord(WalletHandle,
               String, // type
               String, // id
@@ -97,6 +105,14 @@ impl NonSecretsCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "DeleteRecord command received");
                 cb(self.delete_record(handle, &type_, &id));
             }
+            NonSecretsCommand::AddRecordsBatch(handle, type_, records, cb) => {
+                debug!(target: "non_secrets_command_executor", "AddRecordsBatch command received");
+                cb(self.add_records_batch(handle, &type_, &records));
+            }
+            NonSecretsCommand::DeleteRecordsBatch(handle, type_, ids, cb) => {
+                debug!(target: "non_secrets_command_executor", "DeleteRecordsBatch command received");
+                cb(self.delete_records_batch(handle, &type_, &ids));
+            }
             NonSecretsCommand::GetRecord(handle, type_, id, options_json, cb) => {
                 debug!(target: "non_secrets_command_executor", "GetRecord command received");
                 cb(self.get_record(handle, &type_, &id, &options_json));
@@ -215,6 +231,44 @@ impl NonSecretsCommandExecutor {
         Ok(())
     }
 
+    fn add_records_batch(&self,
+                         wallet_handle: WalletHandle,
+                         type_: &str,
+                         records: &[BatchRecord]) -> IndyResult<()> {
+        trace!("add_records_batch >>> wallet_handle: {:?}, type_: {:?}, records: {:?}", wallet_handle, type_, records);
+
+        self._check_type(type_)?;
+
+        let no_tags = Tags::new();
+
+        let records: Vec<(&str, &str, &Tags)> = records.iter()
+            .map(|record| (record.id.as_str(), record.value.as_str(), record.tags.as_ref().unwrap_or(&no_tags)))
+            .collect();
+
+        self.wallet_service.add_records_batch(wallet_handle, type_, &records)?;
+
+        trace!("add_records_batch <<< res: ()");
+
+        Ok(())
+    }
+
+    fn delete_records_batch(&self,
+                            wallet_handle: WalletHandle,
+                            type_: &str,
+                            ids: &[String]) -> IndyResult<()> {
+        trace!("delete_records_batch >>> wallet_handle: {:?}, type_: {:?}, ids: {:?}", wallet_handle, type_, ids);
+
+        self._check_type(type_)?;
+
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+        self.wallet_service.delete_records_batch(wallet_handle, type_, &ids)?;
+
+        trace!("delete_records_batch <<< res: ()");
+
+        Ok(())
+    }
+
     fn get_record(&self,
                   wallet_handle: WalletHandle,
                   type_: &str,
@@ -270,13 +324,7 @@ impl NonSecretsCommandExecutor {
         let search = searches.get_mut(&wallet_search_handle)
             .ok_or_else(||err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown WalletSearch handle: {:?}", wallet_search_handle)))?;
 
-        let mut records: Vec<WalletRecord> = Vec::new();
-        for _ in 0..count {
-            match search.fetch_next_record()? {
-                Some(record) => records.push(record),
-                None => break
-            }
-        }
+        let records = search.fetch_next(count)?;
 
         let search_result = SearchRecords {
             total_count: search.get_total_count()?,
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::domain::ledger::request::ProtocolVersion;
-use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+use crate::domain::pool::{GenesisNodes, PoolConfig, PoolOpenConfig, TrafficCaptureConfig};
 use indy_api_types::errors::prelude::*;
 use crate::services::pool::PoolService;
 use indy_api_types::{PoolHandle, CommandHandle};
@@ -38,6 +38,20 @@ pub enum PoolCommand {
     SetProtocolVersion(
         usize, // protocol version
         Box<dyn Fn(IndyResult<()>) + Send>),
+    SetTrafficCapture(
+        PoolHandle, // pool handle
+        Option<TrafficCaptureConfig>, // config
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    BuildGenesisTxns(
+        GenesisNodes, // nodes
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    SetNamespacePool(
+        String, // namespace
+        PoolHandle, // pool handle
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    RemoveNamespacePool(
+        String, // namespace
+        Box<dyn Fn(IndyResult<()>) + Send>),
 }
 
 pub struct PoolCommandExecutor {
@@ -132,6 +146,22 @@ impl PoolCommandExecutor {
                 debug!(target: "pool_command_executor", "SetProtocolVersion command received");
                 cb(self.set_protocol_version(protocol_version));
             }
+            PoolCommand::SetTrafficCapture(handle, config, cb) => {
+                debug!(target: "pool_command_executor", "SetTrafficCapture command received");
+                cb(self.set_traffic_capture(handle, config));
+            }
+            PoolCommand::BuildGenesisTxns(nodes, cb) => {
+                debug!(target: "pool_command_executor", "BuildGenesisTxns command received");
+                cb(self.build_genesis_txns(&nodes));
+            }
+            PoolCommand::SetNamespacePool(namespace, handle, cb) => {
+                debug!(target: "pool_command_executor", "SetNamespacePool command received");
+                cb(self.set_namespace_pool(&namespace, handle));
+            }
+            PoolCommand::RemoveNamespacePool(namespace, cb) => {
+                debug!(target: "pool_command_executor", "RemoveNamespacePool command received");
+                cb(self.remove_namespace_pool(&namespace));
+            }
         };
     }
 
@@ -234,4 +264,43 @@ impl PoolCommandExecutor {
 
         Ok(())
     }
+
+    fn set_traffic_capture(&self, handle: PoolHandle, config: Option<TrafficCaptureConfig>) -> IndyResult<()> {
+        debug!("set_traffic_capture >>> handle: {:?}, config: {:?}", handle, config);
+
+        self.pool_service.set_traffic_capture(handle, config)?;
+
+        debug!("set_traffic_capture <<<");
+
+        Ok(())
+    }
+
+    fn set_namespace_pool(&self, namespace: &str, handle: PoolHandle) -> IndyResult<()> {
+        debug!("set_namespace_pool >>> namespace: {:?}, handle: {:?}", namespace, handle);
+
+        self.pool_service.set_namespace_pool(namespace, handle)?;
+
+        debug!("set_namespace_pool <<<");
+
+        Ok(())
+    }
+
+    fn remove_namespace_pool(&self, namespace: &str) -> IndyResult<()> {
+        debug!("remove_namespace_pool >>> namespace: {:?}", namespace);
+
+        self.pool_service.remove_namespace_pool(namespace)?;
+
+        debug!("remove_namespace_pool <<<");
+
+        Ok(())
+    }
+
+    fn build_genesis_txns(&self, nodes: &GenesisNodes) -> IndyResult<String> {
+        debug!("build_genesis_txns >>> nodes: {:?}", nodes);
+
+        let res = self.pool_service.build_genesis_txns(&nodes.0)?;
+
+        debug!("build_genesis_txns << res: {:?}", res);
+        Ok(res)
+    }
 }
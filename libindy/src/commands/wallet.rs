@@ -1,14 +1,24 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use named_type::NamedType;
 
 use indy_api_types::wallet::*;
 use crate::commands::{Command, CommandExecutor};
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig, KeyDerivationParams, StorageEncryptionCheckConfig};
 use indy_api_types::errors::prelude::*;
+use crate::domain::anoncreds::credential::Credential;
+use crate::domain::crypto::did::DidMetadata;
+use crate::domain::crypto::key::KeyMetadata;
+use crate::domain::event::{Event, WalletInvalidationReason};
+use crate::domain::wallet::{ExpiringObject, ExpiringObjectType};
+use crate::services::anoncreds::helpers::attr_common_view;
 use crate::services::crypto::CryptoService;
-use indy_wallet::{KeyDerivationData, WalletService, Metadata};
-use indy_utils::crypto::{chacha20poly1305_ietf, randombytes};
+use crate::utils::event::emit_event;
+use indy_wallet::{KeyDerivationData, SearchOptions, WalletService, Metadata};
+use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13, randombytes};
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 use indy_api_types::{WalletHandle, CallbackHandle};
 use rust_base58::ToBase58;
@@ -42,6 +52,9 @@ pub enum WalletCommand {
                        WalletFetchSearchNextRecord, // fetch search next record
                        WalletFreeSearch, // free search
                        Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterWalletStorageCompaction(String, // type_
+                                    WalletCompact, // compact
+                                    Box<dyn Fn(IndyResult<()>) + Send>),
     Create(Config, // config
            Credentials, // credentials
            Box<dyn Fn(IndyResult<()>) + Send>),
@@ -88,6 +101,18 @@ pub enum WalletCommand {
                 Box<dyn Fn(IndyResult<String>) + Send>),
     DeriveKey(KeyDerivationData,
               Box<dyn Fn(DeriveKeyResult<MasterKey>) + Send>),
+    VerifyStorageEncryption(WalletHandle,
+                            Option<StorageEncryptionCheckConfig>, // config
+                            Box<dyn Fn(IndyResult<String>) + Send>),
+    GetSlowQueryLog(WalletHandle,
+                    Box<dyn Fn(IndyResult<String>) + Send>),
+    Compact(WalletHandle,
+            Box<dyn Fn(IndyResult<String>) + Send>),
+    ListExpiringObjects(WalletHandle,
+                        u64, // within_secs
+                        Box<dyn Fn(IndyResult<String>) + Send>),
+    BenchmarkKeyDerivation(u64, // target_ms
+                           Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
 macro_rules! get_cb {
@@ -130,6 +155,10 @@ impl WalletCommandExecutor {
                                        free_storage_metadata, search_records, search_all_records, get_search_total_count,
                                        fetch_search_next_record, free_search));
             }
+            WalletCommand::RegisterWalletStorageCompaction(type_, compact, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterWalletStorageCompaction command received");
+                cb(self.wallet_service.register_wallet_storage_compaction(&type_, compact));
+            }
             WalletCommand::Create(config, credentials, cb) => {
                 debug!(target: "wallet_command_executor", "Create command received");
                 self._create(&config, &credentials, cb)
@@ -182,6 +211,26 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "DeriveKey command received");
                 self._derive_key(key_data, cb);
             }
+            WalletCommand::VerifyStorageEncryption(wallet_handle, config, cb) => {
+                debug!(target: "wallet_command_executor", "VerifyStorageEncryption command received");
+                cb(self._verify_storage_encryption(wallet_handle, config.as_ref()));
+            }
+            WalletCommand::GetSlowQueryLog(wallet_handle, cb) => {
+                debug!(target: "wallet_command_executor", "GetSlowQueryLog command received");
+                cb(self._get_slow_query_log(wallet_handle));
+            }
+            WalletCommand::Compact(wallet_handle, cb) => {
+                debug!(target: "wallet_command_executor", "Compact command received");
+                cb(self._compact(wallet_handle));
+            }
+            WalletCommand::ListExpiringObjects(wallet_handle, within_secs, cb) => {
+                debug!(target: "wallet_command_executor", "ListExpiringObjects command received");
+                cb(self._list_expiring_objects(wallet_handle, within_secs));
+            }
+            WalletCommand::BenchmarkKeyDerivation(target_ms, cb) => {
+                debug!(target: "wallet_command_executor", "BenchmarkKeyDerivation command received");
+                self._benchmark_key_derivation(target_ms, cb);
+            }
         };
     }
 
@@ -232,7 +281,7 @@ impl WalletCommandExecutor {
                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         trace!("_create >>> config: {:?}, credentials: {:?}", config, secret!(credentials));
 
-        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt_and_params(&credentials.key, &credentials.key_derivation_method, credentials.key_derivation_params.as_ref());
 
         let cb_id : CallbackHandle = indy_utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
@@ -336,7 +385,15 @@ impl WalletCommandExecutor {
               wallet_handle: WalletHandle) -> IndyResult<()> {
         trace!("_close >>> handle: {:?}", wallet_handle);
 
-        self.wallet_service.close_wallet(wallet_handle)?;
+        let res = self.wallet_service.close_wallet(wallet_handle);
+
+        // Wallet is forgotten even if its storage fails to close, so the handle is invalidated anyway
+        match res {
+            Err(ref err) if err.kind() == IndyErrorKind::InvalidWalletHandle => {}
+            _ => emit_event(&Event::WalletInvalidated { wallet_handle, reason: WalletInvalidationReason::Closed })
+        }
+
+        res?;
 
         trace!("_close <<< res: ()");
         Ok(())
@@ -393,7 +450,7 @@ impl WalletCommandExecutor {
                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         trace!("_export >>> handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
 
-        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&export_config.key, &export_config.key_derivation_method);
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt_and_params(&export_config.key, &export_config.key_derivation_method, export_config.key_derivation_params.as_ref());
 
         let cb_id = indy_utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
@@ -502,7 +559,137 @@ impl WalletCommandExecutor {
         Ok(res)
     }
 
+    fn _verify_storage_encryption(&self,
+                                  wallet_handle: WalletHandle,
+                                  config: Option<&StorageEncryptionCheckConfig>) -> IndyResult<String> {
+        trace!("_verify_storage_encryption >>> wallet_handle: {:?}, config: {:?}", wallet_handle, config);
+
+        let sample_size = config.and_then(|config| config.sample_size);
+
+        let report = self.wallet_service.verify_storage_encryption(wallet_handle, sample_size)?;
+
+        let res = serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize storage encryption report")?;
+
+        trace!("_verify_storage_encryption <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _get_slow_query_log(&self, wallet_handle: WalletHandle) -> IndyResult<String> {
+        trace!("_get_slow_query_log >>> wallet_handle: {:?}", wallet_handle);
+
+        let slow_query_log = self.wallet_service.get_slow_query_log(wallet_handle)?;
+
+        let res = serde_json::to_string(&slow_query_log)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize wallet slow query log")?;
+
+        trace!("_get_slow_query_log <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _compact(&self, wallet_handle: WalletHandle) -> IndyResult<String> {
+        trace!("_compact >>> wallet_handle: {:?}", wallet_handle);
+
+        let report = self.wallet_service.compact_wallet(wallet_handle)?;
+
+        let res = serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize wallet compaction report")?;
+
+        trace!("_compact <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _list_expiring_objects(&self,
+                              wallet_handle: WalletHandle,
+                              within_secs: u64) -> IndyResult<String> {
+        trace!("_list_expiring_objects >>> wallet_handle: {:?}, within_secs: {:?}", wallet_handle, within_secs);
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot get time")?
+            .as_secs();
+
+        let deadline = now.saturating_add(within_secs);
+
+        let mut objects: Vec<ExpiringObject> = Vec::new();
+
+        for (id, metadata) in self._fetch_indy_records::<KeyMetadata>(wallet_handle, "{}")? {
+            if let Some(expires) = WalletCommandExecutor::_metadata_expires(&metadata.value) {
+                objects.push(ExpiringObject { type_: ExpiringObjectType::Key, id, expires });
+            }
+        }
+
+        for (id, metadata) in self._fetch_indy_records::<DidMetadata>(wallet_handle, "{}")? {
+            if let Some(expires) = WalletCommandExecutor::_metadata_expires(&metadata.value) {
+                objects.push(ExpiringObject { type_: ExpiringObjectType::Did, id, expires });
+            }
+        }
+
+        // Expiration of credentials is taken from their `expires` attribute
+        let query = json!({"attr::expires::marker": "1"}).to_string();
+
+        for (id, credential) in self._fetch_indy_records::<Credential>(wallet_handle, &query)? {
+            let expires = credential.values.0.iter()
+                .find(|&(name, _)| attr_common_view(name) == "expires")
+                .and_then(|(_, value)| value.raw.parse::<u64>().ok());
+
+            if let Some(expires) = expires {
+                objects.push(ExpiringObject { type_: ExpiringObjectType::Credential, id, expires });
+            }
+        }
+
+        objects.retain(|object| object.expires <= deadline);
+        objects.sort_by_key(|object| object.expires);
+
+        let res = serde_json::to_string(&objects)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize list of expiring objects")?;
+
+        trace!("_list_expiring_objects <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _fetch_indy_records<T>(&self, wallet_handle: WalletHandle, query_json: &str) -> IndyResult<Vec<(String, T)>>
+        where T: ::serde::de::DeserializeOwned + NamedType {
+        let mut search = self.wallet_service.search_indy_records::<T>(wallet_handle, query_json, &SearchOptions::id_value())?;
+
+        let mut records = Vec::new();
+
+        while let Some(record) = search.fetch_next_record()? {
+            let value: T = record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, format!("No value for {} record", T::short_type_name())))
+                .and_then(|value| serde_json::from_str(value)
+                    .to_indy(IndyErrorKind::InvalidState, format!("Cannot deserialize {}", T::short_type_name())))?;
+
+            records.push((record.get_id().to_string(), value));
+        }
+
+        Ok(records)
+    }
+
+    // Expiration of keys and DIDs is taken from their metadata: `{"expires": <unix time>, ...}`
+    fn _metadata_expires(metadata: &str) -> Option<u64> {
+        serde_json::from_str::<serde_json::Value>(metadata).ok()
+            .and_then(|metadata| metadata["expires"].as_u64())
+    }
+
     fn _derive_key(&self, key_data: KeyDerivationData, cb: Box<dyn Fn(DeriveKeyResult<MasterKey>) + Send>){
         crate::commands::THREADPOOL.lock().unwrap().execute(move || cb(key_data.calc_master_key()));
     }
+
+    fn _benchmark_key_derivation(&self, target_ms: u64, cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        crate::commands::THREADPOOL.lock().unwrap().execute(move || cb(WalletCommandExecutor::_calc_key_derivation_params(target_ms)));
+    }
+
+    fn _calc_key_derivation_params(target_ms: u64) -> IndyResult<String> {
+        trace!("_calc_key_derivation_params >>> target_ms: {:?}", target_ms);
+
+        let (ops_limit, mem_limit) = pwhash_argon2i13::benchmark(Duration::from_millis(target_ms))?;
+
+        let params = KeyDerivationParams { ops_limit, mem_limit };
+
+        let res = serde_json::to_string(&params)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize KeyDerivationParams")?;
+
+        trace!("_calc_key_derivation_params <<< res: {:?}", res);
+        Ok(res)
+    }
 }
@@ -11,6 +11,8 @@ use ursa::cl::{
 use indy_api_types::validation::Validatable;
 
 use super::credential_definition::CredentialDefinitionId;
+use super::credential_offer::CredentialOffer;
+use super::credential_request::CredentialRequest;
 use super::revocation_registry_definition::RevocationRegistryId;
 use super::schema::SchemaId;
 
@@ -42,7 +44,9 @@ pub struct CredentialInfo {
     pub schema_id: SchemaId,
     pub cred_def_id: CredentialDefinitionId,
     pub rev_reg_id: Option<RevocationRegistryId>,
-    pub cred_rev_id: Option<String>
+    pub cred_rev_id: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub attrs_metadata: HashMap<String, AttributeMetadata>
 }
 
 pub type ShortCredentialValues = HashMap<String, String>;
@@ -50,10 +54,45 @@ pub type ShortCredentialValues = HashMap<String, String>;
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct CredentialValues(pub HashMap<String, AttributeValues>);
 
+impl CredentialValues {
+    /// Sets metadata of the attributes that don't have their own one.
+    pub fn with_metadata(&self, attrs_metadata: &HashMap<String, AttributeMetadata>) -> CredentialValues {
+        let mut values = self.clone();
+
+        for (attr, metadata) in attrs_metadata {
+            if let Some(attr_values) = values.0.get_mut(attr) {
+                if attr_values.metadata.is_empty() {
+                    attr_values.metadata = metadata.clone();
+                }
+            }
+        }
+
+        values
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct AttributeValues {
     pub raw: String,
-    pub encoded: String
+    pub encoded: String,
+    #[serde(flatten)]
+    pub metadata: AttributeMetadata
+}
+
+/// Metadata of the attribute value as in Aries credential preview.
+/// Isn't signed by the issuer, only `encoded` value is.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Eq, PartialEq)]
+pub struct AttributeMetadata {
+    #[serde(rename = "mime-type", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>
+}
+
+impl AttributeMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.mime_type.is_none() && self.encoding.is_none()
+    }
 }
 
 impl Validatable for CredentialValues {
@@ -82,4 +121,63 @@ impl Validatable for Credential {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CredentialIssuanceRequest {
+    pub cred_offer: CredentialOffer,
+    pub cred_req: CredentialRequest,
+    pub cred_values: CredentialValues
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CredentialIssuanceRequests(pub Vec<CredentialIssuanceRequest>);
+
+impl Validatable for CredentialIssuanceRequests {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("CredentialIssuanceRequests validation failed: empty list has been passed"));
+        }
+
+        for request in self.0.iter() {
+            request.cred_offer.validate()?;
+            request.cred_req.validate()?;
+            request.cred_values.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssuedCredential {
+    pub cred: Credential,
+    pub cred_revoc_id: Option<String>
+}
+
+/// Subset of the credential data returned by the credentials search instead of the full CredentialInfo.
+/// Projected data is built from the credential tags only, so the credential value isn't read from the wallet.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, NamedType)]
+pub struct CredentialProjection {
+    #[serde(default)]
+    pub attrs: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Validatable for CredentialProjection {
+    fn validate(&self) -> Result<(), String> {
+        if self.attrs.is_empty() && self.tags.is_empty() {
+            return Err(String::from("CredentialProjection validation failed: neither `attrs` nor `tags` has been passed"));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct ProjectedCredentialInfo {
+    pub referent: String,
+    pub attrs: ShortCredentialValues,
+    pub tags: HashMap<String, String>,
+}
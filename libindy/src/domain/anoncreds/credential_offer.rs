@@ -1,6 +1,10 @@
+use named_type::NamedType;
 use ursa::cl::{CredentialKeyCorrectnessProof, Nonce};
 
+use std::collections::HashMap;
+
 use super::schema::SchemaId;
+use super::credential::AttributeMetadata;
 use super::credential_definition::CredentialDefinitionId;
 
 use indy_api_types::validation::Validatable;
@@ -13,6 +17,8 @@ pub struct CredentialOffer {
     pub nonce: Nonce,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub method_name: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub attrs_metadata: HashMap<String, AttributeMetadata>,
 }
 
 impl CredentialOffer {
@@ -24,6 +30,7 @@ impl CredentialOffer {
             cred_def_id: self.cred_def_id.to_unqualified(),
             key_correctness_proof: self.key_correctness_proof,
             nonce: self.nonce,
+            attrs_metadata: self.attrs_metadata,
         }
     }
 }
@@ -34,4 +41,48 @@ impl Validatable for CredentialOffer {
         self.cred_def_id.validate()?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Lifetime of the credential offer created without configured `ttl` (one week)
+pub const DEFAULT_CREDENTIAL_OFFER_TTL: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Deserialize)]
+pub struct CredentialOfferConfig {
+    pub ttl: Option<u64>,
+    /// Metadata of the offered attributes carried to the issued credential
+    #[serde(default)]
+    pub attrs_metadata: HashMap<String, AttributeMetadata>,
+}
+
+impl Validatable for CredentialOfferConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.ttl == Some(0) {
+            return Err(String::from("`ttl` must be greater than 0"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, NamedType)]
+pub struct CredentialOfferInfo {
+    pub offer: CredentialOffer,
+    pub created: u64,
+    pub expires: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub revoked: bool,
+}
+
+impl CredentialOfferInfo {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires.map(|expires| expires <= now).unwrap_or(false)
+    }
+
+    pub fn is_pending(&self, now: u64) -> bool {
+        !self.revoked && !self.is_expired(now)
+    }
+
+    /// Value of the plain `~expires` tag, padded to compare expiration times as strings in wallet queries
+    pub fn expires_tag(expires: u64) -> String {
+        format!("{:020}", expires)
+    }
+}
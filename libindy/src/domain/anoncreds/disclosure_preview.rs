@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use super::credential_definition::CredentialDefinitionId;
+use super::proof_request::PredicateTypes;
+use super::revocation_registry_definition::RevocationRegistryId;
+use super::schema::SchemaId;
+
+/// What the proof created for the same proof request and requested credentials would disclose.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct DisclosurePreview {
+    pub revealed_attrs: HashMap<String, RevealedAttributePreview>,
+    pub revealed_attr_groups: HashMap<String, RevealedAttributeGroupPreview>,
+    pub unrevealed_attrs: HashMap<String, UnrevealedAttributePreview>,
+    pub self_attested_attrs: HashMap<String, String>,
+    pub predicates: HashMap<String, PredicatePreview>,
+    pub credentials: HashMap<String, CredentialDisclosurePreview>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct RevealedAttributePreview {
+    pub cred_id: String,
+    pub name: String,
+    pub raw: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct RevealedAttributeGroupPreview {
+    pub cred_id: String,
+    pub values: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct UnrevealedAttributePreview {
+    pub cred_id: String,
+    pub names: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PredicatePreview {
+    pub cred_id: String,
+    pub name: String,
+    pub p_type: PredicateTypes,
+    pub p_value: i32,
+}
+
+/// Credential identifiers put into the proof and credential attributes the proof doesn't reveal.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CredentialDisclosurePreview {
+    pub schema_id: SchemaId,
+    pub cred_def_id: CredentialDefinitionId,
+    pub rev_reg_id: Option<RevocationRegistryId>,
+    pub timestamps: Vec<u64>,
+    pub hidden_attrs: Vec<String>,
+}
@@ -0,0 +1,208 @@
+use named_type::NamedType;
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::hash::hash;
+
+use super::super::crypto::did::DidValue;
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::RevocationRegistryId;
+
+/// Previous hash of the first entry of the log.
+pub const ISSUANCE_LOG_GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Id of the wallet record keeping the head of the issuance log.
+pub const ISSUANCE_LOG_HEAD_ID: &str = "head";
+
+/// Domain separation prefix of the payload signed for the head of the issuance log.
+pub const ISSUANCE_LOG_HEAD_SIGNATURE_PREFIX: &str = "indy-issuance-log-head/v1";
+
+/// Credential issuance event recorded to the issuer-side audit log.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct IssuanceEvent {
+    pub cred_def_id: CredentialDefinitionId,
+    pub nonce: String,
+    pub timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_id: Option<RevocationRegistryId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cred_rev_id: Option<String>,
+}
+
+/// Entry of the append-only issuance log. Every entry is chained to the previous one by its hash,
+/// so any modification, removal or reordering of the entries breaks the chain.
+#[derive(Clone, Debug, Deserialize, Serialize, NamedType)]
+pub struct IssuanceLogEntry {
+    pub seq_no: u64,
+    pub event: IssuanceEvent,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+/// The latest entry of the issuance log stored in the wallet.
+/// The head is signed with the key of the issuer DID, so the log can't be rewritten without the key.
+#[derive(Debug, Deserialize, Serialize, NamedType)]
+pub struct IssuanceLogHead {
+    pub seq_no: u64,
+    pub hash: String,
+    pub issuer_did: DidValue,
+    pub signature: String,
+}
+
+/// Exported issuance log.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssuanceLog {
+    pub entries: Vec<IssuanceLogEntry>,
+    pub head: IssuanceLogHead,
+}
+
+impl IssuanceLogEntry {
+    pub fn new(seq_no: u64, prev_hash: &str, event: IssuanceEvent) -> IndyResult<IssuanceLogEntry> {
+        let hash = IssuanceLogEntry::_hash(seq_no, prev_hash, &event)?;
+
+        Ok(IssuanceLogEntry {
+            seq_no,
+            event,
+            prev_hash: prev_hash.to_string(),
+            hash,
+        })
+    }
+
+    fn _hash(seq_no: u64, prev_hash: &str, event: &IssuanceEvent) -> IndyResult<String> {
+        let event_json = serde_json::to_string(event)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize IssuanceEvent")?;
+
+        let mut input = prev_hash.as_bytes().to_vec();
+        input.extend_from_slice(&seq_no.to_be_bytes());
+        input.extend_from_slice(event_json.as_bytes());
+
+        Ok(hex::encode(hash(&input)?))
+    }
+}
+
+impl IssuanceLogHead {
+    /// Builds the payload signed by the issuer: the fixed prefix followed by
+    /// the issuer DID, sequence number and hash of the last entry.
+    pub fn signature_payload(issuer_did: &DidValue, seq_no: u64, hash: &str) -> Vec<u8> {
+        let mut payload = ISSUANCE_LOG_HEAD_SIGNATURE_PREFIX.as_bytes().to_vec();
+
+        for field in &[issuer_did.0.as_bytes(), &seq_no.to_be_bytes(), hash.as_bytes()] {
+            payload.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            payload.extend_from_slice(field);
+        }
+
+        payload
+    }
+}
+
+impl IssuanceLog {
+    /// Checks that entries are numbered consecutively, chained with each other and the last one matches the head.
+    /// Signature of the head must be checked separately against the issuer verkey.
+    /// Returns description of the first found violation.
+    pub fn verify(&self) -> IndyResult<Option<String>> {
+        let mut prev_hash = ISSUANCE_LOG_GENESIS_HASH.to_string();
+
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if entry.seq_no != idx as u64 + 1 {
+                return Ok(Some(format!("Entry {} has unexpected sequence number {}", idx + 1, entry.seq_no)));
+            }
+
+            if entry.prev_hash != prev_hash {
+                return Ok(Some(format!("Entry {} isn't chained with the previous one", entry.seq_no)));
+            }
+
+            if entry.hash != IssuanceLogEntry::_hash(entry.seq_no, &entry.prev_hash, &entry.event)? {
+                return Ok(Some(format!("Entry {} hash doesn't match its content", entry.seq_no)));
+            }
+
+            prev_hash = entry.hash.clone();
+        }
+
+        if self.head.seq_no != self.entries.len() as u64 || self.head.hash != prev_hash {
+            return Ok(Some(String::from("Head doesn't match the last entry")));
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _event(nonce: &str) -> IssuanceEvent {
+        IssuanceEvent {
+            cred_def_id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag".to_string()),
+            nonce: nonce.to_string(),
+            timestamp: 1_570_000_000,
+            rev_reg_id: None,
+            cred_rev_id: Some("1".to_string()),
+        }
+    }
+
+    fn _head(seq_no: u64, hash: &str) -> IssuanceLogHead {
+        IssuanceLogHead {
+            seq_no,
+            hash: hash.to_string(),
+            issuer_did: DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string()),
+            signature: String::new(),
+        }
+    }
+
+    fn _log() -> IssuanceLog {
+        let first = IssuanceLogEntry::new(1, ISSUANCE_LOG_GENESIS_HASH, _event("1")).unwrap();
+        let second = IssuanceLogEntry::new(2, &first.hash, _event("2")).unwrap();
+        let head = _head(2, &second.hash);
+
+        IssuanceLog { entries: vec![first, second], head }
+    }
+
+    #[test]
+    fn issuance_log_verify_works() {
+        assert_eq!(None, _log().verify().unwrap());
+    }
+
+    #[test]
+    fn issuance_log_verify_works_for_empty_log() {
+        let log = IssuanceLog { entries: Vec::new(), head: _head(0, ISSUANCE_LOG_GENESIS_HASH) };
+        assert_eq!(None, log.verify().unwrap());
+    }
+
+    #[test]
+    fn issuance_log_verify_works_for_modified_event() {
+        let mut log = _log();
+        log.entries[0].event.nonce = "3".to_string();
+        assert!(log.verify().unwrap().is_some());
+    }
+
+    #[test]
+    fn issuance_log_verify_works_for_removed_entry() {
+        let mut log = _log();
+        log.entries.remove(0);
+        assert!(log.verify().unwrap().is_some());
+    }
+
+    #[test]
+    fn issuance_log_verify_works_for_truncated_log() {
+        let mut log = _log();
+        log.entries.pop();
+        assert!(log.verify().unwrap().is_some());
+    }
+
+    #[test]
+    fn issuance_log_verify_works_for_truncated_log_with_rewritten_head() {
+        let mut log = _log();
+        log.entries.pop();
+        log.head.hash = log.entries[0].hash.clone();
+        assert!(log.verify().unwrap().is_some());
+    }
+
+    #[test]
+    fn issuance_log_head_signature_payload_is_bound_to_fields() {
+        let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
+        let payload = IssuanceLogHead::signature_payload(&did, 1, "hash");
+
+        assert!(payload.starts_with(ISSUANCE_LOG_HEAD_SIGNATURE_PREFIX.as_bytes()));
+        assert_ne!(payload, IssuanceLogHead::signature_payload(&did, 2, "hash"));
+        assert_ne!(payload, IssuanceLogHead::signature_payload(&DidValue("VsKV7grR1BUE29mG2Fm2kX".to_string()), 1, "hash"));
+    }
+}
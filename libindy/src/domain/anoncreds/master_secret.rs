@@ -10,3 +10,13 @@ pub struct MasterSecret {
 }
 
 impl Validatable for MasterSecret {}
+
+/// Name of the credential tag keeping the id of master secret the credential is bound to.
+pub const MASTER_SECRET_ID_TAG: &str = "master_secret_id";
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct MasterSecretInfo {
+    pub id: String,
+    /// Number of stored credentials bound to the master secret.
+    pub credentials_count: usize,
+}
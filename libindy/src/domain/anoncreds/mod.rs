@@ -4,14 +4,18 @@ pub mod credential_definition;
 pub mod credential_for_proof_request;
 pub mod credential_offer;
 pub mod credential_request;
+pub mod disclosure_preview;
+pub mod issuance_log;
 pub mod proof;
 pub mod proof_request;
+pub mod proof_request_template;
 pub mod requested_credential;
 pub mod revocation_registry_definition;
 pub mod revocation_registry_delta;
 pub mod revocation_registry;
 pub mod revocation_state;
 pub mod schema;
+pub mod w3c_credential;
 pub mod master_secret;
 
 pub const DELIMITER: &str = ":";
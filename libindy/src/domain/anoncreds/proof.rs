@@ -1,17 +1,61 @@
 use std::collections::HashMap;
 
+use serde_json::Value;
 use ursa::cl::Proof as CryptoProof;
 
 use super::schema::SchemaId;
 use super::credential_definition::CredentialDefinitionId;
 use super::revocation_registry_definition::RevocationRegistryId;
+use super::super::crypto::did::DidValue;
 use indy_api_types::validation::Validatable;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Proof {
     pub proof: CryptoProof,
     pub requested_proof: RequestedProof,
-    pub identifiers: Vec<Identifier>
+    pub identifiers: Vec<Identifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub holder_binding: Option<HolderBinding>
+}
+
+impl Proof {
+    /// Data signed by holder binding: nonce of the proof request and the proof itself
+    /// (without binding) serialized with sorted keys.
+    pub fn holder_binding_data(&self, nonce: &str) -> Result<Vec<u8>, serde_json::Error> {
+        let mut proof = serde_json::to_value(self)?;
+
+        if let Value::Object(ref mut map) = proof {
+            map.remove("holder_binding");
+        }
+
+        Ok(format!("{}|{}", nonce, _canonical_json(&proof)).into_bytes())
+    }
+}
+
+fn _canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+
+            let fields: Vec<String> = keys.into_iter()
+                .map(|key| format!("{}:{}", Value::String(key.to_string()), _canonical_json(&map[key])))
+                .collect();
+
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(array) => format!("[{}]", array.iter().map(_canonical_json).collect::<Vec<String>>().join(",")),
+        _ => value.to_string()
+    }
+}
+
+/// Signature of the holder DID key binding the proof to the DID.
+/// Verkey isn't included, verifier checks the signature against the one it expects for the DID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderBinding {
+    pub did: DidValue,
+    pub signature: String
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,4 +136,10 @@ mod tests {
         let req_proof: RequestedProof = serde_json::from_str(&json).unwrap();
         assert!(req_proof.revealed_attr_groups.is_empty())
     }
+
+    #[test]
+    fn canonical_json_sorts_object_keys() {
+        let value = json!({"b": [{"d": 1, "c": "2"}], "a": null});
+        assert_eq!(r#"{"a":null,"b":[{"c":"2","d":1}]}"#, _canonical_json(&value));
+    }
 }
@@ -24,7 +24,17 @@ pub struct ProofRequestPayload {
     pub requested_attributes: HashMap<String, AttributeInfo>,
     #[serde(default)]
     pub requested_predicates: HashMap<String, PredicateInfo>,
-    pub non_revoked: Option<NonRevocedInterval>
+    pub non_revoked: Option<NonRevocedInterval>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub holder_binding: Option<HolderBindingRequest>
+}
+
+/// Holder DID the proof must be bound to with its verkey known to the verifier.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HolderBindingRequest {
+    pub did: DidValue,
+    pub verkey: String
 }
 
 #[derive(Debug)]
@@ -218,6 +228,10 @@ impl Validatable for ProofRequest {
             }
         }
 
+        if let Some(ref holder_binding) = value.holder_binding {
+            holder_binding.did.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -415,6 +429,7 @@ mod tests {
                 requested_attributes,
                 requested_predicates,
                 non_revoked: None,
+                holder_binding: None,
             });
 
             let mut expected_requested_attributes: HashMap<String, AttributeInfo> = HashMap::new();
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+use regex::Regex;
+
+use named_type::NamedType;
+
+use indy_api_types::errors::prelude::*;
+use indy_api_types::validation::Validatable;
+
+lazy_static! {
+    static ref PLACEHOLDER: Regex = Regex::new(r"^\$\{(?P<name>[A-Za-z0-9_]+)\}$").unwrap();
+}
+
+/// Proof request saved by the verifier to be instantiated for every new presentation.
+/// String values in form of `${name}` are placeholders replaced with parameters on instantiation.
+#[derive(Debug, Serialize, Deserialize, NamedType)]
+pub struct ProofRequestTemplate(pub Value);
+
+pub type ProofRequestTemplateParams = HashMap<String, Value>;
+
+impl ProofRequestTemplate {
+    pub fn instantiate(&self, params: &ProofRequestTemplateParams) -> IndyResult<Value> {
+        let mut proof_req = self.0.clone();
+        _fill(&mut proof_req, params)?;
+        Ok(proof_req)
+    }
+}
+
+impl Validatable for ProofRequestTemplate {
+    fn validate(&self) -> Result<(), String> {
+        match self.0 {
+            Value::Object(ref template) if template.contains_key("nonce") => Err(String::from("Proof Request Template validation failed: nonce is generated for every proof request and can't be set by template")),
+            Value::Object(ref template) if template.contains_key("requested_attributes") || template.contains_key("requested_predicates") => Ok(()),
+            Value::Object(_) => Err(String::from("Proof Request Template validation failed: no requested attributes or predicates")),
+            _ => Err(String::from("Proof Request Template validation failed: template must be a json object"))
+        }
+    }
+}
+
+fn _fill(value: &mut Value, params: &ProofRequestTemplateParams) -> IndyResult<()> {
+    match value {
+        Value::String(ref string) => {
+            if let Some(name) = PLACEHOLDER.captures(string).and_then(|caps| caps.name("name")) {
+                let param = params.get(name.as_str())
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Proof Request Template parameter \"{}\" not provided", name.as_str())))?;
+                *value = param.clone();
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                _fill(value, params)?;
+            }
+        }
+        Value::Array(values) => {
+            for value in values.iter_mut() {
+                _fill(value, params)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _template() -> ProofRequestTemplate {
+        ProofRequestTemplate(json!({
+            "name": "proof_req",
+            "version": "0.1",
+            "requested_attributes": {
+                "attr1_referent": {
+                    "name": "name",
+                    "restrictions": {"cred_def_id": "${cred_def_id}"}
+                }
+            },
+            "non_revoked": {"from": "${from}", "to": "${to}"}
+        }))
+    }
+
+    #[test]
+    fn proof_request_template_instantiate_works() {
+        let params: ProofRequestTemplateParams = serde_json::from_value(json!({
+            "cred_def_id": "NcYxiDXkpYi6ov5FcYDi1e:3:CL:1",
+            "from": 10,
+            "to": 100,
+            "unused": "value"
+        })).unwrap();
+
+        let proof_req = _template().instantiate(&params).unwrap();
+
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1", proof_req["requested_attributes"]["attr1_referent"]["restrictions"]["cred_def_id"]);
+        assert_eq!(json!({"from": 10, "to": 100}), proof_req["non_revoked"]);
+        assert_eq!("proof_req", proof_req["name"]);
+    }
+
+    #[test]
+    fn proof_request_template_instantiate_works_for_missed_param() {
+        let params: ProofRequestTemplateParams = serde_json::from_value(json!({"from": 10, "to": 100})).unwrap();
+
+        let res = _template().instantiate(&params);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn proof_request_template_validate_works() {
+        _template().validate().unwrap();
+        ProofRequestTemplate(json!({"name": "proof_req"})).validate().unwrap_err();
+        ProofRequestTemplate(json!(["requested_attributes"])).validate().unwrap_err();
+        ProofRequestTemplate(json!({"nonce": "123", "requested_attributes": {}})).validate().unwrap_err();
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use indy_api_types::validation::Validatable;
+use indy_api_types::WalletHandle;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RequestedCredentials {
@@ -13,13 +14,17 @@ pub struct RequestedCredentials {
 pub struct RequestedAttribute {
     pub cred_id: String,
     pub timestamp: Option<u64>,
-    pub revealed: bool
+    pub revealed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_handle: Option<WalletHandle>
 }
 
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Hash, Clone)]
 pub struct ProvingCredentialKey {
     pub cred_id: String,
-    pub timestamp: Option<u64>
+    pub timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_handle: Option<WalletHandle>
 }
 
 impl Validatable for RequestedCredentials {
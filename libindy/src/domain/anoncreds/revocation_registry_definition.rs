@@ -127,7 +127,51 @@ pub struct RevocationRegistryDefinitionPrivate {
 pub struct RevocationRegistryInfo {
     pub id: RevocationRegistryId,
     pub curr_id: u32,
-    pub used_ids: HashSet<u32>
+    pub used_ids: HashSet<u32>,
+    /// Time the last entry to be published for the registry was created (seconds since Unix Epoch)
+    #[serde(default)]
+    pub last_delta_timestamp: Option<u64>
+}
+
+/// Usage statistics of the issuer's revocation registry
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevocationRegistryStatistics {
+    pub rev_reg_id: RevocationRegistryId,
+    pub issuance_type: IssuanceType,
+    pub max_cred_num: u32,
+    pub issued: u32,
+    pub revoked: u32,
+    pub remaining: u32,
+    pub last_delta_timestamp: Option<u64>
+}
+
+impl RevocationRegistryStatistics {
+    pub fn new(rev_reg_def: &RevocationRegistryDefinitionV1, rev_reg_info: &RevocationRegistryInfo) -> RevocationRegistryStatistics {
+        let issued = rev_reg_info.curr_id;
+        let revoked = RevocationRegistryStatistics::_revoked(&rev_reg_def.value.issuance_type, rev_reg_info);
+
+        RevocationRegistryStatistics {
+            rev_reg_id: rev_reg_def.id.clone(),
+            issuance_type: rev_reg_def.value.issuance_type.clone(),
+            max_cred_num: rev_reg_def.value.max_cred_num,
+            issued,
+            revoked,
+            remaining: rev_reg_def.value.max_cred_num.saturating_sub(issued),
+            last_delta_timestamp: rev_reg_info.last_delta_timestamp,
+        }
+    }
+
+    // For issuance on demand wallet keeps ids of the active credentials, for issuance by default ids of the revoked ones.
+    // Issuance by default allows to revoke ids that aren't issued yet, so only ids of issued credentials are counted.
+    fn _revoked(issuance_type: &IssuanceType, rev_reg_info: &RevocationRegistryInfo) -> u32 {
+        let issued = rev_reg_info.curr_id;
+        let used_issued = rev_reg_info.used_ids.iter().filter(|id| **id >= 1 && **id <= issued).count() as u32;
+
+        match issuance_type {
+            IssuanceType::ISSUANCE_ON_DEMAND => issued - used_issued,
+            IssuanceType::ISSUANCE_BY_DEFAULT => used_issued
+        }
+    }
 }
 
 qualifiable_type!(RevocationRegistryId);
@@ -270,4 +314,35 @@ mod tests {
             _rev_reg_id_qualified().validate().unwrap();
         }
     }
+
+    mod statistics {
+        use super::*;
+
+        fn _rev_reg_info(curr_id: u32, used_ids: &[u32]) -> RevocationRegistryInfo {
+            RevocationRegistryInfo {
+                id: _rev_reg_id_unqualified(),
+                curr_id,
+                used_ids: used_ids.iter().cloned().collect(),
+                last_delta_timestamp: None,
+            }
+        }
+
+        #[test]
+        fn test_revoked_for_issuance_on_demand() {
+            let rev_reg_info = _rev_reg_info(3, &[1, 3]);
+            assert_eq!(1, RevocationRegistryStatistics::_revoked(&IssuanceType::ISSUANCE_ON_DEMAND, &rev_reg_info));
+        }
+
+        #[test]
+        fn test_revoked_for_issuance_by_default() {
+            let rev_reg_info = _rev_reg_info(3, &[2]);
+            assert_eq!(1, RevocationRegistryStatistics::_revoked(&IssuanceType::ISSUANCE_BY_DEFAULT, &rev_reg_info));
+        }
+
+        #[test]
+        fn test_revoked_for_issuance_by_default_and_not_issued_ids_revoked() {
+            let rev_reg_info = _rev_reg_info(2, &[1, 5, 7]);
+            assert_eq!(1, RevocationRegistryStatistics::_revoked(&IssuanceType::ISSUANCE_BY_DEFAULT, &rev_reg_info));
+        }
+    }
 }
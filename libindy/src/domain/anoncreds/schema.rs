@@ -109,6 +109,39 @@ impl Validatable for AttributeNames {
         if self.0.len() > MAX_ATTRIBUTES_COUNT {
             return Err(format!("The number of Schema attributes {} cannot be greater than {}", self.0.len(), MAX_ATTRIBUTES_COUNT));
         }
+
+        Ok(())
+    }
+}
+
+/// Characters allowed in attribute names besides letters and digits.
+/// `:` is excluded as it separates attribute name in wallet tags of credentials (`attr::<name>::value`).
+const ATTRIBUTE_NAME_PUNCTUATION: &str = " _-.";
+
+impl AttributeNames {
+    /// Checks attribute names by the strict rules: names must not be empty, must consist of letters, digits,
+    /// spaces, `_`, `-` and `.` and must be unique ignoring case and spaces. Schemas created before can violate them,
+    /// so the rules can be turned off for schema creation by runtime config.
+    pub fn validate_names(&self) -> Result<(), String> {
+        let mut common_views: HashMap<String, &str> = HashMap::new();
+
+        for attr in self.0.iter() {
+            if attr.trim().is_empty() {
+                return Err(String::from("Empty Schema attribute name has been passed"));
+            }
+
+            if let Some(c) = attr.chars().find(|c| !c.is_alphanumeric() && !ATTRIBUTE_NAME_PUNCTUATION.contains(*c)) {
+                return Err(format!("Schema attribute name {:?} contains forbidden character {:?}", attr, c));
+            }
+
+            // attribute names are compared ignoring case and spaces when credentials are built
+            let common_view = attr.replace(" ", "").to_lowercase();
+
+            if let Some(other) = common_views.insert(common_view, attr) {
+                return Err(format!("Schema attribute names {:?} and {:?} are duplicates (names are compared ignoring case and spaces)", other, attr));
+            }
+        }
+
         Ok(())
     }
 }
@@ -344,4 +377,63 @@ mod tests {
             schema.validate().unwrap_err();
         }
     }
+
+    mod attribute_names_validation {
+        use super::*;
+
+        fn _attr_names(attrs: &[&str]) -> AttributeNames {
+            AttributeNames(attrs.iter().map(|attr| attr.to_string()).collect())
+        }
+
+        #[test]
+        fn test_validate_attribute_names() {
+            let attr_names = _attr_names(&["name", "age", "first name", "Дата рождения"]);
+            attr_names.validate().unwrap();
+            attr_names.validate_names().unwrap();
+        }
+
+        #[test]
+        fn test_validate_attribute_names_for_empty_list() {
+            _attr_names(&[]).validate().unwrap_err();
+        }
+
+        #[test]
+        fn test_validate_attribute_names_for_too_many_attributes() {
+            let attrs: Vec<String> = (0..MAX_ATTRIBUTES_COUNT + 1).map(|i| format!("attr{}", i)).collect();
+            AttributeNames(attrs.into_iter().collect()).validate().unwrap_err();
+        }
+
+        #[test]
+        fn test_validate_attribute_names_for_empty_name() {
+            _attr_names(&["name", "  "]).validate_names().unwrap_err();
+        }
+
+        #[test]
+        fn test_validate_attribute_names_for_control_characters() {
+            _attr_names(&["name", "a\tb"]).validate_names().unwrap_err();
+        }
+
+        #[test]
+        fn test_validate_attribute_names_for_forbidden_characters() {
+            _attr_names(&["name", "attr::name"]).validate_names().unwrap_err();
+            _attr_names(&["name", "a\"b"]).validate_names().unwrap_err();
+            _attr_names(&["name", "a/b"]).validate_names().unwrap_err();
+        }
+
+        #[test]
+        fn test_validate_attribute_names_for_allowed_punctuation() {
+            _attr_names(&["first_name", "last-name", "address.city"]).validate_names().unwrap();
+        }
+
+        #[test]
+        fn test_validate_attribute_names_for_case_insensitive_duplicates() {
+            _attr_names(&["name", "NAME"]).validate_names().unwrap_err();
+            _attr_names(&["first name", "FirstName"]).validate_names().unwrap_err();
+        }
+
+        #[test]
+        fn test_validate_attribute_names_for_existing_schema_with_duplicates() {
+            _attr_names(&["name", "NAME"]).validate().unwrap();
+        }
+    }
 }
\ No newline at end of file
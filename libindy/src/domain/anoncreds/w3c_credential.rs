@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use named_type::NamedType;
+use ursa::cl::{
+    CredentialSignature,
+    RevocationRegistry,
+    SignatureCorrectnessProof,
+    Witness
+};
+
+use indy_api_types::errors::prelude::*;
+use indy_api_types::validation::Validatable;
+
+use super::credential::{AttributeMetadata, AttributeValues, Credential, CredentialValues};
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::RevocationRegistryId;
+use super::schema::SchemaId;
+use super::super::crypto::did::DidValue;
+
+pub const W3C_CREDENTIAL_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
+pub const VERIFIABLE_CREDENTIAL_TYPE: &str = "VerifiableCredential";
+pub const INDY_CREDENTIAL_TYPE: &str = "IndyCredential";
+pub const INDY_CREDENTIAL_SCHEMA_TYPE: &str = "IndyCredentialSchema";
+pub const CL_SIGNATURE_PROOF_TYPE: &str = "CLSignature2019";
+
+/// Method used to qualify issuer DID of the credentials issued with unqualified identifiers.
+const DEFAULT_DID_METHOD: &str = "sov";
+
+/// Anoncreds credential represented in W3C Verifiable Credentials data model.
+/// Raw attribute values are exposed as credential subject, everything required to
+/// restore the original credential (encoded values and CL signature) is kept in the proof.
+/// Anoncreds credentials don't carry the date of issuance, so `issuanceDate` is set only if known.
+#[derive(Debug, Deserialize, Serialize, NamedType)]
+#[serde(rename_all = "camelCase")]
+pub struct W3CCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub type_: Vec<String>,
+    pub issuer: DidValue,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuance_date: Option<String>,
+    pub credential_schema: W3CCredentialSchema,
+    pub credential_subject: HashMap<String, String>,
+    pub proof: CLSignature2019Proof,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3CCredentialSchema {
+    pub id: SchemaId,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub definition: CredentialDefinitionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation_registry: Option<RevocationRegistryId>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CLSignature2019Proof {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub encoded_values: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub attributes_metadata: HashMap<String, AttributeMetadata>,
+    pub signature: CredentialSignature,
+    pub signature_correctness_proof: SignatureCorrectnessProof,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg: Option<RevocationRegistry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness: Option<Witness>,
+}
+
+impl W3CCredential {
+    pub fn from_credential(credential: Credential, issuance_date: Option<String>) -> IndyResult<W3CCredential> {
+        let issuer = credential.cred_def_id.issuer_did()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Can't get issuer DID from Credential Definition Id: {:?}", credential.cred_def_id)))?;
+
+        let issuer = if issuer.is_fully_qualified() { issuer } else { issuer.qualify(DEFAULT_DID_METHOD) };
+
+        let mut credential_subject = HashMap::new();
+        let mut encoded_values = HashMap::new();
+        let mut attributes_metadata = HashMap::new();
+
+        for (attr, values) in credential.values.0 {
+            if !values.metadata.is_empty() {
+                attributes_metadata.insert(attr.clone(), values.metadata);
+            }
+            credential_subject.insert(attr.clone(), values.raw);
+            encoded_values.insert(attr, values.encoded);
+        }
+
+        Ok(W3CCredential {
+            context: vec![W3C_CREDENTIAL_CONTEXT.to_string()],
+            type_: vec![VERIFIABLE_CREDENTIAL_TYPE.to_string(), INDY_CREDENTIAL_TYPE.to_string()],
+            issuer,
+            issuance_date,
+            credential_schema: W3CCredentialSchema {
+                id: credential.schema_id,
+                type_: INDY_CREDENTIAL_SCHEMA_TYPE.to_string(),
+                definition: credential.cred_def_id,
+                revocation_registry: credential.rev_reg_id,
+            },
+            credential_subject,
+            proof: CLSignature2019Proof {
+                type_: CL_SIGNATURE_PROOF_TYPE.to_string(),
+                encoded_values,
+                attributes_metadata,
+                signature: credential.signature,
+                signature_correctness_proof: credential.signature_correctness_proof,
+                rev_reg: credential.rev_reg,
+                witness: credential.witness,
+            },
+        })
+    }
+
+    pub fn into_credential(self) -> IndyResult<Credential> {
+        let W3CCredential { credential_schema, credential_subject, mut proof, .. } = self;
+
+        let mut values = HashMap::new();
+
+        for (attr, raw) in credential_subject {
+            let encoded = proof.encoded_values.remove(&attr)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Encoded value of attribute \"{}\" not found in the proof", attr)))?;
+            let metadata = proof.attributes_metadata.remove(&attr).unwrap_or_default();
+
+            values.insert(attr, AttributeValues { raw, encoded, metadata });
+        }
+
+        let credential = Credential {
+            schema_id: credential_schema.id,
+            cred_def_id: credential_schema.definition,
+            rev_reg_id: credential_schema.revocation_registry,
+            values: CredentialValues(values),
+            signature: proof.signature,
+            signature_correctness_proof: proof.signature_correctness_proof,
+            rev_reg: proof.rev_reg,
+            witness: proof.witness,
+        };
+
+        credential.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        Ok(credential)
+    }
+}
+
+impl Validatable for W3CCredential {
+    fn validate(&self) -> Result<(), String> {
+        if !self.context.iter().any(|context| context == W3C_CREDENTIAL_CONTEXT) {
+            return Err(format!("W3C Credential validation failed: \"{}\" context not found", W3C_CREDENTIAL_CONTEXT));
+        }
+
+        if !self.type_.iter().any(|type_| type_ == VERIFIABLE_CREDENTIAL_TYPE) {
+            return Err(format!("W3C Credential validation failed: \"{}\" type not found", VERIFIABLE_CREDENTIAL_TYPE));
+        }
+
+        if self.proof.type_ != CL_SIGNATURE_PROOF_TYPE {
+            return Err(format!("W3C Credential validation failed: unsupported proof type \"{}\"", self.proof.type_));
+        }
+
+        self.credential_schema.id.validate()?;
+        self.credential_schema.definition.validate()?;
+
+        match self.credential_schema.definition.issuer_did() {
+            Some(ref did) if did.to_unqualified() == self.issuer.to_unqualified() => {}
+            _ => return Err(String::from("W3C Credential validation failed: issuer doesn't match Credential Definition"))
+        }
+
+        let subject_attrs: HashSet<&String> = self.credential_subject.keys().collect();
+        let encoded_attrs: HashSet<&String> = self.proof.encoded_values.keys().collect();
+
+        if subject_attrs != encoded_attrs {
+            return Err(String::from("W3C Credential validation failed: credential subject doesn't match encoded values of the proof"));
+        }
+
+        Ok(())
+    }
+}
@@ -11,4 +11,22 @@ pub struct GetCacheOptions {
     pub no_update: Option<bool>,    // Use only cached data, do not try to update.
     pub no_store: Option<bool>,     // Skip storing fresh data if updated
     pub min_fresh: Option<i32>,     // Return cached data if not older than this many seconds. -1 means do not check age.
-}
\ No newline at end of file
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct NymCacheOptions {
+    pub no_cache: Option<bool>,     // Skip usage of cache,
+    pub no_store: Option<bool>,     // Skip storing fresh data if updated
+    pub min_fresh: Option<i32>,     // Use cached verkey without refresh if not older than this many seconds. -1 means do not check age.
+    pub max_stale: Option<i32>,     // Use cached verkey older than min_fresh but not older than this many seconds and refresh it in background.
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RevocationPrefetchOptions {
+    pub interval: Option<u64>,      // Prefetch revocation states every this many seconds.
+    pub freshness: Option<u64>,     // Refresh cached revocation state if older than this many seconds.
+    pub max_requests: Option<usize>, // Refresh not more than this many revocation states per round.
+    pub max_credentials: Option<usize>, // Keep revocation states of this many most frequently used credentials.
+}
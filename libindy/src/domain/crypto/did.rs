@@ -4,6 +4,7 @@ use regex::Regex;
 use rust_base58::FromBase58;
 
 use indy_api_types::validation::Validatable;
+use super::key::MasterSeedEncoding;
 use crate::utils::qualifier;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
@@ -28,6 +29,10 @@ pub struct MyDidInfo {
     pub crypto_type: Option<String>,
     pub cid: Option<bool>,
     pub method_name: Option<DidMethod>,
+    /// SLIP-0010 path the DID key is derived by from `seed` used as master seed.
+    pub derivation_path: Option<String>,
+    /// Encoding of `seed` used as master seed. Required if `derivation_path` is set.
+    pub seed_encoding: Option<MasterSeedEncoding>,
 }
 
 impl Validatable for MyDidInfo {
@@ -42,6 +47,58 @@ impl Validatable for MyDidInfo {
     }
 }
 
+/// Options of batch creation of my DIDs.
+/// If `hd_seed` is set then key of every DID is derived from it by SLIP-0010 path `m/<index>'`,
+/// so the same DIDs can be recreated later.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct MyDidsInfo {
+    pub hd_seed: Option<String>,
+    pub hd_seed_encoding: Option<MasterSeedEncoding>,
+    pub start_index: Option<u32>,
+    pub crypto_type: Option<String>,
+    pub cid: Option<bool>,
+    pub method_name: Option<DidMethod>,
+}
+
+impl MyDidsInfo {
+    pub fn did_info(&self, index: u32) -> MyDidInfo {
+        MyDidInfo {
+            did: None,
+            seed: self.hd_seed.clone(),
+            crypto_type: self.crypto_type.clone(),
+            cid: self.cid,
+            method_name: self.method_name.clone(),
+            derivation_path: self.hd_seed.as_ref().map(|_| format!("m/{}'", index)),
+            seed_encoding: self.hd_seed_encoding,
+        }
+    }
+}
+
+impl Validatable for MyDidsInfo {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref hd_seed) = self.hd_seed {
+            if hd_seed.is_empty() {
+                return Err(String::from("Empty HD seed"));
+            }
+            if self.crypto_type.as_ref().map(|crypto_type| crypto_type != "ed25519").unwrap_or(false) {
+                return Err(String::from("HD seed is supported for ed25519 keys only"));
+            }
+        }
+        if let Some(ref name) = self.method_name {
+            name.validate()?
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreatedDid {
+    pub did: String,
+    pub verkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TheirDidInfo {
     pub did: DidValue,
@@ -101,6 +158,16 @@ impl DidValue {
         DidValue(qualifier::to_unqualified(&self.0))
     }
 
+    /// Namespace of the fully-qualified DID: everything between `did:` prefix and the method-specific id.
+    /// `sov` for `did:sov:NcYxiDXkpYi6ov5FcYDi1e` and `indicio:testnet` for `did:indicio:testnet:NcYxiDXkpYi6ov5FcYDi1e`.
+    pub fn get_namespace(&self) -> Option<String> {
+        if !self.is_fully_qualified() {
+            return None;
+        }
+
+        self.0[Self::PREFIX.len() + 1..].rsplitn(2, ':').nth(1).map(String::from)
+    }
+
     pub fn is_abbreviatable(&self) -> bool {
         match self.get_method() {
             Some(ref method) if method.starts_with("sov") => true,
@@ -179,6 +246,32 @@ pub struct TemporaryDid {
     pub verkey: String
 }
 
+pub const AUTHENTICATION_KEY_PURPOSE: &str = "authentication";
+pub const ASSERTION_METHOD_KEY_PURPOSE: &str = "assertionMethod";
+pub const KEY_AGREEMENT_KEY_PURPOSE: &str = "keyAgreement";
+
+pub const DID_OWNERSHIP_PROOF_PREFIX: &str = "indy-did-ownership-proof/v1";
+
+/// Keys of my DID designated for particular purposes.
+/// Purposes without designated key fall back to the DID verkey (or its key agreement key).
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, NamedType)]
+#[serde(rename_all = "camelCase")]
+pub struct DidKeys {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assertion_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_agreement: Option<String>,
+}
+
+/// NYM of DID stored in the wallet as it was last observed on the ledger.
+#[derive(Serialize, Deserialize, Debug, NamedType)]
+pub struct DidNymState {
+    pub verkey: String,
+    pub role: Option<String>
+}
+
 impl From<TemporaryDid> for Did {
     fn from(temp_did: TemporaryDid) -> Self {
         Did {
@@ -187,3 +280,37 @@ impl From<TemporaryDid> for Did {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DidOwnershipProof {
+    pub did: DidValue,
+    pub verkey: String,
+    pub challenge: String,
+    pub audience: String,
+    pub signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq_no: Option<u64>,
+}
+
+impl DidOwnershipProof {
+    /// Data signed by the proof. The challenge isn't signed as is, so the DID key can't be used
+    /// to sign arbitrary data chosen by a verifier.
+    pub fn signature_payload(did: &DidValue, challenge: &str, audience: &str) -> Vec<u8> {
+        let mut payload = DID_OWNERSHIP_PROOF_PREFIX.as_bytes().to_vec();
+
+        for field in &[did.0.as_str(), challenge, audience] {
+            payload.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            payload.extend_from_slice(field.as_bytes());
+        }
+
+        payload
+    }
+}
+
+impl Validatable for DidOwnershipProof {
+    fn validate(&self) -> Result<(), String> {
+        self.did.validate()?;
+        Ok(())
+    }
+}
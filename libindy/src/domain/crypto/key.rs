@@ -15,6 +15,10 @@ pub struct Key {
     pub signkey: String,
     #[cfg(test)]
     pub signkey: String,
+    /// Name of the key vault keeping the secret key. `signkey` is a reference to the key inside of the vault then.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_vault: Option<String>,
 }
 
 impl Key {
@@ -22,6 +26,15 @@ impl Key {
         Key {
             verkey,
             signkey,
+            key_vault: None,
+        }
+    }
+
+    pub fn new_vault_key(verkey: String, key_vault: String, key_ref: String) -> Key {
+        Key {
+            verkey,
+            signkey: key_ref,
+            key_vault: Some(key_vault),
         }
     }
 }
@@ -42,9 +55,64 @@ impl Drop for Key {
 pub struct KeyInfo {
     pub seed: Option<String>,
     pub crypto_type: Option<String>,
+    pub key_vault: Option<String>,
+    pub key_ref: Option<String>,
+    /// SLIP-0010 path the key is derived by from `seed` used as master seed.
+    pub derivation_path: Option<String>,
+    /// Encoding of `seed` used as master seed. Required if `derivation_path` is set.
+    pub seed_encoding: Option<MasterSeedEncoding>,
+}
+
+/// Encoding of the master seed of hierarchical key derivation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MasterSeedEncoding {
+    Hex,
+    Base64,
+    Utf8,
 }
 
 #[derive(Serialize, Deserialize, Debug, NamedType)]
 pub struct KeyMetadata {
-    pub value: String
+    #[serde(default)]
+    pub value: String,
+    /// Path the key was derived by from the master seed, so the key can be recovered.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BlsKeyInfo {
+    pub seed: Option<String>,
+}
+
+/// BLS multi-signature key of a validator node with the proof of possession of its secret key.
+/// `blskey` and `blskey_pop` are published with NODE transaction, `signkey` is used by the node itself.
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[derive(Serialize, Deserialize)]
+pub struct BlsKey {
+    pub blskey: String,
+    pub blskey_pop: String,
+    #[derivative(Debug = "ignore")]
+    pub signkey: String,
+}
+
+impl Drop for BlsKey {
+    fn drop(&mut self) {
+        self.signkey.zeroize();
+    }
+}
+
+/// Links X25519 key agreement key to the ed25519 key it has been derived from.
+#[derive(Serialize, Deserialize, Debug, NamedType)]
+pub struct KeyAgreementKey {
+    pub verkey: String
+}
+
+/// Marks the wallet where key agreement keys are stored for the keys created before they were stored with keys.
+#[derive(Serialize, Deserialize, Debug, NamedType)]
+pub struct KeyAgreementKeysMigration {
+    pub keys: u64
 }
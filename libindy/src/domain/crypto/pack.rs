@@ -1,3 +1,5 @@
+use named_type::NamedType;
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub struct JWE {
     pub protected: String,
@@ -32,10 +34,67 @@ pub struct Protected {
     pub recipients: Vec<Recipient>,
 }
 
+/// How the content encryption key of a packed message is encrypted for the recipients.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PackMode {
+    /// The sender is authenticated, its verkey is sealed for every recipient.
+    Authcrypt,
+    /// The sender is anonymous.
+    Anoncrypt,
+    /// The sender is authenticated, but the message is indistinguishable from anoncrypted one
+    /// for anybody except the recipients.
+    AuthcryptWithHiddenSender,
+}
+
+impl PackMode {
+    /// Mode used if it isn't set explicitly: authcrypt if the sender is known and anoncrypt otherwise.
+    pub fn for_sender(sender: Option<&String>) -> PackMode {
+        if sender.is_some() { PackMode::Authcrypt } else { PackMode::Anoncrypt }
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        *self != PackMode::Anoncrypt
+    }
+}
+
+/// Content sealed into `encrypted_key` of the message packed with hidden sender.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct HiddenSenderKey {
+    pub sender: String,
+    pub iv: String,
+    pub encrypted_key: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub struct UnpackMessage {
     pub message: String,
     pub recipient_verkey: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sender_verkey: Option<String>
-}
\ No newline at end of file
+    pub sender_verkey: Option<String>,
+    pub mode: PackMode,
+    pub sender_authenticated: bool,
+}
+
+/// Inbound message which failed to be unpacked, kept in the wallet to be retried later.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, NamedType)]
+pub struct DeadLetter {
+    pub message: JWE,
+    pub error: String,
+    pub received: u64,
+    pub attempts: u32,
+}
+
+impl DeadLetter {
+    /// Value of the plain `~received` tag, padded to compare receiving times as strings in wallet queries
+    pub fn received_tag(received: u64) -> String {
+        format!("{:020}", received)
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct DeadLetterInfo {
+    pub id: String,
+    #[serde(flatten)]
+    pub dead_letter: DeadLetter,
+}
@@ -0,0 +1,46 @@
+use indy_api_types::WalletHandle;
+use indy_wallet::WalletCloseReason;
+
+use super::crypto::did::DidValue;
+
+/// Security relevant event reported to the listener set by `indy_set_event_listener`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum Event {
+    /// NYM of a DID held in the wallet has been changed on the ledger not through this wallet.
+    #[serde(rename = "did_nym_changed")]
+    DidNymChanged {
+        did: DidValue,
+        wallet_verkey: String,
+        previous_verkey: Option<String>,
+        previous_role: Option<String>,
+        verkey: String,
+        role: Option<String>,
+    },
+    /// Wallet handle is not valid anymore and must not be used by the application.
+    #[serde(rename = "wallet_invalidated")]
+    WalletInvalidated {
+        wallet_handle: WalletHandle,
+        reason: WalletInvalidationReason,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletInvalidationReason {
+    /// Wallet has been closed by `indy_close_wallet`.
+    Closed,
+    /// Wallet hasn't been used for `idle_timeout` of its config.
+    Timeout,
+    /// Storage of the wallet failed.
+    StorageFailure,
+}
+
+impl From<WalletCloseReason> for WalletInvalidationReason {
+    fn from(reason: WalletCloseReason) -> Self {
+        match reason {
+            WalletCloseReason::IdleTimeout => WalletInvalidationReason::Timeout,
+            WalletCloseReason::StorageFailure => WalletInvalidationReason::StorageFailure,
+        }
+    }
+}
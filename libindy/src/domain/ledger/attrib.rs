@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use super::constants::{ATTRIB, GET_ATTR};
 use super::response::GetReplyResultV1;
 use super::super::crypto::did::ShortDidValue;
 
+use indy_api_types::validation::Validatable;
 use named_type::NamedType;
 
 #[derive(Serialize, PartialEq, Debug)]
@@ -86,6 +89,7 @@ pub struct AttribData {
 
 #[derive(Serialize, Deserialize, Clone, Debug, NamedType)]
 pub struct Endpoint {
+    #[serde(alias = "endpoint")] // address of standardized endpoint ATTRIB
     pub ha: String, // indy-node and indy-plenum restrict this to ip-address:port
     pub verkey: Option<String>
 }
@@ -98,3 +102,114 @@ impl Endpoint {
         }
     }
 }
+
+pub const DEFAULT_SERVICE_TYPE: &str = "did-communication";
+
+/// Standardized endpoint ATTRIB data: `{"endpoint": {"endpoint": .., "types": [..], "routingKeys": [..]}}`
+/// Legacy `{"endpoint": {"ha": .., "verkey": ..}}` form is also accepted.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceEndpoint {
+    #[serde(alias = "ha")]
+    pub endpoint: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routing_keys: Vec<String>,
+    /// Transport key published together with the endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verkey: Option<String>,
+}
+
+impl Validatable for ServiceEndpoint {
+    fn validate(&self) -> Result<(), String> {
+        if self.endpoint.trim().is_empty() {
+            return Err(String::from("Empty endpoint address has been passed"));
+        }
+
+        if self.types.iter().any(|type_| type_.trim().is_empty()) {
+            return Err(String::from("Empty endpoint type has been passed"));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ServiceEndpointAttribData {
+    pub endpoint: ServiceEndpoint
+}
+
+/// Service entry of DID Document built from endpoint ATTRIB.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocService {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub priority: u32,
+    pub recipient_keys: Vec<String>,
+    pub routing_keys: Vec<String>,
+    pub service_endpoint: String,
+}
+
+pub const DID_DOC_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+pub const ED25519_VERIFICATION_KEY_TYPE: &str = "Ed25519VerificationKey2018";
+pub const SECP256K1_VERIFICATION_KEY_TYPE: &str = "EcdsaSecp256k1VerificationKey2019";
+pub const P256_VERIFICATION_KEY_TYPE: &str = "EcdsaSecp256r1VerificationKey2019";
+pub const X25519_KEY_AGREEMENT_KEY_TYPE: &str = "X25519KeyAgreementKey2019";
+
+pub const PROVENANCE_LEDGER_NYM: &str = "ledger:NYM";
+pub const PROVENANCE_LEDGER_ATTRIB: &str = "ledger:ATTRIB";
+pub const PROVENANCE_WALLET_PAIRWISE: &str = "wallet:pairwise";
+pub const PAIRWISE_PROVENANCE_KEY: &str = "pairwise";
+
+/// Verification method entry of DID Document built from NYM verkey.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationMethod {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub controller: String,
+    pub public_key_base58: String,
+}
+
+/// Verification method type of the key by the crypto type suffix of the verkey.
+pub fn verification_method_type(crypto_type_name: &str) -> Option<&'static str> {
+    match crypto_type_name {
+        "ed25519" => Some(ED25519_VERIFICATION_KEY_TYPE),
+        "secp256k1" => Some(SECP256K1_VERIFICATION_KEY_TYPE),
+        "p256" => Some(P256_VERIFICATION_KEY_TYPE),
+        "x25519" => Some(X25519_KEY_AGREEMENT_KEY_TYPE),
+        _ => None
+    }
+}
+
+/// Locally stored pairwise relation with the DID.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocPairwise {
+    pub my_did: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+}
+
+/// W3C DID Document assembled from NYM, endpoint ATTRIB and local pairwise of the DID.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    #[serde(rename = "@context")]
+    pub context: String,
+    pub id: String,
+    pub verification_method: Vec<VerificationMethod>,
+    pub authentication: Vec<String>,
+    pub assertion_method: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_agreement: Vec<String>,
+    pub service: Vec<DidDocService>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pairwise: Option<DidDocPairwise>,
+    /// Source of each entry: verification method and service ids and "pairwise"
+    pub provenance: HashMap<String, String>,
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use indy_api_types::validation::Validatable;
 
 use super::constants::{GET_TXN_AUTHR_AGRMT, GET_TXN_AUTHR_AGRMT_AML, TXN_AUTHR_AGRMT, TXN_AUTHR_AGRMT_AML, DISABLE_ALL_TXN_AUTHR_AGRMTS};
+use super::response::ReplyType;
 
 #[derive(Serialize, PartialEq, Debug)]
 pub struct TxnAuthorAgreementOperation {
@@ -144,4 +145,62 @@ impl GetAcceptanceMechanismOperation {
             version,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Transaction Author Agreement as it is returned by GET_TXN_AUTHR_AGRMT request.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TxnAuthorAgreementData {
+    pub text: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratification_ts: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retirement_ts: Option<u64>,
+}
+
+impl TxnAuthorAgreementData {
+    pub fn is_retired(&self, now: u64) -> bool {
+        self.retirement_ts.map(|retirement_ts| retirement_ts <= now).unwrap_or(false)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetTxnAuthorAgreementReplyResult {
+    pub data: Option<TxnAuthorAgreementData>
+}
+
+impl ReplyType for GetTxnAuthorAgreementReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_TXN_AUTHR_AGRMT
+    }
+}
+
+/// Acceptance mechanisms list as it is returned by GET_TXN_AUTHR_AGRMT_AML request.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptanceMechanismsData {
+    pub aml: AcceptanceMechanisms,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aml_context: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetAcceptanceMechanismsReplyResult {
+    pub data: Option<AcceptanceMechanismsData>
+}
+
+impl ReplyType for GetAcceptanceMechanismsReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_TXN_AUTHR_AGRMT_AML
+    }
+}
+
+/// Latest Transaction Author Agreement and Acceptance Mechanisms set on the ledger.
+#[derive(Serialize, Debug)]
+pub struct ActiveTxnAuthorAgreement {
+    pub taa: Option<TxnAuthorAgreementData>,
+    pub aml: Option<AcceptanceMechanismsData>,
+}
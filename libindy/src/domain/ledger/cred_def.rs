@@ -90,3 +90,19 @@ pub struct GetCredDefResultDataV1 {
     pub schema_ref: SchemaId,
     pub public_keys: CredentialDefinitionData
 }
+
+/// Result of the check whether Credential Definition published on the ledger
+/// can be used by the local anoncreds implementation.
+#[derive(Serialize, Debug)]
+pub struct CredDefCompatibilityReport {
+    pub cred_def_id: CredentialDefinitionId,
+    pub compatible: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_key_bits: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation_supported: Option<bool>,
+    /// Human readable descriptions of found incompatibilities
+    pub issues: Vec<String>,
+}
@@ -2,6 +2,8 @@ use super::constants::{NYM, GET_NYM};
 use super::response::{GetReplyResultV0, GetReplyResultV1, ReplyType};
 use super::super::crypto::did::ShortDidValue;
 
+use indy_api_types::validation::Validatable;
+
 #[derive(Serialize, PartialEq, Debug)]
 pub struct NymOperation {
     #[serde(rename = "type")]
@@ -79,3 +81,38 @@ pub struct NymData {
     pub verkey: Option<String>,
     pub role: Option<String>,
 }
+
+#[derive(Deserialize, Debug, Default)]
+pub struct NymHistoryOptions {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+impl Validatable for NymHistoryOptions {
+    fn validate(&self) -> Result<(), String> {
+        for seq_no in self.from.iter().chain(self.to.iter()) {
+            if *seq_no == 0 || *seq_no > ::std::i32::MAX as u64 {
+                return Err(format!("Invalid sequence number: {}", seq_no));
+            }
+        }
+
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return Err(String::from("`from` must not be greater than `to`"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NymHistoryEntry {
+    pub seq_no: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txn_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    pub data: ::serde_json::Value,
+}
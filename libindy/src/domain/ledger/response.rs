@@ -38,7 +38,9 @@ pub struct ReplyDataV1<T> {
 
 #[derive(Debug, Deserialize)]
 pub struct GetReplyResultV0<T> {
-    pub  data: Option<T>
+    pub  data: Option<T>,
+    #[serde(rename = "seqNo")]
+    pub  seq_no: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -86,4 +88,26 @@ pub struct ResponseMetadata {
     pub last_txn_time: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_seq_no: Option<u64>,
-}
\ No newline at end of file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txn_id: Option<String>,
+}
+
+/// Signed receipt of the transaction written to the ledger.
+/// `data` is the transaction as stored on the ledger (`txn`, `txnMetadata`, `reqSignature` and `ver`).
+/// `audit_path` leads from `data` to `root_hash` of the ledger merkle tree of `ledger_size` transactions
+/// and `multi_signature` is BLS multi-signature of the nodes over this root hash.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteReceipt {
+    pub ledger_id: i32,
+    pub seq_no: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txn_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    pub data: serde_json::Value,
+    pub root_hash: String,
+    pub audit_path: Vec<String>,
+    pub ledger_size: u64,
+    pub multi_signature: serde_json::Value,
+}
@@ -1,4 +1,11 @@
 use super::constants::GET_TXN;
+use super::response::ReplyType;
+
+use indy_api_types::validation::Validatable;
+
+pub const DEFAULT_FETCH_TXNS_BATCH_SIZE: u64 = 100;
+pub const MAX_FETCH_TXNS_BATCH_SIZE: u64 = MAX_TXN_WALK_LENGTH;
+pub const MAX_TXN_WALK_LENGTH: u64 = 1000;
 
 #[derive(Serialize, PartialEq, Debug)]
 pub struct GetTxnOperation {
@@ -19,6 +26,17 @@ impl GetTxnOperation {
     }
 }
 
+#[derive(Deserialize, Debug)]
+pub struct GetTxnReplyResult {
+    pub data: Option<::serde_json::Value>
+}
+
+impl ReplyType for GetTxnReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_TXN
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub enum LedgerType {
     POOL = 0,
@@ -35,3 +53,50 @@ impl LedgerType {
         }
     }
 }
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchTxnsOptions {
+    pub ledger_type: Option<String>,
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub batch_size: Option<u64>,
+}
+
+impl Validatable for FetchTxnsOptions {
+    fn validate(&self) -> Result<(), String> {
+        for seq_no in self.from.iter().chain(self.to.iter()) {
+            if *seq_no == 0 || *seq_no > ::std::i32::MAX as u64 {
+                return Err(format!("Invalid sequence number: {}", seq_no));
+            }
+        }
+
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return Err(String::from("`from` must not be greater than `to`"));
+            }
+        }
+
+        if let Some(batch_size) = self.batch_size {
+            if batch_size == 0 || batch_size > MAX_FETCH_TXNS_BATCH_SIZE {
+                return Err(format!("`batchSize` must be in range from 1 to {}", MAX_FETCH_TXNS_BATCH_SIZE));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerTxn {
+    pub seq_no: u64,
+    pub data: ::serde_json::Value,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TxnsBatch {
+    pub txns: Vec<LedgerTxn>,
+    pub cursor: u64,
+    pub complete: bool,
+}
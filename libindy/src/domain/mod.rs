@@ -1,9 +1,11 @@
 pub mod anoncreds;
 pub mod crypto;
+pub mod event;
 pub mod ledger;
 pub mod pairwise;
 pub mod pool;
 pub mod cache;
+pub mod wallet;
 
 use indy_api_types::validation::Validatable;
 
@@ -11,7 +13,12 @@ use indy_api_types::validation::Validatable;
 pub struct IndyConfig {
     pub crypto_thread_pool_size: Option<usize>,
     pub collect_backtrace: Option<bool>,
-    pub freshness_threshold: Option<u64>
+    pub freshness_threshold: Option<u64>,
+    pub cred_def_key_cache_size: Option<usize>,
+    pub cred_def_key_cache_ttl: Option<u64>,
+    pub command_timeout: Option<u64>,
+    pub enforce_canonical_attr_encoding: Option<bool>,
+    pub strict_schema_attr_names: Option<bool>,
 }
 
 impl Validatable for IndyConfig {}
\ No newline at end of file
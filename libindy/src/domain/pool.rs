@@ -1,10 +1,21 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+
 use indy_api_types::validation::Validatable;
+use rust_base58::FromBase58;
+
+use super::crypto::did::ShortDidValue;
+use super::ledger::node::Services;
 
 pub const POOL_CON_ACTIVE_TO: i64 = 5;
 pub const POOL_ACK_TIMEOUT: i64 = 20;
 pub const POOL_REPLY_TIMEOUT: i64 = 60;
 pub const MAX_REQ_PER_POOL_CON: usize = 5;
 pub const NUMBER_READ_NODES: u8 = 2;
+pub const BLS_KEY_LEN: usize = 128;
+pub const TRAFFIC_CAPTURE_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+pub const TRAFFIC_CAPTURE_MAX_FILES: usize = 5;
+pub const CURVE_KEY_LEN: usize = 32;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PoolConfig {
@@ -33,6 +44,21 @@ pub struct PoolOpenConfig {
     pub preordered_nodes: Vec<String>,
     #[serde(default = "PoolOpenConfig::default_number_read_nodes")]
     pub number_read_nodes: u8,
+    #[serde(default = "PoolOpenConfig::default_retry_backoff_factor")]
+    pub retry_backoff_factor: f64,
+    #[serde(default)]
+    pub max_retry_timeout: Option<i64>,
+    /// CURVE key pair of the client used for all connections to the nodes.
+    /// Ephemeral key pair is generated for every connection if not set.
+    #[serde(default)]
+    pub client_key_pair: Option<ClientKeyPair>,
+    /// Generate CURVE key pair of the client once and keep it in the pool config directory,
+    /// so the same key pair is used by all sessions of the pool.
+    #[serde(default)]
+    pub persist_client_key_pair: bool,
+    /// Reject nodes whose transport keys differ from the keys in the genesis transactions.
+    #[serde(default)]
+    pub pin_genesis_node_keys: bool,
 }
 
 impl Validatable for PoolOpenConfig {
@@ -52,6 +78,21 @@ impl Validatable for PoolOpenConfig {
         if self.number_read_nodes == 0 {
             return Err(String::from("`number_read_nodes` must be greater than 0"));
         }
+        if !(self.retry_backoff_factor >= 1.0) {
+            return Err(String::from("`retry_backoff_factor` must be greater than or equal to 1"));
+        }
+        if let Some(max_retry_timeout) = self.max_retry_timeout {
+            if max_retry_timeout < self.timeout {
+                return Err(String::from("`max_retry_timeout` must be greater than or equal to `timeout`"));
+            }
+        }
+        if let Some(ref client_key_pair) = self.client_key_pair {
+            client_key_pair.decode()?;
+
+            if self.persist_client_key_pair {
+                return Err(String::from("`client_key_pair` and `persist_client_key_pair` can't be used together"));
+            }
+        }
         Ok(())
     }
 }
@@ -65,6 +106,11 @@ impl Default for PoolOpenConfig {
             conn_active_timeout: PoolOpenConfig::default_conn_active_timeout(),
             preordered_nodes: PoolOpenConfig::default_preordered_nodes(),
             number_read_nodes: PoolOpenConfig::default_number_read_nodes(),
+            retry_backoff_factor: PoolOpenConfig::default_retry_backoff_factor(),
+            max_retry_timeout: None,
+            client_key_pair: None,
+            persist_client_key_pair: false,
+            pin_genesis_node_keys: false,
         }
     }
 }
@@ -91,4 +137,150 @@ impl PoolOpenConfig {
     }
 
     fn default_number_read_nodes() -> u8 { NUMBER_READ_NODES }
+
+    fn default_retry_backoff_factor() -> f64 { 1.0 }
+}
+
+/// CURVE key pair of the client z85 encoded (as generated by `zmq_curve_keypair`).
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ClientKeyPair {
+    pub public_key: String,
+    #[derivative(Debug = "ignore")]
+    pub secret_key: String,
+}
+
+impl ClientKeyPair {
+    /// Returns raw public and secret keys.
+    pub fn decode(&self) -> Result<([u8; CURVE_KEY_LEN], [u8; CURVE_KEY_LEN]), String> {
+        Ok((_decode_curve_key(&self.public_key, "public_key")?, _decode_curve_key(&self.secret_key, "secret_key")?))
+    }
+}
+
+fn _decode_curve_key(key: &str, name: &str) -> Result<[u8; CURVE_KEY_LEN], String> {
+    let bytes = zmq::z85_decode(key)
+        .map_err(|_| format!("`client_key_pair.{}` must be z85 encoded", name))?;
+
+    if bytes.len() != CURVE_KEY_LEN {
+        return Err(format!("`client_key_pair.{}` must be {} bytes long", name, CURVE_KEY_LEN));
+    }
+
+    let mut res = [0u8; CURVE_KEY_LEN];
+    res.copy_from_slice(&bytes);
+    Ok(res)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TrafficCaptureConfig {
+    pub path: String,
+    #[serde(default = "TrafficCaptureConfig::default_max_file_size")]
+    pub max_file_size: u64,
+    #[serde(default = "TrafficCaptureConfig::default_max_files")]
+    pub max_files: usize,
+}
+
+impl TrafficCaptureConfig {
+    fn default_max_file_size() -> u64 { TRAFFIC_CAPTURE_MAX_FILE_SIZE }
+
+    fn default_max_files() -> usize { TRAFFIC_CAPTURE_MAX_FILES }
+}
+
+impl Validatable for TrafficCaptureConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.path.is_empty() {
+            return Err(String::from("`path` must not be empty"));
+        }
+        if self.max_file_size == 0 {
+            return Err(String::from("`max_file_size` must be greater than 0"));
+        }
+        if self.max_files == 0 {
+            return Err(String::from("`max_files` must be greater than 0"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GenesisNode {
+    pub alias: String,
+    pub dest: ShortDidValue,
+    pub steward: ShortDidValue,
+    pub verkey: Option<String>,
+    pub node_ip: String,
+    pub node_port: u16,
+    pub client_ip: String,
+    pub client_port: u16,
+    pub services: Option<Vec<Services>>,
+    pub blskey: Option<String>,
+    pub blskey_pop: Option<String>,
+}
+
+impl Validatable for GenesisNode {
+    fn validate(&self) -> Result<(), String> {
+        if self.alias.is_empty() {
+            return Err(String::from("`alias` must not be empty"));
+        }
+
+        self.dest.validate()?;
+        self.steward.validate()?;
+
+        if let Some(ref verkey) = self.verkey {
+            if verkey.is_empty() {
+                return Err(format!("Node {}: `verkey` must not be empty", self.alias));
+            }
+        }
+
+        for ip in [&self.node_ip, &self.client_ip].iter() {
+            ip.parse::<IpAddr>()
+                .map_err(|_| format!("Node {}: invalid ip address: {}", self.alias, ip))?;
+        }
+
+        if self.node_port == 0 || self.client_port == 0 {
+            return Err(format!("Node {}: ports must be greater than 0", self.alias));
+        }
+
+        if let Some(ref blskey) = self.blskey {
+            let blskey = blskey.from_base58()
+                .map_err(|err| format!("Node {}: invalid `blskey`: {}", self.alias, err))?;
+
+            if blskey.len() != BLS_KEY_LEN {
+                return Err(format!("Node {}: `blskey` has unexpected length: {}", self.alias, blskey.len()));
+            }
+        }
+
+        if self.blskey_pop.is_some() && self.blskey.is_none() {
+            return Err(format!("Node {}: `blskey_pop` can't be specified without `blskey`", self.alias));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GenesisNodes(pub Vec<GenesisNode>);
+
+impl Validatable for GenesisNodes {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("Genesis must contain at least one node"));
+        }
+
+        let mut aliases = HashSet::new();
+        let mut dests = HashSet::new();
+
+        for node in self.0.iter() {
+            node.validate()?;
+
+            if !aliases.insert(node.alias.as_str()) {
+                return Err(format!("Duplicated node alias: {}", node.alias));
+            }
+
+            if !dests.insert(node.dest.0.as_str()) {
+                return Err(format!("Duplicated node dest: {}", node.dest.0));
+            }
+        }
+
+        Ok(())
+    }
 }
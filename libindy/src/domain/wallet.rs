@@ -0,0 +1,15 @@
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpiringObjectType {
+    Key,
+    Did,
+    Credential,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ExpiringObject {
+    #[serde(rename = "type")]
+    pub type_: ExpiringObjectType,
+    pub id: String,
+    pub expires: u64,
+}
@@ -59,6 +59,7 @@ extern crate indy_wallet;
 mod utils;
 
 pub mod api;
+pub mod api_async;
 mod commands;
 mod services;
 mod domain;
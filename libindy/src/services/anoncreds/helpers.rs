@@ -16,6 +16,16 @@ use crate::domain::anoncreds::credential_request::CredentialRequest;
 use crate::domain::anoncreds::proof_request::ProofRequest;
 
 use std::collections::{HashSet, HashMap};
+use std::sync::Mutex;
+
+use sha2::Sha256;
+use sha2::digest::{FixedOutput, Input};
+use ursa::bn::BigNumber;
+
+lazy_static! {
+    static ref ENFORCE_CANONICAL_ENCODING: Mutex<bool> = Mutex::new(false);
+    static ref STRICT_SCHEMA_ATTR_NAMES: Mutex<bool> = Mutex::new(true);
+}
 
 pub fn attr_common_view(attr: &str) -> String {
     attr.replace(" ", "").to_lowercase()
@@ -154,10 +164,135 @@ pub fn to_unqualified(entity: &str) -> IndyResult<String> {
     Ok(entity.to_string())
 }
 
+/// Encodes json of anoncreds object (proof, credential and etc) into MessagePack.
+/// Maps keep their field names, so the result can be decoded back into the same json.
+pub fn json_to_msgpack(json: &str) -> IndyResult<Vec<u8>> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid json has been passed")?;
+
+    rmp_serde::to_vec_named(&value)
+        .to_indy(IndyErrorKind::InvalidState, "Cannot encode json into MessagePack")
+}
+
+/// Decodes MessagePack produced by `json_to_msgpack` back into json.
+pub fn msgpack_to_json(data: &[u8]) -> IndyResult<String> {
+    let value: serde_json::Value = rmp_serde::from_slice(data)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid MessagePack data has been passed")?;
+
+    serde_json::to_string(&value)
+        .to_indy(IndyErrorKind::InvalidState, "Cannot serialize json")
+}
+
+pub fn set_canonical_encoding_enforced(enforced: bool) {
+    *ENFORCE_CANONICAL_ENCODING.lock().unwrap() = enforced;
+}
+
+fn _canonical_encoding_enforced() -> bool {
+    *ENFORCE_CANONICAL_ENCODING.lock().unwrap()
+}
+
+pub fn set_strict_schema_attr_names(strict: bool) {
+    *STRICT_SCHEMA_ATTR_NAMES.lock().unwrap() = strict;
+}
+
+pub fn strict_schema_attr_names() -> bool {
+    *STRICT_SCHEMA_ATTR_NAMES.lock().unwrap()
+}
+
+/// Encodes raw attribute value into the integer used in CL signatures.
+/// 32-bit integers in canonical form are encoded as themselves, any other value as decimal of sha256 hash of its utf-8 bytes.
+pub fn encode_attribute_value(raw: &str) -> IndyResult<String> {
+    trace!("encode_attribute_value >>> raw: {:?}", secret!(raw));
+
+    if let Ok(value) = raw.parse::<i32>() {
+        if value.to_string() == raw {
+            return Ok(raw.to_string());
+        }
+    }
+
+    let mut hasher = Sha256::default();
+    hasher.input(raw.as_bytes());
+
+    let res = BigNumber::from_bytes(&hasher.fixed_result())?.to_dec()?;
+
+    trace!("encode_attribute_value <<< res: {:?}", secret!(&res));
+
+    Ok(res)
+}
+
+/// Checks that encoded attribute value matches canonical encoding of the raw one.
+/// Does nothing unless canonical encoding is enforced by the runtime config.
+pub fn check_attribute_encoding(attr: &str, raw: &str, encoded: &str) -> IndyResult<()> {
+    if !_canonical_encoding_enforced() {
+        return Ok(());
+    }
+
+    verify_attribute_encoding(attr, raw, encoded)
+}
+
+/// Checks that encoded attribute value matches canonical encoding of the raw one regardless of the runtime config.
+pub fn verify_attribute_encoding(attr: &str, raw: &str, encoded: &str) -> IndyResult<()> {
+    let expected = encode_attribute_value(raw)?;
+
+    if expected.trim_start_matches('0') != encoded.trim_start_matches('0') {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Encoded value of attribute \"{}\" doesn't match canonical encoding of its raw value", attr)));
+    }
+
+    Ok(())
+}
+
+pub fn check_credential_values_encoding(credential_values: &HashMap<String, AttributeValues>) -> IndyResult<()> {
+    for (attr, values) in credential_values {
+        check_attribute_encoding(attr, &values.raw, &values.encoded)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn encode_attribute_value_works_for_integer() {
+        assert_eq!("25", encode_attribute_value("25").unwrap());
+        assert_eq!("-3", encode_attribute_value("-3").unwrap());
+        assert_eq!("2147483647", encode_attribute_value("2147483647").unwrap());
+    }
+
+    #[test]
+    fn encode_attribute_value_works_for_string() {
+        let encoded = encode_attribute_value("Alex").unwrap();
+
+        assert!(encoded.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(encoded, encode_attribute_value("Alex").unwrap());
+        assert_ne!(encoded, encode_attribute_value("alex").unwrap());
+        assert_ne!("2147483648", encode_attribute_value("2147483648").unwrap());
+        assert_ne!("5", encode_attribute_value("05").unwrap());
+    }
+
+    #[test]
+    fn json_to_msgpack_works_for_round_trip() {
+        let json = json!({
+            "proof": {"proofs": [{"primary_proof": {"eq_proof": {"m2": "1234567890123456789012345678901234567890"}}}]},
+            "identifiers": [{"schema_id": "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0", "timestamp": null}],
+            "requested_proof": {"revealed_attrs": {"attr1_referent": {"sub_proof_index": 0, "raw": "Alex"}}}
+        });
+
+        let msgpack = json_to_msgpack(&json.to_string()).unwrap();
+        assert!(msgpack.len() < json.to_string().len());
+
+        let decoded: serde_json::Value = serde_json::from_str(&msgpack_to_json(&msgpack).unwrap()).unwrap();
+        assert_eq!(json, decoded);
+    }
+
+    #[test]
+    fn msgpack_to_json_fails_for_invalid_data() {
+        let res = msgpack_to_json(&[0xc1]);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
     fn _interval() -> NonRevocedInterval { NonRevocedInterval { from: None, to: Some(123) } }
 
     #[test]
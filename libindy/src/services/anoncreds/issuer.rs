@@ -97,6 +97,8 @@ impl Issuer {
                cred_def, secret!(&cred_priv_key), secret!(&cred_issuance_blinding_nonce), secret!(&cred_request), secret!(&cred_values), secret!(&rev_idx),
                rev_reg_def, rev_reg, secret!(&rev_key_priv));
 
+        check_credential_values_encoding(&cred_values.0)?;
+
         let credential_values = build_credential_values(&cred_values.0, None)?;
         let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
 
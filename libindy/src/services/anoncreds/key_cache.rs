@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ursa::cl::CredentialPublicKey;
+
+use crate::domain::anoncreds::credential_definition::{CredentialDefinitionId, CredentialDefinitionV1};
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::hash::hash;
+
+lazy_static! {
+    static ref CACHE_CONFIG: Mutex<KeyCacheConfig> = Mutex::new(KeyCacheConfig::default());
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct KeyCacheConfig {
+    size: usize,
+    ttl: Option<Duration>,
+}
+
+/// Sets limits of the credential definition public key cache used by verifier.
+///
+/// Cache is disabled while `size` is 0 (default), keys cached before are dropped on the next lookup.
+/// `ttl` is in seconds, entries never expire if it is not set.
+pub fn set_cred_def_key_cache_config(size: Option<usize>, ttl: Option<u64>) {
+    let mut config = CACHE_CONFIG.lock().unwrap();
+
+    if let Some(size) = size {
+        config.size = size;
+    }
+
+    if let Some(ttl) = ttl {
+        config.ttl = if ttl == 0 { None } else { Some(Duration::from_secs(ttl)) };
+    }
+}
+
+fn _config() -> KeyCacheConfig {
+    *CACHE_CONFIG.lock().unwrap()
+}
+
+struct CacheEntry {
+    key: Arc<CredentialPublicKey>,
+    cred_def_hash: Vec<u8>,
+    created: Instant,
+    last_used: Instant,
+}
+
+/// Keeps public keys built from credential definitions so verifying many proofs
+/// against the same credential definitions doesn't rebuild CL keys every time.
+/// Entries are checked against the hash of the key data, so a rotated credential definition
+/// with the same id gets its key built again. Cache is shared by proofs verified on the thread pool.
+pub struct CredentialKeyCache {
+    entries: Mutex<HashMap<CredentialDefinitionId, CacheEntry>>,
+}
+
+impl CredentialKeyCache {
+    pub fn new() -> CredentialKeyCache {
+        CredentialKeyCache {
+            entries: Mutex::new(HashMap::new())
+        }
+    }
+
+    pub fn get(&self, id: &CredentialDefinitionId, cred_def: &CredentialDefinitionV1) -> IndyResult<Arc<CredentialPublicKey>> {
+        self._get(id, cred_def, _config())
+    }
+
+    fn _get(&self, id: &CredentialDefinitionId, cred_def: &CredentialDefinitionV1, config: KeyCacheConfig) -> IndyResult<Arc<CredentialPublicKey>> {
+        let mut entries = self.entries.lock().unwrap();
+
+        if config.size == 0 {
+            entries.clear();
+            return CredentialKeyCache::_build_key(cred_def);
+        }
+
+        let cred_def_hash = CredentialKeyCache::_cred_def_hash(cred_def)?;
+
+        let now = Instant::now();
+
+        if let Some(ttl) = config.ttl {
+            entries.retain(|_, entry| now.duration_since(entry.created) < ttl);
+        }
+
+        if let Some(entry) = entries.get_mut(id) {
+            if entry.cred_def_hash == cred_def_hash {
+                trace!("CredentialKeyCache::get: cache hit for {:?}", id);
+                entry.last_used = now;
+                return Ok(entry.key.clone());
+            }
+        }
+
+        entries.remove(id);
+
+        let key = CredentialKeyCache::_build_key(cred_def)?;
+
+        while entries.len() >= config.size {
+            let lru_id = entries.iter()
+                .min_by_key(|&(_, entry)| entry.last_used)
+                .map(|(id, _)| id.clone());
+
+            match lru_id {
+                Some(lru_id) => { entries.remove(&lru_id); }
+                None => break
+            }
+        }
+
+        entries.insert(id.clone(), CacheEntry { key: key.clone(), cred_def_hash, created: now, last_used: now });
+
+        Ok(key)
+    }
+
+    fn _build_key(cred_def: &CredentialDefinitionV1) -> IndyResult<Arc<CredentialPublicKey>> {
+        Ok(Arc::new(CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?))
+    }
+
+    fn _cred_def_hash(cred_def: &CredentialDefinitionV1) -> IndyResult<Vec<u8>> {
+        let cred_def_value = serde_json::to_vec(&cred_def.value)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialDefinitionData")?;
+        hash(&cred_def_value)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::anoncreds::credential_definition::{CredentialDefinitionData, SignatureType};
+    use crate::domain::anoncreds::schema::SchemaId;
+    use crate::services::anoncreds::helpers::{build_credential_schema, build_non_credential_schema};
+    use ursa::cl::issuer::Issuer as CryptoIssuer;
+
+    fn _cred_def() -> CredentialDefinitionV1 {
+        _cred_def_for(&["name".to_string()])
+    }
+
+    fn _cred_def_for(attrs: &[String]) -> CredentialDefinitionV1 {
+        let credential_schema = build_credential_schema(&attrs.iter().cloned().collect()).unwrap();
+        let non_credential_schema = build_non_credential_schema().unwrap();
+        let (pub_key, _, _) = CryptoIssuer::new_credential_def(&credential_schema, &non_credential_schema, false).unwrap();
+
+        CredentialDefinitionV1 {
+            id: _id(1),
+            schema_id: SchemaId("1".to_string()),
+            signature_type: SignatureType::CL,
+            tag: "tag".to_string(),
+            value: CredentialDefinitionData {
+                primary: pub_key.get_primary_key().unwrap().try_clone().unwrap(),
+                revocation: None,
+            },
+        }
+    }
+
+    fn _id(i: u32) -> CredentialDefinitionId {
+        CredentialDefinitionId(format!("NcYxiDXkpYi6ov5FcYDi1e:3:CL:{}:tag", i))
+    }
+
+    fn _config(size: usize, ttl: Option<Duration>) -> KeyCacheConfig {
+        KeyCacheConfig { size, ttl }
+    }
+
+    #[test]
+    fn credential_key_cache_reuses_key() {
+        let cache = CredentialKeyCache::new();
+        let cred_def = _cred_def();
+
+        let key = cache._get(&_id(1), &cred_def, _config(10, None)).unwrap();
+        let cached_key = cache._get(&_id(1), &cred_def, _config(10, None)).unwrap();
+
+        assert!(Arc::ptr_eq(&key, &cached_key));
+    }
+
+    #[test]
+    fn credential_key_cache_works_for_disabled_cache() {
+        let cache = CredentialKeyCache::new();
+        let cred_def = _cred_def();
+
+        let key = cache._get(&_id(1), &cred_def, _config(0, None)).unwrap();
+        let other_key = cache._get(&_id(1), &cred_def, _config(0, None)).unwrap();
+
+        assert!(!Arc::ptr_eq(&key, &other_key));
+        assert_eq!(0, cache.len());
+    }
+
+    #[test]
+    fn credential_key_cache_evicts_least_recently_used() {
+        let cache = CredentialKeyCache::new();
+        let cred_def = _cred_def();
+        let config = _config(2, None);
+
+        let key1 = cache._get(&_id(1), &cred_def, config).unwrap();
+        cache._get(&_id(2), &cred_def, config).unwrap();
+        cache._get(&_id(1), &cred_def, config).unwrap();
+        cache._get(&_id(3), &cred_def, config).unwrap();
+
+        assert_eq!(2, cache.len());
+        assert!(Arc::ptr_eq(&key1, &cache._get(&_id(1), &cred_def, config).unwrap()));
+        assert!(!cache.entries.lock().unwrap().contains_key(&_id(2)));
+    }
+
+    #[test]
+    fn credential_key_cache_works_for_rotated_cred_def() {
+        let cache = CredentialKeyCache::new();
+        let cred_def = _cred_def();
+        let rotated_cred_def = _cred_def_for(&["name".to_string(), "age".to_string()]);
+
+        let key = cache._get(&_id(1), &cred_def, _config(10, None)).unwrap();
+        let rotated_key = cache._get(&_id(1), &rotated_cred_def, _config(10, None)).unwrap();
+
+        assert!(!Arc::ptr_eq(&key, &rotated_key));
+        assert!(Arc::ptr_eq(&rotated_key, &cache._get(&_id(1), &rotated_cred_def, _config(10, None)).unwrap()));
+        assert_eq!(1, cache.len());
+    }
+
+    #[test]
+    fn credential_key_cache_expires_entries() {
+        let cache = CredentialKeyCache::new();
+        let cred_def = _cred_def();
+
+        let key = cache._get(&_id(1), &cred_def, _config(10, None)).unwrap();
+        let other_key = cache._get(&_id(1), &cred_def, _config(10, Some(Duration::from_secs(0)))).unwrap();
+
+        assert!(!Arc::ptr_eq(&key, &other_key));
+    }
+}
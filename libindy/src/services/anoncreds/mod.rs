@@ -1,5 +1,6 @@
 pub mod helpers;
 pub mod issuer;
+pub mod key_cache;
 pub mod prover;
 pub mod verifier;
 
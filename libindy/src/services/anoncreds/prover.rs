@@ -1,5 +1,5 @@
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use ursa::cl::{
     BlindedCredentialSecrets,
@@ -18,6 +18,7 @@ use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolic
 use crate::domain::anoncreds::credential_definition::{CredentialDefinitionV1 as CredentialDefinition, CredentialDefinitionId};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::CredentialRequestMetadata;
+use crate::domain::anoncreds::disclosure_preview::{CredentialDisclosurePreview, DisclosurePreview, PredicatePreview, RevealedAttributeGroupPreview, RevealedAttributePreview, UnrevealedAttributePreview};
 use crate::domain::anoncreds::proof::{Identifier, Proof, RequestedProof, RevealedAttributeInfo, SubProofReferent, RevealedAttributeGroupInfo, AttributeValue};
 use crate::domain::anoncreds::proof_request::{PredicateInfo, PredicateTypes, ProofRequest, ProofRequestPayload, ProofRequestsVersion, RequestedAttributeInfo, RequestedPredicateInfo, ProofRequestExtraQuery};
 use crate::domain::anoncreds::requested_credential::ProvingCredentialKey;
@@ -200,6 +201,7 @@ impl Prover {
             proof,
             requested_proof,
             identifiers,
+            holder_binding: None,
         };
 
         trace!("create_proof <<< full_proof: {:?}", full_proof);
@@ -207,6 +209,117 @@ impl Prover {
         Ok(full_proof)
     }
 
+    /// Builds the preview of data `create_proof` would disclose for the same proof request and requested credentials.
+    pub fn preview_disclosure(&self,
+                              credentials: &HashMap<String, Credential>,
+                              proof_req: &ProofRequest,
+                              requested_credentials: &RequestedCredentials) -> IndyResult<DisclosurePreview> {
+        trace!("preview_disclosure >>> credentials: {:?}, proof_req: {:?}, requested_credentials: {:?}", credentials, proof_req, requested_credentials);
+
+        let proof_req_val = proof_req.value();
+
+        let mut preview = DisclosurePreview::default();
+
+        preview.self_attested_attrs = requested_credentials.self_attested_attributes.clone();
+
+        let credentials_for_proving = Prover::_prepare_credentials_for_proving(requested_credentials, proof_req_val)?;
+
+        for (cred_key, (req_attrs_for_cred, req_predicates_for_cred)) in credentials_for_proving {
+            let credential: &Credential = credentials.get(cred_key.cred_id.as_str())
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential not found by id: {:?}", cred_key.cred_id)))?;
+
+            let mut revealed_attrs: HashSet<String> = HashSet::new();
+
+            for attr_info in req_attrs_for_cred {
+                let attribute = &attr_info.attr_info;
+
+                if !attr_info.revealed {
+                    let names = attribute.name.clone().map(|name| vec![name])
+                        .or_else(|| attribute.names.clone())
+                        .unwrap_or_default();
+
+                    preview.unrevealed_attrs.insert(attr_info.attr_referent,
+                                                    UnrevealedAttributePreview { cred_id: cred_key.cred_id.clone(), names });
+                } else if let Some(name) = &attribute.name {
+                    let attribute_values = self._get_credential_values(credential, name)?;
+                    revealed_attrs.insert(attr_common_view(name));
+
+                    preview.revealed_attrs.insert(attr_info.attr_referent.clone(),
+                                                  RevealedAttributePreview {
+                                                      cred_id: cred_key.cred_id.clone(),
+                                                      name: name.clone(),
+                                                      raw: attribute_values.raw,
+                                                  });
+                } else if let Some(names) = &attribute.names {
+                    let mut values: HashMap<String, String> = HashMap::new();
+                    for name in names {
+                        let attribute_values = self._get_credential_values(credential, name)?;
+                        revealed_attrs.insert(attr_common_view(name));
+                        values.insert(name.clone(), attribute_values.raw);
+                    }
+
+                    preview.revealed_attr_groups.insert(attr_info.attr_referent.clone(),
+                                                        RevealedAttributeGroupPreview { cred_id: cred_key.cred_id.clone(), values });
+                }
+            }
+
+            for predicate_info in req_predicates_for_cred {
+                let predicate = predicate_info.predicate_info;
+
+                self._get_credential_values(credential, &predicate.name)?;
+
+                preview.predicates.insert(predicate_info.predicate_referent,
+                                          PredicatePreview {
+                                              cred_id: cred_key.cred_id.clone(),
+                                              name: predicate.name,
+                                              p_type: predicate.p_type,
+                                              p_value: predicate.p_value,
+                                          });
+            }
+
+            // Identifiers are put into the proof the same way as `create_proof` does
+            let (schema_id, cred_def_id, rev_reg_id) = match proof_req {
+                ProofRequest::ProofRequestV1(_) => (credential.schema_id.to_unqualified(),
+                                                    credential.cred_def_id.to_unqualified(),
+                                                    credential.rev_reg_id.as_ref().map(|id| id.to_unqualified())),
+                ProofRequest::ProofRequestV2(_) => (credential.schema_id.clone(),
+                                                    credential.cred_def_id.clone(),
+                                                    credential.rev_reg_id.clone()),
+            };
+
+            let credential_preview = preview.credentials.entry(cred_key.cred_id.clone())
+                .or_insert_with(|| CredentialDisclosurePreview {
+                    schema_id,
+                    cred_def_id,
+                    rev_reg_id,
+                    timestamps: Vec::new(),
+                    hidden_attrs: credential.values.0.keys().cloned().collect(),
+                });
+
+            if let Some(timestamp) = cred_key.timestamp {
+                if !credential_preview.timestamps.contains(&timestamp) {
+                    credential_preview.timestamps.push(timestamp);
+                }
+            }
+
+            credential_preview.hidden_attrs.retain(|attr| !revealed_attrs.contains(&attr_common_view(attr)));
+        }
+
+        for credential_preview in preview.credentials.values_mut() {
+            credential_preview.timestamps.sort();
+            credential_preview.hidden_attrs.sort();
+        }
+
+        trace!("preview_disclosure <<< preview: {:?}", preview);
+
+        Ok(preview)
+    }
+
+    fn _get_credential_values(&self, credential: &Credential, name: &str) -> IndyResult<AttributeValues> {
+        self.get_credential_values_for_attribute(&credential.values.0, name)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential value not found for attribute {:?}", name)))
+    }
+
     pub fn _prepare_credentials_for_proving(requested_credentials: &RequestedCredentials,
                                             proof_req: &ProofRequestPayload) -> IndyResult<HashMap<ProvingCredentialKey, (Vec<RequestedAttributeInfo>, Vec<RequestedPredicateInfo>)>> {
         trace!("_prepare_credentials_for_proving >>> requested_credentials: {:?}, proof_req: {:?}", requested_credentials, proof_req);
@@ -224,7 +337,7 @@ impl Prover {
                 revealed: requested_attr.revealed,
             };
 
-            match credentials_for_proving.entry(ProvingCredentialKey { cred_id: requested_attr.cred_id.clone(), timestamp: requested_attr.timestamp }) {
+            match credentials_for_proving.entry(ProvingCredentialKey { cred_id: requested_attr.cred_id.clone(), timestamp: requested_attr.timestamp, wallet_handle: requested_attr.wallet_handle }) {
                 Entry::Occupied(cred_for_proving) => {
                     let &mut (ref mut attributes_for_credential, _) = cred_for_proving.into_mut();
                     attributes_for_credential.push(req_attr_info);
@@ -308,6 +421,12 @@ impl Prover {
                     // abstain for attrs policy marks untaggable
                     res.insert(format!("attr::{}::marker", attr_common_view(&attr)), ATTRIBUTE_EXISTENCE_MARKER.to_string());
                     res.insert(format!("attr::{}::value", attr_common_view(&attr)), values.raw.clone());
+                    if let Some(ref mime_type) = values.metadata.mime_type {
+                        res.insert(format!("attr::{}::mime_type", attr_common_view(&attr)), mime_type.clone());
+                    }
+                    if let Some(ref encoding) = values.metadata.encoding {
+                        res.insert(format!("attr::{}::encoding", attr_common_view(&attr)), encoding.clone());
+                    }
                 }
             });
 
@@ -529,6 +648,7 @@ impl Prover {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::anoncreds::credential::AttributeMetadata;
 
     const SCHEMA_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0";
     const SCHEMA_ISSUER_DID: &str = "NcYxiDXkpYi6ov5FcYDi1e";
@@ -559,8 +679,8 @@ mod tests {
             // note that encoding is not standardized by Indy except that 32-bit integers are encoded as themselves. IS-786
             // so Alex -> 12345 is an application choice while 25 -> 25 is not
             let mut attr_values: HashMap<String, AttributeValues> = HashMap::new();
-            attr_values.insert("name".to_string(), AttributeValues { raw: "Alex".to_string(), encoded: "12345".to_string() });
-            attr_values.insert("age".to_string(), AttributeValues { raw: "25".to_string(), encoded: "25".to_string() });
+            attr_values.insert("name".to_string(), AttributeValues { raw: "Alex".to_string(), encoded: "12345".to_string(), metadata: AttributeMetadata::default() });
+            attr_values.insert("age".to_string(), AttributeValues { raw: "25".to_string(), encoded: "25".to_string(), metadata: AttributeMetadata::default() });
 
             serde_json::from_str::<Credential>(
                 &json!({
@@ -597,6 +717,23 @@ mod tests {
             assert_eq!(expected_tags, tags)
         }
 
+        #[test]
+        fn build_credential_tags_works_for_attr_metadata() {
+            let ps = Prover::new();
+
+            let mut credential = _credential();
+            credential.values.0.get_mut("name").unwrap().metadata = AttributeMetadata {
+                mime_type: Some("image/png".to_string()),
+                encoding: Some("base64".to_string()),
+            };
+
+            let tags = ps.build_credential_tags(&credential, None).unwrap();
+
+            assert_eq!("image/png", tags["attr::name::mime_type"]);
+            assert_eq!("base64", tags["attr::name::encoding"]);
+            assert!(!tags.contains_key("attr::age::mime_type"));
+        }
+
         #[test]
         fn build_credential_tags_works_for_catpol() {
             let ps = Prover::new();
@@ -757,6 +894,7 @@ mod tests {
                     PREDICATE_REFERENT.to_string() => _predicate_info()
                 ),
                 non_revoked: None,
+                holder_binding: None,
             }
         }
 
@@ -768,10 +906,11 @@ mod tests {
                         cred_id: CRED_ID.to_string(),
                         timestamp: None,
                         revealed: false,
+                        wallet_handle: None,
                     }
                 ),
                 requested_predicates: hashmap!(
-                    PREDICATE_REFERENT.to_string() => ProvingCredentialKey{ cred_id: CRED_ID.to_string(), timestamp: None }
+                    PREDICATE_REFERENT.to_string() => ProvingCredentialKey{ cred_id: CRED_ID.to_string(), timestamp: None, wallet_handle: None }
                 ),
             }
         }
@@ -785,9 +924,9 @@ mod tests {
 
 
             assert_eq!(1, res.len());
-            assert!(res.contains_key(&ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None }));
+            assert!(res.contains_key(&ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None, wallet_handle: None }));
 
-            let (req_attr_info, req_pred_info) = res.get(&ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None }).unwrap();
+            let (req_attr_info, req_pred_info) = res.get(&ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None, wallet_handle: None }).unwrap();
             assert_eq!(1, req_attr_info.len());
             assert_eq!(1, req_pred_info.len());
         }
@@ -801,6 +940,7 @@ mod tests {
                 cred_id: CRED_ID.to_string(),
                 timestamp: None,
                 revealed: false,
+                wallet_handle: None,
             });
 
             proof_req.requested_attributes.insert("attribute_referent_2".to_string(), AttributeInfo {
@@ -813,9 +953,9 @@ mod tests {
             let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req).unwrap();
 
             assert_eq!(1, res.len());
-            assert!(res.contains_key(&ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None }));
+            assert!(res.contains_key(&ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None, wallet_handle: None }));
 
-            let (req_attr_info, req_pred_info) = res.get(&ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None }).unwrap();
+            let (req_attr_info, req_pred_info) = res.get(&ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None, wallet_handle: None }).unwrap();
             assert_eq!(2, req_attr_info.len());
             assert_eq!(1, req_pred_info.len());
         }
@@ -843,11 +983,121 @@ mod tests {
         }
     }
 
+    mod preview_disclosure {
+        use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo};
+        use crate::domain::anoncreds::requested_credential::RequestedAttribute;
+
+        use super::*;
+
+        const CRED_ID: &str = "8591bcac-ee7d-4bef-ba7e-984696440b30";
+
+        fn _credentials() -> HashMap<String, Credential> {
+            let mut attr_values: HashMap<String, AttributeValues> = HashMap::new();
+            attr_values.insert("name".to_string(), AttributeValues { raw: "Alex".to_string(), encoded: "12345".to_string(), metadata: AttributeMetadata::default() });
+            attr_values.insert("sex".to_string(), AttributeValues { raw: "male".to_string(), encoded: "5944657099558967239210949258394887428692050081607692519917050".to_string(), metadata: AttributeMetadata::default() });
+            attr_values.insert("age".to_string(), AttributeValues { raw: "28".to_string(), encoded: "28".to_string(), metadata: AttributeMetadata::default() });
+            attr_values.insert("height".to_string(), AttributeValues { raw: "175".to_string(), encoded: "175".to_string(), metadata: AttributeMetadata::default() });
+
+            let credential = serde_json::from_str::<Credential>(
+                &json!({
+                    "schema_id": SCHEMA_ID,
+                    "cred_def_id": CRED_DEF_ID,
+                    "values": attr_values,
+                    "signature": json!({
+                        "p_credential": json!({"m_2": "0","a": "0","e": "0","v": "0"})
+                    }),
+                    "signature_correctness_proof": json!({"se":"0", "c":"0"})
+                }).to_string()
+            ).unwrap();
+
+            hashmap!(CRED_ID.to_string() => credential)
+        }
+
+        fn _attr_info(name: &str) -> AttributeInfo {
+            AttributeInfo { name: Some(name.to_string()), names: None, restrictions: None, non_revoked: None }
+        }
+
+        fn _proof_req() -> ProofRequest {
+            ProofRequest::ProofRequestV2(ProofRequestPayload {
+                nonce: ursa::cl::new_nonce().unwrap(),
+                name: "Job-Application".to_string(),
+                version: "0.1".to_string(),
+                requested_attributes: hashmap!(
+                    "attr1_referent".to_string() => _attr_info("name"),
+                    "attr2_referent".to_string() => _attr_info("sex"),
+                    "attr3_referent".to_string() => _attr_info("phone")
+                ),
+                requested_predicates: hashmap!(
+                    "predicate1_referent".to_string() => PredicateInfo {
+                        name: "age".to_string(),
+                        p_type: PredicateTypes::GE,
+                        p_value: 18,
+                        restrictions: None,
+                        non_revoked: None,
+                    }
+                ),
+                non_revoked: None,
+                holder_binding: None,
+            })
+        }
+
+        fn _req_cred() -> RequestedCredentials {
+            RequestedCredentials {
+                self_attested_attributes: hashmap!("attr3_referent".to_string() => "8-800-300".to_string()),
+                requested_attributes: hashmap!(
+                    "attr1_referent".to_string() => RequestedAttribute { cred_id: CRED_ID.to_string(), timestamp: None, revealed: true, wallet_handle: None },
+                    "attr2_referent".to_string() => RequestedAttribute { cred_id: CRED_ID.to_string(), timestamp: None, revealed: false, wallet_handle: None }
+                ),
+                requested_predicates: hashmap!(
+                    "predicate1_referent".to_string() => ProvingCredentialKey { cred_id: CRED_ID.to_string(), timestamp: None, wallet_handle: None }
+                ),
+            }
+        }
+
+        #[test]
+        fn preview_disclosure_works() {
+            let preview = Prover::new().preview_disclosure(&_credentials(), &_proof_req(), &_req_cred()).unwrap();
+
+            assert_eq!(1, preview.revealed_attrs.len());
+            assert_eq!(RevealedAttributePreview { cred_id: CRED_ID.to_string(), name: "name".to_string(), raw: "Alex".to_string() },
+                       preview.revealed_attrs["attr1_referent"]);
+
+            assert_eq!(UnrevealedAttributePreview { cred_id: CRED_ID.to_string(), names: vec!["sex".to_string()] },
+                       preview.unrevealed_attrs["attr2_referent"]);
+
+            assert_eq!("8-800-300", preview.self_attested_attrs["attr3_referent"]);
+
+            assert_eq!(PredicatePreview { cred_id: CRED_ID.to_string(), name: "age".to_string(), p_type: PredicateTypes::GE, p_value: 18 },
+                       preview.predicates["predicate1_referent"]);
+
+            let credential_preview = &preview.credentials[CRED_ID];
+            assert_eq!(SchemaId(SCHEMA_ID.to_string()), credential_preview.schema_id);
+            assert_eq!(vec!["age".to_string(), "height".to_string(), "sex".to_string()], credential_preview.hidden_attrs);
+        }
+
+        #[test]
+        fn preview_disclosure_works_for_attribute_missed_in_credential() {
+            let mut req_cred = _req_cred();
+            req_cred.self_attested_attributes.clear();
+            req_cred.requested_attributes.insert("attr3_referent".to_string(),
+                                                 RequestedAttribute { cred_id: CRED_ID.to_string(), timestamp: None, revealed: true, wallet_handle: None });
+
+            let res = Prover::new().preview_disclosure(&_credentials(), &_proof_req(), &req_cred);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn preview_disclosure_works_for_unknown_credential() {
+            let res = Prover::new().preview_disclosure(&HashMap::new(), &_proof_req(), &_req_cred());
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
     mod get_credential_values_for_attribute {
         use super::*;
 
         fn _attr_values() -> AttributeValues {
-            AttributeValues { raw: "Alex".to_string(), encoded: "123".to_string() }
+            AttributeValues { raw: "Alex".to_string(), encoded: "123".to_string(), metadata: AttributeMetadata::default() }
         }
 
         fn _cred_values() -> HashMap<String, AttributeValues> {
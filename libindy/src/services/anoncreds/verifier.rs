@@ -8,9 +8,11 @@ use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistr
 use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
+use crate::services::anoncreds::key_cache::CredentialKeyCache;
 
+use std::sync::Arc;
 
-use ursa::cl::{CredentialPublicKey, new_nonce, Nonce};
+use ursa::cl::{new_nonce, Nonce};
 use ursa::cl::verifier::Verifier as CryptoVerifier;
 use crate::utils::wql::Query;
 use regex::Regex;
@@ -29,11 +31,17 @@ lazy_static! {
     static ref INTERNAL_TAG_MATCHER: Regex = Regex::new("^attr::([^:]+)::(value|marker)$").unwrap();
 }
 
-pub struct Verifier {}
+/// Clones share the credential key cache, so proofs can be verified outside of the commands thread.
+#[derive(Clone)]
+pub struct Verifier {
+    cred_def_key_cache: Arc<CredentialKeyCache>
+}
 
 impl Verifier {
     pub fn new() -> Verifier {
-        Verifier {}
+        Verifier {
+            cred_def_key_cache: Arc::new(CredentialKeyCache::new())
+        }
     }
 
     pub fn verify(&self,
@@ -111,7 +119,7 @@ impl Verifier {
             let credential_schema = build_credential_schema(&schema.attr_names.0)?;
             let sub_proof_request = build_sub_proof_request(&attrs_for_credential, &predicates_for_credential)?;
 
-            let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+            let credential_pub_key = self.cred_def_key_cache.get(&identifier.cred_def_id, cred_def)?;
 
             proof_verifier.add_sub_proof_request(&sub_proof_request,
                                                  &credential_schema,
@@ -359,6 +367,9 @@ impl Verifier {
     fn _verify_revealed_attribute_value(attr_name: &str,
                                         proof: &Proof,
                                         attr_info: &RevealedAttributeInfo) -> IndyResult<()> {
+        check_attribute_encoding(attr_name, &attr_info.raw, &attr_info.encoded)
+            .map_err(|err| err.map(IndyErrorKind::ProofRejected, format!("Encoded Value for \"{}\" in RequestedProof doesn't match its raw value", attr_name)))?;
+
         let reveal_attr_encoded = attr_info.encoded.to_string();
         let reveal_attr_encoded = Regex::new("^0*").unwrap().replace_all(&reveal_attr_encoded, "").to_owned();
         let sub_proof_index = attr_info.sub_proof_index as usize;
@@ -124,6 +124,13 @@ impl BlobStorageService {
         Ok(config_handle)
     }
 
+    pub fn check_reader(&self, config_handle: i32) -> IndyResult<()> {
+        match self.reader_configs.try_borrow()?.get(&config_handle) {
+            Some(_) => Ok(()),
+            None => Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage config handle")) // FIXME: Review error kind
+        }
+    }
+
     pub fn open_blob(&self, config_handle: i32, location: &str, hash: &[u8]) -> IndyResult<i32> {
         let reader = self.reader_configs.try_borrow()?
             .get(&config_handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage config handle"))? // FIXME: Review error kind
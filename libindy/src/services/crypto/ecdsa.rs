@@ -0,0 +1,257 @@
+use openssl::bn::{BigNum, BigNumContext, BigNumRef};
+use openssl::ec::{EcGroup, EcKey, EcPoint, PointConversionForm};
+use openssl::ecdsa::EcdsaSig;
+use openssl::nid::Nid;
+use openssl::pkey::{Private, Public};
+use openssl::sha::sha256;
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::hardened;
+
+pub const SECP256K1_CRYPTO_TYPE: &str = "secp256k1";
+pub const P256_CRYPTO_TYPE: &str = "p256";
+
+const SCALAR_BYTES: usize = 32;
+
+/// ECDSA with SHA-256 digest for keys anchored to other ecosystems. These keys can be used for signing only.
+/// Verkey is compressed public point, signkey is private scalar and signature is `r || s` with low `s`.
+pub struct EcdsaCryptoType {
+    curve: Nid,
+}
+
+impl EcdsaCryptoType {
+    pub fn from_name(crypto_type_name: &str) -> Option<EcdsaCryptoType> {
+        let curve = match crypto_type_name {
+            SECP256K1_CRYPTO_TYPE => Nid::SECP256K1,
+            P256_CRYPTO_TYPE => Nid::X9_62_PRIME256V1,
+            _ => return None
+        };
+
+        Some(EcdsaCryptoType { curve })
+    }
+
+    /// Seed is used as private scalar, so the same seed gives the same key pair.
+    pub fn create_key(&self, seed: Option<&[u8]>) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+        let group = EcGroup::from_curve_name(self.curve)?;
+        let mut ctx = BigNumContext::new()?;
+
+        let key = match seed {
+            Some(seed) => {
+                let mut order = BigNum::new()?;
+                group.order(&mut order, &mut ctx)?;
+
+                let private = BigNum::from_slice(seed)?;
+
+                if private.num_bits() == 0 || private >= order {
+                    return Err(err_msg(IndyErrorKind::InvalidStructure, "Seed is out of range of private keys of the curve"));
+                }
+
+                let mut public = EcPoint::new(&group)?;
+                public.mul_generator(&group, &private, &ctx)?;
+
+                EcKey::from_private_components(&group, &private, &public)?
+            }
+            None => {
+                hardened::check_rng()?;
+                EcKey::generate(&group)?
+            }
+        };
+
+        let vk = key.public_key().to_bytes(&group, PointConversionForm::COMPRESSED, &mut ctx)?;
+        let sk = _to_padded_bytes(key.private_key())?;
+
+        Ok((vk, sk))
+    }
+
+    pub fn validate_key(&self, vk: &[u8]) -> IndyResult<()> {
+        self._public_key(vk).map(|_| ())
+    }
+
+    /// Bytes of the DID of the key. The first byte of compressed point is just a parity prefix,
+    /// so the DID is taken from SHA-256 of the point instead of the point itself.
+    pub fn did_bytes(vk: &[u8]) -> Vec<u8> {
+        sha256(vk)[..16].to_vec()
+    }
+
+    pub fn sign(&self, sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+        let group = EcGroup::from_curve_name(self.curve)?;
+        let ctx = BigNumContext::new()?;
+
+        let private = BigNum::from_slice(sk)?;
+        let mut public = EcPoint::new(&group)?;
+        public.mul_generator(&group, &private, &ctx)?;
+
+        let key: EcKey<Private> = EcKey::from_private_components(&group, &private, &public)?;
+
+        let signature = EcdsaSig::sign(&sha256(doc), &key)?;
+
+        // Both `s` and `n - s` are valid, only low one is produced and accepted to avoid signature malleability
+        let (order, half_order) = self._order()?;
+
+        let s = if signature.s() > &half_order {
+            let mut s = BigNum::new()?;
+            s.checked_sub(&order, signature.s())?;
+            s
+        } else {
+            signature.s().to_owned()?
+        };
+
+        let mut res = _to_padded_bytes(signature.r())?;
+        res.extend(_to_padded_bytes(&s)?);
+
+        Ok(res)
+    }
+
+    pub fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> IndyResult<bool> {
+        if signature.len() != SCALAR_BYTES * 2 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid signature length: expected {} bytes, got {}", SCALAR_BYTES * 2, signature.len())));
+        }
+
+        let key = self._public_key(vk)?;
+
+        let r = BigNum::from_slice(&signature[..SCALAR_BYTES])?;
+        let s = BigNum::from_slice(&signature[SCALAR_BYTES..])?;
+
+        let (_, half_order) = self._order()?;
+
+        if s > half_order {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Signature isn't in canonical low-S form"));
+        }
+
+        let signature = EcdsaSig::from_private_components(r, s)?;
+
+        Ok(signature.verify(&sha256(doc), &key)?)
+    }
+
+    /// Order of the curve and its half.
+    fn _order(&self) -> IndyResult<(BigNum, BigNum)> {
+        let group = EcGroup::from_curve_name(self.curve)?;
+        let mut ctx = BigNumContext::new()?;
+
+        let mut order = BigNum::new()?;
+        group.order(&mut order, &mut ctx)?;
+
+        let mut half_order = BigNum::new()?;
+        half_order.rshift1(&order)?;
+
+        Ok((order, half_order))
+    }
+
+    fn _public_key(&self, vk: &[u8]) -> IndyResult<EcKey<Public>> {
+        let group = EcGroup::from_curve_name(self.curve)?;
+        let mut ctx = BigNumContext::new()?;
+
+        let point = EcPoint::from_bytes(&group, vk, &mut ctx)
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid public key point")?;
+
+        let key = EcKey::from_public_key(&group, &point)?;
+        key.check_key()
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid public key")?;
+
+        Ok(key)
+    }
+}
+
+fn _to_padded_bytes(num: &BigNumRef) -> IndyResult<Vec<u8>> {
+    let bytes = num.to_vec();
+
+    if bytes.len() > SCALAR_BYTES {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Scalar is too big for the curve"));
+    }
+
+    let mut res = vec![0u8; SCALAR_BYTES - bytes.len()];
+    res.extend(bytes);
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &[u8] = b"00000000000000000000000000000My1";
+
+    #[test]
+    fn create_key_works_for_seed() {
+        for name in &[SECP256K1_CRYPTO_TYPE, P256_CRYPTO_TYPE] {
+            let crypto_type = EcdsaCryptoType::from_name(name).unwrap();
+
+            let (vk, sk) = crypto_type.create_key(Some(SEED)).unwrap();
+            assert_eq!(33, vk.len());
+            assert_eq!(SCALAR_BYTES, sk.len());
+
+            assert_eq!((vk, sk), crypto_type.create_key(Some(SEED)).unwrap());
+        }
+    }
+
+    #[test]
+    fn sign_verify_works() {
+        for name in &[SECP256K1_CRYPTO_TYPE, P256_CRYPTO_TYPE] {
+            let crypto_type = EcdsaCryptoType::from_name(name).unwrap();
+            let (vk, sk) = crypto_type.create_key(None).unwrap();
+
+            let signature = crypto_type.sign(&sk, b"message").unwrap();
+            assert_eq!(SCALAR_BYTES * 2, signature.len());
+
+            assert!(crypto_type.verify(&vk, b"message", &signature).unwrap());
+            assert!(!crypto_type.verify(&vk, b"other message", &signature).unwrap());
+        }
+    }
+
+    #[test]
+    fn sign_works_for_low_s() {
+        for name in &[SECP256K1_CRYPTO_TYPE, P256_CRYPTO_TYPE] {
+            let crypto_type = EcdsaCryptoType::from_name(name).unwrap();
+            let (_, sk) = crypto_type.create_key(Some(SEED)).unwrap();
+            let (_, half_order) = crypto_type._order().unwrap();
+
+            for i in 0..16u8 {
+                let signature = crypto_type.sign(&sk, &[i]).unwrap();
+                assert!(BigNum::from_slice(&signature[SCALAR_BYTES..]).unwrap() <= half_order);
+            }
+        }
+    }
+
+    #[test]
+    fn verify_works_for_high_s() {
+        for name in &[SECP256K1_CRYPTO_TYPE, P256_CRYPTO_TYPE] {
+            let crypto_type = EcdsaCryptoType::from_name(name).unwrap();
+            let (vk, sk) = crypto_type.create_key(Some(SEED)).unwrap();
+            let (order, _) = crypto_type._order().unwrap();
+
+            let signature = crypto_type.sign(&sk, b"message").unwrap();
+
+            let mut high_s = BigNum::new().unwrap();
+            high_s.checked_sub(&order, &BigNum::from_slice(&signature[SCALAR_BYTES..]).unwrap()).unwrap();
+
+            let mut malleated = signature[..SCALAR_BYTES].to_vec();
+            malleated.extend(_to_padded_bytes(&high_s).unwrap());
+
+            assert_kind!(IndyErrorKind::InvalidStructure, crypto_type.verify(&vk, b"message", &malleated));
+        }
+    }
+
+    #[test]
+    fn did_bytes_works() {
+        let crypto_type = EcdsaCryptoType::from_name(SECP256K1_CRYPTO_TYPE).unwrap();
+        let (vk, _) = crypto_type.create_key(Some(SEED)).unwrap();
+
+        let did_bytes = EcdsaCryptoType::did_bytes(&vk);
+        assert_eq!(16, did_bytes.len());
+        assert_ne!(&vk[..16], &did_bytes[..]);
+    }
+
+    #[test]
+    fn validate_key_works() {
+        let crypto_type = EcdsaCryptoType::from_name(SECP256K1_CRYPTO_TYPE).unwrap();
+        let (vk, _) = crypto_type.create_key(Some(SEED)).unwrap();
+
+        crypto_type.validate_key(&vk).unwrap();
+        assert_kind!(IndyErrorKind::InvalidStructure, crypto_type.validate_key(&[0u8; 33]));
+    }
+
+    #[test]
+    fn from_name_works_for_unknown_crypto() {
+        assert!(EcdsaCryptoType::from_name("ed25519").is_none());
+    }
+}
@@ -2,6 +2,7 @@ use indy_api_types::errors::IndyError;
 use super::CryptoType;
 use indy_utils::crypto::ed25519_box;
 use indy_utils::crypto::ed25519_sign;
+use indy_utils::crypto::hardened;
 use indy_utils::crypto::sealedbox;
 
 
@@ -15,13 +16,21 @@ impl ED25519CryptoType {
 
 impl CryptoType for ED25519CryptoType {
     fn crypto_box(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> Result<Vec<u8>, IndyError> {
-        ed25519_box::encrypt(&ed25519_sign::sk_to_curve25519(sk)?,
-                           &ed25519_sign::vk_to_curve25519(vk)?, doc, nonce)
+        self.crypto_box_to_x25519(sk, &ed25519_sign::vk_to_curve25519(vk)?, doc, nonce)
+    }
+
+    fn crypto_box_to_x25519(&self, sk: &ed25519_sign::SecretKey, pk: &ed25519_box::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> Result<Vec<u8>, IndyError> {
+        hardened::check_x25519_public_key(&pk[..])?;
+        ed25519_box::encrypt(&ed25519_sign::sk_to_curve25519(sk)?, pk, doc, nonce)
     }
 
     fn crypto_box_open(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> Result<Vec<u8>, IndyError> {
-        ed25519_box::decrypt(&ed25519_sign::sk_to_curve25519(sk)?,
-                           &ed25519_sign::vk_to_curve25519(vk)?, doc, nonce)
+        self.crypto_box_open_from_x25519(sk, &ed25519_sign::vk_to_curve25519(vk)?, doc, nonce)
+    }
+
+    fn crypto_box_open_from_x25519(&self, sk: &ed25519_sign::SecretKey, pk: &ed25519_box::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> Result<Vec<u8>, IndyError> {
+        hardened::check_x25519_public_key(&pk[..])?;
+        ed25519_box::decrypt(&ed25519_sign::sk_to_curve25519(sk)?, pk, doc, nonce)
     }
 
     fn gen_nonce(&self) -> ed25519_box::Nonce {
@@ -29,6 +38,9 @@ impl CryptoType for ED25519CryptoType {
     }
 
     fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> Result<(ed25519_sign::PublicKey, ed25519_sign::SecretKey), IndyError> {
+        if seed.is_none() {
+            hardened::check_rng()?;
+        }
         ed25519_sign::create_key_pair_for_signature(seed)
     }
 
@@ -41,7 +53,12 @@ impl CryptoType for ED25519CryptoType {
     }
 
     fn crypto_box_seal(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> Result<Vec<u8>, IndyError> {
-        sealedbox::encrypt(&ed25519_sign::vk_to_curve25519(vk)?, doc)
+        self.crypto_box_seal_to_x25519(&ed25519_sign::vk_to_curve25519(vk)?, doc)
+    }
+
+    fn crypto_box_seal_to_x25519(&self, pk: &ed25519_box::PublicKey, doc: &[u8]) -> Result<Vec<u8>, IndyError> {
+        hardened::check_x25519_public_key(&pk[..])?;
+        sealedbox::encrypt(pk, doc)
     }
 
     fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> Result<Vec<u8>, IndyError> {
@@ -0,0 +1,262 @@
+use std::convert::TryFrom;
+
+use indy_api_types::ErrorCode;
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::hardened;
+use indy_utils::crypto::hmacsha256;
+
+use crate::api::crypto::EntropySourceFillCB;
+
+/// External source of randomness (host RNG, certified DRBG and etc) used instead of the default OS one.
+pub trait EntropySource {
+    fn random_bytes(&self, len: usize) -> IndyResult<Vec<u8>>;
+}
+
+/// Entropy source implemented by the application and registered through `indy_register_entropy_source`.
+pub struct PluggedEntropySource {
+    fill: EntropySourceFillCB,
+}
+
+impl PluggedEntropySource {
+    pub fn new(fill: EntropySourceFillCB) -> PluggedEntropySource {
+        PluggedEntropySource {
+            fill
+        }
+    }
+}
+
+impl EntropySource for PluggedEntropySource {
+    fn random_bytes(&self, len: usize) -> IndyResult<Vec<u8>> {
+        let len_u32 = u32::try_from(len)
+            .to_indy(IndyErrorKind::InvalidStructure, "Too many random bytes are requested from entropy source")?;
+
+        let mut bytes = vec![0u8; len];
+
+        let err = (self.fill)(bytes.as_mut_ptr(), len_u32);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(bytes)
+    }
+}
+
+const DRBG_ENTROPY_BYTES: usize = 32;
+const DRBG_NONCE_BYTES: usize = 16;
+/// Number of requests served by the generator before it is reseeded from the source.
+const DRBG_RESEED_INTERVAL: u32 = 1024;
+
+/// Random bytes generator fed by an external entropy source. Output of the source is used as seed material
+/// of HMAC-DRBG only. Health of the source is checked when the generator is instantiated and on every reseed.
+pub struct DrbgEntropySource {
+    source: Box<dyn EntropySource>,
+    drbg: HmacDrbg,
+    requests: u32,
+}
+
+impl DrbgEntropySource {
+    pub fn new(source: Box<dyn EntropySource>) -> IndyResult<DrbgEntropySource> {
+        _check_health(&*source)?;
+
+        let entropy = _entropy(&*source, DRBG_ENTROPY_BYTES)?;
+        let nonce = _entropy(&*source, DRBG_NONCE_BYTES)?;
+
+        Ok(DrbgEntropySource {
+            drbg: HmacDrbg::new(&entropy, &nonce),
+            source,
+            requests: 0,
+        })
+    }
+
+    pub fn random_bytes(&mut self, len: usize) -> IndyResult<Vec<u8>> {
+        if self.requests >= DRBG_RESEED_INTERVAL {
+            _check_health(&*self.source)?;
+
+            let entropy = _entropy(&*self.source, DRBG_ENTROPY_BYTES)?;
+            self.drbg.reseed(&entropy);
+            self.requests = 0;
+        }
+
+        self.requests += 1;
+
+        Ok(self.drbg.generate(len))
+    }
+}
+
+// The registered source is used instead of the OS generator, so its health is checked instead
+fn _check_health(source: &dyn EntropySource) -> IndyResult<()> {
+    hardened::check_rng_with(|len| source.random_bytes(len))
+}
+
+fn _entropy(source: &dyn EntropySource, len: usize) -> IndyResult<Vec<u8>> {
+    let bytes = source.random_bytes(len)?;
+
+    if bytes.len() != len || bytes.iter().all(|byte| *byte == bytes[0]) {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Entropy source health check failed"));
+    }
+
+    Ok(bytes)
+}
+
+/// HMAC-DRBG with SHA-256 (NIST SP 800-90A).
+struct HmacDrbg {
+    key: [u8; hmacsha256::KEYBYTES],
+    value: [u8; hmacsha256::TAGBYTES],
+}
+
+impl HmacDrbg {
+    fn new(entropy: &[u8], nonce: &[u8]) -> HmacDrbg {
+        let mut drbg = HmacDrbg {
+            key: [0u8; hmacsha256::KEYBYTES],
+            value: [1u8; hmacsha256::TAGBYTES],
+        };
+
+        drbg._update(&[entropy, nonce].concat());
+
+        drbg
+    }
+
+    fn reseed(&mut self, entropy: &[u8]) {
+        self._update(entropy);
+    }
+
+    fn generate(&mut self, len: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(len);
+
+        while bytes.len() < len {
+            self.value = self._hmac(&[&self.value[..]]);
+            bytes.extend_from_slice(&self.value);
+        }
+
+        bytes.truncate(len);
+
+        self._update(&[]);
+
+        bytes
+    }
+
+    fn _update(&mut self, data: &[u8]) {
+        self.key = self._hmac(&[&self.value[..], &[0x00], data]);
+        self.value = self._hmac(&[&self.value[..]]);
+
+        if !data.is_empty() {
+            self.key = self._hmac(&[&self.value[..], &[0x01], data]);
+            self.value = self._hmac(&[&self.value[..]]);
+        }
+    }
+
+    fn _hmac(&self, data: &[&[u8]]) -> [u8; hmacsha256::TAGBYTES] {
+        let tag = hmacsha256::authenticate(&data.concat(), &hmacsha256::Key::new(self.key));
+
+        let mut res = [0u8; hmacsha256::TAGBYTES];
+        res.copy_from_slice(&tag[..]);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct CounterEntropySource {
+        byte: Cell<u8>,
+    }
+
+    impl CounterEntropySource {
+        fn new(byte: u8) -> CounterEntropySource {
+            CounterEntropySource { byte: Cell::new(byte) }
+        }
+    }
+
+    impl EntropySource for CounterEntropySource {
+        fn random_bytes(&self, len: usize) -> IndyResult<Vec<u8>> {
+            let byte = self.byte.get();
+            self.byte.set(byte.wrapping_add(1));
+            Ok((0..len).map(|i| byte.wrapping_add(i as u8)).collect())
+        }
+    }
+
+    #[test]
+    fn hmac_drbg_works() {
+        let mut drbg = HmacDrbg::new(&[1u8; DRBG_ENTROPY_BYTES], &[2u8; DRBG_NONCE_BYTES]);
+        let mut same_drbg = HmacDrbg::new(&[1u8; DRBG_ENTROPY_BYTES], &[2u8; DRBG_NONCE_BYTES]);
+        let mut other_drbg = HmacDrbg::new(&[3u8; DRBG_ENTROPY_BYTES], &[2u8; DRBG_NONCE_BYTES]);
+
+        let bytes = drbg.generate(40);
+        assert_eq!(40, bytes.len());
+        assert_eq!(bytes, same_drbg.generate(40));
+        assert_ne!(bytes, other_drbg.generate(40));
+        assert_ne!(bytes, drbg.generate(40));
+    }
+
+    #[test]
+    fn drbg_entropy_source_works() {
+        let mut entropy_source = DrbgEntropySource::new(Box::new(CounterEntropySource::new(1))).unwrap();
+
+        let bytes = entropy_source.random_bytes(32).unwrap();
+
+        assert_ne!(CounterEntropySource::new(1).random_bytes(32).unwrap(), bytes);
+        assert_ne!(bytes, entropy_source.random_bytes(32).unwrap());
+    }
+
+    #[cfg(feature = "hardened")]
+    #[test]
+    fn drbg_entropy_source_fails_for_repeating_source() {
+        struct RepeatingEntropySource {}
+
+        impl EntropySource for RepeatingEntropySource {
+            fn random_bytes(&self, len: usize) -> IndyResult<Vec<u8>> {
+                Ok((0..len).map(|i| i as u8).collect())
+            }
+        }
+
+        let res = DrbgEntropySource::new(Box::new(RepeatingEntropySource {}));
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+
+    #[test]
+    fn drbg_entropy_source_fails_for_stuck_source() {
+        struct StuckEntropySource {}
+
+        impl EntropySource for StuckEntropySource {
+            fn random_bytes(&self, len: usize) -> IndyResult<Vec<u8>> {
+                Ok(vec![0u8; len])
+            }
+        }
+
+        let res = DrbgEntropySource::new(Box::new(StuckEntropySource {}));
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+
+    #[test]
+    fn drbg_entropy_source_works_for_reseed_interval() {
+        struct CountingEntropySource {
+            source: CounterEntropySource,
+            calls: Rc<Cell<usize>>,
+        }
+
+        impl EntropySource for CountingEntropySource {
+            fn random_bytes(&self, len: usize) -> IndyResult<Vec<u8>> {
+                self.calls.set(self.calls.get() + 1);
+                self.source.random_bytes(len)
+            }
+        }
+
+        let calls = Rc::new(Cell::new(0));
+        let mut entropy_source = DrbgEntropySource::new(Box::new(CountingEntropySource { source: CounterEntropySource::new(1), calls: calls.clone() })).unwrap();
+
+        let instantiate_calls = calls.get();
+
+        for _ in 0..DRBG_RESEED_INTERVAL {
+            entropy_source.random_bytes(32).unwrap();
+        }
+        assert_eq!(instantiate_calls, calls.get());
+
+        entropy_source.random_bytes(32).unwrap();
+        assert!(calls.get() > instantiate_calls);
+    }
+}
@@ -0,0 +1,102 @@
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::ptr;
+use std::slice;
+
+use libc::{c_char, c_void};
+
+use crate::api::crypto::{KeyVaultFreeCB, KeyVaultGetVerkeyCB, KeyVaultSignCB};
+use indy_api_types::ErrorCode;
+use indy_api_types::errors::prelude::*;
+
+/// External keystore (HSM, secure enclave and etc) which keeps secret keys outside of the process.
+/// Wallet stores only references to such keys and signing is delegated to the keystore.
+pub trait KeyVault {
+    fn get_verkey(&self, key_ref: &str) -> IndyResult<String>;
+    fn sign(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>>;
+}
+
+/// Key vault implemented by the application and registered through `indy_register_key_vault`.
+pub struct PluggedKeyVault {
+    get_verkey: KeyVaultGetVerkeyCB,
+    sign: KeyVaultSignCB,
+    free: KeyVaultFreeCB,
+}
+
+impl PluggedKeyVault {
+    pub fn new(get_verkey: KeyVaultGetVerkeyCB,
+               sign: KeyVaultSignCB,
+               free: KeyVaultFreeCB) -> PluggedKeyVault {
+        PluggedKeyVault {
+            get_verkey,
+            sign,
+            free,
+        }
+    }
+}
+
+struct DataGuard {
+    data: *const c_void,
+    free: KeyVaultFreeCB,
+}
+
+impl Drop for DataGuard {
+    fn drop(&mut self) {
+        if !self.data.is_null() {
+            (self.free)(self.data);
+        }
+    }
+}
+
+impl KeyVault for PluggedKeyVault {
+    fn get_verkey(&self, key_ref: &str) -> IndyResult<String> {
+        let key_ref = CString::new(key_ref)
+            .to_indy(IndyErrorKind::InvalidStructure, "Key reference contains null symbol")?;
+
+        let mut verkey_ptr: *const c_char = ptr::null();
+
+        let err = (self.get_verkey)(key_ref.as_ptr(), &mut verkey_ptr);
+
+        let _guard = DataGuard { data: verkey_ptr as *const c_void, free: self.free };
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        if verkey_ptr.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key vault returned no verkey"));
+        }
+
+        let verkey = unsafe { CStr::from_ptr(verkey_ptr) }
+            .to_str()
+            .to_indy(IndyErrorKind::InvalidState, "Verkey returned by key vault contains non-utf8 symbol")?
+            .to_string();
+
+        Ok(verkey)
+    }
+
+    fn sign(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_ref = CString::new(key_ref)
+            .to_indy(IndyErrorKind::InvalidStructure, "Key reference contains null symbol")?;
+
+        let doc_len = u32::try_from(doc.len())
+            .to_indy(IndyErrorKind::InvalidStructure, "Message is too long to be signed with key vault")?;
+
+        let mut signature_ptr: *const u8 = ptr::null();
+        let mut signature_len: u32 = 0;
+
+        let err = (self.sign)(key_ref.as_ptr(), doc.as_ptr(), doc_len, &mut signature_ptr, &mut signature_len);
+
+        let _guard = DataGuard { data: signature_ptr as *const c_void, free: self.free };
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        if signature_ptr.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key vault returned no signature"));
+        }
+
+        Ok(unsafe { slice::from_raw_parts(signature_ptr, signature_len as usize) }.to_vec())
+    }
+}
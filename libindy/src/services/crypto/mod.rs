@@ -1,43 +1,62 @@
 extern crate hex;
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::str;
 
 use crate::domain::crypto::combo_box::ComboBox;
 use crate::domain::crypto::did::{Did, DidValue, MyDidInfo, TheirDid, TheirDidInfo};
-use crate::domain::crypto::key::{Key, KeyInfo};
+use crate::domain::crypto::key::{BlsKey, BlsKeyInfo, Key, KeyInfo, MasterSeedEncoding};
 use indy_api_types::errors::prelude::*;
 use indy_utils::crypto::base64;
 use indy_utils::crypto::ed25519_box;
 use indy_utils::crypto::chacha20poly1305_ietf;
-use indy_utils::crypto::chacha20poly1305_ietf::gen_nonce_and_encrypt_detached;
 use indy_utils::crypto::ed25519_sign;
+use indy_utils::crypto::hardened;
+use crate::services::pool::DEFAULT_GENERATOR;
 use crate::utils::crypto::verkey_builder::{build_full_verkey, split_verkey, verkey_get_cryptoname};
+use ursa::bls::{Generator, ProofOfPossession, SignKey as BlsSignKey, VerKey as BlsVerKey};
 
+use self::ecdsa::EcdsaCryptoType;
 use self::ed25519::ED25519CryptoType;
+use self::entropy::{DrbgEntropySource, EntropySource};
+use self::key_vault::KeyVault;
 use self::hex::FromHex;
 use rust_base58::{FromBase58, ToBase58};
 
+mod ecdsa;
 mod ed25519;
+pub mod entropy;
+pub mod key_vault;
+mod slip10;
 
 pub const DEFAULT_CRYPTO_TYPE: &str = "ed25519";
+pub const X25519_CRYPTO_TYPE: &str = "x25519";
 
 //TODO fix this crypto trait so it matches the functions below
 //TODO create a second crypto trait for additional functions
 trait CryptoType {
     fn crypto_box(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
+    fn crypto_box_to_x25519(&self, sk: &ed25519_sign::SecretKey, pk: &ed25519_box::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
     fn crypto_box_open(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
+    fn crypto_box_open_from_x25519(&self, sk: &ed25519_sign::SecretKey, pk: &ed25519_box::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
     fn gen_nonce(&self) -> ed25519_box::Nonce;
     fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> IndyResult<(ed25519_sign::PublicKey, ed25519_sign::SecretKey)>;
     fn validate_key(&self, _vk: &ed25519_sign::PublicKey) -> IndyResult<()>;
     fn sign(&self, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<ed25519_sign::Signature>;
     fn verify(&self, vk: &ed25519_sign::PublicKey, doc: &[u8], signature: &ed25519_sign::Signature) -> IndyResult<bool>;
     fn crypto_box_seal(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn crypto_box_seal_to_x25519(&self, pk: &ed25519_box::PublicKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
     fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
 }
 
+/// Produces signature of the document once called.
+pub type Signer = Box<dyn FnOnce() -> IndyResult<Vec<u8>> + Send>;
+
 pub struct CryptoService {
-    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>
+    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>,
+    key_vaults: RefCell<HashMap<String, Box<dyn KeyVault>>>,
+    entropy_source: RefCell<Option<DrbgEntropySource>>,
 }
 
 impl CryptoService {
@@ -46,28 +65,94 @@ impl CryptoService {
         crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519CryptoType::new()));
 
         CryptoService {
-            crypto_types
+            crypto_types,
+            key_vaults: RefCell::new(HashMap::new()),
+            entropy_source: RefCell::new(None),
+        }
+    }
+
+    /// Registers key vault which signing of its keys is delegated to. Vault registered before under the same name is replaced.
+    pub fn register_key_vault(&self, name: &str, key_vault: Box<dyn KeyVault>) {
+        trace!("register_key_vault >>> name: {:?}", name);
+
+        self.key_vaults.borrow_mut().insert(name.to_string(), key_vault);
+
+        trace!("register_key_vault <<<");
+    }
+
+    /// Registers entropy source used instead of the default OS random number generator. Source registered before is replaced.
+    /// Fails if the source doesn't pass the health check.
+    pub fn register_entropy_source(&self, entropy_source: Box<dyn EntropySource>) -> IndyResult<()> {
+        trace!("register_entropy_source >>>");
+
+        *self.entropy_source.borrow_mut() = Some(DrbgEntropySource::new(entropy_source)?);
+
+        trace!("register_entropy_source <<<");
+
+        Ok(())
+    }
+
+    // Returns None if the default random number generator should be used.
+    fn _external_random_bytes(&self, len: usize) -> IndyResult<Option<Vec<u8>>> {
+        match *self.entropy_source.borrow_mut() {
+            Some(ref mut entropy_source) => entropy_source.random_bytes(len).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    fn _seed_or_random(&self, seed: Option<ed25519_sign::Seed>) -> IndyResult<Option<ed25519_sign::Seed>> {
+        if seed.is_some() {
+            return Ok(seed);
+        }
+
+        match self._external_random_bytes(ed25519_sign::SEEDBYTES)? {
+            Some(bytes) => Ok(Some(ed25519_sign::Seed::from_slice(&bytes)?)),
+            None => Ok(None)
+        }
+    }
+
+    fn _gen_nonce(&self, crypto_type: &dyn CryptoType) -> IndyResult<ed25519_box::Nonce> {
+        match self._external_random_bytes(ed25519_box::NONCEBYTES)? {
+            Some(bytes) => ed25519_box::Nonce::from_slice(&bytes),
+            None => Ok(crypto_type.gen_nonce())
+        }
+    }
+
+    /// Generates content encryption key for pack message.
+    pub fn gen_cek(&self) -> IndyResult<chacha20poly1305_ietf::Key> {
+        match self._external_random_bytes(chacha20poly1305_ietf::KEYBYTES)? {
+            Some(bytes) => chacha20poly1305_ietf::Key::from_slice(&bytes),
+            None => {
+                hardened::check_rng()?;
+                Ok(chacha20poly1305_ietf::gen_key())
+            }
         }
     }
 
     pub fn create_key(&self, key_info: &KeyInfo) -> IndyResult<Key> {
         trace!("create_key >>> key_info: {:?}", secret!(key_info));
 
+        if let Some(ref key_vault) = key_info.key_vault {
+            return self._create_vault_key(key_vault, key_info);
+        }
+
         let crypto_type_name = key_info.crypto_type
             .as_ref()
             .map(String::as_str)
             .unwrap_or(DEFAULT_CRYPTO_TYPE);
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        if !self._is_known_crypto(crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("KeyInfo contains unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let seed = self._key_seed(crypto_type_name,
+                                  key_info.seed.as_ref().map(String::as_ref),
+                                  key_info.derivation_path.as_ref().map(String::as_ref),
+                                  key_info.seed_encoding)?;
+        let (vk, sk) = self._create_key_pair(crypto_type_name, seed.as_ref())?;
 
-        let seed = self.convert_seed(key_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
-        let mut vk = vk[..].to_base58();
-        let sk = sk[..].to_base58();
+        let mut vk = vk.to_base58();
+        let sk = sk.to_base58();
         if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
             // Use suffix with crypto type name to store crypto type inside of vk
             vk = format!("{}:{}", vk, crypto_type_name);
@@ -80,6 +165,130 @@ impl CryptoService {
         Ok(key)
     }
 
+    fn _create_vault_key(&self, key_vault_name: &str, key_info: &KeyInfo) -> IndyResult<Key> {
+        if key_info.seed.is_some() || key_info.derivation_path.is_some() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Seed can't be used for keys kept in key vault"));
+        }
+
+        if key_info.crypto_type.as_ref().map(|crypto_type| crypto_type != DEFAULT_CRYPTO_TYPE).unwrap_or(false) {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Key vault supports only {} keys", DEFAULT_CRYPTO_TYPE)));
+        }
+
+        let key_ref = key_info.key_ref.as_ref()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Key reference isn't set for key kept in key vault"))?;
+
+        let verkey = {
+            let key_vaults = self.key_vaults.borrow();
+            let key_vault = key_vaults.get(key_vault_name)
+                .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Unknown key vault: {}", key_vault_name)))?;
+
+            key_vault.get_verkey(key_ref)?
+        };
+
+        self.validate_key(&verkey)?;
+
+        let key = Key::new_vault_key(verkey, key_vault_name.to_string(), key_ref.to_string());
+
+        trace!("create_key <<< key: {:?}", key);
+
+        Ok(key)
+    }
+
+    fn _is_known_crypto(&self, crypto_type_name: &str) -> bool {
+        self.crypto_types.contains_key(crypto_type_name) || EcdsaCryptoType::from_name(crypto_type_name).is_some()
+    }
+
+    // Crypto type must be checked with `_is_known_crypto` before
+    /// Seed of the new key pair: derived from the master seed if the derivation path is set,
+    /// taken as is or random otherwise.
+    fn _key_seed(&self, crypto_type_name: &str, seed: Option<&str>, derivation_path: Option<&str>,
+                 seed_encoding: Option<MasterSeedEncoding>) -> IndyResult<Option<ed25519_sign::Seed>> {
+        let derivation_path = match derivation_path {
+            Some(derivation_path) => derivation_path,
+            None => return self._seed_or_random(self.convert_seed(seed)?)
+        };
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Derivation path is supported for {} keys only", DEFAULT_CRYPTO_TYPE)));
+        }
+
+        let seed = seed
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Master seed is required for key derivation"))?;
+
+        let key = slip10::derive_ed25519_key(&self._convert_master_seed(seed, seed_encoding)?, derivation_path)?;
+
+        Ok(Some(ed25519_sign::Seed::from_slice(&key)?))
+    }
+
+    /// Checks that the master seed of hierarchical derivation has length supported by SLIP-0010.
+    pub fn check_master_seed(&self, seed: &str, encoding: Option<MasterSeedEncoding>) -> IndyResult<()> {
+        let seed = self._convert_master_seed(seed, encoding)?;
+
+        if seed.len() < slip10::MIN_SEED_BYTES || seed.len() > slip10::MAX_SEED_BYTES {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Master seed must be from {} to {} bytes long", slip10::MIN_SEED_BYTES, slip10::MAX_SEED_BYTES)));
+        }
+
+        Ok(())
+    }
+
+    /// Master seed of hierarchical derivation is decoded by the encoding given explicitly,
+    /// as the same string can be valid in several encodings.
+    fn _convert_master_seed(&self, seed: &str, encoding: Option<MasterSeedEncoding>) -> IndyResult<Vec<u8>> {
+        let encoding = encoding
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Encoding of the master seed is required for key derivation"))?;
+
+        match encoding {
+            MasterSeedEncoding::Hex => Vec::from_hex(seed)
+                .to_indy(IndyErrorKind::InvalidStructure, "Master seed is invalid hex"),
+            MasterSeedEncoding::Base64 => base64::decode(seed)
+                .to_indy(IndyErrorKind::InvalidStructure, "Can't deserialize master seed from Base64 string"),
+            MasterSeedEncoding::Utf8 => Ok(seed.as_bytes().to_vec()),
+        }
+    }
+
+    fn _create_key_pair(&self, crypto_type_name: &str, seed: Option<&ed25519_sign::Seed>) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+        if let Some(crypto_type) = EcdsaCryptoType::from_name(crypto_type_name) {
+            return crypto_type.create_key(seed.map(|seed| &seed[..]));
+        }
+
+        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let (vk, sk) = crypto_type.create_key(seed)?;
+
+        Ok((vk[..].to_vec(), sk[..].to_vec()))
+    }
+
+    /// Generates BLS key pair of a validator node. The key isn't stored in the wallet.
+    pub fn create_bls_key(&self, key_info: &BlsKeyInfo) -> IndyResult<BlsKey> {
+        trace!("create_bls_key >>> key_info: {:?}", secret!(key_info));
+
+        let seed = self._seed_or_random(self.convert_seed(key_info.seed.as_ref().map(String::as_ref))?)?;
+
+        let generator = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58()?)?;
+        let sign_key = BlsSignKey::new(seed.as_ref().map(|seed| &seed[..]))?;
+        let ver_key = BlsVerKey::new(&generator, &sign_key)?;
+        let pop = ProofOfPossession::new(&ver_key, &sign_key)?;
+
+        let key = BlsKey {
+            blskey: ver_key.as_bytes().to_base58(),
+            blskey_pop: pop.as_bytes().to_base58(),
+            signkey: sign_key.as_bytes().to_base58(),
+        };
+
+        trace!("create_bls_key <<< key: {:?}", key);
+
+        Ok(key)
+    }
+
+    fn _secret_key(my_key: &Key) -> IndyResult<ed25519_sign::SecretKey> {
+        if let Some(ref key_vault) = my_key.key_vault {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Secret key is kept in key vault {} and can be used for signing only", key_vault)));
+        }
+
+        ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())
+    }
+
     pub fn create_my_did(&self, my_did_info: &MyDidInfo) -> IndyResult<(Did, Key)> {
         trace!("create_my_did >>> my_did_info: {:?}", secret!(my_did_info));
 
@@ -88,24 +297,27 @@ impl CryptoService {
             .map(String::as_str)
             .unwrap_or(DEFAULT_CRYPTO_TYPE);
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
+        if !self._is_known_crypto(crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("MyDidInfo contains unknown crypto: {}", crypto_type_name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
-
-        let seed = self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
+        let seed = self._key_seed(crypto_type_name,
+                                  my_did_info.seed.as_ref().map(String::as_ref),
+                                  my_did_info.derivation_path.as_ref().map(String::as_ref),
+                                  my_did_info.seed_encoding)?;
+        let (vk, sk) = self._create_key_pair(crypto_type_name, seed.as_ref())?;
         let did = match my_did_info.did {
             Some(ref did) => did.clone(),
             _ if my_did_info.cid == Some(true) =>
                 DidValue::new(&vk[..].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str())),
+            _ if EcdsaCryptoType::from_name(crypto_type_name).is_some() =>
+                DidValue::new(&EcdsaCryptoType::did_bytes(&vk).to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str())),
             _ =>
                 DidValue::new(&vk[0..16].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str()))
         };
 
-        let mut vk = vk[..].to_base58();
-        let sk = sk[..].to_base58();
+        let mut vk = vk.to_base58();
+        let sk = sk.to_base58();
 
         if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
             // Use suffix with crypto type name to store crypto type inside of vk
@@ -140,34 +352,87 @@ impl CryptoService {
     pub fn sign(&self, my_key: &Key, doc: &[u8]) -> IndyResult<Vec<u8>> {
         trace!("sign >>> my_key: {:?}, doc: {:?}", my_key, doc);
 
-        let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
+        let signature = self.signer(my_key, doc.to_vec())?()?;
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
-            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to sign message with unknown crypto: {}", crypto_type_name)));
+        trace!("sign <<< signature: {:?}", signature);
+
+        Ok(signature)
+    }
+
+    /// Returns signer of the document that doesn't use state of the service, so it can be run outside
+    /// of the commands thread. Key vaults aren't thread safe, so their signatures are made right away.
+    pub fn signer(&self, my_key: &Key, doc: Vec<u8>) -> IndyResult<Signer> {
+        trace!("signer >>> my_key: {:?}, doc: {:?}", my_key, doc);
+
+        if let Some(ref key_vault_name) = my_key.key_vault {
+            let key_vaults = self.key_vaults.borrow();
+            let key_vault = key_vaults.get(key_vault_name)
+                .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to sign message with unknown key vault: {}", key_vault_name)))?;
+
+            let signature = key_vault.sign(&my_key.signkey, &doc)?;
+
+            if !self.verify(&my_key.verkey, &doc, &signature)? {
+                return Err(err_msg(IndyErrorKind::InvalidState, format!("Key vault {} returned invalid signature", key_vault_name)));
+            }
+
+            return Ok(Box::new(move || Ok(signature)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let my_key = my_key.clone();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.as_str().from_base58()?.as_slice())?;
-        let signature = crypto_type.sign(&my_sk, doc)?[..].to_vec();
+        Ok(Box::new(move || CryptoService::_sign_with_secret_key(&my_key, &doc)))
+    }
 
-        trace!("sign <<< signature: {:?}", signature);
+    fn _sign_with_secret_key(my_key: &Key, doc: &[u8]) -> IndyResult<Vec<u8>> {
+        let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
 
-        Ok(signature)
+        if let Some(crypto_type) = EcdsaCryptoType::from_name(crypto_type_name) {
+            return crypto_type.sign(&my_key.signkey.from_base58()?, doc);
+        }
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to sign message with unknown crypto: {}", crypto_type_name)));
+        }
+
+        let my_sk = CryptoService::_secret_key(my_key)?;
+        Ok(ed25519_sign::sign(&my_sk, doc)?[..].to_vec())
     }
 
+    /// Returns `Ok(false)` for the signature rejected by the key and an error
+    /// with the detail if the key or the signature is malformed.
     pub fn verify(&self, their_vk: &str, msg: &[u8], signature: &[u8]) -> IndyResult<bool> {
         trace!("verify >>> their_vk: {:?}, msg: {:?}, signature: {:?}", their_vk, msg, signature);
 
         let (their_vk, crypto_type_name) = split_verkey(their_vk);
 
+        let their_vk = their_vk.from_base58()
+            .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, format!("Verkey is not a valid base58 string: {}", their_vk)))?;
+
+        if let Some(crypto_type) = EcdsaCryptoType::from_name(crypto_type_name) {
+            let valid = crypto_type.verify(&their_vk, msg, signature)?;
+
+            trace!("verify <<< valid: {:?}", valid);
+
+            return Ok(valid);
+        }
+
         if !self.crypto_types.contains_key(crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to verify message with unknown crypto: {}", crypto_type_name)));
         }
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let their_vk = ed25519_sign::PublicKey::from_slice(&their_vk.from_base58()?)?;
+        if their_vk.len() != ed25519_sign::SIG_PUBLICKEYBYTES {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Invalid verkey length: expected {} bytes, got {}", ed25519_sign::SIG_PUBLICKEYBYTES, their_vk.len())));
+        }
+
+        if signature.len() != ed25519_sign::SIGNATUREBYTES {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Invalid signature length: expected {} bytes, got {}", ed25519_sign::SIGNATUREBYTES, signature.len())));
+        }
+
+        let their_vk = ed25519_sign::PublicKey::from_slice(&their_vk)?;
         let signature = ed25519_sign::Signature::from_slice(&signature)?;
 
         let valid = crypto_type.verify(&their_vk, msg, &signature)?;
@@ -196,6 +461,26 @@ impl CryptoService {
     pub fn crypto_box(&self, my_key: &Key, their_vk: &str, doc: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>)> {
         trace!("crypto_box >>> my_key: {:?}, their_vk: {:?}, doc: {:?}", my_key, their_vk, doc);
 
+        let res = self._crypto_box(my_key, their_vk, doc, None)?;
+
+        trace!("crypto_box <<< encrypted_doc: {:?}, nonce: {:?}", res.0, res.1);
+
+        Ok(res)
+    }
+
+    /// Same as `crypto_box`, but uses nonce provided by the caller instead of generated one.
+    pub fn crypto_box_with_nonce(&self, my_key: &Key, their_vk: &str, doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+        trace!("crypto_box_with_nonce >>> my_key: {:?}, their_vk: {:?}, doc: {:?}, nonce: {:?}", my_key, their_vk, doc, nonce);
+
+        let (encrypted_doc, _) = self._crypto_box(my_key, their_vk, doc, Some(nonce))?;
+
+        trace!("crypto_box_with_nonce <<< encrypted_doc: {:?}", encrypted_doc);
+
+        Ok(encrypted_doc)
+    }
+
+    fn _crypto_box(&self, my_key: &Key, their_vk: &str, doc: &[u8], nonce: Option<&[u8]>) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+
         let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
 
         let (their_vk, their_crypto_type_name) = split_verkey(their_vk);
@@ -204,7 +489,10 @@ impl CryptoService {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to crypto_box message with unknown crypto: {}", crypto_type_name)));
         }
 
-        if !crypto_type_name.eq(their_crypto_type_name) {
+        // Key agreement key can be used directly as recipient of message encrypted with ed25519 key
+        let is_key_agreement_key = their_crypto_type_name == X25519_CRYPTO_TYPE && crypto_type_name == DEFAULT_CRYPTO_TYPE;
+
+        if !crypto_type_name.eq(their_crypto_type_name) && !is_key_agreement_key {
             // TODO: FIXME: Use dedicated error code
             return Err(err_msg(IndyErrorKind::UnknownCrypto,
                                format!("My key crypto type is incompatible with their key crypto type: {} {}",
@@ -214,15 +502,21 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(&crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
-        let nonce = crypto_type.gen_nonce();
+        let my_sk = CryptoService::_secret_key(my_key)?;
+        let nonce = match nonce {
+            Some(nonce) => ed25519_box::Nonce::from_slice(nonce)?,
+            None => self._gen_nonce(&**crypto_type)?
+        };
 
-        let encrypted_doc = crypto_type.crypto_box(&my_sk, &their_vk, doc, &nonce)?;
+        let encrypted_doc = if is_key_agreement_key {
+            let their_pk = ed25519_box::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+            crypto_type.crypto_box_to_x25519(&my_sk, &their_pk, doc, &nonce)?
+        } else {
+            let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+            crypto_type.crypto_box(&my_sk, &their_vk, doc, &nonce)?
+        };
         let nonce = nonce[..].to_vec();
 
-        trace!("crypto_box <<< encrypted_doc: {:?}, nonce: {:?}", encrypted_doc, nonce);
-
         Ok((encrypted_doc, nonce))
     }
 
@@ -238,7 +532,10 @@ impl CryptoService {
                                format!("Trying to crypto_box_open message with unknown crypto: {}", crypto_type_name)));
         }
 
-        if !crypto_type_name.eq(their_crypto_type_name) {
+        // Message can be encrypted by key agreement key of the sender for ed25519 key of the recipient
+        let is_key_agreement_key = their_crypto_type_name == X25519_CRYPTO_TYPE && crypto_type_name == DEFAULT_CRYPTO_TYPE;
+
+        if !crypto_type_name.eq(their_crypto_type_name) && !is_key_agreement_key {
             // TODO: FIXME: Use dedicated error code
             return Err(err_msg(IndyErrorKind::UnknownCrypto,
                                format!("My key crypto type is incompatible with their key crypto type: {} {}",
@@ -248,11 +545,16 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.from_base58()?.as_slice())?;
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+        let my_sk = CryptoService::_secret_key(my_key)?;
         let nonce = ed25519_box::Nonce::from_slice(&nonce)?;
 
-        let decrypted_doc = crypto_type.crypto_box_open(&my_sk, &their_vk, &doc, &nonce)?;
+        let decrypted_doc = if is_key_agreement_key {
+            let their_pk = ed25519_box::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+            crypto_type.crypto_box_open_from_x25519(&my_sk, &their_pk, &doc, &nonce)?
+        } else {
+            let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+            crypto_type.crypto_box_open(&my_sk, &their_vk, &doc, &nonce)?
+        };
 
         trace!("crypto_box_open <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -264,6 +566,18 @@ impl CryptoService {
 
         let (their_vk, crypto_type_name) = split_verkey(their_vk);
 
+        if crypto_type_name == X25519_CRYPTO_TYPE {
+            let crypto_type = self.crypto_types.get(DEFAULT_CRYPTO_TYPE).unwrap();
+
+            let their_pk = ed25519_box::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+
+            let encrypted_doc = crypto_type.crypto_box_seal_to_x25519(&their_pk, doc)?;
+
+            trace!("crypto_box_seal <<< encrypted_doc: {:?}", encrypted_doc);
+
+            return Ok(encrypted_doc);
+        }
+
         if !self.crypto_types.contains_key(&crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to encrypt sealed message with unknown crypto: {}", crypto_type_name)));
         }
@@ -292,7 +606,7 @@ impl CryptoService {
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
         let my_vk = ed25519_sign::PublicKey::from_slice(my_vk.from_base58()?.as_slice())?;
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
+        let my_sk = CryptoService::_secret_key(my_key)?;
 
         let decrypted_doc = crypto_type.crypto_box_seal_open(&my_vk, &my_sk, doc)?;
 
@@ -301,6 +615,37 @@ impl CryptoService {
         Ok(decrypted_doc)
     }
 
+    pub fn convert_to_key_agreement_key(&self, my_key: &Key) -> IndyResult<String> {
+        trace!("convert_to_key_agreement_key >>> my_key: {:?}", my_key);
+
+        let res = self.convert_key(&my_key.verkey)?;
+
+        trace!("convert_to_key_agreement_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    /// Converts ed25519 verkey to x25519 public key in the same form as key agreement keys: `<base58 key>:x25519`.
+    pub fn convert_key(&self, verkey: &str) -> IndyResult<String> {
+        trace!("convert_key >>> verkey: {:?}", verkey);
+
+        let (vk, crypto_type_name) = split_verkey(verkey);
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto,
+                               format!("Trying to convert key with unsupported crypto: {}", crypto_type_name)));
+        }
+
+        let vk = ed25519_sign::PublicKey::from_slice(vk.from_base58()?.as_slice())?;
+        let pk = ed25519_sign::vk_to_curve25519(&vk)?;
+
+        let res = format!("{}:{}", pk[..].to_base58(), X25519_CRYPTO_TYPE);
+
+        trace!("convert_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     pub fn convert_seed(&self, seed: Option<&str>) -> IndyResult<Option<ed25519_sign::Seed>> {
         trace!("convert_seed >>> seed: {:?}", secret!(seed));
 
@@ -347,6 +692,34 @@ impl CryptoService {
 
         let (vk, crypto_type_name) = split_verkey(vk);
 
+        if crypto_type_name == X25519_CRYPTO_TYPE {
+            let pk = vk.from_base58()
+                .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, format!("Key agreement key is not a valid base58 string: {}", vk)))?;
+
+            if pk.len() != ed25519_box::PUBLICKEYBYTES {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Invalid key agreement key length: expected {} bytes, got {}", ed25519_box::PUBLICKEYBYTES, pk.len())));
+            }
+
+            hardened::check_x25519_public_key(&pk)?;
+
+            trace!("validate_key <<<");
+
+            return Ok(());
+        }
+
+        if let Some(crypto_type) = EcdsaCryptoType::from_name(crypto_type_name) {
+            if vk.starts_with('~') {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Abbreviated verkey isn't supported for {} keys", crypto_type_name)));
+            }
+
+            crypto_type.validate_key(&vk.from_base58()?)?;
+
+            trace!("validate_key <<<");
+
+            return Ok(());
+        }
+
         if !self.crypto_types.contains_key(&crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to use key with unknown crypto: {}", crypto_type_name)));
         }
@@ -356,7 +729,15 @@ impl CryptoService {
         if vk.starts_with('~') {
             let _ = vk[1..].from_base58()?; // TODO: proper validate abbreviated verkey
         } else {
-            let vk = ed25519_sign::PublicKey::from_slice(vk.from_base58()?.as_slice())?;
+            let vk = vk.from_base58()
+                .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, format!("Verkey is not a valid base58 string: {}", vk)))?;
+
+            if vk.len() != ed25519_sign::SIG_PUBLICKEYBYTES {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Invalid verkey length: expected {} bytes, got {}", ed25519_sign::SIG_PUBLICKEYBYTES, vk.len())));
+            }
+
+            let vk = ed25519_sign::PublicKey::from_slice(vk.as_slice())?;
             crypto_type.validate_key(&vk)?;
         };
 
@@ -378,17 +759,22 @@ impl CryptoService {
                              plaintext: Vec<u8>,
                              aad: &str,
                              cek: &chacha20poly1305_ietf::Key)
-                             -> (String, String, String) {
+                             -> IndyResult<(String, String, String)> {
+        let iv = match self._external_random_bytes(chacha20poly1305_ietf::NONCEBYTES)? {
+            Some(bytes) => chacha20poly1305_ietf::Nonce::from_slice(&bytes)?,
+            None => chacha20poly1305_ietf::gen_nonce()
+        };
+
         //encrypt message with aad
-        let (ciphertext, iv, tag) = gen_nonce_and_encrypt_detached(
-            plaintext.as_slice(), aad.as_bytes(), &cek);
+        let (ciphertext, tag) = chacha20poly1305_ietf::encrypt_detached(
+            plaintext.as_slice(), aad.as_bytes(), &cek, &iv);
 
         //base64 url encode data
         let iv_encoded = base64::encode_urlsafe(&iv[..]);
         let ciphertext_encoded = base64::encode_urlsafe(ciphertext.as_slice());
         let tag_encoded = base64::encode_urlsafe(&tag[..]);
 
-        (ciphertext_encoded, iv_encoded, tag_encoded)
+        Ok((ciphertext_encoded, iv_encoded, tag_encoded))
     }
 
     /* ciphertext helper functions*/
@@ -445,15 +831,18 @@ impl CryptoService {
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+
     use crate::domain::crypto::did::MyDidInfo;
     use indy_utils::crypto::chacha20poly1305_ietf::gen_key;
+    use ursa::bls::Bls;
 
     use super::*;
 
     #[test]
     fn create_my_did_with_works_for_empty_info() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
         let my_did = service.create_my_did(&did_info);
         assert!(my_did.is_ok());
     }
@@ -463,7 +852,7 @@ mod tests {
         let service = CryptoService::new();
 
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
-        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
 
         let (my_did, _) = service.create_my_did(&did_info).unwrap();
         assert_eq!(did, my_did.did);
@@ -476,7 +865,7 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let crypto_type = Some("type".to_string());
 
-        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None };
+        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None, derivation_path: None, seed_encoding: None };
 
         assert!(service.create_my_did(&did_info).is_err());
     }
@@ -488,8 +877,8 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let seed = Some("00000000000000000000000000000My1".to_string());
 
-        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None };
-        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
+        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
 
         let (did_with_seed, _) = service.create_my_did(&did_info_with_seed).unwrap();
         let (did_without_seed, _) = service.create_my_did(&did_info_without_seed).unwrap();
@@ -536,7 +925,7 @@ mod tests {
     #[test]
     fn sign_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
 
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
@@ -544,6 +933,42 @@ mod tests {
         assert!(sig.is_ok());
     }
 
+    #[test]
+    fn create_bls_key_works() {
+        let service = CryptoService::new();
+        let key = service.create_bls_key(&BlsKeyInfo { seed: None }).unwrap();
+
+        let generator = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+        let ver_key = BlsVerKey::from_bytes(&key.blskey.from_base58().unwrap()).unwrap();
+        let pop = ProofOfPossession::from_bytes(&key.blskey_pop.from_base58().unwrap()).unwrap();
+
+        assert!(Bls::verify_proof_of_posession(&pop, &ver_key, &generator).unwrap());
+    }
+
+    #[test]
+    fn create_bls_key_works_for_seed() {
+        let service = CryptoService::new();
+        let key_info = BlsKeyInfo { seed: Some("00000000000000000000000000000My1".to_string()) };
+
+        let key_1 = service.create_bls_key(&key_info).unwrap();
+        let key_2 = service.create_bls_key(&key_info).unwrap();
+
+        assert_eq!(key_1.blskey, key_2.blskey);
+        assert_eq!(key_1.signkey, key_2.signkey);
+    }
+
+    #[test]
+    fn signer_works() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
+
+        let message = r#"message"#;
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+        let signer = service.signer(&my_key, message.as_bytes().to_vec()).unwrap();
+        let sig = std::thread::spawn(move || signer()).join().unwrap().unwrap();
+        assert_eq!(service.sign(&my_key, message.as_bytes()).unwrap(), sig);
+    }
+
     #[test]
     fn sign_works_for_invalid_signkey() {
         let service = CryptoService::new();
@@ -555,7 +980,7 @@ mod tests {
     #[test]
     fn sign_verify_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -566,7 +991,7 @@ mod tests {
     #[test]
     fn sign_verify_works_for_verkey_contained_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -576,10 +1001,30 @@ mod tests {
     }
 
 
+    #[test]
+    fn sign_verify_works_for_ecdsa_crypto_types() {
+        let service = CryptoService::new();
+        let message = r#"message"#;
+
+        for crypto_type in &["secp256k1", "p256"] {
+            let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: Some(crypto_type.to_string()), method_name: None, derivation_path: None, seed_encoding: None };
+            let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+            assert!(my_did.verkey.ends_with(&format!(":{}", crypto_type)));
+
+            service.validate_key(&my_did.verkey).unwrap();
+
+            let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+            assert!(service.verify(&my_did.verkey, message.as_bytes(), &signature).unwrap());
+
+            let signature = service.signer(&my_key, message.as_bytes().to_vec()).unwrap()().unwrap();
+            assert!(service.verify(&my_did.verkey, message.as_bytes(), &signature).unwrap());
+        }
+    }
+
     #[test]
     fn sign_verify_works_for_verkey_contained_invalid_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -590,7 +1035,7 @@ mod tests {
     #[test]
     fn verify_not_works_for_invalid_verkey() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -599,11 +1044,298 @@ mod tests {
         assert_eq!(false, valid);
     }
 
+    #[test]
+    fn verify_works_for_malformed_inputs() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
+        let message = r#"message"#;
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+
+        let res = service.verify(&my_did.verkey, message.as_bytes(), &signature[1..]);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        let res = service.verify(&my_did.verkey[..10], message.as_bytes(), &signature);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        let res = service.verify("CnEDk___MnmiHXEV1WFgbV___eYnPqs___TdcZaNhFVW", message.as_bytes(), &signature);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        let res = service.verify(&format!("{}:unknown_crypto", my_did.verkey), message.as_bytes(), &signature);
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+    }
+
+    struct TestKeyVault {
+        key: Key,
+    }
+
+    impl KeyVault for TestKeyVault {
+        fn get_verkey(&self, key_ref: &str) -> IndyResult<String> {
+            match key_ref {
+                "key_ref" => Ok(self.key.verkey.clone()),
+                _ => Err(err_msg(IndyErrorKind::WalletItemNotFound, "Unknown key reference"))
+            }
+        }
+
+        fn sign(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+            self.get_verkey(key_ref)?;
+            CryptoService::new().sign(&self.key, doc)
+        }
+    }
+
+    struct InvalidSignatureKeyVault {
+        key: Key,
+    }
+
+    impl KeyVault for InvalidSignatureKeyVault {
+        fn get_verkey(&self, _key_ref: &str) -> IndyResult<String> {
+            Ok(self.key.verkey.clone())
+        }
+
+        fn sign(&self, _key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+            let other_key = CryptoService::new().create_key(&KeyInfo { seed: None, crypto_type: None, key_vault: None, key_ref: None, derivation_path: None, seed_encoding: None })?;
+            CryptoService::new().sign(&other_key, doc)
+        }
+    }
+
+    #[test]
+    fn create_key_works_for_derivation_path() {
+        let service = CryptoService::new();
+
+        let key_info = KeyInfo {
+            seed: Some("000102030405060708090a0b0c0d0e0f".to_string()),
+            crypto_type: None,
+            key_vault: None,
+            key_ref: None,
+            derivation_path: Some("m/0'".to_string()),
+            seed_encoding: Some(MasterSeedEncoding::Hex),
+        };
+
+        let key = service.create_key(&key_info).unwrap();
+        assert_eq!(key.verkey, service.create_key(&key_info).unwrap().verkey);
+
+        // SLIP-0010 test vector 1 for ed25519, m/0'
+        let expected_vk = Vec::from_hex("8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c").unwrap();
+        assert_eq!(expected_vk.to_base58(), key.verkey);
+
+        let other_key = service.create_key(&KeyInfo { derivation_path: Some("m/1'".to_string()), ..key_info }).unwrap();
+        assert_ne!(key.verkey, other_key.verkey);
+    }
+
+    #[test]
+    fn create_key_works_for_derivation_path_without_seed() {
+        let key_info = KeyInfo { seed: None, crypto_type: None, key_vault: None, key_ref: None, derivation_path: Some("m/0'".to_string()), seed_encoding: Some(MasterSeedEncoding::Hex) };
+
+        let res = CryptoService::new().create_key(&key_info);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn create_key_works_for_derivation_path_without_seed_encoding() {
+        let key_info = KeyInfo {
+            seed: Some("000102030405060708090a0b0c0d0e0f".to_string()),
+            crypto_type: None,
+            key_vault: None,
+            key_ref: None,
+            derivation_path: Some("m/0'".to_string()),
+            seed_encoding: None,
+        };
+
+        let res = CryptoService::new().create_key(&key_info);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn create_key_works_for_derivation_path_and_ecdsa() {
+        let key_info = KeyInfo {
+            seed: Some("000102030405060708090a0b0c0d0e0f".to_string()),
+            crypto_type: Some("secp256k1".to_string()),
+            key_vault: None,
+            key_ref: None,
+            derivation_path: Some("m/0'".to_string()),
+            seed_encoding: Some(MasterSeedEncoding::Hex),
+        };
+
+        let res = CryptoService::new().create_key(&key_info);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    fn _key_vault_info(key_vault: &str) -> KeyInfo {
+        KeyInfo { seed: None, crypto_type: None, key_vault: Some(key_vault.to_string()), key_ref: Some("key_ref".to_string()), derivation_path: None, seed_encoding: None }
+    }
+
+    fn _register_test_key_vault(service: &CryptoService) -> Key {
+        let key = CryptoService::new().create_key(&KeyInfo { seed: None, crypto_type: None, key_vault: None, key_ref: None, derivation_path: None, seed_encoding: None }).unwrap();
+        service.register_key_vault("test", Box::new(TestKeyVault { key: key.clone() }));
+        key
+    }
+
+    #[test]
+    fn create_key_works_for_key_vault() {
+        let service = CryptoService::new();
+        let vault_key = _register_test_key_vault(&service);
+
+        let key = service.create_key(&_key_vault_info("test")).unwrap();
+
+        assert_eq!(vault_key.verkey, key.verkey);
+        assert_eq!("key_ref", key.signkey);
+        assert_eq!(Some("test".to_string()), key.key_vault);
+    }
+
+    #[test]
+    fn create_key_works_for_unknown_key_vault() {
+        let service = CryptoService::new();
+
+        let res = service.create_key(&_key_vault_info("unknown"));
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+    }
+
+    #[test]
+    fn sign_verify_works_for_key_vault() {
+        let service = CryptoService::new();
+        _register_test_key_vault(&service);
+
+        let key = service.create_key(&_key_vault_info("test")).unwrap();
+
+        let message = r#"message"#;
+        let signature = service.sign(&key, message.as_bytes()).unwrap();
+        assert!(service.verify(&key.verkey, message.as_bytes(), &signature).unwrap());
+    }
+
+    #[test]
+    fn sign_works_for_key_vault_returning_invalid_signature() {
+        let service = CryptoService::new();
+
+        let vault_key = CryptoService::new().create_key(&KeyInfo { seed: None, crypto_type: None, key_vault: None, key_ref: None, derivation_path: None, seed_encoding: None }).unwrap();
+        service.register_key_vault("test", Box::new(InvalidSignatureKeyVault { key: vault_key }));
+
+        let key = service.create_key(&_key_vault_info("test")).unwrap();
+
+        let res = service.sign(&key, "message".as_bytes());
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+
+    #[test]
+    fn crypto_box_fails_for_key_vault() {
+        let service = CryptoService::new();
+        let vault_key = _register_test_key_vault(&service);
+
+        let key = service.create_key(&_key_vault_info("test")).unwrap();
+
+        let res = service.crypto_box(&key, &vault_key.verkey, "message".as_bytes());
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    struct TestEntropySource {
+        byte: Cell<u8>,
+    }
+
+    impl TestEntropySource {
+        fn new(byte: u8) -> TestEntropySource {
+            TestEntropySource { byte: Cell::new(byte) }
+        }
+    }
+
+    impl EntropySource for TestEntropySource {
+        fn random_bytes(&self, len: usize) -> IndyResult<Vec<u8>> {
+            let byte = self.byte.get();
+            self.byte.set(byte.wrapping_add(1));
+            Ok((0..len).map(|i| byte.wrapping_add(i as u8)).collect())
+        }
+    }
+
+    fn _key_info() -> KeyInfo {
+        KeyInfo { seed: None, crypto_type: None, key_vault: None, key_ref: None, derivation_path: None, seed_encoding: None }
+    }
+
+    #[test]
+    fn create_key_works_for_entropy_source() {
+        let service = CryptoService::new();
+        service.register_entropy_source(Box::new(TestEntropySource::new(1))).unwrap();
+
+        let other_service = CryptoService::new();
+        other_service.register_entropy_source(Box::new(TestEntropySource::new(1))).unwrap();
+
+        let key = service.create_key(&_key_info()).unwrap();
+        let other_key = other_service.create_key(&_key_info()).unwrap();
+
+        assert_eq!(key.verkey, other_key.verkey);
+        assert_ne!(key.verkey, CryptoService::new().create_key(&_key_info()).unwrap().verkey);
+    }
+
+    #[test]
+    fn create_key_works_for_entropy_source_and_seed() {
+        let service = CryptoService::new();
+        service.register_entropy_source(Box::new(TestEntropySource::new(1))).unwrap();
+
+        let key_info = KeyInfo { seed: Some("00000000000000000000000000000My1".to_string()), crypto_type: None, key_vault: None, key_ref: None, derivation_path: None, seed_encoding: None };
+
+        let key = service.create_key(&key_info).unwrap();
+
+        assert_eq!(CryptoService::new().create_key(&key_info).unwrap().verkey, key.verkey);
+    }
+
+    #[test]
+    fn register_entropy_source_fails_for_stuck_source() {
+        struct StuckEntropySource {}
+
+        impl EntropySource for StuckEntropySource {
+            fn random_bytes(&self, len: usize) -> IndyResult<Vec<u8>> {
+                Ok(vec![0u8; len])
+            }
+        }
+
+        let service = CryptoService::new();
+
+        let res = service.register_entropy_source(Box::new(StuckEntropySource {}));
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+
+    #[test]
+    fn crypto_box_works_for_entropy_source() {
+        let service = CryptoService::new();
+        service.register_entropy_source(Box::new(TestEntropySource::new(7))).unwrap();
+
+        let my_key = service.create_key(&_key_info()).unwrap();
+        let their_key = CryptoService::new().create_key(&_key_info()).unwrap();
+
+        let (encrypted_message, nonce) = service.crypto_box(&my_key, &their_key.verkey, "message".as_bytes()).unwrap();
+        assert_ne!(TestEntropySource::new(7).random_bytes(ed25519_box::NONCEBYTES).unwrap(), nonce);
+
+        let other_service = CryptoService::new();
+        other_service.register_entropy_source(Box::new(TestEntropySource::new(7))).unwrap();
+
+        let other_key = other_service.create_key(&_key_info()).unwrap();
+        let (_, other_nonce) = other_service.crypto_box(&other_key, &their_key.verkey, "message".as_bytes()).unwrap();
+        assert_eq!(nonce, other_nonce);
+
+        let decrypted_message = service.crypto_box_open(&their_key, &my_key.verkey, &encrypted_message, &nonce).unwrap();
+        assert_eq!("message".as_bytes().to_vec(), decrypted_message);
+    }
+
+    #[test]
+    fn crypto_box_with_nonce_works() {
+        let service = CryptoService::new();
+
+        let my_key = service.create_key(&_key_info()).unwrap();
+        let their_key = service.create_key(&_key_info()).unwrap();
+        let nonce = vec![1; ed25519_box::NONCEBYTES];
+
+        let encrypted_message = service.crypto_box_with_nonce(&my_key, &their_key.verkey, "message".as_bytes(), &nonce).unwrap();
+        assert_eq!(encrypted_message, service.crypto_box_with_nonce(&my_key, &their_key.verkey, "message".as_bytes(), &nonce).unwrap());
+
+        let decrypted_message = service.crypto_box_open(&their_key, &my_key.verkey, &encrypted_message, &nonce).unwrap();
+        assert_eq!("message".as_bytes().to_vec(), decrypted_message);
+
+        let res = service.crypto_box_with_nonce(&my_key, &their_key.verkey, "message".as_bytes(), &[1, 2, 3]);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
     #[test]
     fn crypto_box_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let their_did = Did::new(their_did.did, their_did.verkey);
@@ -617,7 +1349,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -645,7 +1377,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -671,7 +1403,7 @@ mod tests {
     fn crypto_box_seal_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
         let (did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let did = Did::new(did.did, did.verkey);
         let encrypted_message = service.crypto_box_seal(&did.verkey, msg.as_bytes());
@@ -682,7 +1414,7 @@ mod tests {
     fn crypto_box_seal_and_crypto_box_seal_open_works() {
         let service = CryptoService::new();
         let msg = "some message".as_bytes();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
         let (did, key) = service.create_my_did(&did_info.clone()).unwrap();
         let encrypt_did = Did::new(did.did.clone(), did.verkey.clone());
         let encrypted_message = service.crypto_box_seal(&encrypt_did.verkey, msg).unwrap();
@@ -690,6 +1422,73 @@ mod tests {
         assert_eq!(msg, decrypted_message.as_slice());
     }
 
+    #[test]
+    fn convert_to_key_agreement_key_works() {
+        let service = CryptoService::new();
+        let key_info = KeyInfo { seed: Some("00000000000000000000000000000My1".to_string()), crypto_type: None, key_vault: None, key_ref: None, derivation_path: None, seed_encoding: None };
+        let key = service.create_key(&key_info).unwrap();
+
+        let key_agreement_key = service.convert_to_key_agreement_key(&key).unwrap();
+
+        let (pk, crypto_type_name) = split_verkey(&key_agreement_key);
+        assert_eq!(X25519_CRYPTO_TYPE, crypto_type_name);
+        assert_eq!(32, pk.from_base58().unwrap().len());
+        assert_eq!(key_agreement_key, service.convert_to_key_agreement_key(&key).unwrap());
+    }
+
+    #[test]
+    fn crypto_box_seal_works_for_key_agreement_key() {
+        let service = CryptoService::new();
+        let msg = "some message".as_bytes();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
+        let (_, key) = service.create_my_did(&did_info).unwrap();
+        let key_agreement_key = service.convert_to_key_agreement_key(&key).unwrap();
+
+        let encrypted_message = service.crypto_box_seal(&key_agreement_key, msg).unwrap();
+        let decrypted_message = service.crypto_box_seal_open(&key, &encrypted_message).unwrap();
+        assert_eq!(msg, decrypted_message.as_slice());
+    }
+
+    #[test]
+    fn crypto_box_and_crypto_box_open_works_for_key_agreement_key() {
+        let service = CryptoService::new();
+        let msg = "some message".as_bytes();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+        let (_, their_key) = service.create_my_did(&did_info).unwrap();
+        let their_key_agreement_key = service.convert_to_key_agreement_key(&their_key).unwrap();
+
+        let (encrypted_message, nonce) = service.crypto_box(&my_key, &their_key_agreement_key, msg).unwrap();
+        let decrypted_message = service.crypto_box_open(&their_key, &my_key.verkey, &encrypted_message, &nonce).unwrap();
+        assert_eq!(msg, decrypted_message.as_slice());
+    }
+
+    #[test]
+    fn crypto_box_and_crypto_box_open_works_for_sender_key_agreement_key() {
+        let service = CryptoService::new();
+        let msg = "some message".as_bytes();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+        let (_, their_key) = service.create_my_did(&did_info).unwrap();
+        let my_key_agreement_key = service.convert_to_key_agreement_key(&my_key).unwrap();
+
+        let (encrypted_message, nonce) = service.crypto_box(&my_key, &their_key.verkey, msg).unwrap();
+        let decrypted_message = service.crypto_box_open(&their_key, &my_key_agreement_key, &encrypted_message, &nonce).unwrap();
+        assert_eq!(msg, decrypted_message.as_slice());
+    }
+
+    #[test]
+    fn validate_key_works_for_key_agreement_key() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, derivation_path: None, seed_encoding: None };
+        let (_, key) = service.create_my_did(&did_info).unwrap();
+
+        service.validate_key(&service.convert_to_key_agreement_key(&key).unwrap()).unwrap();
+
+        let res = service.validate_key("invalid_base58:x25519");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
     #[test]
     pub fn test_encrypt_plaintext_and_decrypt_ciphertext_works() {
         let service: CryptoService = CryptoService::new();
@@ -700,7 +1499,7 @@ mod tests {
         let cek = gen_key();
 
         let (expected_ciphertext, iv_encoded, tag) = service
-            .encrypt_plaintext(plaintext.clone(), aad, &cek);
+            .encrypt_plaintext(plaintext.clone(), aad, &cek).unwrap();
 
 
         let expected_plaintext = service
@@ -720,7 +1519,7 @@ mod tests {
         let cek = gen_key();
 
         let (expected_ciphertext, iv_encoded, tag) = service
-            .encrypt_plaintext(plaintext.clone(), aad, &cek);
+            .encrypt_plaintext(plaintext.clone(), aad, &cek).unwrap();
 
 
         let expected_plaintext = service
@@ -739,7 +1538,7 @@ mod tests {
         let cek = gen_key();
 
         let (expected_ciphertext, _, tag) = service
-            .encrypt_plaintext(plaintext, aad, &cek);
+            .encrypt_plaintext(plaintext, aad, &cek).unwrap();
 
         //convert values to base64 encoded strings
         let bad_iv_input = "invalid_iv";
@@ -759,7 +1558,7 @@ mod tests {
         let cek = gen_key();
 
         let (_, iv_encoded, tag) = service
-            .encrypt_plaintext(plaintext, aad, &cek);
+            .encrypt_plaintext(plaintext, aad, &cek).unwrap();
 
         let bad_ciphertext = base64::encode_urlsafe("bad_ciphertext".as_bytes());
 
@@ -778,7 +1577,7 @@ mod tests {
         let cek = chacha20poly1305_ietf::gen_key();
 
         let (expected_ciphertext, iv_encoded, tag) = service
-            .encrypt_plaintext(plaintext, aad, &cek);
+            .encrypt_plaintext(plaintext, aad, &cek).unwrap();
 
         let bad_cek = gen_key();
 
@@ -797,7 +1596,7 @@ mod tests {
         let cek = gen_key();
 
         let (expected_ciphertext, iv_encoded, _) = service
-            .encrypt_plaintext(plaintext, aad, &cek);
+            .encrypt_plaintext(plaintext, aad, &cek).unwrap();
 
         let bad_tag = "bad_tag".to_string();
 
@@ -816,7 +1615,7 @@ mod tests {
         let cek = gen_key();
 
         let (expected_ciphertext, iv_encoded, tag) = service
-            .encrypt_plaintext(plaintext, aad, &cek);
+            .encrypt_plaintext(plaintext, aad, &cek).unwrap();
 
         let bad_aad = "bad aad";
 
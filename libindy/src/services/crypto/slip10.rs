@@ -0,0 +1,129 @@
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+
+use indy_api_types::errors::prelude::*;
+
+pub const MIN_SEED_BYTES: usize = 16;
+pub const MAX_SEED_BYTES: usize = 64;
+
+const KEY_BYTES: usize = 32;
+const ED25519_CURVE_KEY: &[u8] = b"ed25519 seed";
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// SLIP-0010 derivation of ed25519 secret key from the master seed by the path like `m/44'/1729'/0'`.
+/// The result is used as a seed of ed25519 key pair.
+///
+/// Ed25519 supports hardened derivation only, so every index of the path must be marked with `'` or `h`.
+pub fn derive_ed25519_key(seed: &[u8], path: &str) -> IndyResult<[u8; KEY_BYTES]> {
+    if seed.len() < MIN_SEED_BYTES || seed.len() > MAX_SEED_BYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Master seed must be from {} to {} bytes long", MIN_SEED_BYTES, MAX_SEED_BYTES)));
+    }
+
+    let indexes = parse_path(path)?;
+
+    let (mut key, mut chain_code) = _split(&_hmac_sha512(ED25519_CURVE_KEY, seed)?);
+
+    for index in indexes {
+        let mut data = Vec::with_capacity(1 + KEY_BYTES + 4);
+        data.push(0);
+        data.extend_from_slice(&key);
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let (child_key, child_chain_code) = _split(&_hmac_sha512(&chain_code, &data)?);
+        key = child_key;
+        chain_code = child_chain_code;
+    }
+
+    Ok(key)
+}
+
+/// Parses derivation path into the list of hardened indexes.
+pub fn parse_path(path: &str) -> IndyResult<Vec<u32>> {
+    let mut segments = path.split('/');
+
+    if segments.next() != Some("m") {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Derivation path must start with `m`: {}", path)));
+    }
+
+    segments
+        .map(|segment| {
+            let index = if segment.ends_with('\'') || segment.ends_with('h') {
+                &segment[..segment.len() - 1]
+            } else {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Only hardened derivation is supported for ed25519, index `{}` must end with `'`", segment)));
+            };
+
+            let index = index.parse::<u32>()
+                .ok()
+                .filter(|index| *index < HARDENED_OFFSET)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid derivation path index: {}", segment)))?;
+
+            Ok(index + HARDENED_OFFSET)
+        })
+        .collect()
+}
+
+fn _hmac_sha512(key: &[u8], data: &[u8]) -> IndyResult<Vec<u8>> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha512(), &key)?;
+    signer.update(data)?;
+    Ok(signer.sign_to_vec()?)
+}
+
+fn _split(hmac: &[u8]) -> ([u8; KEY_BYTES], [u8; KEY_BYTES]) {
+    let mut key = [0u8; KEY_BYTES];
+    let mut chain_code = [0u8; KEY_BYTES];
+    key.copy_from_slice(&hmac[..KEY_BYTES]);
+    chain_code.copy_from_slice(&hmac[KEY_BYTES..]);
+    (key, chain_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate hex;
+
+    use self::hex::FromHex;
+
+    fn _seed() -> Vec<u8> {
+        Vec::from_hex("000102030405060708090a0b0c0d0e0f").unwrap()
+    }
+
+    fn _derive(path: &str) -> String {
+        hex::encode(derive_ed25519_key(&_seed(), path).unwrap())
+    }
+
+    // SLIP-0010 test vector 1 for ed25519
+    #[test]
+    fn derive_ed25519_key_works() {
+        assert_eq!("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7", _derive("m"));
+        assert_eq!("68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3", _derive("m/0'"));
+        assert_eq!("b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2", _derive("m/0h/1h"));
+        assert_eq!("8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793", _derive("m/0'/1'/2'/2'/1000000000'"));
+    }
+
+    #[test]
+    fn derive_ed25519_key_works_for_invalid_seed_length() {
+        assert_kind!(IndyErrorKind::InvalidStructure, derive_ed25519_key(&[0u8; MIN_SEED_BYTES - 1], "m/0'"));
+        assert_kind!(IndyErrorKind::InvalidStructure, derive_ed25519_key(&[0u8; MAX_SEED_BYTES + 1], "m/0'"));
+    }
+
+    #[test]
+    fn parse_path_works() {
+        assert_eq!(Vec::<u32>::new(), parse_path("m").unwrap());
+        assert_eq!(vec![HARDENED_OFFSET + 44, HARDENED_OFFSET + 1729], parse_path("m/44'/1729h").unwrap());
+    }
+
+    #[test]
+    fn parse_path_works_for_invalid_path() {
+        assert_kind!(IndyErrorKind::InvalidStructure, parse_path(""));
+        assert_kind!(IndyErrorKind::InvalidStructure, parse_path("44'/0'"));
+        assert_kind!(IndyErrorKind::InvalidStructure, parse_path("m/44"));
+        assert_kind!(IndyErrorKind::InvalidStructure, parse_path("m/'"));
+        assert_kind!(IndyErrorKind::InvalidStructure, parse_path("m/2147483648'"));
+    }
+}
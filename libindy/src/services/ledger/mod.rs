@@ -1,29 +1,31 @@
 use hex::FromHex;
+use ursa::bn::BigNumber;
+use ursa::cl::CredentialPublicKey;
 use ursa::cl::RevocationRegistryDelta as CryproRevocationRegistryDelta;
 use serde::de::DeserializeOwned;
 use serde_json;
 use serde_json::Value;
 use log_derive::logfn;
 
-use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionV1, CredentialDefinitionId};
+use crate::domain::anoncreds::credential_definition::{CL_SIGNATURE_TYPE, CredentialDefinition, CredentialDefinitionData, CredentialDefinitionV1, CredentialDefinitionId};
 use crate::domain::anoncreds::revocation_registry::RevocationRegistry;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1, RevocationRegistryId};
 use crate::domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
 use crate::domain::anoncreds::schema::{Schema, SchemaV1, SchemaId};
-use crate::domain::crypto::did::DidValue;
+use crate::domain::crypto::did::{DidValue, ShortDidValue};
 use crate::domain::ledger::attrib::{AttribOperation, GetAttribOperation};
-use crate::domain::ledger::constants::{GET_VALIDATOR_INFO, POOL_RESTART, ROLE_REMOVE, STEWARD, ENDORSER, TRUSTEE, NETWORK_MONITOR, ROLES, txn_name_to_code};
-use crate::domain::ledger::cred_def::{CredDefOperation, GetCredDefOperation, GetCredDefReplyResult};
+use crate::domain::ledger::constants::{GET_VALIDATOR_INFO, NYM, POOL_RESTART, ROLE_REMOVE, STEWARD, ENDORSER, TRUSTEE, NETWORK_MONITOR, ROLES, txn_name_to_code};
+use crate::domain::ledger::cred_def::{CredDefCompatibilityReport, CredDefOperation, GetCredDefOperation, GetCredDefReplyResult};
 use crate::domain::ledger::ddo::GetDdoOperation;
 use crate::domain::ledger::node::{NodeOperation, NodeOperationData};
-use crate::domain::ledger::nym::{GetNymOperation, GetNymReplyResult, GetNymResultDataV0, NymData, NymOperation};
+use crate::domain::ledger::nym::{GetNymOperation, GetNymReplyResult, GetNymResultDataV0, NymData, NymHistoryEntry, NymOperation};
 use crate::domain::ledger::pool::{PoolConfigOperation, PoolRestartOperation, PoolUpgradeOperation, Schedule};
 use crate::domain::ledger::request::{TxnAuthrAgrmtAcceptanceData, Request};
 use crate::domain::ledger::response::{Message, Reply, ReplyType};
 use crate::domain::ledger::rev_reg::{GetRevocRegDeltaReplyResult, GetRevocRegReplyResult, GetRevRegDeltaOperation, GetRevRegOperation, RevRegEntryOperation};
 use crate::domain::ledger::rev_reg_def::{GetRevocRegDefReplyResult, GetRevRegDefOperation, RevRegDefOperation};
 use crate::domain::ledger::schema::{GetSchemaOperation, GetSchemaOperationData, GetSchemaReplyResult, SchemaOperation, SchemaOperationData};
-use crate::domain::ledger::txn::{GetTxnOperation, LedgerType};
+use crate::domain::ledger::txn::{GetTxnOperation, GetTxnReplyResult, LedgerType};
 use crate::domain::ledger::validator_info::GetValidatorInfoOperation;
 use crate::domain::ledger::auth_rule::*;
 use crate::domain::ledger::author_agreement::*;
@@ -32,6 +34,9 @@ use indy_utils::crypto::hash::hash as openssl_hash;
 
 pub mod merkletree;
 
+/// Minimal size of the primary public key modulus accepted for the Credential Definitions.
+const MIN_PRIMARY_KEY_BITS: i32 = 2048;
+
 macro_rules! build_result {
         ($operation:ident, $submitter_did:expr) => ({
             let operation = $operation::new();
@@ -120,6 +125,50 @@ impl LedgerService {
         Ok(res)
     }
 
+    #[logfn(Info)]
+    pub fn parse_get_nym_seq_no(&self, get_nym_response: &str) -> IndyResult<u64> {
+        let reply: Reply<GetNymReplyResult> = LedgerService::parse_response(get_nym_response)?;
+
+        match reply.result() {
+            GetNymReplyResult::GetNymReplyResultV0(res) => {
+                if res.data.is_none() {
+                    return Err(IndyError::from_msg(IndyErrorKind::LedgerItemNotFound, "Nym not found"));
+                }
+
+                res.seq_no
+                    .ok_or_else(|| IndyError::from_msg(IndyErrorKind::InvalidState, "Cannot get seqNo from GET_NYM response"))
+            }
+            GetNymReplyResult::GetNymReplyResultV1(res) => Ok(u64::from(res.txn_metadata.seq_no))
+        }
+    }
+
+    /// Returns NYM transaction from GET_TXN response data if it is written for the given DID.
+    #[logfn(Info)]
+    pub fn parse_nym_history_entry(&self, data: &serde_json::Value, did: &ShortDidValue) -> IndyResult<Option<NymHistoryEntry>> {
+        let txn = &data["txn"];
+
+        if txn["type"] != json!(NYM) || txn["data"]["dest"] != json!(did.0) {
+            return Ok(None);
+        }
+
+        let seq_no = data["txnMetadata"]["seqNo"].as_u64()
+            .ok_or_else(|| IndyError::from_msg(IndyErrorKind::InvalidState, "Cannot get seqNo from GET_TXN response"))?;
+
+        Ok(Some(NymHistoryEntry {
+            seq_no,
+            txn_time: data["txnMetadata"]["txnTime"].as_u64(),
+            from: txn["metadata"]["from"].as_str().map(String::from),
+            data: txn["data"].clone(),
+        }))
+    }
+
+    // Returns None if there is no transaction with requested sequence number on the ledger yet.
+    pub fn parse_get_txn_data(&self, get_txn_response: &str) -> IndyResult<Option<serde_json::Value>> {
+        let reply: Reply<GetTxnReplyResult> = LedgerService::parse_response(get_txn_response)?;
+
+        Ok(reply.result().data.filter(|data| !data.is_null()))
+    }
+
     #[logfn(Info)]
     pub fn build_get_ddo_request(&self, identifier: Option<&DidValue>, dest: &DidValue) -> IndyResult<String> {
         build_result!(GetDdoOperation, identifier, dest.to_short())
@@ -325,6 +374,71 @@ impl LedgerService {
         Ok(res)
     }
 
+    #[logfn(Info)]
+    pub fn check_cred_def_compatibility(&self, cred_def_id: &CredentialDefinitionId, get_cred_def_response: &str) -> IndyResult<CredDefCompatibilityReport> {
+        let message: Value = serde_json::from_str(get_cred_def_response)
+            .to_indy(IndyErrorKind::InvalidTransaction, "Response is invalid json")?;
+
+        if message["op"] != json!("REPLY") {
+            return Err(err_msg(IndyErrorKind::InvalidTransaction, format!("Transaction has been failed: {:?}", message["reason"])));
+        }
+
+        // Keys are checked on raw json to report artifacts the typed reply can't be parsed into
+        let result = &message["result"];
+        let (signature_type, public_keys) = if result["txn"].is_object() {
+            (&result["txn"]["data"]["type"], &result["txn"]["data"]["publicKeys"])
+        } else {
+            (&result["signature_type"], &result["data"])
+        };
+
+        if public_keys.is_null() {
+            return Err(err_msg(IndyErrorKind::LedgerItemNotFound, format!("Credential Definition {:?} not found", cred_def_id)));
+        }
+
+        let mut issues = Vec::new();
+
+        let signature_type = signature_type.as_str().map(String::from);
+        match signature_type.as_ref().map(String::as_str) {
+            Some(CL_SIGNATURE_TYPE) => {}
+            Some(type_) => issues.push(format!("Unsupported signature type \"{}\"", type_)),
+            None => issues.push(String::from("Signature type not specified")),
+        }
+
+        let mut primary_key_bits = None;
+        let mut revocation_supported = None;
+
+        match serde_json::from_value::<CredentialDefinitionData>(public_keys.clone()) {
+            Ok(data) => {
+                revocation_supported = Some(data.revocation.is_some());
+
+                primary_key_bits = serde_json::to_value(&data.primary).ok()
+                    .and_then(|primary| primary["n"].as_str().and_then(|n| BigNumber::from_dec(n).ok()))
+                    .and_then(|n| n.num_bits().ok());
+
+                match primary_key_bits {
+                    Some(bits) if bits < MIN_PRIMARY_KEY_BITS =>
+                        issues.push(format!("Primary public key size {} bits is less than required {} bits", bits, MIN_PRIMARY_KEY_BITS)),
+                    Some(_) => {}
+                    None => issues.push(String::from("Primary public key modulus can't be read")),
+                }
+
+                if let Err(err) = CredentialPublicKey::build_from_parts(&data.primary, data.revocation.as_ref()) {
+                    issues.push(format!("Public key can't be built: {}", err));
+                }
+            }
+            Err(err) => issues.push(format!("Public keys can't be parsed: {}", err)),
+        }
+
+        Ok(CredDefCompatibilityReport {
+            cred_def_id: cred_def_id.clone(),
+            compatible: issues.is_empty(),
+            signature_type,
+            primary_key_bits,
+            revocation_supported,
+            issues,
+        })
+    }
+
     #[logfn(Info)]
     pub fn parse_get_revoc_reg_def_response(&self, get_revoc_reg_def_response: &str) -> IndyResult<(String, String)> {
         let reply: Reply<GetRevocRegDefReplyResult> = LedgerService::parse_response(get_revoc_reg_def_response)?;
@@ -455,6 +569,16 @@ impl LedgerService {
         build_result!(GetAcceptanceMechanismOperation, identifier, timestamp, version.map(String::from))
     }
 
+    pub fn parse_get_txn_author_agreement_response(&self, response: &str) -> IndyResult<Option<TxnAuthorAgreementData>> {
+        let reply: Reply<GetTxnAuthorAgreementReplyResult> = LedgerService::parse_response(response)?;
+        Ok(reply.result().data)
+    }
+
+    pub fn parse_get_acceptance_mechanisms_response(&self, response: &str) -> IndyResult<Option<AcceptanceMechanismsData>> {
+        let reply: Reply<GetAcceptanceMechanismsReplyResult> = LedgerService::parse_response(response)?;
+        Ok(reply.result().data)
+    }
+
     #[logfn(Info)]
     pub fn parse_response<T>(response: &str) -> IndyResult<Reply<T>> where T: DeserializeOwned + ReplyType + ::std::fmt::Debug {
         let message: serde_json::Value = serde_json::from_str(&response)
@@ -1141,6 +1265,37 @@ mod tests {
             let request = ledger_service.build_get_txn_author_agreement_request(Some(&identifier()), Some(&data)).unwrap();
             check_request(&request, expected_result);
         }
+
+        #[test]
+        fn parse_get_txn_author_agreement_response_works() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": GET_TXN_AUTHR_AGRMT,
+                    "data": {"text": TEXT, "version": VERSION, "digest": "abc", "ratification_ts": 12345, "retirement_ts": 54321}
+                }
+            }).to_string();
+
+            let taa = ledger_service.parse_get_txn_author_agreement_response(&response).unwrap().unwrap();
+            assert_eq!(TEXT, taa.text);
+            assert_eq!(VERSION, taa.version);
+            assert!(!taa.is_retired(54320));
+            assert!(taa.is_retired(54321));
+        }
+
+        #[test]
+        fn parse_get_txn_author_agreement_response_works_for_not_set() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {"type": GET_TXN_AUTHR_AGRMT, "data": null}
+            }).to_string();
+
+            assert!(ledger_service.parse_get_txn_author_agreement_response(&response).unwrap().is_none());
+        }
     }
 
     mod acceptance_mechanism {
@@ -1233,6 +1388,148 @@ mod tests {
         }
     }
 
+    mod nym_history {
+        use super::*;
+
+        const DEST: &str = "VsKV7grR1BUE29mG2Fm2kX";
+        const SUBMITTER: &str = "V4SGRU86Z58d6TV7PBUe6f";
+
+        fn _get_txn_data(txn_type: &str, dest: &str) -> serde_json::Value {
+            json!({
+                "txn": {
+                    "type": txn_type,
+                    "data": {"dest": dest, "verkey": "~CoRER63DVYnWZtK8uAzNbx"},
+                    "metadata": {"from": SUBMITTER}
+                },
+                "txnMetadata": {"seqNo": 5, "txnTime": 1560000000}
+            })
+        }
+
+        fn _get_txn_response(txn_type: &str, dest: &str) -> String {
+            json!({
+                "op": "REPLY",
+                "result": {
+                    "type": GET_TXN,
+                    "seqNo": 5,
+                    "data": _get_txn_data(txn_type, dest)
+                }
+            }).to_string()
+        }
+
+        #[test]
+        fn parse_get_nym_seq_no_works() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": GET_NYM,
+                    "seqNo": 10,
+                    "data": json!({"dest": DEST, "identifier": SUBMITTER, "role": null, "verkey": "~CoRER63DVYnWZtK8uAzNbx"}).to_string()
+                }
+            }).to_string();
+
+            assert_eq!(10, ledger_service.parse_get_nym_seq_no(&response).unwrap());
+        }
+
+        #[test]
+        fn parse_get_nym_seq_no_works_for_not_found() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {"type": GET_NYM, "seqNo": null, "data": null}
+            }).to_string();
+
+            let res = ledger_service.parse_get_nym_seq_no(&response);
+            assert_kind!(IndyErrorKind::LedgerItemNotFound, res);
+        }
+
+        #[test]
+        fn parse_nym_history_entry_works() {
+            let ledger_service = LedgerService::new();
+
+            let entry = ledger_service.parse_nym_history_entry(&_get_txn_data(NYM, DEST), &ShortDidValue(DEST.to_string())).unwrap().unwrap();
+
+            assert_eq!(NymHistoryEntry {
+                seq_no: 5,
+                txn_time: Some(1560000000),
+                from: Some(SUBMITTER.to_string()),
+                data: json!({"dest": DEST, "verkey": "~CoRER63DVYnWZtK8uAzNbx"}),
+            }, entry);
+        }
+
+        #[test]
+        fn parse_nym_history_entry_works_for_other_txn() {
+            let ledger_service = LedgerService::new();
+            let did = ShortDidValue(DEST.to_string());
+
+            assert_eq!(None, ledger_service.parse_nym_history_entry(&_get_txn_data(NYM, SUBMITTER), &did).unwrap());
+            assert_eq!(None, ledger_service.parse_nym_history_entry(&_get_txn_data(ATTRIB, DEST), &did).unwrap());
+        }
+
+        #[test]
+        fn parse_get_txn_data_works() {
+            let ledger_service = LedgerService::new();
+
+            let data = ledger_service.parse_get_txn_data(&_get_txn_response(NYM, DEST)).unwrap().unwrap();
+            assert_eq!(json!(5), data["txnMetadata"]["seqNo"]);
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {"type": GET_TXN, "seqNo": null, "data": null}
+            }).to_string();
+            assert_eq!(None, ledger_service.parse_get_txn_data(&response).unwrap());
+        }
+    }
+
+    mod cred_def_compatibility {
+        use super::*;
+
+        const CRED_DEF_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag";
+
+        #[test]
+        fn check_cred_def_compatibility_works_for_incompatible() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": GET_CRED_DEF,
+                    "identifier": "NcYxiDXkpYi6ov5FcYDi1e",
+                    "ref": 1,
+                    "seqNo": 2,
+                    "signature_type": "CL2",
+                    "origin": "NcYxiDXkpYi6ov5FcYDi1e",
+                    "tag": "tag",
+                    "data": {
+                        "primary": {"n": "1", "s": "1", "r": {"name": "1"}, "rctxt": "1", "z": "1"}
+                    }
+                }
+            }).to_string();
+
+            let report = ledger_service.check_cred_def_compatibility(&CredentialDefinitionId(CRED_DEF_ID.to_string()), &response).unwrap();
+            assert!(!report.compatible);
+            assert_eq!(Some("CL2".to_string()), report.signature_type);
+            assert_eq!(Some(1), report.primary_key_bits);
+            assert_eq!(Some(false), report.revocation_supported);
+            assert_eq!(2, report.issues.len());
+        }
+
+        #[test]
+        fn check_cred_def_compatibility_works_for_not_found() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {"type": GET_CRED_DEF, "signature_type": "CL", "data": null}
+            }).to_string();
+
+            let res = ledger_service.check_cred_def_compatibility(&CredentialDefinitionId(CRED_DEF_ID.to_string()), &response);
+            assert_kind!(IndyErrorKind::LedgerItemNotFound, res);
+        }
+    }
+
     #[test]
     fn datetime_to_date() {
         assert_eq!(0, LedgerService::datetime_to_date_timestamp(0));
@@ -10,6 +10,11 @@ use crate::services::pool::merkle_tree_factory;
 use rust_base58::{FromBase58, ToBase58};
 use crate::services::pool::types::{CatchupReq, Message};
 
+/// Maximum number of transactions requested in a single catchup request.
+/// Received batches are persisted to the pool ledger cache one by one, so an interrupted
+/// catchup resumes from the last stored batch instead of downloading the whole ledger again.
+pub const CATCHUP_BATCH_SIZE: usize = 1000;
+
 pub enum CatchupProgress {
     ShouldBeStarted(
         Vec<u8>, //target_mt_root
@@ -27,7 +32,7 @@ pub fn build_catchup_req(merkle: &MerkleTree, target_mt_size: usize) -> IndyResu
         return Ok(None);
     }
     let seq_no_start = merkle.count() + 1;
-    let seq_no_end = target_mt_size;
+    let seq_no_end = ::std::cmp::min(target_mt_size, merkle.count() + CATCHUP_BATCH_SIZE);
 
     let cr = CatchupReq {
         ledgerId: 0,
@@ -160,4 +165,40 @@ pub fn check_cons_proofs(mt: &MerkleTree, cons_proofs: &Vec<String>, target_mt_r
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::Value;
+
+    #[test]
+    fn build_catchup_req_works_for_batches() {
+        let merkle = MerkleTree::default();
+
+        let (req_id, req_json) = build_catchup_req(&merkle, CATCHUP_BATCH_SIZE * 2 + 5).unwrap().unwrap();
+        let req: Value = serde_json::from_str(&req_json).unwrap();
+
+        assert_eq!(format!("1{}", CATCHUP_BATCH_SIZE), req_id);
+        assert_eq!(1, req["seqNoStart"].as_u64().unwrap());
+        assert_eq!(CATCHUP_BATCH_SIZE as u64, req["seqNoEnd"].as_u64().unwrap());
+        assert_eq!((CATCHUP_BATCH_SIZE * 2 + 5) as u64, req["catchupTill"].as_u64().unwrap());
+    }
+
+    #[test]
+    fn build_catchup_req_works_for_last_batch() {
+        let merkle = MerkleTree::default();
+
+        let (_, req_json) = build_catchup_req(&merkle, 5).unwrap().unwrap();
+        let req: Value = serde_json::from_str(&req_json).unwrap();
+
+        assert_eq!(5, req["seqNoEnd"].as_u64().unwrap());
+        assert_eq!(5, req["catchupTill"].as_u64().unwrap());
+    }
+
+    #[test]
+    fn build_catchup_req_works_for_synced_tree() {
+        let merkle = MerkleTree::default();
+        assert!(build_catchup_req(&merkle, 0).unwrap().is_none());
+    }
+}
@@ -197,6 +197,23 @@ impl RequestEvent {
             _ => "".to_string()
         }
     }
+
+    /// Returns key identifying read request regardless of its id and signatures.
+    /// Identical read requests in flight sent to the same nodes with the same timeout share the same reply.
+    pub fn get_read_key(&self, timeout: Option<i32>, nodes: Option<&str>) -> Option<String> {
+        match *self {
+            RequestEvent::CustomSingleRequest(ref msg, _, _, _) => {
+                let req_json = _get_req_json(msg).ok()?;
+                Some(json!({
+                    "operation": req_json["operation"],
+                    "protocolVersion": req_json["protocolVersion"],
+                    "timeout": timeout,
+                    "nodes": nodes,
+                }).to_string())
+            }
+            _ => None
+        }
+    }
 }
 
 impl Into<Option<RequestEvent>> for PoolEvent {
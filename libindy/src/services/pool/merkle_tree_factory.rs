@@ -40,6 +40,17 @@ pub fn create(pool_name: &str) -> IndyResult<MerkleTree> {
     }
 }
 
+/// Restores merkle tree from the genesis transactions only, ignoring the cached ledger.
+pub fn create_from_genesis(pool_name: &str) -> IndyResult<MerkleTree> {
+    let p = get_pool_stored_path_base(pool_name, false, pool_name, POOL_EXT);
+
+    if !p.exists() {
+        return Err(err_msg(IndyErrorKind::PoolNotCreated, format!("Pool is not created for name: {:?}", pool_name)));
+    }
+
+    _from_genesis(&p)
+}
+
 pub fn drop_cache(pool_name: &str) -> IndyResult<()> {
     let p = get_pool_stored_path(pool_name, false);
     if p.exists() {
@@ -11,28 +11,48 @@ use self::zmq::Socket;
 use std::{fs, io};
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::io::Write;
+use std::path::Path;
 use std::sync::Mutex;
 
+use regex::Regex;
 use serde_json;
 use serde::de::DeserializeOwned;
 
 use crate::api::ledger::{CustomFree, CustomTransactionParser};
 use crate::domain::{
-    pool::{PoolConfig, PoolOpenConfig},
-    ledger::response::{
-        Message,
-        Reply,
-        ResponseMetadata
+    crypto::did::DidValue,
+    pool::{ClientKeyPair, GenesisNode, PoolConfig, PoolOpenConfig, TrafficCaptureConfig},
+    ledger::{
+        constants::NODE,
+        node::Services,
+        response::{
+            Message,
+            Reply,
+            ResponseMetadata,
+            WriteReceipt
+        }
     }
 };
 use indy_api_types::errors::*;
+use crate::services::pool::networker::TransportSecurity;
 use crate::services::pool::pool::{Pool, ZMQPool};
+use crate::services::pool::traffic_capture::TrafficCapture;
+use crate::services::pool::types::{
+    KeyValueSimpleData,
+    KeyValueSimpleDataVerificationType,
+    KeyValuesInSP,
+    NodeTransactionV1,
+    ParsedSP
+};
 use crate::utils::environment;
 use crate::services::pool::events::{COMMAND_EXIT, COMMAND_CONNECT, COMMAND_REFRESH};
-use indy_api_types::{CommandHandle, PoolHandle};
+use indy_api_types::{CommandHandle, PoolHandle, INVALID_POOL_HANDLE};
 use indy_utils::{next_command_handle, next_pool_handle};
-use ursa::bls::VerKey;
+use indy_utils::crypto::base64;
+use rust_base58::FromBase58;
+use ursa::bls::{Generator, VerKey};
 
 mod catchup;
 mod commander;
@@ -42,17 +62,23 @@ mod networker;
 mod pool;
 mod request_handler;
 mod state_proof;
+mod traffic_capture;
 mod types;
 
+pub use self::request_handler::DEFAULT_GENERATOR;
+
 lazy_static! {
     static ref REGISTERED_SP_PARSERS: Mutex<HashMap<String, (CustomTransactionParser, CustomFree)>> = Mutex::new(HashMap::new());
 }
 
 type Nodes = HashMap<String, Option<VerKey>>;
 
+const CLIENT_KEY_PAIR_FILE: &str = "client_key_pair.json";
+
 pub struct PoolService {
     open_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
     pending_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
+    namespaces: RefCell<HashMap<String, PoolHandle>>,
 }
 
 impl PoolService {
@@ -60,6 +86,7 @@ impl PoolService {
         PoolService {
             open_pools: RefCell::new(HashMap::new()),
             pending_pools: RefCell::new(HashMap::new()),
+            namespaces: RefCell::new(HashMap::new()),
         }
     }
 
@@ -153,8 +180,23 @@ impl PoolService {
 
         let config = config.unwrap_or_default();
 
+        let pinned_node_keys = if config.pin_genesis_node_keys {
+            Some(pool::genesis_node_keys(name)?)
+        } else {
+            None
+        };
+
+        let client_key_pair = if config.persist_client_key_pair {
+            Some(self._persisted_client_key_pair(name)?)
+        } else {
+            config.client_key_pair.clone()
+        };
+
+        let transport_security = TransportSecurity::new(client_key_pair.as_ref(), pinned_node_keys)?;
+
         let pool_handle: PoolHandle = next_pool_handle();
         let mut new_pool = Pool::new(name, pool_handle, config);
+        new_pool.set_transport_security(transport_security);
 
         let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets(&format!("pool_{}", name));
 
@@ -177,6 +219,20 @@ impl PoolService {
     }
 
 
+    pub fn get_pool_name(&self, handle: PoolHandle) -> IndyResult<String> {
+        self.open_pools.try_borrow()?
+            .get(&handle)
+            .map(|pool| pool.pool.get_name().to_string())
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))
+    }
+
+    /// Verifies write receipt against BLS keys of the nodes of the opened pool (look at `parse_write_receipt`).
+    pub fn verify_write_receipt(&self, handle: PoolHandle, receipt: &WriteReceipt) -> IndyResult<bool> {
+        let nodes = pool::node_bls_keys(&self.get_pool_name(handle)?)?;
+
+        Ok(_verify_write_receipt(receipt, &nodes))
+    }
+
     pub fn send_tx(&self, handle: PoolHandle, msg: &str) -> IndyResult<CommandHandle> {
         self.send_action(handle, msg, None, None)
     }
@@ -224,6 +280,8 @@ impl PoolService {
             None => return Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))
         }
 
+        self.namespaces.try_borrow_mut()?.retain(|_, pool_handle| *pool_handle != handle);
+
         Ok(cmd_id)
     }
 
@@ -231,6 +289,58 @@ impl PoolService {
         self.send_action(handle, COMMAND_REFRESH, None, None)
     }
 
+    pub fn set_traffic_capture(&self, handle: PoolHandle, config: Option<TrafficCaptureConfig>) -> IndyResult<()> {
+        let pools = self.open_pools.try_borrow()?;
+
+        let pool = pools.get(&handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))?;
+
+        let traffic_capture = match config {
+            Some(config) => Some(TrafficCapture::new(config)?),
+            None => None
+        };
+
+        pool.pool.set_traffic_capture(traffic_capture);
+
+        Ok(())
+    }
+
+    /// Routes ledger operations on fully-qualified identifiers of the DID namespace to the pool.
+    pub fn set_namespace_pool(&self, namespace: &str, handle: PoolHandle) -> IndyResult<()> {
+        lazy_static! {
+            static ref REGEX_NAMESPACE: Regex = Regex::new("^[a-z0-9]+(:[a-z0-9]+)*$").unwrap();
+        }
+
+        if !REGEX_NAMESPACE.is_match(namespace) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid DID namespace: {}", namespace)));
+        }
+
+        if !self.open_pools.try_borrow()?.contains_key(&handle) {
+            return Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)));
+        }
+
+        self.namespaces.try_borrow_mut()?.insert(namespace.to_string(), handle);
+
+        Ok(())
+    }
+
+    pub fn remove_namespace_pool(&self, namespace: &str) -> IndyResult<()> {
+        self.namespaces.try_borrow_mut()?.remove(namespace);
+        Ok(())
+    }
+
+    /// Returns the given pool if it is set. Otherwise returns the pool registered for the namespace of the DID
+    /// or `INVALID_POOL_HANDLE` if the DID isn't fully-qualified or its namespace isn't registered.
+    pub fn pool_for_did(&self, handle: PoolHandle, did: &DidValue) -> PoolHandle {
+        if handle != INVALID_POOL_HANDLE {
+            return handle;
+        }
+
+        did.get_namespace()
+            .and_then(|namespace| self.namespaces.borrow().get(&namespace).cloned())
+            .unwrap_or(handle)
+    }
+
     fn _send_msg(&self, cmd_id: CommandHandle, msg: &str, socket: &Socket, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<()> {
         let mut buf = [0u8; 4];
         let mut buf_to = [0u8; 4];
@@ -252,7 +362,14 @@ impl PoolService {
             for entry in entries {
                 let dir_entry = if let Ok(dir_entry) = entry { dir_entry } else { continue; };
                 if let Some(pool_name) = dir_entry.path().file_name().and_then(|os_str| os_str.to_str()) {
-                    let json = json!({"pool":pool_name.to_owned()});
+                    let mut json = json!({"pool":pool_name.to_owned()});
+
+                    match PoolService::_read_client_key_pair(pool_name) {
+                        Ok(Some(client_key_pair)) => json["client_public_key"] = json!(client_key_pair.public_key),
+                        Ok(None) => {}
+                        Err(err) => warn!("Can't read client key pair of pool {}: {}", pool_name, err)
+                    }
+
                     pool.push(json);
                 }
             }
@@ -260,6 +377,137 @@ impl PoolService {
 
         Ok(pool)
     }
+
+    /// Returns CURVE key pair of the client stored in the pool config directory.
+    /// The key pair is generated and stored on the first call to the file readable by the owner only.
+    fn _persisted_client_key_pair(&self, name: &str) -> IndyResult<ClientKeyPair> {
+        if let Some(client_key_pair) = PoolService::_read_client_key_pair(name)? {
+            client_key_pair.decode()
+                .map_err(|err| err_msg(IndyErrorKind::InvalidState, format!("Invalid stored client key pair: {}", err)))?;
+
+            return Ok(client_key_pair);
+        }
+
+        let key_pair = zmq::CurveKeyPair::new()?;
+
+        let client_key_pair = ClientKeyPair {
+            public_key: zmq::z85_encode(&key_pair.public_key)
+                .to_indy(IndyErrorKind::InvalidState, "Can't encode client public key")?,
+            secret_key: zmq::z85_encode(&key_pair.secret_key)
+                .to_indy(IndyErrorKind::InvalidState, "Can't encode client secret key")?,
+        };
+
+        let mut path = environment::pool_path(name);
+        path.push(CLIENT_KEY_PAIR_FILE);
+
+        let mut f: fs::File = PoolService::_create_secret_file(path.as_path())
+            .to_indy(IndyErrorKind::IOError, "Can't create client key pair file")?;
+
+        f
+            .write_all({
+                serde_json::to_string(&client_key_pair)
+                    .to_indy(IndyErrorKind::InvalidState, "Can't serialize client key pair")?
+                    .as_bytes()
+            })
+            .to_indy(IndyErrorKind::IOError, "Can't write to client key pair file")?;
+
+        f
+            .flush()
+            .to_indy(IndyErrorKind::IOError, "Can't write to client key pair file")?;
+
+        Ok(client_key_pair)
+    }
+
+    fn _read_client_key_pair(name: &str) -> IndyResult<Option<ClientKeyPair>> {
+        let mut path = environment::pool_path(name);
+        path.push(CLIENT_KEY_PAIR_FILE);
+
+        let content = match fs::read_to_string(path.as_path()) {
+            Ok(content) => content,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).to_indy(IndyErrorKind::IOError, "Can't read client key pair file")
+        };
+
+        serde_json::from_str(&content)
+            .map(Some)
+            .to_indy(IndyErrorKind::InvalidState, "Invalid stored client key pair")
+    }
+
+    #[cfg(unix)]
+    fn _create_secret_file(path: &Path) -> io::Result<fs::File> {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)
+    }
+
+    #[cfg(not(unix))]
+    fn _create_secret_file(path: &Path) -> io::Result<fs::File> {
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+    }
+
+    pub fn build_genesis_txns(&self, nodes: &[GenesisNode]) -> IndyResult<String> {
+        let mut txns = Vec::with_capacity(nodes.len());
+
+        for (index, node) in nodes.iter().enumerate() {
+            let mut data = json!({
+                "alias": node.alias,
+                "client_ip": node.client_ip,
+                "client_port": node.client_port,
+                "node_ip": node.node_ip,
+                "node_port": node.node_port,
+                "services": match node.services {
+                    Some(ref services) => json!(services),
+                    None => json!([Services::VALIDATOR])
+                },
+            });
+
+            if let Some(ref blskey) = node.blskey {
+                data["blskey"] = json!(blskey);
+            }
+
+            if let Some(ref blskey_pop) = node.blskey_pop {
+                data["blskey_pop"] = json!(blskey_pop);
+            }
+
+            let mut txn_data = json!({
+                "data": data,
+                "dest": node.dest,
+            });
+
+            if let Some(ref verkey) = node.verkey {
+                txn_data["verkey"] = json!(verkey);
+            }
+
+            let txn = json!({
+                "reqSignature": {},
+                "txn": {
+                    "data": txn_data,
+                    "metadata": {
+                        "from": node.steward
+                    },
+                    "type": NODE
+                },
+                "txnMetadata": {
+                    "seqNo": index + 1
+                },
+                "ver": "1"
+            });
+
+            serde_json::from_value::<NodeTransactionV1>(txn.clone())
+                .to_indy(IndyErrorKind::InvalidStructure, format!("Genesis transaction for node {} doesn't match node transaction schema", node.alias))?;
+
+            txns.push(txn.to_string());
+        }
+
+        Ok(txns.join("\n"))
+    }
 }
 
 lazy_static! {
@@ -291,6 +539,94 @@ pub fn parse_response_metadata(response: &str) -> IndyResult<ResponseMetadata> {
     Ok(response_metadata)
 }
 
+/// Builds receipt of the written transaction from the write reply.
+pub fn parse_write_receipt(response: &str) -> IndyResult<WriteReceipt> {
+    trace!("indy::services::pool::parse_write_receipt << response: {}", response);
+    let message: Message<serde_json::Value> = serde_json::from_str(response)
+        .to_indy(IndyErrorKind::InvalidTransaction, "Cannot deserialize transaction Response")?;
+
+    let response_object: Reply<serde_json::Value> = _handle_response_message_type(message)?;
+    let mut response_result = response_object.result();
+
+    if response_result["ver"].as_str() != Some("1") {
+        return Err(err_msg(IndyErrorKind::InvalidTransaction, format!("Unsupported transaction response version: {:?}", response_result["ver"])));
+    }
+
+    let seq_no = response_result["txnMetadata"]["seqNo"].as_u64()
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Response doesn't contain written transaction"))?;
+
+    let ledger_id = response_result["ledgerId"].as_i64()
+        .or_else(|| response_result["multiSignature"]["value"]["ledger_id"].as_i64())
+        .and_then(|ledger_id| i32::try_from(ledger_id).ok())
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Response doesn't contain ledger id"))?;
+
+    let txn_time = response_result["txnMetadata"]["txnTime"].as_u64();
+    let digest = response_result["txn"]["metadata"]["digest"].as_str().map(String::from);
+
+    let data = json!({
+        "txn": response_result["txn"],
+        "txnMetadata": response_result["txnMetadata"],
+        "reqSignature": response_result["reqSignature"],
+        "ver": response_result["ver"],
+    });
+
+    let root_hash = response_result["rootHash"].as_str().map(String::from)
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Response doesn't contain root hash"))?;
+
+    let audit_path: Vec<String> = serde_json::from_value(response_result["auditPath"].take())
+        .to_indy(IndyErrorKind::InvalidStructure, "Response doesn't contain audit path")?;
+
+    // audit path of the write reply is built for the tree containing transactions up to the written one
+    let ledger_size = response_result["ledgerSize"].as_u64().unwrap_or(seq_no);
+
+    let multi_signature = response_result["multiSignature"].take();
+
+    if !multi_signature.is_object() {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Response isn't signed by the nodes multi-signature"));
+    }
+
+    let receipt = WriteReceipt { ledger_id, seq_no, txn_time, digest, data, root_hash, audit_path, ledger_size, multi_signature };
+
+    trace!("indy::services::pool::parse_write_receipt >> receipt: {:?}", receipt);
+
+    Ok(receipt)
+}
+
+/// Checks that the audit path of the receipt leads from the written transaction to the merkle tree root hash
+/// and that this root hash of the receipt ledger is signed by BLS multi-signature of the pool nodes.
+fn _verify_write_receipt(receipt: &WriteReceipt, nodes: &Nodes) -> bool {
+    trace!("indy::services::pool::_verify_write_receipt << receipt: {:?}", receipt);
+
+    if receipt.multi_signature["value"]["ledger_id"].as_i64() != Some(i64::from(receipt.ledger_id)) ||
+        receipt.multi_signature["value"]["txn_root_hash"].as_str() != Some(receipt.root_hash.as_str()) {
+        warn!("Multi-signature of write receipt isn't made for its ledger root hash");
+        return false;
+    }
+
+    if receipt.seq_no == 0 || receipt.seq_no > receipt.ledger_size {
+        warn!("Sequence number of write receipt is out of the ledger: {}", receipt.seq_no);
+        return false;
+    }
+
+    let generator = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+
+    let parsed_sp = ParsedSP {
+        proof_nodes: base64::encode(json!(receipt.audit_path).to_string().as_bytes()),
+        root_hash: receipt.root_hash.clone(),
+        kvs_to_verify: KeyValuesInSP::Simple(KeyValueSimpleData {
+            kvs: vec![(base64::encode(receipt.seq_no.to_string().as_bytes()), Some(receipt.data.to_string()))],
+            verification_type: KeyValueSimpleDataVerificationType::MerkleTree(receipt.ledger_size),
+        }),
+        multi_signature: receipt.multi_signature.clone(),
+    };
+
+    let res = state_proof::verify_parsed_sp(vec![parsed_sp], nodes, pool::_get_f(nodes.len()), &generator);
+
+    trace!("indy::services::pool::_verify_write_receipt >> res: {:?}", res);
+
+    res
+}
+
 pub fn get_last_signed_time(response: &str) -> Option<u64> {
     let c = parse_response_metadata(response);
     c.ok().and_then(|resp| resp.last_txn_time)
@@ -313,6 +649,7 @@ fn _parse_transaction_metadata_v0(message: &serde_json::Value) -> ResponseMetada
         txn_time: message["txnTime"].as_u64(),
         last_txn_time: message["state_proof"]["multi_signature"]["value"]["timestamp"].as_u64(),
         last_seq_no: None,
+        txn_id: None,
     }
 }
 
@@ -322,6 +659,7 @@ fn _parse_transaction_metadata_v1(message: &serde_json::Value) -> ResponseMetada
         txn_time: message["txnMetadata"]["txnTime"].as_u64(),
         last_txn_time: message["multiSignature"]["signedState"]["stateMetadata"]["timestamp"].as_u64(),
         last_seq_no: None,
+        txn_id: message["txnMetadata"]["txnId"].as_str().map(String::from),
     }
 }
 
@@ -444,6 +782,67 @@ mod tests {
             test::cleanup_storage("pool_service_delete_works_for_opened");
         }
 
+        #[test]
+        fn pool_service_persisted_client_key_pair_works() {
+            test::cleanup_storage("pool_service_persisted_client_key_pair_works");
+
+            let ps = PoolService::new();
+            let pool_name = "pool_service_persisted_client_key_pair_works";
+            fs::create_dir_all(environment::pool_path(pool_name)).unwrap();
+
+            let client_key_pair = ps._persisted_client_key_pair(pool_name).unwrap();
+            client_key_pair.decode().unwrap();
+
+            let stored_key_pair = ps._persisted_client_key_pair(pool_name).unwrap();
+            assert_eq!(client_key_pair.public_key, stored_key_pair.public_key);
+            assert_eq!(client_key_pair.secret_key, stored_key_pair.secret_key);
+
+            let pools = ps.list().unwrap();
+            let pool = pools.iter().find(|pool| pool["pool"] == pool_name).unwrap();
+            assert_eq!(client_key_pair.public_key, pool["client_public_key"].as_str().unwrap());
+
+            test::cleanup_storage("pool_service_persisted_client_key_pair_works");
+        }
+
+        #[test]
+        #[cfg(unix)]
+        fn pool_service_persisted_client_key_pair_works_for_owner_only_permissions() {
+            use std::os::unix::fs::PermissionsExt;
+
+            test::cleanup_storage("pool_service_persisted_client_key_pair_works_for_owner_only_permissions");
+
+            let ps = PoolService::new();
+            let pool_name = "pool_service_persisted_client_key_pair_works_for_owner_only_permissions";
+            fs::create_dir_all(environment::pool_path(pool_name)).unwrap();
+
+            ps._persisted_client_key_pair(pool_name).unwrap();
+
+            let mut path = environment::pool_path(pool_name);
+            path.push(CLIENT_KEY_PAIR_FILE);
+            assert_eq!(0o600, fs::metadata(path).unwrap().permissions().mode() & 0o777);
+
+            test::cleanup_storage("pool_service_persisted_client_key_pair_works_for_owner_only_permissions");
+        }
+
+        #[test]
+        fn pool_service_persisted_client_key_pair_works_for_corrupted_file() {
+            test::cleanup_storage("pool_service_persisted_client_key_pair_works_for_corrupted_file");
+
+            let ps = PoolService::new();
+            let pool_name = "pool_service_persisted_client_key_pair_works_for_corrupted_file";
+            fs::create_dir_all(environment::pool_path(pool_name)).unwrap();
+
+            let mut path = environment::pool_path(pool_name);
+            path.push(CLIENT_KEY_PAIR_FILE);
+            fs::write(path.as_path(), "corrupted").unwrap();
+
+            let res = ps._persisted_client_key_pair(pool_name);
+            assert_eq!(IndyErrorKind::InvalidState, res.unwrap_err().kind());
+            assert_eq!("corrupted", fs::read_to_string(path).unwrap());
+
+            test::cleanup_storage("pool_service_persisted_client_key_pair_works_for_corrupted_file");
+        }
+
         #[test]
         fn pool_send_tx_works() {
             test::cleanup_storage("pool_send_tx_works");
@@ -513,6 +912,61 @@ mod tests {
             assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
         }
 
+        #[test]
+        fn pool_set_traffic_capture_works_for_invalid_handle() {
+            test::cleanup_storage("pool_set_traffic_capture_works_for_invalid_handle");
+            let ps = PoolService::new();
+            let res = ps.set_traffic_capture(INVALID_POOL_HANDLE, None);
+            assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
+        }
+
+        #[test]
+        fn pool_set_namespace_pool_works() {
+            test::cleanup_storage("pool_set_namespace_pool_works");
+
+            let (send_cmd_sock, _recv_cmd_sock) = pool_create_pair_of_sockets("pool_set_namespace_pool_works");
+            let pool_id = next_pool_handle();
+            let pool = Pool::new("pool_set_namespace_pool_works", pool_id, PoolOpenConfig::default());
+            let ps = PoolService::new();
+            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+
+            ps.set_namespace_pool("indicio:testnet", pool_id).unwrap();
+
+            let did = DidValue("did:indicio:testnet:NcYxiDXkpYi6ov5FcYDi1e".to_string());
+            assert_eq!(pool_id, ps.pool_for_did(INVALID_POOL_HANDLE, &did));
+
+            // Explicitly passed pool isn't overridden
+            let other_pool_id = next_pool_handle();
+            assert_eq!(other_pool_id, ps.pool_for_did(other_pool_id, &did));
+
+            let did = DidValue("did:sov:NcYxiDXkpYi6ov5FcYDi1e".to_string());
+            assert_eq!(INVALID_POOL_HANDLE, ps.pool_for_did(INVALID_POOL_HANDLE, &did));
+
+            let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
+            assert_eq!(INVALID_POOL_HANDLE, ps.pool_for_did(INVALID_POOL_HANDLE, &did));
+
+            ps.remove_namespace_pool("indicio:testnet").unwrap();
+
+            let did = DidValue("did:indicio:testnet:NcYxiDXkpYi6ov5FcYDi1e".to_string());
+            assert_eq!(INVALID_POOL_HANDLE, ps.pool_for_did(INVALID_POOL_HANDLE, &did));
+        }
+
+        #[test]
+        fn pool_set_namespace_pool_works_for_invalid_handle() {
+            test::cleanup_storage("pool_set_namespace_pool_works_for_invalid_handle");
+            let ps = PoolService::new();
+            let res = ps.set_namespace_pool("sov", INVALID_POOL_HANDLE);
+            assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
+        }
+
+        #[test]
+        fn pool_set_namespace_pool_works_for_invalid_namespace() {
+            test::cleanup_storage("pool_set_namespace_pool_works_for_invalid_namespace");
+            let ps = PoolService::new();
+            let res = ps.set_namespace_pool("indicio testnet", INVALID_POOL_HANDLE);
+            assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        }
+
         #[test]
         fn pool_register_sp_parser_works() {
             test::cleanup_storage("pool_register_sp_parser_works");
@@ -565,6 +1019,25 @@ mod tests {
             let res = ps.add_open_pool(INVALID_POOL_HANDLE);
             assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
         }
+
+        #[test]
+        pub fn pool_get_pool_name_works() {
+            test::cleanup_storage("pool_get_pool_name_works");
+            let ps = PoolService::new();
+            let (send_cmd_sock, _recv_cmd_sock) = pool_create_pair_of_sockets("pool_get_pool_name_works");
+            let pool_id = next_pool_handle();
+            let pool = Pool::new("pool_get_pool_name_works", pool_id, PoolOpenConfig::default());
+            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+            assert_eq!("pool_get_pool_name_works", ps.get_pool_name(pool_id).unwrap());
+        }
+
+        #[test]
+        pub fn pool_get_pool_name_works_for_invalid_handle() {
+            test::cleanup_storage("pool_get_pool_name_works_for_invalid_handle");
+            let ps = PoolService::new();
+            let res = ps.get_pool_name(INVALID_POOL_HANDLE);
+            assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
+        }
     }
 
     #[test]
@@ -604,6 +1077,255 @@ mod tests {
         test::cleanup_storage("pool_drop_works_for_after_close");
     }
 
+    mod genesis_txns {
+        use super::*;
+
+        fn _node(alias: &str, dest: &str) -> GenesisNode {
+            serde_json::from_value(json!({
+                "alias": alias,
+                "dest": dest,
+                "steward": "Th7MpTaRZVRYnPiabds81Y",
+                "node_ip": "10.0.0.2",
+                "node_port": 9701,
+                "client_ip": "10.0.0.2",
+                "client_port": 9702,
+                "blskey": "4N8aUNHSgjQVgkpm8nhNEfDf6txHznoYREg9kirmJrkivgL4oSEimFF6nsQ6M41QvhM2Z33nves5vfSn9n1UwNFJBYtWVnHYMATn76vLuL3zU88KyeAYcHfsih3He6UHcXDxcaecHVz6jhCYz1P2UZn2bDVruL5wXpehgBfBaLKm3Ba",
+            })).unwrap()
+        }
+
+        #[test]
+        fn pool_service_build_genesis_txns_works() {
+            let ps = PoolService::new();
+
+            let nodes = vec![
+                _node("Node1", "Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv"),
+                _node("Node2", "8ECVSk179mjsjKRLWiQtssMLgp6EPhWXtaYyStWPSGAb"),
+            ];
+
+            let genesis = ps.build_genesis_txns(&nodes).unwrap();
+
+            let txns: Vec<NodeTransactionV1> = genesis.lines()
+                .map(|txn| serde_json::from_str(txn).unwrap())
+                .collect();
+
+            assert_eq!(2, txns.len());
+
+            assert_eq!(NODE, txns[0].txn.txn_type);
+            assert_eq!(Some(1), txns[0].txn_metadata.seq_no);
+            assert_eq!("Node1", txns[0].txn.data.data.alias);
+            assert_eq!("Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv", txns[0].txn.data.dest);
+            assert_eq!("Th7MpTaRZVRYnPiabds81Y", txns[0].txn.metadata.from);
+            assert_eq!(Some(9701), txns[0].txn.data.data.node_port);
+            assert_eq!(Some(vec!["VALIDATOR".to_string()]), txns[0].txn.data.data.services);
+            assert_eq!(None, txns[0].txn.data.data.blskey_pop);
+
+            assert_eq!(Some(2), txns[1].txn_metadata.seq_no);
+            assert_eq!("Node2", txns[1].txn.data.data.alias);
+        }
+
+        #[test]
+        fn pool_service_build_genesis_txns_works_for_merkle_tree() {
+            test::cleanup_storage("pool_service_build_genesis_txns_works_for_merkle_tree");
+
+            let ps = PoolService::new();
+
+            let nodes = vec![
+                _node("Node1", "Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv"),
+                _node("Node2", "8ECVSk179mjsjKRLWiQtssMLgp6EPhWXtaYyStWPSGAb"),
+            ];
+
+            let genesis = ps.build_genesis_txns(&nodes).unwrap();
+
+            let mut path = environment::tmp_path();
+            fs::create_dir_all(path.as_path()).unwrap();
+            path.push("pool_service_build_genesis_txns_works_for_merkle_tree.txn");
+            fs::write(path.as_path(), genesis).unwrap();
+
+            let merkle_tree = merkle_tree_factory::from_file(path.to_str().unwrap()).unwrap();
+            assert_eq!(2, merkle_tree.count());
+
+            test::cleanup_storage("pool_service_build_genesis_txns_works_for_merkle_tree");
+        }
+    }
+
+    mod response_metadata {
+        use super::*;
+
+        #[test]
+        fn parse_response_metadata_works_for_write_reply_v1() {
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "ver": "1",
+                    "txn": {"type": "101", "data": {}},
+                    "txnMetadata": {
+                        "seqNo": 15,
+                        "txnTime": 1577836800,
+                        "txnId": "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0"
+                    }
+                }
+            }).to_string();
+
+            let metadata = parse_response_metadata(&response).unwrap();
+            assert_eq!(Some(15), metadata.seq_no);
+            assert_eq!(Some(1577836800), metadata.txn_time);
+            assert_eq!(Some("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()), metadata.txn_id);
+            assert_eq!(None, metadata.last_txn_time);
+        }
+
+        #[test]
+        fn parse_response_metadata_works_for_write_reply_v0() {
+            let response = json!({
+                "op": "REPLY",
+                "result": {"seqNo": 15, "txnTime": 1577836800}
+            }).to_string();
+
+            let metadata = parse_response_metadata(&response).unwrap();
+            assert_eq!(Some(15), metadata.seq_no);
+            assert_eq!(Some(1577836800), metadata.txn_time);
+            assert_eq!(None, metadata.txn_id);
+        }
+
+        #[test]
+        fn parse_response_metadata_works_for_reject() {
+            let response = json!({"op": "REJECT", "reqId": 1, "reason": "some reason"}).to_string();
+
+            let res = parse_response_metadata(&response);
+            assert_eq!(IndyErrorKind::InvalidTransaction, res.unwrap_err().kind());
+        }
+    }
+
+    mod write_receipt {
+        use super::*;
+        use crate::domain::ledger::txn::LedgerType;
+        use indy_utils::crypto::hash::Hash;
+        use rust_base58::ToBase58;
+        use ursa::bls::{Bls, MultiSignature, SignKey};
+
+        fn _txn_data(seq_no: u64) -> serde_json::Value {
+            json!({
+                "txn": {"type": "1", "data": {"dest": "VsKV7grR1BUE29mG2Fm2kX"}, "metadata": {"digest": "digest"}},
+                "txnMetadata": {"seqNo": seq_no, "txnTime": 1577836800},
+                "reqSignature": {},
+                "ver": "1"
+            })
+        }
+
+        fn _write_reply(data: &serde_json::Value, ledger_id: Option<i32>) -> String {
+            let mut result = data.clone();
+            result["rootHash"] = json!("7RJ5bkAKRy2CCvarRij2jiHC16SVPjHcrpVdNsboiQGv");
+            result["auditPath"] = json!(["Dy3bKxtJpm3rxHPw7UZaiNfVcTG7aYqmCPoEWBLx8TSh"]);
+            if let Some(ledger_id) = ledger_id {
+                result["multiSignature"] = json!({"value": {"ledger_id": ledger_id}});
+            }
+
+            json!({"op": "REPLY", "result": result}).to_string()
+        }
+
+        #[test]
+        fn parse_write_receipt_works() {
+            let data = _txn_data(2);
+
+            let receipt = parse_write_receipt(&_write_reply(&data, Some(LedgerType::CONFIG.to_id()))).unwrap();
+            assert_eq!(LedgerType::CONFIG.to_id(), receipt.ledger_id);
+            assert_eq!(2, receipt.seq_no);
+            assert_eq!(Some(1577836800), receipt.txn_time);
+            assert_eq!(Some("digest".to_string()), receipt.digest);
+            assert_eq!(data, receipt.data);
+        }
+
+        #[test]
+        fn parse_write_receipt_works_for_missed_ledger_id() {
+            let res = parse_write_receipt(&_write_reply(&_txn_data(2), None));
+            assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        }
+
+        #[test]
+        fn parse_write_receipt_works_for_unsigned_reply() {
+            let mut response: serde_json::Value = serde_json::from_str(&_write_reply(&_txn_data(2), Some(LedgerType::CONFIG.to_id()))).unwrap();
+            response["result"]["ledgerId"] = json!(LedgerType::CONFIG.to_id());
+            response["result"].as_object_mut().unwrap().remove("multiSignature");
+
+            let res = parse_write_receipt(&response.to_string());
+            assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        }
+
+        #[test]
+        fn parse_write_receipt_works_for_read_reply() {
+            let response = json!({
+                "op": "REPLY",
+                "result": {"type": "105", "data": null, "seqNo": null, "txnTime": null}
+            }).to_string();
+
+            let res = parse_write_receipt(&response);
+            assert_eq!(IndyErrorKind::InvalidTransaction, res.unwrap_err().kind());
+        }
+
+        fn _leaf(data: &serde_json::Value) -> Vec<u8> {
+            Hash::hash_leaf(&rmp_serde::to_vec(data).unwrap()).unwrap()
+        }
+
+        // receipt of the second transaction of the ledger signed by the only node
+        fn _signed_receipt() -> (WriteReceipt, Nodes) {
+            let generator = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+            let sign_key = SignKey::new(None).unwrap();
+            let ver_key = VerKey::new(&generator, &sign_key).unwrap();
+
+            let audit_path = _leaf(&_txn_data(1));
+            let root_hash = Hash::hash_nodes(&audit_path, &_leaf(&_txn_data(2))).unwrap().to_base58();
+
+            let value = json!({"ledger_id": LedgerType::DOMAIN.to_id(), "txn_root_hash": root_hash, "timestamp": 1577836800});
+            let signature = Bls::sign(&rmp_serde::to_vec_named(&value).unwrap(), &sign_key).unwrap();
+            let signature = MultiSignature::new(&[&signature]).unwrap();
+
+            let mut result = _txn_data(2);
+            result["rootHash"] = json!(root_hash);
+            result["auditPath"] = json!([audit_path.to_base58()]);
+            result["multiSignature"] = json!({"value": value, "signature": signature.as_bytes().to_base58(), "participants": ["Node1"]});
+
+            let receipt = parse_write_receipt(&json!({"op": "REPLY", "result": result}).to_string()).unwrap();
+
+            let mut nodes = Nodes::new();
+            nodes.insert("Node1".to_string(), Some(ver_key));
+
+            (receipt, nodes)
+        }
+
+        #[test]
+        fn verify_write_receipt_works() {
+            let (receipt, nodes) = _signed_receipt();
+            assert!(_verify_write_receipt(&receipt, &nodes));
+        }
+
+        #[test]
+        fn verify_write_receipt_works_for_modified_txn() {
+            let (mut receipt, nodes) = _signed_receipt();
+            receipt.data["txn"]["data"]["dest"] = json!("WTJ1xmQViyFb67WAuvPnJP");
+            assert!(!_verify_write_receipt(&receipt, &nodes));
+        }
+
+        #[test]
+        fn verify_write_receipt_works_for_other_root_hash() {
+            let (mut receipt, nodes) = _signed_receipt();
+            receipt.root_hash = _leaf(&_txn_data(2)).to_base58();
+            receipt.audit_path = vec![];
+            receipt.ledger_size = 1;
+            receipt.seq_no = 1;
+            assert!(!_verify_write_receipt(&receipt, &nodes));
+        }
+
+        #[test]
+        fn verify_write_receipt_works_for_other_nodes() {
+            let (receipt, _) = _signed_receipt();
+
+            let generator = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+            let mut nodes = Nodes::new();
+            nodes.insert("Node1".to_string(), Some(VerKey::new(&generator, &SignKey::new(None).unwrap()).unwrap()));
+
+            assert!(!_verify_write_receipt(&receipt, &nodes));
+        }
+    }
+
     pub mod nodes_emulator {
         use rust_base58::{ToBase58, FromBase58};
         use indy_utils::crypto::ed25519_sign;
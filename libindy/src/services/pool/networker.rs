@@ -6,7 +6,9 @@ use rand::prelude::SliceRandom;
 use time::Tm;
 
 use indy_api_types::errors::prelude::*;
+use crate::domain::pool::{ClientKeyPair, CURVE_KEY_LEN};
 use crate::services::pool::events::*;
+use crate::services::pool::traffic_capture::{self, Direction, SharedTrafficCapture};
 use crate::services::pool::types::*;
 use indy_utils::sequence;
 use indy_utils::crypto::base64;
@@ -16,12 +18,81 @@ use super::time::Duration;
 use super::zmq::PollItem;
 use super::zmq::Socket as ZSocket;
 
+/// Controls how timeout grows for each next node a read request is resent to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    pub backoff_factor: f64,
+    pub max_timeout: Option<i64>,
+}
+
+impl RetryPolicy {
+    pub fn timeout(&self, timeout: i64, attempt: usize) -> i64 {
+        let timeout = (timeout as f64 * self.backoff_factor.powi(attempt as i32)).min(::std::i64::MAX as f64) as i64;
+        self.max_timeout.map(|max_timeout| ::std::cmp::min(timeout, max_timeout)).unwrap_or(timeout)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            backoff_factor: 1.0,
+            max_timeout: None,
+        }
+    }
+}
+
+/// CURVE settings of the connections to the pool nodes.
+#[derive(Clone, Debug, Default)]
+pub struct TransportSecurity {
+    /// Decoded public and secret keys of the client.
+    /// Ephemeral key pair is generated for every connection if not set.
+    client_key_pair: Option<([u8; CURVE_KEY_LEN], [u8; CURVE_KEY_LEN])>,
+    /// Transport keys of the nodes by alias pinned from the genesis transactions.
+    pinned_node_keys: Option<HashMap<String, Vec<u8>>>,
+}
+
+impl TransportSecurity {
+    pub fn new(client_key_pair: Option<&ClientKeyPair>, pinned_node_keys: Option<HashMap<String, Vec<u8>>>) -> IndyResult<TransportSecurity> {
+        let client_key_pair = match client_key_pair {
+            Some(client_key_pair) => Some(client_key_pair.decode()
+                .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?),
+            None => None
+        };
+
+        Ok(TransportSecurity { client_key_pair, pinned_node_keys })
+    }
+
+    fn key_pair(&self) -> Option<zmq::CurveKeyPair> {
+        self.client_key_pair
+            .map(|(public_key, secret_key)| zmq::CurveKeyPair { public_key, secret_key })
+    }
+
+    /// Rejects nodes whose transport keys don't match the pinned ones.
+    /// Nodes added to the pool after the genesis are kept as there is nothing to compare with.
+    fn filter_nodes(&self, nodes: Vec<RemoteNode>) -> Vec<RemoteNode> {
+        match self.pinned_node_keys {
+            Some(ref pinned_node_keys) => nodes.into_iter()
+                .filter(|node| match pinned_node_keys.get(&node.name) {
+                    Some(key) if *key != node.public_key => {
+                        warn!("Node {} is rejected: its transport key doesn't match the key from the genesis transactions", node.name);
+                        false
+                    }
+                    _ => true
+                })
+                .collect(),
+            None => nodes
+        }
+    }
+}
+
 pub trait Networker {
-    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>) -> Self;
+    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, retry_policy: RetryPolicy) -> Self;
     fn fetch_events(&self, poll_items: &[PollItem]) -> Vec<PoolEvent>;
     fn process_event(&mut self, pe: Option<NetworkerEvent>) -> Option<RequestEvent>;
     fn get_timeout(&self) -> ((String, String), i64);
     fn get_poll_items(&self) -> Vec<PollItem>;
+    fn set_traffic_capture(&mut self, _traffic_capture: SharedTrafficCapture) {}
+    fn set_transport_security(&mut self, _transport_security: TransportSecurity) {}
 }
 
 pub struct ZMQNetworker {
@@ -31,10 +102,14 @@ pub struct ZMQNetworker {
     active_timeout: i64,
     conn_limit: usize,
     preordered_nodes: Vec<String>,
+    retry_policy: RetryPolicy,
+    resend_attempts: HashMap<String, usize>,
+    traffic_capture: Option<SharedTrafficCapture>,
+    transport_security: TransportSecurity,
 }
 
 impl Networker for ZMQNetworker {
-    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>) -> Self {
+    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, retry_policy: RetryPolicy) -> Self {
         ZMQNetworker {
             req_id_mappings: HashMap::new(),
             pool_connections: BTreeMap::new(),
@@ -42,6 +117,10 @@ impl Networker for ZMQNetworker {
             active_timeout,
             conn_limit,
             preordered_nodes,
+            retry_policy,
+            resend_attempts: HashMap::new(),
+            traffic_capture: None,
+            transport_security: TransportSecurity::default(),
         }
     }
 
@@ -55,6 +134,16 @@ impl Networker for ZMQNetworker {
     }
 
     fn process_event(&mut self, pe: Option<NetworkerEvent>) -> Option<RequestEvent> {
+        let pe = match pe {
+            Some(NetworkerEvent::Resend(req_id, timeout)) => {
+                let attempt = self.resend_attempts.entry(req_id.clone()).or_insert(0);
+                *attempt += 1;
+                let timeout = self.retry_policy.timeout(timeout, *attempt);
+                Some(NetworkerEvent::Resend(req_id, timeout))
+            }
+            pe => pe
+        };
+
         match pe.clone() {
             Some(NetworkerEvent::SendAllRequest(_, req_id, _, _)) | Some(NetworkerEvent::SendOneRequest(_, req_id, _)) | Some(NetworkerEvent::Resend(req_id, _)) => {
                 let num = self.req_id_mappings.get(&req_id).copied().or_else(|| {
@@ -85,6 +174,10 @@ impl Networker for ZMQNetworker {
                         trace!("send request in new conn");
                         let pc_id = sequence::get_next_id();
                         let mut pc = PoolConnection::new(self.nodes.clone(), self.active_timeout, self.preordered_nodes.clone());
+                        pc.traffic_capture = self.traffic_capture.clone();
+                        if let Some(key_pair) = self.transport_security.key_pair() {
+                            pc.key_pair = key_pair;
+                        }
                         pc.send_request(pe).expect("FIXME");
                         self.pool_connections.insert(pc_id, pc);
                         self.req_id_mappings.insert(req_id.clone(), pc_id);
@@ -94,7 +187,7 @@ impl Networker for ZMQNetworker {
             }
             Some(NetworkerEvent::NodesStateUpdated(nodes)) => {
                 trace!("ZMQNetworker::process_event: nodes_updated {:?}", nodes);
-                self.nodes = nodes;
+                self.nodes = self.transport_security.filter_nodes(nodes);
                 None
             }
             Some(NetworkerEvent::ExtendTimeout(req_id, node_alias, timeout)) => {
@@ -133,6 +226,7 @@ impl Networker for ZMQNetworker {
 
                 if node_alias.is_none() {
                     self.req_id_mappings.remove(&req_id);
+                    self.resend_attempts.remove(&req_id);
                 }
 
                 None
@@ -163,6 +257,14 @@ impl Networker for ZMQNetworker {
         self.pool_connections.iter()
             .flat_map(|(_, pool)| pool.get_poll_items()).collect()
     }
+
+    fn set_traffic_capture(&mut self, traffic_capture: SharedTrafficCapture) {
+        self.traffic_capture = Some(traffic_capture);
+    }
+
+    fn set_transport_security(&mut self, transport_security: TransportSecurity) {
+        self.transport_security = transport_security;
+    }
 }
 
 pub struct PoolConnection {
@@ -175,6 +277,7 @@ pub struct PoolConnection {
     time_created: time::Tm,
     req_cnt: usize,
     active_timeout: i64,
+    traffic_capture: Option<SharedTrafficCapture>,
 }
 
 impl PoolConnection {
@@ -205,6 +308,7 @@ impl PoolConnection {
             timeouts: RefCell::new(HashMap::new()),
             req_cnt: 0,
             active_timeout,
+            traffic_capture: None,
         }
     }
 
@@ -217,6 +321,7 @@ impl PoolConnection {
             if let (&Some(ref s), rn) = (&self.sockets[i], &self.nodes[i]) {
                 if poll_items[pi_idx].is_readable() {
                     if let Ok(Ok(str)) = s.recv_string(zmq::DONTWAIT) {
+                        traffic_capture::capture(&self.traffic_capture, Direction::Reply, &rn.name, &str);
                         vec.push(PoolEvent::NodeReply(
                             str,
                             rn.name.clone(),
@@ -323,6 +428,7 @@ impl PoolConnection {
             let s = self._get_socket(idx)?;
             s.send(&req, zmq::DONTWAIT)?;
         }
+        traffic_capture::capture(&self.traffic_capture, Direction::Request, &self.nodes[idx].name, &req);
         self.timeouts.borrow_mut().insert((req_id, self.nodes[idx].name.clone()), time::now() + Duration::seconds(timeout));
         trace!("_send_msg_to_one_node <<");
         Ok(())
@@ -360,7 +466,7 @@ pub struct MockNetworker {
 
 #[cfg(test)]
 impl Networker for MockNetworker {
-    fn new(_active_timeout: i64, _conn_limit: usize, _preordered_nodes: Vec<String>) -> Self {
+    fn new(_active_timeout: i64, _conn_limit: usize, _preordered_nodes: Vec<String>, _retry_policy: RetryPolicy) -> Self {
         MockNetworker {
             events: Vec::new(),
         }
@@ -410,6 +516,27 @@ pub mod networker_tests {
         }
     }
 
+    #[cfg(test)]
+    mod retry_policy {
+        use super::*;
+
+        #[test]
+        pub fn retry_policy_timeout_works_for_default() {
+            let policy = RetryPolicy::default();
+            assert_eq!(POOL_ACK_TIMEOUT, policy.timeout(POOL_ACK_TIMEOUT, 1));
+            assert_eq!(POOL_ACK_TIMEOUT, policy.timeout(POOL_ACK_TIMEOUT, 5));
+        }
+
+        #[test]
+        pub fn retry_policy_timeout_works_for_backoff() {
+            let policy = RetryPolicy { backoff_factor: 2.0, max_timeout: Some(50) };
+            assert_eq!(10, policy.timeout(10, 0));
+            assert_eq!(20, policy.timeout(10, 1));
+            assert_eq!(40, policy.timeout(10, 2));
+            assert_eq!(50, policy.timeout(10, 3));
+        }
+    }
+
     #[cfg(test)]
     mod networker {
         use std::ops::Sub;
@@ -418,12 +545,12 @@ pub mod networker_tests {
 
         #[test]
         pub fn networker_new_works() {
-            ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
         }
 
         #[test]
         pub fn networker_process_event_works() {
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
             networker.process_event(None);
         }
 
@@ -432,7 +559,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
 
             assert_eq!(0, networker.nodes.len());
 
@@ -441,13 +568,68 @@ pub mod networker_tests {
             assert_eq!(1, networker.nodes.len());
         }
 
+        #[test]
+        fn networker_process_update_node_state_event_works_for_pinned_node_keys() {
+            let txn = nodes_emulator::node();
+            let rn = _remote_node(&txn);
+
+            let mut rn_2 = rn.clone();
+            rn_2.name = "n2".to_string();
+
+            let mut pinned_node_keys = HashMap::new();
+            pinned_node_keys.insert(rn.name.clone(), rn.public_key.clone());
+            pinned_node_keys.insert(rn_2.name.clone(), vec![0; CURVE_KEY_LEN]);
+
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
+            networker.set_transport_security(TransportSecurity::new(None, Some(pinned_node_keys)).unwrap());
+
+            networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn.clone(), rn_2])));
+
+            assert_eq!(vec![rn], networker.nodes);
+        }
+
+        #[test]
+        fn networker_process_send_request_event_works_for_client_key_pair() {
+            let mut txn = nodes_emulator::node();
+            let handle = nodes_emulator::start(&mut txn);
+            let rn = _remote_node(&txn);
+
+            let key_pair = zmq::CurveKeyPair::new().unwrap();
+            let client_key_pair = ClientKeyPair {
+                public_key: zmq::z85_encode(&key_pair.public_key).unwrap(),
+                secret_key: zmq::z85_encode(&key_pair.secret_key).unwrap(),
+            };
+
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
+            networker.set_transport_security(TransportSecurity::new(Some(&client_key_pair), None).unwrap());
+
+            networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
+            networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
+
+            let pc = networker.pool_connections.values().next().unwrap();
+            assert_eq!(key_pair.public_key, pc.key_pair.public_key);
+
+            assert_eq!(MESSAGE.to_string(), nodes_emulator::next(&handle).unwrap());
+        }
+
+        #[test]
+        fn transport_security_new_works_for_invalid_client_key_pair() {
+            let client_key_pair = ClientKeyPair {
+                public_key: "invalid".to_string(),
+                secret_key: "invalid".to_string(),
+            };
+
+            let res = TransportSecurity::new(Some(&client_key_pair), None);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
         #[test]
         fn networker_process_send_request_event_works() {
             let mut txn = nodes_emulator::node();
             let handle = nodes_emulator::start(&mut txn);
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             assert!(networker.pool_connections.is_empty());
@@ -473,7 +655,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
             networker.process_event(Some(NetworkerEvent::SendAllRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT, None)));
@@ -496,7 +678,7 @@ pub mod networker_tests {
 
             let send_cnt = 2;
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec!["n2".to_string(), "n1".to_string()]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec!["n2".to_string(), "n1".to_string()], RetryPolicy::default());
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
 
@@ -526,7 +708,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
             networker.process_event(Some(NetworkerEvent::SendAllRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT, Some(vec![NODE_NAME.to_string()]))));
@@ -542,7 +724,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
@@ -565,7 +747,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
@@ -588,7 +770,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -617,7 +799,7 @@ pub mod networker_tests {
             let rn = _remote_node(&txn);
             let conn = PoolConnection::new(vec![rn.clone()], POOL_CON_ACTIVE_TO, vec![]);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.pool_connections.insert(1, conn);
@@ -634,7 +816,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
 
@@ -650,7 +832,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -668,7 +850,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -687,7 +869,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], RetryPolicy::default());
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
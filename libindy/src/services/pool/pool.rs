@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::thread::JoinHandle;
 
@@ -19,8 +20,9 @@ use crate::services::ledger::merkletree::merkletree::MerkleTree;
 use crate::services::pool::commander::Commander;
 use crate::services::pool::events::*;
 use crate::services::pool::{merkle_tree_factory, Nodes};
-use crate::services::pool::networker::{Networker, ZMQNetworker};
+use crate::services::pool::networker::{Networker, RetryPolicy, TransportSecurity, ZMQNetworker};
 use crate::services::pool::request_handler::{RequestHandler, RequestHandlerImpl};
+use crate::services::pool::traffic_capture::{SharedTrafficCapture, TrafficCapture};
 use rust_base58::{FromBase58, ToBase58};
 use crate::services::pool::types::{LedgerStatus, RemoteNode};
 use indy_utils::crypto::ed25519_sign;
@@ -68,6 +70,7 @@ struct GettingCatchupTargetState<T: Networker, R: RequestHandler<T>> {
 struct ActiveState<T: Networker, R: RequestHandler<T>> {
     networker: Rc<RefCell<T>>,
     request_handlers: HashMap<String, R>,
+    read_requests: HashMap<String, String>, // read key -> req_id of the request in flight
     nodes: Nodes,
 }
 
@@ -138,6 +141,7 @@ impl<T: Networker, R: RequestHandler<T>> From<(InitializationState<T>, Nodes)> f
         ActiveState {
             networker: state.networker,
             request_handlers: HashMap::new(),
+            read_requests: HashMap::new(),
             nodes,
         }
     }
@@ -162,6 +166,7 @@ impl<T: Networker, R: RequestHandler<T>> From<(GettingCatchupTargetState<T, R>,
         ActiveState {
             networker: state.networker,
             request_handlers: HashMap::new(),
+            read_requests: HashMap::new(),
             nodes,
         }
     }
@@ -225,6 +230,7 @@ impl<T: Networker, R: RequestHandler<T>> From<(SyncCatchupState<T, R>, Nodes)> f
         ActiveState {
             networker: state.networker,
             request_handlers: HashMap::new(),
+            read_requests: HashMap::new(),
             nodes,
         }
     }
@@ -379,14 +385,29 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                             PoolState::Terminated(state.into())
                         }
                     }
-                    PoolEvent::SendRequest(cmd_id, _, _, _) => {
+                    PoolEvent::SendRequest(cmd_id, _, req_timeout, req_nodes) => {
                         trace!("received request to send");
                         let re: Option<RequestEvent> = pe.into();
                         match re.as_ref().map(|r| r.get_req_id()) {
                             Some(req_id) => {
-                                let mut request_handler = R::new(state.networker.clone(), _get_f(state.nodes.len()), &[cmd_id], &state.nodes, &pool_name, timeout, extended_timeout, number_read_nodes);
-                                request_handler.process_event(re);
-                                state.request_handlers.insert(req_id.to_string(), request_handler); //FIXME check already exists
+                                let read_key = re.as_ref().and_then(|r| r.get_read_key(req_timeout, req_nodes.as_ref().map(String::as_str)));
+
+                                let in_flight = match read_key.as_ref().and_then(|key| state.read_requests.get(key)).cloned() {
+                                    Some(in_flight_req_id) => state.request_handlers.get_mut(&in_flight_req_id).filter(|rh| !rh.is_terminal()),
+                                    None => None
+                                };
+
+                                if let Some(request_handler) = in_flight {
+                                    trace!("identical read request is in flight, waiting for its reply");
+                                    request_handler.add_cmd_id(cmd_id, &req_id);
+                                } else {
+                                    let mut request_handler = R::new(state.networker.clone(), _get_f(state.nodes.len()), &[cmd_id], &state.nodes, &pool_name, timeout, extended_timeout, number_read_nodes);
+                                    request_handler.process_event(re);
+                                    state.request_handlers.insert(req_id.to_string(), request_handler); //FIXME check already exists
+                                    if let Some(key) = read_key {
+                                        state.read_requests.insert(key, req_id);
+                                    }
+                                }
                             }
                             None => {
                                 let res = Err(err_msg(IndyErrorKind::InvalidStructure, "Request id not found"));
@@ -408,6 +429,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                                 };
                                 if remove {
                                     state.request_handlers.remove(&req_id);
+                                    state.read_requests.retain(|_, in_flight_req_id| *in_flight_req_id != req_id);
                                 }
                             }
                             None => warn!("Request id not found in Reply: {:?}", reply)
@@ -475,6 +497,9 @@ pub struct Pool<S: Networker, R: RequestHandler<S>> {
     conn_limit: usize,
     preordered_nodes: Vec<String>,
     number_read_nodes: u8,
+    retry_policy: RetryPolicy,
+    traffic_capture: SharedTrafficCapture,
+    transport_security: TransportSecurity,
 }
 
 impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
@@ -491,9 +516,20 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
             conn_limit: config.conn_limit,
             preordered_nodes: config.preordered_nodes,
             number_read_nodes: config.number_read_nodes,
+            retry_policy: RetryPolicy {
+                backoff_factor: config.retry_backoff_factor,
+                max_timeout: config.max_retry_timeout,
+            },
+            traffic_capture: Arc::new(Mutex::new(None)),
+            transport_security: TransportSecurity::default(),
         }
     }
 
+    /// Sets CURVE settings of the connections to the nodes. Must be called before `work`.
+    pub fn set_transport_security(&mut self, transport_security: TransportSecurity) {
+        self.transport_security = transport_security;
+    }
+
     pub fn work(&mut self, cmd_socket: zmq::Socket) {
         let name = self.name.as_str().to_string();
         let id = self.id;
@@ -503,12 +539,18 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
         let conn_limit = self.conn_limit;
         let preordered_nodes = self.preordered_nodes.clone();
         let number_read_nodes = self.number_read_nodes;
+        let retry_policy = self.retry_policy;
+        let traffic_capture = self.traffic_capture.clone();
+        let transport_security = self.transport_security.clone();
         self.worker = Some(thread::spawn(move || {
             let mut pool_thread: PoolThread<S, R> = PoolThread::new(cmd_socket, name, id,
                                                                     timeout, extended_timeout,
                                                                     active_timeout, conn_limit,
                                                                     preordered_nodes,
-                                                                    number_read_nodes);
+                                                                    number_read_nodes,
+                                                                    retry_policy,
+                                                                    traffic_capture,
+                                                                    transport_security);
             pool_thread.work();
         }));
     }
@@ -520,6 +562,11 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
     pub fn get_id(&self) -> PoolHandle {
         self.id
     }
+
+    /// Starts capturing of the pool traffic or stops it if `None` is passed.
+    pub fn set_traffic_capture(&self, traffic_capture: Option<TrafficCapture>) {
+        *self.traffic_capture.lock().unwrap() = traffic_capture;
+    }
 }
 
 struct PoolThread<S: Networker, R: RequestHandler<S>> {
@@ -530,8 +577,10 @@ struct PoolThread<S: Networker, R: RequestHandler<S>> {
 }
 
 impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
-    pub fn new(cmd_socket: zmq::Socket, name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, number_read_nodes: u8) -> Self {
-        let networker = Rc::new(RefCell::new(S::new(active_timeout, conn_limit, preordered_nodes)));
+    pub fn new(cmd_socket: zmq::Socket, name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, number_read_nodes: u8, retry_policy: RetryPolicy, traffic_capture: SharedTrafficCapture, transport_security: TransportSecurity) -> Self {
+        let networker = Rc::new(RefCell::new(S::new(active_timeout, conn_limit, preordered_nodes, retry_policy)));
+        networker.borrow_mut().set_traffic_capture(traffic_capture);
+        networker.borrow_mut().set_transport_security(transport_security);
         PoolThread {
             pool_sm: Some(PoolSM::new(networker.clone(), &name, id, timeout, extended_timeout, number_read_nodes)),
             events: VecDeque::new(),
@@ -598,7 +647,7 @@ impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
     }
 }
 
-fn _get_f(cnt: usize) -> usize {
+pub(super) fn _get_f(cnt: usize) -> usize {
     if cnt < 4 {
         return 0;
     }
@@ -646,6 +695,24 @@ fn _ledger_status(merkle: &MerkleTree) -> LedgerStatus {
     }
 }
 
+/// Returns transport keys of the nodes by alias from the genesis transactions of the pool.
+pub fn genesis_node_keys(pool_name: &str) -> IndyResult<HashMap<String, Vec<u8>>> {
+    let merkle = merkle_tree_factory::create_from_genesis(pool_name)?;
+    let (_, remotes) = _get_nodes_and_remotes(&merkle)?;
+
+    Ok(remotes.into_iter()
+        .map(|remote| (remote.name, remote.public_key))
+        .collect())
+}
+
+/// Returns BLS keys of the nodes by alias from the cached pool ledger.
+pub fn node_bls_keys(pool_name: &str) -> IndyResult<Nodes> {
+    let merkle = merkle_tree_factory::create(pool_name)?;
+    let (nodes, _) = _get_nodes_and_remotes(&merkle)?;
+
+    Ok(nodes)
+}
+
 fn _get_nodes_and_remotes(merkle: &MerkleTree) -> IndyResult<(Nodes, Vec<RemoteNode>)> {
     let nodes = merkle_tree_factory::build_node_state(merkle)?;
 
@@ -809,7 +876,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_new_initialization_works() {
-            let _p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "name", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let _p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))), "name", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
         }
 
         #[test]
@@ -819,7 +886,7 @@ mod tests {
             ProtocolVersion::set(2);
             _write_genesis_txns("pool_wrapper_check_cache_works");
 
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_check_cache_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))), "pool_wrapper_check_cache_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             assert_match!(PoolState::GettingCatchupTarget(_), p.state);
@@ -830,7 +897,7 @@ mod tests {
         #[test]
         pub fn pool_wrapper_check_cache_works_for_no_pool_created() {
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))),
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))),
                             "pool_wrapper_check_cache_works_for_no_pool_created", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
@@ -839,7 +906,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_terminated_close_works() {
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_terminated_close_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))), "pool_wrapper_terminated_close_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let cmd_id: CommandHandle = next_command_handle();
@@ -850,7 +917,7 @@ mod tests {
         #[test]
         pub fn pool_wrapper_terminated_refresh_works() {
             test::cleanup_pool("pool_wrapper_terminated_refresh_works");
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_terminated_refresh_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))), "pool_wrapper_terminated_refresh_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
 
@@ -869,7 +936,7 @@ mod tests {
                 pool_name: "pool_wrapper_terminated_timeout_works".to_string(),
                 id: next_pool_handle(),
                 state: PoolState::Terminated(TerminatedState {
-                    networker: Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))),
+                    networker: Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))),
                 }),
                 timeout: 0,
                 extended_timeout: 0,
@@ -890,7 +957,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_cloe_works_from_initialization() {
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_cloe_works_from_initialization", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))), "pool_wrapper_cloe_works_from_initialization", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::Close(cmd_id));
             assert_match!(PoolState::Closed(_), p.state);
@@ -904,7 +971,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_close_works_from_getting_catchup_target");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_close_works_from_getting_catchup_target", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))), "pool_wrapper_close_works_from_getting_catchup_target", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let cmd_id: CommandHandle = next_command_handle();
@@ -922,7 +989,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_catchup_target_not_found_works");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_catchup_target_not_found_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))), "pool_wrapper_catchup_target_not_found_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::CatchupTargetNotFound(err_msg(IndyErrorKind::PoolTimeout, "Pool timeout")));
@@ -939,7 +1006,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_getting_catchup_target_synced_works");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_getting_catchup_target_synced_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default()))), "pool_wrapper_getting_catchup_target_synced_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
@@ -958,7 +1025,7 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
-                                       0, vec![]))),
+                                       0, vec![], RetryPolicy::default()))),
                 "pool_wrapper_getting_catchup_target_synced_works_for_node_state_error",
                 next_pool_handle(),
                 0,
@@ -985,7 +1052,7 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], RetryPolicy::default()))),
                 "pool_wrapper_getting_catchup_target_catchup_target_found_works",
                 next_pool_handle(),
                 0,
@@ -1009,7 +1076,7 @@ mod tests {
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
                 PoolSM::new(Rc::new(RefCell::new(
-                    MockNetworker::new(0, 0, vec![]))),
+                    MockNetworker::new(0, 0, vec![], RetryPolicy::default()))),
                             "pool_wrapper_getting_catchup_target_catchup_target_found_works_for_node_state_error",
                             next_pool_handle(),
                             0,
@@ -1037,7 +1104,7 @@ mod tests {
                     RefCell::new(
                         MockNetworker::new(0,
                                            0,
-                                           vec![]))),
+                                           vec![], RetryPolicy::default()))),
                             "pool_wrapper_sync_catchup_close_works",
                             next_pool_handle(),
                             0,
@@ -1065,7 +1132,7 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], RetryPolicy::default()))),
                 "pool_wrapper_sync_catchup_synced_works",
                 next_pool_handle(),
                 0,
@@ -1092,7 +1159,7 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], RetryPolicy::default()))),
                 "pool_wrapper_sync_catchup_synced_works_for_node_state_error",
                 next_pool_handle(),
                 0,
@@ -1124,7 +1191,7 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(
                 RefCell::new(MockNetworker::new(0,
                                                 0,
-                                                vec![]))),
+                                                vec![], RetryPolicy::default()))),
                                                                            "pool_wrapper_active_send_request_works",
                                                                            next_pool_handle(),
                                                                            0,
@@ -1146,6 +1213,70 @@ mod tests {
             test::cleanup_storage("pool_wrapper_active_send_request_works");
         }
 
+        #[test]
+        pub fn pool_wrapper_active_send_request_works_for_read_request() {
+            test::cleanup_storage("pool_wrapper_active_send_request_works_for_read_request");
+
+            ProtocolVersion::set(2);
+            _write_genesis_txns("pool_wrapper_active_send_request_works_for_read_request");
+
+            let req = json!({
+                "reqId": 1,
+                "operation": {
+                    "type": "105",
+                    "dest": "NcYxiDXkpYi6ov5FcYDi1e"
+                },
+                "protocolVersion": 2
+            }).to_string();
+
+            let rep = Message::Reply(Reply::ReplyV1(
+                ReplyV1 {
+                    result: ReplyResultV1 {
+                        txn: ReplyTxnV1 {
+                            metadata: ResponseMetadata {
+                                req_id: 1
+                            }
+                        }
+                    }
+                }
+            ));
+
+            let rep = serde_json::to_string(&rep).unwrap();
+
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(
+                Rc::new(RefCell::new(
+                    MockNetworker::new(0,
+                                       0,
+                                       vec![], RetryPolicy::default()))),
+                "pool_wrapper_active_send_request_works_for_read_request",
+                next_pool_handle(),
+                0,
+                0, NUMBER_READ_NODES);
+            let cmd_id: CommandHandle = next_command_handle();
+            let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
+            let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
+            let cmd_id: CommandHandle = next_command_handle();
+            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None));
+            let p = match p.state {
+                PoolState::Active(state) => {
+                    assert_eq!(state.read_requests.len(), 1);
+                    assert!(state.read_requests.values().all(|req_id| req_id == "1"));
+                    PoolSM::step(p.pool_name, p.id, p.timeout, p.extended_timeout, p.number_read_nodes, PoolState::Active(state))
+                }
+                _ => unreachable!()
+            };
+            let p = p.handle_event(PoolEvent::NodeReply(rep, "node".to_string()));
+            match p.state {
+                PoolState::Active(state) => {
+                    assert_eq!(state.request_handlers.len(), 0);
+                    assert_eq!(state.read_requests.len(), 0);
+                }
+                _ => assert!(false)
+            };
+
+            test::cleanup_storage("pool_wrapper_active_send_request_works_for_read_request");
+        }
+
         #[test]
         pub fn pool_wrapper_active_send_request_works_for_no_req_id() {
             test::cleanup_storage("pool_wrapper_active_send_request_works_for_no_req_id");
@@ -1164,7 +1295,7 @@ mod tests {
                     MockNetworker::new(
                         0,
                         0,
-                        vec![]))),
+                        vec![], RetryPolicy::default()))),
                             "pool_wrapper_active_send_request_works_for_no_req_id",
                             next_pool_handle(),
                             0,
@@ -1217,7 +1348,7 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], RetryPolicy::default()))),
                 "pool_wrapper_active_node_reply_works",
                 next_pool_handle(),
                 0,
@@ -1257,7 +1388,7 @@ mod tests {
                 PoolSM::new(Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], RetryPolicy::default()))),
                             "pool_wrapper_sends_requests_to_two_nodes",
                             next_pool_handle(), 0, 0, NUMBER_READ_NODES);
             let cmd_id: CommandHandle = next_command_handle();
@@ -1307,7 +1438,7 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(
                 RefCell::new(MockNetworker::new(0,
                                                 0,
-                                                vec![]))),
+                                                vec![], RetryPolicy::default()))),
                                                                            "pool_wrapper_active_node_reply_works_for_no_request",
                                                                            next_pool_handle(),
                                                                            0,
@@ -1350,7 +1481,7 @@ mod tests {
                 PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(
                     0,
                     0,
-                    vec![]))),
+                    vec![], RetryPolicy::default()))),
                             "pool_wrapper_active_node_reply_works_for_invalid_reply",
                             next_pool_handle(),
                             0,
@@ -34,9 +34,13 @@ use log_derive::logfn;
 use indy_api_types::CommandHandle;
 use rust_base58::FromBase58;
 
+/// Command waiting for the reply and its own request id if its read request was coalesced
+/// with the identical one in flight, so the reply can be returned with the id the caller expects.
+type Caller = (CommandHandle, Option<String>);
+
 struct RequestSM<T: Networker> {
     f: usize,
-    cmd_ids: Vec<CommandHandle>,
+    cmd_ids: Vec<Caller>,
     nodes: Nodes,
     generator: Generator,
     pool_name: String,
@@ -79,7 +83,7 @@ impl<T: Networker> RequestSM<T> {
         let generator: Generator = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
         RequestSM {
             f,
-            cmd_ids: cmd_ids.to_owned(),
+            cmd_ids: cmd_ids.iter().map(|cmd_id| (*cmd_id, None)).collect(),
             nodes: nodes.clone(),
             generator,
             pool_name: pool_name.to_string(),
@@ -93,7 +97,7 @@ impl<T: Networker> RequestSM<T> {
     }
 
     pub fn step(f: usize,
-                cmd_ids: Vec<CommandHandle>,
+                cmd_ids: Vec<Caller>,
                 nodes: Nodes,
                 generator: Generator,
                 pool_name: String,
@@ -462,13 +466,31 @@ impl<T: Networker> RequestSM<T> {
                     _ => (RequestState::CatchupConsensus(state), None)
                 }
             }
-            RequestState::CatchupSingle(state) => {
+            RequestState::CatchupSingle(mut state) => {
                 match re {
                     RequestEvent::CatchupRep(mut cr, node_alias) => {
                         match _process_catchup_reply(&mut cr, &state.merkle_tree, &state.target_mt_root, state.target_mt_size, &pool_name) {
                             Ok(merkle) => {
                                 state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(state.req_id.clone(), None)));
-                                (RequestState::finish(), Some(PoolEvent::Synced(merkle)))
+
+                                if merkle.count() >= state.target_mt_size {
+                                    (RequestState::finish(), Some(PoolEvent::Synced(merkle)))
+                                } else {
+                                    // received batch is already stored, so request the next one
+                                    match build_catchup_req(&merkle, state.target_mt_size) {
+                                        Ok(Some((req_id, req_json))) => {
+                                            state.networker.borrow_mut().process_event(Some(NetworkerEvent::SendOneRequest(req_json, req_id.clone(), timeout)));
+                                            state.merkle_tree = merkle;
+                                            state.req_id = req_id;
+                                            (RequestState::CatchupSingle(state), None)
+                                        }
+                                        Ok(None) => (RequestState::finish(), Some(PoolEvent::Synced(merkle))),
+                                        Err(e) => {
+                                            _send_replies(&cmd_ids, Err(e));
+                                            (RequestState::finish(), None)
+                                        }
+                                    }
+                                }
                             }
                             Err(_) => {
                                 state.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(state.req_id.clone(), timeout)));
@@ -526,7 +548,7 @@ impl<T: Networker> RequestSM<T> {
 
     fn _full_request_handle_consensus_state(mut state: FullState<T>,
                                             req_id: String, node_alias: String, node_result: String,
-                                            cmd_ids: &[CommandHandle],
+                                            cmd_ids: &[Caller],
                                             nodes: &Nodes) -> RequestState<T> {
         let is_first_resp = state.accum_reply.is_none();
         if is_first_resp {
@@ -615,6 +637,7 @@ pub trait RequestHandler<T: Networker> {
     fn new(networker: Rc<RefCell<T>>, f: usize, cmd_ids: &[CommandHandle], nodes: &Nodes, pool_name: &str, timeout: i64, extended_timeout: i64, number_read_nodes: u8) -> Self;
     fn process_event(&mut self, ore: Option<RequestEvent>) -> Option<PoolEvent>;
     fn is_terminal(&self) -> bool;
+    fn add_cmd_id(&mut self, cmd_id: CommandHandle, req_id: &str);
 }
 
 pub struct RequestHandlerImpl<T: Networker> {
@@ -646,6 +669,12 @@ impl<T: Networker> RequestHandler<T> for RequestHandlerImpl<T> {
     fn is_terminal(&self) -> bool {
         self.request_wrapper.as_ref().map(|w| w.is_terminal()).unwrap_or(true)
     }
+
+    fn add_cmd_id(&mut self, cmd_id: CommandHandle, req_id: &str) {
+        if let Some(ref mut w) = self.request_wrapper {
+            w.cmd_ids.push((cmd_id, Some(req_id.to_string())));
+        }
+    }
 }
 
 impl<T: Networker> SingleState<T> {
@@ -654,7 +683,7 @@ impl<T: Networker> SingleState<T> {
             < total_nodes_cnt
     }
 
-    fn try_to_continue(self, req_id: String, node_alias: String, cmd_ids: &[CommandHandle], nodes_cnt: usize, timeout: i64) -> RequestState<T> {
+    fn try_to_continue(self, req_id: String, node_alias: String, cmd_ids: &[Caller], nodes_cnt: usize, timeout: i64) -> RequestState<T> {
         if self.is_consensus_reachable(nodes_cnt) {
             self.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(req_id.clone(), timeout)));
             self.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(req_id.clone(), timeout)));
@@ -677,7 +706,7 @@ impl<T: Networker> ConsensusState<T> {
     }
 }
 
-fn _parse_nack(denied_nodes: &mut HashSet<String>, f: usize, raw_msg: &str, cmd_ids: &[CommandHandle], node_alias: &str) -> bool {
+fn _parse_nack(denied_nodes: &mut HashSet<String>, f: usize, raw_msg: &str, cmd_ids: &[Caller], node_alias: &str) -> bool {
     if denied_nodes.len() == f {
         _send_ok_replies(cmd_ids, raw_msg);
         true
@@ -707,23 +736,49 @@ fn _process_catchup_reply(rep: &mut CatchupRep, merkle: &MerkleTree, target_mt_r
     Ok(merkle)
 }
 
-fn _send_ok_replies(cmd_ids: &[CommandHandle], msg: &str) {
+fn _send_ok_replies(cmd_ids: &[Caller], msg: &str) {
     _send_replies(cmd_ids, Ok(msg.to_string()))
 }
 
-fn _finish_request(cmd_ids: &[CommandHandle]) {
+fn _finish_request(cmd_ids: &[Caller]) {
     _send_replies(cmd_ids, Err(err_msg(IndyErrorKind::PoolTerminated, "Pool is terminated")))
 }
 
-fn _send_replies(cmd_ids: &[CommandHandle], msg: IndyResult<String>) {
-    cmd_ids.iter().for_each(|id| {
+fn _send_replies(cmd_ids: &[Caller], msg: IndyResult<String>) {
+    cmd_ids.iter().for_each(|(id, req_id)| {
+        let msg = match (req_id, &msg) {
+            (Some(req_id), Ok(msg)) => Ok(_replace_req_id(msg, req_id)),
+            _ => msg.clone()
+        };
+
         CommandExecutor::instance().send(
             Command::Ledger(
-                LedgerCommand::SubmitAck(*id, msg.clone()))
+                LedgerCommand::SubmitAck(*id, msg))
         ).unwrap();
     });
 }
 
+/// Sets request id of the node message to the id of the coalesced request.
+/// The id isn't covered by state proofs and multi-signatures, so the reply stays verifiable.
+fn _replace_req_id(msg: &str, req_id: &str) -> String {
+    let mut msg = match serde_json::from_str::<SJsonValue>(msg) {
+        Ok(msg) => msg,
+        Err(_) => return msg.to_string()
+    };
+
+    let req_id = req_id.parse::<u64>().map(SJsonValue::from).unwrap_or_else(|_| SJsonValue::from(req_id));
+
+    if !msg["result"]["reqId"].is_null() {
+        msg["result"]["reqId"] = req_id.clone();
+    }
+
+    if !msg["reqId"].is_null() {
+        msg["reqId"] = req_id;
+    }
+
+    msg.to_string()
+}
+
 fn _get_msg_result_without_state_proof(msg: &str) -> IndyResult<(SJsonValue, SJsonValue)> {
     let msg = serde_json::from_str::<SJsonValue>(msg)
         .to_indy(IndyErrorKind::InvalidStructure, "Response is malformed json")?;
@@ -839,7 +894,7 @@ fn _get_cur_time() -> u64 {
 #[cfg(test)]
 pub mod tests {
     use crate::services::ledger::merkletree::tree::Tree;
-    use crate::services::pool::networker::MockNetworker;
+    use crate::services::pool::networker::{MockNetworker, RetryPolicy};
     use crate::services::pool::types::{ConsistencyProof, LedgerStatus, Reply, ReplyResultV1, ReplyTxnV1, ReplyV1, Response, ResponseMetadata, ResponseV1};
     use crate::utils::test;
     use crate::utils::test::test_pool_create_poolfile;
@@ -873,6 +928,8 @@ pub mod tests {
         fn is_terminal(&self) -> bool {
             true
         }
+
+        fn add_cmd_id(&mut self, _cmd_id: CommandHandle, _req_id: &str) {}
     }
 
     impl Default for LedgerStatus {
@@ -924,7 +981,7 @@ pub mod tests {
     }
 
     fn _request_handler(pool_name: &str, f: usize, nodes_cnt: usize) -> RequestHandlerImpl<MockNetworker> {
-        let networker = Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![])));
+        let networker = Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], RetryPolicy::default())));
 
         let mut default_nodes: Nodes = HashMap::new();
         default_nodes.insert(NODE.to_string(), None);
@@ -1777,4 +1834,13 @@ pub mod tests {
             assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
         }
     }
+
+    #[test]
+    fn replace_req_id_works() {
+        let reply = _replace_req_id(r#"{"op":"REPLY","result":{"reqId":1,"data":null}}"#, "2");
+        assert_eq!(json!({"op": "REPLY", "result": {"reqId": 2, "data": null}}), serde_json::from_str::<SJsonValue>(&reply).unwrap());
+
+        let nack = _replace_req_id(r#"{"op":"REQNACK","reqId":1,"reason":"reqnack"}"#, "2");
+        assert_eq!(json!({"op": "REQNACK", "reqId": 2, "reason": "reqnack"}), serde_json::from_str::<SJsonValue>(&nack).unwrap());
+    }
 }
@@ -0,0 +1,230 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+
+use crate::domain::pool::TrafficCaptureConfig;
+use indy_api_types::errors::prelude::*;
+
+use super::time;
+
+/// Fields that are replaced in captured messages as they may contain private data.
+const REDACTED_FIELDS: [&str; 4] = ["signature", "signatures", "raw", "enc"];
+const REDACTED_VALUE: &str = "***";
+
+/// Capture slot shared between the pool service (to toggle capturing) and the pool worker thread (to write messages).
+pub type SharedTrafficCapture = Arc<Mutex<Option<TrafficCapture>>>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Request,
+    Reply,
+}
+
+impl Direction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Direction::Request => "request",
+            Direction::Reply => "reply",
+        }
+    }
+}
+
+/// Writes raw messages exchanged with pool nodes to a rotating file as json lines.
+pub struct TrafficCapture {
+    config: TrafficCaptureConfig,
+    file: File,
+    size: u64,
+}
+
+impl TrafficCapture {
+    pub fn new(config: TrafficCaptureConfig) -> IndyResult<TrafficCapture> {
+        trace!("TrafficCapture::new >>> config: {:?}", config);
+
+        let path = PathBuf::from(&config.path);
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)
+                    .to_indy(IndyErrorKind::IOError, "Can't create directory for traffic capture file")?;
+            }
+        }
+
+        let file = _open(&path)
+            .to_indy(IndyErrorKind::IOError, "Can't open traffic capture file")?;
+
+        let size = file.metadata()
+            .to_indy(IndyErrorKind::IOError, "Can't read traffic capture file metadata")?
+            .len();
+
+        Ok(TrafficCapture { config, file, size })
+    }
+
+    pub fn capture(&mut self, direction: Direction, node_alias: &str, msg: &str) {
+        let timestamp = time::get_time();
+
+        let record = json!({
+            "timestamp": timestamp.sec * 1000 + i64::from(timestamp.nsec / 1_000_000),
+            "direction": direction.as_str(),
+            "node": node_alias,
+            "message": redact(msg),
+        });
+
+        if let Err(err) = self._write(&format!("{}\n", record)) {
+            warn!("Can't write pool traffic capture record: {}", err);
+        }
+    }
+
+    fn _write(&mut self, line: &str) -> io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 > self.config.max_file_size {
+            self._rotate()?;
+        }
+
+        self.file.write_all(line.as_bytes())?;
+        self.size += line.len() as u64;
+
+        Ok(())
+    }
+
+    fn _rotate(&mut self) -> io::Result<()> {
+        let path = PathBuf::from(&self.config.path);
+
+        for idx in (1..self.config.max_files).rev() {
+            let from = if idx == 1 { path.clone() } else { _rotated_path(&path, idx - 1) };
+
+            if from.exists() {
+                fs::rename(&from, _rotated_path(&path, idx))?;
+            }
+        }
+
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+
+        self.file = _open(&path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+/// Writes the message to the capture if capturing is enabled.
+pub fn capture(traffic_capture: &Option<SharedTrafficCapture>, direction: Direction, node_alias: &str, msg: &str) {
+    if let Some(traffic_capture) = traffic_capture {
+        if let Some(traffic_capture) = traffic_capture.lock().unwrap().as_mut() {
+            traffic_capture.capture(direction, node_alias, msg);
+        }
+    }
+}
+
+/// Replaces values of private fields of json messages. Messages that are not json are kept as is.
+pub fn redact(msg: &str) -> Value {
+    match serde_json::from_str::<Value>(msg) {
+        Ok(mut value) => {
+            _redact(&mut value);
+            value
+        }
+        Err(_) => Value::String(msg.to_string())
+    }
+}
+
+fn _redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if REDACTED_FIELDS.contains(&key.as_str()) && !value.is_null() {
+                    *value = Value::String(REDACTED_VALUE.to_string());
+                } else {
+                    _redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(_redact),
+        _ => {}
+    }
+}
+
+fn _open(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn _rotated_path(path: &Path, idx: usize) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(format!(".{}", idx));
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils::environment;
+
+    fn _config(name: &str, max_file_size: u64, max_files: usize) -> TrafficCaptureConfig {
+        let path = environment::tmp_file_path(&format!("traffic_capture_{}.log", name));
+        let _ = fs::remove_file(&path);
+        for idx in 1..max_files {
+            let _ = fs::remove_file(_rotated_path(&path, idx));
+        }
+
+        TrafficCaptureConfig {
+            path: path.to_str().unwrap().to_string(),
+            max_file_size,
+            max_files,
+        }
+    }
+
+    #[test]
+    fn redact_works() {
+        let msg = r#"{"reqId":1,"signature":"sig","operation":{"type":"100","raw":"{\"name\":\"Alex\"}"},"signatures":null}"#;
+
+        let expected = json!({"reqId":1,"signature":"***","operation":{"type":"100","raw":"***"},"signatures":null});
+        assert_eq!(expected, redact(msg));
+    }
+
+    #[test]
+    fn redact_works_for_not_json() {
+        assert_eq!(json!("po"), redact("po"));
+    }
+
+    #[test]
+    fn traffic_capture_works() {
+        let config = _config("works", 1024, 2);
+        let path = config.path.clone();
+
+        let mut traffic_capture = TrafficCapture::new(config).unwrap();
+        traffic_capture.capture(Direction::Request, "Node1", r#"{"reqId":1,"signature":"sig"}"#);
+        traffic_capture.capture(Direction::Reply, "Node1", r#"{"op":"REPLY"}"#);
+
+        let content = fs::read_to_string(&path).unwrap();
+        let records: Vec<Value> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+        assert_eq!(2, records.len());
+        assert_eq!("request", records[0]["direction"]);
+        assert_eq!("Node1", records[0]["node"]);
+        assert_eq!("***", records[0]["message"]["signature"]);
+        assert_eq!("reply", records[1]["direction"]);
+        assert!(records[1]["timestamp"].is_i64());
+    }
+
+    #[test]
+    fn traffic_capture_rotates_files() {
+        let config = _config("rotates_files", 100, 3);
+        let path = PathBuf::from(&config.path);
+
+        let mut traffic_capture = TrafficCapture::new(config).unwrap();
+        for idx in 0..5 {
+            traffic_capture.capture(Direction::Request, "Node1", &format!(r#"{{"reqId":{}}}"#, idx));
+        }
+
+        assert!(path.exists());
+        assert!(_rotated_path(&path, 1).exists());
+        assert!(_rotated_path(&path, 2).exists());
+        assert!(!_rotated_path(&path, 3).exists());
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(r#""reqId":4"#));
+    }
+}
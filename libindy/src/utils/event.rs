@@ -0,0 +1,45 @@
+use std::ffi::CString;
+use std::sync::Mutex;
+
+use libc::{c_char, c_void};
+
+use crate::domain::event::Event;
+
+pub type EventCB = extern fn(context: *const c_void,
+                             event_json: *const c_char);
+
+#[derive(Clone, Copy)]
+struct EventListener {
+    context: *const c_void,
+    cb: EventCB,
+}
+
+// Context is owned by application and is only passed back to the listener.
+unsafe impl Send for EventListener {}
+
+lazy_static! {
+    static ref EVENT_LISTENER: Mutex<Option<EventListener>> = Mutex::new(None);
+}
+
+/// Sets listener of libindy events. Previous listener is replaced, `None` unsets it.
+pub fn set_event_listener(context: *const c_void, cb: Option<EventCB>) {
+    *EVENT_LISTENER.lock().unwrap() = cb.map(|cb| EventListener { context, cb });
+}
+
+/// Reports event to the listener if it is set.
+pub fn emit_event(event: &Event) {
+    trace!("emit_event >>> event: {:?}", event);
+
+    // Listener is called without holding the lock, so it can set the listener itself
+    let listener = match *EVENT_LISTENER.lock().unwrap() {
+        Some(listener) => listener,
+        None => return
+    };
+
+    let event_json = match serde_json::to_string(event).ok().and_then(|event| CString::new(event).ok()) {
+        Some(event_json) => event_json,
+        None => return error!("Can't serialize event: {:?}", event)
+    };
+
+    (listener.cb)(listener.context, event_json.as_ptr());
+}
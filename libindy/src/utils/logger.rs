@@ -13,11 +13,14 @@ use self::android_logger::Filter;
 use log::{Record, Metadata};
 
 use libc::{c_void, c_char};
+use std::cell::RefCell;
 use std::ffi::CString;
 use std::ptr;
 
 use indy_api_types::errors::prelude::*;
+use indy_api_types::IndyHandle;
 use indy_utils::ctypes;
+use indy_utils::sequence;
 
 pub static mut LOGGER_STATE: LoggerState = LoggerState::Default;
 
@@ -30,7 +33,8 @@ impl LoggerState {
     pub fn get(&self) -> (*const c_void, Option<EnabledCB>, Option<LogCB>, Option<FlushCB>) {
         match self {
             LoggerState::Default => (ptr::null(), Some(LibindyDefaultLogger::enabled), Some(LibindyDefaultLogger::log), Some(LibindyDefaultLogger::flush)),
-            LoggerState::Custom => unsafe { (CONTEXT, ENABLED_CB, LOG_CB, FLUSH_CB) },
+            // Plugins call "log" handler without command context params, so it is forwarded with the context of the current thread
+            LoggerState::Custom => unsafe { (CONTEXT, ENABLED_CB, Some(LibindyLogger::forward_log), FLUSH_CB) },
         }
     }
 }
@@ -48,22 +52,60 @@ pub type LogCB = extern fn(context: *const c_void,
                            file: *const c_char,
                            line: u32);
 
+/// "log" operation handler of `indy_set_logger`. Handlers declared without trailing
+/// command context params are called the same way.
+pub type CommandLogCB = extern fn(context: *const c_void,
+                                  level: u32,
+                                  target: *const c_char,
+                                  message: *const c_char,
+                                  module_path: *const c_char,
+                                  file: *const c_char,
+                                  line: u32,
+                                  command: *const c_char,
+                                  correlation_id: IndyHandle);
+
 pub type FlushCB = extern fn(context: *const c_void);
 
+/// Context of the command processed by the current thread.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogContext {
+    pub command: &'static str,
+    pub correlation_id: IndyHandle,
+}
+
+thread_local! {
+    static LOG_CONTEXT: RefCell<Option<LogContext>> = RefCell::new(None);
+}
+
+/// Runs `f` with the log context of the command, so all records logged meanwhile can be correlated.
+pub fn with_log_context<T, F: FnOnce() -> T>(command: &'static str, f: F) -> T {
+    let context = LogContext { command, correlation_id: sequence::get_next_id() };
+
+    let prev = LOG_CONTEXT.with(|ctx| ctx.replace(Some(context)));
+    let res = f();
+    LOG_CONTEXT.with(|ctx| ctx.replace(prev));
+
+    res
+}
+
+pub fn get_log_context() -> Option<LogContext> {
+    LOG_CONTEXT.with(|ctx| *ctx.borrow())
+}
+
 static mut CONTEXT: *const c_void = ptr::null();
 static mut ENABLED_CB: Option<EnabledCB> = None;
-static mut LOG_CB: Option<LogCB> = None;
+static mut LOG_CB: Option<CommandLogCB> = None;
 static mut FLUSH_CB: Option<FlushCB> = None;
 
 pub struct LibindyLogger {
     context: *const c_void,
     enabled: Option<EnabledCB>,
-    log: LogCB,
+    log: CommandLogCB,
     flush: Option<FlushCB>,
 }
 
 impl LibindyLogger {
-    fn new(context: *const c_void, enabled: Option<EnabledCB>, log: LogCB, flush: Option<FlushCB>) -> Self {
+    fn new(context: *const c_void, enabled: Option<EnabledCB>, log: CommandLogCB, flush: Option<FlushCB>) -> Self {
         LibindyLogger { context, enabled, log, flush }
     }
 }
@@ -92,6 +134,10 @@ impl log::Log for LibindyLogger {
         let file = record.file().map(|a| CString::new(a).unwrap());
         let line = record.line().unwrap_or(0);
 
+        let log_context = get_log_context();
+        let command = log_context.map(|ctx| CString::new(ctx.command).unwrap());
+        let correlation_id = log_context.map(|ctx| ctx.correlation_id).unwrap_or(0);
+
         log_cb(self.context,
                level,
                target.as_ptr(),
@@ -99,6 +145,8 @@ impl log::Log for LibindyLogger {
                module_path.as_ref().map(|p| p.as_ptr()).unwrap_or(ptr::null()),
                file.as_ref().map(|p| p.as_ptr()).unwrap_or(ptr::null()),
                line,
+               command.as_ref().map(|p| p.as_ptr()).unwrap_or(ptr::null()),
+               correlation_id,
         )
     }
 
@@ -114,7 +162,7 @@ unsafe impl Sync for LibindyLogger {}
 unsafe impl Send for LibindyLogger {}
 
 impl LibindyLogger {
-    pub fn init(context: *const c_void, enabled: Option<EnabledCB>, log: LogCB, flush: Option<FlushCB>) -> Result<(), IndyError> {
+    pub fn init(context: *const c_void, enabled: Option<EnabledCB>, log: CommandLogCB, flush: Option<FlushCB>) -> Result<(), IndyError> {
         let logger = LibindyLogger::new(context, enabled, log, flush);
 
         log::set_boxed_logger(Box::new(logger))?;
@@ -130,6 +178,31 @@ impl LibindyLogger {
 
         Ok(())
     }
+
+    extern fn forward_log(context: *const c_void,
+                          level: u32,
+                          target: *const c_char,
+                          message: *const c_char,
+                          module_path: *const c_char,
+                          file: *const c_char,
+                          line: u32) {
+        if let Some(log_cb) = unsafe { LOG_CB } {
+            let log_context = get_log_context();
+            let command = log_context.map(|ctx| CString::new(ctx.command).unwrap());
+            let correlation_id = log_context.map(|ctx| ctx.correlation_id).unwrap_or(0);
+
+            log_cb(context,
+                   level,
+                   target,
+                   message,
+                   module_path,
+                   file,
+                   line,
+                   command.as_ref().map(|p| p.as_ptr()).unwrap_or(ptr::null()),
+                   correlation_id,
+            )
+        }
+    }
 }
 
 pub struct LibindyDefaultLogger;
@@ -279,4 +352,27 @@ macro_rules! secret {
 #[macro_export]
 macro_rules! secret {
     ($val:expr) => {{ "_" }};
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_log_context_works() {
+        assert_eq!(None, get_log_context());
+
+        let (outer, inner) = with_log_context("Outer", || {
+            let outer = get_log_context().unwrap();
+            let inner = with_log_context("Inner", || get_log_context().unwrap());
+
+            assert_eq!(Some(outer), get_log_context());
+            (outer, inner)
+        });
+
+        assert_eq!("Outer", outer.command);
+        assert_eq!("Inner", inner.command);
+        assert_ne!(outer.correlation_id, inner.correlation_id);
+        assert_eq!(None, get_log_context());
+    }
+}
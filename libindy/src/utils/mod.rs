@@ -4,6 +4,8 @@ pub use indy_utils::environment;
 pub mod ccallback;
 
 pub mod crypto;
+
+pub mod event;
 #[macro_use]
 pub mod logger;
 
@@ -368,6 +368,153 @@ mod demos {
         wallet::close_and_delete_wallet(issuer_xyz_wallet_handle, &issuer_xyz_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_credentials_from_multiple_wallets() {
+        let setup = Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handles
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credentials_from_multiple_wallets").unwrap();
+
+        //2. Prover creates wallet, gets wallet handles
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credentials_from_multiple_wallets").unwrap();
+
+        //3. Issuer creates GVT and XYZ Schemas and Credential Definitions
+        let (gvt_schema_id, gvt_schema,
+            gvt_cred_def_id, gvt_cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                            ISSUER_DID,
+                                                                                            GVT_SCHEMA_NAME,
+                                                                                            GVT_SCHEMA_ATTRIBUTES);
+
+        let (xyz_schema_id, xyz_schema,
+            xyz_cred_def_id, xyz_cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                            ISSUER_DID,
+                                                                                            XYZ_SCHEMA_NAME,
+                                                                                            XYZ_SCHEMA_ATTRIBUTES);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Guardian wallet is created from Prover wallet export so it holds the same Master Secret
+        let path = wallet::export_wallet_path(&setup.name);
+        let export_config = wallet::prepare_export_wallet_config(&path);
+        wallet::export_wallet(prover_wallet_handle, &export_config).unwrap();
+
+        let guardian_wallet_config = json!({"id": format!("guardian_wallet_{}", setup.name)}).to_string();
+        wallet::import_wallet(&guardian_wallet_config, WALLET_CREDENTIALS, &export_config).unwrap();
+        let guardian_wallet_handle = wallet::open_wallet(&guardian_wallet_config, WALLET_CREDENTIALS).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        //6. Issuer issues GVT Credential into Prover wallet
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &gvt_cred_def_id,
+                                                 &gvt_cred_def_json);
+
+        //7. Issuer issues XYZ Credential into Guardian wallet
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 guardian_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL2_ID,
+                                                 &anoncreds::xyz_credential_values_json(),
+                                                 &xyz_cred_def_id,
+                                                 &xyz_cred_def_json);
+
+        //8. Proof request
+        let proof_req_json = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({
+                   "name":"name",
+                   "restrictions": json!({ "cred_def_id": gvt_cred_def_id })
+               }),
+               "attr2_referent": json!({
+                   "name":"status",
+                   "restrictions": json!({ "cred_def_id": xyz_cred_def_id })
+               })
+           }),
+           "requested_predicates": json!({
+               "predicate1_referent": json!({ "name":"period", "p_type":">=", "p_value":5 }),
+           }),
+        }).to_string();
+
+        //9. Prover creates Proof taking XYZ Credential from Guardian wallet
+        let requested_credentials_json = json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":true }),
+                "attr2_referent": json!({ "cred_id": CREDENTIAL2_ID, "revealed":true, "wallet_handle": guardian_wallet_handle })
+             }),
+             "requested_predicates": json!({
+                "predicate1_referent": json!({ "cred_id": CREDENTIAL2_ID, "wallet_handle": guardian_wallet_handle })
+             })
+        }).to_string();
+
+        let schemas_json = json!({
+            gvt_schema_id: serde_json::from_str::<Schema>(&gvt_schema).unwrap(),
+            xyz_schema_id: serde_json::from_str::<Schema>(&xyz_schema).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            gvt_cred_def_id: serde_json::from_str::<CredentialDefinition>(&gvt_cred_def_json).unwrap(),
+            xyz_cred_def_id: serde_json::from_str::<CredentialDefinition>(&xyz_cred_def_json).unwrap()
+        }).to_string();
+        let rev_states_json = json!({}).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+        let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+
+        //10. Verifier verifies proof
+        assert_eq!("Alex", proof.requested_proof.revealed_attrs.get("attr1_referent").unwrap().raw);
+        assert_eq!("partial", proof.requested_proof.revealed_attrs.get("attr2_referent").unwrap().raw);
+
+        let rev_reg_defs_json = json!({}).to_string();
+        let rev_regs_json = json!({}).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //11. Credential cannot be requested from different wallets at once
+        let requested_credentials_json = json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":true }),
+                "attr2_referent": json!({ "cred_id": CREDENTIAL2_ID, "revealed":true, "wallet_handle": guardian_wallet_handle })
+             }),
+             "requested_predicates": json!({
+                "predicate1_referent": json!({ "cred_id": CREDENTIAL2_ID })
+             })
+        }).to_string();
+
+        let res = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                 &proof_req_json,
+                                                 &requested_credentials_json,
+                                                 COMMON_MASTER_SECRET,
+                                                 &schemas_json,
+                                                 &credential_defs_json,
+                                                 &rev_states_json);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        wallet::close_and_delete_wallet(guardian_wallet_handle, &guardian_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+    }
+
     #[test]
     fn anoncreds_works_for_single_issuer_multiple_credentials_single_prover() {
         Setup::empty();
@@ -426,4 +426,88 @@ mod high_cases {
             purge_cred_def_cache(setup.wallet_handle, &json!({"minFresh": 1000}).to_string()).unwrap();
         }
     }
-}
\ No newline at end of file
+
+    mod revocation_prefetch {
+        use super::*;
+        use crate::utils::{anoncreds, blob_storage};
+
+        #[test]
+        fn indy_start_stop_revocation_prefetch_works() {
+            let setup = Setup::wallet_and_pool();
+            let blob_storage_reader_handle = blob_storage::open_reader("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let prefetch_handle = start_revocation_prefetch(setup.pool_handle,
+                                                            setup.wallet_handle,
+                                                            blob_storage_reader_handle,
+                                                            &json!({"interval": 1, "freshness": 10, "maxRequests": 1, "maxCredentials": 1}).to_string()).unwrap();
+
+            stop_revocation_prefetch(prefetch_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_get_cached_revocation_state_works_for_not_prefetched() {
+            let setup = Setup::wallet();
+
+            let res = get_cached_revocation_state(setup.wallet_handle, "unknown_cred_id");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+}
+
+mod medium_cases {
+    use super::*;
+
+    mod revocation_prefetch {
+        use super::*;
+        use crate::utils::{anoncreds, blob_storage};
+
+        #[test]
+        fn indy_stop_revocation_prefetch_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = stop_revocation_prefetch(-1);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_start_revocation_prefetch_works_for_zero_interval() {
+            let setup = Setup::wallet_and_pool();
+            let blob_storage_reader_handle = blob_storage::open_reader("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let res = start_revocation_prefetch(setup.pool_handle,
+                                                setup.wallet_handle,
+                                                blob_storage_reader_handle,
+                                                &json!({"interval": 0}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_start_revocation_prefetch_works_for_zero_max_credentials() {
+            let setup = Setup::wallet_and_pool();
+            let blob_storage_reader_handle = blob_storage::open_reader("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let res = start_revocation_prefetch(setup.pool_handle,
+                                                setup.wallet_handle,
+                                                blob_storage_reader_handle,
+                                                &json!({"maxCredentials": 0}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_start_revocation_prefetch_works_for_invalid_blob_storage_reader_handle() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = start_revocation_prefetch(setup.pool_handle, setup.wallet_handle, -1, "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_start_revocation_prefetch_works_for_invalid_wallet_handle() {
+            let setup = Setup::pool();
+            let blob_storage_reader_handle = blob_storage::open_reader("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let res = start_revocation_prefetch(setup.pool_handle, INVALID_WALLET_HANDLE, blob_storage_reader_handle, "{}");
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+}
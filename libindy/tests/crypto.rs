@@ -6,15 +6,87 @@ inject_indy_dependencies!();
 extern crate indyrs as indy;
 extern crate indyrs as api;
 
-use crate::utils::crypto;
+use crate::utils::{cache, crypto, non_secrets};
 use crate::utils::constants::*;
 use crate::utils::Setup;
 
-use self::indy::ErrorCode;
+use self::indy::{ErrorCode, INVALID_POOL_HANDLE};
 
 pub const ENCRYPTED_MESSAGE: &'static [u8; 45] = &[187, 227, 10, 29, 46, 178, 12, 179, 197, 69, 171, 70, 228, 204, 52, 22, 199, 54, 62, 13, 115, 5, 216, 66, 20, 131, 121, 29, 251, 224, 253, 201, 75, 73, 225, 237, 219, 133, 35, 217, 131, 135, 232, 129, 32];
 pub const SIGNATURE: &'static [u8; 64] = &[169, 215, 8, 225, 7, 107, 110, 9, 193, 162, 202, 214, 162, 66, 238, 211, 63, 209, 12, 196, 8, 211, 55, 27, 120, 94, 204, 147, 53, 104, 103, 61, 60, 249, 237, 127, 103, 46, 220, 223, 10, 95, 75, 53, 245, 210, 241, 151, 191, 41, 48, 30, 9, 16, 78, 252, 157, 206, 210, 145, 125, 133, 109, 11];
 
+mod test_key_vault {
+    extern crate indy_sys;
+
+    use super::*;
+    use self::indy_sys::{BString, CString as CStringPtr, CVoid, Error};
+    use sodiumoxide::crypto::sign;
+    use std::collections::HashMap;
+    use std::ffi::CStr;
+    use std::sync::Mutex;
+
+    pub const KEY_VAULT: &'static str = "test_key_vault";
+    pub const KEY_REF: &'static str = "trustee_key";
+
+    lazy_static! {
+        static ref DATA: Mutex<HashMap<usize, Vec<u8>>> = Mutex::new(HashMap::new());
+    }
+
+    fn _keep(data: Vec<u8>) -> *const u8 {
+        let ptr = data.as_ptr();
+        DATA.lock().unwrap().insert(ptr as usize, data);
+        ptr
+    }
+
+    fn _secret_key(key_ref: CStringPtr) -> Option<sign::SecretKey> {
+        let key_ref = unsafe { CStr::from_ptr(key_ref) }.to_str().unwrap();
+
+        if key_ref != KEY_REF {
+            return None;
+        }
+
+        let seed = sign::Seed::from_slice(TRUSTEE_SEED.as_bytes()).unwrap();
+        Some(sign::keypair_from_seed(&seed).1)
+    }
+
+    extern fn get_verkey(key_ref: CStringPtr, verkey_p: *mut CStringPtr) -> Error {
+        if _secret_key(key_ref).is_none() {
+            return ErrorCode::WalletItemNotFound as Error;
+        }
+
+        let mut verkey = VERKEY_TRUSTEE.as_bytes().to_vec();
+        verkey.push(0);
+
+        unsafe { *verkey_p = _keep(verkey) as CStringPtr; }
+        ErrorCode::Success as Error
+    }
+
+    extern fn sign(key_ref: CStringPtr, message_raw: BString, message_len: u32, signature_raw_p: *mut BString, signature_len_p: *mut u32) -> Error {
+        let sk = match _secret_key(key_ref) {
+            Some(sk) => sk,
+            None => return ErrorCode::WalletItemNotFound as Error
+        };
+
+        let message = unsafe { ::std::slice::from_raw_parts(message_raw, message_len as usize) };
+        let signature = sign::sign_detached(message, &sk)[..].to_vec();
+
+        unsafe {
+            *signature_len_p = signature.len() as u32;
+            *signature_raw_p = _keep(signature);
+        }
+        ErrorCode::Success as Error
+    }
+
+    extern fn free(data: *const CVoid) -> Error {
+        DATA.lock().unwrap().remove(&(data as usize));
+        ErrorCode::Success as Error
+    }
+
+    pub fn register() {
+        crypto::register_key_vault(KEY_VAULT, Some(get_verkey), Some(sign), Some(free)).unwrap();
+    }
+}
+
 mod high_cases {
     use super::*;
 
@@ -35,6 +107,64 @@ mod high_cases {
             let verkey = crypto::create_key(setup.wallet_handle, None).unwrap();
             assert_eq!(verkey.from_base58().unwrap().len(), 32);
         }
+
+        #[test]
+        fn indy_create_key_works_for_key_vault() {
+            let setup = Setup::wallet();
+            test_key_vault::register();
+
+            let verkey = crypto::create_vault_key(setup.wallet_handle, test_key_vault::KEY_VAULT, test_key_vault::KEY_REF).unwrap();
+            assert_eq!(VERKEY_TRUSTEE, verkey);
+        }
+
+        #[test]
+        fn indy_create_key_works_for_entropy_source() {
+            use sodiumoxide::randombytes::randombytes_into;
+            use std::sync::atomic::{AtomicUsize, Ordering};
+
+            static FILLED: AtomicUsize = AtomicUsize::new(0);
+
+            extern fn fill(buf: *mut u8, len: u32) -> i32 {
+                randombytes_into(unsafe { ::std::slice::from_raw_parts_mut(buf, len as usize) });
+                FILLED.fetch_add(1, Ordering::SeqCst);
+                ErrorCode::Success as i32
+            }
+
+            let setup = Setup::wallet();
+            crypto::register_entropy_source(Some(fill)).unwrap();
+
+            let verkey = crypto::create_key(setup.wallet_handle, None).unwrap();
+            assert_eq!(verkey.from_base58().unwrap().len(), 32);
+            assert!(FILLED.load(Ordering::SeqCst) > 0);
+        }
+    }
+
+    mod create_bls_key {
+        use super::*;
+        use rust_base58::FromBase58;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_create_bls_key_works_for_seed() {
+            Setup::empty();
+
+            let bls_key: Value = serde_json::from_str(&crypto::create_bls_key(Some(MY1_SEED)).unwrap()).unwrap();
+            assert_eq!(bls_key["blskey"].as_str().unwrap().from_base58().unwrap().len(), 128);
+            assert!(bls_key["blskey_pop"].is_string());
+            assert!(bls_key["signkey"].is_string());
+
+            let bls_key_2: Value = serde_json::from_str(&crypto::create_bls_key(Some(MY1_SEED)).unwrap()).unwrap();
+            assert_eq!(bls_key, bls_key_2);
+        }
+
+        #[test]
+        fn indy_create_bls_key_works_without_seed() {
+            Setup::empty();
+
+            let bls_key_1: Value = serde_json::from_str(&crypto::create_bls_key(None).unwrap()).unwrap();
+            let bls_key_2: Value = serde_json::from_str(&crypto::create_bls_key(None).unwrap()).unwrap();
+            assert_ne!(bls_key_1["blskey"], bls_key_2["blskey"]);
+        }
     }
 
     mod set_key_metadata {
@@ -83,6 +213,103 @@ mod high_cases {
         }
     }
 
+    mod get_key_derivation_path {
+        use super::*;
+        use crate::utils::did;
+
+        const MASTER_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+        const DERIVATION_PATH: &str = "m/44'/0'/0'";
+
+        #[test]
+        fn indy_get_key_derivation_path_works() {
+            let setup = Setup::wallet();
+
+            let verkey = crypto::create_key_with_derivation_path(setup.wallet_handle, MASTER_SEED, DERIVATION_PATH).unwrap();
+
+            let derivation_path = crypto::get_key_derivation_path(setup.wallet_handle, &verkey).unwrap();
+            assert_eq!(DERIVATION_PATH, derivation_path);
+        }
+
+        #[test]
+        fn indy_get_key_derivation_path_works_for_did() {
+            let setup = Setup::wallet();
+
+            let verkey = crypto::create_key_with_derivation_path(setup.wallet_handle, MASTER_SEED, DERIVATION_PATH).unwrap();
+
+            let my_did_json = json!({"seed": MASTER_SEED, "derivation_path": DERIVATION_PATH, "seed_encoding": "hex"}).to_string();
+            let (_, did_verkey) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+            assert_eq!(verkey, did_verkey);
+
+            let other_did_json = json!({"seed": MASTER_SEED, "derivation_path": "m/44'/0'/1'", "seed_encoding": "hex"}).to_string();
+            let (_, other_verkey) = did::create_my_did(setup.wallet_handle, &other_did_json).unwrap();
+            assert_ne!(verkey, other_verkey);
+            assert_eq!("m/44'/0'/1'", crypto::get_key_derivation_path(setup.wallet_handle, &other_verkey).unwrap());
+        }
+
+        #[test]
+        fn indy_get_key_derivation_path_works_for_metadata_set() {
+            let setup = Setup::wallet();
+
+            let verkey = crypto::create_key_with_derivation_path(setup.wallet_handle, MASTER_SEED, DERIVATION_PATH).unwrap();
+            crypto::set_key_metadata(setup.wallet_handle, &verkey, METADATA).unwrap();
+
+            assert_eq!(DERIVATION_PATH, crypto::get_key_derivation_path(setup.wallet_handle, &verkey).unwrap());
+            assert_eq!(METADATA, crypto::get_key_metadata(setup.wallet_handle, &verkey).unwrap());
+        }
+
+        #[test]
+        fn indy_get_key_derivation_path_works_for_not_derived_key() {
+            let setup = Setup::key();
+
+            let res = crypto::get_key_derivation_path(setup.wallet_handle, &setup.verkey);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod get_key_agreement_key {
+        use super::*;
+
+        #[test]
+        fn indy_get_key_agreement_key_works() {
+            let setup = Setup::key();
+
+            let key_agreement_key = crypto::get_key_agreement_key(setup.wallet_handle, &setup.verkey).unwrap();
+            assert!(key_agreement_key.ends_with(":x25519"));
+            assert_ne!(setup.verkey, key_agreement_key);
+
+            let key_agreement_key_2 = crypto::get_key_agreement_key(setup.wallet_handle, &setup.verkey).unwrap();
+            assert_eq!(key_agreement_key, key_agreement_key_2);
+        }
+
+        #[test]
+        fn indy_get_key_agreement_key_works_for_unknown_key() {
+            let setup = Setup::wallet();
+
+            let res = crypto::get_key_agreement_key(setup.wallet_handle, VERKEY_MY1);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod convert_key {
+        use super::*;
+
+        #[test]
+        fn indy_convert_key_works() {
+            let setup = Setup::key();
+
+            let key = crypto::convert_key(&setup.verkey).unwrap();
+            assert_eq!(crypto::get_key_agreement_key(setup.wallet_handle, &setup.verkey).unwrap(), key);
+        }
+
+        #[test]
+        fn indy_convert_key_works_for_key_not_in_wallet() {
+            Setup::empty();
+
+            let key = crypto::convert_key(VERKEY_MY1).unwrap();
+            assert!(key.ends_with(":x25519"));
+        }
+    }
+
     mod crypto_sign {
         use super::*;
 
@@ -96,12 +323,37 @@ mod high_cases {
             assert_eq!(SIGNATURE.to_vec(), signature);
         }
 
+        #[test]
+        fn indy_crypto_sign_works_for_ecdsa_crypto_types() {
+            let setup = Setup::wallet();
+
+            for crypto_type in &["secp256k1", "p256"] {
+                let my_vk = crypto::create_key_with_crypto_type(setup.wallet_handle, Some(MY1_SEED), crypto_type).unwrap();
+                assert!(my_vk.ends_with(&format!(":{}", crypto_type)));
+
+                let signature = crypto::sign(setup.wallet_handle, &my_vk, MESSAGE.as_bytes()).unwrap();
+                assert!(crypto::verify(&my_vk, MESSAGE.as_bytes(), &signature).unwrap());
+                assert!(!crypto::verify(&my_vk, "other message".as_bytes(), &signature).unwrap());
+            }
+        }
+
         #[test]
         fn indy_crypto_sign_works_for_unknown_signer() {
             let setup = Setup::wallet();
             let res = crypto::sign(setup.wallet_handle, VERKEY, MESSAGE.as_bytes());
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
+
+        #[test]
+        fn indy_crypto_sign_works_for_key_vault() {
+            let setup = Setup::wallet();
+            test_key_vault::register();
+
+            let verkey = crypto::create_vault_key(setup.wallet_handle, test_key_vault::KEY_VAULT, test_key_vault::KEY_REF).unwrap();
+
+            let signature = crypto::sign(setup.wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap();
+            assert!(crypto::verify(&verkey, MESSAGE.as_bytes(), &signature).unwrap());
+        }
     }
 
     mod crypto_verify {
@@ -127,6 +379,50 @@ mod high_cases {
         }
     }
 
+    mod crypto_verify_with_strictness {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_verify_with_strictness_works() {
+            assert!(crypto::verify_with_strictness(&VERKEY_MY1, MESSAGE.as_bytes(), SIGNATURE, true).unwrap());
+            assert!(crypto::verify_with_strictness(&VERKEY_MY1, MESSAGE.as_bytes(), SIGNATURE, false).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_verify_with_strictness_works_for_other_signer() {
+            assert!(!crypto::verify_with_strictness(&VERKEY_MY2, MESSAGE.as_bytes(), SIGNATURE, true).unwrap());
+            assert!(!crypto::verify_with_strictness(&VERKEY_MY2, MESSAGE.as_bytes(), SIGNATURE, false).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_verify_with_strictness_works_for_invalid_signature_len() {
+            let signature: Vec<u8> = vec![20, 191, 100, 213, 101, 12, 197, 198, 203, 49, 89, 220, 205, 192, 224, 221, 97, 77, 220, 190];
+
+            let res = crypto::verify_with_strictness(&VERKEY_MY1, MESSAGE.as_bytes(), &signature, true);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            assert!(!crypto::verify_with_strictness(&VERKEY_MY1, MESSAGE.as_bytes(), &signature, false).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_verify_with_strictness_works_for_invalid_verkey() {
+            let res = crypto::verify_with_strictness(INVALID_BASE58_VERKEY, MESSAGE.as_bytes(), SIGNATURE, true);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            assert!(!crypto::verify_with_strictness(INVALID_BASE58_VERKEY, MESSAGE.as_bytes(), SIGNATURE, false).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_verify_with_strictness_works_for_unknown_crypto_type() {
+            let verkey = format!("{}:unknown_crypto", VERKEY_MY1);
+
+            let res = crypto::verify_with_strictness(&verkey, MESSAGE.as_bytes(), SIGNATURE, true);
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+
+            assert!(!crypto::verify_with_strictness(&verkey, MESSAGE.as_bytes(), SIGNATURE, false).unwrap());
+        }
+    }
+
     mod auth_crypt {
         use super::*;
 
@@ -211,6 +507,47 @@ mod high_cases {
         }
     }
 
+    mod crypto_box {
+        use super::*;
+
+        const NONCE: [u8; 24] = [7; 24];
+
+        #[test]
+        fn indy_crypto_box_works() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let encrypted_msg = crypto::crypto_box(sender_setup.wallet_handle, &sender_setup.verkey, &receiver_setup.verkey, MESSAGE.as_bytes(), &NONCE).unwrap();
+
+            // The same nonce gives the same ciphertext
+            let encrypted_msg_2 = crypto::crypto_box(sender_setup.wallet_handle, &sender_setup.verkey, &receiver_setup.verkey, MESSAGE.as_bytes(), &NONCE).unwrap();
+            assert_eq!(encrypted_msg, encrypted_msg_2);
+
+            let msg = crypto::crypto_box_open(receiver_setup.wallet_handle, &receiver_setup.verkey, &sender_setup.verkey, &encrypted_msg, &NONCE).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+        }
+
+        #[test]
+        fn indy_crypto_box_works_for_converted_key() {
+            let sender_setup = Setup::key();
+
+            let recipient_key = crypto::convert_key(VERKEY_MY2).unwrap();
+            crypto::crypto_box(sender_setup.wallet_handle, &sender_setup.verkey, &recipient_key, MESSAGE.as_bytes(), &NONCE).unwrap();
+        }
+
+        #[test]
+        fn indy_crypto_box_open_works_for_converted_sender_key() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let encrypted_msg = crypto::crypto_box(sender_setup.wallet_handle, &sender_setup.verkey, &receiver_setup.verkey, MESSAGE.as_bytes(), &NONCE).unwrap();
+
+            let sender_key = crypto::convert_key(&sender_setup.verkey).unwrap();
+            let msg = crypto::crypto_box_open(receiver_setup.wallet_handle, &receiver_setup.verkey, &sender_key, &encrypted_msg, &NONCE).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+        }
+    }
+
     mod pack_message_authcrypt {
         use super::*;
 
@@ -239,6 +576,99 @@ mod high_cases {
         }
     }
 
+    mod pack_message_with_mode {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_pack_message_with_mode_works_for_hidden_sender() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE, &receiver_setup.verkey]).unwrap();
+            let pack_message = crypto::pack_message_with_mode(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys,
+                                                              Some(&sender_setup.verkey), "authcrypt_with_hidden_sender").unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap();
+            let res: Value = serde_json::from_slice(res.as_slice()).unwrap();
+
+            assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+            assert_eq!(sender_setup.verkey, res["sender_verkey"].as_str().unwrap());
+            assert_eq!(receiver_setup.verkey, res["recipient_verkey"].as_str().unwrap());
+            assert_eq!("authcrypt_with_hidden_sender", res["mode"].as_str().unwrap());
+            assert_eq!(true, res["sender_authenticated"].as_bool().unwrap());
+        }
+
+        #[test]
+        fn indy_pack_message_with_mode_works_for_anoncrypt() {
+            let sender_setup = Setup::wallet();
+            let receiver_setup = Setup::key();
+
+            let receiver_keys = serde_json::to_string(&vec![&receiver_setup.verkey]).unwrap();
+            let pack_message = crypto::pack_message_with_mode(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None, "anoncrypt").unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap();
+            let res: Value = serde_json::from_slice(res.as_slice()).unwrap();
+
+            assert_eq!("anoncrypt", res["mode"].as_str().unwrap());
+            assert_eq!(false, res["sender_authenticated"].as_bool().unwrap());
+            assert!(res["sender_verkey"].is_null());
+        }
+    }
+
+    mod pack_message_for_dids {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_pack_message_for_dids_works() {
+            let setup = Setup::new_identity();
+
+            let receiver_dids = json!([setup.did]).to_string();
+            let pack_message = crypto::pack_message_for_dids(setup.pool_handle, setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_dids, None, "{}").unwrap();
+
+            let res = crypto::unpack_message(setup.wallet_handle, pack_message.as_slice()).unwrap();
+            let res: Value = serde_json::from_slice(res.as_slice()).unwrap();
+
+            assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+            assert_eq!(setup.verkey, res["recipient_verkey"].as_str().unwrap());
+        }
+
+        #[test]
+        fn indy_pack_message_for_dids_works_for_cached_key() {
+            let setup = Setup::new_identity();
+
+            let receiver_dids = json!([setup.did]).to_string();
+            crypto::pack_message_for_dids(setup.pool_handle, setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_dids, None, "{}").unwrap();
+
+            // Key is cached for the pool it was resolved from
+            let record = non_secrets::get_wallet_record(setup.wallet_handle, "nym_cache", &format!("{}:{}", setup.name, setup.did), "{}").unwrap();
+            let record: Value = serde_json::from_str(&record).unwrap();
+            assert_eq!(setup.verkey, record["value"].as_str().unwrap());
+
+            let options = json!({"minFresh": -1}).to_string();
+            let pack_message = crypto::pack_message_for_dids(setup.pool_handle, setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_dids, None, &options).unwrap();
+
+            let res = crypto::unpack_message(setup.wallet_handle, pack_message.as_slice()).unwrap();
+            let res: Value = serde_json::from_slice(res.as_slice()).unwrap();
+
+            assert_eq!(setup.verkey, res["recipient_verkey"].as_str().unwrap());
+        }
+
+        #[test]
+        fn indy_purge_nym_cache_works() {
+            let setup = Setup::new_identity();
+
+            let receiver_dids = json!([setup.did]).to_string();
+            crypto::pack_message_for_dids(setup.pool_handle, setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_dids, None, "{}").unwrap();
+
+            cache::purge_nym_cache(setup.wallet_handle, "{}").unwrap();
+
+            let res = non_secrets::get_wallet_record(setup.wallet_handle, "nym_cache", &format!("{}:{}", setup.name, setup.did), "{}");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
     mod unpack_message_authcrypt {
         use super::*;
 
@@ -269,6 +699,41 @@ mod high_cases {
             assert_eq!(res_serialized.recipient_verkey, receiver_setup.verkey);
         }
 
+        #[test]
+        fn indy_unpack_message_authcrypt_works_for_key_agreement_key() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let key_agreement_key = crypto::get_key_agreement_key(receiver_setup.wallet_handle, &receiver_setup.verkey).unwrap();
+
+            let receiver_keys = serde_json::to_string(&vec![&key_agreement_key]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, Some(&sender_setup.verkey)).unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap();
+            let res_serialized: UnpackMessage = serde_json::from_slice(res.as_slice()).unwrap();
+
+            assert_eq!(res_serialized.message, AGENT_MESSAGE.to_string());
+            assert_eq!(res_serialized.sender_verkey, sender_setup.verkey);
+            assert_eq!(res_serialized.recipient_verkey, key_agreement_key);
+        }
+
+        #[test]
+        fn indy_unpack_message_authcrypt_works_for_converted_key() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let key_agreement_key = crypto::convert_key(&receiver_setup.verkey).unwrap();
+
+            let receiver_keys = serde_json::to_string(&vec![&key_agreement_key]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, Some(&sender_setup.verkey)).unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap();
+            let res_serialized: UnpackMessage = serde_json::from_slice(res.as_slice()).unwrap();
+
+            assert_eq!(res_serialized.message, AGENT_MESSAGE.to_string());
+            assert_eq!(res_serialized.recipient_verkey, key_agreement_key);
+        }
+
         #[test]
         fn indy_unpack_message_authcrypt_fails_no_matching_key() {
             //Test Setup
@@ -310,6 +775,23 @@ mod high_cases {
             assert_eq!(res_serialized.recipient_verkey, receiver_setup.verkey);
         }
 
+        #[test]
+        fn indy_unpack_message_anoncrypt_works_for_key_agreement_key() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let key_agreement_key = crypto::get_key_agreement_key(receiver_setup.wallet_handle, &receiver_setup.verkey).unwrap();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE, &key_agreement_key]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap();
+            let res_serialized: UnpackMessage = serde_json::from_slice(res.as_slice()).unwrap();
+
+            assert_eq!(res_serialized.message, AGENT_MESSAGE.to_string());
+            assert_eq!(res_serialized.recipient_verkey, key_agreement_key);
+        }
+
         #[test]
         fn indy_unpack_message_anoncrypt_fails_no_matching_key() {
             //Test Setup
@@ -326,6 +808,79 @@ mod high_cases {
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
     }
+
+    mod dead_letters {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_list_dead_letters_works_for_failed_unpack() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            let dead_letters = crypto::list_dead_letters(receiver_setup.wallet_handle).unwrap();
+            let dead_letters: Vec<Value> = serde_json::from_str(&dead_letters).unwrap();
+
+            assert_eq!(1, dead_letters.len());
+            assert_eq!(serde_json::from_slice::<Value>(&pack_message).unwrap(), dead_letters[0]["message"]);
+            assert_eq!(1, dead_letters[0]["attempts"].as_u64().unwrap());
+        }
+
+        #[test]
+        fn indy_list_dead_letters_works_for_empty_wallet() {
+            let setup = Setup::wallet();
+
+            let dead_letters = crypto::list_dead_letters(setup.wallet_handle).unwrap();
+            assert_eq!("[]", dead_letters);
+        }
+
+        #[test]
+        fn indy_retry_dead_letter_works_after_adding_key() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::wallet();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap_err();
+
+            let dead_letters = crypto::list_dead_letters(receiver_setup.wallet_handle).unwrap();
+            let dead_letters: Vec<Value> = serde_json::from_str(&dead_letters).unwrap();
+            let dead_letter_id = dead_letters[0]["id"].as_str().unwrap();
+
+            crypto::create_key(receiver_setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let res = crypto::retry_dead_letter(receiver_setup.wallet_handle, dead_letter_id).unwrap();
+            let res: Value = serde_json::from_slice(&res).unwrap();
+            assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+            assert_eq!(VERKEY_TRUSTEE, res["recipient_verkey"].as_str().unwrap());
+
+            let dead_letters = crypto::list_dead_letters(receiver_setup.wallet_handle).unwrap();
+            assert_eq!("[]", dead_letters);
+        }
+
+        #[test]
+        fn indy_purge_dead_letters_works() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::wallet();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap_err();
+
+            crypto::purge_dead_letters(receiver_setup.wallet_handle, None).unwrap();
+
+            let dead_letters = crypto::list_dead_letters(receiver_setup.wallet_handle).unwrap();
+            assert_eq!("[]", dead_letters);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -343,6 +898,33 @@ mod medium_cases {
             let res = crypto::create_key(INVALID_WALLET_HANDLE, None);
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_create_key_works_for_unknown_key_vault() {
+            let setup = Setup::wallet();
+            let res = crypto::create_vault_key(setup.wallet_handle, "unknown_key_vault", test_key_vault::KEY_REF);
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+
+        #[test]
+        fn indy_create_key_works_for_unknown_key_ref() {
+            let setup = Setup::wallet();
+            test_key_vault::register();
+
+            let res = crypto::create_vault_key(setup.wallet_handle, test_key_vault::KEY_VAULT, "unknown_key_ref");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod create_bls_key {
+        use super::*;
+
+        #[test]
+        fn indy_create_bls_key_works_for_invalid_seed() {
+            Setup::empty();
+            let res = crypto::create_bls_key(Some("invalid_seed"));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
     }
 
     mod set_key_metadata {
@@ -511,6 +1093,18 @@ mod medium_cases {
             let res = crypto::anon_decrypt(INVALID_WALLET_HANDLE, &setup.verkey, &encrypted_msg);
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_crypto_anon_decrypt_works_for_key_vault() {
+            let setup = Setup::wallet();
+            test_key_vault::register();
+
+            let verkey = crypto::create_vault_key(setup.wallet_handle, test_key_vault::KEY_VAULT, test_key_vault::KEY_REF).unwrap();
+            let encrypted_msg = crypto::anon_crypt(&verkey, MESSAGE.as_bytes()).unwrap();
+
+            let res = crypto::anon_decrypt(setup.wallet_handle, &verkey, &encrypted_msg);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
     }
 
     mod pack_message_authcrypt {
@@ -588,6 +1182,173 @@ mod medium_cases {
             assert!(res.is_ok());
         }
     }
+
+    mod crypto_box {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_box_fails_for_invalid_nonce() {
+            let setup = Setup::key();
+
+            let res = crypto::crypto_box(setup.wallet_handle, &setup.verkey, VERKEY_MY2, MESSAGE.as_bytes(), &[1, 2, 3]);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_box_open_fails_for_wrong_nonce() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let encrypted_msg = crypto::crypto_box(sender_setup.wallet_handle, &sender_setup.verkey, &receiver_setup.verkey, MESSAGE.as_bytes(), &[1; 24]).unwrap();
+
+            let res = crypto::crypto_box_open(receiver_setup.wallet_handle, &receiver_setup.verkey, &sender_setup.verkey, &encrypted_msg, &[2; 24]);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod pack_message_with_mode {
+        use super::*;
+
+        #[test]
+        fn indy_pack_message_with_mode_fails_for_unknown_mode() {
+            let setup = Setup::key();
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let res = crypto::pack_message_with_mode(setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, Some(&setup.verkey), "unknown");
+            assert_code!(ErrorCode::CommonInvalidParam6, res);
+        }
+
+        #[test]
+        fn indy_pack_message_with_mode_fails_for_anoncrypt_with_sender() {
+            let setup = Setup::key();
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let res = crypto::pack_message_with_mode(setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, Some(&setup.verkey), "anoncrypt");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_pack_message_with_mode_fails_for_hidden_sender_without_sender() {
+            let setup = Setup::key();
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let res = crypto::pack_message_with_mode(setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None, "authcrypt_with_hidden_sender");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod pack_message_for_dids {
+        use super::*;
+
+        #[test]
+        fn indy_pack_message_for_dids_fails_for_unknown_did() {
+            let setup = Setup::wallet_and_pool();
+
+            let receiver_dids = json!([DID]).to_string();
+            let res = crypto::pack_message_for_dids(setup.pool_handle, setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_dids, None, "{}");
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_pack_message_for_dids_fails_for_no_cached_key_and_invalid_pool_handle() {
+            let setup = Setup::wallet();
+
+            let receiver_dids = json!([DID]).to_string();
+            let res = crypto::pack_message_for_dids(INVALID_POOL_HANDLE, setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_dids, None, "{}");
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+
+        #[test]
+        fn indy_pack_message_for_dids_fails_no_receivers() {
+            let setup = Setup::wallet();
+
+            let res = crypto::pack_message_for_dids(INVALID_POOL_HANDLE, setup.wallet_handle, AGENT_MESSAGE.as_bytes(), "[]", None, "{}");
+            assert_code!(ErrorCode::CommonInvalidParam6, res);
+        }
+    }
+
+    mod dead_letters {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_retry_dead_letter_fails_for_unknown_id() {
+            let setup = Setup::wallet();
+
+            let res = crypto::retry_dead_letter(setup.wallet_handle, "unknown_id");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_retry_dead_letter_keeps_message_on_failure() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::wallet();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap_err();
+
+            let dead_letters = crypto::list_dead_letters(receiver_setup.wallet_handle).unwrap();
+            let dead_letters: Vec<Value> = serde_json::from_str(&dead_letters).unwrap();
+            let dead_letter_id = dead_letters[0]["id"].as_str().unwrap();
+
+            let res = crypto::retry_dead_letter(receiver_setup.wallet_handle, dead_letter_id);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            let dead_letters = crypto::list_dead_letters(receiver_setup.wallet_handle).unwrap();
+            let dead_letters: Vec<Value> = serde_json::from_str(&dead_letters).unwrap();
+            assert_eq!(1, dead_letters.len());
+            assert_eq!(2, dead_letters[0]["attempts"].as_u64().unwrap());
+        }
+
+        #[test]
+        fn indy_list_dead_letters_fails_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = crypto::list_dead_letters(INVALID_WALLET_HANDLE);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_purge_dead_letters_keeps_newer_ones() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::wallet();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap_err();
+
+            crypto::purge_dead_letters(receiver_setup.wallet_handle, Some(1)).unwrap();
+
+            let dead_letters = crypto::list_dead_letters(receiver_setup.wallet_handle).unwrap();
+            let dead_letters: Vec<Value> = serde_json::from_str(&dead_letters).unwrap();
+            assert_eq!(1, dead_letters.len());
+        }
+
+        #[test]
+        fn indy_unpack_message_keeps_dead_letters_bounded() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::wallet();
+
+            let receiver_keys = serde_json::to_string(&vec![VERKEY_TRUSTEE]).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            for _ in 0..101 {
+                crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap_err();
+            }
+
+            let dead_letters = crypto::list_dead_letters(receiver_setup.wallet_handle).unwrap();
+            let dead_letters: Vec<Value> = serde_json::from_str(&dead_letters).unwrap();
+            assert_eq!(100, dead_letters.len());
+        }
+
+        #[test]
+        fn indy_purge_dead_letters_fails_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = crypto::purge_dead_letters(INVALID_WALLET_HANDLE, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -6,7 +6,7 @@ inject_indy_dependencies!();
 extern crate indyrs as indy;
 extern crate indyrs as api;
 
-use crate::utils::{did, pool, ledger};
+use crate::utils::{did, pool, ledger, crypto};
 use crate::utils::constants::*;
 use crate::utils::types::ResponseType;
 use crate::utils::Setup;
@@ -458,6 +458,73 @@ mod high_cases {
         }
     }
 
+    mod create_my_dids {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_create_and_store_my_dids_works() {
+            let setup = Setup::wallet();
+
+            let dids = did::create_and_store_my_dids(setup.wallet_handle, 3, "{}").unwrap();
+            let dids: Vec<Value> = serde_json::from_str(&dids).unwrap();
+            assert_eq!(3, dids.len());
+
+            for created in dids.iter() {
+                let verkey = did::key_for_local_did(setup.wallet_handle, created["did"].as_str().unwrap()).unwrap();
+                assert_eq!(created["verkey"], verkey);
+                assert!(created.get("index").is_none());
+            }
+        }
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_hd_seed() {
+            let setup = Setup::wallet();
+
+            let options = json!({"hd_seed": MY1_SEED, "hd_seed_encoding": "utf8", "start_index": 5}).to_string();
+            let dids = did::create_and_store_my_dids(setup.wallet_handle, 2, &options).unwrap();
+            let dids: Vec<Value> = serde_json::from_str(&dids).unwrap();
+
+            assert_eq!(5, dids[0]["index"].as_u64().unwrap());
+            assert_eq!(6, dids[1]["index"].as_u64().unwrap());
+            assert_ne!(dids[0]["did"], dids[1]["did"]);
+
+            // DIDs are recreated from the same HD seed and index
+            let options = json!({"hd_seed": MY1_SEED, "hd_seed_encoding": "utf8", "start_index": 6}).to_string();
+            let recreated = did::create_and_store_my_dids(setup.wallet_handle, 1, &options).unwrap();
+            let recreated: Vec<Value> = serde_json::from_str(&recreated).unwrap();
+
+            assert_eq!(dids[1]["did"], recreated[0]["did"]);
+            assert_eq!(dids[1]["verkey"], recreated[0]["verkey"]);
+        }
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_hd_seed_same_as_derivation_path() {
+            let setup = Setup::wallet();
+
+            let options = json!({"hd_seed": MY1_SEED, "hd_seed_encoding": "utf8", "start_index": 3}).to_string();
+            let dids = did::create_and_store_my_dids(setup.wallet_handle, 1, &options).unwrap();
+            let dids: Vec<Value> = serde_json::from_str(&dids).unwrap();
+
+            let did_json = json!({"seed": MY1_SEED, "derivation_path": "m/3'", "seed_encoding": "utf8"}).to_string();
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(&did_json)).unwrap();
+
+            assert_eq!(dids[0]["did"].as_str().unwrap(), did);
+            assert_eq!(dids[0]["verkey"].as_str().unwrap(), verkey);
+        }
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_fully_qualified() {
+            let setup = Setup::wallet();
+
+            let options = json!({"method_name": DEFAULT_METHOD_NAME}).to_string();
+            let dids = did::create_and_store_my_dids(setup.wallet_handle, 2, &options).unwrap();
+            let dids: Vec<Value> = serde_json::from_str(&dids).unwrap();
+
+            assert!(dids.iter().all(|created| created["did"].as_str().unwrap().starts_with(DEFAULT_PREFIX)));
+        }
+    }
+
     mod replace_keys_start {
         use super::*;
 
@@ -647,6 +714,17 @@ mod high_cases {
 
             assert_eq!(verkey, full_verkey);
         }
+
+        #[test]
+        fn indy_abbreviate_verkey_works_for_secp256k1_key() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_my_did(setup.wallet_handle, r#"{"crypto_type":"secp256k1"}"#).unwrap();
+
+            // DID of ECDSA key is derived from the key hash, so the key can't be abbreviated
+            let abbr_verkey = did::abbreviate_verkey(&did, &verkey).unwrap();
+            assert_eq!(verkey, abbr_verkey);
+        }
     }
 
     mod qualify_did {
@@ -747,12 +825,490 @@ mod high_cases {
             }
         }
     }
+
+    mod did_ownership_proof {
+        use super::*;
+        use rust_base58::FromBase58;
+
+        const CHALLENGE: &str = "b3f6c7a1-0e56-4f1c-8d3a-27e0c3f9b1d2";
+        const AUDIENCE: &str = "did:sov:V4SGRU86Z58d6TV7PBUe6f";
+
+        #[test]
+        fn did_ownership_proof_works() {
+            let setup = Setup::trustee();
+
+            let proof_json = did::create_did_ownership_proof(setup.wallet_handle, setup.pool_handle, &setup.did, CHALLENGE, AUDIENCE).unwrap();
+            let proof: serde_json::Value = serde_json::from_str(&proof_json).unwrap();
+            assert_eq!(setup.did, proof["did"].as_str().unwrap());
+            assert_eq!(setup.verkey, proof["verkey"].as_str().unwrap());
+            assert!(proof["seqNo"].is_u64());
+
+            let valid = did::verify_did_ownership_proof(setup.pool_handle, &proof_json, CHALLENGE, AUDIENCE).unwrap();
+            assert!(valid);
+        }
+
+        #[test]
+        fn did_ownership_proof_works_for_other_challenge() {
+            let setup = Setup::trustee();
+
+            let proof_json = did::create_did_ownership_proof(setup.wallet_handle, setup.pool_handle, &setup.did, CHALLENGE, AUDIENCE).unwrap();
+
+            let valid = did::verify_did_ownership_proof(setup.pool_handle, &proof_json, "other challenge", AUDIENCE).unwrap();
+            assert!(!valid);
+        }
+
+        #[test]
+        fn did_ownership_proof_works_for_other_audience() {
+            let setup = Setup::trustee();
+
+            let proof_json = did::create_did_ownership_proof(setup.wallet_handle, setup.pool_handle, &setup.did, CHALLENGE, AUDIENCE).unwrap();
+            let mut proof: serde_json::Value = serde_json::from_str(&proof_json).unwrap();
+            proof["audience"] = json!("did:sov:other");
+
+            let valid = did::verify_did_ownership_proof(setup.pool_handle, &proof.to_string(), CHALLENGE, "did:sov:other").unwrap();
+            assert!(!valid);
+        }
+
+        #[test]
+        fn did_ownership_proof_signature_does_not_cover_bare_challenge() {
+            let setup = Setup::trustee();
+
+            let proof_json = did::create_did_ownership_proof(setup.wallet_handle, setup.pool_handle, &setup.did, CHALLENGE, AUDIENCE).unwrap();
+            let proof: serde_json::Value = serde_json::from_str(&proof_json).unwrap();
+            let signature = proof["signature"].as_str().unwrap().from_base58().unwrap();
+
+            let valid = crypto::verify(&setup.verkey, CHALLENGE.as_bytes(), &signature).unwrap();
+            assert!(!valid);
+        }
+
+        #[test]
+        fn did_ownership_proof_works_for_other_verkey() {
+            let setup = Setup::trustee();
+
+            let proof_json = did::create_did_ownership_proof(setup.wallet_handle, setup.pool_handle, &setup.did, CHALLENGE, AUDIENCE).unwrap();
+            let mut proof: serde_json::Value = serde_json::from_str(&proof_json).unwrap();
+            proof["verkey"] = json!(VERKEY);
+
+            let valid = did::verify_did_ownership_proof(setup.pool_handle, &proof.to_string(), CHALLENGE, AUDIENCE).unwrap();
+            assert!(!valid);
+        }
+
+        #[test]
+        fn create_did_ownership_proof_works_for_did_not_on_ledger() {
+            let setup = Setup::wallet_and_pool();
+
+            let (my_did, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let res = did::create_did_ownership_proof(setup.wallet_handle, setup.pool_handle, &my_did, CHALLENGE, AUDIENCE);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod check_my_did_nym {
+        use super::*;
+
+        use libc::{c_char, c_void};
+        use std::ffi::CStr;
+        use std::ptr;
+        use std::sync::Mutex;
+
+        lazy_static! {
+            static ref EVENTS: Mutex<Vec<serde_json::Value>> = Mutex::new(Vec::new());
+        }
+
+        extern fn event_listener(_context: *const c_void, event_json: *const c_char) {
+            let event_json = unsafe { CStr::from_ptr(event_json) }.to_str().unwrap();
+            EVENTS.lock().unwrap().push(serde_json::from_str(event_json).unwrap());
+        }
+
+        fn did_events(did: &str) -> Vec<serde_json::Value> {
+            EVENTS.lock().unwrap().iter().filter(|event| event["did"] == json!(did)).cloned().collect()
+        }
+
+        #[test]
+        fn check_my_did_nym_works() {
+            let setup = Setup::new_identity();
+
+            let nym_json = did::check_my_did_nym(setup.wallet_handle, setup.pool_handle, &setup.did).unwrap();
+            let nym: serde_json::Value = serde_json::from_str(&nym_json).unwrap();
+            assert_eq!(json!(setup.verkey), nym["verkey"]);
+            assert_eq!(json!("0"), nym["role"]);
+            assert_eq!(json!(false), nym["changed"]);
+        }
+
+        #[test]
+        fn check_my_did_nym_works_for_verkey_differs_from_ledger() {
+            let setup = Setup::new_identity();
+            assert_eq!(ErrorCode::Success, api::set_event_listener(ptr::null(), Some(event_listener)));
+
+            did::check_my_did_nym(setup.wallet_handle, setup.pool_handle, &setup.did).unwrap();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            let nym_json = did::check_my_did_nym(setup.wallet_handle, setup.pool_handle, &setup.did).unwrap();
+            let nym: serde_json::Value = serde_json::from_str(&nym_json).unwrap();
+            assert_eq!(json!(setup.verkey), nym["verkey"]);
+            assert_eq!(json!(true), nym["changed"]);
+
+            let events = did_events(&setup.did);
+            assert_eq!(1, events.len());
+            assert_eq!(json!("did_nym_changed"), events[0]["type"]);
+            assert_eq!(json!(new_verkey), events[0]["wallet_verkey"]);
+            assert_eq!(json!(setup.verkey), events[0]["previous_verkey"]);
+            assert_eq!(json!(setup.verkey), events[0]["verkey"]);
+        }
+
+        #[test]
+        fn check_my_did_nym_works_for_key_rotation_in_progress() {
+            let setup = Setup::new_identity();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            let nym = ledger::build_nym_request(&setup.did, &setup.did, Some(&new_verkey), None, None).unwrap();
+            ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &nym).unwrap();
+
+            let nym_json = did::check_my_did_nym(setup.wallet_handle, setup.pool_handle, &setup.did).unwrap();
+            let nym: serde_json::Value = serde_json::from_str(&nym_json).unwrap();
+            assert_eq!(json!(new_verkey), nym["verkey"]);
+            assert_eq!(json!(false), nym["changed"]);
+        }
+
+        #[test]
+        fn check_my_did_nym_works_for_did_not_on_ledger() {
+            let setup = Setup::wallet_and_pool();
+
+            let (my_did, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let res = did::check_my_did_nym(setup.wallet_handle, setup.pool_handle, &my_did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod publish_endpoint_for_did {
+        use super::*;
+
+        const SERVICE_ENDPOINT: &str = "https://agent.example.com:8080/messages";
+
+        #[test]
+        fn publish_endpoint_for_did_works() {
+            let setup = Setup::new_identity();
+
+            let routing_keys = json!([VERKEY_TRUSTEE]).to_string();
+            did::publish_endpoint_for_did(setup.wallet_handle, setup.pool_handle, &setup.did, SERVICE_ENDPOINT,
+                                          Some(r#"["did-communication"]"#), Some(&routing_keys)).unwrap();
+
+            let (endpoint, key) = did::get_endpoint_for_did(setup.wallet_handle, -1, &setup.did).unwrap();
+            assert_eq!(SERVICE_ENDPOINT, endpoint);
+            assert_eq!(None, key);
+
+            thread::sleep(std::time::Duration::from_secs(1));
+
+            let service_json = did::resolve_service_endpoint_for_did(setup.pool_handle, &setup.did).unwrap();
+            let service: serde_json::Value = serde_json::from_str(&service_json).unwrap();
+            assert_eq!(json!(format!("did:sov:{}#did-communication", setup.did)), service["id"]);
+            assert_eq!(json!("did-communication"), service["type"]);
+            assert_eq!(json!([setup.verkey]), service["recipientKeys"]);
+            assert_eq!(json!([VERKEY_TRUSTEE]), service["routingKeys"]);
+            assert_eq!(json!(SERVICE_ENDPOINT), service["serviceEndpoint"]);
+        }
+
+        #[test]
+        fn publish_endpoint_for_did_works_for_address_only() {
+            let setup = Setup::new_identity();
+
+            did::publish_endpoint_for_did(setup.wallet_handle, setup.pool_handle, &setup.did, SERVICE_ENDPOINT, None, None).unwrap();
+
+            thread::sleep(std::time::Duration::from_secs(1));
+
+            let service_json = did::resolve_service_endpoint_for_did(setup.pool_handle, &setup.did).unwrap();
+            let service: serde_json::Value = serde_json::from_str(&service_json).unwrap();
+            assert_eq!(json!([]), service["routingKeys"]);
+            assert_eq!(json!(SERVICE_ENDPOINT), service["serviceEndpoint"]);
+        }
+
+        #[test]
+        fn resolve_service_endpoint_for_did_works_for_legacy_endpoint_attrib() {
+            let setup = Setup::new_identity();
+
+            let attrib_data = json!({"endpoint": {"ha": ENDPOINT}}).to_string();
+            let attrib_request = ledger::build_attrib_request(&setup.did, &setup.did, None, Some(&attrib_data), None).unwrap();
+            ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &attrib_request).unwrap();
+
+            thread::sleep(std::time::Duration::from_secs(1));
+
+            let service_json = did::resolve_service_endpoint_for_did(setup.pool_handle, &setup.did).unwrap();
+            let service: serde_json::Value = serde_json::from_str(&service_json).unwrap();
+            assert_eq!(json!(ENDPOINT), service["serviceEndpoint"]);
+        }
+
+        #[test]
+        fn publish_endpoint_for_did_works_for_did_not_in_wallet() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::publish_endpoint_for_did(setup.wallet_handle, setup.pool_handle, DID, SERVICE_ENDPOINT, None, None);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod did_keys {
+        use super::*;
+
+        #[test]
+        fn indy_get_did_keys_works_for_default_keys() {
+            let setup = Setup::did();
+
+            let did_keys_json = did::get_did_keys(setup.wallet_handle, &setup.did).unwrap();
+            let did_keys: serde_json::Value = serde_json::from_str(&did_keys_json).unwrap();
+
+            let key_agreement_key = crypto::get_key_agreement_key(setup.wallet_handle, &setup.verkey).unwrap();
+
+            assert_eq!(json!({
+                "authentication": setup.verkey,
+                "assertionMethod": setup.verkey,
+                "keyAgreement": key_agreement_key,
+            }), did_keys);
+        }
+
+        #[test]
+        fn indy_set_did_key_works_for_assertion_method() {
+            let setup = Setup::did();
+
+            let verkey = crypto::create_key(setup.wallet_handle, None).unwrap();
+
+            did::set_did_key(setup.wallet_handle, &setup.did, "assertionMethod", &verkey).unwrap();
+
+            let did_keys_json = did::get_did_keys(setup.wallet_handle, &setup.did).unwrap();
+            let did_keys: serde_json::Value = serde_json::from_str(&did_keys_json).unwrap();
+
+            assert_eq!(json!(setup.verkey), did_keys["authentication"]);
+            assert_eq!(json!(verkey), did_keys["assertionMethod"]);
+        }
+
+        #[test]
+        fn indy_set_did_key_works_for_key_agreement() {
+            let setup = Setup::did();
+
+            let verkey = crypto::create_key(setup.wallet_handle, None).unwrap();
+            let key_agreement_key = crypto::get_key_agreement_key(setup.wallet_handle, &verkey).unwrap();
+
+            did::set_did_key(setup.wallet_handle, &setup.did, "keyAgreement", &key_agreement_key).unwrap();
+
+            let did_keys_json = did::get_did_keys(setup.wallet_handle, &setup.did).unwrap();
+            let did_keys: serde_json::Value = serde_json::from_str(&did_keys_json).unwrap();
+
+            assert_eq!(json!(key_agreement_key), did_keys["keyAgreement"]);
+        }
+
+        #[test]
+        fn indy_get_did_keys_works_for_unpacking_to_default_key_agreement_key() {
+            let setup = Setup::did();
+
+            let did_keys_json = did::get_did_keys(setup.wallet_handle, &setup.did).unwrap();
+            let did_keys: serde_json::Value = serde_json::from_str(&did_keys_json).unwrap();
+
+            let receivers = json!([did_keys["keyAgreement"]]).to_string();
+            let jwe = crypto::pack_message(setup.wallet_handle, b"message", &receivers, None).unwrap();
+
+            let unpacked = crypto::unpack_message(setup.wallet_handle, &jwe).unwrap();
+            let unpacked: serde_json::Value = serde_json::from_slice(&unpacked).unwrap();
+
+            assert_eq!(json!("message"), unpacked["message"]);
+        }
+    }
+
+    mod resolve_did {
+        use super::*;
+
+        const SERVICE_ENDPOINT: &str = "https://agent.example.com:8080/messages";
+
+        #[test]
+        fn resolve_did_works() {
+            let setup = Setup::new_identity();
+
+            did::publish_endpoint_for_did(setup.wallet_handle, setup.pool_handle, &setup.did, SERVICE_ENDPOINT, None, None).unwrap();
+
+            thread::sleep(std::time::Duration::from_secs(1));
+
+            let did_doc_json = did::resolve_did(setup.pool_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc_json).unwrap();
+
+            let did_url = format!("did:sov:{}", setup.did);
+            let key_id = format!("{}#key-1", did_url);
+
+            assert_eq!(json!("https://www.w3.org/ns/did/v1"), did_doc["@context"]);
+            assert_eq!(json!(did_url), did_doc["id"]);
+            assert_eq!(json!(key_id), did_doc["verificationMethod"][0]["id"]);
+            assert_eq!(json!("Ed25519VerificationKey2018"), did_doc["verificationMethod"][0]["type"]);
+            assert_eq!(json!(setup.verkey), did_doc["verificationMethod"][0]["publicKeyBase58"]);
+            assert_eq!(json!([key_id]), did_doc["authentication"]);
+            assert_eq!(json!([setup.verkey]), did_doc["service"][0]["recipientKeys"]);
+            assert_eq!(json!(SERVICE_ENDPOINT), did_doc["service"][0]["serviceEndpoint"]);
+            assert_eq!(json!("ledger:NYM"), did_doc["provenance"][&key_id]);
+            assert_eq!(json!("ledger:ATTRIB"), did_doc["provenance"][did_doc["service"][0]["id"].as_str().unwrap()]);
+        }
+
+        #[test]
+        fn resolve_did_works_for_endpoint_types() {
+            let setup = Setup::new_identity();
+
+            did::publish_endpoint_for_did(setup.wallet_handle, setup.pool_handle, &setup.did, SERVICE_ENDPOINT,
+                                          Some(r#"["DIDCommMessaging", "did-communication"]"#), None).unwrap();
+
+            thread::sleep(std::time::Duration::from_secs(1));
+
+            let did_doc_json = did::resolve_did(setup.pool_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc_json).unwrap();
+
+            assert_eq!(json!("DIDCommMessaging"), did_doc["service"][0]["type"]);
+            assert_eq!(json!(format!("did:sov:{}#DIDCommMessaging", setup.did)), did_doc["service"][0]["id"]);
+            assert_eq!(json!("did-communication"), did_doc["service"][1]["type"]);
+            assert_eq!(json!(SERVICE_ENDPOINT), did_doc["service"][1]["serviceEndpoint"]);
+
+            let service_json = did::resolve_service_endpoint_for_did(setup.pool_handle, &setup.did).unwrap();
+            let service: serde_json::Value = serde_json::from_str(&service_json).unwrap();
+            assert_eq!(json!("DIDCommMessaging"), service["type"]);
+        }
+
+        #[test]
+        fn resolve_did_works_for_pairwise() {
+            let setup = Setup::new_identity();
+
+            let (my_did, _) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+            did::store_their_did(setup.wallet_handle, &json!({"did": &setup.did, "verkey": &setup.verkey}).to_string()).unwrap();
+            utils::pairwise::create_pairwise(setup.wallet_handle, &setup.did, &my_did, Some(METADATA)).unwrap();
+
+            let did_doc_json = did::resolve_did_with_wallet(setup.pool_handle, setup.wallet_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc_json).unwrap();
+
+            assert_eq!(json!(setup.verkey), did_doc["verificationMethod"][0]["publicKeyBase58"]);
+            assert_eq!(json!({"myDid": my_did, "metadata": METADATA}), did_doc["pairwise"]);
+            assert_eq!(json!("wallet:pairwise"), did_doc["provenance"]["pairwise"]);
+
+            let did_doc_json = did::resolve_did(setup.pool_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc_json).unwrap();
+
+            assert!(did_doc["pairwise"].is_null());
+        }
+
+        #[test]
+        fn resolve_did_works_for_did_without_endpoint() {
+            let setup = Setup::new_identity();
+
+            let did_doc_json = did::resolve_did(setup.pool_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc_json).unwrap();
+
+            assert_eq!(json!(setup.verkey), did_doc["verificationMethod"][0]["publicKeyBase58"]);
+            assert_eq!(json!([]), did_doc["service"]);
+            assert!(did_doc["pairwise"].is_null());
+        }
+
+        #[test]
+        fn resolve_did_works_for_designated_keys() {
+            let setup = Setup::new_identity();
+
+            let verkey = crypto::create_key(setup.wallet_handle, None).unwrap();
+            did::set_did_key(setup.wallet_handle, &setup.did, "assertionMethod", &verkey).unwrap();
+
+            let nym_key_agreement_key = crypto::get_key_agreement_key(setup.wallet_handle, &setup.verkey).unwrap();
+
+            let did_doc_json = did::resolve_did(setup.pool_handle, &setup.did).unwrap();
+            let did_doc: serde_json::Value = serde_json::from_str(&did_doc_json).unwrap();
+
+            let did_url = format!("did:sov:{}", setup.did);
+            let nym_key_id = format!("{}#key-1", did_url);
+            let key_agreement_key_id = format!("{}#key-2", did_url);
+
+            // Designated keys aren't published, key agreement key is derived from NYM verkey
+            assert_eq!(2, did_doc["verificationMethod"].as_array().unwrap().len());
+            assert_eq!(json!([nym_key_id]), did_doc["assertionMethod"]);
+            assert_eq!(json!("X25519KeyAgreementKey2019"), did_doc["verificationMethod"][1]["type"]);
+            assert_eq!(json!(nym_key_agreement_key.split(':').next().unwrap()), did_doc["verificationMethod"][1]["publicKeyBase58"]);
+            assert_eq!(json!([key_agreement_key_id]), did_doc["keyAgreement"]);
+            assert_eq!(json!("ledger:NYM"), did_doc["provenance"][&key_agreement_key_id]);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
 mod medium_cases {
     use super::*;
 
+    mod check_my_did_nym {
+        use super::*;
+
+        #[test]
+        fn check_my_did_nym_works_for_unknown_did() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::check_my_did_nym(setup.wallet_handle, setup.pool_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn check_my_did_nym_works_for_invalid_wallet_handle() {
+            let setup = Setup::trustee();
+
+            let res = did::check_my_did_nym(INVALID_WALLET_HANDLE, setup.pool_handle, &setup.did);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod publish_endpoint_for_did {
+        use super::*;
+
+        #[test]
+        fn publish_endpoint_for_did_works_for_empty_address() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::publish_endpoint_for_did(setup.wallet_handle, setup.pool_handle, DID, "", None, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn publish_endpoint_for_did_works_for_invalid_routing_keys() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::publish_endpoint_for_did(setup.wallet_handle, setup.pool_handle, DID, ENDPOINT, None, Some("not a json"));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn resolve_service_endpoint_for_did_works_for_unknown_did() {
+            let setup = Setup::pool();
+
+            let res = did::resolve_service_endpoint_for_did(setup.pool_handle, DID_MY2);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+    }
+
+    mod resolve_did {
+        use super::*;
+
+        #[test]
+        fn resolve_did_works_for_unknown_did() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::resolve_did(setup.pool_handle, DID_MY2);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn resolve_did_works_for_invalid_did() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::resolve_did(setup.pool_handle, INVALID_BASE58_DID);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn resolve_did_with_wallet_works_for_invalid_wallet_handle() {
+            let setup = Setup::pool();
+
+            let res = did::resolve_did_with_wallet(setup.pool_handle, INVALID_WALLET_HANDLE, DID_TRUSTEE);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
     mod key_for_did {
         use super::*;
 
@@ -805,6 +1361,44 @@ mod medium_cases {
         }
     }
 
+    mod did_keys {
+        use super::*;
+
+        #[test]
+        fn indy_set_did_key_works_for_unknown_purpose() {
+            let setup = Setup::did();
+
+            let res = did::set_did_key(setup.wallet_handle, &setup.did, "capabilityInvocation", &setup.verkey);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_set_did_key_works_for_key_not_in_wallet() {
+            let setup = Setup::did();
+
+            let res = did::set_did_key(setup.wallet_handle, &setup.did, "authentication", VERKEY_MY2);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_set_did_key_works_for_not_key_agreement_key() {
+            let setup = Setup::did();
+
+            let res = did::set_did_key(setup.wallet_handle, &setup.did, "keyAgreement", &setup.verkey);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_get_did_keys_works_for_their_did() {
+            let setup = Setup::wallet();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID, VERKEY).unwrap();
+
+            let res = did::get_did_keys(setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
     mod get_did_metadata {
         use super::*;
 
@@ -863,6 +1457,60 @@ mod medium_cases {
         }
     }
 
+    mod create_my_dids {
+        use super::*;
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_zero_count() {
+            let setup = Setup::wallet();
+
+            let res = did::create_and_store_my_dids(setup.wallet_handle, 0, "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_too_short_hd_seed() {
+            let setup = Setup::wallet();
+
+            let options = json!({"hd_seed": "short", "hd_seed_encoding": "utf8"}).to_string();
+            let res = did::create_and_store_my_dids(setup.wallet_handle, 1, &options);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_hd_seed_and_ecdsa_crypto_type() {
+            let setup = Setup::wallet();
+
+            let options = json!({"hd_seed": MY1_SEED, "hd_seed_encoding": "utf8", "crypto_type": "secp256k1"}).to_string();
+            let res = did::create_and_store_my_dids(setup.wallet_handle, 1, &options);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_too_big_count() {
+            let setup = Setup::wallet();
+
+            let res = did::create_and_store_my_dids(setup.wallet_handle, 1001, "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_invalid_method_name() {
+            let setup = Setup::wallet();
+
+            let res = did::create_and_store_my_dids(setup.wallet_handle, 2, r#"{"method_name": "invalid-method"}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_and_store_my_dids_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::create_and_store_my_dids(INVALID_WALLET_HANDLE, 2, "{}");
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
     mod replace_keys_start {
         use super::*;
 
@@ -3,4 +3,9 @@ extern crate indyrs as indy;
 #[test]
 fn set_runtime_config_works() {
     indy::set_runtime_config(r#"{"crypto_thread_pool_size": 2}"#);
-}
\ No newline at end of file
+}
+
+#[test]
+fn cancel_command_works_for_not_pending_command() {
+    assert_eq!(indy::ErrorCode::Success, indy::cancel_command(12345));
+}
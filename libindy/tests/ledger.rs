@@ -266,6 +266,24 @@ mod high_cases {
             let res = ledger::multi_sign_request(setup.wallet_handle, DID, REQUEST);
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
+
+        #[test]
+        fn indy_multi_sign_request_with_dids_works() {
+            let setup = Setup::wallet();
+
+            let (did1, _) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (did2, _) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let dids = json!([did1, did2]).to_string();
+            let message = ledger::multi_sign_request_with_dids(setup.wallet_handle, &dids, REQUEST).unwrap();
+
+            let msg: serde_json::Value = serde_json::from_str(&message).unwrap();
+            let signatures = msg["signatures"].as_object().unwrap();
+
+            assert_eq!(2, signatures.len());
+            assert_eq!(signatures[DID_TRUSTEE], r#"65hzs4nsdQsTUqLCLy2qisbKLfwYKZSWoyh1C6CU59p5pfG3EHQXGAsjW4Qw4QdwkrvjSgQuyv8qyABcXRBznFKW"#);
+            assert_eq!(signatures[DID_MY1], r#"49aXkbrtTE3e522AefE76J51WzUiakw3ZbxxWzf44cv7RS21n8mMr4vJzi4TymuqDupzCz7wEtuGz6rA94Y73kKR"#);
+        }
     }
 
     mod nym_requests {
@@ -387,6 +405,34 @@ mod high_cases {
             assert_eq!(my_verkey, nym_data.verkey.unwrap());
             assert!(nym_data.role.is_none());
         }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_get_nym_history_works() {
+            let setup = Setup::trustee();
+
+            let (my_did, my_verkey) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let nym_request = ledger::build_nym_request(&setup.did, &my_did, Some(&my_verkey), None, None).unwrap();
+            let nym_resp = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &nym_request).unwrap();
+            pool::check_response_type(&nym_resp, ResponseType::REPLY);
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &my_did, "{}").unwrap();
+            let nym_request = ledger::build_nym_request(&my_did, &my_did, Some(&new_verkey), None, None).unwrap();
+            let nym_resp = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &my_did, &nym_request).unwrap();
+            pool::check_response_type(&nym_resp, ResponseType::REPLY);
+
+            let get_nym_request = ledger::build_get_nym_request(None, &my_did).unwrap();
+            ledger::submit_request_with_retries(setup.pool_handle, &get_nym_request, &nym_resp).unwrap();
+
+            let history = ledger::get_nym_history(setup.pool_handle, &my_did, None).unwrap();
+            let history: Vec<serde_json::Value> = serde_json::from_str(&history).unwrap();
+
+            assert_eq!(2, history.len());
+            assert_eq!(my_verkey, history[0]["data"]["verkey"].as_str().unwrap());
+            assert_eq!(new_verkey, history[1]["data"]["verkey"].as_str().unwrap());
+            assert!(history[0]["seqNo"].as_u64().unwrap() < history[1]["seqNo"].as_u64().unwrap());
+        }
     }
 
     mod attrib_requests {
@@ -774,6 +820,21 @@ mod high_cases {
 
             let _cred_def: CredentialDefinitionV1 = serde_json::from_str(&cred_def_json).unwrap();
         }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_check_cred_def_compatibility_works() {
+            let setup = Setup::pool();
+
+            let (_, cred_def_id, _) = ledger::post_entities();
+
+            let report = ledger::check_cred_def_compatibility(setup.pool_handle, &cred_def_id).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(json!(true), report["compatible"]);
+            assert_eq!(json!("CL"), report["signature_type"]);
+            assert_eq!(json!([]), report["issues"]);
+        }
     }
 
     mod get_validator_info {
@@ -885,6 +946,35 @@ mod high_cases {
             let expected_schema_data: SchemaData = serde_json::from_str(r#"{"name":"gvt","version":"1.0","attr_names":["name", "age", "sex", "height"]}"#).unwrap();
             assert_eq!(expected_schema_data, get_txn_schema_data);
         }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_fetch_ledger_txns_works() {
+            let setup = Setup::pool();
+
+            let batch = ledger::fetch_ledger_txns(setup.pool_handle, Some(r#"{"from":1,"batchSize":5}"#)).unwrap();
+            let batch: serde_json::Value = serde_json::from_str(&batch).unwrap();
+
+            let txns = batch["txns"].as_array().unwrap();
+            assert_eq!(5, txns.len());
+            assert_eq!(1, txns[0]["seqNo"].as_u64().unwrap());
+            assert_eq!(5, txns[4]["seqNo"].as_u64().unwrap());
+            assert_eq!(6, batch["cursor"].as_u64().unwrap());
+            assert_eq!(false, batch["complete"].as_bool().unwrap());
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_fetch_ledger_txns_works_for_to() {
+            let setup = Setup::pool();
+
+            let batch = ledger::fetch_ledger_txns(setup.pool_handle, Some(r#"{"ledgerType":"POOL","from":2,"to":3}"#)).unwrap();
+            let batch: serde_json::Value = serde_json::from_str(&batch).unwrap();
+
+            assert_eq!(2, batch["txns"].as_array().unwrap().len());
+            assert_eq!(4, batch["cursor"].as_u64().unwrap());
+            assert_eq!(true, batch["complete"].as_bool().unwrap());
+        }
     }
 
     mod pool_config {
@@ -1475,6 +1565,51 @@ mod high_cases {
         }
     }
 
+    mod write_receipt {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn write_receipt_works_for_nym_request() {
+            let setup = Setup::trustee();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let nym_request = ledger::build_nym_request(&setup.did, &did, None, None, None).unwrap();
+            let nym_resp = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &nym_request).unwrap();
+            pool::check_response_type(&nym_resp, ResponseType::REPLY);
+
+            let receipt = ledger::get_write_receipt(&nym_resp).unwrap();
+
+            let parsed_receipt: serde_json::Value = serde_json::from_str(&receipt).unwrap();
+            assert_eq!(json!(1), parsed_receipt["ledgerId"]);
+            assert!(parsed_receipt["seqNo"].as_u64().is_some());
+            assert!(parsed_receipt["rootHash"].as_str().is_some());
+            assert!(parsed_receipt["multiSignature"].is_object());
+
+            assert!(ledger::verify_write_receipt(setup.pool_handle, &receipt).unwrap());
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn verify_write_receipt_works_for_modified_txn() {
+            let setup = Setup::trustee();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let nym_request = ledger::build_nym_request(&setup.did, &did, None, None, None).unwrap();
+            let nym_resp = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &nym_request).unwrap();
+            pool::check_response_type(&nym_resp, ResponseType::REPLY);
+
+            let receipt = ledger::get_write_receipt(&nym_resp).unwrap();
+
+            let mut receipt: serde_json::Value = serde_json::from_str(&receipt).unwrap();
+            receipt["data"]["txn"]["data"]["dest"] = json!(DID_MY1);
+
+            assert!(!ledger::verify_write_receipt(setup.pool_handle, &receipt.to_string()).unwrap());
+        }
+    }
+
     mod auth_rule {
         use super::*;
 
@@ -2782,6 +2917,35 @@ mod high_cases {
             _disable_taa(setup.pool_handle, setup.wallet_handle, &setup.did);
         }
 
+        #[test]
+        fn indy_get_active_txn_author_agreement_works_for_retired_taa() {
+            let setup = Setup::trustee();
+
+            let (_, aml_label, aml_version, _) = _set_aml(setup.pool_handle, setup.wallet_handle, &setup.did);
+            let (taa_text, taa_version, taa_digest, ratification_ts) = _set_taa(setup.pool_handle, setup.wallet_handle, &setup.did);
+            let (_, taa_version_2, taa_digest_2, _) = _set_taa(setup.pool_handle, setup.wallet_handle, &setup.did);
+
+            let retirement_ts = time::get_time().sec as u64;
+
+            let txn_author_agreement_request = ledger::build_txn_author_agreement_request(&setup.did, None, &taa_version, None, Some(retirement_ts)).unwrap();
+            let txn_author_agreement_response = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &txn_author_agreement_request).unwrap();
+            pool::check_response_type(&txn_author_agreement_response, ResponseType::REPLY);
+
+            let expected_data = json!({"digest": taa_digest, "text": taa_text, "version": taa_version, "ratification_ts": ratification_ts, "retirement_ts": retirement_ts});
+            _check_taa(setup.pool_handle, &txn_author_agreement_response, &taa_version, expected_data);
+
+            let active_taa = ledger::get_active_txn_author_agreement(setup.pool_handle, Some(&taa_digest_2)).unwrap();
+            let active_taa: serde_json::Value = serde_json::from_str(&active_taa).unwrap();
+            assert_eq!(json!(taa_version_2), active_taa["taa"]["version"]);
+            assert_eq!(json!(aml_version), active_taa["aml"]["version"]);
+            assert!(active_taa["aml"]["aml"].get(&aml_label).is_some());
+
+            let res = ledger::get_active_txn_author_agreement(setup.pool_handle, Some(&taa_digest));
+            assert_code!(ErrorCode::LedgerTxnAuthorAgreementRetired, res);
+
+            _disable_taa(setup.pool_handle, setup.wallet_handle, &setup.did);
+        }
+
         #[test]
         #[cfg(not(feature = "only_high_cases"))]
         fn indy_author_agreement_works_for_using_not_latest_taa() {
@@ -3135,6 +3299,36 @@ mod medium_cases {
             assert_eq!(1, signatures.len());
             assert_eq!(signatures[DID_MY1], r#"49aXkbrtTE3e522AefE76J51WzUiakw3ZbxxWzf44cv7RS21n8mMr4vJzi4TymuqDupzCz7wEtuGz6rA94Y73kKR"#);
         }
+
+        #[test]
+        fn indy_multi_sign_request_with_dids_works_for_empty_list() {
+            let setup = Setup::wallet();
+
+            let res = ledger::multi_sign_request_with_dids(setup.wallet_handle, "[]", REQUEST);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_multi_sign_request_with_dids_works_for_duplicated_did() {
+            let setup = Setup::wallet();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let dids = json!([did, did]).to_string();
+            let res = ledger::multi_sign_request_with_dids(setup.wallet_handle, &dids, REQUEST);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_multi_sign_request_with_dids_works_for_unknown_signer() {
+            let setup = Setup::wallet();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let dids = json!([did, DID]).to_string();
+            let res = ledger::multi_sign_request_with_dids(setup.wallet_handle, &dids, REQUEST);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
     }
 
     mod nym_requests {
@@ -3303,6 +3497,39 @@ mod medium_cases {
             assert!(get_nym_response_data_without_role.role.is_none());
             assert_ne!(get_nym_response_data_without_role.role, get_nym_response_data_with_role.role);
         }
+
+        #[test]
+        fn indy_get_nym_history_works_for_invalid_did() {
+            Setup::empty();
+
+            let res = ledger::get_nym_history(INVALID_POOL_HANDLE, INVALID_IDENTIFIER, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_get_nym_history_works_for_invalid_range() {
+            Setup::empty();
+
+            let res = ledger::get_nym_history(INVALID_POOL_HANDLE, DID, Some(r#"{"from":5,"to":1}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_get_nym_history_works_for_too_long_range() {
+            Setup::empty();
+
+            let res = ledger::get_nym_history(INVALID_POOL_HANDLE, DID, Some(r#"{"from":1,"to":1001}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_get_nym_history_works_for_invalid_pool_handle() {
+            Setup::empty();
+
+            let res = ledger::get_nym_history(INVALID_POOL_HANDLE, DID, None);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
     }
 
     mod attrib_requests {
@@ -3759,6 +3986,31 @@ mod medium_cases {
             let get_txn_response: Reply<GetTxnResult> = serde_json::from_str(&get_txn_response).unwrap();
             assert!(get_txn_response.result.data.is_none());
         }
+
+        #[test]
+        fn indy_fetch_ledger_txns_works_for_invalid_range() {
+            Setup::empty();
+
+            let res = ledger::fetch_ledger_txns(INVALID_POOL_HANDLE, Some(r#"{"from":5,"to":1}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_fetch_ledger_txns_works_for_invalid_batch_size() {
+            Setup::empty();
+
+            let res = ledger::fetch_ledger_txns(INVALID_POOL_HANDLE, Some(r#"{"batchSize":0}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_fetch_ledger_txns_works_for_invalid_pool_handle() {
+            Setup::empty();
+
+            let res = ledger::fetch_ledger_txns(INVALID_POOL_HANDLE, None);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
     }
 
     mod revoc_reg_def_requests {
@@ -3850,6 +4102,28 @@ mod medium_cases {
             assert!(response_metadata["lastSeqNo"].as_u64().is_none());
         }
     }
+
+    mod write_receipt {
+        use super::*;
+
+        #[test]
+        fn get_write_receipt_works_for_reject() {
+            Setup::empty();
+
+            let response = json!({"op": "REJECT", "reqId": 1, "reason": "some reason"}).to_string();
+
+            let res = ledger::get_write_receipt(&response);
+            assert_code!(ErrorCode::LedgerInvalidTransaction, res);
+        }
+
+        #[test]
+        fn verify_write_receipt_works_for_invalid_receipt() {
+            Setup::empty();
+
+            let res = ledger::verify_write_receipt(1, r#"{"seqNo": 1}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
 }
 
 fn check_request(request: &str, expected_operation: serde_json::Value, expected_identifier: &str) {
@@ -232,6 +232,120 @@ mod high_cases {
         }
     }
 
+    mod records_batch {
+        use super::*;
+
+        #[test]
+        fn indy_add_wallet_records_batch_works() {
+            let setup = Setup::wallet();
+
+            let records = json!([
+                {"id": ID, "value": VALUE, "tags": serde_json::from_str::<serde_json::Value>(TAGS).unwrap()},
+                {"id": ID_2, "value": VALUE_2},
+            ]).to_string();
+
+            add_wallet_records_batch(setup.wallet_handle, TYPE, &records).unwrap();
+
+            check_record_field(setup.wallet_handle, TYPE, ID, "value", VALUE);
+            check_record_field(setup.wallet_handle, TYPE, ID, "tags", TAGS);
+            check_record_field(setup.wallet_handle, TYPE, ID_2, "value", VALUE_2);
+        }
+
+        #[test]
+        fn indy_add_wallet_records_batch_works_for_plugged_wallet() {
+            let setup = Setup::plugged_wallet();
+
+            let records = json!([{"id": ID, "value": VALUE}, {"id": ID_2, "value": VALUE_2}]).to_string();
+            add_wallet_records_batch(setup.wallet_handle, TYPE, &records).unwrap();
+
+            check_record_field(setup.wallet_handle, TYPE, ID_2, "value", VALUE_2);
+        }
+
+        #[test]
+        fn indy_add_wallet_records_batch_works_for_already_existing_record() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+
+            let records = json!([{"id": ID, "value": VALUE}, {"id": ID_2, "value": VALUE_3}]).to_string();
+            let res = add_wallet_records_batch(setup.wallet_handle, TYPE, &records);
+            assert_code!(ErrorCode::WalletItemAlreadyExists, res);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+            check_record_field(setup.wallet_handle, TYPE, ID_2, "value", VALUE_2);
+        }
+
+        #[test]
+        fn indy_add_wallet_records_batch_works_for_already_existing_record_in_plugged_wallet() {
+            let setup = Setup::plugged_wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+
+            let records = json!([{"id": ID, "value": VALUE}, {"id": ID_2, "value": VALUE_3}]).to_string();
+            let res = add_wallet_records_batch(setup.wallet_handle, TYPE, &records);
+            assert_code!(ErrorCode::WalletItemAlreadyExists, res);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_add_wallet_records_batch_works_for_invalid_records() {
+            let setup = Setup::wallet();
+
+            let res = add_wallet_records_batch(setup.wallet_handle, TYPE, r#"[{"id": "id"}]"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_delete_wallet_records_batch_works() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+
+            delete_wallet_records_batch(setup.wallet_handle, TYPE, &json!([ID, ID_2]).to_string()).unwrap();
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID_2, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_delete_wallet_records_batch_works_for_not_found_record() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let res = delete_wallet_records_batch(setup.wallet_handle, TYPE, &json!([ID, ID_2]).to_string());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            check_record_field(setup.wallet_handle, TYPE, ID, "value", VALUE);
+        }
+
+        #[test]
+        fn indy_delete_wallet_records_batch_works_for_not_found_record_in_plugged_wallet() {
+            let setup = Setup::plugged_wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(TAGS)).unwrap();
+
+            let res = delete_wallet_records_batch(setup.wallet_handle, TYPE, &json!([ID, ID_2]).to_string());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            check_record_field(setup.wallet_handle, TYPE, ID, "tags", TAGS);
+        }
+
+        #[test]
+        fn indy_delete_wallet_records_batch_works_for_invalid_type() {
+            let setup = Setup::wallet();
+
+            let res = delete_wallet_records_batch(setup.wallet_handle, FORBIDDEN_TYPE, &json!([ID]).to_string());
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+        }
+    }
+
     mod get_record {
         use super::*;
 
@@ -1016,6 +1130,31 @@ mod medium_cases {
             check_record_field(setup.wallet_handle, TYPE, ID, "tags", expected_result);
         }
 
+        #[test]
+        fn indy_add_wallet_record_tags_works_for_changed_encryption() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(TAGS)).unwrap();
+
+            let tags_json = r#"{"~tagName1": "str1", "tagName2": "5"}"#;
+            add_wallet_record_tags(setup.wallet_handle, TYPE, ID, tags_json).unwrap();
+
+            let expected_result = r#"{"~tagName1": "str1", "tagName2": "5", "~tagName3": "8"}"#;
+            check_record_field(setup.wallet_handle, TYPE, ID, "tags", expected_result);
+        }
+
+        #[test]
+        fn indy_add_wallet_record_tags_works_for_tag_both_encrypted_and_not() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(TAGS)).unwrap();
+
+            let res = add_wallet_record_tags(setup.wallet_handle, TYPE, ID, r#"{"tagName1": "str1", "~tagName1": "str1"}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            check_record_field(setup.wallet_handle, TYPE, ID, "tags", TAGS);
+        }
+
         #[test]
         fn indy_add_wallet_record_tags_works_for_not_invalid_handle() {
             Setup::empty();
@@ -173,6 +173,71 @@ mod high_cases {
             pool::set_protocol_version(2).unwrap();
         }
     }
+
+    mod build_genesis_txns {
+        use super::*;
+
+        #[test]
+        fn indy_build_pool_genesis_txns_works() {
+            let setup = Setup::empty();
+
+            let genesis_txns = pool::build_pool_genesis_txns(&_genesis_nodes().to_string()).unwrap();
+            assert_eq!(2, genesis_txns.lines().count());
+
+            let txn_file_path = pool::create_genesis_txn_file(&setup.name, &genesis_txns, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+        }
+    }
+
+    mod traffic_capture {
+        use super::*;
+        use std::fs;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_set_pool_traffic_capture_works() {
+            let setup = Setup::pool();
+
+            let capture_path = environment::tmp_file_path(&format!("{}_traffic.log", setup.name));
+            let _ = fs::remove_file(&capture_path);
+            let config = json!({"path": capture_path.to_str().unwrap()}).to_string();
+
+            pool::set_pool_traffic_capture(setup.pool_handle, Some(&config)).unwrap();
+            pool::refresh(setup.pool_handle).unwrap();
+            pool::set_pool_traffic_capture(setup.pool_handle, None).unwrap();
+
+            let records: Vec<serde_json::Value> = fs::read_to_string(&capture_path).unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect();
+
+            assert!(records.iter().any(|record| record["direction"] == "request"));
+            assert!(records.iter().any(|record| record["direction"] == "reply"));
+            assert!(records.iter().all(|record| record["node"].is_string()));
+        }
+    }
+
+    mod set_pool_for_namespace {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_set_pool_for_namespace_works() {
+            use crate::utils::did;
+
+            let setup = Setup::wallet_and_pool();
+
+            pool::set_pool_for_namespace("sov", setup.pool_handle).unwrap();
+
+            let did = format!("did:sov:{}", DID_TRUSTEE);
+            let verkey = did::key_for_did(0, setup.wallet_handle, &did).unwrap();
+            assert_eq!(VERKEY_TRUSTEE, verkey);
+
+            pool::remove_pool_for_namespace("sov").unwrap();
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -277,6 +342,22 @@ mod medium_cases {
             assert_code!(ErrorCode::PoolLedgerNotCreatedError, res);
         }
 
+        #[test]
+        fn open_pool_ledger_works_for_client_key_pair_with_persist_client_key_pair() {
+            let setup = Setup::empty();
+
+            let config = json!({
+                "client_key_pair": {
+                    "public_key": "Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID",
+                    "secret_key": "D:)Q[IlAW!ahhC2ac:9*A}h:p?([4%wOTJ%JR%cs"
+                },
+                "persist_client_key_pair": true
+            }).to_string();
+
+            let res = pool::open_pool_ledger(&setup.name, Some(&config));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         #[cfg(feature = "local_nodes_pool")]
         fn open_pool_ledger_works_after_error() {
@@ -477,6 +558,46 @@ mod medium_cases {
         }
     }
 
+    mod traffic_capture {
+        use super::*;
+
+        #[test]
+        fn indy_set_pool_traffic_capture_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = pool::set_pool_traffic_capture(0, None);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+
+        #[test]
+        fn indy_set_pool_traffic_capture_works_for_invalid_config() {
+            Setup::empty();
+
+            let res = pool::set_pool_traffic_capture(0, Some(r#"{"path":"capture.log","max_files":0}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod set_pool_for_namespace {
+        use super::*;
+
+        #[test]
+        fn indy_set_pool_for_namespace_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = pool::set_pool_for_namespace("sov", 0);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+
+        #[test]
+        fn indy_set_pool_for_namespace_works_for_invalid_namespace() {
+            Setup::empty();
+
+            let res = pool::set_pool_for_namespace("Sovrin Main Net", 0);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod set_protocol_version {
         use super::*;
 
@@ -486,4 +607,67 @@ mod medium_cases {
             assert_code!(ErrorCode::PoolIncompatibleProtocolVersion, res);
         }
     }
+
+    mod build_genesis_txns {
+        use super::*;
+
+        #[test]
+        fn indy_build_pool_genesis_txns_works_for_empty_nodes() {
+            let res = pool::build_pool_genesis_txns("[]");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_build_pool_genesis_txns_works_for_duplicated_alias() {
+            let mut nodes = _genesis_nodes();
+            nodes[1]["alias"] = json!("Node1");
+
+            let res = pool::build_pool_genesis_txns(&nodes.to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_build_pool_genesis_txns_works_for_invalid_ip() {
+            let mut nodes = _genesis_nodes();
+            nodes[0]["node_ip"] = json!("not_an_ip");
+
+            let res = pool::build_pool_genesis_txns(&nodes.to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_build_pool_genesis_txns_works_for_invalid_blskey() {
+            let mut nodes = _genesis_nodes();
+            nodes[0]["blskey"] = json!("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW");
+
+            let res = pool::build_pool_genesis_txns(&nodes.to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+}
+
+fn _genesis_nodes() -> serde_json::Value {
+    json!([
+        {
+            "alias": "Node1",
+            "dest": "Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv",
+            "steward": "Th7MpTaRZVRYnPiabds81Y",
+            "node_ip": "127.0.0.1",
+            "node_port": 9701,
+            "client_ip": "127.0.0.1",
+            "client_port": 9702,
+            "blskey": "4N8aUNHSgjQVgkpm8nhNEfDf6txHznoYREg9kirmJrkivgL4oSEimFF6nsQ6M41QvhM2Z33nves5vfSn9n1UwNFJBYtWVnHYMATn76vLuL3zU88KyeAYcHfsih3He6UHcXDxcaecHVz6jhCYz1P2UZn2bDVruL5wXpehgBfBaLKm3Ba",
+            "blskey_pop": "RahHYiCvoNCtPTrVtP7nMC5eTYrsUA8WjXbdhNc8debh1agE9bGiJxWBXYNFbnJXoXhWFMvyqhqhRoq737YQemH5ik9oL7R4NTTCz2LEZhkgLJzB3QRQqJyBNyv7acbdHrAT8nQ9UkLbaVL9NBpnWXBTw4LEMePaSHEw66RzPNdAX1"
+        },
+        {
+            "alias": "Node2",
+            "dest": "8ECVSk179mjsjKRLWiQtssMLgp6EPhWXtaYyStWPSGAb",
+            "steward": "EbP4aYNeTHL6q385GuVpRV",
+            "node_ip": "127.0.0.1",
+            "node_port": 9703,
+            "client_ip": "127.0.0.1",
+            "client_port": 9704,
+            "services": ["VALIDATOR"]
+        }
+    ])
 }
@@ -52,6 +52,10 @@ pub fn issuer_create_schema(issuer_did: &str, name: &str, version: &str, attr_na
     anoncreds::issuer_create_schema(issuer_did, name, version, attr_names).wait()
 }
 
+pub fn issuer_validate_schema_attributes(attr_names: &str) -> Result<(), IndyError> {
+    anoncreds::issuer_validate_schema_attributes(attr_names).wait()
+}
+
 pub fn issuer_create_credential_definition(wallet_handle: WalletHandle, issuer_did: &str, schema: &str, tag: &str,
                                            signature_type: Option<&str>, config: Option<&str>) -> Result<(String, String), IndyError> {
     anoncreds::issuer_create_and_store_credential_def(wallet_handle, issuer_did, schema, tag, signature_type, config.unwrap_or("{}")).wait() // TODO: FIXME OPTIONAL CONFIG
@@ -75,23 +79,68 @@ pub fn issuer_create_credential_offer(wallet_handle: WalletHandle, cred_def_id:
     anoncreds::issuer_create_credential_offer(wallet_handle, cred_def_id).wait()
 }
 
+pub fn issuer_create_credential_offer_with_config(wallet_handle: WalletHandle, cred_def_id: &str, config_json: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_create_credential_offer_with_config(wallet_handle, cred_def_id, config_json).wait()
+}
+
+pub fn issuer_list_pending_credential_offers(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    anoncreds::issuer_list_pending_credential_offers(wallet_handle).wait()
+}
+
+pub fn issuer_revoke_credential_offer(wallet_handle: WalletHandle, nonce: &str) -> Result<(), IndyError> {
+    anoncreds::issuer_revoke_credential_offer(wallet_handle, nonce).wait()
+}
+
 pub fn issuer_create_credential(wallet_handle: WalletHandle, cred_offer_json: &str, cred_req_json: &str, cred_values_json: &str,
                                 rev_reg_id: Option<&str>, blob_storage_reader_handle: Option<i32>) -> Result<(String, Option<String>, Option<String>), IndyError> {
     anoncreds::issuer_create_credential(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait() // TODO OPTIONAL blob_storage_reader_handle
 }
 
+pub fn issuer_create_credentials(wallet_handle: WalletHandle, credentials_json: &str, rev_reg_id: Option<&str>,
+                                 blob_storage_reader_handle: Option<i32>) -> Result<(String, Option<String>), IndyError> {
+    anoncreds::issuer_create_credentials(wallet_handle, credentials_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait()
+}
+
 pub fn issuer_revoke_credential(wallet_handle: WalletHandle, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_id: &str) -> Result<String, IndyError> {
     anoncreds::issuer_revoke_credential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id).wait()
 }
 
+pub fn issuer_get_revocation_registry_info(wallet_handle: WalletHandle, rev_reg_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_get_revocation_registry_info(wallet_handle, rev_reg_id).wait()
+}
+
 pub fn issuer_merge_revocation_registry_deltas(rev_reg_delta: &str, other_rev_reg_delta: &str) -> Result<String, IndyError> {
     anoncreds::issuer_merge_revocation_registry_deltas(rev_reg_delta, other_rev_reg_delta).wait()
 }
 
+pub fn issuer_enable_issuance_log(wallet_handle: WalletHandle, issuer_did: &str) -> Result<(), IndyError> {
+    anoncreds::issuer_enable_issuance_log(wallet_handle, issuer_did).wait()
+}
+
+pub fn issuer_export_issuance_log(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    anoncreds::issuer_export_issuance_log(wallet_handle).wait()
+}
+
+pub fn issuer_verify_issuance_log(issuance_log_json: &str, issuer_verkey: &str) -> Result<bool, IndyError> {
+    anoncreds::issuer_verify_issuance_log(issuance_log_json, issuer_verkey).wait()
+}
+
 pub fn prover_create_master_secret(wallet_handle: WalletHandle, master_secret_id: &str) -> Result<String, IndyError> {
     anoncreds::prover_create_master_secret(wallet_handle, Some(master_secret_id)).wait()
 }
 
+pub fn prover_list_master_secrets(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    anoncreds::prover_list_master_secrets(wallet_handle).wait()
+}
+
+pub fn prover_rename_master_secret(wallet_handle: WalletHandle, master_secret_id: &str, new_master_secret_id: &str) -> Result<(), IndyError> {
+    anoncreds::prover_rename_master_secret(wallet_handle, master_secret_id, new_master_secret_id).wait()
+}
+
+pub fn prover_delete_master_secret(wallet_handle: WalletHandle, master_secret_id: &str) -> Result<(), IndyError> {
+    anoncreds::prover_delete_master_secret(wallet_handle, master_secret_id).wait()
+}
+
 pub fn prover_create_credential_req(wallet_handle: WalletHandle, prover_did: &str, cred_offer_json: &str,
                                     cred_def_json: &str, master_secret_id: &str) -> Result<(String, String), IndyError> {
     anoncreds::prover_create_credential_req(wallet_handle, prover_did, cred_offer_json, cred_def_json, master_secret_id).wait()
@@ -128,6 +177,10 @@ pub fn prover_search_credentials(wallet_handle: WalletHandle, filter_json: &str)
     anoncreds::prover_search_credentials(wallet_handle, Some(filter_json)).wait()
 }
 
+pub fn prover_search_credentials_with_projection(wallet_handle: WalletHandle, filter_json: &str, projection_json: &str) -> Result<(i32, usize), IndyError> {
+    anoncreds::prover_search_credentials_with_projection(wallet_handle, Some(filter_json), projection_json).wait()
+}
+
 pub fn prover_fetch_credentials(search_handle: i32, count: usize) -> Result<String, IndyError> {
     anoncreds::prover_fetch_credentials(search_handle, count).wait()
 }
@@ -160,11 +213,39 @@ pub fn prover_create_proof(wallet_handle: WalletHandle, proof_req_json: &str, re
                                    master_secret_name, schemas_json, cred_defs_json, rev_states_json).wait()
 }
 
+pub fn prover_preview_disclosure(wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str) -> Result<String, IndyError> {
+    anoncreds::prover_preview_disclosure(wallet_handle, proof_req_json, requested_credentials_json).wait()
+}
+
+pub fn prover_bind_proof(wallet_handle: WalletHandle, proof_req_json: &str, proof_json: &str, holder_did: &str) -> Result<String, IndyError> {
+    anoncreds::prover_bind_proof(wallet_handle, proof_req_json, proof_json, holder_did).wait()
+}
+
 pub fn verifier_verify_proof(proof_request_json: &str, proof_json: &str, schemas_json: &str,
                              cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Result<bool, IndyError> {
     anoncreds::verifier_verify_proof(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
 }
 
+pub fn verifier_create_session(schemas_json: &str, cred_defs_json: &str, rev_reg_defs_json: &str) -> Result<i32, IndyError> {
+    anoncreds::verifier_create_session(schemas_json, cred_defs_json, rev_reg_defs_json).wait()
+}
+
+pub fn verifier_verify_proof_with_session(session_handle: i32, proof_request_json: &str, proof_json: &str, rev_regs_json: &str) -> Result<bool, IndyError> {
+    anoncreds::verifier_verify_proof_with_session(session_handle, proof_request_json, proof_json, rev_regs_json).wait()
+}
+
+pub fn verifier_close_session(session_handle: i32) -> Result<(), IndyError> {
+    anoncreds::verifier_close_session(session_handle).wait()
+}
+
+pub fn verifier_save_proof_request_template(wallet_handle: WalletHandle, template_id: &str, template_json: &str) -> Result<(), IndyError> {
+    anoncreds::verifier_save_proof_request_template(wallet_handle, template_id, template_json).wait()
+}
+
+pub fn verifier_instantiate_proof_request_template(wallet_handle: WalletHandle, template_id: &str, params_json: &str) -> Result<String, IndyError> {
+    anoncreds::verifier_instantiate_proof_request_template(wallet_handle, template_id, params_json).wait()
+}
+
 pub fn create_revocation_state(blob_storage_reader_handle: i32, rev_reg_def_json: &str,
                                rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str) -> Result<String, IndyError> {
     anoncreds::create_revocation_state(blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id).wait()
@@ -183,6 +264,26 @@ pub fn to_unqualified(entity: &str) -> Result<String, IndyError> {
     anoncreds::to_unqualified(entity).wait()
 }
 
+pub fn json_to_msgpack(json: &str) -> Result<Vec<u8>, IndyError> {
+    anoncreds::json_to_msgpack(json).wait()
+}
+
+pub fn msgpack_to_json(msgpack: &[u8]) -> Result<String, IndyError> {
+    anoncreds::msgpack_to_json(msgpack).wait()
+}
+
+pub fn encode_attribute_value(raw: &str) -> Result<String, IndyError> {
+    anoncreds::encode_attribute_value(raw).wait()
+}
+
+pub fn to_w3c_credential(cred_json: &str) -> Result<String, IndyError> {
+    anoncreds::to_w3c_credential(cred_json).wait()
+}
+
+pub fn from_w3c_credential(w3c_cred_json: &str) -> Result<String, IndyError> {
+    anoncreds::from_w3c_credential(w3c_cred_json).wait()
+}
+
 pub fn default_cred_def_config() -> String {
     serde_json::to_string(&CredentialDefinitionConfig { support_revocation: false }).unwrap()
 }
@@ -353,10 +454,10 @@ pub fn issuer_2_gvt_cred_offer_info() -> CredentialOfferInfo {
 // note that encoding is not standardized by Indy except that 32-bit integers are encoded as themselves. IS-786
 pub fn gvt_credential_values() -> HashMap<String, AttributeValues> {
     map! {
-            "sex".to_string() => AttributeValues {raw: "male".to_string(), encoded: "5944657099558967239210949258394887428692050081607692519917050011144233115103".to_string()},
-            "name".to_string() => AttributeValues {raw: "Alex".to_string(), encoded: "1139481716457488690172217916278103335".to_string()},
-            "height".to_string() => AttributeValues {raw: "175".to_string(), encoded: "175".to_string()},
-            "age".to_string() => AttributeValues {raw: "28".to_string(), encoded: "28".to_string()}
+            "sex".to_string() => AttributeValues {raw: "male".to_string(), encoded: "5944657099558967239210949258394887428692050081607692519917050011144233115103".to_string(), metadata: Default::default()},
+            "name".to_string() => AttributeValues {raw: "Alex".to_string(), encoded: "1139481716457488690172217916278103335".to_string(), metadata: Default::default()},
+            "height".to_string() => AttributeValues {raw: "175".to_string(), encoded: "175".to_string(), metadata: Default::default()},
+            "age".to_string() => AttributeValues {raw: "28".to_string(), encoded: "28".to_string(), metadata: Default::default()}
           }
 }
 
@@ -366,8 +467,8 @@ pub fn gvt_credential_values_json() -> String {
 
 pub fn gvt_sub_credential_values() -> HashMap<String, AttributeValues> {
     map! {
-            "sex".to_string() => AttributeValues {raw: "male".to_string(), encoded: "5944657099558967239210949258394887428692050081607692519917050011144233115103".to_string()},
-            "height_sub".to_string() => AttributeValues {raw: "175".to_string(), encoded: "175".to_string()}
+            "sex".to_string() => AttributeValues {raw: "male".to_string(), encoded: "5944657099558967239210949258394887428692050081607692519917050011144233115103".to_string(), metadata: Default::default()},
+            "height_sub".to_string() => AttributeValues {raw: "175".to_string(), encoded: "175".to_string(), metadata: Default::default()}
           }
 }
 
@@ -377,8 +478,8 @@ pub fn gvt_sub_credential_values_json() -> String {
 
 pub fn xyz_credential_values() -> HashMap<String, AttributeValues> {
     map! {
-            "status".to_string() => AttributeValues {raw: "partial".to_string(), encoded: "51792877103171595686471452153480627530895".to_string()},
-            "period".to_string() => AttributeValues {raw: "8".to_string(), encoded: "8".to_string()}
+            "status".to_string() => AttributeValues {raw: "partial".to_string(), encoded: "51792877103171595686471452153480627530895".to_string(), metadata: Default::default()},
+            "period".to_string() => AttributeValues {raw: "8".to_string(), encoded: "8".to_string(), metadata: Default::default()}
           }
 }
 
@@ -388,10 +489,10 @@ pub fn xyz_credential_values_json() -> String {
 
 pub fn gvt2_credential_values() -> HashMap<String, AttributeValues> {
     map! {
-            "sex".to_string() => AttributeValues {raw: "male".to_string(), encoded: "2142657394558967239210949258394838228692050081607692519917028371144233115103".to_string()},
-            "name".to_string() => AttributeValues {raw: "Alexander".to_string(), encoded: "21332817548165488690172217217278169335".to_string()},
-            "height".to_string() => AttributeValues {raw: "170".to_string(), encoded: "170".to_string()},
-            "Age".to_string() => AttributeValues {raw: "28".to_string(), encoded: "28".to_string()}
+            "sex".to_string() => AttributeValues {raw: "male".to_string(), encoded: "2142657394558967239210949258394838228692050081607692519917028371144233115103".to_string(), metadata: Default::default()},
+            "name".to_string() => AttributeValues {raw: "Alexander".to_string(), encoded: "21332817548165488690172217217278169335".to_string(), metadata: Default::default()},
+            "height".to_string() => AttributeValues {raw: "170".to_string(), encoded: "170".to_string(), metadata: Default::default()},
+            "Age".to_string() => AttributeValues {raw: "28".to_string(), encoded: "28".to_string(), metadata: Default::default()}
           }
 }
 
@@ -401,10 +502,10 @@ pub fn gvt2_credential_values_json() -> String {
 
 pub fn gvt3_credential_values() -> HashMap<String, AttributeValues> {
     map! {
-            "sex".to_string() => AttributeValues {raw: "male".to_string(), encoded: "1234567890442222223345678958394838228692050081607692519917028371144233115103".to_string()},
-            "name".to_string() => AttributeValues {raw: "Artem".to_string(), encoded: "12356325715837025980172217217278169335".to_string()},
-            "height".to_string() => AttributeValues {raw: "180".to_string(), encoded: "180".to_string()},
-            "age".to_string() => AttributeValues {raw: "25".to_string(), encoded: "25".to_string()}
+            "sex".to_string() => AttributeValues {raw: "male".to_string(), encoded: "1234567890442222223345678958394838228692050081607692519917028371144233115103".to_string(), metadata: Default::default()},
+            "name".to_string() => AttributeValues {raw: "Artem".to_string(), encoded: "12356325715837025980172217217278169335".to_string(), metadata: Default::default()},
+            "height".to_string() => AttributeValues {raw: "180".to_string(), encoded: "180".to_string(), metadata: Default::default()},
+            "age".to_string() => AttributeValues {raw: "25".to_string(), encoded: "25".to_string(), metadata: Default::default()}
           }
 }
 
@@ -425,6 +526,7 @@ pub fn issuer_1_gvt_credential() -> CredentialInfo {
                        "height".to_string() => "175".to_string(),
                        "age".to_string() => "28".to_string()
                    },
+        attrs_metadata: HashMap::new(),
     }
 }
 
@@ -439,6 +541,7 @@ pub fn issuer_1_xyz_credential() -> CredentialInfo {
                        "status".to_string() => "partial".to_string(),
                        "period".to_string() => "8".to_string()
                    },
+        attrs_metadata: HashMap::new(),
     }
 }
 
@@ -455,6 +558,7 @@ pub fn issuer_2_gvt_credential() -> CredentialInfo {
                        "height".to_string() => "170".to_string(),
                        "Age".to_string() => "28".to_string()
                    },
+        attrs_metadata: HashMap::new(),
     }
 }
 
@@ -672,6 +776,12 @@ pub fn proof_request_attr() -> String {
         }).to_string()
 }
 
+pub fn proof_request_attr_with_holder_binding(holder_did: &str, holder_verkey: &str) -> String {
+    let mut proof_req: serde_json::Value = serde_json::from_str(&proof_request_attr()).unwrap();
+    proof_req["holder_binding"] = json!({"did": holder_did, "verkey": holder_verkey});
+    proof_req.to_string()
+}
+
 pub fn proof_json_names() -> String {
     r#"{"proof":{"proofs":[{"primary_proof":{"eq_proof":{"revealed_attrs":{"age":"28","name":"1139481716457488690172217916278103335"},"a_prime":"37059438242994259998790346319434066563259262851520355480091172150889826130883393237926093321799440681909856961532640980048449223384805671899607862659376095023842581006802537983950207932496792785144734656825477404865618508668977376090591355838593886141620410371749415095554373277355509688260699210360869358785006563706591833856987245193114424070438013063355809733139650073893157583349774448671800293597451613258120928500855420774040035399988790635466156695875407657672993004830316697816281308896527384225080552033515729968331567171219679379473102757252104487685693002070188976642694202528257589046582080709109752935022","e":"109922005779405233943121743010554164412839436706339658425836679038556189672243290902929142208097769762943034219777885671776752614244091909","v":"1284125915860499669248852735479317096776719124959570347995473846767193414681232645848163339499108677754895570637169906769917439890666153543756837147366902490935443325961637122673889400243827346197368006028001780374947587482033411185560936517011006508036126537084103997207939297366657758940447914263690958368525082493075666522936208229179177506160262689731500367805118210523383098538513341379010456780856240340164863012154755712101591845153767452478810954083326872665765593321265442487243886298797961711223784823025655610368018613908959702591553249764645833524021917311744642968807457723747488191724643933369323339439131438966193247834546274609039204958605178132803990774813639824288602105473621379074346108397569767201430866324929811858040334046133024151120890828681541301553801379580949720247081708351217629622993044672922686194020828891720587862076224530881559036468002049156322198695898248214640042505280000582614623246","m":{"height":"6959077476651161779684574416838553328889210956914819956415532052678753320921972039328902793176095008644286859578890788916500546569517337307957606535111643517870736617007894947290","sex":"893288084832905070533301048840996400689551521791830443783389909394169951782753885801543504768735861345250554844473703958225223057610041365470394500145847061634125263984993695118","master_secret":"28964969107326245445186522007509714666417333989344308192807238824533729139528375723094650589872809641576900097517905181465585338145114953472640727001478696440390332622000037914"},"m2":"13335055121995665245887259625985450346942763879809728105637603608948578710861386965292046088372415755254360438523216452082325286343438346537397088988052200472812564806383761270685"},"ge_proofs":[]},"non_revoc_proof":null}],"aggregated_proof":{"c_hash":"2779655636103467443483025522910754087684294079111604105703620998863293181442","c_list":[[1,37,145,58,133,4,199,32,127,85,224,91,135,252,1,247,67,6,63,22,34,5,55,78,75,84,54,197,117,43,172,54,219,107,79,237,139,15,215,69,5,242,97,100,62,149,165,29,92,48,24,53,219,159,64,249,101,15,37,229,76,53,121,85,239,147,0,58,114,117,21,194,171,66,218,252,154,125,62,228,230,169,71,45,37,131,247,32,212,204,231,168,243,246,39,152,121,130,187,213,109,99,186,203,7,198,67,162,25,109,38,188,234,208,204,15,93,215,47,110,122,179,202,85,185,90,23,114,35,44,253,236,144,220,41,228,44,21,55,3,107,55,37,229,2,13,0,217,116,33,220,242,68,157,149,90,55,188,20,23,253,222,13,114,4,109,230,123,100,218,210,201,139,251,58,90,204,70,132,153,194,192,25,168,98,161,193,236,221,143,219,136,242,217,10,235,250,43,179,153,224,110,35,83,168,201,162,4,182,51,38,9,62,74,249,153,217,94,174,89,224,99,20,18,108,29,130,38,239,41,188,44,154,170,194,1,185,157,35,185,71,40,240,63,94,62,133,30,166,53,50,249,198,220,14,50,110]]}},"requested_proof":{"revealed_attrs":{},"revealed_attr_groups":{"attr1_referent":{"sub_proof_index":0,"values":{"age":{"sub_proof_index":0,"raw":"28","encoded":"28"},"name":{"sub_proof_index":0,"raw":"Alex","encoded":"1139481716457488690172217916278103335"}}}},"self_attested_attrs":{},"unrevealed_attrs":{},"predicates":{}},"identifiers":[{"schema_id":"NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0","cred_def_id":"NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:TAG1","rev_reg_id":null,"timestamp":null}]}"#.to_string()
 }
@@ -4,7 +4,7 @@ use indy::IndyError;
 use self::futures::Future;
 
 use indy::cache;
-use indy::{WalletHandle, PoolHandle};
+use indy::{WalletHandle, PoolHandle, IndyHandle};
 
 
 pub fn get_schema_cache(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, id: &str, options_json: &str) -> Result<String, IndyError> {
@@ -21,4 +21,20 @@ pub fn purge_schema_cache(wallet_handle: WalletHandle, options_json: &str) -> Re
 
 pub fn purge_cred_def_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
     cache::purge_cred_def_cache(wallet_handle, options_json).wait()
-}
\ No newline at end of file
+}
+
+pub fn purge_nym_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
+    cache::purge_nym_cache(wallet_handle, options_json).wait()
+}
+
+pub fn start_revocation_prefetch(pool_handle: PoolHandle, wallet_handle: WalletHandle, blob_storage_reader_handle: IndyHandle, options_json: &str) -> Result<IndyHandle, IndyError> {
+    cache::start_revocation_prefetch(pool_handle, wallet_handle, blob_storage_reader_handle, options_json).wait()
+}
+
+pub fn stop_revocation_prefetch(prefetch_handle: IndyHandle) -> Result<(), IndyError> {
+    cache::stop_revocation_prefetch(prefetch_handle).wait()
+}
+
+pub fn get_cached_revocation_state(wallet_handle: WalletHandle, cred_id: &str) -> Result<String, IndyError> {
+    cache::get_cached_revocation_state(wallet_handle, cred_id).wait()
+}
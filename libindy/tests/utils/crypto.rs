@@ -1,16 +1,33 @@
 extern crate futures;
+extern crate indy_sys;
 
 use indy::IndyError;
 use indy::crypto;
 use self::futures::Future;
+use self::indy_sys::crypto::{EntropySourceFill, KeyVaultGetVerkey, KeyVaultSign, KeyVaultFree};
 
-use indy::WalletHandle;
+use indy::{WalletHandle, PoolHandle};
 
 pub fn create_key(wallet_handle: WalletHandle, seed: Option<&str>) -> Result<String, IndyError> {
     let key_json = json!({"seed": seed}).to_string();
     crypto::create_key(wallet_handle, Some(&key_json)).wait()
 }
 
+pub fn create_key_with_crypto_type(wallet_handle: WalletHandle, seed: Option<&str>, crypto_type: &str) -> Result<String, IndyError> {
+    let key_json = json!({"seed": seed, "crypto_type": crypto_type}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
+
+pub fn create_key_with_derivation_path(wallet_handle: WalletHandle, seed: &str, derivation_path: &str) -> Result<String, IndyError> {
+    let key_json = json!({"seed": seed, "derivation_path": derivation_path, "seed_encoding": "hex"}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
+
+pub fn create_bls_key(seed: Option<&str>) -> Result<String, IndyError> {
+    let key_json = json!({"seed": seed}).to_string();
+    crypto::create_bls_key(Some(&key_json)).wait()
+}
+
 pub fn set_key_metadata(wallet_handle: WalletHandle, verkey: &str, metadata: &str) -> Result<(), IndyError> {
     crypto::set_key_metadata(wallet_handle, verkey, metadata).wait()
 }
@@ -19,6 +36,14 @@ pub fn get_key_metadata(wallet_handle: WalletHandle, verkey: &str) -> Result<Str
     crypto::get_key_metadata(wallet_handle, verkey).wait()
 }
 
+pub fn get_key_derivation_path(wallet_handle: WalletHandle, verkey: &str) -> Result<String, IndyError> {
+    crypto::get_key_derivation_path(wallet_handle, verkey).wait()
+}
+
+pub fn get_key_agreement_key(wallet_handle: WalletHandle, verkey: &str) -> Result<String, IndyError> {
+    crypto::get_key_agreement_key(wallet_handle, verkey).wait()
+}
+
 pub fn sign(wallet_handle: WalletHandle, my_vk: &str, msg: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::sign(wallet_handle, my_vk, msg).wait()
 }
@@ -27,6 +52,10 @@ pub fn verify(their_vk: &str, msg: &[u8], signature: &[u8]) -> Result<bool, Indy
     crypto::verify(their_vk, msg, signature).wait()
 }
 
+pub fn verify_with_strictness(their_vk: &str, msg: &[u8], signature: &[u8], strict: bool) -> Result<bool, IndyError> {
+    crypto::verify_with_strictness(their_vk, msg, signature, strict).wait()
+}
+
 pub fn auth_crypt(wallet_handle: WalletHandle, my_vk: &str, their_vk: &str, msg: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::auth_crypt(wallet_handle, my_vk, their_vk, msg).wait()
 }
@@ -39,6 +68,18 @@ pub fn anon_crypt(their_vk: &str, msg: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::anon_crypt(their_vk, msg).wait()
 }
 
+pub fn convert_key(verkey: &str) -> Result<String, IndyError> {
+    crypto::convert_key(verkey).wait()
+}
+
+pub fn crypto_box(wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &str, message: &[u8], nonce: &[u8]) -> Result<Vec<u8>, IndyError> {
+    crypto::crypto_box(wallet_handle, sender_vk, recipient_vk, message, nonce).wait()
+}
+
+pub fn crypto_box_open(wallet_handle: WalletHandle, recipient_vk: &str, sender_vk: &str, encrypted_message: &[u8], nonce: &[u8]) -> Result<Vec<u8>, IndyError> {
+    crypto::crypto_box_open(wallet_handle, recipient_vk, sender_vk, encrypted_message, nonce).wait()
+}
+
 pub fn anon_decrypt(wallet_handle: WalletHandle, my_vk: &str, encrypted_msg: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::anon_decrypt(wallet_handle, my_vk, encrypted_msg).wait()
 }
@@ -47,6 +88,42 @@ pub fn pack_message(wallet_handle: WalletHandle, message: &[u8], receiver_keys:
     crypto::pack_message(wallet_handle, message, receiver_keys, sender).wait()
 }
 
+pub fn pack_message_with_mode(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, mode: &str) -> Result<Vec<u8>, IndyError> {
+    crypto::pack_message_with_mode(wallet_handle, message, receiver_keys, sender, mode).wait()
+}
+
+pub fn pack_message_for_dids(pool_handle: PoolHandle, wallet_handle: WalletHandle, message: &[u8], receiver_dids: &str, sender: Option<&str>, options_json: &str) -> Result<Vec<u8>, IndyError> {
+    crypto::pack_message_for_dids(pool_handle, wallet_handle, message, receiver_dids, sender, options_json).wait()
+}
+
 pub fn unpack_message(wallet_handle: WalletHandle, jwe: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::unpack_message(wallet_handle, jwe).wait()
-}
\ No newline at end of file
+}
+
+pub fn list_dead_letters(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    crypto::list_dead_letters(wallet_handle).wait()
+}
+
+pub fn retry_dead_letter(wallet_handle: WalletHandle, dead_letter_id: &str) -> Result<Vec<u8>, IndyError> {
+    crypto::retry_dead_letter(wallet_handle, dead_letter_id).wait()
+}
+
+pub fn purge_dead_letters(wallet_handle: WalletHandle, received_before: Option<u64>) -> Result<(), IndyError> {
+    crypto::purge_dead_letters(wallet_handle, received_before).wait()
+}
+
+pub fn register_key_vault(key_vault: &str,
+                          get_verkey: Option<KeyVaultGetVerkey>,
+                          sign: Option<KeyVaultSign>,
+                          free: Option<KeyVaultFree>) -> Result<(), IndyError> {
+    crypto::register_key_vault(key_vault, get_verkey, sign, free).wait()
+}
+
+pub fn register_entropy_source(fill: Option<EntropySourceFill>) -> Result<(), IndyError> {
+    crypto::register_entropy_source(fill).wait()
+}
+
+pub fn create_vault_key(wallet_handle: WalletHandle, key_vault: &str, key_ref: &str) -> Result<String, IndyError> {
+    let key_json = json!({"key_vault": key_vault, "key_ref": key_ref}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
@@ -46,6 +46,10 @@ pub fn create_my_did(wallet_handle: WalletHandle, my_did_json: &str) -> Result<(
     did::create_and_store_my_did(wallet_handle, my_did_json).wait()
 }
 
+pub fn create_and_store_my_dids(wallet_handle: WalletHandle, count: u32, options_json: &str) -> Result<String, IndyError> {
+    did::create_and_store_my_dids(wallet_handle, count, options_json).wait()
+}
+
 pub fn store_their_did(wallet_handle: WalletHandle, identity_json: &str) -> Result<(), IndyError> {
     did::store_their_did(wallet_handle, identity_json).wait()
 }
@@ -98,6 +102,14 @@ pub fn get_did_metadata(wallet_handle: WalletHandle, did: &str) -> Result<String
     did::get_did_metadata(wallet_handle, did).wait()
 }
 
+pub fn set_did_key(wallet_handle: WalletHandle, did: &str, purpose: &str, verkey: &str) -> Result<(), IndyError> {
+    did::set_did_key(wallet_handle, did, purpose, verkey).wait()
+}
+
+pub fn get_did_keys(wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
+    did::get_did_keys(wallet_handle, did).wait()
+}
+
 pub fn get_my_did_with_metadata(wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
     did::get_my_did_with_metadata(wallet_handle, did).wait()
 }
@@ -109,3 +121,31 @@ pub fn abbreviate_verkey(did: &str, verkey: &str) -> Result<String, IndyError> {
 pub fn qualify_did(wallet_handle: WalletHandle, did: &str, prefix: &str) -> Result<String, IndyError> {
     did::qualify_did(wallet_handle, did, prefix).wait()
 }
+
+pub fn create_did_ownership_proof(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, challenge: &str, audience: &str) -> Result<String, IndyError> {
+    did::create_did_ownership_proof(wallet_handle, pool_handle, did, challenge, audience).wait()
+}
+
+pub fn check_my_did_nym(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str) -> Result<String, IndyError> {
+    did::check_my_did_nym(wallet_handle, pool_handle, did).wait()
+}
+
+pub fn verify_did_ownership_proof(pool_handle: PoolHandle, proof_json: &str, challenge: &str, audience: &str) -> Result<bool, IndyError> {
+    did::verify_did_ownership_proof(pool_handle, proof_json, challenge, audience).wait()
+}
+
+pub fn publish_endpoint_for_did(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, address: &str, types_json: Option<&str>, routing_keys_json: Option<&str>) -> Result<(), IndyError> {
+    did::publish_endpoint_for_did(wallet_handle, pool_handle, did, address, types_json, routing_keys_json).wait()
+}
+
+pub fn resolve_service_endpoint_for_did(pool_handle: PoolHandle, did: &str) -> Result<String, IndyError> {
+    did::resolve_service_endpoint_for_did(pool_handle, did).wait()
+}
+
+pub fn resolve_did(pool_handle: PoolHandle, did: &str) -> Result<String, IndyError> {
+    did::resolve_did(pool_handle, did).wait()
+}
+
+pub fn resolve_did_with_wallet(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
+    did::resolve_did_with_wallet(pool_handle, wallet_handle, did).wait()
+}
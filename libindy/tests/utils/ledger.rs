@@ -51,6 +51,10 @@ pub fn multi_sign_request(wallet_handle: WalletHandle, submitter_did: &str, requ
     ledger::multi_sign_request(wallet_handle, submitter_did, request_json).wait()
 }
 
+pub fn multi_sign_request_with_dids(wallet_handle: WalletHandle, submitter_dids_json: &str, request_json: &str) -> Result<String, IndyError> {
+    ledger::multi_sign_request_with_dids(wallet_handle, submitter_dids_json, request_json).wait()
+}
+
 pub fn extract_seq_no_from_reply(reply: &str) -> Result<u64, &'static str> {
     let metadata = get_response_metadata(reply).map_err(|_| "Can not get Metadata from Reply")?;
 
@@ -98,6 +102,14 @@ pub fn parse_get_nym_response(get_nym_response: &str) -> Result<String, IndyErro
     ledger::parse_get_nym_response(get_nym_response).wait()
 }
 
+pub fn get_nym_history(pool_handle: PoolHandle, target_did: &str, options_json: Option<&str>) -> Result<String, IndyError> {
+    ledger::get_nym_history(pool_handle, target_did, options_json).wait()
+}
+
+pub fn fetch_ledger_txns(pool_handle: PoolHandle, options_json: Option<&str>) -> Result<String, IndyError> {
+    ledger::fetch_ledger_txns(pool_handle, options_json).wait()
+}
+
 pub fn build_attrib_request(submitter_did: &str, target_did: &str, hash: Option<&str>, raw: Option<&str>, enc: Option<&str>) -> Result<String, IndyError> {
     ledger::build_attrib_request(submitter_did, target_did, hash, raw, enc).wait()
 }
@@ -269,6 +281,22 @@ pub fn build_get_acceptance_mechanisms_request(submitter_did: Option<&str>,
     ledger::build_get_acceptance_mechanisms_request(submitter_did, timestamp, version).wait()
 }
 
+pub fn get_active_txn_author_agreement(pool_handle: PoolHandle, accepted_taa_digest: Option<&str>) -> Result<String, IndyError> {
+    ledger::get_active_txn_author_agreement(pool_handle, accepted_taa_digest).wait()
+}
+
+pub fn check_cred_def_compatibility(pool_handle: PoolHandle, cred_def_id: &str) -> Result<String, IndyError> {
+    ledger::check_cred_def_compatibility(pool_handle, cred_def_id).wait()
+}
+
+pub fn get_write_receipt(response: &str) -> Result<String, IndyError> {
+    ledger::get_write_receipt(response).wait()
+}
+
+pub fn verify_write_receipt(pool_handle: PoolHandle, receipt_json: &str) -> Result<bool, IndyError> {
+    ledger::verify_write_receipt(pool_handle, receipt_json).wait()
+}
+
 pub fn append_txn_author_agreement_acceptance_to_request(request_json: &str,
                                                          text: Option<&str>,
                                                          version: Option<&str>,
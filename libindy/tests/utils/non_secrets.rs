@@ -62,6 +62,14 @@ pub fn delete_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str)
     wallet::delete_wallet_record(wallet_handle, type_, id).wait()
 }
 
+pub fn add_wallet_records_batch(wallet_handle: WalletHandle, type_: &str, records_json: &str) -> Result<(), IndyError> {
+    wallet::add_wallet_records_batch(wallet_handle, type_, records_json).wait()
+}
+
+pub fn delete_wallet_records_batch(wallet_handle: WalletHandle, type_: &str, ids_json: &str) -> Result<(), IndyError> {
+    wallet::delete_wallet_records_batch(wallet_handle, type_, ids_json).wait()
+}
+
 pub fn get_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str, options_json: &str) -> Result<String, IndyError> {
     wallet::get_wallet_record(wallet_handle, type_, id, options_json).wait()
 }
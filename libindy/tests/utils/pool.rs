@@ -186,6 +186,22 @@ pub fn set_protocol_version(protocol_version: usize) -> Result<(), IndyError> {
     pool::set_protocol_version(protocol_version).wait()
 }
 
+pub fn build_pool_genesis_txns(nodes_json: &str) -> Result<String, IndyError> {
+    pool::build_pool_genesis_txns(nodes_json).wait()
+}
+
+pub fn set_pool_traffic_capture(pool_handle: PoolHandle, config: Option<&str>) -> Result<(), IndyError> {
+    pool::set_pool_traffic_capture(pool_handle, config).wait()
+}
+
+pub fn set_pool_for_namespace(namespace: &str, pool_handle: PoolHandle) -> Result<(), IndyError> {
+    pool::set_pool_for_namespace(namespace, pool_handle).wait()
+}
+
+pub fn remove_pool_for_namespace(namespace: &str) -> Result<(), IndyError> {
+    pool::remove_pool_for_namespace(namespace).wait()
+}
+
 pub fn check_response_type(response: &str, _type: ResponseType) {
     let response: Response = serde_json::from_str(&response).unwrap();
     assert_eq!(response.op, _type);
@@ -73,6 +73,18 @@ pub fn register_wallet_storage(xtype: &str, force_create: bool) -> Result<(), Er
     super::results::result_to_empty(err as i32, receiver)
 }
 
+pub fn register_wallet_storage_compaction(xtype: &str, compact: WalletCompact) -> Result<(), ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let xxtype = CString::new(xtype).unwrap();
+
+    let err = unsafe {
+        indy_register_wallet_storage_compaction(command_handle, xxtype.as_ptr(), Some(compact), cb)
+    };
+
+    super::results::result_to_empty(err as i32, receiver)
+}
+
 pub fn create_wallet(config: &str, credentials: &str) -> Result<(), IndyError> {
     wallet::create_wallet(config, credentials).wait()
 }
@@ -141,6 +153,26 @@ pub fn generate_wallet_key(config: Option<&str>) -> Result<String, IndyError> {
     wallet::generate_wallet_key(config).wait()
 }
 
+pub fn verify_wallet_storage_encryption(wallet_handle: WalletHandle, config: Option<&str>) -> Result<String, IndyError> {
+    wallet::verify_wallet_storage_encryption(wallet_handle, config).wait()
+}
+
+pub fn get_wallet_slow_query_log(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    wallet::get_wallet_slow_query_log(wallet_handle).wait()
+}
+
+pub fn compact_wallet(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    wallet::compact_wallet(wallet_handle).wait()
+}
+
+pub fn list_expiring_wallet_objects(wallet_handle: WalletHandle, within_secs: u64) -> Result<String, IndyError> {
+    wallet::list_expiring_wallet_objects(wallet_handle, within_secs).wait()
+}
+
+pub fn benchmark_wallet_key_derivation(target_ms: u64) -> Result<String, IndyError> {
+    wallet::benchmark_wallet_key_derivation(target_ms).wait()
+}
+
 extern {
     #[no_mangle]
     pub fn indy_register_wallet_storage(command_handle: CommandHandle,
@@ -170,6 +202,12 @@ extern {
                                         fetch_search_next_record: Option<WalletFetchSearchNextRecord>,
                                         free_search: Option<WalletFreeSearch>,
                                         cb: Option<ResponseEmptyCB>) -> ErrorCode;
+
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_compaction(command_handle: CommandHandle,
+                                                   type_: *const c_char,
+                                                   compact: Option<WalletCompact>,
+                                                   cb: Option<ResponseEmptyCB>) -> ErrorCode;
 }
 
 pub type WalletCreate = extern fn(name: *const c_char,
@@ -252,5 +290,7 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: i32,
                                                  record_handle_p: *mut i32) -> ErrorCode;
 pub type WalletFreeSearch = extern fn(storage_handle: i32,
                                       search_handle: i32) -> ErrorCode;
+pub type WalletCompact = extern fn(storage_handle: i32,
+                                   reclaimed_bytes_p: *mut u64) -> ErrorCode;
 
 pub type ResponseEmptyCB = extern fn(xcommand_handle: i32, err: i32);
@@ -7,7 +7,7 @@ extern crate indyrs as indy;
 extern crate indyrs as api;
 
 use crate::utils::inmem_wallet::InmemWallet;
-use crate::utils::{environment, wallet, test, did};
+use crate::utils::{environment, wallet, test, did, non_secrets};
 use crate::utils::constants::*;
 use crate::utils::Setup;
 
@@ -186,6 +186,34 @@ mod high_cases {
 
             InmemWallet::cleanup();
         }
+
+        #[test]
+        fn indy_open_wallet_works_for_profiles() {
+            let setup = Setup::empty();
+
+            let config_1 = json!({"id": &setup.name, "profile": "profile_1"}).to_string();
+            let config_2 = json!({"id": &setup.name, "profile": "profile_2"}).to_string();
+
+            wallet::create_wallet(&config_1, WALLET_CREDENTIALS).unwrap();
+            wallet::create_wallet(&config_2, WALLET_CREDENTIALS).unwrap();
+
+            let wallet_handle_1 = wallet::open_wallet(&config_1, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle_2 = wallet::open_wallet(&config_2, WALLET_CREDENTIALS).unwrap();
+
+            // the same DID can be stored in both profiles as they don't share records
+            did::create_and_store_my_did(wallet_handle_1, Some(MY1_SEED)).unwrap();
+            did::create_and_store_my_did(wallet_handle_2, Some(MY1_SEED)).unwrap();
+
+            wallet::close_wallet(wallet_handle_1).unwrap();
+            wallet::close_wallet(wallet_handle_2).unwrap();
+
+            wallet::delete_wallet(&config_1, WALLET_CREDENTIALS).unwrap();
+
+            let res = wallet::open_wallet(&config_1, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletNotFoundError, res);
+
+            wallet::delete_wallet(&config_2, WALLET_CREDENTIALS).unwrap();
+        }
     }
 
     mod close_wallet {
@@ -224,6 +252,71 @@ mod high_cases {
         }
     }
 
+    mod wallet_invalidated_event {
+        use super::*;
+
+        use libc::{c_char, c_void};
+        use std::ffi::CStr;
+        use std::ptr;
+        use std::sync::Mutex;
+
+        lazy_static! {
+            static ref EVENTS: Mutex<Vec<serde_json::Value>> = Mutex::new(Vec::new());
+        }
+
+        extern fn event_listener(_context: *const c_void, event_json: *const c_char) {
+            let event_json = unsafe { CStr::from_ptr(event_json) }.to_str().unwrap();
+            EVENTS.lock().unwrap().push(serde_json::from_str(event_json).unwrap());
+        }
+
+        fn wallet_events(wallet_handle: indy::WalletHandle) -> Vec<serde_json::Value> {
+            EVENTS.lock().unwrap().iter().filter(|event| event["wallet_handle"] == json!(wallet_handle)).cloned().collect()
+        }
+
+        #[test]
+        fn wallet_invalidated_event_works_for_close() {
+            let setup = Setup::empty();
+            assert_eq!(ErrorCode::Success, api::set_event_listener(ptr::null(), Some(event_listener)));
+
+            let config = config(&setup.name);
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let events = wallet_events(wallet_handle);
+            assert_eq!(1, events.len());
+            assert_eq!(json!("wallet_invalidated"), events[0]["type"]);
+            assert_eq!(json!("closed"), events[0]["reason"]);
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn wallet_invalidated_event_works_for_idle_timeout() {
+            let setup = Setup::empty();
+            assert_eq!(ErrorCode::Success, api::set_event_listener(ptr::null(), Some(event_listener)));
+
+            let config = json!({"id": &setup.name, "idle_timeout": 1}).to_string();
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            ::std::thread::sleep(::std::time::Duration::from_secs(2));
+
+            let events = wallet_events(wallet_handle);
+            assert_eq!(1, events.len());
+            assert_eq!(json!("timeout"), events[0]["reason"]);
+
+            let res = non_secrets::add_wallet_record(wallet_handle, "type", "id", "value", None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+
+            let res = wallet::close_wallet(wallet_handle);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+            assert_eq!(1, wallet_events(wallet_handle).len());
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+    }
+
     mod export_wallet {
         use super::*;
 
@@ -319,6 +412,236 @@ mod high_cases {
             wallet::delete_wallet(&wallet_config, &credentials).unwrap();
         }
     }
+
+    mod verify_wallet_storage_encryption {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_verify_wallet_storage_encryption_works() {
+            let setup = Setup::wallet();
+
+            did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let report = wallet::verify_wallet_storage_encryption(setup.wallet_handle, None).unwrap();
+            let report: Value = serde_json::from_str(&report).unwrap();
+
+            assert!(report["checkedRecords"].as_u64().unwrap() > 0);
+            assert_eq!(true, report["encrypted"].as_bool().unwrap());
+            assert!(report["violations"].as_array().unwrap().is_empty());
+        }
+
+        #[test]
+        fn indy_verify_wallet_storage_encryption_works_for_sample_size() {
+            let setup = Setup::wallet();
+
+            did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let config = json!({"sample_size": 1}).to_string();
+            let report = wallet::verify_wallet_storage_encryption(setup.wallet_handle, Some(&config)).unwrap();
+            let report: Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(1, report["checkedRecords"].as_u64().unwrap());
+            assert_eq!(true, report["encrypted"].as_bool().unwrap());
+        }
+    }
+
+    mod get_wallet_slow_query_log {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_get_wallet_slow_query_log_works() {
+            let setup = Setup::empty();
+
+            let config = json!({"id": &setup.name, "slow_query_threshold": 0}).to_string();
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            did::create_my_did(wallet_handle, "{}").unwrap();
+
+            let slow_query_log = wallet::get_wallet_slow_query_log(wallet_handle).unwrap();
+            let slow_query_log: Vec<Value> = serde_json::from_str(&slow_query_log).unwrap();
+
+            assert!(!slow_query_log.is_empty());
+            assert!(slow_query_log.iter().any(|record| record["operation"] == "add"));
+            assert!(slow_query_log.iter().all(|record| record["durationMs"].is_u64()));
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn indy_get_wallet_slow_query_log_works_for_disabled_log() {
+            let setup = Setup::wallet();
+
+            did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let slow_query_log = wallet::get_wallet_slow_query_log(setup.wallet_handle).unwrap();
+            assert_eq!("[]", slow_query_log);
+        }
+    }
+
+    mod compact_wallet {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_compact_wallet_works() {
+            let setup = Setup::wallet();
+
+            let value = "a".repeat(4096);
+            for i in 0..50 {
+                non_secrets::add_wallet_record(setup.wallet_handle, "type", &i.to_string(), &value, None).unwrap();
+            }
+            for i in 0..50 {
+                non_secrets::delete_wallet_record(setup.wallet_handle, "type", &i.to_string()).unwrap();
+            }
+
+            let report = wallet::compact_wallet(setup.wallet_handle).unwrap();
+            let report: Value = serde_json::from_str(&report).unwrap();
+
+            assert!(report["reclaimedBytes"].as_u64().unwrap() > 0);
+        }
+
+        extern fn _inmem_compact(_storage_handle: i32, reclaimed_bytes_p: *mut u64) -> ErrorCode {
+            unsafe { *reclaimed_bytes_p = 42; }
+            ErrorCode::Success
+        }
+
+        #[test]
+        fn indy_compact_wallet_works_for_plugged() {
+            Setup::empty();
+            InmemWallet::cleanup();
+
+            wallet::register_wallet_storage(INMEM_TYPE, false).unwrap();
+            wallet::register_wallet_storage_compaction(INMEM_TYPE, _inmem_compact).unwrap();
+            wallet::create_wallet(INMEM_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(INMEM_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let report = wallet::compact_wallet(wallet_handle).unwrap();
+            let report: Value = serde_json::from_str(&report).unwrap();
+            assert_eq!(42, report["reclaimedBytes"].as_u64().unwrap());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            InmemWallet::cleanup();
+        }
+
+        #[test]
+        fn indy_compact_wallet_works_for_compaction_interval() {
+            let setup = Setup::empty();
+
+            // Slow query log records every storage operation including scheduled compaction
+            let config = json!({"id": &setup.name, "compaction_interval": 0, "slow_query_threshold": 0}).to_string();
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            non_secrets::add_wallet_record(wallet_handle, "type", "id", "value", None).unwrap();
+            non_secrets::delete_wallet_record(wallet_handle, "type", "id").unwrap();
+
+            ::std::thread::sleep(::std::time::Duration::from_secs(1));
+
+            let slow_query_log = wallet::get_wallet_slow_query_log(wallet_handle).unwrap();
+            let slow_query_log: Vec<Value> = serde_json::from_str(&slow_query_log).unwrap();
+            assert!(slow_query_log.iter().any(|record| record["operation"] == "compact"));
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn indy_compact_wallet_works_for_compaction_interval_without_deletions() {
+            let setup = Setup::empty();
+
+            let config = json!({"id": &setup.name, "compaction_interval": 0, "slow_query_threshold": 0}).to_string();
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            non_secrets::add_wallet_record(wallet_handle, "type", "id", "value", None).unwrap();
+
+            ::std::thread::sleep(::std::time::Duration::from_secs(1));
+
+            let slow_query_log = wallet::get_wallet_slow_query_log(wallet_handle).unwrap();
+            let slow_query_log: Vec<Value> = serde_json::from_str(&slow_query_log).unwrap();
+            assert!(!slow_query_log.iter().any(|record| record["operation"] == "compact"));
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+    }
+
+    mod list_expiring_wallet_objects {
+        use super::*;
+        use serde_json::Value;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        fn _now() -> u64 {
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+        }
+
+        #[test]
+        fn indy_list_expiring_wallet_objects_works() {
+            let setup = Setup::wallet();
+
+            let now = _now();
+
+            let (expiring_did, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            did::set_did_metadata(setup.wallet_handle, &expiring_did, &json!({"expires": now + 100}).to_string()).unwrap();
+
+            let (later_did, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            did::set_did_metadata(setup.wallet_handle, &later_did, &json!({"expires": now + 100_000}).to_string()).unwrap();
+
+            let (expired_did, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            did::set_did_metadata(setup.wallet_handle, &expired_did, &json!({"expires": now - 100}).to_string()).unwrap();
+
+            let (other_did, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            did::set_did_metadata(setup.wallet_handle, &other_did, METADATA).unwrap();
+
+            let objects = wallet::list_expiring_wallet_objects(setup.wallet_handle, 1000).unwrap();
+            let objects: Value = serde_json::from_str(&objects).unwrap();
+
+            assert_eq!(json!([
+                {"type": "did", "id": expired_did, "expires": now - 100},
+                {"type": "did", "id": expiring_did, "expires": now + 100},
+            ]), objects);
+        }
+
+        #[test]
+        fn indy_list_expiring_wallet_objects_works_for_empty_wallet() {
+            let setup = Setup::wallet();
+
+            let objects = wallet::list_expiring_wallet_objects(setup.wallet_handle, 1000).unwrap();
+            let objects: Value = serde_json::from_str(&objects).unwrap();
+
+            assert_eq!(json!([]), objects);
+        }
+    }
+
+    mod benchmark_wallet_key_derivation {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn indy_benchmark_wallet_key_derivation_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let params = wallet::benchmark_wallet_key_derivation(100).unwrap();
+            let params: Value = serde_json::from_str(&params).unwrap();
+
+            assert!(params["ops_limit"].as_u64().unwrap() > 0);
+            assert!(params["mem_limit"].as_u64().unwrap() > 0);
+
+            let credentials = json!({"key": "key", "key_derivation_method": "ARGON2I_INT", "key_derivation_params": params}).to_string();
+            wallet::create_wallet(&config, &credentials).unwrap();
+
+            let credentials = json!({"key": "key", "key_derivation_method": "ARGON2I_INT"}).to_string();
+            let wallet_handle = wallet::open_wallet(&config, &credentials).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, &credentials).unwrap();
+        }
+    }
 }
 
 #[cfg(not(feature="only_high_cases"))]
@@ -718,6 +1041,88 @@ mod medium_cases {
             cleanup_file(&path);
         }
     }
+
+    mod verify_wallet_storage_encryption {
+        use super::*;
+
+        #[test]
+        fn indy_verify_wallet_storage_encryption_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::verify_wallet_storage_encryption(INVALID_WALLET_HANDLE, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_verify_wallet_storage_encryption_works_for_invalid_config() {
+            let setup = Setup::wallet();
+
+            let res = wallet::verify_wallet_storage_encryption(setup.wallet_handle, Some("not a json"));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod get_wallet_slow_query_log {
+        use super::*;
+
+        #[test]
+        fn indy_get_wallet_slow_query_log_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::get_wallet_slow_query_log(INVALID_WALLET_HANDLE);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod compact_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_compact_wallet_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::compact_wallet(INVALID_WALLET_HANDLE);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        extern fn _compact(_storage_handle: i32, _reclaimed_bytes_p: *mut u64) -> ErrorCode {
+            ErrorCode::Success
+        }
+
+        #[test]
+        fn indy_register_wallet_storage_compaction_works_for_unknown_type() {
+            Setup::empty();
+
+            let res = wallet::register_wallet_storage_compaction("unknown", _compact).unwrap_err();
+            assert_eq!(ErrorCode::WalletUnknownTypeError, res);
+        }
+    }
+
+    mod list_expiring_wallet_objects {
+        use super::*;
+
+        #[test]
+        fn indy_list_expiring_wallet_objects_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::list_expiring_wallet_objects(INVALID_WALLET_HANDLE, 1000);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod benchmark_wallet_key_derivation {
+        use super::*;
+
+        #[test]
+        fn indy_create_wallet_works_for_too_low_key_derivation_params() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let credentials = json!({"key": "key", "key_derivation_params": {"ops_limit": 0, "mem_limit": 0}}).to_string();
+            let res = wallet::create_wallet(&config, &credentials);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
 }
 
 fn _custom_path(name: &str) -> String {
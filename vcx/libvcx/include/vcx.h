@@ -298,6 +298,31 @@ vcx_error_t vcx_connection_get_pw_did(vcx_command_handle_t command_handle, vcx_c
 // Error code as a u32
 vcx_error_t vcx_connection_get_their_pw_did(vcx_command_handle_t command_handle, vcx_connection_handle_t connection_handle, void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
 
+// Sets the callback invoked every time a state of an Aries connection changes.
+// Connections are persisted in the wallet (under their pairwise DID) before the callback is invoked.
+//
+// NOTE: The callback is invoked on the thread that performed the transition and must not block.
+//
+// #Params
+// cb: Callback that takes the connection handle and its new state. Pass NULL to stop receiving the events.
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_connection_register_state_callback(void (*cb)(vcx_connection_handle_t, vcx_state_t));
+
+// Recreates the Aries connection from the record persisted in the wallet.
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+//
+// pw_did: pairwise DID of the connection
+//
+// cb: Callback that provides connection handle and error status of request
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_connection_restore(vcx_command_handle_t command_handle, const char *pw_did, void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_connection_handle_t));
+
 // Create a Credential object that requests and receives a credential for an institution
 //
 // #Params
@@ -2084,6 +2109,27 @@ vcx_error_t vcx_get_logger(const void*  vcx_get_logger,
                                           vcx_u32_t line),
                            void (**flushFn)(const void*  context) );
 
+// Registers the transport used to deliver messages to the endpoints with the given URL scheme.
+// Built-in transports are used for `http`, `https` (HTTP POST) and `ws`, `wss` (WebSocket) schemes
+// and can be replaced by the registered one. Messages to the agency are delivered the same way.
+//
+// #params
+// scheme: URL scheme of the endpoints the transport is used for (for example `https`).
+// send_cb: Sends the packed message to the endpoint. The transport must invoke the passed `cb` with
+//          the same command handle once the message is delivered, providing the response of the endpoint if any.
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_register_transport(const char *scheme,
+                                   void (*send_cb)(vcx_command_handle_t command_handle,
+                                                   const char *endpoint,
+                                                   const unsigned char *message_raw,
+                                                   vcx_u32_t message_len,
+                                                   void (*cb)(vcx_command_handle_t command_handle,
+                                                              vcx_error_t err,
+                                                              const unsigned char *response_raw,
+                                                              vcx_u32_t response_len)));
+
 /// Get details for last occurred error.
 ///
 /// This function should be called in two places to handle both cases of error occurrence:
@@ -1225,6 +1225,71 @@ pub extern fn vcx_connection_get_their_pw_did(command_handle: u32,
     error::SUCCESS.code_num
 }
 
+/// Sets the callback invoked every time a state of an Aries connection changes
+/// (look at the states and transitions described above).
+/// Connections are persisted in the wallet (under their pairwise DID) before the callback is invoked.
+///
+/// NOTE: The callback is invoked on the thread that performed the transition and must not block.
+///
+/// #Params
+/// cb: Callback that takes the connection handle and its new state.
+///     Pass NULL to stop receiving the events.
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_register_state_callback(cb: Option<extern fn(connection_handle: u32, state: u32)>) -> u32 {
+    info!("vcx_connection_register_state_callback >>>");
+
+    set_state_callback(cb);
+
+    error::SUCCESS.code_num
+}
+
+/// Recreates the Aries connection from the record persisted in the wallet.
+///
+/// Aries connections are stored in the wallet on every state transition once `vcx_connection_connect` is called.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// pw_did: pairwise DID of the connection (look at `vcx_connection_get_pw_did`).
+///
+/// cb: Callback that provides connection handle and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_restore(command_handle: CommandHandle,
+                                     pw_did: *const c_char,
+                                     cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, connection_handle: u32)>) -> u32 {
+    info!("vcx_connection_restore >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(pw_did, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_restore(command_handle: {}, pw_did: {})", command_handle, pw_did);
+
+    spawn(move || {
+        match restore_from_wallet(&pw_did) {
+            Ok(handle) => {
+                trace!("vcx_connection_restore_cb(command_handle: {}, rc: {}, handle: {})",
+                       command_handle, error::SUCCESS.message, handle);
+                cb(command_handle, error::SUCCESS.code_num, handle);
+            }
+            Err(x) => {
+                warn!("vcx_connection_restore_cb(command_handle: {}, rc: {}, handle: {})",
+                      command_handle, x, 0);
+                cb(command_handle, x.into(), 0);
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,6 +3,9 @@ use libc::c_char;
 use messages;
 use std::ptr;
 use utils::httpclient;
+use utils::transport;
+use utils::transport::TransportSendCB;
+use std::sync::Arc;
 use utils::constants::*;
 use utils::cstring::CStringUtils;
 use utils::error;
@@ -212,6 +215,35 @@ pub extern fn vcx_set_next_agency_response(message_index: u32) {
     httpclient::set_next_u8_response(message);
 }
 
+/// Registers the transport used to deliver messages to the endpoints with the given URL scheme.
+/// Built-in transports are used for `http`, `https` (HTTP POST) and `ws`, `wss` (WebSocket) schemes
+/// and can be replaced by the registered one. Messages to the agency are delivered the same way.
+///
+/// #params
+///
+/// scheme: URL scheme of the endpoints the transport is used for (for example `https`).
+///
+/// send_cb: Sends the packed message to the endpoint.
+///     The transport must invoke the passed `cb` with the same `command_handle` once the message is delivered,
+///     providing the response of the endpoint if any (response is required for messages sent to the agency).
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_register_transport(scheme: *const c_char,
+                                     send_cb: Option<TransportSendCB>) -> u32 {
+    info!("vcx_register_transport >>>");
+
+    check_useful_c_str!(scheme, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(send_cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_register_transport(scheme: {})", scheme);
+
+    transport::register_transport(&scheme, Arc::new(transport::CTransport::new(send_cb)));
+
+    error::SUCCESS.code_num
+}
+
 /// Retrieve messages from the Cloud Agent
 ///
 /// #params
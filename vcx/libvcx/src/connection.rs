@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 use rmp_serde;
 use serde_json;
@@ -18,6 +19,7 @@ use settings;
 use utils::error;
 use utils::libindy::signus::create_my_did;
 use utils::libindy::crypto;
+use utils::libindy::wallet;
 use utils::json::mapped_key_rewrite;
 use utils::json::KeyMatch;
 
@@ -28,8 +30,11 @@ use v3::messages::connection::invite::Invitation as InvitationV3;
 
 lazy_static! {
     static ref CONNECTION_MAP: ObjectCache<Connections> = Default::default();
+    static ref STATE_CALLBACK: Mutex<Option<extern fn(connection_handle: u32, state: u32)>> = Default::default();
 }
 
+const CONNECTION_RECORD_TYPE: &str = "VcxConnection";
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "version")]
 enum Connections {
@@ -832,7 +837,7 @@ pub fn send_generic_message(connection_handle: u32, msg: &str, msg_options: &str
     })
 }
 pub fn update_state_with_message(handle: u32, message: Message) -> VcxResult<u32> {
-    CONNECTION_MAP.get_mut(handle, |connection| {
+    track_state(handle, || CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
             Connections::V1(ref mut connection) => {
                 if message.status_code == MessageStatusCode::Redirected && message.msg_type == RemoteMessageType::ConnReqRedirect {
@@ -847,7 +852,7 @@ pub fn update_state_with_message(handle: u32, message: Message) -> VcxResult<u32
             }
         }
     })
-        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))
+        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle))))
 }
 
 impl Connection {
@@ -887,7 +892,7 @@ impl Connection {
 }
 
 pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
-    CONNECTION_MAP.get_mut(handle, |connection| {
+    track_state(handle, || CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
             Connections::V1(ref mut connection) => {
                 connection.update_state(message.clone())
@@ -898,11 +903,11 @@ pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
             }
         }
     })
-        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))
+        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle))))
 }
 
 pub fn process_acceptance_message(handle: u32, message: &Message) -> VcxResult<u32> {
-    CONNECTION_MAP.get_mut(handle, |connection| {
+    track_state(handle, || CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
             Connections::V1(ref mut connection) => {
                 connection.process_acceptance_message(&message)
@@ -913,7 +918,7 @@ pub fn process_acceptance_message(handle: u32, message: &Message) -> VcxResult<u
             }
         }
     })
-        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))
+        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle))))
 }
 
 impl Connection {
@@ -943,6 +948,13 @@ pub fn process_redirect_message(handle: u32, message: &Message) -> VcxResult<u32
 
 
 pub fn delete_connection(handle: u32) -> VcxResult<u32> {
+    let record_id = CONNECTION_MAP.get(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Ok(None),
+            Connections::V3(ref connection) => Ok(Some(connection.agent_info().pw_did.to_string()))
+        }
+    }).unwrap_or(None);
+
     CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
             Connections::V1(ref mut connection) => {
@@ -956,14 +968,20 @@ pub fn delete_connection(handle: u32) -> VcxResult<u32> {
     })
         .map(|_| error::SUCCESS.code_num)
         .or(Err(VcxError::from(VcxErrorKind::DeleteConnection)))
-        .and(release(handle))
-        .and_then(|_| Ok(error::SUCCESS.code_num))
+        .and(release(handle))?;
+
+    if let Some(record_id) = record_id {
+        _delete_record(&record_id)?;
+        _notify_state(handle, VcxStateType::VcxStateNone as u32);
+    }
+
+    Ok(error::SUCCESS.code_num)
 }
 
 pub fn connect(handle: u32, options: Option<String>) -> VcxResult<u32> {
     let options_obj: ConnectionOptions = ConnectionOptions::from_opt_str(options)?;
 
-    CONNECTION_MAP.get_mut(handle, |connection| {
+    track_state(handle, || CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
             Connections::V1(ref mut connection) => {
                 debug!("establish connection {}", connection.source_id);
@@ -976,7 +994,7 @@ pub fn connect(handle: u32, options: Option<String>) -> VcxResult<u32> {
                 Ok(error::SUCCESS.code_num)
             }
         }
-    })
+    }))
 }
 
 pub fn redirect(handle: u32, redirect_handle: u32) -> VcxResult<u32> {
@@ -1043,6 +1061,98 @@ pub fn from_string(connection_data: &str) -> VcxResult<u32> {
     Ok(handle)
 }
 
+/// Recreates the connection from the record persisted in the wallet under its pairwise DID.
+///
+/// Aries connections are stored in the wallet on every state transition once pairwise keys
+/// have been created for them (`connect`).
+pub fn restore_from_wallet(pw_did: &str) -> VcxResult<u32> {
+    trace!("restore_from_wallet >>> pw_did: {}", pw_did);
+
+    let options = json!({"retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string();
+
+    let record = wallet::get_record(CONNECTION_RECORD_TYPE, pw_did, &options)?;
+
+    let record: Value = serde_json::from_str(&record)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Connection record: {:?}", err)))?;
+
+    let connection_data = record["value"].as_str()
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Connection record doesn't contain value"))?;
+
+    from_string(connection_data)
+}
+
+/// Sets the callback invoked every time an Aries connection transitions to another state.
+pub fn set_state_callback(cb: Option<extern fn(connection_handle: u32, state: u32)>) {
+    *STATE_CALLBACK.lock().unwrap() = cb;
+}
+
+fn is_aries_connection(handle: u32) -> bool {
+    CONNECTION_MAP.get(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Ok(false),
+            Connections::V3(_) => Ok(true)
+        }
+    }).unwrap_or(false)
+}
+
+/// Runs the action and, if it has succeeded and changed the state of an Aries connection,
+/// persists the connection in the wallet and emits the state change event.
+/// Both happen once the connection is unlocked, so the callback can use the connection handle.
+/// The transition has already happened at this point, so failure to persist it is only logged.
+fn track_state<F, T>(handle: u32, action: F) -> VcxResult<T> where F: FnOnce() -> VcxResult<T> {
+    let prev_state = get_state(handle);
+
+    let res = action();
+
+    let state = get_state(handle);
+
+    if res.is_ok() && state != prev_state && is_aries_connection(handle) {
+        debug!("connection {} changed state: {} -> {}", handle, prev_state, state);
+
+        if let Err(err) = _store_record(handle) {
+            warn!("connection {} in state {} can't be stored in the wallet: {}", handle, state, err);
+        }
+
+        _notify_state(handle, state);
+    }
+
+    res
+}
+
+fn _store_record(handle: u32) -> VcxResult<()> {
+    let pw_did = get_pw_did(handle)?;
+
+    // pairwise keys aren't created before `connect`
+    if pw_did.is_empty() {
+        return Ok(());
+    }
+
+    let connection_data = to_string(handle)?;
+
+    match wallet::update_record_value(CONNECTION_RECORD_TYPE, &pw_did, &connection_data) {
+        Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound =>
+            wallet::add_record(CONNECTION_RECORD_TYPE, &pw_did, &connection_data, None),
+        res => res
+    }
+}
+
+fn _delete_record(pw_did: &str) -> VcxResult<()> {
+    if pw_did.is_empty() {
+        return Ok(());
+    }
+
+    match wallet::delete_record(CONNECTION_RECORD_TYPE, pw_did) {
+        Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound => Ok(()),
+        res => res
+    }
+}
+
+fn _notify_state(handle: u32, state: u32) {
+    if let Some(cb) = *STATE_CALLBACK.lock().unwrap() {
+        cb(handle, state);
+    }
+}
+
 pub fn release(handle: u32) -> VcxResult<()> {
     CONNECTION_MAP.release(handle)
         .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))
@@ -1502,6 +1612,30 @@ pub mod tests {
         assert_eq!(first_string, second_string);
     }
 
+    lazy_static! {
+        static ref STATE_EVENTS: Mutex<Vec<(u32, u32)>> = Default::default();
+    }
+
+    extern fn _state_cb(connection_handle: u32, state: u32) {
+        STATE_EVENTS.lock().unwrap().push((connection_handle, state));
+    }
+
+    #[test]
+    fn test_state_callback_works() {
+        init!("true");
+        ::settings::set_config_value(::settings::COMMUNICATION_METHOD, "aries");
+        set_state_callback(Some(_state_cb));
+
+        let handle = create_connection("test_state_callback_works").unwrap();
+        connect(handle, None).unwrap();
+        assert!(STATE_EVENTS.lock().unwrap().contains(&(handle, VcxStateType::VcxStateOfferSent as u32)));
+
+        delete_connection(handle).unwrap();
+        assert!(STATE_EVENTS.lock().unwrap().contains(&(handle, VcxStateType::VcxStateNone as u32)));
+
+        set_state_callback(None);
+    }
+
     #[test]
     fn test_deserialize_existing() {
         init!("true");
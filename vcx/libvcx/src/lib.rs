@@ -5,6 +5,7 @@
 extern crate serde;
 extern crate rand;
 extern crate reqwest;
+extern crate tungstenite;
 extern crate url;
 extern crate openssl;
 extern crate indyrs as indy;
@@ -5,6 +5,7 @@ use reqwest;
 use reqwest::header::CONTENT_TYPE;
 use std::env;
 use error::prelude::*;
+use utils::transport;
 
 lazy_static! {
     static ref NEXT_U8_RESPONSE: Mutex<Vec<Vec<u8>>> = Mutex::new(vec![]);
@@ -13,7 +14,7 @@ lazy_static! {
 //Todo: change this RC to a u32
 pub fn post_u8(body_content: &Vec<u8>) -> VcxResult<Vec<u8>> {
     let endpoint = format!("{}/agency/msg", settings::get_config_value(settings::CONFIG_AGENCY_ENDPOINT)?);
-    post_message(body_content, &endpoint)
+    transport::send_message(body_content, &endpoint)
 }
 
 pub fn post_message(body_content: &Vec<u8>, url: &str) -> VcxResult<Vec<u8>> {
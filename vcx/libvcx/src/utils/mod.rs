@@ -24,6 +24,7 @@ macro_rules! secret {
 
 pub mod error;
 pub mod httpclient;
+pub mod transport;
 pub mod constants;
 pub mod timeout;
 pub mod openssl;
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::net::TcpStream;
+use std::slice;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::mpsc::{channel, Sender};
+
+use libc::c_char;
+use tungstenite;
+use tungstenite::stream::Stream;
+use url::Url;
+
+use error::prelude::*;
+use indy_sys::CommandHandle;
+use utils::generate_command_handle;
+use utils::httpclient;
+use utils::timeout::TimeoutUtils;
+
+/// Delivers packed messages to the endpoint of the counterparty (or the agency).
+pub trait Transport: Send + Sync {
+    /// Sends the message to the endpoint and returns the response body (empty if the endpoint doesn't reply).
+    fn send(&self, message: &[u8], endpoint: &str) -> VcxResult<Vec<u8>>;
+}
+
+/// Sends messages by HTTP(S) POST requests.
+pub struct HttpTransport;
+
+impl Transport for HttpTransport {
+    fn send(&self, message: &[u8], endpoint: &str) -> VcxResult<Vec<u8>> {
+        httpclient::post_message(&message.to_vec(), endpoint)
+    }
+}
+
+/// Sends messages as binary frames over a WebSocket connection opened for each message.
+/// The first frame sent back by the endpoint (if any) is returned as the response.
+pub struct WebSocketTransport;
+
+impl Transport for WebSocketTransport {
+    fn send(&self, message: &[u8], endpoint: &str) -> VcxResult<Vec<u8>> {
+        debug!("Sending encrypted bundle to: \"{}\"", endpoint);
+
+        let (mut socket, _) = tungstenite::connect(endpoint)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Could not connect {:?}", err)))?;
+
+        {
+            let stream: &TcpStream = match socket.get_ref() {
+                Stream::Plain(ref stream) => stream,
+                Stream::Tls(ref stream) => stream.get_ref(),
+            };
+
+            // the endpoint isn't required to reply, so don't wait for the response for long
+            stream.set_read_timeout(Some(TimeoutUtils::short_timeout()))
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Could not set timeout {:?}", err)))?;
+        }
+
+        socket.write_message(tungstenite::Message::Binary(message.to_vec()))
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Could not send message {:?}", err)))?;
+
+        let response = loop {
+            match socket.read_message() {
+                Ok(tungstenite::Message::Binary(data)) => break data,
+                Ok(tungstenite::Message::Text(data)) => break data.into_bytes(),
+                Ok(_) => continue,
+                Err(tungstenite::Error::ConnectionClosed) | Err(tungstenite::Error::AlreadyClosed) => return Ok(Vec::new()),
+                Err(err) => {
+                    debug!("No response has been received from \"{}\": {:?}", endpoint, err);
+                    return Ok(Vec::new());
+                }
+            }
+        };
+
+        socket.close(None).ok();
+
+        Ok(response)
+    }
+}
+
+pub type TransportSendCB = extern fn(command_handle: CommandHandle,
+                                     endpoint: *const c_char,
+                                     message_raw: *const u8,
+                                     message_len: u32,
+                                     cb: extern fn(command_handle: CommandHandle,
+                                                   err: u32,
+                                                   response_raw: *const u8,
+                                                   response_len: u32));
+
+lazy_static! {
+    static ref TRANSPORTS: RwLock<HashMap<String, Arc<dyn Transport>>> = RwLock::new(_default_transports());
+    static ref PENDING_SENDS: Mutex<HashMap<CommandHandle, Sender<VcxResult<Vec<u8>>>>> = Default::default();
+}
+
+/// Transport implemented by the application through the C callback (look at `vcx_register_transport`).
+pub struct CTransport {
+    send: TransportSendCB,
+}
+
+impl CTransport {
+    pub fn new(send: TransportSendCB) -> CTransport {
+        CTransport { send }
+    }
+}
+
+impl Transport for CTransport {
+    fn send(&self, message: &[u8], endpoint: &str) -> VcxResult<Vec<u8>> {
+        let endpoint = CString::new(endpoint)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidUrl, format!("Invalid endpoint {:?}", err)))?;
+
+        let command_handle = generate_command_handle();
+        let (sender, receiver) = channel();
+        PENDING_SENDS.lock().unwrap().insert(command_handle, sender);
+
+        (self.send)(command_handle, endpoint.as_ptr(), message.as_ptr(), message.len() as u32, _send_cb);
+
+        let res = receiver.recv_timeout(TimeoutUtils::long_timeout())
+            .unwrap_or_else(|_| Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, "Custom transport hasn't replied in time")));
+
+        PENDING_SENDS.lock().unwrap().remove(&command_handle);
+
+        res
+    }
+}
+
+extern fn _send_cb(command_handle: CommandHandle, err: u32, response_raw: *const u8, response_len: u32) {
+    let res = if err != 0 {
+        Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Custom transport failed with: {}", err)))
+    } else if response_raw.is_null() || response_len == 0 {
+        Ok(Vec::new())
+    } else {
+        Ok(unsafe { slice::from_raw_parts(response_raw, response_len as usize) }.to_vec())
+    };
+
+    if let Some(sender) = PENDING_SENDS.lock().unwrap().remove(&command_handle) {
+        sender.send(res).ok();
+    }
+}
+
+fn _default_transports() -> HashMap<String, Arc<dyn Transport>> {
+    let mut transports: HashMap<String, Arc<dyn Transport>> = HashMap::new();
+    transports.insert("http".to_string(), Arc::new(HttpTransport));
+    transports.insert("https".to_string(), Arc::new(HttpTransport));
+    transports.insert("ws".to_string(), Arc::new(WebSocketTransport));
+    transports.insert("wss".to_string(), Arc::new(WebSocketTransport));
+    transports
+}
+
+/// Registers the transport used for the endpoints with the given URL scheme (replaces the built-in one if any).
+pub fn register_transport(scheme: &str, transport: Arc<dyn Transport>) {
+    trace!("register_transport >>> scheme: {}", scheme);
+    TRANSPORTS.write().unwrap().insert(scheme.to_lowercase(), transport);
+}
+
+/// Sends the message to the endpoint by the transport registered for its URL scheme.
+pub fn send_message(message: &[u8], endpoint: &str) -> VcxResult<Vec<u8>> {
+    let scheme = Url::parse(endpoint)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidUrl, format!("Invalid endpoint {}: {:?}", endpoint, err)))?
+        .scheme()
+        .to_string();
+
+    let transport = TRANSPORTS.read().unwrap().get(&scheme).cloned()
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidUrl, format!("No transport is registered for {} scheme", scheme)))?;
+
+    transport.send(message, endpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoTransport;
+
+    impl Transport for EchoTransport {
+        fn send(&self, message: &[u8], endpoint: &str) -> VcxResult<Vec<u8>> {
+            Ok([endpoint.as_bytes(), message].concat())
+        }
+    }
+
+    extern fn _c_transport(command_handle: CommandHandle, _endpoint: *const c_char, message_raw: *const u8, message_len: u32,
+                           cb: extern fn(CommandHandle, u32, *const u8, u32)) {
+        cb(command_handle, 0, message_raw, message_len);
+    }
+
+    #[test]
+    fn test_send_message_uses_registered_transport() {
+        register_transport("echo", Arc::new(EchoTransport));
+        assert_eq!(send_message(b"message", "echo://endpoint").unwrap(), b"echo://endpointmessage".to_vec());
+    }
+
+    #[test]
+    fn test_send_message_works_for_c_transport() {
+        register_transport("ctransport", Arc::new(CTransport::new(_c_transport)));
+        assert_eq!(send_message(b"message", "ctransport://endpoint").unwrap(), b"message".to_vec());
+    }
+
+    #[test]
+    fn test_send_message_fails_for_unknown_scheme() {
+        assert_eq!(send_message(b"message", "unknown://endpoint").unwrap_err().kind(), VcxErrorKind::InvalidUrl);
+        assert_eq!(send_message(b"message", "not an url").unwrap_err().kind(), VcxErrorKind::InvalidUrl);
+    }
+}
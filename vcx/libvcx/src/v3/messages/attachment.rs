@@ -1,5 +1,6 @@
 use std::str::from_utf8;
 use serde_json;
+use openssl::sha::sha256;
 
 use error::{VcxResult, VcxError, VcxErrorKind};
 use v3::messages::a2a::MessageId;
@@ -53,18 +54,16 @@ impl Json {
     pub fn new(json: serde_json::Value, encoding: AttachmentEncoding) -> VcxResult<Json> {
         let data: AttachmentData = match encoding {
             AttachmentEncoding::Base64 => {
-                AttachmentData::Base64(
-                    base64::encode(&
-                        match json {
-                            ::serde_json::Value::Object(obj) => {
-                                serde_json::to_string(&obj)
-                                    .map_err(|_| VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid Attachment Json".to_string()))?
-                            }
-                            ::serde_json::Value::String(str) => str,
-                            val => return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Unsupported Json value: {:?}", val)))
+                AttachmentData::from_bytes(
+                    match json {
+                        ::serde_json::Value::Object(obj) => {
+                            serde_json::to_string(&obj)
+                                .map_err(|_| VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid Attachment Json".to_string()))?
                         }
-                    )
-                )
+                        ::serde_json::Value::String(str) => str,
+                        val => return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Unsupported Json value: {:?}", val)))
+                    }.as_bytes()
+                )?
             }
         };
         Ok(Json {
@@ -73,6 +72,18 @@ impl Json {
         })
     }
 
+    pub fn from_data(data: AttachmentData) -> VcxResult<Json> {
+        data.validate()?;
+        Ok(Json {
+            id: MessageId::new(),
+            data
+        })
+    }
+
+    pub fn data(&self) -> &AttachmentData {
+        &self.data
+    }
+
     pub fn get_data(&self) -> VcxResult<String> {
         let data = self.data.get_bytes()?;
         from_utf8(data.as_slice())
@@ -86,20 +97,97 @@ pub enum AttachmentEncoding {
     Base64
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum AttachmentData {
-    #[serde(rename = "base64")]
-    Base64(String)
+/// Content bigger than this number of bytes isn't inlined into the message and must be sent by links.
+pub const MAX_INLINE_ATTACHMENT_SIZE: usize = 1024 * 1024;
+
+/// Content of attachment: either inlined as base64 or referenced by links.
+/// Links require sha256 of the content so the fetched content can be verified.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct AttachmentData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base64: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    links: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
 }
 
 impl AttachmentData {
+    pub fn from_bytes(bytes: &[u8]) -> VcxResult<AttachmentData> {
+        AttachmentData::_check_size(bytes.len())?;
+
+        Ok(AttachmentData {
+            base64: Some(base64::encode(bytes)),
+            links: None,
+            sha256: Some(_sha256_hex(bytes)),
+        })
+    }
+
+    pub fn from_links(links: Vec<String>, content: &[u8]) -> VcxResult<AttachmentData> {
+        if links.is_empty() {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Attachment links are empty"));
+        }
+
+        Ok(AttachmentData {
+            base64: None,
+            links: Some(links),
+            sha256: Some(_sha256_hex(content)),
+        })
+    }
+
+    pub fn links(&self) -> Option<&Vec<String>> {
+        self.links.as_ref()
+    }
+
+    pub fn validate(&self) -> VcxResult<()> {
+        match (&self.base64, &self.links, &self.sha256) {
+            (Some(_), _, _) => self.get_bytes().map(|_| ()),
+            (None, Some(links), Some(_)) if !links.is_empty() => Ok(()),
+            (None, Some(_), None) => Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Attachment links must be accompanied by sha256 of the content")),
+            _ => Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Attachment data contains neither base64 nor links"))
+        }
+    }
+
+    /// Returns inlined content verified against the recorded hash if any.
+    /// Content referenced by links must be fetched by the caller and checked with `verify_content`.
     pub fn get_bytes(&self) -> VcxResult<Vec<u8>> {
-        match self {
-            AttachmentData::Base64(s) => {
-                base64::decode(s).map_err(|_| VcxError::from_msg(VcxErrorKind::IOError, "Wrong bytes in attachment"))
-            }
+        let base64 = self.base64.as_ref()
+            .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidJson, "Attachment content isn't inlined, it must be fetched from links"))?;
+
+        let bytes = base64::decode(base64).map_err(|_| VcxError::from_msg(VcxErrorKind::IOError, "Wrong bytes in attachment"))?;
+
+        AttachmentData::_check_size(bytes.len())?;
+
+        if self.sha256.is_some() {
+            self.verify_content(&bytes)?;
         }
+
+        Ok(bytes)
     }
+
+    /// Checks the content (e.g. fetched from links) against sha256 recorded in the attachment.
+    pub fn verify_content(&self, content: &[u8]) -> VcxResult<()> {
+        let expected = self.sha256.as_ref()
+            .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidJson, "Attachment doesn't contain sha256 of the content"))?;
+
+        if !expected.eq_ignore_ascii_case(&_sha256_hex(content)) {
+            return Err(VcxError::from_msg(VcxErrorKind::IOError, "Attachment content doesn't match its sha256"));
+        }
+
+        Ok(())
+    }
+
+    fn _check_size(size: usize) -> VcxResult<()> {
+        if size > MAX_INLINE_ATTACHMENT_SIZE {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson,
+                                          format!("Attachment content of {} bytes exceeds inline limit of {} bytes, use links instead", size, MAX_INLINE_ATTACHMENT_SIZE)));
+        }
+        Ok(())
+    }
+}
+
+fn _sha256_hex(content: &[u8]) -> String {
+    sha256(content).iter().map(|byte| format!("{:02x}", byte)).collect()
 }
 
 #[cfg(test)]
@@ -136,4 +224,54 @@ pub mod tests {
             assert_eq!(_json().to_string(), attachments.content().unwrap());
         }
     }
+
+    #[test]
+    fn test_attachment_data_from_bytes_works() {
+        let data = AttachmentData::from_bytes(b"content").unwrap();
+        data.validate().unwrap();
+
+        assert_eq!(b"content".to_vec(), data.get_bytes().unwrap());
+        assert_eq!("ed7002b439e9ac845f22357d822bac1444730fbdb6016d3ec9432297b9ec9f73", data.sha256.as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_attachment_data_from_bytes_works_for_too_big_content() {
+        let res = AttachmentData::from_bytes(&vec![0; MAX_INLINE_ATTACHMENT_SIZE + 1]);
+        assert_eq!(VcxErrorKind::InvalidJson, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_attachment_data_get_bytes_works_for_tampered_content() {
+        let mut data = AttachmentData::from_bytes(b"content").unwrap();
+        data.base64 = Some(base64::encode(b"tampered"));
+
+        assert_eq!(VcxErrorKind::IOError, data.get_bytes().unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_attachment_data_get_bytes_works_without_sha256() {
+        let data: AttachmentData = serde_json::from_value(json!({"base64": base64::encode(b"content")})).unwrap();
+        assert_eq!(b"content".to_vec(), data.get_bytes().unwrap());
+    }
+
+    #[test]
+    fn test_attachment_data_from_links_works() {
+        let data = AttachmentData::from_links(vec!["https://example.com/content".to_string()], b"content").unwrap();
+        data.validate().unwrap();
+
+        assert_eq!(&vec!["https://example.com/content".to_string()], data.links().unwrap());
+        assert_eq!(VcxErrorKind::InvalidJson, data.get_bytes().unwrap_err().kind());
+
+        data.verify_content(b"content").unwrap();
+        assert_eq!(VcxErrorKind::IOError, data.verify_content(b"tampered").unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_attachment_data_validate_works_for_links_without_sha256() {
+        let data: AttachmentData = serde_json::from_value(json!({"links": ["https://example.com/content"]})).unwrap();
+        assert_eq!(VcxErrorKind::InvalidJson, data.validate().unwrap_err().kind());
+
+        let data: AttachmentData = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(VcxErrorKind::InvalidJson, data.validate().unwrap_err().kind());
+    }
 }
\ No newline at end of file
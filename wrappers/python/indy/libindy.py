@@ -97,6 +97,7 @@ def _indy_callback(command_handle: int, err: IndyError, *args):
     logger.debug("_indy_callback: >>> command_handle: %i, err %s, args: %s", command_handle, err, args)
 
     (event_loop, future) = _futures[command_handle]
+    # Libindy dispatcher thread must never block, so the result is only passed to the event loop
     event_loop.call_soon_threadsafe(_indy_loop_callback, command_handle, err, *args)
 
     logger.debug("_indy_callback: <<<")
@@ -1,6 +1,6 @@
 use super::*;
 
-use {CString, Error};
+use {BString, CString, Error};
 
 
 extern {
@@ -13,6 +13,11 @@ extern {
                                      attrs: CString,
                                      cb: Option<ResponseStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_validate_schema_attributes(command_handle: CommandHandle,
+                                                  attrs: CString,
+                                                  cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_create_and_store_credential_def(command_handle: CommandHandle,
                                                        wallet_handle: WalletHandle,
@@ -53,6 +58,24 @@ extern {
                                                cred_def_id: CString,
                                                cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_create_credential_offer_with_config(command_handle: CommandHandle,
+                                                           wallet_handle: WalletHandle,
+                                                           cred_def_id: CString,
+                                                           config_json: CString,
+                                                           cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_list_pending_credential_offers(command_handle: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_revoke_credential_offer(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               nonce: CString,
+                                               cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_create_credential(command_handle: CommandHandle,
                                          wallet_handle: WalletHandle,
@@ -63,6 +86,14 @@ extern {
                                          blob_storage_reader_handle: BlobStorageReaderHandle,
                                          cb: Option<ResponseStringStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          credentials_json: CString,
+                                          rev_reg_id: CString,
+                                          blob_storage_reader_handle: BlobStorageReaderHandle,
+                                          cb: Option<ResponseStringStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_revoke_credential(command_handle: CommandHandle,
                                          wallet_handle: WalletHandle,
@@ -71,18 +102,59 @@ extern {
                                          cred_revoc_id: CString,
                                          cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_get_revocation_registry_info(command_handle: CommandHandle,
+                                                    wallet_handle: WalletHandle,
+                                                    rev_reg_id: CString,
+                                                    cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_merge_revocation_registry_deltas(command_handle: CommandHandle,
                                                         rev_reg_delta_json: CString,
                                                         other_rev_reg_delta_json: CString,
                                                         cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_enable_issuance_log(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           issuer_did: CString,
+                                           cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_export_issuance_log(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_verify_issuance_log(command_handle: CommandHandle,
+                                           issuance_log_json: CString,
+                                           issuer_verkey: CString,
+                                           cb: Option<ResponseBoolCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_prover_create_master_secret(command_handle: CommandHandle,
                                             wallet_handle: WalletHandle,
                                             master_secret_id: CString,
                                             cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_prover_list_master_secrets(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_prover_rename_master_secret(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            master_secret_id: CString,
+                                            new_master_secret_id: CString,
+                                            cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_prover_delete_master_secret(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            master_secret_id: CString,
+                                            cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_prover_create_credential_req(command_handle: CommandHandle,
                                              wallet_handle: WalletHandle,
@@ -140,6 +212,13 @@ extern {
                                           query_json: CString,
                                           cb: Option<ResponseI32UsizeCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_prover_search_credentials_with_projection(command_handle: CommandHandle,
+                                                          wallet_handle: WalletHandle,
+                                                          query_json: CString,
+                                                          projection_json: CString,
+                                                          cb: Option<ResponseI32UsizeCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_prover_fetch_credentials(command_handle: CommandHandle,
                                          search_handle: SearchHandle,
@@ -187,6 +266,21 @@ extern {
                                     rev_states_json: CString,
                                     cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_prover_preview_disclosure(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          proof_req_json: CString,
+                                          requested_credentials_json: CString,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_prover_bind_proof(command_handle: CommandHandle,
+                                  wallet_handle: WalletHandle,
+                                  proof_req_json: CString,
+                                  proof_json: CString,
+                                  holder_did: CString,
+                                  cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_verifier_verify_proof(command_handle: CommandHandle,
                                       proof_request_json: CString,
@@ -197,6 +291,40 @@ extern {
                                       rev_regs_json: CString,
                                       cb: Option<ResponseBoolCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_verifier_create_session(command_handle: CommandHandle,
+                                        schemas_json: CString,
+                                        credential_defs_json: CString,
+                                        rev_reg_defs_json: CString,
+                                        cb: Option<ResponseI32CB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verifier_verify_proof_with_session(command_handle: CommandHandle,
+                                                   session_handle: IndyHandle,
+                                                   proof_request_json: CString,
+                                                   proof_json: CString,
+                                                   rev_regs_json: CString,
+                                                   cb: Option<ResponseBoolCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verifier_close_session(command_handle: CommandHandle,
+                                       session_handle: IndyHandle,
+                                       cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verifier_save_proof_request_template(command_handle: CommandHandle,
+                                                     wallet_handle: WalletHandle,
+                                                     template_id: CString,
+                                                     template_json: CString,
+                                                     cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verifier_instantiate_proof_request_template(command_handle: CommandHandle,
+                                                            wallet_handle: WalletHandle,
+                                                            template_id: CString,
+                                                            params_json: CString,
+                                                            cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_revocation_state(command_handle: CommandHandle,
                                         blob_storage_reader_handle: BlobStorageReaderHandle,
@@ -223,5 +351,31 @@ extern {
     pub fn indy_to_unqualified(command_handle: CommandHandle,
                                entity: CString,
                                cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_anoncreds_json_to_msgpack(command_handle: CommandHandle,
+                                          json: CString,
+                                          cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_anoncreds_msgpack_to_json(command_handle: CommandHandle,
+                                          msgpack_data: BString,
+                                          msgpack_len: u32,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_encode_attribute_value(command_handle: CommandHandle,
+                                       raw: CString,
+                                       cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_to_w3c_credential(command_handle: CommandHandle,
+                                  cred_json: CString,
+                                  cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_from_w3c_credential(command_handle: CommandHandle,
+                                    w3c_cred_json: CString,
+                                    cb: Option<ResponseStringCB>) -> Error;
 }
 
@@ -1,6 +1,6 @@
 use super::*;
 
-use {CString, Error, CommandHandle, WalletHandle, PoolHandle};
+use {CString, Error, CommandHandle, WalletHandle, PoolHandle, IndyHandle};
 
 extern {
 
@@ -33,4 +33,29 @@ extern {
                                      wallet_handle: WalletHandle,
                                      options_json: CString,
                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_purge_nym_cache(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                options_json: CString,
+                                cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_start_revocation_prefetch(command_handle: CommandHandle,
+                                          pool_handle: PoolHandle,
+                                          wallet_handle: WalletHandle,
+                                          blob_storage_reader_handle: IndyHandle,
+                                          options_json: CString,
+                                          cb: Option<ResponseI32CB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_stop_revocation_prefetch(command_handle: CommandHandle,
+                                         prefetch_handle: IndyHandle,
+                                         cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_cached_revocation_state(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            cred_id: CString,
+                                            cb: Option<ResponseStringCB>) -> Error;
 }
@@ -1,15 +1,33 @@
 use super::*;
 
-use {BString, CString, Error, CommandHandle, WalletHandle};
+use {BString, CString, CVoid, Error, CommandHandle, WalletHandle, PoolHandle};
 
 extern {
 
+    #[no_mangle]
+    pub fn indy_register_key_vault(command_handle: CommandHandle,
+                                   key_vault: CString,
+                                   get_verkey: Option<KeyVaultGetVerkey>,
+                                   sign: Option<KeyVaultSign>,
+                                   free: Option<KeyVaultFree>,
+                                   cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_register_entropy_source(command_handle: CommandHandle,
+                                        fill: Option<EntropySourceFill>,
+                                        cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_key(command_handle: CommandHandle,
                            wallet_handle: WalletHandle,
                            key_json: CString,
                            cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_create_bls_key(command_handle: CommandHandle,
+                               key_json: CString,
+                               cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_set_key_metadata(command_handle: CommandHandle,
                                  wallet_handle: WalletHandle,
@@ -23,6 +41,23 @@ extern {
                                  verkey: CString,
                                  cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_get_key_derivation_path(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        verkey: CString,
+                                        cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_key_agreement_key(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      verkey: CString,
+                                      cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_convert_key(command_handle: CommandHandle,
+                            verkey: CString,
+                            cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_crypto_sign(command_handle: CommandHandle,
                             wallet_handle: WalletHandle,
@@ -40,6 +75,16 @@ extern {
                               signature_len: u32,
                               cb: Option<ResponseBoolCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_crypto_verify_with_strictness(command_handle: CommandHandle,
+                                              signer_vk: CString,
+                                              message_raw: BString,
+                                              message_len: u32,
+                                              signature_raw: BString,
+                                              signature_len: u32,
+                                              strict: bool,
+                                              cb: Option<ResponseBoolCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_crypto_auth_crypt(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
@@ -72,6 +117,28 @@ extern {
                                     encrypted_len: u32,
                                     cb: Option<ResponseSliceCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_crypto_box(command_handle: CommandHandle,
+                           wallet_handle: WalletHandle,
+                           sender_vk: CString,
+                           recipient_vk: CString,
+                           msg_data: BString,
+                           msg_len: u32,
+                           nonce_data: BString,
+                           nonce_len: u32,
+                           cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_box_open(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                recipient_vk: CString,
+                                sender_vk: CString,
+                                encrypted_msg: BString,
+                                encrypted_len: u32,
+                                nonce_data: BString,
+                                nonce_len: u32,
+                                cb: Option<ResponseSliceCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_pack_message(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
@@ -81,11 +148,59 @@ extern {
                              sender: CString,
                              cb: Option<ResponseSliceCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_pack_message_with_mode(command_handle: CommandHandle,
+                                       wallet_handle: WalletHandle,
+                                       message: BString,
+                                       message_len: u32,
+                                       receiver_keys: CString,
+                                       sender: CString,
+                                       mode: CString,
+                                       cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_pack_message_for_dids(command_handle: CommandHandle,
+                                      pool_handle: PoolHandle,
+                                      wallet_handle: WalletHandle,
+                                      message: BString,
+                                      message_len: u32,
+                                      receiver_dids: CString,
+                                      sender: CString,
+                                      options_json: CString,
+                                      cb: Option<ResponseSliceCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_unpack_message(command_handle: CommandHandle,
                                wallet_handle: WalletHandle,
                                jwe_msg: BString,
                                jwe_len: u32,
                                cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_list_dead_letters(command_handle: CommandHandle,
+                                  wallet_handle: WalletHandle,
+                                  cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_retry_dead_letter(command_handle: CommandHandle,
+                                  wallet_handle: WalletHandle,
+                                  dead_letter_id: CString,
+                                  cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_purge_dead_letters(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   received_before: i64,
+                                   cb: Option<ResponseEmptyCB>) -> Error;
 }
 
+pub type KeyVaultGetVerkey = extern fn(key_ref: CString,
+                                       verkey_p: *mut CString) -> Error;
+pub type KeyVaultSign = extern fn(key_ref: CString,
+                                  message_raw: BString,
+                                  message_len: u32,
+                                  signature_raw_p: *mut BString,
+                                  signature_len_p: *mut u32) -> Error;
+pub type KeyVaultFree = extern fn(data: *const CVoid) -> Error;
+pub type EntropySourceFill = extern fn(buf: *mut u8,
+                                       len: u32) -> Error;
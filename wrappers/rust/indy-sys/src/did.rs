@@ -9,6 +9,13 @@ extern {
                                         did_info: CString,
                                         cb: Option<ResponseStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_create_and_store_my_dids(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         count: u32,
+                                         options_json: CString,
+                                         cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_replace_keys_start(command_handle: CommandHandle,
                                    wallet_handle: WalletHandle,
@@ -69,6 +76,20 @@ extern {
                                  did: CString,
                                  cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_set_did_key(command_handle: CommandHandle,
+                            wallet_handle: WalletHandle,
+                            did: CString,
+                            purpose: CString,
+                            verkey: CString,
+                            cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_did_keys(command_handle: CommandHandle,
+                             wallet_handle: WalletHandle,
+                             did: CString,
+                             cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_get_my_did_with_meta(command_handle: CommandHandle,
                                      wallet_handle: WalletHandle,
@@ -92,5 +113,57 @@ extern {
                             method: CString,
                             prefix: CString,
                             cb: Option<ResponseStringCB>) -> Error;
-}
 
+    #[no_mangle]
+    pub fn indy_create_did_ownership_proof(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           pool_handle: PoolHandle,
+                                           did: CString,
+                                           challenge: CString,
+                                           audience: CString,
+                                           cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_check_my_did_nym(command_handle: CommandHandle,
+                                 wallet_handle: WalletHandle,
+                                 pool_handle: PoolHandle,
+                                 did: CString,
+                                 cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verify_did_ownership_proof(command_handle: CommandHandle,
+                                           pool_handle: PoolHandle,
+                                           proof_json: CString,
+                                           challenge: CString,
+                                           audience: CString,
+                                           cb: Option<ResponseBoolCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_publish_endpoint_for_did(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         pool_handle: PoolHandle,
+                                         did: CString,
+                                         address: CString,
+                                         types_json: CString,
+                                         routing_keys_json: CString,
+                                         cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_resolve_service_endpoint_for_did(command_handle: CommandHandle,
+                                                 pool_handle: PoolHandle,
+                                                 did: CString,
+                                                 cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_resolve_did(command_handle: CommandHandle,
+                            pool_handle: PoolHandle,
+                            did: CString,
+                            cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_resolve_did_with_wallet(command_handle: CommandHandle,
+                                        pool_handle: PoolHandle,
+                                        wallet_handle: WalletHandle,
+                                        did: CString,
+                                        cb: Option<ResponseStringCB>) -> Error;
+}
@@ -39,6 +39,13 @@ extern {
                                    request_json: CString,
                                    cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_multi_sign_request_with_dids(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             submitter_dids_json: CString,
+                                             request_json: CString,
+                                             cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_build_get_ddo_request(command_handle: CommandHandle,
                                       submitter_did: CString,
@@ -65,6 +72,19 @@ extern {
                                        get_nym_response: CString,
                                        cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_get_nym_history(command_handle: CommandHandle,
+                                pool_handle: PoolHandle,
+                                target_did: CString,
+                                options_json: CString,
+                                cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_fetch_ledger_txns(command_handle: CommandHandle,
+                                  pool_handle: PoolHandle,
+                                  options_json: CString,
+                                  cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_build_attrib_request(command_handle: CommandHandle,
                                      submitter_did: CString,
@@ -290,6 +310,29 @@ extern {
                                                         version: CString,
                                                         cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_get_active_txn_author_agreement(command_handle: CommandHandle,
+                                                pool_handle: PoolHandle,
+                                                accepted_taa_digest: CString,
+                                                cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_check_cred_def_compatibility(command_handle: CommandHandle,
+                                             pool_handle: PoolHandle,
+                                             cred_def_id: CString,
+                                             cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_write_receipt(command_handle: CommandHandle,
+                                  response: CString,
+                                  cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verify_write_receipt(command_handle: CommandHandle,
+                                     pool_handle: PoolHandle,
+                                     receipt_json: CString,
+                                     cb: Option<ResponseBoolCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_append_txn_author_agreement_acceptance_to_request(command_handle: CommandHandle,
                                                                   request_json: CString,
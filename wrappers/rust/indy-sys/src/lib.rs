@@ -62,10 +62,22 @@ pub type ResponseStringSliceCB = extern fn(xcommand_handle: CommandHandle, err:
 pub type ResponseStringStringU64CB = extern fn(xcommand_handle: CommandHandle, err: Error, arg1: CString, arg2: CString, arg3: u64);
 pub type ResponseStringI64CB = extern fn(xcommand_handle: CommandHandle, err: Error, arg1: CString, arg3: i64);
 
+pub type EventCB = extern fn(context: *const CVoid, event_json: CString);
+
 extern {
     #[no_mangle]
     pub fn indy_set_runtime_config(config: CString) -> Error;
 
+    #[no_mangle]
+    pub fn indy_set_event_listener(context: *const CVoid,
+                                   cb: Option<EventCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_cancel_command(cancel_handle: IndyHandle) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_current_cancel_handle(cancel_handle_p: *mut IndyHandle) -> Error;
+
     #[no_mangle]
     pub fn indy_get_current_error(error_json_p: *mut CString);
 }
\ No newline at end of file
@@ -1,11 +1,11 @@
-use {CString, CVoid, Error};
+use {CString, CVoid, Error, IndyHandle};
 
 extern {
 
     #[no_mangle]
     pub fn indy_set_logger(context: *const CVoid,
                            enabled: Option<EnabledCB>,
-                           log: Option<LogCB>,
+                           log: Option<CommandLogCB>,
                            flush: Option<FlushCB>) -> Error;
 
     #[no_mangle]
@@ -30,5 +30,15 @@ pub type LogCB = extern fn(context: *const CVoid,
                            file: CString,
                            line: u32);
 
+pub type CommandLogCB = extern fn(context: *const CVoid,
+                                  level: u32,
+                                  target: CString,
+                                  message: CString,
+                                  module_path: CString,
+                                  file: CString,
+                                  line: u32,
+                                  command: CString,
+                                  correlation_id: IndyHandle);
+
 pub type FlushCB = extern fn(context: *const CVoid);
 
@@ -52,6 +52,20 @@ extern {
                                      id: CString,
                                      cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_add_wallet_records_batch(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         type_: CString,
+                                         records_json: CString,
+                                         cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_delete_wallet_records_batch(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            type_: CString,
+                                            ids_json: CString,
+                                            cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_get_wallet_record(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
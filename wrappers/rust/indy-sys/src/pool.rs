@@ -39,5 +39,27 @@ extern {
     pub fn indy_set_protocol_version(command_handle: CommandHandle,
                                      protocol_version: usize,
                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_build_pool_genesis_txns(command_handle: CommandHandle,
+                                        nodes_json: CString,
+                                        cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_set_pool_traffic_capture(command_handle: CommandHandle,
+                                         handle: PoolHandle,
+                                         config: CString,
+                                         cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_set_pool_for_namespace(command_handle: CommandHandle,
+                                       namespace: CString,
+                                       handle: PoolHandle,
+                                       cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_remove_pool_for_namespace(command_handle: CommandHandle,
+                                          namespace: CString,
+                                          cb: Option<ResponseEmptyCB>) -> Error;
 }
 
@@ -33,6 +33,12 @@ extern {
                                         free_search: Option<WalletFreeSearch>,
                                         cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_compaction(command_handle: CommandHandle,
+                                                   type_: CString,
+                                                   compact: Option<WalletCompact>,
+                                                   cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_wallet(command_handle: CommandHandle,
                               config: CString,
@@ -73,6 +79,33 @@ extern {
     pub fn indy_generate_wallet_key(command_handle: CommandHandle,
                                     config: CString,
                                     cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verify_wallet_storage_encryption(command_handle: CommandHandle,
+                                                 wallet_handle: WalletHandle,
+                                                 config: CString,
+                                                 cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_wallet_slow_query_log(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_compact_wallet(command_handle: CommandHandle,
+                               wallet_handle: WalletHandle,
+                               cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_list_expiring_wallet_objects(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             within_secs: u64,
+                                             cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_benchmark_wallet_key_derivation(command_handle: CommandHandle,
+                                                target_ms: u64,
+                                                cb: Option<ResponseStringCB>) -> Error;
 }
 
 pub type WalletCreate = extern fn(name: CString,
@@ -155,3 +188,5 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: StorageHandle,
                                                  record_handle_p: *mut RecordHandle) -> Error;
 pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                       search_handle: SearchHandle) -> Error;
+pub type WalletCompact = extern fn(storage_handle: StorageHandle,
+                                   reclaimed_bytes_p: *mut u64) -> Error;
@@ -14,8 +14,9 @@ use ffi::{ResponseStringStringCB,
           ResponseStringCB,
           ResponseI32CB,
           ResponseEmptyCB,
-          ResponseBoolCB};
-use {CommandHandle, WalletHandle, SearchHandle, BlobStorageReaderHandle, TailsWriterHandle};
+          ResponseBoolCB,
+          ResponseSliceCB};
+use {CommandHandle, IndyHandle, WalletHandle, SearchHandle, BlobStorageReaderHandle, TailsWriterHandle};
 use ffi::BlobStorageReaderCfgHandle;
 
 /*
@@ -66,6 +67,29 @@ fn _issuer_create_schema(command_handle: CommandHandle, issuer_did: &str, name:
     })
 }
 
+/// Validates a list of schema attributes against the rules applied by `issuer_create_schema`
+/// (unless "strict_schema_attr_names" runtime config is turned off):
+/// the number of attributes, non-empty names of letters, digits, spaces, `_`, `-` and `.` and uniqueness of names
+/// ignoring case and spaces.
+///
+/// # Arguments
+/// * `attrs`: a list of schema attributes descriptions
+pub fn issuer_validate_schema_attributes(attrs: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _issuer_validate_schema_attributes(command_handle, attrs, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _issuer_validate_schema_attributes(command_handle: CommandHandle, attrs: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let attrs = c_str!(attrs);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_validate_schema_attributes(command_handle, attrs.as_ptr(), cb)
+    })
+}
+
 /// Create credential definition entity that encapsulates credentials issuer DID, credential schema, secrets used for signing credentials
 /// and secrets used for credentials revocation.
 ///
@@ -331,6 +355,91 @@ fn _issuer_create_credential_offer(command_handle: CommandHandle, wallet_handle:
     })
 }
 
+/// Create credential offer that will be used by Prover for
+/// credential request creation and can be used only during configured period of time.
+///
+/// Each created offer is stored in the wallet until credential is issued for it.
+/// Credential can't be issued for expired or revoked offer.
+/// Expired offers are removed from the wallet when the next offer is created.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet)
+/// * `cred_def_id`: id of credential definition stored in the wallet
+/// * `config_json`: credential offer config json
+/// {
+///     "ttl": int, (optional) - offer lifetime in seconds (one week by default)
+///     "attrs_metadata": (optional) - metadata of the offered attributes carried to the credential:
+///         {"attr1": {"mime-type": string, (optional) "encoding": string (optional)}}
+/// }
+///
+/// # Returns
+/// * `credential_offer_json` - see `issuer_create_credential_offer`
+pub fn issuer_create_credential_offer_with_config(wallet_handle: WalletHandle, cred_def_id: &str, config_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_create_credential_offer_with_config(command_handle, wallet_handle, cred_def_id, config_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_create_credential_offer_with_config(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, config_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_def_id = c_str!(cred_def_id);
+    let config_json = c_str!(config_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credential_offer_with_config(command_handle, wallet_handle, cred_def_id.as_ptr(), config_json.as_ptr(), cb)
+    })
+}
+
+/// Lists credential offers created by the issuer for which credentials have not been issued yet.
+/// Expired and revoked offers are skipped.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet)
+///
+/// # Returns
+/// * `offers_json` - pending offers ordered by creation time
+/// [{
+///     "offer": <credential offer json>,
+///     "created": int, - offer creation time (seconds since Unix Epoch)
+///     "expires": Optional<int>, - offer expiration time (seconds since Unix Epoch)
+/// }]
+pub fn issuer_list_pending_credential_offers(wallet_handle: WalletHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_list_pending_credential_offers(command_handle, wallet_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_list_pending_credential_offers(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_list_pending_credential_offers(command_handle, wallet_handle, cb)
+    })
+}
+
+/// Revokes credential offer created by the issuer.
+/// Credential can't be issued for revoked offer.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet)
+/// * `nonce`: nonce of the credential offer to revoke
+pub fn issuer_revoke_credential_offer(wallet_handle: WalletHandle, nonce: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _issuer_revoke_credential_offer(command_handle, wallet_handle, nonce, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _issuer_revoke_credential_offer(command_handle: CommandHandle, wallet_handle: WalletHandle, nonce: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let nonce = c_str!(nonce);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_revoke_credential_offer(command_handle, wallet_handle, nonce.as_ptr(), cb)
+    })
+}
+
 /// Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request.
 ///
 /// Cred Request must match Cred Offer. The credential definition and revocation registry definition
@@ -406,6 +515,56 @@ fn _issuer_create_credential(
     })
 }
 
+/// Issue Credentials for the batch of Cred Requests in one call.
+///
+/// Works as `issuer_create_credential` called for every item of the batch, but credential definitions
+/// are loaded from the wallet once and revocation registry deltas are merged into a single one.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `credentials_json`: a list of credentials to issue.
+///     [{
+///         "cred_offer": a cred offer created by create_credential_offer,
+///         "cred_req": a credential request created by store_credential,
+///         "cred_values": a credential containing attribute values for each of requested attribute names
+///     }]
+/// * `rev_reg_id`: id of revocation registry stored in the wallet
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+///
+/// # Returns
+/// * `creds_json`: issued credentials in the same order as in `credentials_json`
+///     [{
+///         "cred": <credential json>,
+///         "cred_revoc_id": Optional<string> - local id for revocation info
+///     }]
+/// * `revoc_reg_delta_json`: Revocation registry delta json with all newly issued credentials
+pub fn issuer_create_credentials(wallet_handle: WalletHandle,
+                                 credentials_json: &str,
+                                 rev_reg_id: Option<&str>,
+                                 blob_storage_reader_handle: BlobStorageReaderHandle) -> Box<dyn Future<Item=(String, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string();
+
+    let err = _issuer_create_credentials(command_handle, wallet_handle, credentials_json, rev_reg_id, blob_storage_reader_handle, cb);
+
+    ResultHandler::str_optstr(command_handle, err, receiver)
+}
+
+fn _issuer_create_credentials(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    credentials_json: &str,
+    rev_reg_id: Option<&str>,
+    blob_storage_reader_handle: BlobStorageReaderHandle,
+    cb: Option<ResponseStringStringCB>
+) -> ErrorCode {
+    let credentials_json = c_str!(credentials_json);
+    let rev_reg_id_str = opt_c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credentials(command_handle, wallet_handle, credentials_json.as_ptr(), opt_c_ptr!(rev_reg_id, rev_reg_id_str), blob_storage_reader_handle, cb)
+    })
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -444,6 +603,40 @@ fn _issuer_revoke_credential(command_handle: CommandHandle,
     })
 }
 
+/// Returns usage statistics of the revocation registry stored in the wallet.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `rev_reg_id`: id of revocation registry stored in wallet
+///
+/// # Returns
+/// * `rev_reg_info_json`: Revocation registry statistics json
+/// {
+///     "rev_reg_id": string - id of the revocation registry,
+///     "issuance_type": string - type of issuance (ISSUANCE_BY_DEFAULT or ISSUANCE_ON_DEMAND),
+///     "max_cred_num": int - maximum number of credentials the registry can serve,
+///     "issued": int - number of credentials issued with the registry,
+///     "revoked": int - number of revoked credentials,
+///     "remaining": int - number of credentials that can still be issued,
+///     "last_delta_timestamp": Optional<int> - time the last revocation registry entry (or delta) to publish
+///                                             was created (seconds since Unix Epoch)
+/// }
+pub fn issuer_get_revocation_registry_info(wallet_handle: WalletHandle, rev_reg_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_get_revocation_registry_info(command_handle, wallet_handle, rev_reg_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_get_revocation_registry_info(command_handle: CommandHandle, wallet_handle: WalletHandle, rev_reg_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let rev_reg_id = c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_get_revocation_registry_info(command_handle, wallet_handle, rev_reg_id.as_ptr(), cb)
+    })
+}
+
 /// Merge two revocation registry deltas (returned by create_credential or revoke_credential) to accumulate common delta.
 /// Send common delta to ledger to reduce the load.
 ///
@@ -470,6 +663,96 @@ fn _issuer_merge_revocation_registry_deltas(command_handle: CommandHandle, rev_r
     })
 }
 
+/// Enables the log of credentials issued with the wallet.
+/// After the call every created credential is recorded to the log signed with the key of the issuer DID.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `issuer_did`: DID of the issuer signing the log.
+pub fn issuer_enable_issuance_log(wallet_handle: WalletHandle, issuer_did: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _issuer_enable_issuance_log(command_handle, wallet_handle, issuer_did, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _issuer_enable_issuance_log(command_handle: CommandHandle, wallet_handle: WalletHandle, issuer_did: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let issuer_did = c_str!(issuer_did);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_enable_issuance_log(command_handle, wallet_handle, issuer_did.as_ptr(), cb)
+    })
+}
+
+/// Exports the log of credentials issued with the wallet.
+/// Entries of the log are chained by their hashes and the head is signed with the issuer DID verkey,
+/// so it can be checked for modifications by `issuer_verify_issuance_log`.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+///
+/// # Returns
+/// Issuance log json
+/// {
+///     entries: [{
+///         seq_no: number - sequence number of the entry starting from 1,
+///         event: {
+///             cred_def_id: string - id of credential definition the credential is issued for,
+///             nonce: string - nonce of credential offer,
+///             timestamp: number - time of issuance,
+///             rev_reg_id: Optional<string> - id of revocation registry,
+///             cred_rev_id: Optional<string> - revocation index of the credential,
+///         },
+///         prev_hash: string - hash of the previous entry,
+///         hash: string - hash of the entry,
+///     }],
+///     head: {
+///         seq_no: number - sequence number of the last entry,
+///         hash: string - hash of the last entry,
+///         issuer_did: string - DID of the issuer,
+///         signature: string - base58 encoded signature of the head by the issuer DID key,
+///     }
+/// }
+pub fn issuer_export_issuance_log(wallet_handle: WalletHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_export_issuance_log(command_handle, wallet_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_export_issuance_log(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_export_issuance_log(command_handle, wallet_handle, cb)
+    })
+}
+
+/// Verifies integrity of the issuance log exported by `issuer_export_issuance_log`.
+///
+/// # Arguments
+/// * `issuance_log_json`: issuance log json returned by `issuer_export_issuance_log`.
+/// * `issuer_verkey`: verkey of the issuer DID NYM resolved by the verifier from the ledger.
+///
+/// # Returns
+/// true - if the log isn't modified, false - otherwise
+pub fn issuer_verify_issuance_log(issuance_log_json: &str, issuer_verkey: &str) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _issuer_verify_issuance_log(command_handle, issuance_log_json, issuer_verkey, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _issuer_verify_issuance_log(command_handle: CommandHandle, issuance_log_json: &str, issuer_verkey: &str, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let issuance_log_json = c_str!(issuance_log_json);
+    let issuer_verkey = c_str!(issuer_verkey);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_verify_issuance_log(command_handle, issuance_log_json.as_ptr(), issuer_verkey.as_ptr(), cb)
+    })
+}
+
 
 /// Creates a master secret with a given id and stores it in the wallet.
 /// The id must be unique.
@@ -496,6 +779,78 @@ fn _prover_create_master_secret(command_handle: CommandHandle, wallet_handle: Wa
     })
 }
 
+/// Lists master secrets stored in the wallet.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+///
+/// # Returns
+/// * `master_secrets_json` - [{
+///     "id": string, // id of master secret
+///     "credentials_count": int, // number of stored credentials bound to the master secret
+/// }]
+pub fn prover_list_master_secrets(wallet_handle: WalletHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_list_master_secrets(command_handle, wallet_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_list_master_secrets(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_list_master_secrets(command_handle, wallet_handle, cb)
+    })
+}
+
+/// Renames master secret stored in the wallet.
+/// Stored credentials bound to the master secret are updated to refer to the new id.
+/// If renaming is interrupted, calling it again with the same ids completes it.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `master_secret_id`: id of master secret to rename
+/// * `new_master_secret_id`: new id of master secret. Must be unique.
+pub fn prover_rename_master_secret(wallet_handle: WalletHandle, master_secret_id: &str, new_master_secret_id: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _prover_rename_master_secret(command_handle, wallet_handle, master_secret_id, new_master_secret_id, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _prover_rename_master_secret(command_handle: CommandHandle, wallet_handle: WalletHandle, master_secret_id: &str, new_master_secret_id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let master_secret_id = c_str!(master_secret_id);
+    let new_master_secret_id = c_str!(new_master_secret_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_rename_master_secret(command_handle, wallet_handle, master_secret_id.as_ptr(), new_master_secret_id.as_ptr(), cb)
+    })
+}
+
+/// Deletes master secret stored in the wallet.
+/// Master secret referenced by stored credentials can't be deleted.
+/// No master secret can be deleted while credentials stored without master secret reference exist.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `master_secret_id`: id of master secret to delete
+pub fn prover_delete_master_secret(wallet_handle: WalletHandle, master_secret_id: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _prover_delete_master_secret(command_handle, wallet_handle, master_secret_id, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _prover_delete_master_secret(command_handle: CommandHandle, wallet_handle: WalletHandle, master_secret_id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let master_secret_id = c_str!(master_secret_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_delete_master_secret(command_handle, wallet_handle, master_secret_id.as_ptr(), cb)
+    })
+}
+
 /// Gets human readable credential by the given id.
 ///
 /// # Arguments
@@ -784,6 +1139,39 @@ fn _prover_search_credentials(command_handle: CommandHandle, wallet_handle: Wall
     })
 }
 
+/// Search for credentials stored in wallet returning only the requested subset of the credentials data.
+/// Projected data is built from the credential tags, so credential values aren't read and decrypted.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `query_json`: Wql query filter for credentials searching based on tags.
+///     where query: indy-sdk/doc/design/011-wallet-query-language/README.md
+/// * `projection_json`: subset of the credentials data to return
+///     {
+///         "attrs": [string], - (Optional) names of the attributes to return raw values of
+///         "tags": [string], - (Optional) names of the credential tags to return
+///     }
+///
+/// # Returns
+/// * `search_handle`: Search handle that can be used later to fetch records by small batches (with fetch_credentials)
+/// * `total_count`: Total count of records
+pub fn prover_search_credentials_with_projection(wallet_handle: WalletHandle, query_json: Option<&str>, projection_json: &str) -> Box<dyn Future<Item=(SearchHandle, usize), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle_usize();
+
+    let err = _prover_search_credentials_with_projection(command_handle, wallet_handle, query_json, projection_json, cb);
+
+    ResultHandler::handle_usize(command_handle, err, receiver)
+}
+
+fn _prover_search_credentials_with_projection(command_handle: CommandHandle, wallet_handle: WalletHandle, query_json: Option<&str>, projection_json: &str, cb: Option<ResponseI32UsizeCB>) -> ErrorCode {
+    let query_json_str = opt_c_str!(query_json);
+    let projection_json = c_str!(projection_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_search_credentials_with_projection(command_handle, wallet_handle, opt_c_ptr!(query_json, query_json_str), projection_json.as_ptr(), cb)
+    })
+}
+
 /// Fetch next credentials for search.
 ///
 /// # Arguments
@@ -1120,13 +1508,18 @@ fn _prover_close_credentials_search_for_proof_req(command_handle: CommandHandle,
 ///             "self_attested_attribute_referent": string
 ///         },
 ///         "requested_attributes": {
-///             "requested_attribute_referent_1": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool> }},
-///             "requested_attribute_referent_2": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool> }}
+///             "requested_attribute_referent_1": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool>, "wallet_handle": Optional<number> }},
+///             "requested_attribute_referent_2": {"cred_id": string, "timestamp": Optional<number>, revealed: <bool>, "wallet_handle": Optional<number> }}
 ///         },
 ///         "requested_predicates": {
-///             "requested_predicates_referent_1": {"cred_id": string, "timestamp": Optional<number> }},
+///             "requested_predicates_referent_1": {"cred_id": string, "timestamp": Optional<number>, "wallet_handle": Optional<number> }},
 ///         }
 ///     }
+///     "wallet_handle" allows to take the credential from another opened wallet (e.g. guardian wallet).
+///     If omitted the credential is taken from the wallet passed to this function.
+///     NOTE: all used credentials must be issued for the same master secret stored in the wallet passed to this function.
+///     Proof can't combine credentials issued for different master secrets, so credentials held by the guardian
+///     on behalf of the dependent must be issued for the dependent's master secret.
 /// * `master_secret_id`: the id of the master secret stored in the wallet
 /// * `schemas_json`: all schemas json participating in the proof request
 ///     {
@@ -1235,6 +1628,95 @@ fn _prover_create_proof(command_handle: CommandHandle, wallet_handle: WalletHand
     })
 }
 
+/// Previews what the proof created by `prover_create_proof` for the same proof request and
+/// requested credentials would disclose, so the holder can review it before the proof is created.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `proof_req_json`: proof request json (see `prover_create_proof`)
+/// * `requested_credentials_json`: either a credential or self-attested attribute for each requested attribute (see `prover_create_proof`)
+///
+/// # Returns
+/// Disclosure preview json
+///     {
+///         "revealed_attrs": {
+///             "requested_attr1_id": {"cred_id": string, "name": string, "raw": string},
+///         },
+///         "revealed_attr_groups": {
+///             "requested_attr5_id": {"cred_id": string, "values": {"attribute_name": raw_value}},
+///         },
+///         "unrevealed_attrs": {
+///             "requested_attr3_id": {"cred_id": string, "names": [string]}, - proven to exist, value stays hidden
+///         },
+///         "self_attested_attrs": {
+///             "requested_attr2_id": self_attested_value,
+///         },
+///         "predicates": {
+///             "requested_predicate_1_referent": {"cred_id": string, "name": string, "p_type": string, "p_value": int}, - attribute value stays hidden
+///         },
+///         "credentials": {
+///             "cred_id": {
+///                 "schema_id": string, "cred_def_id": string, "rev_reg_id": Optional<string>, - disclosed as proof identifiers
+///                 "timestamps": [int], - disclosed as proof identifiers
+///                 "hidden_attrs": [string] - attributes of the credential the proof doesn't reveal
+///             }
+///         }
+///     }
+pub fn prover_preview_disclosure(wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_preview_disclosure(command_handle, wallet_handle, proof_req_json, requested_credentials_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_preview_disclosure(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_req_json = c_str!(proof_req_json);
+    let requested_credentials_json = c_str!(requested_credentials_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_preview_disclosure(command_handle, wallet_handle, proof_req_json.as_ptr(), requested_credentials_json.as_ptr(), cb)
+    })
+}
+
+/// Binds a proof to the holder DID by signing it with the DID verkey stored in the wallet.
+///
+/// The signature covers the proof and the nonce of the proof request it was created for.
+/// Verifier checks the binding as part of `verifier_verify_proof`.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `proof_req_json`: proof request json the proof was created for
+/// * `proof_json`: proof json created by prover_create_proof
+/// * `holder_did`: DID of the holder stored in the wallet
+///
+/// # Returns
+/// Proof json with appended holder binding:
+///     {
+///         ... - fields of the proof
+///         "holder_binding": {
+///             "did": string, - holder DID
+///             "signature": string - base58 encoded signature over the proof request nonce and the proof
+///         }
+///     }
+pub fn prover_bind_proof(wallet_handle: WalletHandle, proof_req_json: &str, proof_json: &str, holder_did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_bind_proof(command_handle, wallet_handle, proof_req_json, proof_json, holder_did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_bind_proof(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_req_json: &str, proof_json: &str, holder_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_req_json = c_str!(proof_req_json);
+    let proof_json = c_str!(proof_json);
+    let holder_did = c_str!(holder_did);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_bind_proof(command_handle, wallet_handle, proof_req_json.as_ptr(), proof_json.as_ptr(), holder_did.as_ptr(), cb)
+    })
+}
+
 
 /// Verifies a proof (of multiple credential).
 /// All required schemas, public keys and revocation registries must be provided.
@@ -1345,6 +1827,131 @@ fn _verifier_verify_proof(command_handle: CommandHandle, proof_request_json: &st
     })
 }
 
+/// Creates verifier session keeping parsed ledger artifacts to verify many proofs built from them.
+///
+/// # Arguments
+/// * `schemas_json`: all schemas json participating in the proofs (see `verifier_verify_proof`)
+/// * `credential_defs_json`: all credential definitions json participating in the proofs (see `verifier_verify_proof`)
+/// * `rev_reg_defs_json`: all revocation registry definitions json participating in the proofs (see `verifier_verify_proof`)
+///
+/// # Returns
+/// * `session_handle`: handle of the session to pass to `verifier_verify_proof_with_session`
+pub fn verifier_create_session(schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str) -> Box<dyn Future<Item=IndyHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _verifier_create_session(command_handle, schemas_json, credential_defs_json, rev_reg_defs_json, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+fn _verifier_create_session(command_handle: CommandHandle, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, cb: Option<ResponseI32CB>) -> ErrorCode {
+    let schemas_json = c_str!(schemas_json);
+    let credential_defs_json = c_str!(credential_defs_json);
+    let rev_reg_defs_json = c_str!(rev_reg_defs_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_create_session(command_handle, schemas_json.as_ptr(), credential_defs_json.as_ptr(), rev_reg_defs_json.as_ptr(), cb)
+    })
+}
+
+/// Verifies a proof using ledger artifacts kept by the verifier session.
+///
+/// # Arguments
+/// * `session_handle`: session handle (created by `verifier_create_session`)
+/// * `proof_request_json`: proof request json (see `verifier_verify_proof`)
+/// * `proof_json`: created for request proof json (see `verifier_verify_proof`)
+/// * `rev_regs_json`: all revocation registries json participating in the proof (see `verifier_verify_proof`)
+///
+/// # Returns
+/// * `valid`: true - if signature is valid, false - otherwise
+pub fn verifier_verify_proof_with_session(session_handle: IndyHandle, proof_request_json: &str, proof_json: &str, rev_regs_json: &str) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verifier_verify_proof_with_session(command_handle, session_handle, proof_request_json, proof_json, rev_regs_json, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verifier_verify_proof_with_session(command_handle: CommandHandle, session_handle: IndyHandle, proof_request_json: &str, proof_json: &str, rev_regs_json: &str, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let proof_request_json = c_str!(proof_request_json);
+    let proof_json = c_str!(proof_json);
+    let rev_regs_json = c_str!(rev_regs_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_verify_proof_with_session(command_handle, session_handle, proof_request_json.as_ptr(), proof_json.as_ptr(), rev_regs_json.as_ptr(), cb)
+    })
+}
+
+/// Closes verifier session and frees ledger artifacts kept by it.
+///
+/// # Arguments
+/// * `session_handle`: session handle (created by `verifier_create_session`)
+pub fn verifier_close_session(session_handle: IndyHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _verifier_close_session(command_handle, session_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _verifier_close_session(command_handle: CommandHandle, session_handle: IndyHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_close_session(command_handle, session_handle, cb)
+    })
+}
+
+/// Saves proof request template in the wallet to instantiate proof requests from it later.
+/// Template with the same id is replaced.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet)
+/// * `template_id`: identifier of the template in the wallet
+/// * `template_json`: proof request json without nonce. Any string value in form of "${param_name}"
+///     is a placeholder replaced with the parameter passed to `verifier_instantiate_proof_request_template`.
+pub fn verifier_save_proof_request_template(wallet_handle: WalletHandle, template_id: &str, template_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _verifier_save_proof_request_template(command_handle, wallet_handle, template_id, template_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _verifier_save_proof_request_template(command_handle: CommandHandle, wallet_handle: WalletHandle, template_id: &str, template_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let template_id = c_str!(template_id);
+    let template_json = c_str!(template_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_save_proof_request_template(command_handle, wallet_handle, template_id.as_ptr(), template_json.as_ptr(), cb)
+    })
+}
+
+/// Builds proof request from the template saved by `verifier_save_proof_request_template`.
+/// Placeholders of the template are replaced with the parameters and fresh nonce is generated.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet)
+/// * `template_id`: identifier of the template in the wallet
+/// * `params_json`: values of the template placeholders {"<param_name>": <any json value>}
+///
+/// # Returns
+/// * `proof_request_json`: proof request json ready to be sent to the prover
+pub fn verifier_instantiate_proof_request_template(wallet_handle: WalletHandle, template_id: &str, params_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_instantiate_proof_request_template(command_handle, wallet_handle, template_id, params_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verifier_instantiate_proof_request_template(command_handle: CommandHandle, wallet_handle: WalletHandle, template_id: &str, params_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let template_id = c_str!(template_id);
+    let params_json = c_str!(params_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_instantiate_proof_request_template(command_handle, wallet_handle, template_id.as_ptr(), params_json.as_ptr(), cb)
+    })
+}
+
 
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
@@ -1494,3 +2101,120 @@ fn _to_unqualified(command_handle: CommandHandle, entity: &str, cb: Option<Respo
         anoncreds::indy_to_unqualified(command_handle, entity.as_ptr(), cb)
     })
 }
+
+/// Encodes json of anoncreds object (Proof, Credential and etc) into MessagePack.
+/// Binary form is more compact than json and can be decoded back with `msgpack_to_json`.
+///
+/// # Arguments
+/// * `json` - json of anoncreds object to encode.
+///
+/// # Returns
+/// MessagePack encoded object
+pub fn json_to_msgpack(json: &str) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _json_to_msgpack(command_handle, json, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _json_to_msgpack(command_handle: CommandHandle, json: &str, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let json = c_str!(json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_anoncreds_json_to_msgpack(command_handle, json.as_ptr(), cb)
+    })
+}
+
+/// Decodes anoncreds object encoded by `json_to_msgpack` back into json.
+///
+/// # Arguments
+/// * `msgpack` - MessagePack encoded object.
+///
+/// # Returns
+/// json of the decoded object
+pub fn msgpack_to_json(msgpack: &[u8]) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _msgpack_to_json(command_handle, msgpack, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _msgpack_to_json(command_handle: CommandHandle, msgpack: &[u8], cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_anoncreds_msgpack_to_json(command_handle, msgpack.as_ptr() as *const u8, msgpack.len() as u32, cb)
+    })
+}
+
+/// Encodes raw value of credential attribute into the integer used in CL signatures.
+/// 32-bit integers in canonical form are encoded as themselves,
+/// any other value as decimal representation of sha256 hash of its utf-8 bytes.
+///
+/// # Arguments
+/// * `raw` - raw value of the attribute.
+///
+/// # Returns
+/// encoded value of the attribute
+pub fn encode_attribute_value(raw: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _encode_attribute_value(command_handle, raw, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _encode_attribute_value(command_handle: CommandHandle, raw: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let raw = c_str!(raw);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_encode_attribute_value(command_handle, raw.as_ptr(), cb)
+    })
+}
+
+/// Wraps anoncreds credential into W3C Verifiable Credential data model (Experimental).
+///
+/// # Arguments
+/// * `cred_json` - credential json returned by issuer_create_credential.
+///
+/// # Returns
+/// W3C Verifiable Credential json with the proof of CLSignature2019 type
+pub fn to_w3c_credential(cred_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _to_w3c_credential(command_handle, cred_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _to_w3c_credential(command_handle: CommandHandle, cred_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_json = c_str!(cred_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_to_w3c_credential(command_handle, cred_json.as_ptr(), cb)
+    })
+}
+
+/// Converts W3C Verifiable Credential created by to_w3c_credential back to anoncreds credential (Experimental).
+/// Raw values of "credentialSubject" must match the encoded values of the proof.
+///
+/// # Arguments
+/// * `w3c_cred_json` - W3C Verifiable Credential json.
+///
+/// # Returns
+/// credential json in the format returned by issuer_create_credential
+pub fn from_w3c_credential(w3c_cred_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _from_w3c_credential(command_handle, w3c_cred_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _from_w3c_credential(command_handle: CommandHandle, w3c_cred_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let w3c_cred_json = c_str!(w3c_cred_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_from_w3c_credential(command_handle, w3c_cred_json.as_ptr(), cb)
+    })
+}
@@ -7,8 +7,8 @@ use std::ffi::CString;
 use utils::callbacks::{ClosureHandler, ResultHandler};
 
 use ffi::cache;
-use ffi::{ResponseEmptyCB, ResponseStringCB};
-use {WalletHandle, CommandHandle, PoolHandle};
+use ffi::{ResponseEmptyCB, ResponseI32CB, ResponseStringCB};
+use {WalletHandle, CommandHandle, PoolHandle, IndyHandle};
 
 /// Get schema json data for specified schema id.
 /// If data is present inside of cache, cached data is returned.
@@ -176,4 +176,120 @@ fn _purge_cred_def_cache(command_handle: CommandHandle, wallet_handle: WalletHan
     let options_json = c_str!(options_json);
 
     ErrorCode::from(unsafe { cache::indy_purge_cred_def_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
-}
\ No newline at end of file
+}
+
+/// Purge NYM cache keeping verkeys resolved by `crypto::pack_message_for_dids`.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `options_json` -
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+pub fn purge_nym_cache(wallet_handle: WalletHandle, options_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _purge_nym_cache(command_handle, wallet_handle, options_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _purge_nym_cache(command_handle: CommandHandle, wallet_handle: WalletHandle, options_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { cache::indy_purge_nym_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
+}
+
+/// Starts background prefetching of revocation states for frequently used credentials.
+/// Revocation states are periodically updated from the ledger and stored inside of cache,
+/// so they can be taken by `get_cached_revocation_state` without waiting for the ledger.
+/// Credentials are ranked by the number of proofs created with them by `anoncreds::prover_create_proof`.
+/// Prefetching is stopped once the wallet or blob storage reader is closed.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `blob_storage_reader_handle` - blob storage reader handle that will allow to read revocation tails.
+/// * `options_json` -
+///  {
+///    interval: (int, optional, 300 by default) Prefetch revocation states every this many seconds.
+///    freshness: (int, optional, 600 by default) Refresh cached revocation state if older than this many seconds.
+///    maxRequests: (int, optional, 10 by default) Refresh not more than this many revocation states per round.
+///    maxCredentials: (int, optional, 20 by default) Keep revocation states of this many credentials
+///                    most frequently used to build proofs.
+///  }
+/// # Returns
+/// Handle to stop prefetching with `stop_revocation_prefetch`.
+pub fn start_revocation_prefetch(pool_handle: PoolHandle,
+                                 wallet_handle: WalletHandle,
+                                 blob_storage_reader_handle: IndyHandle,
+                                 options_json: &str) -> Box<dyn Future<Item=IndyHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _start_revocation_prefetch(command_handle, pool_handle, wallet_handle, blob_storage_reader_handle, options_json, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+fn _start_revocation_prefetch(command_handle: CommandHandle,
+                              pool_handle: PoolHandle,
+                              wallet_handle: WalletHandle,
+                              blob_storage_reader_handle: IndyHandle,
+                              options_json: &str,
+                              cb: Option<ResponseI32CB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        cache::indy_start_revocation_prefetch(command_handle,
+                                              pool_handle,
+                                              wallet_handle,
+                                              blob_storage_reader_handle,
+                                              options_json.as_ptr(),
+                                              cb)
+    })
+}
+
+/// Stops background prefetching of revocation states started by `start_revocation_prefetch`.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `prefetch_handle` - handle returned by `start_revocation_prefetch`.
+pub fn stop_revocation_prefetch(prefetch_handle: IndyHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _stop_revocation_prefetch(command_handle, prefetch_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _stop_revocation_prefetch(command_handle: CommandHandle, prefetch_handle: IndyHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { cache::indy_stop_revocation_prefetch(command_handle, prefetch_handle, cb) })
+}
+
+/// Gets revocation state of the credential prefetched by `start_revocation_prefetch`.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `cred_id` - id of the credential stored in the wallet.
+/// # Returns
+/// Revocation state json.
+pub fn get_cached_revocation_state(wallet_handle: WalletHandle, cred_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_cached_revocation_state(command_handle, wallet_handle, cred_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_cached_revocation_state(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_id = c_str!(cred_id);
+
+    ErrorCode::from(unsafe { cache::indy_get_cached_revocation_state(command_handle, wallet_handle, cred_id.as_ptr(), cb) })
+}
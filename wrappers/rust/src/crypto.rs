@@ -12,7 +12,62 @@ use std::ptr::null;
 
 use {ErrorCode, IndyError};
 use utils::callbacks::{ClosureHandler, ResultHandler};
-use {WalletHandle, CommandHandle};
+use {WalletHandle, CommandHandle, PoolHandle};
+
+/// Registers key vault (HSM, secure enclave and etc) which keeps secret keys outside of libindy.
+/// Keys created with "key_vault" and "key_ref" fields of `create_key` config are stored in the wallet as references only
+/// and signing with them is delegated to the vault. Signatures returned by the vault are verified with the verkey of the key.
+///
+/// # Arguments
+/// * `key_vault` - name of the key vault
+/// * `get_verkey` - returns base58 verkey of the key by its reference
+/// * `sign` - signs message with the key by its reference
+/// * `free` - frees data returned by `get_verkey` and `sign`
+pub fn register_key_vault(key_vault: &str,
+                          get_verkey: Option<crypto::KeyVaultGetVerkey>,
+                          sign: Option<crypto::KeyVaultSign>,
+                          free: Option<crypto::KeyVaultFree>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_key_vault(command_handle, key_vault, get_verkey, sign, free, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_key_vault(command_handle: CommandHandle,
+                       key_vault: &str,
+                       get_verkey: Option<crypto::KeyVaultGetVerkey>,
+                       sign: Option<crypto::KeyVaultSign>,
+                       free: Option<crypto::KeyVaultFree>,
+                       cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let key_vault = c_str!(key_vault);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_register_key_vault(command_handle, key_vault.as_ptr(), get_verkey, sign, free, cb)
+    })
+}
+
+/// Registers external source of randomness (host RNG, certified DRBG and etc) used instead of the default OS one
+/// for generation of keys created without seed, nonces and content encryption keys.
+/// Bytes returned by the source are used as seed material of HMAC-DRBG only.
+///
+/// # Arguments
+/// * `fill` - fills buffer with random bytes
+pub fn register_entropy_source(fill: Option<crypto::EntropySourceFill>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_entropy_source(command_handle, fill, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_entropy_source(command_handle: CommandHandle,
+                            fill: Option<crypto::EntropySourceFill>,
+                            cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        crypto::indy_register_entropy_source(command_handle, fill, cb)
+    })
+}
 
 /// Creates key pair in wallet
 /// # Arguments
@@ -24,7 +79,9 @@ use {WalletHandle, CommandHandle};
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values are 'ed25519', 'secp256k1' and 'p256'; 'secp256k1' and 'p256' keys can be used for signing only.
+///     "key_vault": string, (optional) Name of the key vault keeping the secret key (see `register_key_vault`).
+///     "key_ref": string, (optional) Reference to the key inside of the key vault. Required if "key_vault" is set.
 /// }
 /// # Returns
 /// verkey of generated key pair, also used as key identifier
@@ -42,6 +99,38 @@ fn _create_key(command_handle: CommandHandle, wallet_handle: WalletHandle, my_ke
     ErrorCode::from(unsafe { crypto::indy_create_key(command_handle, wallet_handle, my_key_json.as_ptr(), cb) })
 }
 
+/// Generates BLS key pair with proof of possession of the secret key for a validator node.
+/// Generated key isn't stored in the wallet.
+/// # Arguments
+/// * `key_json` - Optional key information as json. If none then defaults are used.
+///
+/// # Example
+/// key_json
+/// {
+///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
+///                                Can be UTF-8, base64 or hex string.
+/// }
+/// # Returns
+/// generated key as json
+/// {
+///     "blskey": string, - BLS multi-signature key as base58-encoded string.
+///     "blskey_pop": string, - BLS key proof of possession as base58-encoded string.
+///     "signkey": string, - BLS secret key as base58-encoded string.
+/// }
+pub fn create_bls_key(key_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _create_bls_key(command_handle, key_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _create_bls_key(command_handle: CommandHandle, key_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let key_json = opt_c_str_json!(key_json);
+
+    ErrorCode::from(unsafe { crypto::indy_create_bls_key(command_handle, key_json.as_ptr(), cb) })
+}
+
 /// Saves/replaces the metadata for the `verkey` in the wallet
 /// # Arguments
 /// * `wallet_handle` - wallet handle (created by Wallet::open)
@@ -82,6 +171,65 @@ fn _get_key_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle,
     ErrorCode::from(unsafe { crypto::indy_get_key_metadata(command_handle, wallet_handle, verkey.as_ptr(), cb) })
 }
 
+/// Retrieves the path the `verkey` was derived by from the master seed
+/// # Argument
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `verkey` - the public key or key id to retrieve derivation path
+/// # Returns
+/// SLIP-0010 derivation path passed on key creation
+pub fn get_key_derivation_path(wallet_handle: WalletHandle, verkey: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_key_derivation_path(command_handle, wallet_handle, verkey, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_key_derivation_path(command_handle: CommandHandle, wallet_handle: WalletHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe { crypto::indy_get_key_derivation_path(command_handle, wallet_handle, verkey.as_ptr(), cb) })
+}
+
+/// Retrieves X25519 key agreement key of the ed25519 `verkey` stored in the wallet
+/// # Argument
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `verkey` - the public key or key id stored in the wallet
+/// # Returns
+/// base58 encoded X25519 public key with ":x25519" crypto type suffix; can be used as a receiver key in pack_message
+pub fn get_key_agreement_key(wallet_handle: WalletHandle, verkey: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_key_agreement_key(command_handle, wallet_handle, verkey, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_key_agreement_key(command_handle: CommandHandle, wallet_handle: WalletHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe { crypto::indy_get_key_agreement_key(command_handle, wallet_handle, verkey.as_ptr(), cb) })
+}
+
+/// Converts ed25519 `verkey` to X25519 public key. The key doesn't need to be stored in the wallet.
+/// # Argument
+/// * `verkey` - ed25519 verkey
+/// # Returns
+/// base58 encoded X25519 public key with ":x25519" crypto type suffix
+pub fn convert_key(verkey: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _convert_key(command_handle, verkey, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _convert_key(command_handle: CommandHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe { crypto::indy_convert_key(command_handle, verkey.as_ptr(), cb) })
+}
+
 /// Signs a message with a key
 /// # Arguments
 /// * `wallet_handle` - wallet handle (created by Wallet::open)
@@ -133,6 +281,34 @@ fn _verify(command_handle: CommandHandle, signer_vk: &str, message: &[u8], signa
     })
 }
 
+/// Verify a signature with a verkey choosing how malformed inputs are reported
+/// # Arguments
+/// * `signer_vk` - verkey of the message signer
+/// * `message` - the data that was signed
+/// * `signature` - the signature to verify
+/// * `strict` - if true malformed verkey or signature is reported as an error with the detail,
+///              otherwise it is reported as invalid signature
+/// # Returns
+/// true if signature is valid, false otherwise
+pub fn verify_with_strictness(signer_vk: &str, message: &[u8], signature: &[u8], strict: bool) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verify_with_strictness(command_handle, signer_vk, message, signature, strict, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verify_with_strictness(command_handle: CommandHandle, signer_vk: &str, message: &[u8], signature: &[u8], strict: bool, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let signer_vk = c_str!(signer_vk);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_verify_with_strictness(command_handle, signer_vk.as_ptr(),
+                                                   message.as_ptr() as *const u8, message.len() as u32,
+                                                   signature.as_ptr() as *const u8, signature.len() as u32,
+                                                   strict, cb)
+    })
+}
+
 /// **** THIS FUNCTION WILL BE DEPRECATED USE pack_message INSTEAD ****
 /// Encrypt a message by authenticated-encryption scheme.
 ///
@@ -282,6 +458,75 @@ fn _anon_decrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, rec
     })
 }
 
+/// Encrypts a message with libsodium crypto_box primitive using the given nonce.
+/// The result contains only the ciphertext with authentication tag.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `sender_vk`: key id or verkey of my key. The key must be created by calling create_key or Did::new
+/// * `recipient_vk`: verkey of message recipient or X25519 key returned by convert_key
+/// * `message`: message to be encrypted
+/// * `nonce`: 24 bytes nonce. Must never be reused with the same pair of keys
+///
+/// # Returns
+/// encrypted message
+pub fn crypto_box(wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &str, message: &[u8], nonce: &[u8]) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _crypto_box(command_handle, wallet_handle, sender_vk, recipient_vk, message, nonce, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _crypto_box(command_handle: CommandHandle, wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &str, message: &[u8], nonce: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let sender_vk = c_str!(sender_vk);
+    let recipient_vk = c_str!(recipient_vk);
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_box(command_handle,
+                                wallet_handle,
+                                sender_vk.as_ptr(),
+                                recipient_vk.as_ptr(),
+                                message.as_ptr() as *const u8,
+                                message.len() as u32,
+                                nonce.as_ptr() as *const u8,
+                                nonce.len() as u32, cb)
+    })
+}
+
+/// Decrypts a message encrypted with crypto_box.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `recipient_vk`: key id or verkey of my key. The key must be created by calling create_key or Did::new
+/// * `sender_vk`: verkey of message sender
+/// * `encrypted_message`: message to be decrypted
+/// * `nonce`: 24 bytes nonce the message was encrypted with
+///
+/// # Returns
+/// decrypted message
+pub fn crypto_box_open(wallet_handle: WalletHandle, recipient_vk: &str, sender_vk: &str, encrypted_message: &[u8], nonce: &[u8]) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _crypto_box_open(command_handle, wallet_handle, recipient_vk, sender_vk, encrypted_message, nonce, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _crypto_box_open(command_handle: CommandHandle, wallet_handle: WalletHandle, recipient_vk: &str, sender_vk: &str, encrypted_message: &[u8], nonce: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let recipient_vk = c_str!(recipient_vk);
+    let sender_vk = c_str!(sender_vk);
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_box_open(command_handle,
+                                     wallet_handle,
+                                     recipient_vk.as_ptr(),
+                                     sender_vk.as_ptr(),
+                                     encrypted_message.as_ptr() as *const u8,
+                                     encrypted_message.len() as u32,
+                                     nonce.as_ptr() as *const u8,
+                                     nonce.len() as u32, cb)
+    })
+}
+
 /// Unpacks a message packed using indy_pack_message which follows the wire message format HIPE
 /// (Experimental)
 ///
@@ -317,6 +562,86 @@ fn _pack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, mes
 
 }
 
+/// Packs a message with explicitly chosen packing mode (Experimental)
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `message`: a pointer to the first byte of the message to be encrypted
+/// * `receiver_keys`: a JSON array as a string containing a list of the receivers verkey's
+/// * `sender` : a string of the sender's verkey. Must be None for anoncrypt mode
+/// * `mode`: packing mode: "authcrypt", "anoncrypt" or "authcrypt_with_hidden_sender"
+/// # Returns
+/// a json structure in the form of a JWE that contains the encrypted message and associated metadata
+pub fn pack_message_with_mode(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, mode: &str) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _pack_message_with_mode(command_handle, wallet_handle, message, receiver_keys, sender, mode, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _pack_message_with_mode(command_handle: CommandHandle, wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, mode: &str, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let receiver_keys = c_str!(receiver_keys);
+    let sender_str = opt_c_str!(sender);
+    let mode = c_str!(mode);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_pack_message_with_mode(command_handle,
+                                            wallet_handle,
+                                            message.as_ptr() as *const u8,
+                                            message.len() as u32,
+                                            receiver_keys.as_ptr(),
+                                            opt_c_ptr!(sender, sender_str),
+                                            mode.as_ptr(),
+                                            cb)
+    })
+}
+
+/// Packs a message for receivers identified by their DIDs (Experimental)
+/// Receivers keys are resolved through the ledger and cached in the wallet.
+///
+/// # Arguments
+/// * `pool_handle`: pool handle (created by open_pool_ledger).
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `message`: a pointer to the first byte of the message to be encrypted
+/// * `receiver_dids`: a JSON array as a string containing a list of the receivers DIDs
+/// * `sender` : a string of the sender's verkey When None is used in this parameter, anoncrypt is used
+/// * `options_json`: cache options
+/// {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, 60 by default) Use cached key without refresh if not older than this many seconds.
+///    maxStale: (int, optional, 3600 by default) Use cached key older than minFresh but not older than this many seconds
+///              and refresh it in background.
+/// }
+/// # Returns
+/// a json structure in the form of a JWE that contains the encrypted message and associated metadata
+pub fn pack_message_for_dids(pool_handle: PoolHandle, wallet_handle: WalletHandle, message: &[u8], receiver_dids: &str, sender: Option<&str>, options_json: &str) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _pack_message_for_dids(command_handle, pool_handle, wallet_handle, message, receiver_dids, sender, options_json, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _pack_message_for_dids(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, message: &[u8], receiver_dids: &str, sender: Option<&str>, options_json: &str, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let receiver_dids = c_str!(receiver_dids);
+    let sender_str = opt_c_str!(sender);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_pack_message_for_dids(command_handle,
+                                           pool_handle,
+                                           wallet_handle,
+                                           message.as_ptr() as *const u8,
+                                           message.len() as u32,
+                                           receiver_dids.as_ptr(),
+                                           opt_c_ptr!(sender, sender_str),
+                                           options_json.as_ptr(),
+                                           cb)
+    })
+}
+
 /// Unpacks a message packed using indy_pack_message which follows the wire message format HIPE
 /// (Experimental)
 ///
@@ -344,3 +669,63 @@ fn _unpack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, j
     })
 }
 
+/// Lists messages which failed to be unpacked by unpack_message (dead letters).
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open)
+/// # Returns
+/// a json list of dead letters: [{"id", "message", "error", "received", "attempts"}]
+pub fn list_dead_letters(wallet_handle: WalletHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _list_dead_letters(command_handle, wallet_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _list_dead_letters(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { crypto::indy_list_dead_letters(command_handle, wallet_handle, cb) })
+}
+
+/// Retries unpacking of the message stored as dead letter.
+/// Dead letter is removed from the wallet if the message is unpacked successfully.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open)
+/// * `dead_letter_id`: id of the dead letter
+/// # Returns
+/// the same json structure as unpack_message returns
+pub fn retry_dead_letter(wallet_handle: WalletHandle, dead_letter_id: &str) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _retry_dead_letter(command_handle, wallet_handle, dead_letter_id, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _retry_dead_letter(command_handle: CommandHandle, wallet_handle: WalletHandle, dead_letter_id: &str, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let dead_letter_id = c_str!(dead_letter_id);
+
+    ErrorCode::from(unsafe { crypto::indy_retry_dead_letter(command_handle, wallet_handle, dead_letter_id.as_ptr(), cb) })
+}
+
+/// Removes dead letters from the wallet.
+/// At most 100 dead letters are kept, the oldest ones are dropped when a new one is stored.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open)
+/// * `received_before`: remove only dead letters received before this unix timestamp, all of them if None
+pub fn purge_dead_letters(wallet_handle: WalletHandle, received_before: Option<u64>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _purge_dead_letters(command_handle, wallet_handle, received_before, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _purge_dead_letters(command_handle: CommandHandle, wallet_handle: WalletHandle, received_before: Option<u64>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let received_before = opt_u64!(received_before);
+
+    ErrorCode::from(unsafe { crypto::indy_purge_dead_letters(command_handle, wallet_handle, received_before, cb) })
+}
+
@@ -1,11 +1,13 @@
 use {ErrorCode, IndyError};
 
 use std::ffi::CString;
+use std::ptr::null;
 
 use futures::Future;
 
 use ffi::did;
-use ffi::{ResponseEmptyCB,
+use ffi::{ResponseBoolCB,
+          ResponseEmptyCB,
           ResponseStringCB,
           ResponseStringStringCB};
 
@@ -32,7 +34,8 @@ use {CommandHandle, WalletHandle, PoolHandle};
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+///               'secp256k1' and 'p256' keys can be used for signing only)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
 /// }
@@ -54,6 +57,43 @@ fn _create_and_store_my_did(command_handle: CommandHandle, wallet_handle: Wallet
     ErrorCode::from(unsafe { did::indy_create_and_store_my_did(command_handle, wallet_handle, did_json.as_ptr(), cb) })
 }
 
+/// Creates and stores in the wallet a batch of new DIDs in one command.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `count` - number of DIDs to create (from 1 to 1000).
+/// * `options_json` - batch options as json.
+///
+///  # Examples
+/// `options_json`
+/// {
+///     "hd_seed": string, (optional) Master seed of 16 to 64 bytes the ed25519 key of every DID is derived from
+///                by SLIP-0010 path m/<index>'.
+///     "hd_seed_encoding": string, (required if "hd_seed" is set) Encoding of "hd_seed": 'hex', 'base64' or 'utf8'.
+///     "start_index": int, (optional) Index of the first DID derived from "hd_seed" (0 by default).
+///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+///               'secp256k1' and 'p256' keys can be used for signing only)
+///     "cid": bool, (optional; if not set then false is used;)
+///     "method_name": string, (optional) method name to create fully qualified dids.
+/// }
+///
+/// # Returns
+///   * `dids_json` - list of created DIDs: [{"did": string, "verkey": string, "index": int (only for "hd_seed")}]
+pub fn create_and_store_my_dids(wallet_handle: WalletHandle, count: u32, options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _create_and_store_my_dids(command_handle, wallet_handle, count, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _create_and_store_my_dids(command_handle: CommandHandle, wallet_handle: WalletHandle, count: u32, options_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { did::indy_create_and_store_my_dids(command_handle, wallet_handle, count, options_json.as_ptr(), cb) })
+}
+
 /// Generated temporary keys (signing and encryption keys) for an existing
 /// DID (owned by the caller of the library).
 ///
@@ -67,7 +107,8 @@ fn _create_and_store_my_did(command_handle: CommandHandle, wallet_handle: Wallet
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               'ed25519', 'secp256k1' and 'p256' values are supported for this field;
+///               'secp256k1' and 'p256' keys can be used for signing only)
 /// }
 ///
 /// # Returns
@@ -289,6 +330,51 @@ fn _get_did_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle,
     ErrorCode::from(unsafe { did::indy_get_did_metadata(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
 }
 
+/// Designates the key to be used by my DID for the particular purpose.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `did` - my DID.
+/// * `purpose` - key purpose: "authentication" | "assertionMethod" | "keyAgreement".
+/// * `verkey` - verkey of the key stored in the wallet or key agreement key for "keyAgreement" purpose.
+pub fn set_did_key(wallet_handle: WalletHandle, did: &str, purpose: &str, verkey: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _set_did_key(command_handle, wallet_handle, did, purpose, verkey, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _set_did_key(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, purpose: &str, verkey: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let did = c_str!(did);
+    let purpose = c_str!(purpose);
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe { did::indy_set_did_key(command_handle, wallet_handle, did.as_ptr(), purpose.as_ptr(), verkey.as_ptr(), cb) })
+}
+
+/// Returns keys my DID uses for every purpose.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `did` - my DID.
+///
+/// # Returns
+/// * `did_keys_json` - "authentication", "assertionMethod" and optional "keyAgreement" keys of the DID.
+pub fn get_did_keys(wallet_handle: WalletHandle, did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_did_keys(command_handle, wallet_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_did_keys(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_get_did_keys(command_handle, wallet_handle, did.as_ptr(), cb) })
+}
+
 /// Retrieves the information about the giving DID in the wallet.
 ///
 /// # Arguments
@@ -389,3 +475,237 @@ fn _qualify_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did:
 
     ErrorCode::from(unsafe { did::indy_qualify_did(command_handle, wallet_handle, did.as_ptr(), method.as_ptr(), cb) })
 }
+
+/// Creates a self-contained proof of control over DID written to the ledger.
+///
+/// The challenge is signed with the DID's key and the proof references the DID's NYM transaction on the ledger.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `did` - DID stored in the wallet and written to the ledger.
+/// * `challenge` - challenge (nonce) provided by the verifier.
+/// * `audience` - identifier of the verifier the proof is created for.
+///
+/// # Returns
+/// proof of DID control json:
+///     {
+///         "did": string,
+///         "verkey": string,
+///         "challenge": string,
+///         "audience": string,
+///         "signature": string, - base58 encoded signature of the challenge bound to the DID and audience
+///         "seqNo": Optional<int> - sequence number of the DID's NYM transaction on the ledger
+///     }
+pub fn create_did_ownership_proof(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, challenge: &str, audience: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _create_did_ownership_proof(command_handle, wallet_handle, pool_handle, did, challenge, audience, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _create_did_ownership_proof(command_handle: CommandHandle, wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, challenge: &str, audience: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+    let challenge = c_str!(challenge);
+    let audience = c_str!(audience);
+
+    ErrorCode::from(unsafe { did::indy_create_did_ownership_proof(command_handle, wallet_handle, pool_handle, did.as_ptr(), challenge.as_ptr(), audience.as_ptr(), cb) })
+}
+
+/// Checks NYM of DID stored in the wallet against the ledger.
+///
+/// Observed verkey and role are remembered in the wallet. If the ledger verkey differs from the wallet one
+/// or the role differs from previously observed, "did_nym_changed" event is reported to the event listener.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open).
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `did` - DID stored in the wallet and written to the ledger.
+///
+/// # Returns
+/// NYM state on the ledger json:
+///     {
+///         "verkey": string,
+///         "role": Optional<string>,
+///         "changed": bool - whether NYM has been changed not through this wallet
+///     }
+pub fn check_my_did_nym(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _check_my_did_nym(command_handle, wallet_handle, pool_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _check_my_did_nym(command_handle: CommandHandle, wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_check_my_did_nym(command_handle, wallet_handle, pool_handle, did.as_ptr(), cb) })
+}
+
+/// Verifies a proof of DID control created by `create_did_ownership_proof` against the ledger.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `proof_json` - proof of DID control.
+/// * `challenge` - challenge expected to be signed.
+/// * `audience` - identifier of the verifier expected in the proof.
+///
+/// # Returns
+/// true - if the proof is correct, false - otherwise
+pub fn verify_did_ownership_proof(pool_handle: PoolHandle, proof_json: &str, challenge: &str, audience: &str) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verify_did_ownership_proof(command_handle, pool_handle, proof_json, challenge, audience, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verify_did_ownership_proof(command_handle: CommandHandle, pool_handle: PoolHandle, proof_json: &str, challenge: &str, audience: &str, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let proof_json = c_str!(proof_json);
+    let challenge = c_str!(challenge);
+    let audience = c_str!(audience);
+
+    ErrorCode::from(unsafe { did::indy_verify_did_ownership_proof(command_handle, pool_handle, proof_json.as_ptr(), challenge.as_ptr(), audience.as_ptr(), cb) })
+}
+
+/// Publishes endpoint of the DID to the ledger as standardized endpoint ATTRIB
+/// and updates the endpoint address stored in the wallet for this DID.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `did` - The DID to publish endpoint for.
+/// * `address` - The DIDs endpoint address.
+/// * `types_json` - (Optional) json array of endpoint types.
+/// * `routing_keys_json` - (Optional) json array of routing keys ordered from the recipient.
+pub fn publish_endpoint_for_did(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, address: &str, types_json: Option<&str>, routing_keys_json: Option<&str>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _publish_endpoint_for_did(command_handle, wallet_handle, pool_handle, did, address, types_json, routing_keys_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _publish_endpoint_for_did(command_handle: CommandHandle, wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, address: &str, types_json: Option<&str>, routing_keys_json: Option<&str>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let did = c_str!(did);
+    let address = c_str!(address);
+    let types_json_str = opt_c_str!(types_json);
+    let routing_keys_json_str = opt_c_str!(routing_keys_json);
+
+    ErrorCode::from(unsafe {
+        did::indy_publish_endpoint_for_did(command_handle,
+                                           wallet_handle,
+                                           pool_handle,
+                                           did.as_ptr(),
+                                           address.as_ptr(),
+                                           opt_c_ptr!(types_json, types_json_str),
+                                           opt_c_ptr!(routing_keys_json, routing_keys_json_str),
+                                           cb)
+    })
+}
+
+/// Resolves endpoint ATTRIB of the DID into DID Document service entry.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `did` - The DID to resolve endpoint.
+///
+/// # Returns
+/// DID Document service entry json:
+///     {
+///         "id": string,
+///         "type": string, - the first endpoint type published ("did-communication" if types aren't published)
+///         "priority": 0,
+///         "recipientKeys": [string],
+///         "routingKeys": [string, ...],
+///         "serviceEndpoint": string
+///     }
+pub fn resolve_service_endpoint_for_did(pool_handle: PoolHandle, did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _resolve_service_endpoint_for_did(command_handle, pool_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _resolve_service_endpoint_for_did(command_handle: CommandHandle, pool_handle: PoolHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_resolve_service_endpoint_for_did(command_handle, pool_handle, did.as_ptr(), cb) })
+}
+
+/// Resolves the DID into W3C DID Document assembled from its NYM and endpoint ATTRIB written to the ledger.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `did` - The DID to resolve.
+///
+/// # Returns
+/// DID Document json:
+///     {
+///         "@context": "https://www.w3.org/ns/did/v1",
+///         "id": string, - fully qualified DID
+///         "verificationMethod": [{
+///             "id": string, - fully qualified DID with "#key-1" fragment
+///             "type": "Ed25519VerificationKey2018",
+///             "controller": string, - fully qualified DID
+///             "publicKeyBase58": string - verkey of the DID
+///         }],
+///         "authentication": [string], - ids of verification methods
+///         "assertionMethod": [string], - ids of verification methods
+///         "keyAgreement": optional<[string]>, - ids of "X25519KeyAgreementKey2019" verification methods
+///         "service": [{ - one per published endpoint type, empty if no endpoint ATTRIB is written for the DID
+///             "id": string,
+///             "type": string, - endpoint type ("did-communication" if types aren't published)
+///             "priority": 0,
+///             "recipientKeys": [string],
+///             "routingKeys": [string, ...],
+///             "serviceEndpoint": string
+///         }],
+///         "pairwise": optional<{ - pairwise with the DID stored in the wallet (set by `resolve_did_with_wallet` only)
+///             "myDid": string,
+///             "metadata": optional<string>
+///         }>,
+///         "provenance": { - source of each verification method and service (by id) and pairwise (by "pairwise" key):
+///             <id>: "ledger:NYM" | "ledger:ATTRIB" | "wallet:pairwise"
+///         }
+///     }
+pub fn resolve_did(pool_handle: PoolHandle, did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _resolve_did(command_handle, pool_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _resolve_did(command_handle: CommandHandle, pool_handle: PoolHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_resolve_did(command_handle, pool_handle, did.as_ptr(), cb) })
+}
+
+/// Resolves the DID into W3C DID Document as `resolve_did` does and merges pairwise
+/// with the DID stored in the wallet into "pairwise" field of the document.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `wallet_handle` - wallet handle (created by Wallet::open).
+/// * `did` - The DID to resolve.
+///
+/// # Returns
+/// DID Document json (see `resolve_did`)
+pub fn resolve_did_with_wallet(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _resolve_did_with_wallet(command_handle, pool_handle, wallet_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _resolve_did_with_wallet(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_resolve_did_with_wallet(command_handle, pool_handle, wallet_handle, did.as_ptr(), cb) })
+}
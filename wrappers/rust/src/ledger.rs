@@ -6,7 +6,8 @@ use std::ptr::null;
 use futures::Future;
 
 use ffi::ledger;
-use ffi::{ResponseStringCB,
+use ffi::{ResponseBoolCB,
+          ResponseStringCB,
           ResponseStringStringCB,
           ResponseStringStringU64CB};
 
@@ -144,6 +145,32 @@ fn _multi_sign_request(command_handle: CommandHandle, wallet_handle: WalletHandl
     ErrorCode::from(unsafe { ledger::indy_multi_sign_request(command_handle, wallet_handle, submitter_did.as_ptr(), request_json.as_ptr(), cb) })
 }
 
+/// Multi signs request message with several DIDs at once.
+///
+/// Adds signatures of all passed DIDs to `signatures` field of the request json (see multi_sign_request).
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open).
+/// * `submitter_dids_json` - Ids of Identities stored in secured Wallet as json array: [string, ...].
+/// * `request_json` - Request data json.
+///
+/// # Returns
+/// Signed request json.
+pub fn multi_sign_request_with_dids(wallet_handle: WalletHandle, submitter_dids_json: &str, request_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _multi_sign_request_with_dids(command_handle, wallet_handle, submitter_dids_json, request_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _multi_sign_request_with_dids(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_dids_json: &str, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_dids_json = c_str!(submitter_dids_json);
+    let request_json = c_str!(request_json);
+
+    ErrorCode::from(unsafe { ledger::indy_multi_sign_request_with_dids(command_handle, wallet_handle, submitter_dids_json.as_ptr(), request_json.as_ptr(), cb) })
+}
+
 /// Builds a request to get a DDO.
 ///
 /// # Arguments
@@ -274,6 +301,88 @@ fn _parse_get_nym_response(command_handle: CommandHandle, get_nym_response: &str
     ErrorCode::from(unsafe { ledger::indy_parse_get_nym_response(command_handle, get_nym_response.as_ptr(), cb) })
 }
 
+/// Collects the history of NYM transactions written for the DID (creation, key rotations, role changes)
+/// by walking GET_TXN requests over the domain ledger.
+/// The walked range can't be longer than 1000 transactions.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `target_did` - Target DID as base58-encoded string for 16 or 32 bit DID value.
+/// * `options_json` - (Optional) range of domain ledger sequence numbers to walk:
+/// {
+///     "from": int (optional) - first sequence number (1 by default),
+///     "to": int (optional) - last sequence number (sequence number of the current NYM of `target_did` by default),
+/// }
+///
+/// # Returns
+/// NYM transactions of the DID in chronological order:
+/// [{
+///     "seqNo": int - sequence number of the transaction,
+///     "txnTime": int (optional) - time when the transaction was written,
+///     "from": string (optional) - DID of the transaction author,
+///     "data": json - NYM transaction data (dest, verkey, role, alias)
+/// }]
+pub fn get_nym_history(pool_handle: PoolHandle, target_did: &str, options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_nym_history(command_handle, pool_handle, target_did, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_nym_history(command_handle: CommandHandle, pool_handle: PoolHandle, target_did: &str, options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let target_did = c_str!(target_did);
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_get_nym_history(command_handle, pool_handle, target_did.as_ptr(), opt_c_ptr!(options_json, options_json_str), cb)
+    })
+}
+
+/// Fetches a batch of ledger transactions in sequence number order by sending GET_TXN requests.
+///
+/// Pass `cursor` from the returned batch as `from` option of the next call until `complete` is set.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `options_json` - (Optional) batch to fetch:
+/// {
+///     "ledgerType": string (optional) - type of the ledger the transactions are fetched from:
+///         DOMAIN - used default,
+///         POOL,
+///         CONFIG
+///         any number
+///     "from": int (optional) - first sequence number of the batch (1 by default),
+///     "to": int (optional) - last sequence number to fetch (the end of the ledger by default),
+///     "batchSize": int (optional) - max number of transactions in the batch (100 by default, 1000 at most),
+/// }
+///
+/// # Returns
+/// Batch of transactions:
+/// {
+///     "txns": [{
+///         "seqNo": int - sequence number of the transaction,
+///         "data": json - transaction as it is returned by GET_TXN request
+///     }],
+///     "cursor": int - sequence number to pass as `from` to fetch the next batch,
+///     "complete": bool - true if the end of the ledger or `to` sequence number is reached
+/// }
+pub fn fetch_ledger_txns(pool_handle: PoolHandle, options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _fetch_ledger_txns(command_handle, pool_handle, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _fetch_ledger_txns(command_handle: CommandHandle, pool_handle: PoolHandle, options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_fetch_ledger_txns(command_handle, pool_handle, opt_c_ptr!(options_json, options_json_str), cb)
+    })
+}
+
 /// Builds a GET_TXN request. Request to get any transaction by its seq_no.
 ///
 /// # Arguments
@@ -1009,6 +1118,8 @@ fn _parse_get_revoc_reg_delta_response(command_handle: CommandHandle, get_revoc_
 ///     1) based on "seqNo" - sender knows the sequence number of transaction that he consider as a fresh enough.
 ///     2) based on "txnTime" - sender knows the timestamp that he consider as a fresh enough.
 ///
+/// For write responses "seqNo", "txnTime" and "txnId" can be persisted as ledger anchors of the written transaction.
+///
 /// Note: response of GET_VALIDATOR_INFO request isn't supported
 ///
 /// # Arguments
@@ -1022,7 +1133,8 @@ fn _parse_get_revoc_reg_delta_response(command_handle: CommandHandle, get_revoc_
 ///     "seqNo": Option<u64> - transaction sequence number,
 ///     "txnTime": Option<u64> - transaction ordering time,
 ///     "lastSeqNo": Option<u64> - the latest transaction seqNo for particular Node,
-///     "lastTxnTime": Option<u64> - the latest transaction ordering time for particular Node
+///     "lastTxnTime": Option<u64> - the latest transaction ordering time for particular Node,
+///     "txnId": Option<string> - identifier of the written transaction (for example: schema or credential definition id)
 /// }
 pub fn get_response_metadata(response: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
@@ -1413,6 +1525,104 @@ fn _build_get_acceptance_mechanisms_request(command_handle: CommandHandle,
     })
 }
 
+/// Gets the latest Transaction Author Agreement and Acceptance Mechanisms set on the ledger.
+///
+/// If digest of the previously accepted agreement is passed and it differs from the latest one,
+/// `LedgerTxnAuthorAgreementRetired` error is returned once the accepted agreement is retired.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `accepted_taa_digest` - (Optional) digest of the accepted agreement to check for retirement.
+///
+/// # Returns
+/// json with the latest agreement (`taa`) and acceptance mechanisms (`aml`).
+pub fn get_active_txn_author_agreement(pool_handle: PoolHandle, accepted_taa_digest: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_active_txn_author_agreement(command_handle, pool_handle, accepted_taa_digest, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_active_txn_author_agreement(command_handle: CommandHandle, pool_handle: PoolHandle, accepted_taa_digest: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let accepted_taa_digest_str = opt_c_str!(accepted_taa_digest);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_get_active_txn_author_agreement(command_handle, pool_handle, opt_c_ptr!(accepted_taa_digest, accepted_taa_digest_str), cb)
+    })
+}
+
+/// Checks whether Credential Definition published on the ledger can be used by the local anoncreds implementation.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `cred_def_id` - id of Credential Definition to check.
+///
+/// # Returns
+/// json report with `compatible` flag and descriptions of found `issues`.
+pub fn check_cred_def_compatibility(pool_handle: PoolHandle, cred_def_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _check_cred_def_compatibility(command_handle, pool_handle, cred_def_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _check_cred_def_compatibility(command_handle: CommandHandle, pool_handle: PoolHandle, cred_def_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_def_id = c_str!(cred_def_id);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_check_cred_def_compatibility(command_handle, pool_handle, cred_def_id.as_ptr(), cb)
+    })
+}
+
+/// Builds receipt of the written transaction from the reply to the write request.
+///
+/// # Arguments
+/// * `response` - reply to the write request.
+///
+/// # Returns
+/// write receipt json with ledger id, sequence number, root hash, audit path and nodes multi-signature of the written transaction.
+pub fn get_write_receipt(response: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_write_receipt(command_handle, response, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_write_receipt(command_handle: CommandHandle, response: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let response = c_str!(response);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_get_write_receipt(command_handle, response.as_ptr(), cb)
+    })
+}
+
+/// Verifies receipt of the written transaction against the nodes multi-signature.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `receipt_json` - write receipt (created by `get_write_receipt`).
+///
+/// # Returns
+/// true if the transaction of the receipt is written to the ledger.
+pub fn verify_write_receipt(pool_handle: PoolHandle, receipt_json: &str) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verify_write_receipt(command_handle, pool_handle, receipt_json, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verify_write_receipt(command_handle: CommandHandle, pool_handle: PoolHandle, receipt_json: &str, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let receipt_json = c_str!(receipt_json);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_verify_write_receipt(command_handle, pool_handle, receipt_json.as_ptr(), cb)
+    })
+}
+
 /// Append transaction author agreement acceptance data to a request.
 /// This function should be called before signing and sending a request
 /// if there is any transaction author agreement set on the Ledger.
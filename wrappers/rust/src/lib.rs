@@ -73,6 +73,46 @@ pub fn set_runtime_config(config: &str) -> ErrorCode {
     })
 }
 
+/// Set listener of libindy security events.
+///
+/// # Arguments
+/// * `context` - pointer to some listener context that will be passed to the listener.
+/// * `cb` - listener. Previously set listener is removed if `None`.
+///     event_json: {
+///         "type": "did_nym_changed",
+///         "did": string, "wallet_verkey": string,
+///         "previous_verkey": Optional<string>, "previous_role": Optional<string>,
+///         "verkey": string, "role": Optional<string>
+///     }
+pub fn set_event_listener(context: *const ffi::CVoid, cb: Option<ffi::EventCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        ffi::indy_set_event_listener(context, cb)
+    })
+}
+
+/// Cancels the command started with the given cancel handle.
+///
+/// Callback of the cancelled command is called with `CommonCancelled` error.
+///
+/// # Arguments
+/// * `cancel_handle` - handle returned by `get_current_cancel_handle` after the function starting the command.
+pub fn cancel_command(cancel_handle: IndyHandle) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        ffi::indy_cancel_command(cancel_handle)
+    })
+}
+
+/// Get cancel handle of the last cancellable command started in the current thread.
+///
+/// Should be called right after the function starting the command (0 if no cancellable command was started).
+pub fn get_current_cancel_handle() -> IndyHandle {
+    let mut cancel_handle: IndyHandle = 0;
+
+    unsafe { ffi::indy_get_current_cancel_handle(&mut cancel_handle) };
+
+    cancel_handle
+}
+
 #[derive(Fail, Debug, PartialEq, Copy, Clone, FromPrimitive, ToPrimitive)]
 #[repr(i32)]
 #[allow(dead_code)]
@@ -178,6 +218,10 @@ pub enum ErrorCode
     // Caller passed invalid value as param 27 (null, invalid json and etc..)
     #[fail(display = "CommonInvalidParam27")]
     CommonInvalidParam27 = 129,
+
+    // Command was cancelled by the caller or its timeout expired
+    #[fail(display = "CommonCancelled")]
+    CommonCancelled = 130,
     // Wallet errors
     // Caller passed invalid wallet handle
     #[fail(display = "WalletInvalidHandle")]
@@ -262,6 +306,10 @@ pub enum ErrorCode
     #[fail(display = "LedgerNotFound")]
     LedgerNotFound = 309,
 
+    // Accepted Transaction Author Agreement has been retired on the ledger.
+    #[fail(display = "LedgerTxnAuthorAgreementRetired")]
+    LedgerTxnAuthorAgreementRetired = 310,
+
     // Revocation registry is full and creation of new registry is necessary
     #[fail(display = "AnoncredsRevocationRegistryFullError")]
     AnoncredsRevocationRegistryFullError = 400,
@@ -278,6 +326,10 @@ pub enum ErrorCode
     // Attempt to create credential definition with duplicated did schema pair
     #[fail(display = "AnoncredsCredDefAlreadyExistsError")]
     AnoncredsCredDefAlreadyExistsError = 407,
+
+    // Attempt to delete master secret referenced by stored credentials
+    #[fail(display = "AnoncredsMasterSecretInUseError")]
+    AnoncredsMasterSecretInUseError = 408,
     // Signus errors
     // Unknown format of DID entity keys
     #[fail(display = "UnknownCryptoTypeError")]
@@ -2,7 +2,7 @@ use {ErrorCode, IndyError};
 
 use std::ffi::CString;
 
-use ffi::{logger, CVoid, CString as IndyCString};
+use ffi::{logger, CVoid, CString as IndyCString, IndyHandle};
 
 use log::{Log, Record, Metadata, Level};
 
@@ -95,7 +95,9 @@ impl IndyLogger {
                      args: IndyCString,
                      module_path: IndyCString,
                      file: IndyCString,
-                     line: u32) {
+                     line: u32,
+                     _command: IndyCString,
+                     _correlation_id: IndyHandle) {
         unsafe {
             if let Some(ref logger) = LOGGER {
                 let target = c_str_to_string(target).unwrap().unwrap();
@@ -58,6 +58,14 @@ fn _create_pool_ledger_config(command_handle: CommandHandle, pool_name: &str, po
 ///     "number_read_nodes": int (optional) - the number of nodes to send read requests (2 by default)
 ///         By default Libindy sends a read requests to 2 nodes in the pool.
 ///         If response isn't received or `state proof` is invalid Libindy sends the request again but to 2 (`number_read_nodes`) * 2 = 4 nodes and so far until completion.
+///     "retry_backoff_factor": float (optional) - multiplier applied to `timeout` each time a read request is resent to the next node (1.0 by default).
+///     "max_retry_timeout": int (optional) - upper bound of the timeout (in sec) for resent read requests. Must not be less than `timeout`.
+///     "client_key_pair": (optional) - CURVE key pair of the client used for connections to the nodes:
+///         {"public_key": string, "secret_key": string} z85 encoded (as generated by `zmq_curve_keypair`).
+///         By default an ephemeral key pair is generated for every connection.
+///     "pin_genesis_node_keys": bool (optional) - reject nodes whose transport keys differ from the keys
+///         in the genesis transactions, e.g. after key rotation on the ledger (false by default).
+///         Nodes added to the pool after the genesis are accepted.
 /// }
 ///
 /// # Returns
@@ -165,3 +173,108 @@ fn _set_protocol_version(command_handle: CommandHandle, protocol_version: usize,
       pool::indy_set_protocol_version(command_handle, protocol_version, cb)
     })
 }
+
+/// Builds genesis transactions of the pool ledger from the list of nodes.
+/// The result can be written to the file and used as `genesis_txn` of `create_pool_ledger_config`.
+///
+/// Transactions are built in the format of Indy Node 1.4 and greater (PROTOCOL_VERSION=2).
+///
+/// # Arguments
+/// * `nodes_json` - List of the genesis nodes:
+/// [{
+///     "alias": string, Node's alias
+///     "dest": string, Target Node's verkey as base58
+///     "steward": string, DID of the Steward owning the node
+///     "verkey": string, (optional) Node's verkey
+///     "node_ip": string, Node's ip address
+///     "node_port": int, Node's port
+///     "client_ip": string, Node's client listener ip address
+///     "client_port": int, Node's client listener port
+///     "services": array<string>, (optional) The service of the Node. VALIDATOR or OBSERVER (["VALIDATOR"] by default)
+///     "blskey": string, (optional) BLS multi-signature key as base58
+///     "blskey_pop": string, (optional) BLS key proof of possession as base58
+/// }]
+///
+/// # Returns
+/// Genesis transactions separated by new line.
+pub fn build_pool_genesis_txns(nodes_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _build_pool_genesis_txns(command_handle, nodes_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _build_pool_genesis_txns(command_handle: CommandHandle, nodes_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let nodes_json = c_str!(nodes_json);
+
+    ErrorCode::from(unsafe { pool::indy_build_pool_genesis_txns(command_handle, nodes_json.as_ptr(), cb) })
+}
+
+/// Enables or disables capturing of raw traffic between the pool and its nodes.
+///
+/// Requests and replies are written to the capture file as json lines with signatures and
+/// private attribute values redacted. The file is rotated when it exceeds `max_file_size`.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle returned by open_ledger
+/// * `config` - (Optional) capture config json. If None, capturing is stopped.
+/// {
+///     "path": string, path to the capture file.
+///     "max_file_size": int (optional), max size of the capture file in bytes before rotation. Defaults to 10485760.
+///     "max_files": int (optional), max number of capture files including rotated ones. Defaults to 5.
+/// }
+pub fn set_pool_traffic_capture(pool_handle: PoolHandle, config: Option<&str>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _set_pool_traffic_capture(command_handle, pool_handle, config, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _set_pool_traffic_capture(command_handle: CommandHandle, pool_handle: PoolHandle, config: Option<&str>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let config_str = opt_c_str!(config);
+
+    ErrorCode::from(unsafe { pool::indy_set_pool_traffic_capture(command_handle, pool_handle, opt_c_ptr!(config, config_str), cb) })
+}
+
+/// Routes ledger operations on fully-qualified identifiers of the DID namespace to the pool.
+///
+/// Ledger-backed operations called with `INVALID_POOL_HANDLE` send requests for DIDs of the namespace (and schemas
+/// and credential definitions issued by them) to the registered pool. Explicitly passed pool handle always takes
+/// precedence. Registration is removed when the pool is closed.
+///
+/// # Arguments
+/// * `namespace` - DID namespace (`sov` for `did:sov:...` or `indicio:testnet` for `did:indicio:testnet:...`)
+/// * `pool_handle` - pool handle returned by open_ledger
+pub fn set_pool_for_namespace(namespace: &str, pool_handle: PoolHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _set_pool_for_namespace(command_handle, namespace, pool_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _set_pool_for_namespace(command_handle: CommandHandle, namespace: &str, pool_handle: PoolHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let namespace = c_str!(namespace);
+
+    ErrorCode::from(unsafe { pool::indy_set_pool_for_namespace(command_handle, namespace.as_ptr(), pool_handle, cb) })
+}
+
+/// Removes routing of the DID namespace set by `set_pool_for_namespace`.
+///
+/// # Arguments
+/// * `namespace` - DID namespace
+pub fn remove_pool_for_namespace(namespace: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _remove_pool_for_namespace(command_handle, namespace, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _remove_pool_for_namespace(command_handle: CommandHandle, namespace: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let namespace = c_str!(namespace);
+
+    ErrorCode::from(unsafe { pool::indy_remove_pool_for_namespace(command_handle, namespace.as_ptr(), cb) })
+}
@@ -61,6 +61,7 @@ macro_rules! cb_ec {
     )
 }
 
+/// Callbacks only send the result to the receiver, so they never block libindy dispatcher threads.
 pub struct ClosureHandler {}
 
 impl ClosureHandler {
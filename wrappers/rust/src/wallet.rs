@@ -148,6 +148,31 @@ fn _register_storage(command_handle: CommandHandle,
     })
 }
 
+/// Registers compaction handler for custom wallet storage registered with `register_wallet_storage`.
+///
+/// # Arguments
+/// * `xtype` - Wallet type name.
+/// * `compact` - WalletType compact operation handler
+pub fn register_wallet_storage_compaction(xtype: &str,
+                                          compact: Option<wallet::WalletCompact>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_storage_compaction(command_handle, xtype, compact, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_storage_compaction(command_handle: CommandHandle,
+                                xtype: &str,
+                                compact: Option<wallet::WalletCompact>,
+                                cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_register_wallet_storage_compaction(command_handle, xtype.as_ptr(), compact, cb)
+    })
+}
+
 /// Creates a new secure wallet with the given unique name.
 ///
 /// # Arguments
@@ -431,7 +456,8 @@ fn _update_wallet_record_tags(command_handle: CommandHandle, wallet_handle: Wall
 ///   usage of this tag in complex search queries (comparison, predicates)
 ///   Encrypted tags can be searched only for exact matching
 ///   Note if some from provided tags already assigned to the record than
-///     corresponding tags values will be replaced
+///     corresponding tags values will be replaced.
+///   Tag changing from encrypted to un-encrypted (or vice versa) replaces the tag stored the other way.
 pub fn add_wallet_record_tags(wallet_handle: WalletHandle, xtype: &str, id: &str, tags_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
 
@@ -499,6 +525,64 @@ fn _delete_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHan
     })
 }
 
+/// Create many non-secret records of the same type in the wallet at once.
+/// Records are added with all-or-nothing semantics: if one of them can't be added none of them are.
+/// Plugged storages have no transactions, so records added before the failure are deleted back at best effort.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `xtype` - allows to separate different record types collections
+/// * `records_json` - the list of records to add as json array:
+///   [
+///     {
+///       "id": string, the id of record
+///       "value": string, the value of record
+///       "tags": optional<object>, the record tags (see add_wallet_record)
+///     }
+///   ]
+pub fn add_wallet_records_batch(wallet_handle: WalletHandle, xtype: &str, records_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _add_wallet_records_batch(command_handle, wallet_handle, xtype, records_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _add_wallet_records_batch(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, records_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+    let records_json = c_str!(records_json);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_add_wallet_records_batch(command_handle, wallet_handle, xtype.as_ptr(), records_json.as_ptr(), cb)
+    })
+}
+
+/// Delete many existing wallet records of the same type at once.
+/// Records are deleted with all-or-nothing semantics: if one of them can't be deleted none of them are.
+/// Plugged storages have no transactions, so records deleted before the failure are added back at best effort.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `xtype` - record type
+/// * `ids_json` - the list of ids of records to delete as json array:
+///   ["id1", "id2", ...]
+pub fn delete_wallet_records_batch(wallet_handle: WalletHandle, xtype: &str, ids_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _delete_wallet_records_batch(command_handle, wallet_handle, xtype, ids_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _delete_wallet_records_batch(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, ids_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+    let ids_json = c_str!(ids_json);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_delete_wallet_records_batch(command_handle, wallet_handle, xtype.as_ptr(), ids_json.as_ptr(), cb)
+    })
+}
+
 /// Get an wallet record by id
 ///
 /// # Arguments
@@ -551,9 +635,11 @@ fn _get_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle
 ///    "tagName": "tagValue",
 ///    $or: {
 ///      "tagName2": { $regex: 'pattern' },
-///      "tagName3": { $gte: '123' },
+///      "~tagName3": { $gte: '123' },
 ///    },
 ///  }
+///   Comparison operators ($gt, $gte, $lt, $lte) and $like are allowed only for
+///   un-encrypted tags (tag name starts with "~").
 /// * `options_json` - //TODO: FIXME: Think about replacing by bitmaks
 ///  {
 ///    retrieveRecords: (optional, true by default) If false only "counts" will be calculated,
@@ -668,3 +754,129 @@ fn _generate_wallet_key(command_handle: CommandHandle, config: Option<&str>, cb:
 
     ErrorCode::from(unsafe { wallet::indy_generate_wallet_key(command_handle, config.as_ptr(), cb) })
 }
+
+/// Verifies that records of opened wallet are encrypted at rest.
+/// None of decrypted ids, types, values and encrypted tags may appear in the raw storage rows.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `config` - (optional) check configuration json.
+/// {
+///   "sample_size": int, (optional) Maximum number of records to check (all records are checked by default).
+/// }
+///
+/// # Returns
+/// report json
+/// {
+///   "checkedRecords": int, number of checked records
+///   "plaintextTags": int, number of plaintext tags skipped by the check
+///   "encrypted": bool, true if no violation was found
+///   "violations": [{"record": int, "field": string, "reason": string}]
+/// }
+pub fn verify_wallet_storage_encryption(wallet_handle: WalletHandle, config: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verify_wallet_storage_encryption(command_handle, wallet_handle, config, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verify_wallet_storage_encryption(command_handle: CommandHandle, wallet_handle: WalletHandle, config: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let config = opt_c_str_json!(config);
+
+    ErrorCode::from(unsafe { wallet::indy_verify_wallet_storage_encryption(command_handle, wallet_handle, config.as_ptr(), cb) })
+}
+
+/// Returns the latest storage operations of opened wallet that took longer than
+/// `slow_query_threshold` set in the wallet config on opening.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+///
+/// # Returns
+/// slow query log json
+/// [{
+///   "operation": string, storage operation
+///   "type": optional<string>, type of the records
+///   "durationMs": int, duration of the operation in milliseconds
+///   "rows": optional<int>, number of affected or found records if known
+///   "timestamp": int, unix time in milliseconds when the operation was finished
+/// }]
+pub fn get_wallet_slow_query_log(wallet_handle: WalletHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_wallet_slow_query_log(command_handle, wallet_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_wallet_slow_query_log(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_get_wallet_slow_query_log(command_handle, wallet_handle, cb) })
+}
+
+/// Compacts the storage of opened wallet reclaiming the space left by deleted records.
+/// Plugged storages are compacted by the handler registered with `register_wallet_storage_compaction`,
+/// storages without the handler report zero reclaimed bytes.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+///
+/// # Returns
+/// compaction report json
+/// {
+///   "reclaimedBytes": int, number of bytes reclaimed by the compaction
+/// }
+pub fn compact_wallet(wallet_handle: WalletHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _compact_wallet(command_handle, wallet_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _compact_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_compact_wallet(command_handle, wallet_handle, cb) })
+}
+
+/// Lists wallet objects which expire within the given time window.
+/// Expiration of keys and DIDs is read from their metadata json "expires" field,
+/// expiration of credentials is read from their "expires" attribute.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `within_secs` - size of the time window in seconds starting from now.
+///
+/// # Returns
+/// list of expiring objects ordered by expiration time
+/// [{"type": "key" | "did" | "credential", "id": string, "expires": int}]
+pub fn list_expiring_wallet_objects(wallet_handle: WalletHandle, within_secs: u64) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _list_expiring_wallet_objects(command_handle, wallet_handle, within_secs, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _list_expiring_wallet_objects(command_handle: CommandHandle, wallet_handle: WalletHandle, within_secs: u64, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_list_expiring_wallet_objects(command_handle, wallet_handle, within_secs, cb) })
+}
+
+/// Calculates limits of wallet key derivation suitable for the current hardware.
+///
+/// # Arguments
+/// * `target_ms` - desired duration of wallet key derivation in milliseconds.
+///
+/// # Returns
+/// key derivation params to use as "key_derivation_params" of wallet credentials
+/// {"ops_limit": int, "mem_limit": int}
+pub fn benchmark_wallet_key_derivation(target_ms: u64) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _benchmark_wallet_key_derivation(command_handle, target_ms, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _benchmark_wallet_key_derivation(command_handle: CommandHandle, target_ms: u64, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_benchmark_wallet_key_derivation(command_handle, target_ms, cb) })
+}